@@ -0,0 +1,586 @@
+use crate::cli_error::L1XConfigError;
+use crate::forge_client::{ForgeClient, ForgeClientConfig};
+use l1x_common::{
+    toolkit_config::{DevAccount, ToolkitConfig},
+    TxnBuildError,
+};
+
+use anyhow::Result;
+use ethers::signers::{coins_bip39::English, MnemonicBuilder};
+use secp256k1::SecretKey;
+use serde::Serialize;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+/// BIP-44 path of the first Ethereum account, as used by MetaMask
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Passphrase of `account export --format keystore`, prompted for when unset
+pub const KEYSTORE_PASSPHRASE_ENV: &str = "L1X_FORGE_KEYSTORE_PASSPHRASE";
+
+#[derive(Debug, thiserror::Error)]
+pub enum L1XAccountImportError {
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+    #[error("Invalid derivation path '{path}': {reason}")]
+    InvalidDerivationPath { path: String, reason: String },
+    #[error("{0}")]
+    ConfigError(#[from] L1XConfigError),
+}
+
+/// Manage the dev accounts of the wallet YAML file
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "account")]
+pub struct L1XAccountCmd {
+    #[clap(subcommand)]
+    command: L1XAccountSubCmd,
+}
+
+#[derive(Clone, Debug, clap::Subcommand)]
+enum L1XAccountSubCmd {
+    /// Generate a keypair and add it to `l1x_dev_wallets.yaml`
+    #[command(name = "create")]
+    Create {
+        /// Wallet name, used with --owner
+        #[clap(long = "name")]
+        name: String,
+
+        /// Replace an existing wallet of the same name
+        #[clap(long = "force", default_value_t = false)]
+        force: bool,
+    },
+    /// List the wallets of `l1x_dev_wallets.yaml`
+    #[command(name = "list")]
+    List {
+        /// Query the balance and nonce of every account on the active chain
+        #[clap(long = "with-balance", default_value_t = false)]
+        with_balance: bool,
+
+        /// Print the accounts as a JSON array
+        #[clap(long = "json", default_value_t = false)]
+        json: bool,
+
+        /// Balance queries awaiting a node response at the same time
+        #[clap(long = "max-in-flight", default_value_t = 8)]
+        max_in_flight: usize,
+
+        /// Network of l1x_chain_config.yaml to query, instead of
+        /// L1X_CFG_CHAIN_TYPE or its default_network
+        #[clap(long = "network", requires = "with_balance")]
+        network: Option<String>,
+    },
+    /// Write the key of a wallet to a file other wallets can import
+    #[command(name = "export")]
+    Export {
+        /// Name of the wallet to export
+        #[clap(long = "name")]
+        name: String,
+
+        /// File the key is written to
+        #[clap(long = "out")]
+        out: std::path::PathBuf,
+
+        #[clap(long = "format", value_enum, default_value_t = L1XKeyExportFormat::Keystore)]
+        format: L1XKeyExportFormat,
+
+        /// Replace an existing file
+        #[clap(long = "force", default_value_t = false)]
+        force: bool,
+    },
+    /// Add an existing key to `l1x_dev_wallets.yaml`
+    #[command(name = "import")]
+    Import {
+        /// Wallet name, used with --owner
+        #[clap(long = "name")]
+        name: String,
+
+        /// Hex encoded secp256k1 private key, `0x` prefixed or not
+        #[clap(
+            long = "private-key",
+            required_unless_present = "mnemonic",
+            conflicts_with = "mnemonic"
+        )]
+        private_key: Option<String>,
+
+        /// BIP-39 mnemonic of 12 to 24 words
+        #[clap(long = "mnemonic")]
+        mnemonic: Option<String>,
+
+        /// BIP-32 path of the key derived from the mnemonic
+        #[clap(long = "derivation-path", default_value = DEFAULT_DERIVATION_PATH)]
+        derivation_path: String,
+
+        /// Replace an existing wallet of the same name
+        #[clap(long = "force", default_value_t = false)]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum L1XKeyExportFormat {
+    /// Passphrase encrypted V3 keystore JSON, as read by MetaMask and foundry
+    Keystore,
+    /// `0x` prefixed hex private key
+    Hex,
+}
+
+/// A generated dev account
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1XCreatedAccount {
+    pub address: String,
+    pub peer_id: String,
+}
+
+/// A wallet of `l1x_dev_wallets.yaml`. `balance` and `nonce` are only set
+/// once queried from the chain.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct L1XAccountInfo {
+    pub name: String,
+    /// `None` when the keys of the wallet are invalid
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+impl std::fmt::Display for L1XAccountInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.address {
+            Some(address) => write!(f, "{} :: 0x{}", self.name, address)?,
+            None => write!(f, "{} :: invalid key", self.name)?,
+        }
+
+        match (&self.balance, &self.nonce) {
+            (Some(balance), Some(nonce)) => {
+                write!(f, " :: balance: {} :: nonce: {}", balance, nonce)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Account address of `account`, from its public key when the wallet has one
+fn wallet_address(account: &DevAccount) -> Result<String> {
+    let pub_key = match account.pub_key() {
+        Some(pub_key) => pub_key.to_string(),
+        None => {
+            l1x_common::secp256k1_creds(Some(account.priv_key().to_string()))
+                .map_err(|err_code| anyhow::anyhow!("{}", err_code))?
+                .1
+        }
+    };
+
+    let address = l1x_common::account_address(&hex::decode(pub_key)?)?;
+    Ok(hex::encode(address))
+}
+
+/// Wallets of `l1x_dev_wallets.yaml` sorted by name, with their private key
+fn load_accounts() -> Result<Vec<(L1XAccountInfo, String)>, L1XConfigError> {
+    let wallet_config = ToolkitConfig::from_env()
+        .and_then(|toolkit| toolkit.wallet_config())
+        .map_err(|err_code| {
+            L1XConfigError(format!(
+                "Failed to load wallet yaml file: {}",
+                err_code
+            ))
+        })?;
+
+    let mut accounts = wallet_config
+        .dev_accounts()
+        .iter()
+        .map(|(name, account)| {
+            let address = wallet_address(account)
+                .map_err(|err_code| {
+                    tracing::warn!(
+                        "Wallet {} | Invalid key :: {}",
+                        name,
+                        err_code
+                    )
+                })
+                .ok();
+
+            (
+                L1XAccountInfo {
+                    name: name.clone(),
+                    address,
+                    balance: None,
+                    nonce: None,
+                },
+                account.priv_key().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    accounts.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+
+    Ok(accounts)
+}
+
+/// Query the balance and nonce of `accounts` from `end_point`, at most
+/// `max_in_flight` at a time. Accounts the node couldn't report on keep no
+/// balance.
+pub(crate) async fn fetch_balances(
+    end_point: &str,
+    accounts: Vec<(L1XAccountInfo, String)>,
+    max_in_flight: usize,
+) -> Vec<L1XAccountInfo> {
+    let in_flight = Arc::new(Semaphore::new(max_in_flight.max(1)));
+    let mut queries = JoinSet::new();
+
+    for (index, (mut account, priv_key)) in accounts.into_iter().enumerate() {
+        let in_flight = in_flight.clone();
+        let end_point = end_point.to_string();
+
+        queries.spawn(async move {
+            let _permit = in_flight
+                .acquire_owned()
+                .await
+                .expect("the in-flight semaphore is never closed");
+
+            let address = match &account.address {
+                Some(address) => address.clone(),
+                None => return (index, account),
+            };
+            let account_state = match ForgeClient::new(ForgeClientConfig::new(
+                &end_point, &priv_key,
+            )) {
+                Ok(client) => client.account_state(&address).await,
+                Err(err_code) => Err(err_code),
+            };
+
+            match account_state {
+                Ok(Some(state)) => {
+                    account.balance = Some(state.balance.to_string());
+                    account.nonce = Some(state.nonce.to_string());
+                }
+                Ok(None) => {
+                    account.balance = Some("0".to_string());
+                    account.nonce = Some("0".to_string());
+                }
+                Err(err_code) => tracing::warn!(
+                    "Wallet {} | Balance unavailable :: {}",
+                    account.name,
+                    err_code
+                ),
+            }
+
+            (index, account)
+        });
+    }
+
+    let mut accounts = Vec::new();
+    while let Some(query) = queries.join_next().await {
+        match query {
+            Ok(account) => accounts.push(account),
+            Err(err_code) => {
+                tracing::warn!("Balance query task failed :: {}", err_code)
+            }
+        }
+    }
+    accounts.sort_by_key(|(index, _)| *index);
+
+    accounts.into_iter().map(|(_, account)| account).collect()
+}
+
+/// Generate a keypair and register it as the wallet `name`, replacing an
+/// existing wallet only when `force` is set
+pub fn create_account(name: &str, force: bool) -> Result<L1XCreatedAccount> {
+    let (priv_key, _, _) =
+        l1x_common::secp256k1_creds(None).map_err(|err_code| {
+            anyhow::anyhow!("Failed to generate a keypair :: {}", err_code)
+        })?;
+
+    Ok(register_account(name, &priv_key, force)?)
+}
+
+/// Register the secp256k1 `secret_key` as the wallet `name`, replacing an
+/// existing wallet only when `force` is set
+pub fn import_account(
+    name: &str,
+    secret_key: &SecretKey,
+    force: bool,
+) -> Result<L1XCreatedAccount, L1XAccountImportError> {
+    register_account(name, &hex::encode(secret_key.secret_bytes()), force)
+}
+
+fn register_account(
+    name: &str,
+    priv_key: &str,
+    force: bool,
+) -> Result<L1XCreatedAccount, L1XAccountImportError> {
+    let (priv_key, pub_key, peer_id) = l1x_common::secp256k1_creds(Some(
+        priv_key.to_string(),
+    ))
+    .map_err(|err_code| {
+        L1XAccountImportError::InvalidPrivateKey(err_code.to_string())
+    })?;
+    let address = hex::decode(&pub_key)
+        .map_err(anyhow::Error::from)
+        .and_then(|pub_key| l1x_common::account_address(&pub_key))
+        .map_err(|err_code| {
+            L1XAccountImportError::InvalidPrivateKey(err_code.to_string())
+        })?;
+
+    ToolkitConfig::from_env()
+        .and_then(|toolkit| {
+            toolkit.add_wallet_account(
+                name,
+                &DevAccount::new(&priv_key, Some(&pub_key)),
+                force,
+            )
+        })
+        .map_err(L1XConfigError::from)?;
+
+    Ok(L1XCreatedAccount {
+        address: hex::encode(address),
+        peer_id: peer_id.to_string(),
+    })
+}
+
+/// Write the key of the wallet `name` to `out` in `format`, the keystore
+/// encrypted with `passphrase`. Returns the `0x` prefixed account address.
+pub fn export_account(
+    name: &str,
+    out: &Path,
+    format: L1XKeyExportFormat,
+    passphrase: &str,
+    force: bool,
+) -> Result<String> {
+    if out.exists() && !force {
+        anyhow::bail!(
+            "'{}' already exists, use --force to overwrite it",
+            out.display()
+        );
+    }
+
+    let priv_key = ToolkitConfig::from_env()
+        .and_then(|toolkit| toolkit.wallet_priv_key(name))
+        .map_err(L1XConfigError::from)?;
+    let secret_key = parse_private_key(&priv_key)?;
+    let address = crate::address_of::L1XAddressInfo::from_pub_key(
+        &secret_key.public_key(&secp256k1::Secp256k1::new()),
+    )?
+    .address;
+
+    match format {
+        L1XKeyExportFormat::Keystore => {
+            let file_name = out
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Invalid keystore path '{}'", out.display())
+                })?;
+            let out_dir = match out.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+
+            ethers::signers::LocalWallet::encrypt_keystore(
+                out_dir,
+                &mut ethers::core::rand::thread_rng(),
+                secret_key.secret_bytes(),
+                passphrase,
+                Some(file_name),
+            )?;
+        }
+        L1XKeyExportFormat::Hex => std::fs::write(
+            out,
+            format!("0x{}\n", hex::encode(secret_key.secret_bytes())),
+        )?,
+    }
+
+    Ok(address)
+}
+
+/// Passphrase of [`KEYSTORE_PASSPHRASE_ENV`], or read twice from the
+/// terminal
+fn keystore_passphrase() -> Result<String> {
+    if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+
+    let passphrase = prompt_hidden("Keystore passphrase: ")?;
+    if prompt_hidden("Repeat the passphrase: ")? != passphrase {
+        anyhow::bail!("The passphrases do not match");
+    }
+
+    Ok(passphrase)
+}
+
+/// Read a line from stdin, without echoing it when stdin is a terminal
+fn prompt_hidden(prompt: &str) -> Result<String> {
+    eprint!("{}", prompt);
+    std::io::stderr().flush()?;
+
+    let is_terminal = std::io::stdin().is_terminal();
+    let set_echo = |flag: &str| {
+        std::process::Command::new("stty")
+            .arg(flag)
+            .stdin(std::process::Stdio::inherit())
+            .status()
+    };
+    if is_terminal {
+        set_echo("-echo")?;
+    }
+
+    let mut line = String::new();
+    let read_result = std::io::stdin().lock().read_line(&mut line);
+
+    if is_terminal {
+        set_echo("echo")?;
+        eprintln!();
+    }
+    read_result?;
+
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Parse a hex encoded private key, `0x` prefixed or not
+pub fn parse_private_key(
+    private_key: &str,
+) -> Result<SecretKey, L1XAccountImportError> {
+    l1x_common::parse_secret_key(private_key).map_err(|err_code| {
+        L1XAccountImportError::InvalidPrivateKey(match err_code {
+            TxnBuildError::InvalidPrivateKey(reason) => reason,
+            err_code => err_code.to_string(),
+        })
+    })
+}
+
+/// Derive the private key at `derivation_path` of the BIP-39 `mnemonic`
+pub fn mnemonic_private_key(
+    mnemonic: &str,
+    derivation_path: &str,
+) -> Result<SecretKey, L1XAccountImportError> {
+    let wallet = MnemonicBuilder::<English>::default()
+        .phrase(mnemonic.trim())
+        .derivation_path(derivation_path)
+        .map_err(|err_code| L1XAccountImportError::InvalidDerivationPath {
+            path: derivation_path.to_string(),
+            reason: err_code.to_string(),
+        })?
+        .build()
+        .map_err(|err_code| {
+            L1XAccountImportError::InvalidMnemonic(err_code.to_string())
+        })?;
+
+    secret_key_from_slice(&wallet.signer().to_bytes())
+}
+
+/// `SecretKey` of `key_bytes`, which must survive the round trip unchanged
+fn secret_key_from_slice(
+    key_bytes: &[u8],
+) -> Result<SecretKey, L1XAccountImportError> {
+    let secret_key = SecretKey::from_slice(key_bytes).map_err(|err_code| {
+        L1XAccountImportError::InvalidPrivateKey(err_code.to_string())
+    })?;
+
+    if secret_key.secret_bytes().as_slice() != key_bytes {
+        return Err(L1XAccountImportError::InvalidPrivateKey(
+            "key does not round trip".to_string(),
+        ));
+    }
+
+    Ok(secret_key)
+}
+
+impl L1XAccountCmd {
+    pub async fn exec(&self) -> Result<()> {
+        match &self.command {
+            L1XAccountSubCmd::Create { name, force } => {
+                let account = create_account(name, *force)?;
+
+                println!("Created wallet {}", name);
+                println!("address: 0x{}", account.address);
+                println!("peer id: {}", account.peer_id);
+
+                Ok(())
+            }
+            L1XAccountSubCmd::List {
+                with_balance,
+                json,
+                max_in_flight,
+                network,
+            } => {
+                let accounts = load_accounts()?;
+
+                let accounts = if *with_balance {
+                    let end_point = ToolkitConfig::from_env()
+                        .and_then(|toolkit| {
+                            toolkit
+                                .with_network(network.as_deref())
+                                .active_chain_json_rpc_endpoint()
+                        })
+                        .map_err(L1XConfigError::from)?;
+                    fetch_balances(&end_point, accounts, *max_in_flight).await
+                } else {
+                    accounts.into_iter().map(|(account, _)| account).collect()
+                };
+
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&accounts)?);
+                } else {
+                    for account in &accounts {
+                        match (with_balance, &account.balance) {
+                            (true, None) => {
+                                println!("{} :: balance: unavailable", account)
+                            }
+                            _ => println!("{}", account),
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            L1XAccountSubCmd::Export { name, out, format, force } => {
+                // Fail on an existing file before prompting
+                if out.exists() && !force {
+                    anyhow::bail!(
+                        "'{}' already exists, use --force to overwrite it",
+                        out.display()
+                    );
+                }
+                let passphrase = match format {
+                    L1XKeyExportFormat::Keystore => keystore_passphrase()?,
+                    L1XKeyExportFormat::Hex => String::new(),
+                };
+
+                let address =
+                    export_account(name, out, *format, &passphrase, *force)?;
+
+                println!("Exported wallet {} to {}", name, out.display());
+                println!("address: {}", address);
+
+                Ok(())
+            }
+            L1XAccountSubCmd::Import {
+                name,
+                private_key,
+                mnemonic,
+                derivation_path,
+                force,
+            } => {
+                let secret_key = match (private_key, mnemonic) {
+                    (Some(private_key), _) => parse_private_key(private_key)?,
+                    (None, Some(mnemonic)) => {
+                        mnemonic_private_key(mnemonic, derivation_path)?
+                    }
+                    (None, None) => {
+                        anyhow::bail!("--private-key or --mnemonic is required")
+                    }
+                };
+                let account = import_account(name, &secret_key, *force)?;
+
+                println!("Imported wallet {}", name);
+                println!("address: 0x{}", account.address);
+                println!("peer id: {}", account.peer_id);
+
+                Ok(())
+            }
+        }
+    }
+}
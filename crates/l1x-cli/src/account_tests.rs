@@ -0,0 +1,341 @@
+use crate::account::*;
+use crate::contract_install_test::{with_temp_registry, write_toolkit_config};
+use l1x_common::toolkit_config::{DevAccount, ToolkitConfig};
+
+use std::fs;
+
+fn wallet_names(ws_home: &std::path::Path) -> Vec<String> {
+    let wallets: serde_yaml::Value = serde_yaml::from_str(
+        &fs::read_to_string(ws_home.join("l1x-conf/l1x_dev_wallets.yaml"))
+            .unwrap(),
+    )
+    .unwrap();
+
+    wallets["dev_accounts"]
+        .as_mapping()
+        .unwrap()
+        .keys()
+        .map(|name| name.as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_add_wallet_account_keeps_existing_entries_in_order() {
+    with_temp_registry(None, |ws_home| {
+        // Arrange
+        write_toolkit_config(ws_home);
+        let key = "0".repeat(63) + "2";
+
+        // Act
+        let result = ToolkitConfig::from_env().unwrap().add_wallet_account(
+            "alice",
+            &DevAccount::new(&key, None),
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(wallet_names(ws_home), ["ava", "bobby", "super", "alice"]);
+        assert_eq!(
+            ToolkitConfig::from_env()
+                .unwrap()
+                .wallet_priv_key("alice")
+                .unwrap(),
+            key
+        );
+        assert_eq!(
+            ToolkitConfig::from_env().unwrap().wallet_priv_key("ava").unwrap(),
+            "0".repeat(63) + "1"
+        );
+    });
+}
+
+#[test]
+fn test_add_wallet_account_creates_wallet_file() {
+    with_temp_registry(None, |ws_home| {
+        let key = "0".repeat(63) + "2";
+
+        let result = ToolkitConfig::from_env().unwrap().add_wallet_account(
+            "alice",
+            &DevAccount::new(&key, Some("02ab")),
+            false,
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(wallet_names(ws_home), ["alice"]);
+        let account =
+            ToolkitConfig::from_env().unwrap().wallet_account("alice").unwrap();
+        assert_eq!(account.pub_key(), Some("02ab"));
+    });
+}
+
+#[test]
+fn test_add_wallet_account_refuses_existing_name_without_force() {
+    with_temp_registry(None, |ws_home| {
+        // Arrange
+        write_toolkit_config(ws_home);
+        let key = "0".repeat(63) + "2";
+        let account = DevAccount::new(&key, None);
+
+        // Act
+        let refused = ToolkitConfig::from_env()
+            .unwrap()
+            .add_wallet_account("bobby", &account, false);
+        let replaced = ToolkitConfig::from_env()
+            .unwrap()
+            .add_wallet_account("bobby", &account, true);
+
+        // Assert
+        assert!(refused.unwrap_err().to_string().contains("--force"));
+        assert!(replaced.is_ok());
+        assert_eq!(wallet_names(ws_home), ["ava", "bobby", "super"]);
+        assert_eq!(
+            ToolkitConfig::from_env()
+                .unwrap()
+                .wallet_priv_key("bobby")
+                .unwrap(),
+            key
+        );
+    });
+}
+
+#[test]
+fn test_create_account_registers_its_address() {
+    with_temp_registry(None, |ws_home| {
+        // Act
+        let account = create_account("alice", false).unwrap();
+
+        // Assert
+        let wallet =
+            ToolkitConfig::from_env().unwrap().wallet_account("alice").unwrap();
+        let pub_key = hex::decode(wallet.pub_key().unwrap()).unwrap();
+        assert_eq!(
+            account.address,
+            hex::encode(l1x_common::account_address(&pub_key).unwrap())
+        );
+        assert_eq!(account.address.len(), 40);
+        assert!(create_account("alice", false).is_err());
+        assert_eq!(wallet_names(ws_home), ["alice"]);
+    });
+}
+
+/// Hardhat / Anvil default mnemonic
+const TEST_MNEMONIC: &str =
+    "test test test test test test test test test test test junk";
+
+#[test]
+fn test_mnemonic_private_key_test_vectors() {
+    for (path, private_key) in [
+        (
+            DEFAULT_DERIVATION_PATH,
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        ),
+        (
+            "m/44'/60'/0'/0/1",
+            "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        ),
+    ] {
+        let secret_key = mnemonic_private_key(TEST_MNEMONIC, path).unwrap();
+
+        assert_eq!(hex::encode(secret_key.secret_bytes()), private_key);
+    }
+}
+
+#[test]
+fn test_import_mnemonic_account_address() {
+    with_temp_registry(None, |_| {
+        // Arrange
+        let secret_key =
+            mnemonic_private_key(TEST_MNEMONIC, DEFAULT_DERIVATION_PATH)
+                .unwrap();
+
+        // Act
+        let account = import_account("bob", &secret_key, false).unwrap();
+
+        // Assert
+        assert_eq!(account.address, "f39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+        assert_eq!(
+            ToolkitConfig::from_env().unwrap().wallet_priv_key("bob").unwrap(),
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        );
+    });
+}
+
+#[test]
+fn test_mnemonic_private_key_invalid_input() {
+    let invalid_mnemonic =
+        mnemonic_private_key("test test test", DEFAULT_DERIVATION_PATH);
+    let invalid_path = mnemonic_private_key(TEST_MNEMONIC, "m/not/a/path");
+
+    assert!(matches!(
+        invalid_mnemonic,
+        Err(L1XAccountImportError::InvalidMnemonic(_))
+    ));
+    assert!(matches!(
+        invalid_path,
+        Err(L1XAccountImportError::InvalidDerivationPath { .. })
+    ));
+}
+
+#[test]
+fn test_parse_private_key() {
+    let key =
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    for private_key in [key.to_string(), format!("0x{}", key)] {
+        let secret_key = parse_private_key(&private_key).unwrap();
+
+        assert_eq!(hex::encode(secret_key.secret_bytes()), key);
+    }
+    for malformed in ["0xzz", "abcd", &"0".repeat(64)] {
+        assert!(matches!(
+            parse_private_key(malformed),
+            Err(L1XAccountImportError::InvalidPrivateKey(_))
+        ));
+    }
+}
+
+fn account_info(name: &str, address: Option<&str>) -> L1XAccountInfo {
+    L1XAccountInfo {
+        name: name.to_string(),
+        address: address.map(str::to_string),
+        balance: None,
+        nonce: None,
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_balances_queries_every_account() {
+    // Arrange
+    let server = wiremock::MockServer::start().await;
+    wiremock::Mock::given(wiremock::matchers::method("POST"))
+        .and(wiremock::matchers::body_partial_json(serde_json::json!({
+            "method": "l1x_getAccountState",
+            "params": { "request": { "address": "02" } },
+        })))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "account_state": { "balance": "500", "nonce": "3" } },
+            }),
+        ))
+        .mount(&server)
+        .await;
+    wiremock::Mock::given(wiremock::matchers::method("POST"))
+        .and(wiremock::matchers::body_partial_json(serde_json::json!({
+            "params": { "request": { "address": "01" } },
+        })))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+            serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": {} }),
+        ))
+        .mount(&server)
+        .await;
+    let key = "0".repeat(63) + "1";
+    let accounts = vec![
+        (account_info("ava", Some("01")), key.clone()),
+        (account_info("bobby", Some("02")), key.clone()),
+        (account_info("super", None), key),
+    ];
+
+    // Act
+    let accounts = fetch_balances(&server.uri(), accounts, 2).await;
+
+    // Assert
+    let balances = accounts
+        .iter()
+        .map(|account| (account.name.as_str(), account.balance.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        balances,
+        [("ava", Some("0")), ("bobby", Some("500")), ("super", None)]
+    );
+    assert_eq!(accounts[1].nonce.as_deref(), Some("3"));
+}
+
+#[tokio::test]
+async fn test_fetch_balances_unreachable_node() {
+    let key = "0".repeat(63) + "1";
+    let accounts = vec![(account_info("ava", Some("01")), key)];
+
+    let accounts = fetch_balances("http://127.0.0.1:1", accounts, 4).await;
+
+    assert_eq!(accounts, [account_info("ava", Some("01"))]);
+    assert_eq!(
+        format!("{} :: balance: unavailable", accounts[0]),
+        "ava :: 0x01 :: balance: unavailable"
+    );
+}
+
+#[test]
+fn test_account_info_json_skips_unqueried_balance() {
+    let account = account_info("ava", Some("01"));
+
+    assert_eq!(
+        serde_json::to_value(account).unwrap(),
+        serde_json::json!({ "name": "ava", "address": "01" })
+    );
+}
+
+#[test]
+fn test_export_keystore_reimports_same_key() {
+    with_temp_registry(None, |ws_home| {
+        // Arrange
+        write_toolkit_config(ws_home);
+        let out = ws_home.join("ava.json");
+
+        // Act
+        let address = export_account(
+            "ava",
+            &out,
+            L1XKeyExportFormat::Keystore,
+            "hunter2",
+            false,
+        )
+        .unwrap();
+        let wallet =
+            ethers::signers::LocalWallet::decrypt_keystore(&out, "hunter2")
+                .unwrap();
+        let secret_key =
+            parse_private_key(&hex::encode(wallet.signer().to_bytes()))
+                .unwrap();
+        let account = import_account("ava-copy", &secret_key, false).unwrap();
+
+        // Assert
+        assert_eq!(address, "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+        assert_eq!(format!("0x{}", account.address), address);
+        assert_eq!(
+            ToolkitConfig::from_env()
+                .unwrap()
+                .wallet_priv_key("ava-copy")
+                .unwrap(),
+            ToolkitConfig::from_env().unwrap().wallet_priv_key("ava").unwrap()
+        );
+    });
+}
+
+#[test]
+fn test_export_refuses_existing_file_without_force() {
+    with_temp_registry(None, |ws_home| {
+        // Arrange
+        write_toolkit_config(ws_home);
+        let out = ws_home.join("ava.key");
+        fs::write(&out, "keep").unwrap();
+
+        // Act
+        let refused =
+            export_account("ava", &out, L1XKeyExportFormat::Hex, "", false);
+        let kept = fs::read_to_string(&out).unwrap();
+        let forced =
+            export_account("ava", &out, L1XKeyExportFormat::Hex, "", true);
+
+        // Assert
+        assert!(refused.unwrap_err().to_string().contains("--force"));
+        assert_eq!(kept, "keep");
+        assert!(forced.is_ok());
+        assert_eq!(
+            fs::read_to_string(&out).unwrap(),
+            format!("0x{}\n", "0".repeat(63) + "1")
+        );
+    });
+}
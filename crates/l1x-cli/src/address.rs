@@ -0,0 +1,189 @@
+use crate::address_of::{self, L1XAddressInfo};
+use crate::balance::resolve_address;
+use crate::cli_error::L1XConfigError;
+use l1x_common::{
+    nonce_manager, rpc, toolkit_config::ToolkitConfig, types::AddressStr,
+};
+
+use anyhow::Result;
+
+/// Derive account addresses and predict contract addresses
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "address")]
+pub struct L1XAddressCmd {
+    #[clap(subcommand)]
+    command: L1XAddressSubCmd,
+}
+
+#[derive(Clone, Debug, clap::Subcommand)]
+enum L1XAddressSubCmd {
+    /// Account address of a compressed or uncompressed public key
+    #[command(name = "from-pubkey")]
+    FromPubkey { pubkey: String },
+    /// Address a contract deployment will get
+    #[command(name = "contract")]
+    Contract {
+        #[clap(long = "deployer")]
+        deployer: String,
+
+        #[clap(long = "cluster")]
+        cluster: String,
+
+        /// Nonce of the deployment transaction
+        #[clap(long = "nonce")]
+        nonce: u128,
+    },
+    /// Address a contract initialization will get
+    #[command(name = "contract-instance")]
+    ContractInstance {
+        #[clap(long = "deployer")]
+        deployer: String,
+
+        /// Address of the deployed contract
+        #[clap(long = "contract")]
+        contract: String,
+
+        #[clap(long = "cluster")]
+        cluster: String,
+
+        /// Nonce of the initialization transaction
+        #[clap(long = "nonce")]
+        nonce: u128,
+    },
+    /// Address the next contract deployment of a wallet or address will get
+    #[command(name = "predict-deploy")]
+    PredictDeploy {
+        #[clap(long = "owner")]
+        owner: String,
+
+        /// Cluster address instead of the cluster_address of the network
+        #[clap(long = "cluster")]
+        cluster: Option<String>,
+
+        /// Network of l1x_chain_config.yaml to query, instead of
+        /// L1X_CFG_CHAIN_TYPE or its default_network
+        #[clap(long = "network")]
+        network: Option<String>,
+    },
+}
+
+fn parse_address(address: &str) -> Result<[u8; 20]> {
+    Ok(address.parse::<AddressStr>()?.into())
+}
+
+/// `0x` prefixed account address of the hex public key `pub_key`
+pub fn address_from_pub_key(pub_key: &str) -> Result<String> {
+    let pub_key = address_of::parse_pub_key(pub_key)?;
+
+    Ok(L1XAddressInfo::from_pub_key(&pub_key)?.address)
+}
+
+/// `0x` prefixed address of the contract `deployer` deploys to `cluster`
+/// with `nonce`
+pub fn predict_contract_address(
+    deployer: &str,
+    cluster: &str,
+    nonce: u128,
+) -> Result<String> {
+    let address = l1x_common::contract_address(
+        &parse_address(deployer)?,
+        &parse_address(cluster)?,
+        nonce,
+    );
+
+    Ok(format!("0x{}", hex::encode(address)))
+}
+
+/// `0x` prefixed address of the instance of `contract` `deployer`
+/// initializes on `cluster` with `nonce`
+pub fn predict_contract_instance_address(
+    deployer: &str,
+    contract: &str,
+    cluster: &str,
+    nonce: u128,
+) -> Result<String> {
+    let address = l1x_common::contract_instance_address(
+        &parse_address(deployer)?,
+        &parse_address(contract)?,
+        &parse_address(cluster)?,
+        nonce,
+    );
+
+    Ok(format!("0x{}", hex::encode(address)))
+}
+
+/// `0x` prefixed address of the contract `deployer` deploys to `cluster`
+/// with its next nonce, read from the node at `end_point`
+pub async fn predict_next_deploy_address(
+    end_point: &str,
+    deployer: &str,
+    cluster: &str,
+) -> Result<String> {
+    let deployer = parse_address(deployer)?;
+    let nonce = rpc::fetch_nonce(
+        end_point,
+        &hex::encode(deployer),
+        rpc::DEFAULT_RPC_TIMEOUT,
+    )
+    .await?;
+
+    predict_contract_address(
+        &hex::encode(deployer),
+        cluster,
+        nonce_manager::next_nonce(nonce),
+    )
+}
+
+impl L1XAddressCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let address = match &self.command {
+            L1XAddressSubCmd::FromPubkey { pubkey } => {
+                address_from_pub_key(pubkey)?
+            }
+            L1XAddressSubCmd::Contract { deployer, cluster, nonce } => {
+                predict_contract_address(deployer, cluster, *nonce)?
+            }
+            L1XAddressSubCmd::ContractInstance {
+                deployer,
+                contract,
+                cluster,
+                nonce,
+            } => predict_contract_instance_address(
+                deployer, contract, cluster, *nonce,
+            )?,
+            L1XAddressSubCmd::PredictDeploy { owner, cluster, network } => {
+                let toolkit = ToolkitConfig::from_env()
+                    .map_err(L1XConfigError::from)?
+                    .with_network(network.as_deref());
+                let end_point = toolkit
+                    .active_chain_json_rpc_endpoint()
+                    .map_err(L1XConfigError::from)?;
+                let cluster = match cluster {
+                    Some(cluster) => cluster.clone(),
+                    None => toolkit
+                        .active_cluster_address()
+                        .map_err(L1XConfigError::from)?
+                        .map(|cluster| cluster.to_hex())
+                        .ok_or_else(|| {
+                            L1XConfigError(format!(
+                                "No cluster_address for network '{}' in {}, pass --cluster",
+                                toolkit.active_network().unwrap_or_default(),
+                                toolkit.paths().chain_config_file().display()
+                            ))
+                        })?,
+                };
+
+                predict_next_deploy_address(
+                    &end_point,
+                    &resolve_address(owner)?,
+                    &cluster,
+                )
+                .await?
+            }
+        };
+
+        println!("{}", address);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::cli_error::L1XConfigError;
+use l1x_common::toolkit_config::ToolkitConfig;
+
+use anyhow::Result;
+use secp256k1::{PublicKey, Secp256k1};
+
+/// Account address and public keys of a secp256k1 key
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1XAddressInfo {
+    /// 0x prefixed hex account address
+    pub address: String,
+    pub compressed_pub_key: String,
+    pub uncompressed_pub_key: String,
+}
+
+impl L1XAddressInfo {
+    pub fn from_pub_key(pub_key: &PublicKey) -> Result<Self> {
+        let address = l1x_common::account_address(&pub_key.serialize())?;
+
+        Ok(L1XAddressInfo {
+            address: format!("0x{}", hex::encode(address)),
+            compressed_pub_key: hex::encode(pub_key.serialize()),
+            uncompressed_pub_key: hex::encode(pub_key.serialize_uncompressed()),
+        })
+    }
+}
+
+pub fn parse_pub_key(pub_key: &str) -> Result<PublicKey> {
+    let clean_pub_key = pub_key.trim().trim_start_matches("0x");
+    Ok(PublicKey::from_slice(&hex::decode(clean_pub_key)?)?)
+}
+
+pub fn parse_priv_key(priv_key: &str) -> Result<PublicKey> {
+    let secret_key = l1x_common::parse_secret_key(priv_key)?;
+    Ok(secret_key.public_key(&Secp256k1::new()))
+}
+
+/// Print the account address a wallet, public or private key maps to
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "address-of")]
+#[clap(group(clap::ArgGroup::new("key").required(true).args(["owner", "pub_key", "priv_key"])))]
+pub struct L1XAddressOfCmd {
+    #[clap(long = "owner")]
+    owner: Option<String>,
+
+    #[clap(long = "pub-key")]
+    pub_key: Option<String>,
+
+    #[clap(long = "priv-key")]
+    priv_key: Option<String>,
+}
+
+impl L1XAddressOfCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let pub_key = match (&self.owner, &self.pub_key, &self.priv_key) {
+            (Some(owner), _, _) => self.wallet_pub_key(owner)?,
+            (_, Some(pub_key), _) => parse_pub_key(pub_key)?,
+            (_, _, Some(priv_key)) => parse_priv_key(priv_key)?,
+            (None, None, None) => {
+                anyhow::bail!(
+                    "One of --owner, --pub-key or --priv-key is required"
+                )
+            }
+        };
+
+        let address_info = L1XAddressInfo::from_pub_key(&pub_key)?;
+        println!("address: {}", address_info.address);
+        println!("compressed_pub_key: {}", address_info.compressed_pub_key);
+        println!("uncompressed_pub_key: {}", address_info.uncompressed_pub_key);
+
+        Ok(())
+    }
+
+    /// Public key of the wallet `priv_key`, checked against the `pub_key` of
+    /// the same wallet entry
+    fn wallet_pub_key(&self, owner: &str) -> Result<PublicKey> {
+        let account = ToolkitConfig::from_env()
+            .and_then(|toolkit| toolkit.wallet_account(owner))
+            .map_err(L1XConfigError::from)?;
+        let pub_key = parse_priv_key(account.priv_key())?;
+
+        if let Some(wallet_pub_key) = account.pub_key() {
+            if let Some(warning) =
+                wallet_key_mismatch(owner, &pub_key, wallet_pub_key)?
+            {
+                tracing::warn!("{}", warning);
+                eprintln!("WARNING: {}", warning);
+            }
+        }
+
+        Ok(pub_key)
+    }
+}
+
+/// Describe how the `pub_key` of a wallet entry disagrees with the public key
+/// of its `priv_key`, `None` when they match
+pub fn wallet_key_mismatch(
+    owner: &str,
+    pub_key: &PublicKey,
+    wallet_pub_key: &str,
+) -> Result<Option<String>> {
+    let wallet_pub_key = parse_pub_key(wallet_pub_key).map_err(|err| {
+        anyhow::anyhow!("Wallet '{}' has an invalid pub_key: {}", owner, err)
+    })?;
+
+    if &wallet_pub_key == pub_key {
+        return Ok(None);
+    }
+
+    Ok(Some(format!(
+        "Wallet '{}' pub_key maps to {} but its priv_key, which signs its transactions, maps to {}",
+        owner,
+        L1XAddressInfo::from_pub_key(&wallet_pub_key)?.address,
+        L1XAddressInfo::from_pub_key(pub_key)?.address,
+    )))
+}
@@ -0,0 +1,69 @@
+use crate::address_of::*;
+
+// secp256k1 keys 1 and 2 and the addresses `Account::address` derives
+const PRIV_KEY_ONE: &str =
+    "0000000000000000000000000000000000000000000000000000000000000001";
+const PRIV_KEY_TWO: &str =
+    "0000000000000000000000000000000000000000000000000000000000000002";
+const ADDRESS_ONE: &str = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+const ADDRESS_TWO: &str = "0x2b5ad5c4795c026514f8317c7a215e218dccd6cf";
+const COMPRESSED_PUB_KEY_ONE: &str =
+    "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+#[test]
+fn test_address_of_priv_key_vectors() {
+    for (priv_key, address) in
+        [(PRIV_KEY_ONE, ADDRESS_ONE), (PRIV_KEY_TWO, ADDRESS_TWO)]
+    {
+        let pub_key = parse_priv_key(priv_key).unwrap();
+
+        let address_info = L1XAddressInfo::from_pub_key(&pub_key).unwrap();
+
+        assert_eq!(address_info.address, address);
+    }
+}
+
+#[test]
+fn test_address_of_compressed_and_uncompressed_pub_key_agree() {
+    // Arrange
+    let address_info =
+        L1XAddressInfo::from_pub_key(&parse_priv_key(PRIV_KEY_ONE).unwrap())
+            .unwrap();
+
+    // Act
+    let compressed = L1XAddressInfo::from_pub_key(
+        &parse_pub_key(&format!("0x{}", COMPRESSED_PUB_KEY_ONE)).unwrap(),
+    )
+    .unwrap();
+    let uncompressed = L1XAddressInfo::from_pub_key(
+        &parse_pub_key(&address_info.uncompressed_pub_key).unwrap(),
+    )
+    .unwrap();
+
+    // Assert
+    assert_eq!(address_info.compressed_pub_key, COMPRESSED_PUB_KEY_ONE);
+    assert_eq!(compressed, address_info);
+    assert_eq!(uncompressed, address_info);
+}
+
+#[test]
+fn test_wallet_key_mismatch_detects_stale_pub_key() {
+    let pub_key = parse_priv_key(PRIV_KEY_TWO).unwrap();
+
+    let warning =
+        wallet_key_mismatch("ava", &pub_key, COMPRESSED_PUB_KEY_ONE).unwrap();
+
+    let warning = warning.unwrap();
+    assert!(warning.contains(ADDRESS_ONE));
+    assert!(warning.contains(ADDRESS_TWO));
+}
+
+#[test]
+fn test_wallet_key_mismatch_accepts_consistent_entry() {
+    let pub_key = parse_priv_key(PRIV_KEY_ONE).unwrap();
+
+    let warning =
+        wallet_key_mismatch("ava", &pub_key, COMPRESSED_PUB_KEY_ONE).unwrap();
+
+    assert_eq!(warning, None);
+}
@@ -0,0 +1,79 @@
+use crate::address::*;
+
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DEPLOYER: &str = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+const CLUSTER: &str = "2b5ad5c4795c026514f8317c7a215e218dccd6cf";
+const CONTRACT: &str = "1111111111111111111111111111111111111111";
+
+#[test]
+fn test_address_from_pub_key_vector() {
+    let address = address_from_pub_key(
+        "0x0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    )
+    .unwrap();
+
+    assert_eq!(address, "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+}
+
+#[test]
+fn test_address_from_pub_key_rejects_invalid_key() {
+    let result = address_from_pub_key("02abcd");
+
+    assert!(result.unwrap_err().to_string().contains("malformed public key"));
+}
+
+// keccak256(deployer || cluster || nonce as 16 big endian bytes)[12..]
+#[test]
+fn test_predict_contract_address_vector() {
+    let address = predict_contract_address(DEPLOYER, CLUSTER, 5).unwrap();
+
+    assert_eq!(address, "0xc1c9b088c0f9f0ae2eab88a4115f8bd8b281fa16");
+}
+
+// keccak256(deployer || contract || cluster || nonce)[12..]
+#[test]
+fn test_predict_contract_instance_address_vector() {
+    let address =
+        predict_contract_instance_address(DEPLOYER, CONTRACT, CLUSTER, 5)
+            .unwrap();
+
+    assert_eq!(address, "0x8db50ac1914a9c32531b61567f08f8d1b81a8f20");
+}
+
+#[tokio::test]
+async fn test_predict_next_deploy_address_uses_next_nonce() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "l1x_getAccountState" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "account_state": { "balance": "0", "nonce": "4", "account_type": 0 }
+            },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Act
+    let address =
+        predict_next_deploy_address(&server.uri(), DEPLOYER, CLUSTER).await;
+
+    // Assert
+    assert_eq!(address.unwrap(), "0xc1c9b088c0f9f0ae2eab88a4115f8bd8b281fa16");
+}
+
+#[test]
+fn test_predict_contract_address_rejects_short_address() {
+    let result = predict_contract_address("0x1234", CLUSTER, 5);
+
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("expected 40 hex chars, got 4"));
+}
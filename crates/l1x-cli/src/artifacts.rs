@@ -0,0 +1,111 @@
+use crate::cli_error::L1XConfigError;
+use crate::contract_install::L1XVMType;
+use l1x_common::toolkit_config::{L1XVMArtifactType, ToolkitConfig};
+
+use anyhow::Result;
+use secp256k1::hashes::{sha256, Hash};
+use std::{fs, path::Path};
+
+/// Inspect the build artifacts available to `vm-install-contract`
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "artifacts")]
+pub struct L1XArtifactsCmd {
+    #[clap(subcommand)]
+    command: L1XArtifactsSubCmd,
+}
+
+#[derive(Clone, Debug, clap::Subcommand)]
+enum L1XArtifactsSubCmd {
+    /// List the artifacts of the configured artifact directories
+    #[command(name = "list")]
+    List {
+        #[clap(long = "vm-type")]
+        vm_type: Option<L1XVMType>,
+    },
+}
+
+/// A file of an artifact directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1XArtifactInfo {
+    pub file_name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Artifacts of `artifact_dir` sorted by file name, empty when the directory
+/// doesn't exist
+pub fn scan_artifact_dir(artifact_dir: &Path) -> Result<Vec<L1XArtifactInfo>> {
+    if !artifact_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut artifacts = Vec::new();
+    for entry in fs::read_dir(artifact_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+
+        let content = fs::read(entry.path())?;
+        artifacts.push(L1XArtifactInfo {
+            file_name: entry.file_name().to_string_lossy().to_string(),
+            size: content.len() as u64,
+            sha256: sha256::Hash::hash(&content).to_string(),
+        });
+    }
+    artifacts.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+
+    Ok(artifacts)
+}
+
+impl L1XArtifactsCmd {
+    pub async fn exec(&self) -> Result<()> {
+        match &self.command {
+            L1XArtifactsSubCmd::List { vm_type } => {
+                let vm_types = match vm_type {
+                    Some(vm_type) => vec![*vm_type],
+                    None => vec![L1XVMType::L1xVmEbpf, L1XVMType::L1xVmEvm],
+                };
+
+                for vm_type in vm_types {
+                    Self::list(vm_type)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn list(vm_type: L1XVMType) -> Result<()> {
+        let artifact_type = L1XVMArtifactType::from(vm_type);
+        let toolkit =
+            ToolkitConfig::from_env().map_err(L1XConfigError::from)?;
+        let artifact_dir = toolkit.artifact_dir(artifact_type);
+
+        println!("{} artifacts in {}", vm_type, artifact_dir.display());
+
+        for artifact in scan_artifact_dir(&artifact_dir)? {
+            let deploy_address =
+                match artifact_type {
+                    L1XVMArtifactType::Ebpf => toolkit
+                        .ebpf_contract_address_for(&artifact.file_name, None),
+                    L1XVMArtifactType::Evm => toolkit
+                        .evm_contract_address_for(&artifact.file_name, None),
+                    L1XVMArtifactType::Wasm => toolkit
+                        .wasm_contract_address_for(&artifact.file_name, None),
+                };
+
+            println!(
+                "{} | {} bytes | sha256 {} | {}",
+                artifact.file_name,
+                artifact.size,
+                artifact.sha256,
+                deploy_address
+                    .map(|address| format!("deployed {}", address))
+                    .unwrap_or_else(|_| "not deployed".to_string())
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::artifacts::*;
+use crate::contract_install_test::with_temp_registry;
+use l1x_common::toolkit_config::{L1XVMArtifactType, ToolkitConfig};
+
+use std::fs;
+
+#[test]
+fn test_scan_artifact_dir_lists_files() {
+    // Arrange
+    let artifact_dir = tempfile::tempdir().unwrap();
+    fs::write(artifact_dir.path().join("b.o"), b"abc").unwrap();
+    fs::write(artifact_dir.path().join("a.o"), b"").unwrap();
+    fs::create_dir(artifact_dir.path().join("nested")).unwrap();
+
+    // Act
+    let artifacts = scan_artifact_dir(artifact_dir.path()).unwrap();
+
+    // Assert
+    assert_eq!(
+        artifacts,
+        vec![
+            L1XArtifactInfo {
+                file_name: "a.o".to_string(),
+                size: 0,
+                sha256: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+            },
+            L1XArtifactInfo {
+                file_name: "b.o".to_string(),
+                size: 3,
+                sha256: "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_scan_artifact_dir_missing_dir_is_empty() {
+    let ws_home = tempfile::tempdir().unwrap();
+
+    let artifacts = scan_artifact_dir(&ws_home.path().join("missing")).unwrap();
+
+    assert!(artifacts.is_empty());
+}
+
+#[test]
+fn test_get_artifact_dir_defaults_and_overrides() {
+    // Act
+    let (default_ebpf_dir, ebpf_dir, evm_dir, ws_home) =
+        with_temp_registry(None, |ws_home| {
+            let default_ebpf_dir = ToolkitConfig::from_env()
+                .unwrap()
+                .artifact_dir(L1XVMArtifactType::Ebpf);
+            fs::write(
+                ws_home.join("l1x-conf/l1x_chain_config.yaml"),
+                "networks: {}\nartifact_dirs:\n  evm: build/evm\n",
+            )
+            .unwrap();
+            (
+                default_ebpf_dir,
+                ToolkitConfig::from_env()
+                    .unwrap()
+                    .artifact_dir(L1XVMArtifactType::Ebpf),
+                ToolkitConfig::from_env()
+                    .unwrap()
+                    .artifact_dir(L1XVMArtifactType::Evm),
+                ws_home.to_path_buf(),
+            )
+        });
+
+    // Assert
+    assert_eq!(default_ebpf_dir, ws_home.join("l1x-artifacts"));
+    assert_eq!(ebpf_dir, ws_home.join("l1x-artifacts"));
+    assert_eq!(evm_dir, ws_home.join("build/evm"));
+}
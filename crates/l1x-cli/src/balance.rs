@@ -0,0 +1,79 @@
+use crate::address_of::{parse_priv_key, L1XAddressInfo};
+use crate::cli_error::L1XConfigError;
+use l1x_common::{
+    rpc,
+    toolkit_config::{self, ToolkitConfig},
+    types::AddressStr,
+};
+
+use anyhow::Result;
+
+/// Print the balance, nonce and type of an account on the active chain
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "balance")]
+pub struct L1XBalanceCmd {
+    /// Hex account address, with or without `0x`, or a dev wallet name
+    target: String,
+
+    /// Print the account state as JSON
+    #[clap(long = "json", default_value_t = false)]
+    json: bool,
+
+    /// Network of l1x_chain_config.yaml to query, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+}
+
+/// Account address `target` refers to: `target` itself when it is a 20 byte
+/// hex address, the address of the wallet `target` otherwise
+pub fn resolve_address(target: &str) -> Result<String> {
+    let address_err = match toolkit_config::normalize_address(target) {
+        Ok(address) => return Ok(address),
+        Err(err_code) => err_code,
+    };
+
+    let priv_key = ToolkitConfig::from_env()
+        .and_then(|toolkit| toolkit.wallet_priv_key(target))
+        .map_err(|wallet_err| {
+            L1XConfigError(format!(
+                "'{}' is neither an address nor a wallet: {}; {}",
+                target, address_err, wallet_err
+            ))
+        })?;
+    let address_info =
+        L1XAddressInfo::from_pub_key(&parse_priv_key(&priv_key)?)?;
+
+    Ok(address_info.address.parse::<AddressStr>()?.to_hex())
+}
+
+impl L1XBalanceCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let address = resolve_address(&self.target)?;
+        let end_point = ToolkitConfig::from_env()
+            .and_then(|toolkit| {
+                toolkit
+                    .with_network(self.network.as_deref())
+                    .active_chain_json_rpc_endpoint()
+            })
+            .map_err(L1XConfigError::from)?;
+
+        let account_state = rpc::get_account_state(
+            &end_point,
+            &address,
+            rpc::DEFAULT_RPC_TIMEOUT,
+        )
+        .await?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&account_state)?);
+        } else {
+            println!("address: 0x{}", account_state.address);
+            println!("balance: {}", account_state.balance);
+            println!("nonce: {}", account_state.nonce);
+            println!("account_type: {}", account_state.account_type);
+        }
+
+        Ok(())
+    }
+}
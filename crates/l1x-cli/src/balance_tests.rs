@@ -0,0 +1,112 @@
+use crate::balance::*;
+use crate::contract_install_test::{with_temp_registry, write_toolkit_config};
+use l1x_common::rpc;
+
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+// Address of the secp256k1 key 1, the key of every `write_toolkit_config`
+// wallet
+const ADDRESS_ONE: &str = "7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
+#[test]
+fn test_resolve_address_normalizes_hex_address() {
+    let address =
+        resolve_address("0x7E5F4552091A69125D5DFCB7B8C2659029395BDF").unwrap();
+
+    assert_eq!(address, ADDRESS_ONE);
+}
+
+#[test]
+fn test_resolve_address_of_wallet() {
+    with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+
+        let address = resolve_address("ava").unwrap();
+
+        assert_eq!(address, ADDRESS_ONE);
+    });
+}
+
+#[test]
+fn test_resolve_address_rejects_short_address_and_unknown_wallet() {
+    with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+
+        let err = resolve_address("0x7e5f45").unwrap_err().to_string();
+
+        assert!(err.contains("expected 40 hex chars, got 6"), "{}", err);
+        assert!(err.contains("wallet '0x7e5f45' not found"), "{}", err);
+    });
+}
+
+#[tokio::test]
+async fn test_get_account_state() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_getAccountState",
+            "params": { "request": { "address": ADDRESS_ONE } },
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "account_state": {
+                    "balance": "1000000",
+                    "nonce": 7,
+                    "account_type": 1,
+                },
+            },
+        })))
+        .mount(&server)
+        .await;
+
+    // Act
+    let account_state = rpc::get_account_state(
+        &server.uri(),
+        &format!("0x{}", ADDRESS_ONE),
+        rpc::DEFAULT_RPC_TIMEOUT,
+    )
+    .await
+    .unwrap();
+
+    // Assert
+    assert_eq!(
+        account_state,
+        rpc::AccountStateInfo {
+            address: ADDRESS_ONE.to_string(),
+            balance: 1_000_000,
+            nonce: 7,
+            account_type: "1".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_get_account_state_of_unknown_account_is_zero() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "account_state": null },
+        })))
+        .mount(&server)
+        .await;
+
+    let account_state = rpc::get_account_state(
+        &server.uri(),
+        ADDRESS_ONE,
+        rpc::DEFAULT_RPC_TIMEOUT,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(account_state.balance, 0);
+    assert_eq!(account_state.nonce, 0);
+}
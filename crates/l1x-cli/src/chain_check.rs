@@ -0,0 +1,50 @@
+use crate::cli_error::L1XConfigError;
+use l1x_common::{
+    rpc::{self, ChainIdCheck, RpcError},
+    toolkit_config::ToolkitConfig,
+};
+
+/// Abort when the node at `end_point` is on another chain than the active
+/// network of `toolkit`. A node failing to answer is left to the requests
+/// that follow to report.
+pub(crate) async fn check_active_chain_id(
+    toolkit: &ToolkitConfig,
+    end_point: &str,
+) -> Result<(), L1XConfigError> {
+    let network = toolkit.active_network()?;
+    let chain_id = match toolkit.network_config()?.networks().get(&network) {
+        Some(config_network) => config_network.chain_id(),
+        None => return Ok(()),
+    };
+
+    match rpc::verify_chain_id(end_point, chain_id, rpc::DEFAULT_RPC_TIMEOUT)
+        .await
+    {
+        Ok(ChainIdCheck::Match) => Ok(()),
+        Ok(ChainIdCheck::Unsupported) => {
+            tracing::warn!(
+                "Node {} doesn't report its chain id, chain id {} of network {} not checked",
+                end_point,
+                chain_id,
+                network
+            );
+            Ok(())
+        }
+        Err(err_code @ RpcError::ChainIdMismatch { .. }) => {
+            Err(L1XConfigError(format!(
+                "{}\nhint: fix the chain_id or rpc_endpoint of network '{}' in {}, or pass --skip-chain-check",
+                err_code,
+                network,
+                toolkit.paths().chain_config_file().display()
+            )))
+        }
+        Err(err_code) => {
+            tracing::warn!(
+                "Unable to check the chain id of {} :: {}",
+                end_point,
+                err_code
+            );
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::chain_check::check_active_chain_id;
+use crate::contract_install_test::toolkit_on;
+
+use serde_json::{json, Value};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mock_chain_state(server: &MockServer, response: Value) {
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "l1x_getChainState" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_check_active_chain_id_passes_on_matching_node() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_chain_state(
+        &server,
+        json!({ "jsonrpc": "2.0", "id": 1, "result": { "chain_id": "1" } }),
+    )
+    .await;
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = toolkit_on(ws_home.path(), 1, &server.uri());
+
+    // Act
+    let first = check_active_chain_id(&toolkit, &server.uri()).await;
+    let cached = check_active_chain_id(&toolkit, &server.uri()).await;
+
+    // Assert
+    assert!(first.is_ok());
+    assert!(cached.is_ok());
+}
+
+#[tokio::test]
+async fn test_check_active_chain_id_rejects_node_on_other_chain() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_chain_state(
+        &server,
+        json!({ "jsonrpc": "2.0", "id": 1, "result": { "chain_id": 1776 } }),
+    )
+    .await;
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = toolkit_on(ws_home.path(), 1, &server.uri());
+
+    // Act
+    let result = check_active_chain_id(&toolkit, &server.uri()).await;
+
+    // Assert
+    let message = result.unwrap_err().to_string();
+    assert!(message.contains("chain id 1776"), "{}", message);
+    assert!(message.contains("chain id 1"), "{}", message);
+    assert!(message.contains("--skip-chain-check"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_check_active_chain_id_skips_node_without_chain_state() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_chain_state(
+        &server,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32601, "message": "Method not found" },
+        }),
+    )
+    .await;
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = toolkit_on(ws_home.path(), 1, &server.uri());
+
+    // Act
+    let result = check_active_chain_id(&toolkit, &server.uri()).await;
+
+    // Assert
+    assert!(result.is_ok());
+}
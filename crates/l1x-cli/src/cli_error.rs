@@ -0,0 +1,97 @@
+use l1x_common::toolkit_config::{
+    ToolkitConfigError, CHAIN_TYPE_ENV, WS_HOME_ENV,
+};
+
+/// Missing or inconsistent toolkit configuration, e.g. an unknown owner or
+/// chain type. The CLI reports it, followed by a `hint:` line when it knows
+/// the fix, and exits with code 2.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct L1XConfigError(pub String);
+
+impl From<String> for L1XConfigError {
+    fn from(message: String) -> Self {
+        L1XConfigError(message)
+    }
+}
+
+/// The toolkit error followed by a `hint:` line telling how to fix it
+impl From<ToolkitConfigError> for L1XConfigError {
+    fn from(config_err: ToolkitConfigError) -> Self {
+        match remediation_hint(&config_err) {
+            Some(hint) => {
+                L1XConfigError(format!("{}\nhint: {}", config_err, hint))
+            }
+            None => L1XConfigError(config_err.to_string()),
+        }
+    }
+}
+
+/// How the user fixes `config_err`, none when the message already says it
+pub fn remediation_hint(config_err: &ToolkitConfigError) -> Option<String> {
+    match config_err {
+        ToolkitConfigError::MissingEnvVar(name) if *name == WS_HOME_ENV => {
+            Some(format!(
+                "export {}=<workspace>, create one with `l1x-forge init-workspace`",
+                WS_HOME_ENV
+            ))
+        }
+        ToolkitConfigError::MissingEnvVar(name) if *name == CHAIN_TYPE_ENV => {
+            Some(format!(
+                "export {}=<network> naming a network of l1x_chain_config.yaml",
+                CHAIN_TYPE_ENV
+            ))
+        }
+        ToolkitConfigError::MissingEnvVar(name) => {
+            Some(format!("export {}", name))
+        }
+        ToolkitConfigError::MissingConfigFile(_) => Some(format!(
+            "create it with `l1x-forge init-workspace` or export {}=<workspace> holding it; `l1x-forge config path` prints the searched locations",
+            WS_HOME_ENV
+        )),
+        ToolkitConfigError::ConfigParse { path, .. }
+        | ToolkitConfigError::InvalidConfig { path, .. } => Some(format!(
+            "fix {} or recreate it with `l1x-forge init-workspace --force`",
+            path
+        )),
+        ToolkitConfigError::InvalidNetwork { network, path, .. } => Some(format!(
+            "fix the network in {} or replace it with `l1x-forge config network add --name {} --force`",
+            path, network
+        )),
+        ToolkitConfigError::UnknownNetwork { .. } => Some(format!(
+            "pass --network or set {} to an available chain type or add the network to l1x_chain_config.yaml",
+            CHAIN_TYPE_ENV
+        )),
+        ToolkitConfigError::NoActiveNetwork { .. } => Some(
+            "add `default_network: <network>` to l1x_chain_config.yaml to skip --network"
+                .to_string(),
+        ),
+        ToolkitConfigError::UnknownOwner { .. } => Some(
+            "use an available wallet or add one with `l1x-forge account create`"
+                .to_string(),
+        ),
+        ToolkitConfigError::RegistryMissingArtifact(_) => Some(
+            "deploy the artifact first with `l1x-forge vm-install-contract`"
+                .to_string(),
+        ),
+        ToolkitConfigError::RegistryMissingInstance { .. } => Some(
+            "initialize the contract first with `l1x-forge vm-install-contract --contract-id`"
+                .to_string(),
+        ),
+        ToolkitConfigError::RegistryImportConflict { .. } => Some(
+            "pass --replace to overwrite the registry of the network with the manifest, or remove the conflicting entries with `l1x-forge registry remove`"
+                .to_string(),
+        ),
+        ToolkitConfigError::MissingConfigKey { .. } => Some(
+            "print the parent key with `l1x-forge config get <key>` to see the available keys"
+                .to_string(),
+        ),
+        ToolkitConfigError::ConfigWrite { .. }
+        | ToolkitConfigError::InvalidConfigValue { .. }
+        | ToolkitConfigError::OwnerExists { .. }
+        | ToolkitConfigError::NetworkExists { .. }
+        | ToolkitConfigError::InvalidRegistryManifest(_)
+        | ToolkitConfigError::InvalidPrivateKey(_)
+        | ToolkitConfigError::MissingSigningKey => None,
+    }
+}
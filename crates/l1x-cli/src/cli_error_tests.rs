@@ -0,0 +1,218 @@
+use crate::cli_error::*;
+use crate::contract_install_test::{
+    toolkit_for, with_temp_registry, write_toolkit_config,
+};
+use l1x_common::toolkit_config::{
+    self, L1XVMContractAddressUpdateType, ToolkitConfigError, ToolkitPaths,
+    WsHomeSource, WS_HOME_ENV,
+};
+
+use std::{env, fs};
+
+const REGISTRY: &str = r#"
+l1x_vm:
+  ft:
+    deploy_hash: "d1"
+    deploy_address: "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+    instance: {}
+l1x_evm: {}
+"#;
+
+#[test]
+#[allow(deprecated)]
+fn test_missing_ws_home_falls_back_to_user_config_dir() {
+    // Arrange
+    let config_home = tempfile::tempdir().unwrap();
+    let (paths, err) = with_temp_registry(None, |_| {
+        env::remove_var(WS_HOME_ENV);
+        let saved_config_home = env::var_os("XDG_CONFIG_HOME");
+        env::set_var("XDG_CONFIG_HOME", config_home.path());
+
+        // Act
+        let result = (
+            ToolkitPaths::from_env().unwrap(),
+            toolkit_config::get_toolkit_wallet_config().unwrap_err(),
+        );
+
+        match saved_config_home {
+            Some(saved_config_home) => {
+                env::set_var("XDG_CONFIG_HOME", saved_config_home)
+            }
+            None => env::remove_var("XDG_CONFIG_HOME"),
+        }
+        result
+    });
+
+    // Assert
+    assert_eq!(paths.ws_home(), config_home.path().join("l1x"));
+    assert_eq!(paths.ws_home_source(), WsHomeSource::UserConfigDir);
+    match &err {
+        ToolkitConfigError::MissingConfigFile(path) => assert_eq!(
+            path,
+            &config_home
+                .path()
+                .join("l1x/l1x-conf/l1x_dev_wallets.yaml")
+                .display()
+                .to_string()
+        ),
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert!(L1XConfigError::from(err)
+        .to_string()
+        .contains("hint: create it with `l1x-forge init-workspace`"));
+}
+
+#[test]
+fn test_no_active_network_hint() {
+    let ws_home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    write_toolkit_config(ws_home.path());
+
+    let err = toolkit_for(ws_home.path())
+        .active_chain_json_rpc_endpoint()
+        .unwrap_err();
+
+    assert!(matches!(err, ToolkitConfigError::NoActiveNetwork { .. }));
+    assert_eq!(
+        L1XConfigError::from(err).to_string(),
+        "No network selected: pass --network, set L1X_CFG_CHAIN_TYPE or default_network in l1x_chain_config.yaml; available chain types: local\n\
+         hint: add `default_network: <network>` to l1x_chain_config.yaml to skip --network"
+    );
+}
+
+#[test]
+fn test_malformed_config_is_parse_error_with_path() {
+    let err = with_temp_registry(None, |ws_home| {
+        fs::write(
+            ws_home.join("l1x-conf/l1x_dev_wallets.yaml"),
+            "dev_accounts: [",
+        )
+        .unwrap();
+
+        toolkit_for(ws_home).wallet_priv_key("ava").unwrap_err()
+    });
+
+    match &err {
+        ToolkitConfigError::ConfigParse { path, .. } => {
+            assert!(path.ends_with("l1x-conf/l1x_dev_wallets.yaml"), "{}", path)
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert!(L1XConfigError::from(err).to_string().contains("hint: fix "));
+}
+
+#[test]
+fn test_malformed_registry_update_is_parse_error() {
+    let err = with_temp_registry(Some("l1x_vm: ["), |ws_home| {
+        toolkit_for(ws_home)
+            .update_contract_address_registry(
+                L1XVMContractAddressUpdateType::L1XEVM_DEPLOY {
+                    artifact_id: "erc20".to_string(),
+                    response_hash: "h1".to_string(),
+                    response_address: "c".repeat(40).parse().unwrap(),
+                },
+            )
+            .unwrap_err()
+    });
+
+    assert!(matches!(err, ToolkitConfigError::ConfigParse { .. }), "{}", err);
+}
+
+#[test]
+fn test_unknown_network_and_owner() {
+    let (network_err, owner_err) = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        let toolkit = toolkit_for(ws_home).with_chain_type("mainnet");
+
+        (
+            toolkit.active_chain_json_rpc_endpoint().unwrap_err(),
+            toolkit.wallet_priv_key("alice").unwrap_err(),
+        )
+    });
+
+    assert!(matches!(
+        &network_err,
+        ToolkitConfigError::UnknownNetwork { network, available }
+            if network == "mainnet" && available == "local"
+    ));
+    assert!(matches!(
+        &owner_err,
+        ToolkitConfigError::UnknownOwner { owner, available }
+            if owner == "alice" && available == "ava, bobby, super"
+    ));
+    assert!(remediation_hint(&network_err).is_some());
+    assert!(remediation_hint(&owner_err).is_some());
+}
+
+#[test]
+fn test_registry_missing_artifact_and_instance() {
+    let (artifact_err, instance_err, init_err) =
+        with_temp_registry(Some(REGISTRY), |ws_home| {
+            let toolkit = toolkit_for(ws_home).with_chain_type("local");
+            (
+                toolkit.evm_contract_address_for("erc20", None).unwrap_err(),
+                toolkit
+                    .ebpf_contract_address_for("ft", Some("ft_main"))
+                    .unwrap_err(),
+                toolkit
+                    .update_contract_address_registry(
+                        L1XVMContractAddressUpdateType::L1XEBPF_INIT {
+                            artifact_id: "nft".to_string(),
+                            contract_id: "nft_main".to_string(),
+                            response_hash: "h1".to_string(),
+                            response_address: "c".repeat(40).parse().unwrap(),
+                        },
+                    )
+                    .unwrap_err(),
+            )
+        });
+
+    assert!(matches!(
+        &artifact_err,
+        ToolkitConfigError::RegistryMissingArtifact(artifact_id)
+            if artifact_id == "erc20"
+    ));
+    assert!(matches!(
+        &instance_err,
+        ToolkitConfigError::RegistryMissingInstance { artifact_id, contract_id }
+            if artifact_id == "ft" && contract_id == "ft_main"
+    ));
+    assert!(matches!(
+        &init_err,
+        ToolkitConfigError::RegistryMissingArtifact(artifact_id)
+            if artifact_id == "nft"
+    ));
+    assert!(L1XConfigError::from(init_err)
+        .to_string()
+        .contains("hint: deploy the artifact first"));
+}
+
+#[test]
+fn test_errors_without_hint_stay_single_line() {
+    let err = ToolkitConfigError::MissingSigningKey;
+
+    assert_eq!(remediation_hint(&err), None);
+    assert_eq!(
+        L1XConfigError::from(err).to_string(),
+        "No signing key: pass --owner or --private-key, or set L1X_PRIVATE_KEY"
+    );
+}
+
+#[test]
+fn test_env_override_of_endpoint() {
+    let end_point = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        env::set_var(
+            "L1X__NETWORKS__LOCAL__RPC_ENDPOINT",
+            "http://10.1.2.3:40041",
+        );
+
+        let end_point = toolkit_for(ws_home)
+            .with_chain_type("local")
+            .active_chain_json_rpc_endpoint();
+        env::remove_var("L1X__NETWORKS__LOCAL__RPC_ENDPOINT");
+        end_point
+    });
+
+    assert_eq!(end_point.unwrap(), "http://10.1.2.3:40041");
+}
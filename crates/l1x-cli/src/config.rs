@@ -0,0 +1,317 @@
+use crate::cli_error::L1XConfigError;
+use l1x_common::toolkit_config::{
+    self, L1XConfigFile, L1XNetworkUpdateType, Network, ToolkitConfig,
+    ToolkitConfigError, ToolkitPaths,
+};
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Manage the configuration files of the workspace
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "config")]
+pub struct L1XConfigCmd {
+    #[clap(subcommand)]
+    command: L1XConfigSubCmd,
+}
+
+#[derive(Clone, Debug, clap::Subcommand)]
+enum L1XConfigSubCmd {
+    /// Manage the networks of `l1x_chain_config.yaml`
+    #[command(name = "network", subcommand)]
+    Network(L1XNetworkSubCmd),
+    /// Print the resolved locations of the configuration files
+    #[command(name = "path")]
+    Path,
+    /// Print the value of a dotted key, e.g. networks.devnet.rpc_endpoint
+    #[command(name = "get")]
+    Get {
+        key: String,
+
+        #[clap(long = "file", value_enum, default_value_t = L1XConfigFileArg::Chain)]
+        file: L1XConfigFileArg,
+
+        /// Print the value as JSON
+        #[clap(long = "json", default_value_t = false)]
+        json: bool,
+    },
+    /// Set a dotted key, keeping the type of the value it replaces
+    #[command(name = "set")]
+    Set {
+        key: String,
+
+        value: String,
+
+        #[clap(long = "file", value_enum, default_value_t = L1XConfigFileArg::Chain)]
+        file: L1XConfigFileArg,
+    },
+}
+
+/// Configuration file of `config get` and `config set`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum L1XConfigFileArg {
+    /// l1x_chain_config.yaml
+    Chain,
+    /// l1x_dev_wallets.yaml
+    Wallet,
+    /// config-contract-address-registry.yaml
+    Registry,
+}
+
+impl From<L1XConfigFileArg> for L1XConfigFile {
+    fn from(file: L1XConfigFileArg) -> Self {
+        match file {
+            L1XConfigFileArg::Chain => L1XConfigFile::Chain,
+            L1XConfigFileArg::Wallet => L1XConfigFile::Wallet,
+            L1XConfigFileArg::Registry => L1XConfigFile::Registry,
+        }
+    }
+}
+
+#[derive(Clone, Debug, clap::Subcommand)]
+enum L1XNetworkSubCmd {
+    /// Register a network
+    #[command(name = "add")]
+    Add {
+        #[clap(long = "name")]
+        name: String,
+
+        #[clap(long = "chain-id")]
+        chain_id: u32,
+
+        /// JSON-RPC endpoint, e.g. http://127.0.0.1:50051
+        #[clap(long = "endpoint")]
+        endpoint: String,
+
+        /// Replace an existing network of the same name
+        #[clap(long = "force", default_value_t = false)]
+        force: bool,
+    },
+    /// Remove a network, unsetting the default network when it names it
+    #[command(name = "remove")]
+    Remove {
+        #[clap(long = "name")]
+        name: String,
+    },
+    /// List the networks, marking the active one
+    #[command(name = "list")]
+    List {
+        /// Print the networks as a JSON array
+        #[clap(long = "json", default_value_t = false)]
+        json: bool,
+    },
+    /// Use a network when neither --network nor L1X_CFG_CHAIN_TYPE is given
+    #[command(name = "set-default")]
+    SetDefault {
+        #[clap(long = "name")]
+        name: String,
+    },
+}
+
+/// A network of `l1x_chain_config.yaml`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct L1XNetworkInfo {
+    pub name: String,
+    pub chain_id: u32,
+    pub rpc_endpoint: String,
+    /// Network the networked commands use without --network
+    pub active: bool,
+    pub default: bool,
+}
+
+impl std::fmt::Display for L1XNetworkInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} :: chain id {} :: {}",
+            if self.active { "*" } else { " " },
+            self.name,
+            self.chain_id,
+            self.rpc_endpoint
+        )?;
+        if self.default {
+            f.write_str(" (default)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Networks of `toolkit` sorted by name
+pub fn list_networks(
+    toolkit: &ToolkitConfig,
+) -> Result<Vec<L1XNetworkInfo>, ToolkitConfigError> {
+    let config_network = toolkit.network_config()?;
+    // No active network is not an error for a listing
+    let active_network = toolkit.active_network().ok();
+
+    let mut networks: Vec<L1XNetworkInfo> = config_network
+        .networks()
+        .iter()
+        .map(|(name, network)| L1XNetworkInfo {
+            name: name.clone(),
+            chain_id: network.chain_id(),
+            rpc_endpoint: network.rpc_endpoint().to_string(),
+            active: active_network.as_deref() == Some(name.as_str()),
+            default: config_network.default_network() == Some(name.as_str()),
+        })
+        .collect();
+    networks.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(networks)
+}
+
+/// A configuration file of the workspace and where it is searched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L1XConfigPath {
+    pub name: &'static str,
+    pub path: PathBuf,
+    pub exists: bool,
+}
+
+impl std::fmt::Display for L1XConfigPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:<10} {}", self.name, self.path.display())?;
+        if !self.exists {
+            f.write_str(" (missing)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Workspace root and configuration files of `paths`
+pub fn config_paths(paths: &ToolkitPaths) -> Vec<L1XConfigPath> {
+    [
+        ("workspace", paths.ws_home().to_path_buf()),
+        ("chain", paths.chain_config_file()),
+        ("wallets", paths.wallet_config_file()),
+        ("registry", paths.contract_address_registry_file()),
+        ("templates", paths.template_config_file()),
+    ]
+    .into_iter()
+    .map(|(name, path)| L1XConfigPath { name, exists: path.exists(), path })
+    .collect()
+}
+
+/// `value` as printed by `config get`: scalars bare, subtrees in YAML or
+/// with `json` in JSON
+pub fn display_config_value(
+    value: &serde_yaml::Value,
+    json: bool,
+) -> Result<String> {
+    if json {
+        return Ok(serde_json::to_string_pretty(value)?);
+    }
+
+    Ok(match value {
+        serde_yaml::Value::Null => "null".to_string(),
+        serde_yaml::Value::Bool(value) => value.to_string(),
+        serde_yaml::Value::Number(value) => value.to_string(),
+        serde_yaml::Value::String(value) => value.clone(),
+        value => serde_yaml::to_string(value)?.trim_end().to_string(),
+    })
+}
+
+/// Network entry of `endpoint`, with the host and port it names
+pub fn network_of_endpoint(
+    chain_id: u32,
+    endpoint: &str,
+) -> Result<Network, L1XConfigError> {
+    let url = toolkit_config::parse_rpc_endpoint(endpoint)
+        .map_err(|reason| L1XConfigError(format!("Invalid {}", reason)))?;
+
+    match (url.host_str(), url.port_or_known_default()) {
+        (Some(host_ip), Some(rpc_port)) => {
+            Ok(Network::new(chain_id, host_ip, rpc_port.into(), endpoint))
+        }
+        _ => Err(L1XConfigError(format!(
+            "Invalid endpoint '{}': expected a host and a port",
+            endpoint
+        ))),
+    }
+}
+
+impl L1XConfigCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let toolkit =
+            ToolkitConfig::from_env().map_err(L1XConfigError::from)?;
+
+        match &self.command {
+            L1XConfigSubCmd::Network(network_cmd) => network_cmd.exec(&toolkit),
+            L1XConfigSubCmd::Get { key, file, json } => {
+                let value = toolkit
+                    .config_value((*file).into(), key)
+                    .map_err(L1XConfigError::from)?;
+
+                println!("{}", display_config_value(&value, *json)?);
+                Ok(())
+            }
+            L1XConfigSubCmd::Set { key, value, file } => {
+                toolkit
+                    .set_config_value((*file).into(), key, value)
+                    .map_err(L1XConfigError::from)?;
+
+                println!("Set {} = {}", key, value);
+                Ok(())
+            }
+            L1XConfigSubCmd::Path => {
+                println!("Workspace from {}", toolkit.paths().ws_home_source());
+                for config_path in config_paths(toolkit.paths()) {
+                    println!("{}", config_path);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl L1XNetworkSubCmd {
+    fn exec(&self, toolkit: &ToolkitConfig) -> Result<()> {
+        match self {
+            L1XNetworkSubCmd::Add { name, chain_id, endpoint, force } => {
+                let network = network_of_endpoint(*chain_id, endpoint)?;
+                toolkit
+                    .update_network_config(L1XNetworkUpdateType::Add {
+                        name: name.clone(),
+                        network,
+                        force: *force,
+                    })
+                    .map_err(L1XConfigError::from)?;
+
+                println!("Added network {} :: {}", name, endpoint);
+            }
+            L1XNetworkSubCmd::Remove { name } => {
+                toolkit
+                    .update_network_config(L1XNetworkUpdateType::Remove {
+                        name: name.clone(),
+                    })
+                    .map_err(L1XConfigError::from)?;
+
+                println!("Removed network {}", name);
+            }
+            L1XNetworkSubCmd::List { json } => {
+                let networks =
+                    list_networks(toolkit).map_err(L1XConfigError::from)?;
+
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&networks)?);
+                } else {
+                    for network in &networks {
+                        println!("{}", network);
+                    }
+                }
+            }
+            L1XNetworkSubCmd::SetDefault { name } => {
+                toolkit
+                    .update_network_config(L1XNetworkUpdateType::SetDefault {
+                        name: name.clone(),
+                    })
+                    .map_err(L1XConfigError::from)?;
+
+                println!("Default network {}", name);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use crate::config::*;
+use crate::contract_install_test::{
+    toolkit_for, with_temp_registry, write_toolkit_config,
+};
+use l1x_common::toolkit_config::Network;
+
+use clap::Parser;
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    config: L1XConfigCmd,
+}
+
+fn exec(args: &[&str]) -> anyhow::Result<()> {
+    let cmd = TestCli::parse_from(["config"].iter().chain(args)).config;
+    tokio::runtime::Runtime::new().unwrap().block_on(cmd.exec())
+}
+
+#[test]
+fn test_network_of_endpoint() {
+    assert_eq!(
+        network_of_endpoint(1076, "http://10.0.0.1:50052").unwrap(),
+        Network::new(1076, "10.0.0.1", 50052, "http://10.0.0.1:50052")
+    );
+    assert_eq!(
+        network_of_endpoint(1076, "https://rpc.l1x.foundation")
+            .unwrap()
+            .rpc_port(),
+        443
+    );
+    assert!(network_of_endpoint(1076, "10.0.0.1:50052").is_err());
+}
+
+#[test]
+fn test_network_commands_round_trip() {
+    // Arrange
+    let networks = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+
+        // Act
+        exec(&[
+            "network",
+            "add",
+            "--name",
+            "testnet",
+            "--chain-id",
+            "1076",
+            "--endpoint",
+            "http://10.0.0.1:50052",
+        ])
+        .unwrap();
+        exec(&["network", "set-default", "--name", "testnet"]).unwrap();
+        exec(&["network", "list", "--json"]).unwrap();
+
+        list_networks(&toolkit_for(ws_home)).unwrap()
+    });
+
+    // Assert
+    assert_eq!(
+        networks,
+        [
+            L1XNetworkInfo {
+                name: "local".to_string(),
+                chain_id: 1,
+                rpc_endpoint: "http://127.0.0.1:50051".to_string(),
+                active: false,
+                default: false,
+            },
+            L1XNetworkInfo {
+                name: "testnet".to_string(),
+                chain_id: 1076,
+                rpc_endpoint: "http://10.0.0.1:50052".to_string(),
+                active: true,
+                default: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_list_marks_chain_type_over_default() {
+    let networks = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        exec(&[
+            "network",
+            "add",
+            "--name",
+            "testnet",
+            "--chain-id",
+            "1076",
+            "--endpoint",
+            "http://10.0.0.1:50052",
+        ])
+        .unwrap();
+        exec(&["network", "set-default", "--name", "testnet"]).unwrap();
+
+        list_networks(&toolkit_for(ws_home).with_chain_type("local")).unwrap()
+    });
+
+    let active: Vec<&str> = networks
+        .iter()
+        .filter(|network| network.active)
+        .map(|network| network.name.as_str())
+        .collect();
+    assert_eq!(active, ["local"]);
+    assert_eq!(
+        networks[0].to_string(),
+        "* local :: chain id 1 :: http://127.0.0.1:50051"
+    );
+}
+
+#[test]
+fn test_remove_unknown_network_is_config_error() {
+    let err = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        exec(&["network", "remove", "--name", "mainnet"]).unwrap_err()
+    });
+
+    assert!(err.to_string().starts_with(
+        "chain type 'mainnet' not found; available chain types: local"
+    ));
+}
+
+#[test]
+fn test_config_paths_mark_missing_files() {
+    let ws_home = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    write_toolkit_config(ws_home.path());
+
+    let config_paths = config_paths(toolkit_for(ws_home.path()).paths());
+
+    let missing: Vec<&str> = config_paths
+        .iter()
+        .filter(|config_path| !config_path.exists)
+        .map(|config_path| config_path.name)
+        .collect();
+    assert_eq!(config_paths[0].path, ws_home.path());
+    assert_eq!(
+        config_paths[1].path,
+        ws_home.path().join("l1x-conf/l1x_chain_config.yaml")
+    );
+    assert_eq!(missing, ["registry", "templates"]);
+}
+
+#[test]
+fn test_get_and_set_commands() {
+    // Arrange
+    let (rpc_port, wallet_err) = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+
+        // Act
+        exec(&["set", "networks.local.rpc_port", "50052"]).unwrap();
+        exec(&["get", "networks.local", "--json"]).unwrap();
+        let wallet_err =
+            exec(&["get", "dev_accounts.alice", "--file", "wallet"])
+                .unwrap_err();
+
+        (
+            toolkit_for(ws_home)
+                .with_chain_type("local")
+                .network_config()
+                .unwrap()
+                .networks()["local"]
+                .rpc_port(),
+            wallet_err,
+        )
+    });
+
+    // Assert
+    assert_eq!(rpc_port, 50052);
+    assert!(wallet_err.to_string().starts_with("Key 'dev_accounts.alice'"));
+}
+
+#[test]
+fn test_display_config_value() {
+    let subtree: serde_yaml::Value =
+        serde_yaml::from_str("chain_id: 1\nhost_ip: 127.0.0.1\n").unwrap();
+
+    assert_eq!(
+        display_config_value(&"http://127.0.0.1:50051".into(), false).unwrap(),
+        "http://127.0.0.1:50051"
+    );
+    assert_eq!(
+        display_config_value(&subtree, false).unwrap(),
+        "chain_id: 1\nhost_ip: 127.0.0.1"
+    );
+    assert_eq!(
+        display_config_value(&subtree, true).unwrap(),
+        "{\n  \"chain_id\": 1,\n  \"host_ip\": \"127.0.0.1\"\n}"
+    );
+}
@@ -0,0 +1,139 @@
+use crate::cli_error::L1XConfigError;
+use l1x_common::{toolkit_config::ToolkitConfig, types::AddressStr};
+
+use std::io::{BufRead, IsTerminal, Write};
+
+#[derive(Debug, thiserror::Error)]
+pub enum L1XConfirmError {
+    #[error(
+        "Network '{network}' is not a development network and stdin is not a terminal to confirm on, pass --yes to submit without confirmation"
+    )]
+    NonInteractive { network: String },
+    #[error("Cancelled, nothing was submitted to network '{network}'")]
+    Declined { network: String },
+    #[error("Unable to read the confirmation: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Where a submission is confirmed, the terminal outside of tests
+pub trait L1XPrompt {
+    /// Whether someone can answer, i.e. stdin is a terminal
+    fn is_interactive(&self) -> bool;
+
+    /// Show `summary`, ask `question` and return the answer
+    fn ask(&mut self, summary: &str, question: &str)
+        -> std::io::Result<String>;
+}
+
+/// [`L1XPrompt`] of stdin, writing to stderr
+#[derive(Debug, Default)]
+pub struct L1XTerminalPrompt;
+
+impl L1XPrompt for L1XTerminalPrompt {
+    fn is_interactive(&self) -> bool {
+        std::io::stdin().is_terminal()
+    }
+
+    fn ask(
+        &mut self,
+        summary: &str,
+        question: &str,
+    ) -> std::io::Result<String> {
+        eprintln!("{}", summary);
+        eprint!("{}", question);
+        std::io::stderr().flush()?;
+
+        let mut line = String::new();
+        std::io::stdin().lock().read_line(&mut line)?;
+        Ok(line)
+    }
+}
+
+/// What a command is about to submit, confirmed before submitting to a
+/// network that is not marked `is_dev`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L1XSubmitSummary {
+    pub network: String,
+    pub endpoint: String,
+    pub owner: AddressStr,
+    pub fee_limit: String,
+    /// Artifact or manifest installed, none for transfers and staking
+    pub artifact: Option<String>,
+}
+
+impl L1XSubmitSummary {
+    /// Summary of `owner` submitting to the active network of `toolkit`
+    pub fn of_toolkit(
+        toolkit: &ToolkitConfig,
+        owner: AddressStr,
+        fee_limit: impl std::fmt::Display,
+        artifact: Option<String>,
+    ) -> Result<Self, L1XConfigError> {
+        Ok(L1XSubmitSummary {
+            network: toolkit.active_network()?,
+            endpoint: toolkit.active_chain_json_rpc_endpoint()?,
+            owner,
+            fee_limit: fee_limit.to_string(),
+            artifact,
+        })
+    }
+}
+
+impl std::fmt::Display for L1XSubmitSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Submitting to network '{}', which is not marked is_dev\n  endpoint:  {}\n  owner:     {}\n  fee limit: {}",
+            self.network, self.endpoint, self.owner, self.fee_limit
+        )?;
+        if let Some(artifact) = &self.artifact {
+            write!(f, "\n  artifact:  {}", artifact)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether submitting to the active network of `toolkit` needs a
+/// confirmation: it is not marked `is_dev` and `--yes` is not given
+pub fn needs_confirmation(
+    toolkit: &ToolkitConfig,
+    yes: bool,
+) -> Result<bool, L1XConfigError> {
+    Ok(!yes && !toolkit.active_network_is_dev()?)
+}
+
+/// Ask `prompt` to confirm the submission of `summary`, failing without
+/// asking when the prompt is not interactive
+pub fn confirm_submission(
+    summary: &L1XSubmitSummary,
+    prompt: &mut dyn L1XPrompt,
+) -> Result<(), L1XConfirmError> {
+    if !prompt.is_interactive() {
+        return Err(L1XConfirmError::NonInteractive {
+            network: summary.network.clone(),
+        });
+    }
+
+    let answer = prompt.ask(&summary.to_string(), "Continue? [y/N] ")?;
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Ok(()),
+        _ => {
+            Err(L1XConfirmError::Declined { network: summary.network.clone() })
+        }
+    }
+}
+
+/// Confirm on `prompt` the submission of `summary` when the active network
+/// of `toolkit` [`needs_confirmation`]
+pub fn confirm_network(
+    toolkit: &ToolkitConfig,
+    yes: bool,
+    summary: impl FnOnce() -> Result<L1XSubmitSummary, L1XConfigError>,
+    prompt: &mut dyn L1XPrompt,
+) -> anyhow::Result<()> {
+    if needs_confirmation(toolkit, yes)? {
+        confirm_submission(&summary()?, prompt)?;
+    }
+
+    Ok(())
+}
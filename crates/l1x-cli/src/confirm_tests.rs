@@ -0,0 +1,170 @@
+use crate::cli_error::L1XConfigError;
+use crate::confirm::*;
+use crate::contract_install_test::toolkit_for;
+use l1x_common::toolkit_config::ToolkitConfig;
+
+use std::fs;
+
+/// Simulated prompt answering `answer`, recording the summaries it showed
+struct ScriptedPrompt {
+    interactive: bool,
+    answer: &'static str,
+    shown: Vec<String>,
+}
+
+impl ScriptedPrompt {
+    fn new(interactive: bool, answer: &'static str) -> Self {
+        ScriptedPrompt { interactive, answer, shown: Vec::new() }
+    }
+}
+
+impl L1XPrompt for ScriptedPrompt {
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    fn ask(
+        &mut self,
+        summary: &str,
+        _question: &str,
+    ) -> std::io::Result<String> {
+        self.shown.push(summary.to_string());
+        Ok(format!("{}\n", self.answer))
+    }
+}
+
+/// Workspace with the dev network `local` and the network `testnet`, on
+/// `chain_type`
+fn toolkit_on(chain_type: &str) -> (tempfile::TempDir, ToolkitConfig) {
+    let ws_home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    fs::write(
+        ws_home.path().join("l1x-conf/l1x_chain_config.yaml"),
+        r#"
+networks:
+  local:
+    chain_id: 1
+    host_ip: "127.0.0.1"
+    rpc_port: 50051
+    is_dev: true
+  testnet:
+    chain_id: 1076
+    host_ip: "10.0.0.1"
+    rpc_port: 50052
+"#,
+    )
+    .unwrap();
+
+    let toolkit = toolkit_for(ws_home.path()).with_chain_type(chain_type);
+    (ws_home, toolkit)
+}
+
+fn summary_of(
+    toolkit: &ToolkitConfig,
+) -> impl FnOnce() -> Result<L1XSubmitSummary, L1XConfigError> + '_ {
+    move || {
+        L1XSubmitSummary::of_toolkit(
+            toolkit,
+            "11".repeat(20).parse().unwrap(),
+            100,
+            Some("ft".to_string()),
+        )
+    }
+}
+
+#[test]
+fn test_dev_network_is_not_confirmed() {
+    // Arrange
+    let (_ws_home, toolkit) = toolkit_on("local");
+    let mut prompt = ScriptedPrompt::new(false, "");
+
+    // Act
+    let confirmed =
+        confirm_network(&toolkit, false, summary_of(&toolkit), &mut prompt);
+
+    // Assert
+    assert!(confirmed.is_ok());
+    assert!(prompt.shown.is_empty());
+}
+
+#[test]
+fn test_non_dev_network_is_confirmed_with_a_summary() {
+    // Arrange
+    let (_ws_home, toolkit) = toolkit_on("testnet");
+    let mut prompt = ScriptedPrompt::new(true, "y");
+
+    // Act
+    let confirmed =
+        confirm_network(&toolkit, false, summary_of(&toolkit), &mut prompt);
+
+    // Assert
+    assert!(confirmed.is_ok());
+    assert_eq!(prompt.shown.len(), 1);
+    for expected in [
+        "network 'testnet'",
+        "http://10.0.0.1:50052",
+        "0x1111111111111111111111111111111111111111",
+        "fee limit: 100",
+        "artifact:  ft",
+    ] {
+        assert!(prompt.shown[0].contains(expected), "{}", prompt.shown[0]);
+    }
+}
+
+#[test]
+fn test_declined_confirmation_fails() {
+    let (_ws_home, toolkit) = toolkit_on("testnet");
+    let mut prompt = ScriptedPrompt::new(true, "n");
+
+    let confirmed =
+        confirm_network(&toolkit, false, summary_of(&toolkit), &mut prompt);
+
+    assert!(matches!(
+        confirmed.unwrap_err().downcast_ref::<L1XConfirmError>(),
+        Some(L1XConfirmError::Declined { network }) if network == "testnet"
+    ));
+}
+
+#[test]
+fn test_non_tty_without_yes_aborts_without_asking() {
+    // Arrange
+    let (_ws_home, toolkit) = toolkit_on("testnet");
+    let mut prompt = ScriptedPrompt::new(false, "y");
+
+    // Act
+    let confirmed =
+        confirm_network(&toolkit, false, summary_of(&toolkit), &mut prompt);
+
+    // Assert
+    let err_code = confirmed.unwrap_err();
+    assert!(matches!(
+        err_code.downcast_ref::<L1XConfirmError>(),
+        Some(L1XConfirmError::NonInteractive { .. })
+    ));
+    assert!(err_code.to_string().contains("pass --yes"));
+    assert!(prompt.shown.is_empty());
+}
+
+#[test]
+fn test_yes_skips_the_confirmation() {
+    let (_ws_home, toolkit) = toolkit_on("testnet");
+    let mut prompt = ScriptedPrompt::new(false, "");
+
+    let confirmed =
+        confirm_network(&toolkit, true, summary_of(&toolkit), &mut prompt);
+
+    assert!(confirmed.is_ok());
+    assert!(prompt.shown.is_empty());
+}
+
+#[test]
+fn test_unknown_network_fails_before_confirming() {
+    let (_ws_home, toolkit) = toolkit_on("mainnet");
+
+    let needed = needs_confirmation(&toolkit, false);
+
+    assert!(needed
+        .unwrap_err()
+        .to_string()
+        .starts_with("chain type 'mainnet' not found"));
+}
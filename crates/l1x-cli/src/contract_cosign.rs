@@ -0,0 +1,434 @@
+use crate::cli_error::L1XConfigError;
+use crate::forge_client::{ForgeClient, ForgeClientConfig};
+use l1x_common::{
+    read_payload, toolkit_config::ToolkitConfig, types, PayloadSource,
+    SigningScheme, TxnBuildError,
+};
+use l1x_rpc::{
+    json as l1x_rpc_json,
+    rpc_model::{
+        submit_transaction_request::TransactionType, SubmitTransactionRequest,
+        SubmitTransactionResponse,
+    },
+};
+
+use anyhow::Result;
+use reqwest::Client;
+use secp256k1::Secp256k1;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+
+#[derive(Debug, thiserror::Error)]
+pub enum L1XTxnEnvelopeError {
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("Signing error: {0}")]
+    SigningError(String),
+    #[error("Duplicate signer: {0}")]
+    DuplicateSigner(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Threshold not met: {0}")]
+    ThresholdNotMet(String),
+    #[error("Envelope file error: {0}")]
+    FileError(String),
+}
+
+/// One signer's approval of an envelope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L1XTxnEnvelopeSignature {
+    /// Hex encoded compressed verifying key
+    pub verifying_key: String,
+    /// Hex encoded compact signature
+    pub signature: String,
+}
+
+/// A transaction collecting signatures before it is submitted.
+///
+/// The first signature belongs to the primary signer, it is the one the chain
+/// checks. The remaining ones are forwarded as metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L1XTxnEnvelope {
+    pub payload: types::Transaction,
+    pub fee_limit: u128,
+    pub nonce: u128,
+    pub threshold: usize,
+    pub signatures: Vec<L1XTxnEnvelopeSignature>,
+}
+
+impl L1XTxnEnvelope {
+    pub fn new(
+        payload: types::Transaction,
+        fee_limit: u128,
+        nonce: u128,
+        threshold: usize,
+    ) -> Self {
+        Self { payload, fee_limit, nonce, threshold, signatures: Vec::new() }
+    }
+
+    pub fn load(path: &str) -> Result<Self, L1XTxnEnvelopeError> {
+        let content = fs::read_to_string(path).map_err(|err_code| {
+            L1XTxnEnvelopeError::FileError(format!(
+                "Unable to read {}: {}",
+                path, err_code
+            ))
+        })?;
+
+        serde_json::from_str(&content).map_err(|err_code| {
+            L1XTxnEnvelopeError::FileError(format!(
+                "Unable to parse {}: {}",
+                path, err_code
+            ))
+        })
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), L1XTxnEnvelopeError> {
+        let content =
+            serde_json::to_string_pretty(self).map_err(|err_code| {
+                L1XTxnEnvelopeError::FileError(format!(
+                    "Unable to serialize envelope: {}",
+                    err_code
+                ))
+            })?;
+
+        fs::write(path, content).map_err(|err_code| {
+            L1XTxnEnvelopeError::FileError(format!(
+                "Unable to write {}: {}",
+                path, err_code
+            ))
+        })
+    }
+
+    /// True once enough signatures were collected to submit the envelope
+    pub fn is_complete(&self) -> bool {
+        self.signatures.len() >= self.threshold
+    }
+
+    fn txn_type(&self) -> Result<TransactionType, L1XTxnEnvelopeError> {
+        self.payload.clone().try_into().map_err(|err_code| {
+            L1XTxnEnvelopeError::SigningError(format!(
+                "Unable to convert the payload: {:#?}",
+                err_code
+            ))
+        })
+    }
+
+    /// Append the signature of `private_key`
+    pub fn sign(
+        &mut self,
+        private_key: &str,
+    ) -> Result<&L1XTxnEnvelopeSignature, L1XTxnEnvelopeError> {
+        let secret_key =
+            l1x_common::parse_secret_key(private_key).map_err(|err_code| {
+                L1XTxnEnvelopeError::InvalidPrivateKey(match err_code {
+                    TxnBuildError::InvalidPrivateKey(reason) => reason,
+                    err_code => err_code.to_string(),
+                })
+            })?;
+
+        let verifying_key =
+            hex::encode(secret_key.public_key(&Secp256k1::new()).serialize());
+
+        if self.signatures.iter().any(|sig| sig.verifying_key == verifying_key)
+        {
+            return Err(L1XTxnEnvelopeError::DuplicateSigner(format!(
+                "{} already signed this envelope",
+                verifying_key
+            )));
+        }
+
+        let signature = l1x_common::sign_txn(
+            &secret_key,
+            &self.txn_type()?,
+            self.fee_limit,
+            self.nonce,
+            SigningScheme::Canonical,
+        )
+        .map_err(|err_code| {
+            L1XTxnEnvelopeError::SigningError(format!("{:#?}", err_code))
+        })?;
+
+        self.signatures.push(L1XTxnEnvelopeSignature {
+            verifying_key,
+            signature: hex::encode(signature),
+        });
+
+        Ok(self.signatures.last().expect("signature was just pushed"))
+    }
+
+    /// Check every collected signature against the envelope content
+    pub fn verify(&self) -> Result<(), L1XTxnEnvelopeError> {
+        let txn_type = self.txn_type()?;
+
+        for (index, sig) in self.signatures.iter().enumerate() {
+            if self.signatures[..index]
+                .iter()
+                .any(|prev| prev.verifying_key == sig.verifying_key)
+            {
+                return Err(L1XTxnEnvelopeError::DuplicateSigner(
+                    sig.verifying_key.clone(),
+                ));
+            }
+
+            let verifying_key = hex::decode(&sig.verifying_key);
+            let signature = hex::decode(&sig.signature);
+            let is_valid = match (verifying_key, signature) {
+                (Ok(verifying_key), Ok(signature)) => {
+                    l1x_common::verify_txn_signature(
+                        &verifying_key,
+                        &signature,
+                        &txn_type,
+                        self.fee_limit,
+                        self.nonce,
+                        SigningScheme::Canonical,
+                    )
+                    .unwrap_or(false)
+                }
+                _ => false,
+            };
+
+            if !is_valid {
+                return Err(L1XTxnEnvelopeError::InvalidSignature(
+                    sig.verifying_key.clone(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the request signed by the primary signer, along with the
+    /// co-signatures to forward as metadata
+    pub fn submit_request(
+        &self,
+    ) -> Result<
+        (SubmitTransactionRequest, Vec<L1XTxnEnvelopeSignature>),
+        L1XTxnEnvelopeError,
+    > {
+        if !self.is_complete() {
+            return Err(L1XTxnEnvelopeError::ThresholdNotMet(format!(
+                "{} of {} signatures collected",
+                self.signatures.len(),
+                self.threshold
+            )));
+        }
+        self.verify()?;
+
+        let (primary, cosignatures) =
+            self.signatures.split_first().ok_or_else(|| {
+                L1XTxnEnvelopeError::ThresholdNotMet(
+                    "The envelope has no signature".to_string(),
+                )
+            })?;
+
+        let request = SubmitTransactionRequest {
+            nonce: self.nonce.to_string(),
+            fee_limit: self.fee_limit.to_string(),
+            signature: hex::decode(&primary.signature).map_err(|err_code| {
+                L1XTxnEnvelopeError::InvalidSignature(format!("{}", err_code))
+            })?,
+            verifying_key: hex::decode(&primary.verifying_key).map_err(
+                |err_code| {
+                    L1XTxnEnvelopeError::InvalidSignature(format!(
+                        "{}",
+                        err_code
+                    ))
+                },
+            )?,
+            transaction_type: Some(self.txn_type()?),
+        };
+
+        Ok((request, cosignatures.to_vec()))
+    }
+}
+
+fn print_envelope_status(envelope: &L1XTxnEnvelope, path: &str) {
+    println!(
+        "{}: {} of {} signatures{}",
+        path,
+        envelope.signatures.len(),
+        envelope.threshold,
+        if envelope.is_complete() { ", ready to send" } else { "" }
+    );
+}
+
+/// Create a transaction envelope, optionally signed by its primary signer
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "vm-sign-txn")]
+pub struct L1XVmSignTxnCmd {
+    /// Transaction payload JSON, `-` reading it from stdin
+    #[clap(long = "payload-file")]
+    payload_file: String,
+
+    /// Primary signer, leave unset to write an unsigned envelope
+    #[clap(long = "owner")]
+    owner: Option<String>,
+
+    /// Network of l1x_chain_config.yaml the nonce is fetched from, instead
+    /// of L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+
+    /// Defaults to the next nonce of the owner account
+    #[clap(long = "nonce")]
+    nonce: Option<u128>,
+
+    #[clap(long = "fee_limit", default_value_t = 100)]
+    fee_limit: u128,
+
+    #[clap(long = "threshold", default_value_t = 1)]
+    threshold: usize,
+
+    /// Allow writing an envelope that still needs co-signatures
+    #[clap(long = "partial", default_value_t = false)]
+    partial: bool,
+
+    #[clap(long = "out")]
+    out: String,
+}
+
+impl L1XVmSignTxnCmd {
+    pub async fn exec(&self) -> Result<()> {
+        tracing::debug!("L1X VM Sign Txn With Args :: {:#?}!", &self);
+
+        if self.threshold == 0 {
+            anyhow::bail!("--threshold must be at least 1");
+        }
+
+        let source = PayloadSource::from_arg(&self.payload_file);
+        let payload: types::Transaction =
+            serde_json::from_str(&read_payload(source.clone())?)?;
+        let payload = payload.relative_to(&source.base_dir());
+
+        let private_key = match &self.owner {
+            Some(owner) => Some((
+                owner,
+                ToolkitConfig::from_env()
+                    .and_then(|toolkit| toolkit.wallet_priv_key(owner))
+                    .map_err(L1XConfigError::from)?,
+            )),
+            None => None,
+        };
+
+        let nonce = match (self.nonce, &private_key) {
+            (Some(nonce), _) => nonce,
+            (None, Some((_, private_key))) => {
+                let end_point = ToolkitConfig::from_env()
+                    .and_then(|toolkit| {
+                        toolkit
+                            .with_network(self.network.as_deref())
+                            .active_chain_json_rpc_endpoint()
+                    })
+                    .map_err(L1XConfigError::from)?;
+                ForgeClient::new(ForgeClientConfig::new(
+                    &end_point,
+                    private_key,
+                ))?
+                .next_nonce()
+                .await?
+            }
+            (None, None) => {
+                anyhow::bail!("--nonce is required when --owner is not set")
+            }
+        };
+
+        let mut envelope =
+            L1XTxnEnvelope::new(payload, self.fee_limit, nonce, self.threshold);
+
+        if let Some((owner, private_key)) = &private_key {
+            envelope.sign(private_key)?;
+            tracing::info!("Envelope signed by primary signer {}", owner);
+        }
+
+        if !envelope.is_complete() && !self.partial {
+            anyhow::bail!(
+                "{} of {} signatures collected, pass --partial to write an envelope for co-signing",
+                envelope.signatures.len(),
+                envelope.threshold
+            );
+        }
+
+        envelope.save(&self.out)?;
+        print_envelope_status(&envelope, &self.out);
+
+        Ok(())
+    }
+}
+
+/// Add the owner's signature to a transaction envelope
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "vm-cosign")]
+pub struct L1XVmCosignCmd {
+    #[clap(long = "file")]
+    file: String,
+
+    #[clap(long = "owner")]
+    owner: String,
+}
+
+impl L1XVmCosignCmd {
+    pub async fn exec(&self) -> Result<()> {
+        tracing::debug!("L1X VM Cosign With Args :: {:#?}!", &self);
+
+        let mut envelope = L1XTxnEnvelope::load(&self.file)?;
+        // Refuse to add to an envelope somebody tampered with
+        envelope.verify()?;
+
+        let private_key = ToolkitConfig::from_env()
+            .and_then(|toolkit| toolkit.wallet_priv_key(&self.owner))
+            .map_err(L1XConfigError::from)?;
+        envelope.sign(&private_key)?;
+
+        envelope.save(&self.file)?;
+        print_envelope_status(&envelope, &self.file);
+
+        Ok(())
+    }
+}
+
+/// Submit a transaction envelope once its threshold is met
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "vm-send-raw")]
+pub struct L1XVmSendRawCmd {
+    #[clap(long = "file")]
+    file: String,
+
+    /// Network of l1x_chain_config.yaml to submit to, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+}
+
+impl L1XVmSendRawCmd {
+    pub async fn exec(&self) -> Result<()> {
+        tracing::debug!("L1X VM Send Raw With Args :: {:#?}!", &self);
+
+        let envelope = L1XTxnEnvelope::load(&self.file)?;
+        let (request, cosignatures) = envelope.submit_request()?;
+
+        let end_point = ToolkitConfig::from_env()
+            .and_then(|toolkit| {
+                toolkit
+                    .with_network(self.network.as_deref())
+                    .active_chain_json_rpc_endpoint()
+            })
+            .map_err(L1XConfigError::from)?;
+        let result = l1x_rpc_json::post_json_rpc(
+            Client::new().post(end_point),
+            "l1x_submitTransaction",
+            json!({
+                "request": serde_json::to_value(&request)?,
+                "metadata": { "cosignatures": cosignatures },
+            }),
+        )
+        .await?;
+
+        let response =
+            l1x_rpc_json::parse_response::<SubmitTransactionResponse>(result)?;
+
+        println!("Submitted {} :: {}", self.file, response.hash);
+
+        Ok(())
+    }
+}
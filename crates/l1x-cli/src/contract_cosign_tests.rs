@@ -0,0 +1,83 @@
+use crate::contract_cosign::*;
+use l1x_common::types::{Transaction, U8s};
+
+const FIRST_PRIVATE_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+const SECOND_PRIVATE_KEY: &str =
+    "a7d2b7b9f4d1c8e3065f2d9a4c3b6e1f8d7c9b0a1e2f3d4c5b6a7980e1f2a3b4";
+
+fn test_envelope(threshold: usize) -> L1XTxnEnvelope {
+    L1XTxnEnvelope::new(
+        Transaction::SmartContractFunctionCall {
+            contract_instance_address: U8s::Hex("00".repeat(20)),
+            function: U8s::Text("transfer".to_string()),
+            arguments: U8s::Text("{}".to_string()),
+        },
+        100,
+        7,
+        threshold,
+    )
+}
+
+#[test]
+fn test_independent_cosigns_produce_verifiable_envelope() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("env.json");
+    let path = path.to_str().unwrap();
+    test_envelope(2).save(path).unwrap();
+
+    // Act
+    for private_key in [FIRST_PRIVATE_KEY, SECOND_PRIVATE_KEY] {
+        let mut envelope = L1XTxnEnvelope::load(path).unwrap();
+        envelope.sign(private_key).unwrap();
+        envelope.save(path).unwrap();
+    }
+    let envelope = L1XTxnEnvelope::load(path).unwrap();
+
+    // Assert
+    assert!(envelope.is_complete());
+    assert!(envelope.verify().is_ok());
+    let (request, cosignatures) = envelope.submit_request().unwrap();
+    assert_eq!(
+        hex::encode(request.verifying_key),
+        envelope.signatures[0].verifying_key
+    );
+    assert_eq!(cosignatures, envelope.signatures[1..].to_vec());
+}
+
+#[test]
+fn test_cosign_rejects_duplicate_signer() {
+    let mut envelope = test_envelope(2);
+    envelope.sign(FIRST_PRIVATE_KEY).unwrap();
+
+    let result = envelope.sign(FIRST_PRIVATE_KEY);
+
+    assert!(matches!(result, Err(L1XTxnEnvelopeError::DuplicateSigner(_))));
+}
+
+#[test]
+fn test_submit_request_requires_threshold() {
+    let mut envelope = test_envelope(2);
+    envelope.sign(FIRST_PRIVATE_KEY).unwrap();
+
+    let result = envelope.submit_request();
+
+    assert!(matches!(result, Err(L1XTxnEnvelopeError::ThresholdNotMet(_))));
+}
+
+#[test]
+fn test_verify_detects_tampered_payload() {
+    // Arrange
+    let mut envelope = test_envelope(1);
+    envelope.sign(FIRST_PRIVATE_KEY).unwrap();
+
+    // Act
+    envelope.nonce += 1;
+
+    // Assert
+    assert!(matches!(
+        envelope.verify(),
+        Err(L1XTxnEnvelopeError::InvalidSignature(_))
+    ));
+}
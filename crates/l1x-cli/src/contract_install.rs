@@ -1,153 +1,393 @@
-use l1x_common::toolkit_config;
-use l1x_rpc::{
-    json as l1x_rpc_json,
-    rpc_model::{
-        GetEventsRequest, GetEventsResponse, SubmitTransactionRequest,
-        SubmitTransactionResponse,
+use crate::chain_check::check_active_chain_id;
+use crate::cli_error::L1XConfigError;
+use crate::confirm::{self, L1XSubmitSummary, L1XTerminalPrompt};
+use crate::evm_artifact::{L1XEvmArtifact, L1XEvmArtifactError};
+use crate::forge_client::{
+    Confirmation, DeployError, DeployResult, ForgeClient, ForgeClientConfig,
+    ForgeClientError,
+};
+use crate::install_manifest::{
+    L1XInstallManifest, L1XInstallManifestEntry, L1XManifestEntryOutcome,
+    L1XManifestEntryStatus,
+};
+use crate::signer::{signer_address, signer_private_key, L1XPrivateKeyArg};
+pub use l1x_common::toolkit_config::L1XVMType;
+use l1x_common::{
+    fee::{self, ChosenFee, L1XFeeLimit},
+    nonce_manager::{NonceManager, NonceManagers, NonceReservation},
+    rpc,
+    toolkit_config::{
+        self, L1XVMArtifactType, ToolkitConfig, ToolkitConfigError,
     },
+    transport::L1XProtocol,
+    types::{self, AddressParseError, AddressStr},
 };
 
 use anyhow::Result;
-use reqwest::{Client, RequestBuilder};
-use secp256k1::{Secp256k1, SecretKey};
-use serde_json::json;
+use secp256k1::hashes::{sha256, Hash};
+use serde::{Deserialize, Serialize};
 use std::{
-    env, error::Error, fmt::Display, fs::File, io::Read, process::Command,
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::{Duration, Instant},
 };
-use tokio::sync::RwLock;
-
-#[derive(Debug)]
-pub struct L1XVmContractInstallError(String);
+use tokio::{
+    sync::{RwLock, Semaphore},
+    task::JoinSet,
+};
+use tracing::Instrument;
+
+/// Kind of an install failure, the stable `error_kind` of `--output json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum L1XInstallErrorKind {
+    /// Workspace, network or wallet configuration
+    Config,
+    /// Command line flags or init arguments
+    InvalidArgs,
+    /// Contract artifact file
+    InvalidArtifact,
+    /// Transaction payload the node would reject
+    InvalidPayload,
+    /// Signing key or signature
+    Signing,
+    /// Request to the node or its response
+    Rpc,
+    /// Transaction not final in time
+    Finality,
+    /// Transaction failed in its block, out of fee or trapped
+    TxFailed,
+    /// Node response without the created address
+    MissingAddress,
+    /// Contract registry not updated after the transaction
+    Registry,
+    /// Any other failure
+    Install,
+}
 
-impl L1XVmContractInstallError {
-    pub fn new(message: String) -> Self {
-        L1XVmContractInstallError(message)
+/// Hint naming the input to fix when a transaction could not be built
+fn request_build_hint(err_code: &ForgeClientError) -> &'static str {
+    match err_code {
+        ForgeClientError::InvalidPrivateKey(_)
+        | ForgeClientError::SigningError(_) => {
+            " :: check the key of --owner or --private-key"
+        }
+        ForgeClientError::InvalidPayload(_) => {
+            " :: check the contract artifact and the init arguments"
+        }
+        _ => "",
     }
 }
 
-impl Display for L1XVmContractInstallError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.0)
-    }
+/// Failure of `vm-install-contract`, naming the install stage it stopped at
+#[derive(Debug, thiserror::Error)]
+pub enum L1XVmContractInstallError {
+    #[error("{0}")]
+    Config(#[from] L1XConfigError),
+    #[error("{0}")]
+    InvalidArgs(String),
+    #[error("{0}")]
+    InvalidArtifact(String),
+    #[error("{stage} Failed: Unable to read {} :: {source}", path.display())]
+    ArtifactIo {
+        stage: String,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{stage} Failed: {source}")]
+    NonceFetch {
+        stage: String,
+        #[source]
+        source: ForgeClientError,
+    },
+    #[error("{stage} Failed: {source}{}", request_build_hint(.source))]
+    RequestBuild {
+        stage: String,
+        #[source]
+        source: ForgeClientError,
+    },
+    #[error("{stage} Failed: {method} :: {source}")]
+    Rpc {
+        stage: String,
+        method: &'static str,
+        #[source]
+        source: ForgeClientError,
+    },
+    #[error("{stage} Failed: {source}")]
+    ResponseParse {
+        stage: String,
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
+    #[error("{stage} Failed: {source}")]
+    EventTimeout {
+        stage: String,
+        #[source]
+        source: ForgeClientError,
+    },
+    #[error("{stage} Failed: Txn {hash} failed on chain, registry not updated :: {message}")]
+    TxFailed { stage: String, hash: String, message: String },
+    #[error("L1X Contract Install Failed: Txn {hash} did not reach finality, registry not updated :: {source}")]
+    Finality {
+        hash: String,
+        #[source]
+        source: ForgeClientError,
+    },
+    #[error("{stage} Failed: the node reported no contract address for {hash}, the registry is unchanged")]
+    MissingAddress { stage: String, hash: String },
+    #[error("{stage} Failed: Txn {hash} succeeded but the contract registry was not updated :: {source}")]
+    RegistryUpdate {
+        stage: String,
+        hash: String,
+        #[source]
+        source: ToolkitConfigError,
+    },
+    #[error("Unable to dump the payload to {} :: {source}", path.display())]
+    PayloadDump {
+        path: PathBuf,
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
 }
 
-impl Error for L1XVmContractInstallError {}
+impl L1XVmContractInstallError {
+    /// `err_code` of submitting the transaction of the install `stage` with
+    /// the JSON-RPC `method`
+    fn from_forge_client(
+        stage: &str,
+        method: &'static str,
+        err_code: ForgeClientError,
+    ) -> Self {
+        let stage = stage.to_string();
+        match err_code {
+            ForgeClientError::InvalidPrivateKey(_)
+            | ForgeClientError::SigningError(_)
+            | ForgeClientError::InvalidPayload(_)
+            | ForgeClientError::RequestCreationError(_) => {
+                Self::RequestBuild { stage, source: err_code }
+            }
+            ForgeClientError::InValidNonceError(_) => {
+                Self::NonceFetch { stage, source: err_code }
+            }
+            ForgeClientError::JsonParseError(_) => {
+                Self::ResponseParse { stage, source: Box::new(err_code) }
+            }
+            ForgeClientError::EventsTimeout(_)
+            | ForgeClientError::ReceiptTimeout(_)
+            | ForgeClientError::FinalityTimeout(_) => {
+                Self::EventTimeout { stage, source: err_code }
+            }
+            ForgeClientError::TxFailed { hash, message } => {
+                Self::TxFailed { stage, hash, message }
+            }
+            ForgeClientError::PostJsonRpcError(_)
+            | ForgeClientError::ReqIdMismatch(_) => {
+                Self::Rpc { stage, method, source: err_code }
+            }
+        }
+    }
 
-#[derive(Debug)]
-struct L1XVmContractInstallInternal {
-    cfg_ws_home: String,
-    cfg_cli_scripts_base: String,
-    json_client: RequestBuilder,
-    private_key: String,
-    secret_key: SecretKey,
-}
+    /// `err_code` of submitting the transaction of the install `stage`
+    /// through [`ForgeClient::deploy_reserved`]
+    fn from_deploy(stage: &str, err_code: DeployError) -> Self {
+        match err_code {
+            DeployError::Sign(source) => Self::request_build(stage, source),
+            DeployError::Rpc { method, source } => {
+                Self::from_forge_client(stage, method, source)
+            }
+            DeployError::Finality { hash, source } => {
+                Self::Finality { hash, source }
+            }
+        }
+    }
 
-impl L1XVmContractInstallInternal {
-    fn new(install_cmd: &L1XVmInstallContractCmd) -> Self {
-        let cfg_ws_home = env::var("L1X_CFG_WS_HOME")
-            .expect("The L1X_CFG_WS_HOME environment variable must be set");
+    /// `err_code` of building the transaction of the install `stage`
+    fn request_build(stage: &str, err_code: ForgeClientError) -> Self {
+        Self::RequestBuild { stage: stage.to_string(), source: err_code }
+    }
 
-        let cfg_cli_scripts_base = env::var("L1X_CFG_CLI_SCRIPTS")
-            .expect("The L1X_CFG_CLI_SCRIPTS environment variable must be set");
+    /// `err_code` of reading the nonce before the install `stage`
+    fn nonce_fetch(stage: &str, err_code: ForgeClientError) -> Self {
+        Self::NonceFetch { stage: stage.to_string(), source: err_code }
+    }
 
-        let end_point = toolkit_config::get_active_chain_json_rpc_endpoint();
+    /// `err_code` of recording the transaction `hash` of the install `stage`
+    /// in the contract registry
+    fn registry_update(
+        stage: &str,
+        hash: &str,
+        err_code: ToolkitConfigError,
+    ) -> Self {
+        Self::RegistryUpdate {
+            stage: stage.to_string(),
+            hash: hash.to_string(),
+            source: err_code,
+        }
+    }
 
-        let json_client = Client::new().post(&end_point);
+    /// Address the transaction of the install `stage` created, an error when
+    /// the node reported none or not an address
+    pub(crate) fn created_address(
+        stage: &str,
+        result: &DeployResult,
+    ) -> Result<AddressStr, Self> {
+        let address = result
+            .address
+            .as_deref()
+            .map(str::trim)
+            .filter(|address| !address.is_empty() && *address != "null")
+            .ok_or_else(|| Self::MissingAddress {
+                stage: stage.to_string(),
+                hash: result.hash.clone(),
+            })?;
 
-        let private_key =
-            toolkit_config::get_wallet_priv_key(&install_cmd.owner);
+        address.parse().map_err(|err_code: AddressParseError| {
+            Self::ResponseParse {
+                stage: stage.to_string(),
+                source: Box::new(err_code),
+            }
+        })
+    }
 
-        let secret_key = SecretKey::from_slice(
-            &hex::decode(&private_key)
-            .map_err(|err_code| {
-                log::error!("L1X eBPF Deployment Failed: Unable to hex decode private_key :: {:#?} err :: {:#?}",
-                    &private_key, err_code
-                );
-                err_code
-            }).unwrap()
-        )
-        .map_err(|err_code| {
-            log::error!("L1X eBPF Deployment Failed: Failed to parse provided private_key :: {:#?} err :: {:#?}",
-                &private_key, err_code
-            )
-        }).unwrap();
+    /// Stable kind of the failure, the `error_kind` of `--output json`
+    pub fn kind(&self) -> L1XInstallErrorKind {
+        match self {
+            Self::Config(_) => L1XInstallErrorKind::Config,
+            Self::InvalidArgs(_) => L1XInstallErrorKind::InvalidArgs,
+            Self::InvalidArtifact(_) | Self::ArtifactIo { .. } => {
+                L1XInstallErrorKind::InvalidArtifact
+            }
+            Self::RequestBuild { source, .. } => match source {
+                ForgeClientError::InvalidPrivateKey(_)
+                | ForgeClientError::SigningError(_) => {
+                    L1XInstallErrorKind::Signing
+                }
+                _ => L1XInstallErrorKind::InvalidPayload,
+            },
+            Self::NonceFetch { .. }
+            | Self::Rpc { .. }
+            | Self::ResponseParse { .. }
+            | Self::EventTimeout { .. } => L1XInstallErrorKind::Rpc,
+            Self::Finality { .. } => L1XInstallErrorKind::Finality,
+            Self::TxFailed { .. } => L1XInstallErrorKind::TxFailed,
+            Self::MissingAddress { .. } => L1XInstallErrorKind::MissingAddress,
+            Self::RegistryUpdate { .. } => L1XInstallErrorKind::Registry,
+            Self::PayloadDump { .. } => L1XInstallErrorKind::Install,
+        }
+    }
 
-        Self {
-            cfg_ws_home,
-            cfg_cli_scripts_base,
-            json_client,
-            private_key,
-            secret_key,
+    /// Process exit code of the failure: 2 for configuration, flags or keys
+    /// to fix, 3 for the artifact, 4 for the node, 5 for local files the
+    /// install could not write
+    pub fn exit_code(&self) -> i32 {
+        match self.kind() {
+            L1XInstallErrorKind::Config
+            | L1XInstallErrorKind::InvalidArgs
+            | L1XInstallErrorKind::Signing => 2,
+            L1XInstallErrorKind::InvalidArtifact
+            | L1XInstallErrorKind::InvalidPayload => 3,
+            L1XInstallErrorKind::Rpc
+            | L1XInstallErrorKind::Finality
+            | L1XInstallErrorKind::TxFailed
+            | L1XInstallErrorKind::MissingAddress => 4,
+            L1XInstallErrorKind::Registry | L1XInstallErrorKind::Install => 5,
         }
     }
+}
 
-    async fn submit_transaction(
-        &self,
-        install_cmd: &L1XVmInstallContractCmd,
-        json_payload_file_path: &str,
-    ) -> Result<SubmitTransactionResponse, L1XVmContractInstallError> {
-        let nonce = l1x_rpc_json::get_nonce(
-            self.json_client.try_clone().expect(
-                "L1X Submit Transaction Failed: Unable to clone RequestBuilder",
-            ),
-            &self.secret_key,
-        )
-        .await
-        .map_err(|err_code| {
-            L1XVmContractInstallError::new(format!(
-                "L1X Submit Transaction Failed: Unable to get nounce {:#?}",
-                err_code
-            ))
-        })?;
+/// Whether the node created the `predicted` address, warning when it created
+/// `created` instead: another transaction of the account took the nonce the
+/// prediction was made with. Without a prediction there is nothing to check.
+pub(crate) fn check_predicted_address(
+    stage: &str,
+    predicted: Option<AddressStr>,
+    created: &AddressStr,
+) -> bool {
+    match predicted {
+        Some(predicted) if predicted != *created => {
+            tracing::warn!(
+                "{} :: NODE CREATED {} BUT {} WAS PREDICTED :: another transaction of the account likely used the same nonce",
+                stage,
+                created,
+                predicted
+            );
+            false
+        }
+        _ => true,
+    }
+}
 
-        let request: SubmitTransactionRequest =
-            l1x_common::load_submit_txn_req(
-                json_payload_file_path,
-                &self.private_key,
-                install_cmd.fee_limit,
-                nonce + 1,
-            )
-            .map_err(|err_code| {
-                L1XVmContractInstallError::new(format!(
-                    "L1X Submit Transaction Failed: Unable to create SubmitTransactionRequest {:#?}",
-                    err_code
-                    ))
-            })?;
+#[derive(Debug)]
+struct L1XVmContractInstallInternal {
+    client: ForgeClient,
+    toolkit: ToolkitConfig,
+    /// `cluster_address` of the network, addresses are only predicted when
+    /// it is set
+    cluster_address: Option<AddressStr>,
+    /// Nonces of the signing account, shared by the installs of a manifest
+    nonces: Arc<NonceManager>,
+}
 
-        let request_json =
-            serde_json::to_value(&request).map_err(|err_code| {
-                L1XVmContractInstallError::new(format!(
-                        "L1X Submit Transaction Failed: Unable to serialize transaction to JSON {:#?}",
-                        err_code
-                        ))
-            })?;
+impl L1XVmContractInstallInternal {
+    async fn new(
+        install_cmd: &L1XVmInstallContractCmd,
+    ) -> Result<Self, L1XConfigError> {
+        let toolkit = ToolkitConfig::from_env()?
+            .with_network(install_cmd.network.as_deref());
+        let end_point = toolkit.active_chain_json_rpc_endpoint()?;
+        let protocol = match install_cmd.protocol {
+            Some(protocol) => protocol,
+            None => toolkit.active_protocol()?,
+        };
 
-        let result = l1x_rpc_json::post_json_rpc(
-            self.json_client.try_clone().expect(
-                "L1X Submit Transaction Failed: Unable to clone RequestBuilder",
+        let private_key = signer_private_key(
+            &toolkit,
+            install_cmd.owner.as_deref(),
+            install_cmd.private_key.as_ref(),
+        )?;
+
+        let client_config = ForgeClientConfig {
+            fee_limit: install_cmd.fee_limit.fallback(),
+            fee_estimation: install_cmd.fee_limit.estimation(
+                install_cmd.fee_buffer_pct,
+                install_cmd.fee_per_byte,
             ),
-            "l1x_submitTransaction",
-            json!({ "request": request_json }),
-        )
-        .await
-        .map_err(|err_code| {
-            L1XVmContractInstallError::new(format!(
-            "L1X Submit Transaction Failed: l1x_submitTransaction request failed {:#?}",
-            err_code
-            ))
+            req_id: install_cmd.req_id,
+            event_poll_interval: Duration::from_millis(
+                install_cmd.poll_interval,
+            ),
+            event_wait_timeout: Duration::from_secs(install_cmd.wait_timeout),
+            receipt_method: install_cmd.receipt_method.clone(),
+            protocol,
+            rpc_retry: rpc::RetryPolicy::new(
+                install_cmd.rpc_retries,
+                Duration::from_millis(install_cmd.rpc_retry_delay),
+            ),
+            ..ForgeClientConfig::new(&end_point, &private_key)
+        };
+
+        let client = ForgeClient::new(client_config).map_err(|err_code| {
+            L1XConfigError(format!("Invalid signing key: {}", err_code))
         })?;
 
-        let response =
-            l1x_rpc_json::parse_response::<SubmitTransactionResponse>(result)
-                .map_err(|err_code| {
-                L1XVmContractInstallError::new(format!(
-                        "L1X Submit Transaction Failed: Unable to parse the response {:#?}",
-                        err_code
-                    ))
-            })?;
+        // The chain id is only served over JSON-RPC
+        if !install_cmd.skip_chain_check && protocol.is_json() {
+            check_active_chain_id(&toolkit, &end_point).await?;
+        }
+
+        let cluster_address = toolkit.active_cluster_address()?;
+        if cluster_address.is_none() {
+            tracing::debug!(
+                "No cluster_address in l1x_chain_config.yaml, contract addresses are not predicted"
+            );
+        }
+
+        let nonces = install_cmd.nonces.of(&client.account_address().to_hex());
 
-        Ok(response)
+        Ok(Self { client, toolkit, cluster_address, nonces })
     }
 }
 
@@ -158,379 +398,541 @@ struct L1XVmContractInstaller {
 }
 
 impl L1XVmContractInstaller {
-    fn new(install_cmd: &L1XVmInstallContractCmd) -> Self {
-        let install_init = L1XVmContractInstallInternal::new(install_cmd);
+    async fn new(
+        install_cmd: &L1XVmInstallContractCmd,
+    ) -> Result<Self, L1XConfigError> {
+        let install_init =
+            L1XVmContractInstallInternal::new(install_cmd).await?;
         let internal_installer = Arc::new(RwLock::new(install_init));
-        L1XVmContractInstaller {
+        Ok(L1XVmContractInstaller {
             install_cmd: install_cmd.clone(),
             internal_installer,
+        })
+    }
+
+    /// The receipt check of each transaction unless --no-wait, and with
+    /// --wait-for-finality the wait until it is buried under the requested
+    /// number of blocks
+    fn confirmation(&self) -> Confirmation {
+        Confirmation {
+            receipt: !self.install_cmd.no_wait,
+            finality: self.install_cmd.wait_for_finality.map(|confirmations| {
+                (
+                    confirmations,
+                    Duration::from_secs(self.install_cmd.finality_timeout),
+                )
+            }),
         }
     }
 
-    pub async fn l1x_ebpf_init_contract(
+    /// Reserve the nonce of the next transaction of the installer account
+    async fn reserve_nonce<'a>(
+        stage: &str,
+        internal: &'a L1XVmContractInstallInternal,
+    ) -> Result<NonceReservation<'a>, L1XVmContractInstallError> {
+        internal.nonces.reserve(|| internal.client.next_nonce()).await.map_err(
+            |err_code| L1XVmContractInstallError::nonce_fetch(stage, err_code),
+        )
+    }
+
+    /// Nonce reserved for the next transaction of the installer account, and
+    /// the address `predict` derives from the account, cluster and nonce,
+    /// logged before the transaction of `stage` is submitted
+    async fn predict_address<'a>(
+        stage: &str,
+        internal: &'a L1XVmContractInstallInternal,
+        predict: impl FnOnce(&[u8; 20], &[u8; 20], u128) -> [u8; 20],
+    ) -> Result<
+        (NonceReservation<'a>, Option<AddressStr>),
+        L1XVmContractInstallError,
+    > {
+        let nonce = Self::reserve_nonce(stage, internal).await?;
+
+        let predicted = internal.cluster_address.map(|cluster_address| {
+            AddressStr::from(predict(
+                internal.client.account_address().as_bytes(),
+                cluster_address.as_bytes(),
+                nonce.nonce(),
+            ))
+        });
+        if let Some(predicted) = predicted {
+            tracing::info!(
+                "{} :: Nonce {} | Expected address :: {}",
+                stage,
+                nonce.nonce(),
+                predicted
+            );
+        }
+
+        Ok((nonce, predicted))
+    }
+
+    /// Submit `txn` of the install `stage` with the reserved `nonce` through
+    /// [`ForgeClient::deploy_reserved`], following it as far as
+    /// [`L1XVmContractInstaller::confirmation`] asks
+    async fn submit(
         &self,
-        deploy_address: &str,
-    ) -> Result<SubmitTransactionResponse, L1XVmContractInstallError> {
-        let self_internal = self.internal_installer.read().await;
+        stage: &str,
+        client: &ForgeClient,
+        txn: types::Transaction,
+        nonce: NonceReservation<'_>,
+    ) -> Result<DeployResult, L1XVmContractInstallError> {
+        client.deploy_reserved(txn, nonce, self.confirmation()).await.map_err(
+            |err_code| L1XVmContractInstallError::from_deploy(stage, err_code),
+        )
+    }
 
-        let json_payload_file_path = format!(
-            "{}/l1x-forge-cli/cli-uc-init-{}.json",
-            self_internal.cfg_cli_scripts_base, &self.install_cmd.contract_id
+    fn log_events(label: &str, id: &str, events: &[Vec<u8>]) {
+        tracing::info!(
+            "{} GetEventsResponse :: {:#?} | Num Events: {:#?}",
+            label,
+            id,
+            events.len()
         );
 
-        // Create a JSON payload using serde_json
-        let init_json_payload = json!({
-            "smart_contract_init": [
-                { "hex": format!("{}", deploy_address) },
-                { "text": "{}" }
-            ]
+        events.iter().enumerate().for_each(|(index, event_item)| {
+            tracing::info!(
+                "Evt[{:#?}] :: {:#?}",
+                index,
+                hex::encode(event_item)
+            );
         });
+    }
+
+    /// Sign the transaction of `stage` with `nonce` and print it as JSON
+    /// instead of submitting it, with the sha256 of `artifact` for a
+    /// deployment
+    async fn print_dry_run(
+        stage: &str,
+        internal: &L1XVmContractInstallInternal,
+        txn: types::Transaction,
+        nonce: u128,
+        artifact: Option<&[u8]>,
+    ) -> Result<(), L1XVmContractInstallError> {
+        let dry_run = internal
+            .client
+            .dry_run(
+                stage,
+                txn,
+                nonce,
+                artifact
+                    .map(|artifact| sha256::Hash::hash(artifact).to_string()),
+            )
+            .await
+            .map_err(|err_code| {
+                L1XVmContractInstallError::request_build(stage, err_code)
+            })?;
+        let dry_run_json =
+            serde_json::to_string_pretty(&dry_run).map_err(|err_code| {
+                L1XVmContractInstallError::request_build(
+                    stage,
+                    ForgeClientError::RequestCreationError(format!(
+                        "Unable to serialize the request :: {}",
+                        err_code
+                    )),
+                )
+            })?;
 
-        // Serialize the JSON payload to a string
-        let init_json_string = serde_json::to_string(&init_json_payload)
-            .expect("Failed to serialize JSON");
+        println!("{}", dry_run_json);
+        Ok(())
+    }
 
-        log::info!(
-            "eBPF Contract Init :: {:#?} | JSON Payload :: {:#?}",
-            &self.install_cmd.contract_id,
-            init_json_string
-        );
+    /// Print the deployment of `--artifact-id` when `deploy_address` is
+    /// `None`, then the initialization of `--contract-id`, signed but not
+    /// submitted. The initialization of a new deployment is only printed when
+    /// its address can be predicted.
+    async fn l1x_ebpf_dry_run(
+        &self,
+        deploy_address: Option<AddressStr>,
+        init_args: types::U8s,
+    ) -> Result<(), L1XVmContractInstallError> {
+        let self_internal = self.internal_installer.read().await;
+        let init_stage = self.install_cmd.stage("Contract Init");
+
+        let (nonce, deploy_address) = match deploy_address {
+            Some(deploy_address) => {
+                let nonce = self_internal.client.next_nonce().await.map_err(
+                    |err_code| {
+                        L1XVmContractInstallError::nonce_fetch(
+                            &init_stage,
+                            err_code,
+                        )
+                    },
+                )?;
+                (nonce, Some(deploy_address))
+            }
+            None => {
+                let stage = self.install_cmd.stage("Contract Deploy");
+                let (_, artifact_bytes) =
+                    self.install_cmd.read_artifact(&self_internal.toolkit)?;
+                let deploy_txn = self
+                    .install_cmd
+                    .object_deploy_txn(artifact_bytes.clone())?;
+                let (nonce, predicted) = Self::predict_address(
+                    &stage,
+                    &self_internal,
+                    l1x_common::contract_address,
+                )
+                .await?;
+
+                Self::print_dry_run(
+                    &stage,
+                    &self_internal,
+                    deploy_txn,
+                    nonce.nonce(),
+                    Some(&artifact_bytes),
+                )
+                .await?;
+                (nonce.nonce() + 1, predicted)
+            }
+        };
 
-        // Write the JSON payload to the specified file path
-        std::fs::write(&json_payload_file_path, init_json_string)
-            .unwrap_or_else(|_| {
-                panic!(
-                    "Failed to write JSON to file :: {:#?}",
-                    &json_payload_file_path
+        match deploy_address {
+            Some(deploy_address) => {
+                Self::print_dry_run(
+                    &init_stage,
+                    &self_internal,
+                    init_payload(&deploy_address, init_args),
+                    nonce,
+                    None,
                 )
-            });
+                .await
+            }
+            None => {
+                tracing::warn!(
+                    "{} :: not shown, the address of the deployment is unknown without a cluster_address",
+                    init_stage
+                );
+                Ok(())
+            }
+        }
+    }
 
-        let init_response = self_internal
-            .submit_transaction(&self.install_cmd, &json_payload_file_path)
-            .await?;
+    /// Print the deployment of the EVM artifact `--artifact-id`, signed but
+    /// not submitted
+    async fn l1x_evm_dry_run(&self) -> Result<(), L1XVmContractInstallError> {
+        let self_internal = self.internal_installer.read().await;
+        let stage = self.install_cmd.stage("Contract Deploy");
+
+        let (_, artifact_content) =
+            self.install_cmd.read_artifact(&self_internal.toolkit)?;
+        let deploy_txn = self.install_cmd.evm_deploy_txn(&artifact_content)?;
+        let nonce =
+            self_internal.client.next_nonce().await.map_err(|err_code| {
+                L1XVmContractInstallError::nonce_fetch(&stage, err_code)
+            })?;
 
-        log::info!(
-            "eBPF Contract Init :: {:#?} | Resp :: {:#?}",
-            &self.install_cmd.contract_id,
-            init_response
-        );
+        Self::print_dry_run(
+            &stage,
+            &self_internal,
+            deploy_txn,
+            nonce,
+            Some(&artifact_content),
+        )
+        .await
+    }
+
+    pub async fn l1x_ebpf_init_contract(
+        &self,
+        deploy_address: &AddressStr,
+        init_args: types::U8s,
+    ) -> Result<DeployResult, L1XVmContractInstallError> {
+        let self_internal = self.internal_installer.read().await;
+        let stage = self.install_cmd.stage("Contract Init");
+        let init_txn = init_payload(deploy_address, init_args);
 
-        log::info!(
-            "eBPF Contract Init :: {:#?} | Waiting for Event Data ...",
+        tracing::info!(
+            "{} :: {:#?} | Address :: {:#?} | Payload :: {}",
+            stage,
             &self.install_cmd.contract_id,
+            deploy_address,
+            serde_json::to_string(&init_txn).unwrap_or_default()
         );
+        self.install_cmd.dump_payload(
+            &format!("init-{}", self.install_cmd.contract_id),
+            &init_txn,
+        )?;
+
+        let (nonce, predicted) = Self::predict_address(
+            &stage,
+            &self_internal,
+            |account, cluster, nonce| {
+                l1x_common::contract_instance_address(
+                    account,
+                    deploy_address.as_bytes(),
+                    cluster,
+                    nonce,
+                )
+            },
+        )
+        .await?;
 
-        tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
-
-        let init_event_response = l1x_rpc_json::post_json_rpc(
-				self_internal.json_client.try_clone().expect(
-					"L1X Submit Transaction Failed: Unable to clone RequestBuilder",
-				),
-                "l1x_getEvents",
-                json!({"request": GetEventsRequest{tx_hash: init_response.hash.clone(), timestamp: 0u64}}),
-            )
-			.await
-            .map_err(|err_code| {
-				L1XVmContractInstallError::new(format!(
-				"L1X Submit Transaction Failed: l1x_submitTransaction request failed {:#?}",
-				err_code
-				))
-			})?;
-
-        let init_event_response = l1x_rpc_json::parse_response::<
-            GetEventsResponse,
-        >(init_event_response)
-        .map_err(|err_code| {
-            L1XVmContractInstallError::new(format!(
-				"L1X Submit Transaction Failed: Unable to parse the response {:#?}",
-				err_code
-			))
-        })?;
+        let init_result =
+            self.submit(&stage, &self_internal.client, init_txn, nonce).await?;
 
-        log::info!(
-            "eBPF Contract GetEventsResponse :: {:#?} | Num Events: {:#?}",
+        tracing::trace!(
+            "{} :: {:#?} | Resp :: {:#?}",
+            stage,
             &self.install_cmd.contract_id,
-            init_event_response.events_data.len()
+            init_result
         );
 
-        init_event_response.events_data.iter().enumerate().for_each(
-            |(index, event_item)| {
-                log::info!(
-                    "Evt[{:#?}] :: {:#?}",
-                    index,
-                    hex::encode(event_item)
-                );
-            },
+        Self::log_events(
+            &stage,
+            &self.install_cmd.contract_id,
+            &init_result.events,
         );
 
-        let _ = toolkit_config::update_toolkit_contract_address_registry(
-            toolkit_config::L1XVMContractAddressUpdateType::L1XEBPF_INIT {
-                artifact_id: self.install_cmd.artifact_id.clone(),
-                contract_id: self.install_cmd.contract_id.clone(),
-                response_hash: init_response.hash.clone(),
-                response_address: init_response
-                    .contract_address
-                    .clone()
-                    .unwrap_or_default(),
-            },
-        );
+        let instance_address =
+            L1XVmContractInstallError::created_address(&stage, &init_result)?;
+        check_predicted_address(&stage, predicted, &instance_address);
+
+        self_internal
+            .toolkit
+            .update_contract_address_registry(
+                self.install_cmd
+                    .init_update(init_result.hash.clone(), instance_address),
+            )
+            .map_err(|err_code| {
+                L1XVmContractInstallError::registry_update(
+                    &stage,
+                    &init_result.hash,
+                    err_code,
+                )
+            })?;
 
-        Ok(init_response)
+        Ok(init_result)
     }
 
     pub async fn l1x_ebpf_deploy_contract(
         &self,
-    ) -> Result<SubmitTransactionResponse, L1XVmContractInstallError> {
+    ) -> Result<DeployResult, L1XVmContractInstallError> {
         let self_internal = self.internal_installer.read().await;
+        let stage = self.install_cmd.stage("Contract Deploy");
 
-        let json_payload_file_path = format!(
-            "{}/l1x-forge-cli/cli-uc-deploy-{}.json",
-            self_internal.cfg_cli_scripts_base, &self.install_cmd.contract_id
-        );
-
-        // Create a JSON payload using serde_json
-        let deploy_json_payload = json!({
-            "smart_contract_deployment": [
-                "PRIVATE",
-                "L1XVM",
-                {
-                    "file": format!("{}/l1x-artifacts/{}", self_internal.cfg_ws_home, &self.install_cmd.artifact_id)
-                },
-                0,
-                {
-                    "text": "00000000000000000000000000000000"
-                }
-            ]
-        });
-
-        // Serialize the JSON payload to a string
-        let deploy_json_string = serde_json::to_string(&deploy_json_payload)
-            .expect("Failed to serialize JSON");
+        let (artifact_file, artifact_bytes) =
+            self.install_cmd.read_artifact(&self_internal.toolkit)?;
 
-        log::info!(
-            "eBPF Contract Deploy :: {:#?} | JSON Payload :: {:#?}",
+        tracing::info!(
+            "{} :: {:#?} | Artifact :: {:#?}",
+            stage,
             &self.install_cmd.artifact_id,
-            deploy_json_string
+            artifact_file
         );
+        let mut deploy_metadata =
+            self.install_cmd.deploy_metadata(&artifact_file, &artifact_bytes);
+
+        let deploy_txn = self.install_cmd.object_deploy_txn(artifact_bytes)?;
+        self.install_cmd.dump_payload(
+            &format!(
+                "{}-deploy-{}",
+                L1XVMArtifactType::from(self.install_cmd.vm_type).config_key(),
+                self.install_cmd.artifact_id
+            ),
+            &deploy_txn,
+        )?;
 
-        // Write the JSON payload to the specified file path
-        std::fs::write(&json_payload_file_path, deploy_json_string)
-            .unwrap_or_else(|_| {
-                panic!(
-                    "Failed to write JSON to file :: {:#?}",
-                    &json_payload_file_path
-                )
-            });
+        let (nonce, predicted) = Self::predict_address(
+            &stage,
+            &self_internal,
+            l1x_common::contract_address,
+        )
+        .await?;
 
-        let deploy_response = self_internal
-            .submit_transaction(&self.install_cmd, &json_payload_file_path)
+        let deploy_result = self
+            .submit(&stage, &self_internal.client, deploy_txn, nonce)
             .await?;
 
-        log::info!(
-            "eBPF Contract Deploy :: {:#?} | Resp :: {:#?}",
+        tracing::trace!(
+            "{} :: {:#?} | Resp :: {:#?}",
+            stage,
             &self.install_cmd.artifact_id,
-            deploy_response
+            deploy_result
         );
 
-        log::info!(
-            "eBPF Contract Deploy :: {:#?} | Waiting for Event Data ...",
+        Self::log_events(
+            &stage,
             &self.install_cmd.artifact_id,
+            &deploy_result.events,
         );
 
-        tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
-
-        let init_event_response = l1x_rpc_json::post_json_rpc(
-				self_internal.json_client.try_clone().expect(
-					"L1X Submit Transaction Failed: Unable to clone RequestBuilder",
-				),
-                "l1x_getEvents",
-                json!({"request": GetEventsRequest{tx_hash: deploy_response.hash.clone(), timestamp: 0u64}}),
+        deploy_metadata.fee_limit =
+            Some(deploy_result.fee.fee_limit.to_string());
+        deploy_metadata.fee_used =
+            deploy_result.fee_used.map(|fee_used| fee_used.to_string());
+
+        let deploy_address =
+            L1XVmContractInstallError::created_address(&stage, &deploy_result)?;
+        check_predicted_address(&stage, predicted, &deploy_address);
+
+        self_internal
+            .toolkit
+            .update_contract_address_registry_with_metadata(
+                self.install_cmd
+                    .deploy_update(deploy_result.hash.clone(), deploy_address),
+                deploy_metadata,
             )
-			.await
             .map_err(|err_code| {
-				L1XVmContractInstallError::new(format!(
-				"L1X Submit Transaction Failed: l1x_submitTransaction request failed {:#?}",
-				err_code
-				))
-			})?;
-
-        let init_event_response = l1x_rpc_json::parse_response::<
-            GetEventsResponse,
-        >(init_event_response)
-        .map_err(|err_code| {
-            L1XVmContractInstallError::new(format!(
-				"L1X Submit Transaction Failed: Unable to parse the response {:#?}",
-				err_code
-			))
-        })?;
-
-        log::info!(
-            "eBPF Contract Deploy GetEventsResponse :: {:#?} | Num Events: {:#?}",
-            &self.install_cmd.artifact_id,
-			init_event_response.events_data.len()
-        );
-
-        init_event_response.events_data.iter().enumerate().for_each(
-            |(index, event_item)| {
-                log::info!(
-                    "Evt[{:#?}] :: {:#?}",
-                    index,
-                    hex::encode(event_item)
-                );
-            },
-        );
-
-        let _ = toolkit_config::update_toolkit_contract_address_registry(
-            toolkit_config::L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
-                artifact_id: self.install_cmd.artifact_id.clone(),
-                response_hash: deploy_response.hash.clone(),
-                response_address: deploy_response
-                    .contract_address
-                    .clone()
-                    .unwrap_or_default(),
-            },
-        );
+                L1XVmContractInstallError::registry_update(
+                    &stage,
+                    &deploy_result.hash,
+                    err_code,
+                )
+            })?;
 
-        Ok(deploy_response)
+        Ok(deploy_result)
     }
 
     pub async fn l1x_evm_deploy_contract(
         &self,
-    ) -> Result<SubmitTransactionResponse, L1XVmContractInstallError> {
+    ) -> Result<DeployResult, L1XVmContractInstallError> {
         let self_internal = self.internal_installer.read().await;
 
-        let sol_file = format!(
-            "{}/l1x-evm-artifacts/{}",
-            self_internal.cfg_ws_home, &self.install_cmd.artifact_id
-        );
+        let (artifact_file, artifact_content) =
+            self.install_cmd.read_artifact(&self_internal.toolkit)?;
 
-        let mut file = File::open(sol_file).unwrap();
-        let mut hex_code = String::new();
-        file.read_to_string(&mut hex_code).unwrap();
-
-        let clean_hex_string =
-            if hex_code.starts_with("0x") { &hex_code[2..] } else { &hex_code };
-
-        let txn = l1x_common::types::Transaction::SmartContractDeployment(
-            l1x_common::types::AccessType::PUBLIC,
-            l1x_common::types::ContractType::EVM,
-            l1x_common::types::U8s::Hex(clean_hex_string.parse().map_err(
-                |err_code| {
-                    L1XVmContractInstallError::new(format!(
-						"EVM Contract Deploy Failed: Hex File Parse Error :: {:#?}",
-						err_code
-					))
-                },
-            )?),
-            0,
-            l1x_common::types::U8s::Text(format!(
-                "{:#?}",
-                self.install_cmd.salt.clone()
-            )),
-        );
+        let mut deploy_metadata =
+            self.install_cmd.deploy_metadata(&artifact_file, &artifact_content);
 
-        let nonce = l1x_rpc_json::get_nonce(
-            self_internal.json_client.try_clone().expect(
-                "EVM Contract Deploy Failed: Unable to clone RequestBuilder",
-            ),
-            &self_internal.secret_key,
-        )
-        .await
-        .map_err(|err_code| {
-            L1XVmContractInstallError::new(format!(
-                "EVM Contract Deploy Failed: Unable to get nounce {:#?}",
-                err_code
-            ))
-        })?;
+        let stage = self.install_cmd.stage("Contract Deploy");
+        let deploy_txn = self.install_cmd.evm_deploy_txn(&artifact_content)?;
+        self.install_cmd.dump_payload(
+            &format!("evm-deploy-{}", self.install_cmd.artifact_id),
+            &deploy_txn,
+        )?;
 
-        let request = l1x_common::get_submit_txn_req(
-			txn,
-			&self_internal.private_key,
-			self.install_cmd.fee_limit,
-			nonce + 1
-		)
-        .map_err(|err_code| {
-            L1XVmContractInstallError::new(format!(
-                "EVM Contract Deploy Failed: Unable to get_submit_txn_req :: {:#?}",
-                err_code
-            ))
-        })?;
+        let nonce = Self::reserve_nonce(&stage, &self_internal).await?;
+        let deploy_result = self
+            .submit(&stage, &self_internal.client, deploy_txn, nonce)
+            .await?;
 
-        let request_json =
-            serde_json::to_value(&request).map_err(|err_code| {
-                L1XVmContractInstallError::new(format!(
-					"EVM Contract Deploy Failed: Can serialize transaction to JSON :: {:#?}",
-					err_code
-				))
-            })?;
+        tracing::trace!(
+            "EVM Contract Deploy :: {:#?} | Resp :: {:#?}",
+            &self.install_cmd.artifact_id,
+            deploy_result
+        );
 
-        let result = l1x_rpc_json::post_json_rpc(
-            self_internal.json_client.try_clone().expect(
-                "EVM Contract Deploy Failed: Unable to clone RequestBuilder",
-            ),
-            "l1x_submitTransaction",
-            json!({ "request": request_json }),
-        )
-        .await
-        .map_err(|err_code| {
-            L1XVmContractInstallError::new(format!(
-                "EVM Contract Deploy Failed: Unable to post_json_rpc {:#?}",
-                err_code
-            ))
-        })?;
+        deploy_metadata.fee_limit =
+            Some(deploy_result.fee.fee_limit.to_string());
+        deploy_metadata.fee_used =
+            deploy_result.fee_used.map(|fee_used| fee_used.to_string());
+
+        let deploy_address =
+            L1XVmContractInstallError::created_address(&stage, &deploy_result)?;
+        self_internal
+            .toolkit
+            .update_contract_address_registry_with_metadata(
+                toolkit_config::L1XVMContractAddressUpdateType::L1XEVM_DEPLOY {
+                    artifact_id: self.install_cmd.artifact_id.clone(),
+                    response_hash: deploy_result.hash.clone(),
+                    response_address: deploy_address,
+                },
+                deploy_metadata,
+            )
+            .map_err(|err_code| {
+                L1XVmContractInstallError::registry_update(
+                    &stage,
+                    &deploy_result.hash,
+                    err_code,
+                )
+            })?;
 
-        log::info!("EVM Contract Deploy: Txn Respo {:#?}", &result);
+        Ok(deploy_result)
+    }
 
-        let deploy_response =
-            l1x_rpc_json::parse_response::<SubmitTransactionResponse>(result)
-                .map_err(|err_code| {
-                L1XVmContractInstallError::new(format!(
-					"EVM Contract Deploy Failed: Unable to parse the response {:#?}",
-					err_code
-				))
-            })?;
+    /// Submit the `--init-call` `init_call_txn` of a new EVM deployment
+    pub async fn l1x_evm_init_call(
+        &self,
+        init_call_txn: types::Transaction,
+    ) -> Result<DeployResult, L1XVmContractInstallError> {
+        let self_internal = self.internal_installer.read().await;
+        let stage = self.install_cmd.stage("Contract Init Call");
 
-        log::info!(
-            "EVM Contract Deploy :: {:#?} | Resp :: {:#?}",
+        tracing::info!(
+            "{} :: {:#?} | Payload :: {}",
+            stage,
             &self.install_cmd.artifact_id,
-            deploy_response
+            serde_json::to_string(&init_call_txn).unwrap_or_default()
         );
+        self.install_cmd.dump_payload(
+            &format!("evm-init-call-{}", self.install_cmd.artifact_id),
+            &init_call_txn,
+        )?;
+
+        let nonce = Self::reserve_nonce(&stage, &self_internal).await?;
+        let init_result = self
+            .submit(&stage, &self_internal.client, init_call_txn, nonce)
+            .await?;
 
-        let _ = toolkit_config::update_toolkit_contract_address_registry(
-            toolkit_config::L1XVMContractAddressUpdateType::L1XEVM_DEPLOY {
-                artifact_id: self.install_cmd.artifact_id.clone(),
-                response_hash: deploy_response.hash.clone(),
-                response_address: deploy_response
-                    .contract_address
-                    .clone()
-                    .unwrap_or_default(),
-            },
+        Self::log_events(
+            &stage,
+            &self.install_cmd.artifact_id,
+            &init_result.events,
         );
 
-        tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+        Ok(init_result)
+    }
+}
+
+/// Access type of a contract deployment
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum L1XAccessType {
+    Public,
+    Private,
+    Restricted,
+}
 
-        Ok(deploy_response)
+impl From<L1XAccessType> for types::AccessType {
+    fn from(access_type: L1XAccessType) -> Self {
+        match access_type {
+            L1XAccessType::Public => types::AccessType::PUBLIC,
+            L1XAccessType::Private => types::AccessType::PRIVATE,
+            L1XAccessType::Restricted => types::AccessType::RESTICTED,
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, clap::ValueEnum)]
-#[value(rename_all = "kebab-case")]
-pub enum L1XVMType {
-    #[clap(name = "ebpf")]
-    L1xVmEbpf,
-    #[clap(name = "evm")]
-    L1xVmEvm,
+/// Contract type of an eBPF or WASM deployment
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum L1XContractType {
+    L1xvm,
+    Xtalk,
 }
 
-impl std::fmt::Display for L1XVMType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::L1xVmEbpf => write!(f, "L1xVmEbpf"),
-            Self::L1xVmEvm => write!(f, "L1xVmEvm"),
+impl From<L1XContractType> for types::ContractType {
+    fn from(contract_type: L1XContractType) -> Self {
+        match contract_type {
+            L1XContractType::L1xvm => types::ContractType::L1XVM,
+            L1XContractType::Xtalk => types::ContractType::XTALK,
         }
     }
 }
 
+/// What an EVM install submits for the current registry state
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum L1XEvmInstallPlan {
+    /// The deployment is registered already, nothing to submit
+    Reuse {
+        deploy_address: AddressStr,
+    },
+    Deploy,
+}
+
+/// What an eBPF or WASM install submits for the current registry state
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum L1XEbpfInstallPlan {
+    /// The instance is registered already, nothing to submit
+    Skip {
+        instance_address: AddressStr,
+    },
+    /// Initialize a new instance of the registered deployment
+    Init {
+        deploy_address: AddressStr,
+    },
+    DeployAndInit,
+}
+
 /// Deploy and initialize the contract to l1x-vm
 #[derive(Clone, Debug, clap::Args)]
 #[clap(name = "vm-install-contract")]
@@ -541,109 +943,1327 @@ pub struct L1XVmInstallContractCmd {
     #[clap(long = "force", default_value_t = false)]
     force: bool,
 
-    #[clap(long = "contract-id")]
+    /// Initialize a new instance of the registered deployment, replacing the
+    /// registered instance
+    #[clap(
+        long = "reinit-only",
+        default_value_t = false,
+        conflicts_with = "force"
+    )]
+    reinit_only: bool,
+
+    #[clap(
+        long = "contract-id",
+        default_value = "",
+        required_unless_present = "manifest",
+        conflicts_with = "manifest"
+    )]
     contract_id: String,
 
-    #[clap(long = "artifact-id")]
+    #[clap(
+        long = "artifact-id",
+        default_value = "",
+        required_unless_present = "manifest",
+        conflicts_with = "manifest"
+    )]
     artifact_id: String,
 
+    /// Artifact file to deploy instead of `--artifact-id` in the artifact
+    /// directory of `--vm-type`, still registered as `--artifact-id`
+    #[clap(
+        long = "artifact-path",
+        value_name = "FILE",
+        conflicts_with = "manifest"
+    )]
+    artifact_path: Option<PathBuf>,
+
+    /// YAML manifest of the contract instances to install, each deployment
+    /// made once and every instance initialized with its own arguments
+    #[clap(
+        long = "manifest",
+        conflicts_with_all = ["reinit_only", "init_args", "init_args_file", "dry_run"]
+    )]
+    manifest: Option<PathBuf>,
+
+    /// Install up to N artifacts of the `--manifest` at once, their
+    /// transactions signed with consecutive nonces
+    #[clap(
+        long = "parallel",
+        value_name = "N",
+        default_value_t = 1,
+        requires = "manifest"
+    )]
+    parallel: usize,
+
+    /// Wallet signing the transactions, unless --private-key or
+    /// L1X_PRIVATE_KEY is given
     #[clap(long = "owner")]
-    owner: String,
+    owner: Option<String>,
+
+    /// Hex private key signing the transactions instead of the wallet
+    #[clap(long = "private-key")]
+    private_key: Option<L1XPrivateKeyArg>,
+
+    /// Network of l1x_chain_config.yaml to install on, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
 
     #[clap(long = "salt")]
     salt: String,
 
-    #[clap(long = "fee_limit", default_value_t = 100)]
-    fee_limit: u128,
+    /// Fee limit of each transaction, `auto` to estimate it
+    #[clap(long = "fee_limit", default_value = "100")]
+    fee_limit: L1XFeeLimit,
+
+    /// Percentage added to the estimated fee limit of --fee_limit auto
+    #[clap(long = "fee-buffer-pct", default_value_t = fee::DEFAULT_FEE_BUFFER_PCT)]
+    fee_buffer_pct: u32,
 
+    /// Fee per byte of a transaction under --fee_limit auto, when the node
+    /// has no fee estimate
+    #[clap(long = "fee-per-byte", default_value_t = fee::DEFAULT_FEE_PER_BYTE)]
+    fee_per_byte: f64,
+
+    /// JSON-RPC id of the first request, each following request carries
+    /// the next id
     #[clap(long = "req_id", default_value_t = 1)]
     req_id: u64,
+
+    /// Wait until each transaction is this many blocks deep before updating
+    /// the contract address registry
+    #[clap(long = "wait-for-finality", value_name = "BLOCKS")]
+    wait_for_finality: Option<u64>,
+
+    /// Trust the node accepting a transaction instead of checking its
+    /// receipt, the registry is updated even when it fails in its block
+    #[clap(
+        long = "no-wait",
+        default_value_t = false,
+        conflicts_with = "wait_for_finality"
+    )]
+    no_wait: bool,
+
+    /// JSON-RPC method returning the receipt of a transaction
+    #[clap(long = "receipt-method", default_value = rpc::DEFAULT_RECEIPT_METHOD)]
+    receipt_method: String,
+
+    /// Protocol to submit transactions with, the `protocol` of the network
+    /// otherwise
+    #[clap(long = "protocol", value_enum)]
+    protocol: Option<L1XProtocol>,
+
+    /// Seconds to wait for a transaction to reach finality
+    #[clap(long = "finality-timeout", default_value_t = 120)]
+    finality_timeout: u64,
+
+    /// Milliseconds between two queries of the events of a transaction
+    #[clap(long = "poll-interval", default_value_t = 500)]
+    poll_interval: u64,
+
+    /// Seconds to wait for the events of a transaction
+    #[clap(long = "wait-timeout", default_value_t = 60)]
+    wait_timeout: u64,
+
+    /// Retries of a JSON-RPC request failing on a timeout, a connection
+    /// error or a server error
+    #[clap(long = "rpc-retries", default_value_t = 3)]
+    rpc_retries: u32,
+
+    /// Milliseconds before the first retry of a JSON-RPC request, doubling
+    /// with every retry
+    #[clap(long = "rpc-retry-delay", default_value_t = 250)]
+    rpc_retry_delay: u64,
+
+    /// Submit without checking the chain id of the node against the network
+    #[clap(long = "skip-chain-check", default_value_t = false)]
+    skip_chain_check: bool,
+
+    /// Access type of the deployment, `private` for eBPF and WASM and
+    /// `public` for EVM when omitted
+    #[clap(long = "access-type", value_enum)]
+    access_type: Option<L1XAccessType>,
+
+    /// Contract type of an eBPF or WASM deployment, `l1xvm` when omitted
+    #[clap(long = "contract-type", value_enum)]
+    contract_type: Option<L1XContractType>,
+
+    /// JSON arguments of the contract initialization, `{}` when omitted
+    #[clap(long = "init-args", conflicts_with = "init_args_file")]
+    init_args: Option<String>,
+
+    /// File holding the arguments of the contract initialization
+    #[clap(long = "init-args-file")]
+    init_args_file: Option<PathBuf>,
+
+    /// Read the init arguments as hex encoded bytes rather than JSON
+    #[clap(long = "init-args-hex", default_value_t = false)]
+    init_args_hex: bool,
+
+    /// Constructor arguments of an EVM deployment, encoded with the
+    /// constructor ABI of the JSON artifact
+    #[clap(long = "constructor-args", num_args = 1.., allow_hyphen_values = true)]
+    constructor_args: Vec<String>,
+
+    /// Hex calldata, a 4 bytes selector followed by the ABI encoded
+    /// arguments, called on a new EVM deployment once it is deployed
+    #[clap(long = "init-call", value_name = "HEX")]
+    init_call: Option<String>,
+
+    /// Sign the transactions and print them as JSON instead of submitting
+    /// them, leaving the registry untouched
+    #[clap(long = "dry-run", default_value_t = false)]
+    dry_run: bool,
+
+    /// `json` prints the result, or the error, as a single JSON document on
+    /// stdout, logs stay on stderr
+    #[clap(
+        long = "output",
+        value_enum,
+        default_value_t = L1XOutputFormat::Plain,
+        conflicts_with = "dry_run"
+    )]
+    output: L1XOutputFormat,
+
+    /// Also write each submitted payload as JSON under
+    /// $L1X_CFG_CLI_SCRIPTS/l1x-forge-cli, for debugging
+    #[clap(long = "dump-payloads", default_value_t = false)]
+    dump_payloads: bool,
+
+    /// Submit to a network that is not marked is_dev without asking for
+    /// confirmation
+    #[clap(long = "yes", visible_alias = "confirm", default_value_t = false)]
+    yes: bool,
+
+    /// Nonces of the signing accounts, shared by the installs of a manifest
+    #[clap(skip)]
+    nonces: Arc<NonceManagers>,
 }
 
-impl L1XVmInstallContractCmd {
-    pub async fn exec(&self) -> Result<()> {
-        log::info!("L1X VM Contract Install With Args :: {:#?}!", &self);
+/// Format of the result of `vm-install-contract` on stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum L1XOutputFormat {
+    Plain,
+    Json,
+}
 
-        match self.vm_type {
-            L1XVMType::L1xVmEbpf => {
-                self.l1x_ebpf_install_contract().await?;
+/// Milliseconds each phase of an install took, `None` for a phase that
+/// submitted nothing
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct L1XInstallTimings {
+    pub deploy: Option<u64>,
+    pub init: Option<u64>,
+    pub total: u64,
+}
+
+/// Result of `vm-install-contract`, printed by `--output json`. The hashes
+/// are `None` for the phases the registry already covered.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct L1XInstallOutcome {
+    pub artifact_id: String,
+    pub contract_id: String,
+    pub network: Option<String>,
+    pub deploy_tx_hash: Option<String>,
+    pub deploy_address: Option<AddressStr>,
+    pub init_tx_hash: Option<String>,
+    pub instance_address: Option<AddressStr>,
+    /// Fee limit of the deployment transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deploy_fee: Option<ChosenFee>,
+    /// Fee limit of the initialization transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_fee: Option<ChosenFee>,
+    /// Fee the receipt of the deployment reports as consumed
+    pub deploy_fee_used: Option<u128>,
+    /// Fee the receipt of the initialization reports as consumed
+    pub init_fee_used: Option<u128>,
+    pub elapsed_ms: L1XInstallTimings,
+}
+
+/// Fee the transactions of one or more installs consumed, as their receipts
+/// report it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct L1XFeeUsedTotal {
+    pub fee_used: u128,
+    pub transactions: usize,
+    /// Transactions whose receipt reports no fee, left out of `fee_used`
+    pub unreported: usize,
+}
+
+impl L1XFeeUsedTotal {
+    fn add(&mut self, fee_used: Option<u128>) {
+        self.transactions += 1;
+        match fee_used {
+            Some(fee_used) => {
+                self.fee_used = self.fee_used.saturating_add(fee_used)
             }
-            L1XVMType::L1xVmEvm => {
-                self.l1x_evm_install_contract().await?;
+            None => self.unreported += 1,
+        }
+    }
+
+    /// Total of the transactions `outcomes` submitted, counting a deployment
+    /// the outcomes of a manifest share once
+    pub fn of_outcomes<'a>(
+        outcomes: impl IntoIterator<Item = &'a L1XInstallOutcome>,
+    ) -> Self {
+        let mut total = L1XFeeUsedTotal::default();
+        let mut deployments = Vec::new();
+        for outcome in outcomes {
+            if let Some(deploy_tx_hash) = &outcome.deploy_tx_hash {
+                if !deployments.contains(&deploy_tx_hash) {
+                    deployments.push(deploy_tx_hash);
+                    total.add(outcome.deploy_fee_used);
+                }
+            }
+            if outcome.init_tx_hash.is_some() {
+                total.add(outcome.init_fee_used);
             }
         }
+        total
+    }
+}
 
+impl std::fmt::Display for L1XFeeUsedTotal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Fee used :: {} over {} transaction(s)",
+            self.fee_used, self.transactions
+        )?;
+        if self.unreported > 0 {
+            write!(f, ", {} without a fee in its receipt", self.unreported)?;
+        }
         Ok(())
     }
 }
 
+/// Failure of `vm-install-contract`, printed by `--output json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct L1XInstallFailure {
+    pub error_kind: L1XInstallErrorKind,
+    pub message: String,
+}
+
+impl From<&L1XVmContractInstallError> for L1XInstallFailure {
+    fn from(err_code: &L1XVmContractInstallError) -> Self {
+        L1XInstallFailure {
+            error_kind: err_code.kind(),
+            message: err_code.to_string(),
+        }
+    }
+}
+
+/// Milliseconds since `started`
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
+
+/// First bytes of every WASM module
+const WASM_MAGIC: &[u8] = b"\0asm";
+
+/// Write `txn` as `cli-uc-{name}.json` under `dump_dir`, creating the
+/// directory. Returns the path of the written file.
+pub(crate) fn dump_payload(
+    dump_dir: &Path,
+    name: &str,
+    txn: &types::Transaction,
+) -> Result<PathBuf, L1XVmContractInstallError> {
+    let dump_path = dump_dir.join(format!("cli-uc-{}.json", name));
+    let dump_err = |err_code: Box<dyn Error + Send + Sync>| {
+        L1XVmContractInstallError::PayloadDump {
+            path: dump_path.clone(),
+            source: err_code,
+        }
+    };
+
+    std::fs::create_dir_all(dump_dir)
+        .map_err(|err_code| dump_err(err_code.into()))?;
+    let content = serde_json::to_string_pretty(txn)
+        .map_err(|err_code| dump_err(err_code.into()))?;
+    std::fs::write(&dump_path, content)
+        .map_err(|err_code| dump_err(err_code.into()))?;
+
+    Ok(dump_path)
+}
+
+/// `smart_contract_init` payload of the deployment `deploy_address`
+pub(crate) fn init_payload(
+    deploy_address: &AddressStr,
+    init_args: types::U8s,
+) -> types::Transaction {
+    types::Transaction::SmartContractInit((*deploy_address).into(), init_args)
+}
+
 impl L1XVmInstallContractCmd {
-    // Function to deploy and initialize a contract on ebpf VM
-    async fn l1x_ebpf_install_contract(
+    /// Deploy the eBPF artifact `artifact_id` and initialize it as
+    /// `contract_id`, with the command line defaults otherwise
+    pub fn ebpf(artifact_id: &str, contract_id: &str, owner: &str) -> Self {
+        use clap::Parser;
+
+        #[derive(clap::Parser)]
+        struct InstallCli {
+            #[clap(flatten)]
+            install: L1XVmInstallContractCmd,
+        }
+
+        InstallCli::parse_from([
+            "vm-install-contract",
+            "--vm-type=ebpf",
+            "--force",
+            &format!("--artifact-id={}", artifact_id),
+            &format!("--contract-id={}", contract_id),
+            &format!("--owner={}", owner),
+            // eBPF deployments always use the same salt
+            "--salt=",
+        ])
+        .install
+    }
+
+    /// With `--dump-payloads`, write `txn` as `cli-uc-{name}.json` under
+    /// `$L1X_CFG_CLI_SCRIPTS/l1x-forge-cli`. Without it nothing is written.
+    pub(crate) fn dump_payload(
         &self,
+        name: &str,
+        txn: &types::Transaction,
     ) -> Result<(), L1XVmContractInstallError> {
-        // Load install settings
-        let installer = L1XVmContractInstaller::new(self);
-        let artifact_deploy_status = if self.force == false {
-            toolkit_config::get_toolkit_ebpf_contract_address_for(
-                &self.artifact_id,
-                None,
+        if !self.dump_payloads {
+            return Ok(());
+        }
+
+        let scripts_dir = std::env::var(toolkit_config::CLI_SCRIPTS_ENV)
+            .ok()
+            .filter(|dir| !dir.trim().is_empty())
+            .ok_or_else(|| {
+                L1XVmContractInstallError::InvalidArgs(format!(
+                    "--dump-payloads needs {} to name the directory to write to",
+                    toolkit_config::CLI_SCRIPTS_ENV
+                ))
+            })?;
+        let dump_path = dump_payload(
+            &Path::new(&scripts_dir).join("l1x-forge-cli"),
+            name,
+            txn,
+        )?;
+        tracing::info!("Payload dumped to {}", dump_path.display());
+
+        Ok(())
+    }
+
+    /// Access and contract type of the deployment of `--vm-type`, rejecting
+    /// a `--contract-type` on EVM deployments
+    pub(crate) fn deployment_types(
+        &self,
+    ) -> Result<
+        (types::AccessType, types::ContractType),
+        L1XVmContractInstallError,
+    > {
+        match (self.vm_type, self.contract_type) {
+            (L1XVMType::L1xVmEbpf | L1XVMType::L1xVmWasm, contract_type) => Ok((
+                self.access_type
+                    .map_or(types::AccessType::PRIVATE, Into::into),
+                contract_type.map_or(types::ContractType::L1XVM, Into::into),
+            )),
+            (L1XVMType::L1xVmEvm, None) => Ok((
+                self.access_type.map_or(types::AccessType::PUBLIC, Into::into),
+                types::ContractType::EVM,
+            )),
+            (L1XVMType::L1xVmEvm, Some(_)) => {
+                Err(L1XVmContractInstallError::InvalidArgs(
+                    "--contract-type only applies to --vm-type ebpf and wasm, EVM contracts always deploy as EVM"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+
+    /// `err_code` of reading the artifact file `path`
+    fn artifact_io(
+        &self,
+        path: &Path,
+        err_code: std::io::Error,
+    ) -> L1XVmContractInstallError {
+        L1XVmContractInstallError::ArtifactIo {
+            stage: self.stage("Contract Deploy"),
+            path: path.to_path_buf(),
+            source: err_code,
+        }
+    }
+
+    /// Absolute path of `--artifact-path`, checked to be a readable file.
+    /// `None` without it.
+    pub(crate) fn artifact_source(
+        &self,
+    ) -> Result<Option<PathBuf>, L1XVmContractInstallError> {
+        let artifact_path = match &self.artifact_path {
+            Some(artifact_path) => artifact_path,
+            None => return Ok(None),
+        };
+
+        let artifact_file = std::fs::canonicalize(artifact_path)
+            .map_err(|err_code| self.artifact_io(artifact_path, err_code))?;
+        std::fs::File::open(&artifact_file)
+            .map_err(|err_code| self.artifact_io(&artifact_file, err_code))?;
+
+        Ok(Some(artifact_file))
+    }
+
+    /// Path and content of the `--artifact-path` file, or of the
+    /// `--artifact-id` file in the artifact directory of `--vm-type`
+    fn read_artifact(
+        &self,
+        toolkit: &ToolkitConfig,
+    ) -> Result<(PathBuf, Vec<u8>), L1XVmContractInstallError> {
+        let artifact_file = match self.artifact_source()? {
+            Some(artifact_file) => artifact_file,
+            None => toolkit
+                .artifact_dir(self.vm_type.into())
+                .join(&self.artifact_id),
+        };
+
+        let artifact_content = std::fs::read(&artifact_file)
+            .map_err(|err_code| self.artifact_io(&artifact_file, err_code))?;
+
+        Ok((artifact_file, artifact_content))
+    }
+
+    /// `stage` of the install, prefixed with the VM of `--vm-type`
+    pub(crate) fn stage(&self, stage: &str) -> String {
+        let vm = match self.vm_type {
+            L1XVMType::L1xVmEbpf => "eBPF",
+            L1XVMType::L1xVmEvm => "EVM",
+            L1XVMType::L1xVmWasm => "WASM",
+        };
+        format!("{} {}", vm, stage)
+    }
+
+    /// `smart_contract_deployment` payload of the eBPF or WASM object
+    /// `artifact_bytes`, rejecting a `--vm-type wasm` artifact that is not a
+    /// WASM module
+    pub(crate) fn object_deploy_txn(
+        &self,
+        artifact_bytes: Vec<u8>,
+    ) -> Result<types::Transaction, L1XVmContractInstallError> {
+        let stage = self.stage("Contract Deploy");
+        if self.vm_type == L1XVMType::L1xVmWasm
+            && !artifact_bytes.starts_with(WASM_MAGIC)
+        {
+            return Err(L1XVmContractInstallError::InvalidArtifact(format!(
+                "{} Failed: {} is not a WASM module",
+                stage, self.artifact_id
+            )));
+        }
+
+        let (access_type, contract_type) = self.deployment_types()?;
+        ForgeClient::ebpf_deploy_txn(artifact_bytes, access_type, contract_type)
+            .map_err(|err_code| {
+                L1XVmContractInstallError::request_build(&stage, err_code)
+            })
+    }
+
+    /// `smart_contract_deployment` payload of the EVM artifact
+    /// `artifact_content`, a raw hex bytecode or a Foundry or Hardhat JSON
+    /// artifact, followed by the encoded `--constructor-args`
+    pub(crate) fn evm_deploy_txn(
+        &self,
+        artifact_content: &[u8],
+    ) -> Result<types::Transaction, L1XVmContractInstallError> {
+        let invalid_artifact = |err_code: L1XEvmArtifactError| {
+            let message = format!(
+                "EVM Contract Deploy Failed: {} :: {}",
+                self.artifact_id, err_code
+            );
+            match err_code {
+                L1XEvmArtifactError::InvalidConstructorArgs(_) => {
+                    L1XVmContractInstallError::InvalidArgs(message)
+                }
+                _ => L1XVmContractInstallError::InvalidArtifact(message),
+            }
+        };
+        let artifact = std::str::from_utf8(artifact_content)
+            .map_err(|err_code| {
+                L1XVmContractInstallError::InvalidArtifact(format!(
+                    "EVM Contract Deploy Failed: {} is not text :: {}",
+                    self.artifact_id, err_code
+                ))
+            })
+            .and_then(|content| {
+                L1XEvmArtifact::parse(content).map_err(invalid_artifact)
+            })?;
+        let constructor_args = artifact
+            .encode_constructor_args(&self.constructor_args)
+            .map_err(invalid_artifact)?;
+
+        let (access_type, _) = self.deployment_types()?;
+        ForgeClient::evm_deploy_txn(
+            artifact.bytecode,
+            constructor_args,
+            &format!("{:#?}", self.salt.clone()),
+            access_type,
+        )
+        .map_err(|err_code| {
+            L1XVmContractInstallError::request_build(
+                "EVM Contract Deploy",
+                err_code,
             )
-        } else {
-            Err(format!(
-                "L1X eBPF Deployment Failed: Unknown Contract Deployment Address"
+        })
+    }
+
+    /// Registry update recording the deployment of `--artifact-id` in the
+    /// section of `--vm-type`
+    fn deploy_update(
+        &self,
+        response_hash: String,
+        response_address: AddressStr,
+    ) -> toolkit_config::L1XVMContractAddressUpdateType {
+        let artifact_id = self.artifact_id.clone();
+        match self.vm_type {
+            L1XVMType::L1xVmWasm => {
+                toolkit_config::L1XVMContractAddressUpdateType::L1XWASM_DEPLOY {
+                    artifact_id,
+                    response_hash,
+                    response_address,
+                }
+            }
+            _ => {
+                toolkit_config::L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
+                    artifact_id,
+                    response_hash,
+                    response_address,
+                }
+            }
+        }
+    }
+
+    /// Registry update recording the instance `--contract-id` in the section
+    /// of `--vm-type`
+    fn init_update(
+        &self,
+        response_hash: String,
+        response_address: AddressStr,
+    ) -> toolkit_config::L1XVMContractAddressUpdateType {
+        let artifact_id = self.artifact_id.clone();
+        let contract_id = self.contract_id.clone();
+        match self.vm_type {
+            L1XVMType::L1xVmWasm => {
+                toolkit_config::L1XVMContractAddressUpdateType::L1XWASM_INIT {
+                    artifact_id,
+                    contract_id,
+                    response_hash,
+                    response_address,
+                }
+            }
+            _ => toolkit_config::L1XVMContractAddressUpdateType::L1XEBPF_INIT {
+                artifact_id,
+                contract_id,
+                response_hash,
+                response_address,
+            },
+        }
+    }
+
+    /// Init arguments of `--init-args` or `--init-args-file`, JSON unless
+    /// `--init-args-hex` is given. `{}` when both are omitted or empty.
+    pub(crate) fn init_args(
+        &self,
+    ) -> Result<types::U8s, L1XVmContractInstallError> {
+        let (source, content) = match (&self.init_args, &self.init_args_file) {
+            (Some(init_args), _) => {
+                ("--init-args".to_string(), init_args.clone())
+            }
+            (None, Some(path)) => (
+                path.display().to_string(),
+                std::fs::read_to_string(path).map_err(|err_code| {
+                    L1XVmContractInstallError::InvalidArgs(format!(
+                        "Unable to read --init-args-file {} :: {}",
+                        path.display(),
+                        err_code
+                    ))
+                })?,
+            ),
+            (None, None) => (String::new(), String::new()),
+        };
+        let content = content.trim();
+        let invalid = |reason: String| {
+            L1XVmContractInstallError::InvalidArgs(format!(
+                "Invalid init arguments of {} :: {}",
+                source, reason
             ))
         };
 
-        let contract_deploy_address = match (self.force, artifact_deploy_status)
+        if content.is_empty() {
+            return Ok(types::U8s::Text("{}".to_string()));
+        }
+        if self.init_args_hex {
+            let hex_args = content
+                .strip_prefix("0x")
+                .or_else(|| content.strip_prefix("0X"))
+                .unwrap_or(content);
+            hex::decode(hex_args).map_err(|err_code| {
+                invalid(format!("not hex, {}", err_code))
+            })?;
+            return Ok(types::U8s::Hex(hex_args.to_lowercase()));
+        }
+
+        serde_json::from_str::<serde_json::Value>(content)
+            .map_err(|err_code| invalid(format!("not JSON, {}", err_code)))?;
+        Ok(types::U8s::Text(content.to_string()))
+    }
+
+    /// Registry metadata of deploying `artifact_content` read from
+    /// `artifact_file`, the owner only when its wallet signs and the path
+    /// only for an `--artifact-path`
+    pub(crate) fn deploy_metadata(
+        &self,
+        artifact_file: &Path,
+        artifact_content: &[u8],
+    ) -> toolkit_config::L1XVMDeployMetadata {
+        let wallet_signs = self.private_key.is_none()
+            && std::env::var(toolkit_config::PRIVATE_KEY_ENV)
+                .map_or(true, |key| key.trim().is_empty());
+
+        toolkit_config::L1XVMDeployMetadata {
+            owner: self.owner.clone().filter(|_| wallet_signs),
+            fee_limit: Some(self.fee_limit.fallback().to_string()),
+            artifact_sha256: Some(
+                sha256::Hash::hash(artifact_content).to_string(),
+            ),
+            artifact_path: self
+                .artifact_path
+                .as_ref()
+                .map(|_| artifact_file.display().to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub async fn exec(&self) -> Result<()> {
+        let span = tracing::info_span!(
+            "vm-install-contract",
+            artifact_id = %self.artifact_id,
+            contract_id = %self.contract_id,
+            req_id = self.req_id
+        );
+
+        async {
+            tracing::debug!(
+                "L1X VM Contract Install With Args :: {:#?}!",
+                &self
+            );
+
+            if !self.dry_run {
+                self.confirm_network()?;
+            }
+            if let Some(manifest_path) = &self.manifest {
+                return self.exec_manifest(manifest_path).await;
+            }
+
+            let installed = self.install().await;
+            if self.output == L1XOutputFormat::Json {
+                let document = match &installed {
+                    Ok(outcome) => serde_json::to_string_pretty(outcome)?,
+                    Err(err_code) => serde_json::to_string_pretty(
+                        &L1XInstallFailure::from(err_code),
+                    )?,
+                };
+                println!("{}", document);
+            }
+
+            let fee_used = L1XFeeUsedTotal::of_outcomes(&installed);
+            if self.output == L1XOutputFormat::Plain
+                && fee_used.transactions > 0
+            {
+                println!("{}", fee_used);
+            }
+
+            installed?;
+            Ok(())
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Confirm installing on a network that is not marked is_dev on the
+    /// terminal, unless --yes
+    fn confirm_network(&self) -> Result<()> {
+        let toolkit = ToolkitConfig::from_env()
+            .map_err(L1XConfigError::from)?
+            .with_network(self.network.as_deref());
+
+        let summary = || {
+            let owner = signer_address(
+                &toolkit,
+                self.owner.as_deref(),
+                self.private_key.as_ref(),
+            )?;
+            let artifact = match (&self.manifest, &self.artifact_path) {
+                (Some(manifest_path), _) => manifest_path.display().to_string(),
+                (None, Some(artifact_path)) => {
+                    artifact_path.display().to_string()
+                }
+                (None, None) => self.artifact_id.clone(),
+            };
+            L1XSubmitSummary::of_toolkit(
+                &toolkit,
+                owner,
+                self.fee_limit,
+                Some(artifact),
+            )
+        };
+
+        confirm::confirm_network(
+            &toolkit,
+            self.yes,
+            summary,
+            &mut L1XTerminalPrompt,
+        )
+    }
+
+    /// Install `--artifact-id` as `--contract-id`, timing the whole install
+    pub(crate) async fn install(
+        &self,
+    ) -> Result<L1XInstallOutcome, L1XVmContractInstallError> {
+        let started = Instant::now();
+
+        self.deployment_types()?;
+        self.artifact_source()?;
+        if !self.constructor_args.is_empty()
+            && self.vm_type != L1XVMType::L1xVmEvm
         {
-            (true, _) | (false, Err(_)) => {
-                let deploy_response =
+            return Err(L1XVmContractInstallError::InvalidArgs(
+                "--constructor-args only applies to --vm-type evm, initialize eBPF and WASM contracts with --init-args"
+                    .to_string(),
+            ));
+        }
+        if self.init_call.is_some() && self.vm_type != L1XVMType::L1xVmEvm {
+            return Err(L1XVmContractInstallError::InvalidArgs(
+                "--init-call only applies to --vm-type evm, initialize eBPF and WASM contracts with --init-args"
+                    .to_string(),
+            ));
+        }
+
+        let mut outcome = match self.vm_type {
+            L1XVMType::L1xVmEbpf | L1XVMType::L1xVmWasm => {
+                self.l1x_ebpf_install_contract().await?
+            }
+            L1XVMType::L1xVmEvm => self.l1x_evm_install_contract().await?,
+        };
+
+        outcome.elapsed_ms.total = elapsed_ms(started);
+        Ok(outcome)
+    }
+
+    /// Outcome of an install of `--artifact-id` on the network of `installer`
+    /// that has not submitted anything yet
+    async fn install_outcome(
+        &self,
+        installer: &L1XVmContractInstaller,
+    ) -> L1XInstallOutcome {
+        L1XInstallOutcome {
+            artifact_id: self.artifact_id.clone(),
+            contract_id: self.contract_id.clone(),
+            network: installer
+                .internal_installer
+                .read()
+                .await
+                .toolkit
+                .active_network()
+                .ok(),
+            ..Default::default()
+        }
+    }
+}
+
+impl L1XVmInstallContractCmd {
+    /// Install every entry of the manifest `manifest_path`, print the outcome
+    /// of each and fail when any entry failed
+    async fn exec_manifest(&self, manifest_path: &Path) -> Result<()> {
+        if self.vm_type == L1XVMType::L1xVmEvm {
+            anyhow::bail!(
+                "--manifest only applies to --vm-type ebpf and wasm, EVM contracts have no instances to initialize"
+            );
+        }
+        let manifest = L1XInstallManifest::load(manifest_path)?;
+
+        let outcomes = self.install_manifest(&manifest).await;
+        match self.output {
+            L1XOutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&outcomes)?)
+            }
+            L1XOutputFormat::Plain => {
+                outcomes.iter().for_each(|outcome| println!("{}", outcome))
+            }
+        }
+        let fee_used = L1XFeeUsedTotal::of_outcomes(
+            outcomes.iter().filter_map(|outcome| outcome.outcome.as_ref()),
+        );
+        match self.output {
+            L1XOutputFormat::Json => tracing::info!("{}", fee_used),
+            L1XOutputFormat::Plain => println!("{}", fee_used),
+        }
+
+        let failed = outcomes
+            .iter()
+            .filter(|outcome| outcome.status == L1XManifestEntryStatus::Failed)
+            .count();
+        if failed > 0 {
+            anyhow::bail!(
+                "{} of {} manifest entries failed",
+                failed,
+                outcomes.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// Deploy each artifact of `manifest` once, reusing its registered
+    /// deployment unless --force, then initialize every entry with its own
+    /// arguments, up to --parallel at once. A failing entry doesn't stop the
+    /// others, the entries of an artifact whose deployment failed fail with
+    /// it.
+    pub(crate) async fn install_manifest(
+        &self,
+        manifest: &L1XInstallManifest,
+    ) -> Vec<L1XManifestEntryOutcome> {
+        let in_flight = Arc::new(Semaphore::new(self.parallel.max(1)));
+
+        let mut deploying = JoinSet::new();
+        let mut artifact_ids = Vec::new();
+        for entry in &manifest.contracts {
+            if artifact_ids.contains(&entry.artifact_id) {
+                continue;
+            }
+            artifact_ids.push(entry.artifact_id.clone());
+
+            let permit = in_flight
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the in-flight semaphore is never closed");
+            let entry_cmd = self.manifest_entry_cmd(entry);
+
+            deploying.spawn(
+                async move {
+                    let deployment = entry_cmd
+                        .manifest_deployment()
+                        .await
+                        .map_err(|err_code| L1XInstallFailure::from(&err_code));
+                    drop(permit);
+                    (entry_cmd.artifact_id, deployment)
+                }
+                .in_current_span(),
+            );
+        }
+
+        let mut deployments = HashMap::new();
+        while let Some(deployed) = deploying.join_next().await {
+            match deployed {
+                Ok((artifact_id, deployment)) => {
+                    deployments.insert(artifact_id, deployment);
+                }
+                Err(err_code) => {
+                    tracing::warn!(
+                        "Manifest deploy task failed :: {}",
+                        err_code
+                    )
+                }
+            }
+        }
+
+        let mut initializing = JoinSet::new();
+        let mut outcomes = vec![None; manifest.contracts.len()];
+        for (index, entry) in manifest.contracts.iter().enumerate() {
+            let (deploy_address, deployment) =
+                match deployments.get(&entry.artifact_id) {
+                    Some(Ok(deployment)) => deployment.clone(),
+                    Some(Err(failure)) => {
+                        outcomes[index] = Some(L1XManifestEntryOutcome::new(
+                            entry,
+                            Err(failure.clone()),
+                        ));
+                        continue;
+                    }
+                    None => continue,
+                };
+
+            let permit = in_flight
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the in-flight semaphore is never closed");
+            let entry_cmd = self.manifest_entry_cmd(entry);
+
+            initializing.spawn(
+                async move {
+                    let result = entry_cmd
+                        .manifest_init(&deploy_address, &deployment)
+                        .await
+                        .map_err(|err_code| L1XInstallFailure::from(&err_code));
+                    drop(permit);
+                    (index, result)
+                }
+                .in_current_span(),
+            );
+        }
+
+        while let Some(initialized) = initializing.join_next().await {
+            match initialized {
+                Ok((index, result)) => {
+                    outcomes[index] = Some(L1XManifestEntryOutcome::new(
+                        &manifest.contracts[index],
+                        result,
+                    ))
+                }
+                Err(err_code) => {
+                    tracing::warn!("Manifest init task failed :: {}", err_code)
+                }
+            }
+        }
+
+        outcomes
+            .into_iter()
+            .zip(&manifest.contracts)
+            .map(|(outcome, entry)| {
+                outcome.unwrap_or_else(|| {
+                    L1XManifestEntryOutcome::new(
+                        entry,
+                        Err(L1XInstallFailure {
+                            error_kind: L1XInstallErrorKind::Install,
+                            message: "install task failed".to_string(),
+                        }),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// The install of the manifest `entry`, with the flags of this command
+    /// otherwise
+    fn manifest_entry_cmd(&self, entry: &L1XInstallManifestEntry) -> Self {
+        L1XVmInstallContractCmd {
+            artifact_id: entry.artifact_id.clone(),
+            contract_id: entry.contract_id.clone(),
+            manifest: None,
+            owner: entry.owner.clone().or_else(|| self.owner.clone()),
+            access_type: entry.access_type.or(self.access_type),
+            init_args: entry.init_args_text(),
+            ..self.clone()
+        }
+    }
+
+    /// Address and outcome of the deployment of `--artifact-id`, the
+    /// registered one unless --force
+    async fn manifest_deployment(
+        &self,
+    ) -> Result<(AddressStr, L1XInstallOutcome), L1XVmContractInstallError>
+    {
+        let installer = L1XVmContractInstaller::new(self).await?;
+        let mut outcome = self.install_outcome(&installer).await;
+
+        let registered = match self.force {
+            true => None,
+            false => installer
+                .internal_installer
+                .read()
+                .await
+                .toolkit
+                .contract_address_for(
+                    self.vm_type.into(),
+                    &self.artifact_id,
+                    None,
+                )
+                .ok(),
+        };
+        let deploy_address = match registered {
+            Some(deploy_address) => deploy_address,
+            None => {
+                let started = Instant::now();
+                let deploy_result =
                     installer.l1x_ebpf_deploy_contract().await?;
-                Some(deploy_response.contract_address.unwrap_or_default())
+                outcome.elapsed_ms.deploy = Some(elapsed_ms(started));
+                outcome.deploy_tx_hash = Some(deploy_result.hash.clone());
+                outcome.deploy_fee = Some(deploy_result.fee);
+                outcome.deploy_fee_used = deploy_result.fee_used;
+
+                L1XVmContractInstallError::created_address(
+                    &self.stage("Contract Deploy"),
+                    &deploy_result,
+                )?
             }
-            (false, Ok(address)) => Some(address),
-            _ => None,
         };
+        outcome.deploy_address = Some(deploy_address);
 
-        if let Some(deploy_address) = contract_deploy_address {
-            installer.l1x_ebpf_init_contract(&deploy_address).await?;
-            Ok(())
-        } else {
-            Err(L1XVmContractInstallError::new(format!(
-                "L1X eBPF Deployment Failed: Unknown Contract Deployment Address"
-            )))
+        Ok((deploy_address, outcome))
+    }
+
+    /// Initialize `--contract-id` on the manifest deployment `deploy_address`,
+    /// unless it is registered and no --force
+    async fn manifest_init(
+        &self,
+        deploy_address: &AddressStr,
+        deployment: &L1XInstallOutcome,
+    ) -> Result<L1XInstallOutcome, L1XVmContractInstallError> {
+        let started = Instant::now();
+        let init_args = self.init_args()?;
+        let installer = L1XVmContractInstaller::new(self).await?;
+        let mut outcome = L1XInstallOutcome {
+            contract_id: self.contract_id.clone(),
+            ..deployment.clone()
+        };
+
+        let registered = match self.force {
+            true => None,
+            false => installer
+                .internal_installer
+                .read()
+                .await
+                .toolkit
+                .contract_address_for(
+                    self.vm_type.into(),
+                    &self.artifact_id,
+                    Some(&self.contract_id),
+                )
+                .ok(),
+        };
+        if let Some(instance_address) = registered {
+            outcome.instance_address = Some(instance_address);
+            return Ok(outcome);
         }
+
+        let init_result =
+            installer.l1x_ebpf_init_contract(deploy_address, init_args).await?;
+        outcome.elapsed_ms.init = Some(elapsed_ms(started));
+        outcome.elapsed_ms.total =
+            outcome.elapsed_ms.deploy.unwrap_or_default() + elapsed_ms(started);
+        outcome.init_tx_hash = Some(init_result.hash.clone());
+        outcome.init_fee = Some(init_result.fee);
+        outcome.init_fee_used = init_result.fee_used;
+        outcome.instance_address =
+            Some(L1XVmContractInstallError::created_address(
+                &self.stage("Contract Init"),
+                &init_result,
+            )?);
+
+        Ok(outcome)
     }
+}
 
-    // Function to deploy and initialize a contract on evm VM
-    async fn l1x_evm_install_contract(
+impl L1XVmInstallContractCmd {
+    /// Decide what the eBPF or WASM install has to submit from the registry
+    /// section of `--vm-type`
+    pub(crate) fn l1x_ebpf_install_plan(
         &self,
-    ) -> Result<(), L1XVmContractInstallError> {
-        // Load install settings
-        let installer = L1XVmContractInstaller::new(self);
-        let artifact_deploy_status = if self.force == false {
-            toolkit_config::get_toolkit_evm_contract_address_for(
+        toolkit: &ToolkitConfig,
+    ) -> Result<L1XEbpfInstallPlan, L1XVmContractInstallError> {
+        let deploy_address = toolkit
+            .contract_address_for(self.vm_type.into(), &self.artifact_id, None)
+            .ok();
+        let instance_address = toolkit
+            .contract_address_for(
+                self.vm_type.into(),
                 &self.artifact_id,
-                None,
+                Some(&self.contract_id),
             )
-        } else {
-            Err(format!(
-                "L1X EVM Deployment Failed: Unknown Contract Deployment Address"
-            ))
-        };
+            .ok();
 
-        let contract_deploy_address = match (self.force, artifact_deploy_status)
+        match (self.reinit_only, self.force, deploy_address, instance_address)
         {
-            (true, _) | (false, Err(_)) => {
-                let deploy_response =
-                    installer.l1x_evm_deploy_contract().await?;
-                Some(deploy_response.contract_address.unwrap_or_default())
+            (true, _, Some(deploy_address), _) => {
+                Ok(L1XEbpfInstallPlan::Init { deploy_address })
+            }
+            (true, _, None, _) => Err(L1XVmContractInstallError::InvalidArgs(format!(
+                "L1X {} Failed: --reinit-only needs a deployment of '{}' in the contract registry",
+                self.stage("Init"),
+                self.artifact_id
+            ))),
+            (false, true, _, _) => Ok(L1XEbpfInstallPlan::DeployAndInit),
+            (false, false, _, Some(instance_address)) => {
+                Ok(L1XEbpfInstallPlan::Skip { instance_address })
+            }
+            (false, false, Some(deploy_address), None) => {
+                Ok(L1XEbpfInstallPlan::Init { deploy_address })
             }
-            (false, Ok(address)) => Some(address),
-            _ => None,
+            (false, false, None, None) => {
+                Ok(L1XEbpfInstallPlan::DeployAndInit)
+            }
+        }
+    }
+
+    // Function to deploy and initialize a contract on ebpf or wasm VM
+    async fn l1x_ebpf_install_contract(
+        &self,
+    ) -> Result<L1XInstallOutcome, L1XVmContractInstallError> {
+        let init_args = self.init_args()?;
+
+        // Load install settings
+        let installer = L1XVmContractInstaller::new(self).await?;
+        let mut outcome = self.install_outcome(&installer).await;
+
+        let plan = self.l1x_ebpf_install_plan(
+            &installer.internal_installer.read().await.toolkit,
+        )?;
+        let registered_deploy_address = match plan {
+            L1XEbpfInstallPlan::Skip { instance_address } => {
+                if self.output == L1XOutputFormat::Plain {
+                    println!(
+                        "Contract {} is already initialized :: {}, pass --force to re-initialize",
+                        self.contract_id, instance_address
+                    );
+                }
+                outcome.instance_address = Some(instance_address);
+                return Ok(outcome);
+            }
+            L1XEbpfInstallPlan::Init { deploy_address } => Some(deploy_address),
+            L1XEbpfInstallPlan::DeployAndInit => None,
         };
+        if self.dry_run {
+            installer
+                .l1x_ebpf_dry_run(registered_deploy_address, init_args)
+                .await?;
+            return Ok(outcome);
+        }
 
-        Ok(())
+        let deploy_address = match registered_deploy_address {
+            Some(deploy_address) => deploy_address,
+            None => {
+                let started = Instant::now();
+                let deploy_result =
+                    installer.l1x_ebpf_deploy_contract().await?;
+                outcome.elapsed_ms.deploy = Some(elapsed_ms(started));
+                outcome.deploy_tx_hash = Some(deploy_result.hash.clone());
+                outcome.deploy_fee = Some(deploy_result.fee);
+                outcome.deploy_fee_used = deploy_result.fee_used;
+
+                L1XVmContractInstallError::created_address(
+                    &self.stage("Contract Deploy"),
+                    &deploy_result,
+                )?
+            }
+        };
+        outcome.deploy_address = Some(deploy_address);
+
+        let started = Instant::now();
+        let init_result = installer
+            .l1x_ebpf_init_contract(&deploy_address, init_args)
+            .await?;
+        outcome.elapsed_ms.init = Some(elapsed_ms(started));
+        outcome.init_tx_hash = Some(init_result.hash.clone());
+        outcome.init_fee = Some(init_result.fee);
+        outcome.init_fee_used = init_result.fee_used;
+        outcome.instance_address =
+            Some(L1XVmContractInstallError::created_address(
+                &self.stage("Contract Init"),
+                &init_result,
+            )?);
+
+        Ok(outcome)
+    }
+
+    /// Decide whether the EVM install deploys `--artifact-id` from the
+    /// registry section of EVM contracts
+    pub(crate) fn l1x_evm_install_plan(
+        &self,
+        toolkit: &ToolkitConfig,
+    ) -> L1XEvmInstallPlan {
+        let deploy_address =
+            toolkit.evm_contract_address_for(&self.artifact_id, None).ok();
+
+        match (self.force, deploy_address) {
+            (false, Some(deploy_address)) => {
+                L1XEvmInstallPlan::Reuse { deploy_address }
+            }
+            (true, _) | (false, None) => L1XEvmInstallPlan::Deploy,
+        }
+    }
+
+    /// `smart_contract_function_call` payload of `--init-call` on the EVM
+    /// deployment `deploy_address`, `None` without `--init-call`
+    pub(crate) fn evm_init_call_txn(
+        &self,
+        deploy_address: &AddressStr,
+    ) -> Result<Option<types::Transaction>, L1XVmContractInstallError> {
+        let init_call = match &self.init_call {
+            Some(init_call) => init_call,
+            None => return Ok(None),
+        };
+
+        let calldata = hex::decode(init_call.trim().trim_start_matches("0x"))
+            .map_err(|err_code| {
+            L1XVmContractInstallError::InvalidArgs(format!(
+                "--init-call is not hex :: {}",
+                err_code
+            ))
+        })?;
+        if calldata.len() < 4 {
+            return Err(L1XVmContractInstallError::InvalidArgs(format!(
+                "--init-call needs a 4 bytes function selector, got {} bytes",
+                calldata.len()
+            )));
+        }
+
+        let (selector, arguments) = calldata.split_at(4);
+        Ok(Some(types::Transaction::SmartContractFunctionCall {
+            contract_instance_address: (*deploy_address).into(),
+            function: types::U8s::Bytes(selector.to_vec()),
+            arguments: types::U8s::Bytes(arguments.to_vec()),
+        }))
+    }
+
+    // Function to deploy a contract on evm VM, then make its --init-call
+    async fn l1x_evm_install_contract(
+        &self,
+    ) -> Result<L1XInstallOutcome, L1XVmContractInstallError> {
+        // Load install settings
+        let installer = L1XVmContractInstaller::new(self).await?;
+        let mut outcome = self.install_outcome(&installer).await;
+
+        let plan = self.l1x_evm_install_plan(
+            &installer.internal_installer.read().await.toolkit,
+        );
+        if let L1XEvmInstallPlan::Reuse { deploy_address } = plan {
+            if self.output == L1XOutputFormat::Plain {
+                println!(
+                    "Contract {} is already deployed :: {}, pass --force to redeploy",
+                    self.artifact_id, deploy_address
+                );
+            }
+            outcome.deploy_address = Some(deploy_address);
+            return Ok(outcome);
+        }
+        if self.dry_run {
+            installer.l1x_evm_dry_run().await?;
+            if self.init_call.is_some() {
+                tracing::warn!(
+                    "{} :: not shown, the address of the deployment is unknown before it is deployed",
+                    self.stage("Contract Init Call")
+                );
+            }
+            return Ok(outcome);
+        }
+
+        let started = Instant::now();
+        let deploy_response = installer.l1x_evm_deploy_contract().await?;
+        outcome.elapsed_ms.deploy = Some(elapsed_ms(started));
+        outcome.deploy_tx_hash = Some(deploy_response.hash.clone());
+        outcome.deploy_fee = Some(deploy_response.fee);
+        outcome.deploy_fee_used = deploy_response.fee_used;
+
+        // A deployment without address fails here rather than reporting a
+        // successful install that registered nothing
+        let deploy_address = L1XVmContractInstallError::created_address(
+            &self.stage("Contract Deploy"),
+            &deploy_response,
+        )?;
+        outcome.deploy_address = Some(deploy_address);
+
+        if let Some(init_call_txn) = self.evm_init_call_txn(&deploy_address)? {
+            let started = Instant::now();
+            let init_result =
+                installer.l1x_evm_init_call(init_call_txn).await?;
+            outcome.elapsed_ms.init = Some(elapsed_ms(started));
+            outcome.init_tx_hash = Some(init_result.hash.clone());
+            outcome.init_fee = Some(init_result.fee);
+            outcome.init_fee_used = init_result.fee_used;
+        }
+
+        Ok(outcome)
     }
 }
@@ -0,0 +1,2263 @@
+use crate::cli_error::L1XConfigError;
+use crate::contract_install::*;
+use crate::forge_client::{
+    DeployResult, ForgeClient, ForgeClientConfig, ForgeClientError,
+};
+use crate::forge_client_tests::rpc_result;
+use crate::install_manifest::{
+    L1XInstallManifest, L1XManifestEntryOutcome, L1XManifestEntryStatus,
+};
+use l1x_common::{
+    fee::ChosenFee,
+    toolkit_config::{
+        self, L1XVMContractAddressUpdateType, ToolkitConfig, ToolkitPaths,
+    },
+    types::{self, AddressStr},
+};
+
+use clap::Parser;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fs,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer};
+
+const TEST_PRIVATE_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+
+/// `L1X_CFG_WS_HOME` is process wide, tests using a temp registry take turns
+pub(crate) static REGISTRY_ENV_LOCK: Mutex<()> = Mutex::new(());
+
+const REGISTRY: &str = r#"
+l1x_vm:
+  ft:
+    deploy_hash: "d1"
+    deploy_address: "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+    instance:
+      ft_main:
+        inst_hash: "i1"
+        inst_address: "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+l1x_evm: {}
+"#;
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    install: L1XVmInstallContractCmd,
+}
+
+fn install_cmd(contract_id: &str, flags: &[&str]) -> L1XVmInstallContractCmd {
+    let args = [
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        contract_id,
+        "--owner",
+        "super",
+        "--salt",
+        "",
+    ];
+    TestCli::parse_from(args.iter().chain(flags)).install
+}
+
+/// Run `test` with `L1X_CFG_WS_HOME` pointing at a workspace holding
+/// `registry`, `None` for a workspace without registry file, on the `local`
+/// chain type
+pub(crate) fn with_temp_registry<T>(
+    registry: Option<&str>,
+    test: impl FnOnce(&std::path::Path) -> T,
+) -> T {
+    let _guard =
+        REGISTRY_ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let ws_home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    if let Some(registry) = registry {
+        fs::write(
+            ws_home
+                .path()
+                .join("l1x-conf/config-contract-address-registry.yaml"),
+            registry,
+        )
+        .unwrap();
+    }
+    env::set_var("L1X_CFG_WS_HOME", ws_home.path());
+    env::set_var("L1X_CFG_CHAIN_TYPE", "local");
+
+    test(ws_home.path())
+}
+
+/// Handle on the workspace `ws_home`, independent of `L1X_CFG_WS_HOME`
+pub(crate) fn toolkit_for(ws_home: &std::path::Path) -> ToolkitConfig {
+    ToolkitConfig::new(ToolkitPaths::new(ws_home.to_path_buf()))
+}
+
+/// Write a chain config under `ws_home` whose `local` network has chain id
+/// `chain_id` and points at `end_point`
+pub(crate) fn write_chain_config(
+    ws_home: &std::path::Path,
+    chain_id: u32,
+    end_point: &str,
+) {
+    fs::create_dir_all(ws_home.join("l1x-conf")).unwrap();
+    fs::write(
+        ws_home.join("l1x-conf/l1x_chain_config.yaml"),
+        format!(
+            "networks:\n  local:\n    chain_id: {chain_id}\n    host_ip: \"127.0.0.1\"\n    rpc_port: 50051\n    rpc_endpoint: \"{end_point}\"\n"
+        ),
+    )
+    .unwrap();
+}
+
+/// Toolkit on the `local` network of [`write_chain_config`], ignoring the
+/// `L1X_CFG_*` variables of the test process
+pub(crate) fn toolkit_on(
+    ws_home: &std::path::Path,
+    chain_id: u32,
+    end_point: &str,
+) -> ToolkitConfig {
+    write_chain_config(ws_home, chain_id, end_point);
+
+    toolkit_for(ws_home)
+        .with_network(Some("local"))
+        .with_env_overrides(HashMap::new())
+}
+
+#[test]
+fn test_ebpf_cmd_is_a_forced_install_with_the_command_line_defaults() {
+    let cmd = L1XVmInstallContractCmd::ebpf("ft", "ft_main", "super");
+
+    assert_eq!(
+        format!("{:?}", cmd),
+        format!("{:?}", install_cmd("ft_main", &["--force"]))
+    );
+}
+
+#[test]
+fn test_ebpf_install_plan_skips_registered_instance() {
+    // Arrange
+    let cmd = install_cmd("ft_main", &[]);
+
+    // Act
+    let plan = with_temp_registry(Some(REGISTRY), |ws_home| {
+        cmd.l1x_ebpf_install_plan(&toolkit_for(ws_home))
+    });
+
+    // Assert
+    assert_eq!(
+        plan.unwrap(),
+        L1XEbpfInstallPlan::Skip { instance_address: [0xbb; 20].into() }
+    );
+}
+
+#[test]
+fn test_ebpf_install_plan_inits_new_instance_of_deployment() {
+    let cmd = install_cmd("ft_second", &[]);
+
+    let plan = with_temp_registry(Some(REGISTRY), |ws_home| {
+        cmd.l1x_ebpf_install_plan(&toolkit_for(ws_home))
+    });
+
+    assert_eq!(
+        plan.unwrap(),
+        L1XEbpfInstallPlan::Init { deploy_address: [0xaa; 20].into() }
+    );
+}
+
+#[test]
+fn test_ebpf_install_plan_deploys_unknown_artifact() {
+    let cmd = install_cmd("ft_main", &[]);
+
+    let plan = with_temp_registry(None, |ws_home| {
+        cmd.l1x_ebpf_install_plan(&toolkit_for(ws_home))
+    });
+
+    assert_eq!(plan.unwrap(), L1XEbpfInstallPlan::DeployAndInit);
+}
+
+#[test]
+fn test_ebpf_install_plan_force_redeploys_registered_instance() {
+    let cmd = install_cmd("ft_main", &["--force"]);
+
+    let plan = with_temp_registry(Some(REGISTRY), |ws_home| {
+        cmd.l1x_ebpf_install_plan(&toolkit_for(ws_home))
+    });
+
+    assert_eq!(plan.unwrap(), L1XEbpfInstallPlan::DeployAndInit);
+}
+
+#[test]
+fn test_ebpf_install_plan_reinit_only_reuses_deployment() {
+    let cmd = install_cmd("ft_main", &["--reinit-only"]);
+
+    let plan = with_temp_registry(Some(REGISTRY), |ws_home| {
+        cmd.l1x_ebpf_install_plan(&toolkit_for(ws_home))
+    });
+
+    assert_eq!(
+        plan.unwrap(),
+        L1XEbpfInstallPlan::Init { deploy_address: [0xaa; 20].into() }
+    );
+}
+
+#[test]
+fn test_ebpf_install_plan_reinit_only_requires_deployment() {
+    let cmd = install_cmd("ft_main", &["--reinit-only"]);
+
+    let plan = with_temp_registry(None, |ws_home| {
+        cmd.l1x_ebpf_install_plan(&toolkit_for(ws_home))
+    });
+
+    assert!(plan.is_err());
+}
+
+/// Record a redeploy of `ft` at `0xcc..cc` in the registry of the
+/// environment
+fn redeploy_ft() {
+    ToolkitConfig::from_env()
+        .unwrap()
+        .update_contract_address_registry(
+            L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
+                artifact_id: "ft".to_string(),
+                response_hash: "d2".to_string(),
+                response_address: "c".repeat(40).parse().unwrap(),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_ebpf_install_plan_inits_again_after_redeploy() {
+    let cmd = install_cmd("ft_main", &[]);
+
+    let plan = with_temp_registry(Some(REGISTRY), |ws_home| {
+        redeploy_ft();
+        cmd.l1x_ebpf_install_plan(&toolkit_for(ws_home))
+    });
+
+    assert_eq!(
+        plan.unwrap(),
+        L1XEbpfInstallPlan::Init { deploy_address: [0xcc; 20].into() }
+    );
+}
+
+#[test]
+fn test_ebpf_redeploy_moves_instances_to_previous_deployment() {
+    // Act
+    let (instance_address, registry) =
+        with_temp_registry(Some(REGISTRY), |ws_home| {
+            redeploy_ft();
+            ToolkitConfig::from_env()
+                .unwrap()
+                .update_contract_address_registry(
+                    L1XVMContractAddressUpdateType::L1XEBPF_INIT {
+                        artifact_id: "ft".to_string(),
+                        contract_id: "ft_main".to_string(),
+                        response_hash: "i2".to_string(),
+                        response_address: "d".repeat(40).parse().unwrap(),
+                    },
+                )
+                .unwrap();
+
+            let instance_address = ToolkitConfig::from_env()
+                .unwrap()
+                .ebpf_contract_address_for("ft", Some("ft_main"))
+                .unwrap();
+            let registry = fs::read_to_string(
+                ws_home.join("l1x-conf/config-contract-address-registry.yaml"),
+            )
+            .unwrap();
+            (instance_address, registry)
+        });
+
+    // Assert
+    assert_eq!(instance_address.to_hex(), "d".repeat(40));
+    let registry: serde_yaml::Value = serde_yaml::from_str(&registry).unwrap();
+    let ft = &registry["networks"]["local"]["l1x_vm"]["ft"];
+    assert!(ft["instance"]["ft_main"]["previous"].is_null());
+    let previous = &ft["previous"][0];
+    assert_eq!(previous["deploy_hash"], "d1");
+    assert_eq!(previous["deploy_address"], format!("0x{}", "a".repeat(40)));
+    assert_eq!(previous["instance"]["ft_main"]["inst_hash"], "i1");
+    assert_eq!(
+        previous["instance"]["ft_main"]["inst_address"],
+        format!("0x{}", "b".repeat(40))
+    );
+}
+
+#[test]
+fn test_wasm_install_plan_reads_the_wasm_section() {
+    // Arrange
+    let registry = format!(
+        "{}l1x_wasm:\n  ft:\n    deploy_hash: \"w1\"\n    deploy_address: \"0x{}\"\n    instance: {{}}\n",
+        REGISTRY,
+        "cc".repeat(20)
+    );
+    let cmd = TestCli::parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "wasm",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--salt",
+        "",
+    ])
+    .install;
+
+    // Act
+    let plan = with_temp_registry(Some(&registry), |ws_home| {
+        cmd.l1x_ebpf_install_plan(&toolkit_for(ws_home))
+    });
+
+    // Assert
+    assert_eq!(
+        plan.unwrap(),
+        L1XEbpfInstallPlan::Init { deploy_address: [0xcc; 20].into() }
+    );
+}
+
+const EVM_REGISTRY: &str = r#"
+l1x_vm: {}
+l1x_evm:
+  ft:
+    deploy_hash: "e1"
+    deploy_address: "0xcccccccccccccccccccccccccccccccccccccccc"
+    instance: {}
+"#;
+
+fn evm_install_cmd(flags: &[&str]) -> L1XVmInstallContractCmd {
+    let args = [
+        "vm-install-contract",
+        "--vm-type",
+        "evm",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft",
+        "--owner",
+        "super",
+        "--salt",
+        "",
+        "--skip-chain-check",
+    ];
+    TestCli::parse_from(args.iter().chain(flags)).install
+}
+
+#[test]
+fn test_evm_install_plan_follows_force_and_registry() {
+    let reuse = L1XEvmInstallPlan::Reuse { deploy_address: [0xcc; 20].into() };
+    for (flags, registry, expected) in [
+        (&[][..], Some(EVM_REGISTRY), reuse),
+        (&[][..], None, L1XEvmInstallPlan::Deploy),
+        (&["--force"][..], Some(EVM_REGISTRY), L1XEvmInstallPlan::Deploy),
+        (&["--force"][..], None, L1XEvmInstallPlan::Deploy),
+    ] {
+        let cmd = evm_install_cmd(flags);
+
+        let plan = with_temp_registry(registry, |ws_home| {
+            cmd.l1x_evm_install_plan(&toolkit_for(ws_home))
+        });
+
+        assert_eq!(plan, expected, "{:?} {:?}", flags, registry);
+    }
+}
+
+#[test]
+fn test_evm_init_call_splits_the_selector_from_the_arguments() {
+    // Arrange
+    let cmd = evm_install_cmd(&["--init-call", "0x8129fc1c0001"]);
+
+    // Act
+    let txn = cmd.evm_init_call_txn(&[0xcc; 20].into()).unwrap();
+
+    // Assert
+    assert_eq!(
+        txn,
+        Some(types::Transaction::SmartContractFunctionCall {
+            contract_instance_address: AddressStr::from([0xcc; 20]).into(),
+            function: types::U8s::Bytes(vec![0x81, 0x29, 0xfc, 0x1c]),
+            arguments: types::U8s::Bytes(vec![0x00, 0x01]),
+        })
+    );
+}
+
+#[test]
+fn test_evm_init_call_needs_a_hex_selector() {
+    for init_call in ["0x8129", "initialize()"] {
+        let cmd = evm_install_cmd(&["--init-call", init_call]);
+
+        let txn = cmd.evm_init_call_txn(&[0xcc; 20].into());
+
+        assert_eq!(
+            txn.unwrap_err().kind(),
+            L1XInstallErrorKind::InvalidArgs,
+            "{}",
+            init_call
+        );
+    }
+}
+
+/// Address `vm-install-contract` registers for the `l1x_submitTransaction`
+/// result `submit_result` of a deployment
+async fn created_address_of(
+    submit_result: Value,
+) -> Result<AddressStr, String> {
+    let server = MockServer::start().await;
+    for (rpc_method, result) in [
+        ("l1x_submitTransaction", submit_result),
+        ("l1x_getEvents", json!({ "events_data": [[1]] })),
+    ] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": rpc_method })))
+            .respond_with(rpc_result(result))
+            .mount(&server)
+            .await;
+    }
+    let client = ForgeClient::new(ForgeClientConfig {
+        event_poll_interval: Duration::from_millis(10),
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    })
+    .unwrap();
+
+    let deploy_result = client
+        .deploy_transaction(
+            ForgeClient::evm_deploy_txn(
+                vec![0x60],
+                Vec::new(),
+                "s",
+                types::AccessType::PUBLIC,
+            )
+            .unwrap(),
+            1,
+        )
+        .await
+        .map_err(|err_code: ForgeClientError| err_code.to_string())?;
+    L1XVmContractInstallError::created_address(
+        "EVM Contract Deploy",
+        &deploy_result,
+    )
+    .map_err(|err_code| err_code.to_string())
+}
+
+#[tokio::test]
+async fn test_created_address_is_read_as_a_string() {
+    for contract_address in
+        [json!(format!("0x{}", "ab".repeat(20))), json!("ab".repeat(20))]
+    {
+        let address = created_address_of(
+            json!({ "hash": "h1", "contract_address": contract_address }),
+        )
+        .await;
+
+        assert_eq!(address.unwrap().to_hex(), "ab".repeat(20));
+    }
+}
+
+#[tokio::test]
+async fn test_missing_created_address_is_an_error() {
+    for submit_result in [
+        json!({ "hash": "h1" }),
+        json!({ "hash": "h1", "contract_address": null }),
+        json!({ "hash": "h1", "contract_address": "" }),
+    ] {
+        let err = created_address_of(submit_result).await.unwrap_err();
+
+        assert_eq!(
+            err,
+            "EVM Contract Deploy Failed: the node reported no contract address for h1, the registry is unchanged"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_non_string_created_address_is_rejected() {
+    for contract_address in [json!(42), json!({ "address": "ab" })] {
+        let err = created_address_of(
+            json!({ "hash": "h1", "contract_address": contract_address }),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(err.contains("Unable to parse the response"), "{}", err);
+    }
+}
+
+#[test]
+fn test_check_predicted_address() {
+    let created: AddressStr = "11".repeat(20).parse().unwrap();
+    let other: AddressStr = "22".repeat(20).parse().unwrap();
+
+    assert!(check_predicted_address("Deploy", Some(created), &created));
+    assert!(check_predicted_address("Deploy", None, &created));
+    assert!(!check_predicted_address("Deploy", Some(other), &created));
+}
+
+/// Access and contract type of the `smart_contract_deployment` payload an
+/// install with `flags` submits
+fn deployment_json(vm_type: &str, flags: &[&str]) -> (Value, Value) {
+    let args = [
+        "vm-install-contract",
+        "--vm-type",
+        vm_type,
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--salt",
+        "s",
+    ];
+    let cmd = TestCli::parse_from(args.iter().chain(flags)).install;
+    let (access_type, contract_type) = cmd.deployment_types().unwrap();
+    let txn = match vm_type {
+        "ebpf" => {
+            ForgeClient::ebpf_deploy_txn(vec![0x7f], access_type, contract_type)
+        }
+        _ => ForgeClient::evm_deploy_txn(
+            vec![0x60],
+            Vec::new(),
+            "s",
+            access_type,
+        ),
+    }
+    .unwrap();
+
+    let payload = serde_json::to_value(txn).unwrap();
+    let deployment = &payload["smart_contract_deployment"];
+    (deployment[0].clone(), deployment[1].clone())
+}
+
+#[test]
+fn test_deployment_types_default_to_previous_payloads() {
+    assert_eq!(
+        deployment_json("ebpf", &[]),
+        (json!("PRIVATE"), json!("L1XVM"))
+    );
+    assert_eq!(deployment_json("evm", &[]), (json!("PUBLIC"), json!("EVM")));
+}
+
+#[test]
+fn test_deployment_types_follow_flags() {
+    for (access_flag, access_type) in [
+        ("public", "PUBLIC"),
+        ("private", "PRIVATE"),
+        ("restricted", "RESTICTED"),
+    ] {
+        for (contract_flag, contract_type) in
+            [("l1xvm", "L1XVM"), ("xtalk", "XTALK")]
+        {
+            assert_eq!(
+                deployment_json(
+                    "ebpf",
+                    &[
+                        "--access-type",
+                        access_flag,
+                        "--contract-type",
+                        contract_flag
+                    ]
+                ),
+                (json!(access_type), json!(contract_type))
+            );
+        }
+        assert_eq!(
+            deployment_json("evm", &["--access-type", access_flag]),
+            (json!(access_type), json!("EVM"))
+        );
+    }
+}
+
+#[test]
+fn test_deployment_types_reject_contract_type_on_evm() {
+    let cmd = install_cmd("ft_main", &["--contract-type", "xtalk"]);
+    let evm_cmd = TestCli::parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "evm",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--salt",
+        "s",
+        "--contract-type",
+        "xtalk",
+    ])
+    .install;
+
+    let err = evm_cmd.deployment_types().unwrap_err();
+
+    assert!(cmd.deployment_types().is_ok());
+    assert!(
+        err.to_string()
+            .contains("--contract-type only applies to --vm-type ebpf"),
+        "{}",
+        err
+    );
+    assert!(TestCli::try_parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--salt",
+        "s",
+        "--access-type",
+        "open",
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_wasm_deploy_payload_carries_the_module() {
+    let cmd = |vm_type: &str| {
+        TestCli::parse_from([
+            "vm-install-contract",
+            "--vm-type",
+            vm_type,
+            "--artifact-id",
+            "ft.wasm",
+            "--contract-id",
+            "ft_main",
+            "--salt",
+            "",
+        ])
+        .install
+    };
+    let module = b"\0asm\x01\0\0\0".to_vec();
+
+    let txn = cmd("wasm").object_deploy_txn(module.clone()).unwrap();
+    let err = cmd("wasm").object_deploy_txn(vec![0x7f]).unwrap_err();
+
+    assert_eq!(
+        serde_json::to_value(&txn).unwrap(),
+        serde_json::to_value(
+            ForgeClient::ebpf_deploy_txn(
+                module,
+                types::AccessType::PRIVATE,
+                types::ContractType::L1XVM,
+            )
+            .unwrap()
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        err.to_string(),
+        "WASM Contract Deploy Failed: ft.wasm is not a WASM module"
+    );
+    assert!(cmd("ebpf").object_deploy_txn(vec![0x7f]).is_ok());
+}
+
+#[test]
+fn test_evm_deploy_payload_appends_constructor_args() {
+    let artifact = fs::read_to_string(format!(
+        "{}/fixtures/artifacts/Token.json",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    let cmd = |flags: &[&str]| {
+        let args = [
+            "vm-install-contract",
+            "--vm-type",
+            "evm",
+            "--artifact-id",
+            "Token.json",
+            "--contract-id",
+            "token",
+            "--salt",
+            "s",
+        ];
+        TestCli::parse_from(args.iter().chain(flags)).install
+    };
+    let constructor_args = [
+        format!("{:064x}", 1000),
+        format!("{:0>64}", "11".repeat(20)),
+        format!("{:064x}", 0x60),
+        format!("{:064x}", 3),
+        format!("{:0<64}", hex::encode("L1X")),
+    ]
+    .concat();
+
+    let txn = cmd(&[
+        "--constructor-args",
+        "1000",
+        &format!("0x{}", "11".repeat(20)),
+        "L1X",
+    ])
+    .evm_deploy_txn(artifact.as_bytes())
+    .unwrap();
+    let err = cmd(&["--constructor-args", "1000"])
+        .evm_deploy_txn(artifact.as_bytes())
+        .unwrap_err();
+
+    assert_eq!(
+        serde_json::to_value(&txn).unwrap(),
+        serde_json::to_value(
+            ForgeClient::evm_deploy_txn(
+                hex::decode(
+                    "6080604052348015600f57600080fd5b50603f80601d6000396000f3fe"
+                )
+                .unwrap(),
+                hex::decode(constructor_args).unwrap(),
+                "\"s\"",
+                types::AccessType::PUBLIC,
+            )
+            .unwrap()
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        err.to_string(),
+        "EVM Contract Deploy Failed: Token.json :: Invalid constructor arguments: the constructor takes 3 arguments, got 1"
+    );
+}
+
+/// JSON `smart_contract_init` payload of the init arguments of `flags`
+fn init_payload_json(flags: &[&str]) -> Value {
+    let deploy_address: AddressStr = "ab".repeat(20).parse().unwrap();
+    let init_args = install_cmd("ft_main", flags).init_args().unwrap();
+
+    serde_json::to_value(init_payload(&deploy_address, init_args)).unwrap()
+}
+
+#[test]
+fn test_init_args_default_to_empty_object() {
+    let expected = json!({
+        "smart_contract_init": [{ "hex": "ab".repeat(20) }, { "text": "{}" }]
+    });
+
+    assert_eq!(init_payload_json(&[]), expected);
+    assert_eq!(init_payload_json(&["--init-args", "  "]), expected);
+}
+
+#[test]
+fn test_init_args_from_flag_file_and_hex() {
+    let dir = tempfile::tempdir().unwrap();
+    let args_file = dir.path().join("init.json");
+    fs::write(&args_file, "{\"name\": \"FT\", \"decimals\": 18}\n").unwrap();
+    let init_args = |flags: &[&str]| {
+        init_payload_json(flags)["smart_contract_init"][1].clone()
+    };
+
+    assert_eq!(
+        init_args(&["--init-args", r#"{"supply": 1000}"#]),
+        json!({ "text": r#"{"supply": 1000}"# })
+    );
+    assert_eq!(
+        init_args(&["--init-args-file", args_file.to_str().unwrap()]),
+        json!({ "text": r#"{"name": "FT", "decimals": 18}"# })
+    );
+    assert_eq!(
+        init_args(&["--init-args", "0xABCD", "--init-args-hex"]),
+        json!({ "hex": "abcd" })
+    );
+}
+
+#[test]
+fn test_init_args_rejects_invalid_content() {
+    let error = |flags: &[&str]| {
+        install_cmd("ft_main", flags).init_args().unwrap_err().to_string()
+    };
+
+    let not_json = error(&["--init-args", "{supply: 1000"]);
+    let not_hex = error(&["--init-args", "xyz", "--init-args-hex"]);
+    let missing = error(&["--init-args-file", "/nonexistent/init.json"]);
+
+    assert!(not_json.contains("--init-args :: not JSON"), "{}", not_json);
+    assert!(not_hex.contains("--init-args :: not hex"), "{}", not_hex);
+    assert!(missing.contains("/nonexistent/init.json"), "{}", missing);
+    assert!(TestCli::try_parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--salt",
+        "",
+        "--init-args",
+        "{}",
+        "--init-args-file",
+        "init.json",
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_deploy_records_checksum_of_artifact() {
+    // Arrange
+    let cmd = install_cmd("ft_main", &["--fee_limit", "250"]);
+
+    let entries = with_temp_registry(None, |ws_home| {
+        let toolkit = toolkit_for(ws_home).with_chain_type("local");
+        write_toolkit_config(ws_home);
+        let artifact_file = toolkit
+            .artifact_dir(toolkit_config::L1XVMArtifactType::Ebpf)
+            .join("ft");
+        fs::create_dir_all(artifact_file.parent().unwrap()).unwrap();
+        fs::write(&artifact_file, b"\x7fELF fixture").unwrap();
+
+        // Act
+        toolkit
+            .update_contract_address_registry_with_metadata(
+                L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
+                    artifact_id: "ft".to_string(),
+                    response_hash: "d1".to_string(),
+                    response_address: "a".repeat(40).parse().unwrap(),
+                },
+                cmd.deploy_metadata(
+                    &artifact_file,
+                    &fs::read(&artifact_file).unwrap(),
+                ),
+            )
+            .unwrap();
+        toolkit.contract_address_registry_entries().unwrap()
+    });
+
+    // Assert
+    let metadata = &entries[0].metadata;
+    assert_eq!(
+        metadata.artifact_sha256.as_deref(),
+        Some(
+            "262d1940abd611bd872afa6bc61d3e49568f2819d9bb88dd2279dafdb0b8d978"
+        )
+    );
+    assert_eq!(metadata.owner.as_deref(), Some("super"));
+    assert_eq!(metadata.fee_limit.as_deref(), Some("250"));
+    assert_eq!(metadata.network.as_deref(), Some("local"));
+    assert!(metadata.deployed_at.is_some());
+    assert_eq!(metadata.artifact_path, None);
+}
+
+/// Write a chain config with a `local` network and a wallet config with the
+/// `ava`, `bobby` and `super` wallets under `ws_home`
+pub(crate) fn write_toolkit_config(ws_home: &std::path::Path) {
+    fs::write(
+        ws_home.join("l1x-conf/l1x_chain_config.yaml"),
+        r#"
+networks:
+  local:
+    chain_id: 1
+    host_ip: "127.0.0.1"
+    rpc_port: 50051
+    rpc_endpoint: "http://127.0.0.1:50051"
+    is_dev: true
+"#,
+    )
+    .unwrap();
+    let key = "0".repeat(63) + "1";
+    fs::write(
+        ws_home.join("l1x-conf/l1x_dev_wallets.yaml"),
+        format!(
+            "dev_accounts:\n  ava:\n    priv_key: \"{key}\"\n  bobby:\n    priv_key: \"{key}\"\n  super:\n    priv_key: \"{key}\"\n"
+        ),
+    )
+    .unwrap();
+}
+
+/// Malformed key of the `broken` wallet of [`write_broken_wallet`]
+pub(crate) const BROKEN_WALLET_KEY: &str =
+    "5ec12e7000000000000000000000000000000000000000000000000000000zz";
+
+/// Add a `broken` wallet holding [`BROKEN_WALLET_KEY`] to the wallet config
+/// of [`write_toolkit_config`] under `ws_home`
+pub(crate) fn write_broken_wallet(ws_home: &std::path::Path) {
+    let wallets_path = ws_home.join("l1x-conf/l1x_dev_wallets.yaml");
+    let wallets = fs::read_to_string(&wallets_path).unwrap();
+    fs::write(
+        wallets_path,
+        format!(
+            "{}  broken:\n    priv_key: \"{}\"\n",
+            wallets, BROKEN_WALLET_KEY
+        ),
+    )
+    .unwrap();
+}
+
+/// Buffer the log events of [`with_captured_logs`] are written to
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Run `test` with the log events of every level of the current thread
+/// captured, returned with its result
+pub(crate) fn with_captured_logs<T>(test: impl FnOnce() -> T) -> (T, String) {
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+
+    let result = tracing::subscriber::with_default(subscriber, test);
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    (result, logs)
+}
+
+/// Run `cmd.exec()` against the toolkit config of [`write_toolkit_config`]
+/// with `L1X_CFG_CHAIN_TYPE` set to `chain_type`
+fn exec_with_toolkit_config(
+    cmd: &L1XVmInstallContractCmd,
+    chain_type: &str,
+) -> anyhow::Result<()> {
+    with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        env::set_var("L1X_CFG_CHAIN_TYPE", chain_type);
+
+        tokio::runtime::Runtime::new().unwrap().block_on(cmd.exec())
+    })
+}
+
+/// Mock node answering each JSON-RPC method of `results` with its result,
+/// and the `local` network of the workspace `ws_home` pointing at it
+pub(crate) async fn start_mock_node(
+    ws_home: &std::path::Path,
+    results: Vec<(&str, Value)>,
+) -> MockServer {
+    let server = MockServer::start().await;
+    for (rpc_method, result) in results {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": rpc_method })))
+            .respond_with(rpc_result(result))
+            .mount(&server)
+            .await;
+    }
+    fs::write(
+        ws_home.join("l1x-conf/l1x_chain_config.yaml"),
+        format!(
+            "networks:\n  local:\n    chain_id: 1\n    host_ip: \"127.0.0.1\"\n    rpc_port: 50051\n    rpc_endpoint: \"{}\"\n    is_dev: true\n",
+            server.uri()
+        ),
+    )
+    .unwrap();
+
+    server
+}
+
+/// JSON-RPC methods `server` received, in order
+pub(crate) async fn received_rpc_methods(server: &MockServer) -> Vec<String> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            request.body_json::<Value>().unwrap()["method"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Run `cmd.install()` on a workspace holding `registry`, once `prepare` ran
+/// on it, whose `local` network is a node answering each JSON-RPC method of
+/// `rpc_results` with its result. Returns the outcome, the JSON-RPC methods
+/// the node received and the registry file afterwards.
+fn install_against(
+    cmd: &L1XVmInstallContractCmd,
+    registry: Option<&str>,
+    rpc_results: Vec<(&str, Value)>,
+    prepare: impl FnOnce(&std::path::Path),
+) -> (
+    Result<L1XInstallOutcome, L1XVmContractInstallError>,
+    Vec<String>,
+    Option<String>,
+) {
+    with_temp_registry(registry, |ws_home| {
+        write_toolkit_config(ws_home);
+        for vm_type in [
+            toolkit_config::L1XVMArtifactType::Ebpf,
+            toolkit_config::L1XVMArtifactType::Evm,
+        ] {
+            let artifact_dir = toolkit_for(ws_home).artifact_dir(vm_type);
+            fs::create_dir_all(&artifact_dir).unwrap();
+            fs::write(artifact_dir.join("ft"), "6080604052").unwrap();
+        }
+        prepare(ws_home);
+
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let server = start_mock_node(ws_home, rpc_results).await;
+
+            let result = cmd.install().await;
+            let registry = fs::read_to_string(
+                ws_home.join("l1x-conf/config-contract-address-registry.yaml"),
+            )
+            .ok();
+            (result, received_rpc_methods(&server).await, registry)
+        })
+    })
+}
+
+/// `l1x_getTransactionReceipt` result of a transaction that succeeded
+pub(crate) fn success_receipt() -> Value {
+    json!({ "block_number": 10, "status": "success" })
+}
+
+/// [`install_against`] a node answering every `l1x_submitTransaction` with
+/// `submit_result` and every receipt query with `receipt`
+fn install_against_receipt(
+    cmd: &L1XVmInstallContractCmd,
+    registry: Option<&str>,
+    submit_result: Value,
+    receipt: Value,
+) -> (
+    Result<L1XInstallOutcome, L1XVmContractInstallError>,
+    Vec<String>,
+    Option<String>,
+) {
+    install_against(
+        cmd,
+        registry,
+        vec![
+            ("l1x_submitTransaction", submit_result),
+            ("l1x_getEvents", json!({ "events_data": [[1]] })),
+            ("l1x_getTransactionReceipt", receipt),
+            (
+                "l1x_getAccountState",
+                json!({ "account_state": { "balance": "5", "nonce": "7", "account_type": 0 } }),
+            ),
+        ],
+        |_| {},
+    )
+}
+
+/// [`install_against_receipt`] of transactions that succeed
+fn install_against_node(
+    cmd: &L1XVmInstallContractCmd,
+    registry: Option<&str>,
+    submit_result: Value,
+) -> (
+    Result<L1XInstallOutcome, L1XVmContractInstallError>,
+    Vec<String>,
+    Option<String>,
+) {
+    install_against_receipt(cmd, registry, submit_result, success_receipt())
+}
+
+#[test]
+fn test_deploy_without_address_leaves_registry_untouched() {
+    for vm_type in ["ebpf", "evm"] {
+        // Arrange
+        let cmd = TestCli::parse_from([
+            "vm-install-contract",
+            "--vm-type",
+            vm_type,
+            "--artifact-id",
+            "ft",
+            "--contract-id",
+            "ft_main",
+            "--owner",
+            "super",
+            "--salt",
+            "",
+            "--skip-chain-check",
+        ])
+        .install;
+
+        // Act
+        let (result, _, registry) =
+            install_against_node(&cmd, None, json!({ "hash": "d9" }));
+
+        // Assert
+        let err = result.unwrap_err();
+        assert_eq!(err.kind(), L1XInstallErrorKind::MissingAddress);
+        let err = err.to_string();
+        assert!(
+            err.ends_with(
+                "Contract Deploy Failed: the node reported no contract address for d9, the registry is unchanged"
+            ),
+            "{}",
+            err
+        );
+        assert_eq!(registry, None, "{}", vm_type);
+    }
+}
+
+#[test]
+fn test_init_without_address_leaves_registry_untouched() {
+    // Arrange
+    let cmd = install_cmd("ft_second", &["--skip-chain-check"]);
+
+    // Act
+    let (result, _, registry) = install_against_node(
+        &cmd,
+        Some(REGISTRY),
+        json!({ "hash": "i9", "contract_address": null }),
+    );
+
+    // Assert
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "eBPF Contract Init Failed: the node reported no contract address for i9, the registry is unchanged"
+    );
+    assert_eq!(registry.as_deref(), Some(REGISTRY));
+}
+
+#[test]
+fn test_dry_run_submits_nothing_and_leaves_registry_untouched() {
+    let submitted =
+        json!({ "hash": "d1", "contract_address": "ab".repeat(20) });
+    for (vm_type, contract_id, registry) in [
+        ("ebpf", "ft_main", None),
+        ("ebpf", "ft_second", Some(REGISTRY)),
+        ("evm", "ft_main", None),
+    ] {
+        // Arrange
+        let cmd = TestCli::parse_from([
+            "vm-install-contract",
+            "--vm-type",
+            vm_type,
+            "--artifact-id",
+            "ft",
+            "--contract-id",
+            contract_id,
+            "--owner",
+            "super",
+            "--salt",
+            "",
+            "--skip-chain-check",
+            "--dry-run",
+        ])
+        .install;
+
+        // Act
+        let (result, rpc_methods, registry_after) =
+            install_against_node(&cmd, registry, submitted.clone());
+
+        // Assert
+        result.unwrap();
+        assert_eq!(rpc_methods, vec!["l1x_getAccountState"], "{}", vm_type);
+        assert_eq!(registry_after.as_deref(), registry, "{}", vm_type);
+    }
+}
+
+#[test]
+fn test_install_unknown_owner_is_config_error() {
+    // Arrange
+    let cmd = TestCli::parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--owner",
+        "alice",
+        "--salt",
+        "",
+    ])
+    .install;
+
+    // Act
+    let err = exec_with_toolkit_config(&cmd, "local").unwrap_err();
+
+    // Assert
+    let config_err = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<L1XConfigError>())
+        .unwrap();
+    assert_eq!(
+        config_err.to_string(),
+        "wallet 'alice' not found; available wallets: ava, bobby, super\n\
+         hint: use an available wallet or add one with `l1x-forge account create`"
+    );
+}
+
+#[test]
+fn test_install_unknown_chain_type_is_config_error() {
+    let cmd = install_cmd("ft_main", &[]);
+
+    let err = exec_with_toolkit_config(&cmd, "mainnet").unwrap_err();
+
+    let config_err = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<L1XConfigError>())
+        .unwrap();
+    assert_eq!(
+        config_err.to_string().lines().next(),
+        Some("chain type 'mainnet' not found; available chain types: local")
+    );
+    assert!(config_err
+        .to_string()
+        .contains("hint: pass --network or set L1X_CFG_CHAIN_TYPE"));
+}
+
+#[test]
+fn test_install_network_flag_overrides_chain_type() {
+    // Arrange
+    let cmd = install_cmd("ft_main", &["--network", "mainnet"]);
+
+    // Act
+    let err = exec_with_toolkit_config(&cmd, "local").unwrap_err();
+
+    // Assert
+    let config_err = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<L1XConfigError>())
+        .unwrap();
+    assert_eq!(
+        config_err.to_string().lines().next(),
+        Some("chain type 'mainnet' not found; available chain types: local")
+    );
+}
+
+#[test]
+fn test_dumped_payloads_sign_to_the_in_memory_request() {
+    // Arrange
+    let dump_dir = tempfile::tempdir().unwrap();
+    let deploy_address: AddressStr =
+        "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".parse().unwrap();
+    let payloads = [
+        (
+            "ebpf-deploy-ft",
+            ForgeClient::ebpf_deploy_txn(
+                vec![0x7f, 0x45, 0x4c, 0x46],
+                types::AccessType::PRIVATE,
+                types::ContractType::L1XVM,
+            )
+            .unwrap(),
+        ),
+        (
+            "init-ft_main",
+            init_payload(
+                &deploy_address,
+                types::U8s::Text(r#"{"supply":"1000"}"#.to_string()),
+            ),
+        ),
+        (
+            "evm-deploy-erc20",
+            ForgeClient::evm_deploy_txn(
+                vec![0x60, 0x80],
+                vec![0x01],
+                "s",
+                types::AccessType::PUBLIC,
+            )
+            .unwrap(),
+        ),
+    ];
+
+    for (name, txn) in payloads {
+        // Act
+        let dump_path = dump_payload(dump_dir.path(), name, &txn).unwrap();
+        let from_file = l1x_common::load_submit_txn_req(
+            dump_path.to_str().unwrap(),
+            None,
+            TEST_PRIVATE_KEY,
+            100,
+            7,
+        )
+        .unwrap();
+        let in_memory = l1x_common::get_submit_txn_req(
+            txn,
+            TEST_PRIVATE_KEY,
+            100,
+            7,
+            l1x_common::SigningScheme::Canonical,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(
+            dump_path,
+            dump_dir.path().join(format!("cli-uc-{}.json", name))
+        );
+        assert_eq!(from_file, in_memory, "{}", name);
+    }
+}
+
+#[test]
+fn test_dump_payloads_needs_cli_scripts_only_when_given() {
+    let scripts_dir = tempfile::tempdir().unwrap();
+    let txn = init_payload(
+        &"0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".parse().unwrap(),
+        types::U8s::Text("{}".to_string()),
+    );
+    let dumped = install_cmd("ft_main", &["--dump-payloads"]);
+
+    env::remove_var(toolkit_config::CLI_SCRIPTS_ENV);
+    let undumped = install_cmd("ft_main", &[]).dump_payload("init", &txn);
+    let unset = dumped.dump_payload("init", &txn);
+    env::set_var(toolkit_config::CLI_SCRIPTS_ENV, scripts_dir.path());
+    let set = dumped.dump_payload("init", &txn);
+    env::remove_var(toolkit_config::CLI_SCRIPTS_ENV);
+
+    assert!(undumped.is_ok());
+    assert_eq!(
+        unset.unwrap_err().to_string(),
+        "--dump-payloads needs L1X_CFG_CLI_SCRIPTS to name the directory to write to"
+    );
+    assert!(set.is_ok());
+    let dumped_txn: types::Transaction = serde_json::from_str(
+        &fs::read_to_string(
+            scripts_dir.path().join("l1x-forge-cli/cli-uc-init.json"),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        serde_json::to_value(dumped_txn).unwrap(),
+        serde_json::to_value(txn).unwrap()
+    );
+}
+
+#[test]
+fn test_install_outcome_reports_both_phases() {
+    // Arrange
+    let cmd = install_cmd("ft_main", &["--skip-chain-check"]);
+    let created = "ab".repeat(20);
+
+    // Act
+    let (result, _, _) = install_against_node(
+        &cmd,
+        None,
+        json!({ "hash": "d1", "contract_address": created }),
+    );
+
+    // Assert
+    let outcome = result.unwrap();
+    let created: AddressStr = created.parse().unwrap();
+    assert_eq!(outcome.artifact_id, "ft");
+    assert_eq!(outcome.contract_id, "ft_main");
+    assert_eq!(outcome.network.as_deref(), Some("local"));
+    assert_eq!(outcome.deploy_tx_hash.as_deref(), Some("d1"));
+    assert_eq!(outcome.deploy_address, Some(created));
+    assert_eq!(outcome.init_tx_hash.as_deref(), Some("d1"));
+    assert_eq!(outcome.instance_address, Some(created));
+    assert!(outcome.elapsed_ms.deploy.is_some());
+    assert!(outcome.elapsed_ms.init.is_some());
+}
+
+#[test]
+fn test_skipped_install_reports_the_registered_instance() {
+    let cmd = install_cmd("ft_main", &["--skip-chain-check"]);
+
+    let (result, rpc_methods, _) =
+        install_against_node(&cmd, Some(REGISTRY), json!({ "hash": "d1" }));
+
+    let outcome = result.unwrap();
+    assert!(rpc_methods.is_empty());
+    assert_eq!(outcome.deploy_tx_hash, None);
+    assert_eq!(outcome.init_tx_hash, None);
+    assert_eq!(
+        outcome.instance_address,
+        Some("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".parse().unwrap())
+    );
+}
+
+#[test]
+fn test_evm_install_reuses_the_registered_deployment() {
+    let cmd = evm_install_cmd(&["--init-call", "8129fc1c"]);
+
+    let (result, rpc_methods, registry) = install_against_node(
+        &cmd,
+        Some(EVM_REGISTRY),
+        json!({ "hash": "e2", "contract_address": "ab".repeat(20) }),
+    );
+
+    let outcome = result.unwrap();
+    assert!(rpc_methods.is_empty());
+    assert_eq!(outcome.deploy_tx_hash, None);
+    assert_eq!(outcome.init_tx_hash, None);
+    assert_eq!(outcome.deploy_address, Some([0xcc; 20].into()));
+    assert_eq!(registry.as_deref(), Some(EVM_REGISTRY));
+}
+
+#[test]
+fn test_evm_force_install_redeploys_the_registered_deployment() {
+    // Arrange
+    let cmd = evm_install_cmd(&["--force"]);
+
+    // Act
+    let (result, rpc_methods, registry) = install_against_node(
+        &cmd,
+        Some(EVM_REGISTRY),
+        json!({ "hash": "e2", "contract_address": "ab".repeat(20) }),
+    );
+
+    // Assert
+    let outcome = result.unwrap();
+    assert!(rpc_methods.contains(&"l1x_submitTransaction".to_string()));
+    assert_eq!(outcome.deploy_tx_hash.as_deref(), Some("e2"));
+    assert_eq!(outcome.deploy_address, Some([0xab; 20].into()));
+    let registry = registry.unwrap();
+    assert!(registry.contains(&"ab".repeat(20)), "{}", registry);
+    assert!(!registry.contains(&"cc".repeat(20)), "{}", registry);
+}
+
+#[test]
+fn test_evm_install_deploys_then_makes_the_init_call() {
+    // Arrange
+    let cmd = evm_install_cmd(&["--init-call", "0x8129fc1c"]);
+
+    // Act
+    let (result, rpc_methods, _) = install_against_node(
+        &cmd,
+        None,
+        json!({ "hash": "e2", "contract_address": "ab".repeat(20) }),
+    );
+
+    // Assert
+    let outcome = result.unwrap();
+    let submits = rpc_methods
+        .iter()
+        .filter(|rpc_method| *rpc_method == "l1x_submitTransaction")
+        .count();
+    assert_eq!(submits, 2);
+    assert_eq!(outcome.deploy_address, Some([0xab; 20].into()));
+    assert_eq!(outcome.init_tx_hash.as_deref(), Some("e2"));
+    assert!(outcome.elapsed_ms.init.is_some());
+    assert_eq!(outcome.instance_address, None);
+}
+
+#[test]
+fn test_evm_install_without_init_call_submits_the_deployment_only() {
+    let cmd = evm_install_cmd(&[]);
+
+    let (result, rpc_methods, _) = install_against_node(
+        &cmd,
+        None,
+        json!({ "hash": "e2", "contract_address": "ab".repeat(20) }),
+    );
+
+    let outcome = result.unwrap();
+    assert_eq!(
+        rpc_methods
+            .iter()
+            .filter(|rpc_method| *rpc_method == "l1x_submitTransaction")
+            .count(),
+        1
+    );
+    assert_eq!(outcome.init_tx_hash, None);
+}
+
+#[test]
+fn test_init_call_is_rejected_for_ebpf() {
+    let cmd = install_cmd("ft_main", &["--init-call", "8129fc1c"]);
+
+    let (result, rpc_methods, _) =
+        install_against_node(&cmd, None, json!({ "hash": "d1" }));
+
+    assert_eq!(result.unwrap_err().kind(), L1XInstallErrorKind::InvalidArgs);
+    assert!(rpc_methods.is_empty());
+}
+
+#[test]
+fn test_install_records_the_fee_used_of_the_receipts() {
+    // Arrange
+    let cmd = install_cmd("ft_main", &["--skip-chain-check"]);
+
+    // Act
+    let (result, _, registry) = install_against_receipt(
+        &cmd,
+        None,
+        json!({ "hash": "d1", "contract_address": "ab".repeat(20) }),
+        json!({ "block_number": 10, "status": "success", "fee_used": "37" }),
+    );
+
+    // Assert
+    let outcome = result.unwrap();
+    assert_eq!(outcome.deploy_fee_used, Some(37));
+    assert_eq!(outcome.init_fee_used, Some(37));
+    let registry: serde_yaml::Value =
+        serde_yaml::from_str(&registry.unwrap()).unwrap();
+    assert_eq!(
+        registry["networks"]["local"]["l1x_vm"]["ft"]["fee_used"],
+        serde_yaml::Value::from("37")
+    );
+}
+
+#[test]
+fn test_receipt_without_fee_records_no_fee_used() {
+    let cmd = install_cmd("ft_main", &["--skip-chain-check"]);
+
+    let (result, _, registry) = install_against_node(
+        &cmd,
+        None,
+        json!({ "hash": "d1", "contract_address": "ab".repeat(20) }),
+    );
+
+    let outcome = result.unwrap();
+    assert_eq!(outcome.deploy_fee_used, None);
+    assert_eq!(outcome.init_fee_used, None);
+    assert!(!registry.unwrap().contains("fee_used"));
+}
+
+/// Outcome of an install that submitted the deployment `deploy_tx_hash`
+/// and an initialization, with the fees their receipts report
+fn outcome_with_fees(
+    deploy_tx_hash: &str,
+    deploy_fee_used: Option<u128>,
+    init_fee_used: Option<u128>,
+) -> L1XInstallOutcome {
+    L1XInstallOutcome {
+        deploy_tx_hash: Some(deploy_tx_hash.to_string()),
+        deploy_fee_used,
+        init_tx_hash: Some(format!("init-{}", deploy_tx_hash)),
+        init_fee_used,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_fee_used_total_counts_a_shared_deployment_once() {
+    // Arrange
+    let outcomes = [
+        outcome_with_fees("d1", Some(100), Some(10)),
+        outcome_with_fees("d1", Some(100), Some(20)),
+        outcome_with_fees("d2", Some(200), None),
+        L1XInstallOutcome::default(),
+    ];
+
+    // Act
+    let total = L1XFeeUsedTotal::of_outcomes(&outcomes);
+
+    // Assert
+    assert_eq!(
+        total,
+        L1XFeeUsedTotal { fee_used: 330, transactions: 5, unreported: 1 }
+    );
+    assert_eq!(
+        total.to_string(),
+        "Fee used :: 330 over 5 transaction(s), 1 without a fee in its receipt"
+    );
+}
+
+#[test]
+fn test_fee_used_total_of_nothing_submitted() {
+    let total = L1XFeeUsedTotal::of_outcomes(&[L1XInstallOutcome::default()]);
+
+    assert_eq!(total, L1XFeeUsedTotal::default());
+    assert_eq!(total.to_string(), "Fee used :: 0 over 0 transaction(s)");
+}
+
+#[test]
+fn test_install_outcome_json_snapshot() {
+    let outcome = L1XInstallOutcome {
+        artifact_id: "ft".to_string(),
+        contract_id: "ft_main".to_string(),
+        network: Some("local".to_string()),
+        deploy_tx_hash: Some("d1".to_string()),
+        deploy_address: Some(
+            "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".parse().unwrap(),
+        ),
+        init_tx_hash: None,
+        instance_address: None,
+        deploy_fee: None,
+        init_fee: None,
+        deploy_fee_used: Some(37),
+        init_fee_used: None,
+        elapsed_ms: L1XInstallTimings {
+            deploy: Some(120),
+            init: None,
+            total: 150,
+        },
+    };
+
+    assert_eq!(
+        serde_json::to_string_pretty(&outcome).unwrap(),
+        r#"{
+  "artifact_id": "ft",
+  "contract_id": "ft_main",
+  "network": "local",
+  "deploy_tx_hash": "d1",
+  "deploy_address": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+  "init_tx_hash": null,
+  "instance_address": null,
+  "deploy_fee_used": 37,
+  "init_fee_used": null,
+  "elapsed_ms": {
+    "deploy": 120,
+    "init": null,
+    "total": 150
+  }
+}"#
+    );
+}
+
+#[test]
+fn test_install_failure_json_snapshot() {
+    let failures = [
+        (
+            L1XVmContractInstallError::from(L1XConfigError(
+                "wallet 'alice' not found".to_string(),
+            )),
+            r#"{"error_kind":"config","message":"wallet 'alice' not found"}"#,
+        ),
+        (
+            install_cmd("ft_main", &["--init-args", "{"])
+                .init_args()
+                .unwrap_err(),
+            r#"{"error_kind":"invalid_args","message":"Invalid init arguments of --init-args :: not JSON, EOF while parsing an object at line 1 column 1"}"#,
+        ),
+        (
+            L1XVmContractInstallError::created_address(
+                "eBPF Contract Deploy",
+                &DeployResult {
+                    hash: "d9".to_string(),
+                    address: None,
+                    events: Vec::new(),
+                    fee: ChosenFee::fixed(100),
+                    fee_used: None,
+                },
+            )
+            .unwrap_err(),
+            r#"{"error_kind":"missing_address","message":"eBPF Contract Deploy Failed: the node reported no contract address for d9, the registry is unchanged"}"#,
+        ),
+    ];
+
+    for (err_code, snapshot) in failures {
+        assert_eq!(
+            serde_json::to_string(&L1XInstallFailure::from(&err_code)).unwrap(),
+            snapshot
+        );
+    }
+}
+
+#[test]
+fn test_missing_artifact_is_an_artifact_io_error() {
+    let cmd = TestCli::parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "missing",
+        "--contract-id",
+        "ft_main",
+        "--owner",
+        "super",
+        "--salt",
+        "",
+        "--skip-chain-check",
+    ])
+    .install;
+
+    let (result, rpc_methods, _) =
+        install_against_node(&cmd, None, json!({ "hash": "d1" }));
+
+    let err = result.unwrap_err();
+    match &err {
+        L1XVmContractInstallError::ArtifactIo { stage, path, .. } => {
+            assert_eq!(stage, "eBPF Contract Deploy");
+            assert!(path.ends_with("missing"), "{}", path.display());
+        }
+        other => panic!("expected an artifact read error, got {:?}", other),
+    }
+    assert!(err.source().is_some());
+    assert_eq!(err.exit_code(), 3);
+    assert!(rpc_methods.is_empty());
+}
+
+#[test]
+fn test_unknown_account_is_a_nonce_fetch_error() {
+    let cmd = install_cmd("ft_main", &["--skip-chain-check"]);
+
+    let (result, _, registry) = install_against(
+        &cmd,
+        None,
+        vec![("l1x_getAccountState", json!({ "account_state": null }))],
+        |_| {},
+    );
+
+    let err = result.unwrap_err();
+    assert!(
+        matches!(&err, L1XVmContractInstallError::NonceFetch { stage, .. } if stage == "eBPF Contract Deploy"),
+        "{:?}",
+        err
+    );
+    assert_eq!(err.exit_code(), 4);
+    assert_eq!(registry, None);
+}
+
+#[test]
+fn test_unparsable_submit_response_is_a_response_parse_error() {
+    let cmd = install_cmd("ft_main", &["--skip-chain-check"]);
+
+    let (result, _, _) = install_against_node(&cmd, None, json!(42));
+
+    let err = result.unwrap_err();
+    assert!(
+        matches!(err, L1XVmContractInstallError::ResponseParse { .. }),
+        "{:?}",
+        err
+    );
+    assert_eq!(err.kind(), L1XInstallErrorKind::Rpc);
+}
+
+#[test]
+fn test_unwritable_registry_is_a_registry_update_error() {
+    let cmd = install_cmd("ft_main", &["--skip-chain-check"]);
+    let submitted =
+        json!({ "hash": "d1", "contract_address": "ab".repeat(20) });
+
+    let (result, rpc_methods, _) = install_against(
+        &cmd,
+        None,
+        vec![
+            ("l1x_submitTransaction", submitted),
+            ("l1x_getEvents", json!({ "events_data": [[1]] })),
+            ("l1x_getTransactionReceipt", success_receipt()),
+            (
+                "l1x_getAccountState",
+                json!({ "account_state": { "balance": "5", "nonce": "7", "account_type": 0 } }),
+            ),
+        ],
+        |ws_home| {
+            fs::create_dir_all(
+                ws_home.join("l1x-conf/config-contract-address-registry.yaml"),
+            )
+            .unwrap();
+        },
+    );
+
+    let err = result.unwrap_err();
+    match &err {
+        L1XVmContractInstallError::RegistryUpdate { stage, hash, .. } => {
+            assert_eq!(stage, "eBPF Contract Deploy");
+            assert_eq!(hash, "d1");
+        }
+        other => panic!("expected a registry update error, got {:?}", other),
+    }
+    assert!(rpc_methods.contains(&"l1x_submitTransaction".to_string()));
+    assert_eq!(err.exit_code(), 5);
+}
+
+#[test]
+fn test_install_errors_keep_their_config_source() {
+    let err = L1XVmContractInstallError::from(L1XConfigError(
+        "wallet 'alice' not found".to_string(),
+    ));
+
+    assert_eq!(err.exit_code(), 2);
+    assert_eq!(
+        err.source()
+            .and_then(|source| source.downcast_ref::<L1XConfigError>())
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("wallet 'alice' not found")
+    );
+}
+
+#[test]
+fn test_malformed_wallet_key_is_a_clean_config_error() {
+    // Arrange
+    let cmd = TestCli::parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--owner",
+        "broken",
+        "--salt",
+        "",
+        "--skip-chain-check",
+    ])
+    .install;
+
+    // Act
+    let (result, logs) = with_captured_logs(|| {
+        install_against(&cmd, None, Vec::new(), write_broken_wallet)
+    });
+
+    // Assert
+    let (result, rpc_methods, _) = result;
+    let err = result.unwrap_err();
+    assert!(matches!(err, L1XVmContractInstallError::Config(_)), "{:?}", err);
+    assert!(
+        err.to_string().starts_with(
+            "Invalid private key from wallet 'broken': expected 32 hex encoded bytes"
+        ),
+        "{}",
+        err
+    );
+    assert!(rpc_methods.is_empty());
+    assert!(!logs.contains("5ec12e7"), "{}", logs);
+    assert!(!format!("{:?}", err).contains("5ec12e7"));
+}
+
+#[test]
+fn test_signer_logs_owner_and_key_length_only() {
+    let cmd = install_cmd("ft_main", &["--skip-chain-check", "--dry-run"]);
+
+    let (result, logs) = with_captured_logs(|| {
+        install_against_node(&cmd, None, json!({ "hash": "d1" }))
+    });
+
+    result.0.unwrap();
+    assert!(logs.contains("Signer :: owner super | 32 byte key"), "{}", logs);
+    assert!(!logs.contains(&"0".repeat(63)), "{}", logs);
+}
+
+/// Run `install_manifest` of a `--manifest` install on a fresh workspace
+/// holding the `ft` artifact, against a node answering every submission with
+/// `contract_address`. Returns the outcomes, the JSON-RPC methods the node
+/// received and the registry file afterwards.
+fn install_manifest_against(
+    manifest: &str,
+) -> (Vec<L1XManifestEntryOutcome>, Vec<String>, String) {
+    let cmd = TestCli::parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--manifest",
+        "contracts.yaml",
+        "--owner",
+        "super",
+        "--salt",
+        "",
+        "--skip-chain-check",
+    ])
+    .install;
+    let manifest =
+        L1XInstallManifest::parse("contracts.yaml", manifest).unwrap();
+
+    with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        let artifact_dir = toolkit_for(ws_home)
+            .artifact_dir(toolkit_config::L1XVMArtifactType::Ebpf);
+        fs::create_dir_all(&artifact_dir).unwrap();
+        fs::write(artifact_dir.join("ft"), "6080604052").unwrap();
+
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let server = start_mock_node(
+                ws_home,
+                vec![
+                    (
+                        "l1x_submitTransaction",
+                        json!({ "hash": "h1", "contract_address": "ab".repeat(20) }),
+                    ),
+                    ("l1x_getEvents", json!({ "events_data": [[1]] })),
+                    ("l1x_getTransactionReceipt", success_receipt()),
+                    (
+                        "l1x_getAccountState",
+                        json!({ "account_state": { "balance": "5", "nonce": "7", "account_type": 0 } }),
+                    ),
+                ],
+            )
+            .await;
+
+            let outcomes = cmd.install_manifest(&manifest).await;
+            let registry = fs::read_to_string(
+                ws_home.join("l1x-conf/config-contract-address-registry.yaml"),
+            )
+            .unwrap();
+            (outcomes, received_rpc_methods(&server).await, registry)
+        })
+    })
+}
+
+#[test]
+fn test_manifest_deploys_each_artifact_once() {
+    // Arrange
+    let manifest = r#"
+contracts:
+  - artifact_id: ft
+    contract_id: ft_usd
+    init_args: { symbol: "USD" }
+  - artifact_id: ft
+    contract_id: ft_eur
+    init_args: { symbol: "EUR" }
+"#;
+
+    // Act
+    let (outcomes, rpc_methods, registry) = install_manifest_against(manifest);
+
+    // Assert
+    let statuses: Vec<_> =
+        outcomes.iter().map(|outcome| outcome.status).collect();
+    assert_eq!(statuses, vec![L1XManifestEntryStatus::Installed; 2]);
+    let submissions = rpc_methods
+        .iter()
+        .filter(|rpc_method| *rpc_method == "l1x_submitTransaction")
+        .count();
+    assert_eq!(submissions, 3);
+    assert_eq!(
+        outcomes[0].outcome.as_ref().unwrap().deploy_tx_hash.as_deref(),
+        Some("h1")
+    );
+    assert_eq!(
+        outcomes[1].outcome.as_ref().unwrap().deploy_tx_hash.as_deref(),
+        Some("h1")
+    );
+    assert!(registry.contains("ft_usd"), "{}", registry);
+    assert!(registry.contains("ft_eur"), "{}", registry);
+}
+
+#[test]
+fn test_parallel_manifest_submits_increasing_nonces() {
+    // Arrange
+    let artifact_ids = ["ft", "nft", "dex"];
+    let cmd = TestCli::parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--manifest",
+        "contracts.yaml",
+        "--parallel",
+        "3",
+        "--owner",
+        "super",
+        "--salt",
+        "",
+        "--skip-chain-check",
+    ])
+    .install;
+    let manifest = L1XInstallManifest::parse(
+        "contracts.yaml",
+        &format!(
+            "contracts:\n{}",
+            artifact_ids
+                .iter()
+                .map(|id| format!(
+                    "  - artifact_id: {id}\n    contract_id: {id}_main\n"
+                ))
+                .collect::<String>()
+        ),
+    )
+    .unwrap();
+
+    // Act
+    let (outcomes, requests, instances) = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        let artifact_dir = toolkit_for(ws_home)
+            .artifact_dir(toolkit_config::L1XVMArtifactType::Ebpf);
+        fs::create_dir_all(&artifact_dir).unwrap();
+        for artifact_id in artifact_ids {
+            fs::write(artifact_dir.join(artifact_id), "6080604052").unwrap();
+        }
+
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+                let server = start_mock_node(
+                    ws_home,
+                    vec![
+                        (
+                            "l1x_submitTransaction",
+                            json!({ "hash": "h1", "contract_address": "ab".repeat(20) }),
+                        ),
+                        ("l1x_getEvents", json!({ "events_data": [[1]] })),
+                        ("l1x_getTransactionReceipt", success_receipt()),
+                        (
+                            "l1x_getAccountState",
+                            json!({ "account_state": { "balance": "5", "nonce": "7", "account_type": 0 } }),
+                        ),
+                    ],
+                )
+                .await;
+
+                let outcomes = cmd.install_manifest(&manifest).await;
+                let requests: Vec<Value> = server
+                    .received_requests()
+                    .await
+                    .unwrap()
+                    .iter()
+                    .map(|request| request.body_json().unwrap())
+                    .collect();
+                let toolkit = ToolkitConfig::from_env().unwrap();
+                let instances: Vec<_> = artifact_ids
+                    .iter()
+                    .map(|id| {
+                        toolkit
+                            .ebpf_contract_address_for(
+                                id,
+                                Some(&format!("{id}_main")),
+                            )
+                            .is_ok()
+                    })
+                    .collect();
+                (outcomes, requests, instances)
+            })
+    });
+
+    // Assert
+    assert!(outcomes
+        .iter()
+        .all(|outcome| outcome.status == L1XManifestEntryStatus::Installed));
+    let nonces: Vec<u128> = requests
+        .iter()
+        .filter(|request| request["method"] == "l1x_submitTransaction")
+        .map(|request| {
+            request["params"]["request"]["nonce"]
+                .as_str()
+                .unwrap()
+                .parse()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(nonces, (8..14).collect::<Vec<u128>>());
+    let nonce_fetches = requests
+        .iter()
+        .filter(|request| request["method"] == "l1x_getAccountState")
+        .count();
+    assert_eq!(nonce_fetches, 1);
+    assert_eq!(instances, vec![true; 3]);
+}
+
+#[test]
+fn test_manifest_reports_failed_entries_and_installs_the_others() {
+    // Arrange
+    let manifest = r#"
+contracts:
+  - artifact_id: missing
+    contract_id: missing_main
+  - artifact_id: ft
+    contract_id: ft_main
+"#;
+
+    // Act
+    let (outcomes, _, registry) = install_manifest_against(manifest);
+
+    // Assert
+    assert_eq!(outcomes[0].status, L1XManifestEntryStatus::Failed);
+    let failure = outcomes[0].error.as_ref().unwrap();
+    assert_eq!(failure.error_kind, L1XInstallErrorKind::InvalidArtifact);
+    assert!(outcomes[0]
+        .to_string()
+        .starts_with("missing_main of missing :: failed :: "));
+    assert_eq!(outcomes[1].status, L1XManifestEntryStatus::Installed);
+    let created: AddressStr = "ab".repeat(20).parse().unwrap();
+    assert_eq!(
+        outcomes[1].to_string(),
+        format!("ft_main of ft :: installed :: {}", created)
+    );
+    assert!(registry.contains("ft_main"), "{}", registry);
+    assert!(!registry.contains("missing_main"), "{}", registry);
+}
+
+#[test]
+fn test_manifest_conflicts_with_single_install_flags() {
+    for flags in [
+        &["--artifact-id", "ft"][..],
+        &["--contract-id", "ft_main"][..],
+        &["--init-args", "{}"][..],
+        &["--dry-run"][..],
+    ] {
+        let args = [
+            "vm-install-contract",
+            "--vm-type",
+            "ebpf",
+            "--manifest",
+            "contracts.yaml",
+            "--salt",
+            "",
+        ];
+
+        assert!(
+            TestCli::try_parse_from(args.iter().chain(flags)).is_err(),
+            "{:?}",
+            flags
+        );
+    }
+}
+
+#[test]
+fn test_deploy_failing_on_chain_leaves_registry_untouched() {
+    // Arrange
+    let cmd = install_cmd("ft_main", &["--skip-chain-check"]);
+
+    // Act
+    let (result, rpc_methods, registry) = install_against_receipt(
+        &cmd,
+        None,
+        json!({ "hash": "d1", "contract_address": "ab".repeat(20) }),
+        json!({ "block_number": 10, "status": "failed", "error": "out of fee" }),
+    );
+
+    // Assert
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "eBPF Contract Deploy Failed: Txn d1 failed on chain, registry not updated :: out of fee"
+    );
+    assert_eq!(err.kind(), L1XInstallErrorKind::TxFailed);
+    assert_eq!(err.exit_code(), 4);
+    let submissions = rpc_methods
+        .iter()
+        .filter(|rpc_method| *rpc_method == "l1x_submitTransaction")
+        .count();
+    assert_eq!(submissions, 1);
+    assert_eq!(registry, None);
+}
+
+#[test]
+fn test_no_wait_installs_without_checking_the_receipt() {
+    let cmd = install_cmd("ft_main", &["--skip-chain-check", "--no-wait"]);
+
+    let (result, rpc_methods, registry) = install_against_receipt(
+        &cmd,
+        None,
+        json!({ "hash": "d1", "contract_address": "ab".repeat(20) }),
+        json!({ "status": "failed", "error": "out of fee" }),
+    );
+
+    assert!(result.unwrap().instance_address.is_some());
+    assert!(!rpc_methods.contains(&"l1x_getTransactionReceipt".to_string()));
+    assert!(registry.unwrap().contains("ft_main"));
+}
+
+#[test]
+fn test_no_wait_conflicts_with_wait_for_finality() {
+    assert!(TestCli::try_parse_from([
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--salt",
+        "",
+        "--no-wait",
+        "--wait-for-finality",
+        "2",
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_protocol_flag_accepts_json_and_grpc() {
+    let parse = |protocol: &str| {
+        TestCli::try_parse_from([
+            "vm-install-contract",
+            "--vm-type",
+            "ebpf",
+            "--artifact-id",
+            "ft",
+            "--contract-id",
+            "ft_main",
+            "--salt",
+            "",
+            "--protocol",
+            protocol,
+        ])
+    };
+
+    assert!(parse("json").is_ok());
+    assert!(parse("grpc").is_ok());
+    assert!(parse("websocket").is_err());
+}
+
+#[test]
+fn test_fee_limit_flag_accepts_auto_and_a_number() {
+    let parse = |fee_limit: &str| {
+        TestCli::try_parse_from([
+            "vm-install-contract",
+            "--vm-type",
+            "ebpf",
+            "--artifact-id",
+            "ft",
+            "--contract-id",
+            "ft_main",
+            "--salt",
+            "",
+            "--fee_limit",
+            fee_limit,
+            "--fee-buffer-pct",
+            "30",
+        ])
+    };
+
+    assert!(parse("auto").is_ok());
+    assert!(parse("250").is_ok());
+    assert!(parse("lots").is_err());
+}
+
+/// `vm-install-contract` of `--artifact-path artifact_path` registered as
+/// the artifact `token`
+fn artifact_path_cmd(
+    artifact_path: &std::path::Path,
+    flags: &[&str],
+) -> L1XVmInstallContractCmd {
+    let args = [
+        "vm-install-contract",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "token",
+        "--artifact-path",
+        artifact_path.to_str().unwrap(),
+        "--contract-id",
+        "token_main",
+        "--owner",
+        "super",
+        "--salt",
+        "",
+    ];
+    TestCli::parse_from(args.iter().chain(flags)).install
+}
+
+#[test]
+fn test_install_from_an_artifact_path_outside_the_workspace() {
+    // Arrange
+    let build_dir = tempfile::tempdir().unwrap();
+    let artifact_file = build_dir.path().join("token.o");
+    fs::write(&artifact_file, b"\x7fELF fixture").unwrap();
+    let cmd = artifact_path_cmd(&artifact_file, &["--skip-chain-check"]);
+
+    // Act
+    let (result, _, registry) = install_against_node(
+        &cmd,
+        None,
+        json!({ "hash": "d1", "contract_address": "ab".repeat(20) }),
+    );
+
+    // Assert
+    assert_eq!(result.unwrap().artifact_id, "token");
+    let registry = registry.unwrap();
+    let artifact_file = fs::canonicalize(&artifact_file).unwrap();
+    for recorded in [
+        "token:".to_string(),
+        "token_main:".to_string(),
+        format!("artifact_path: {}", artifact_file.display()),
+        "artifact_sha256: 262d1940abd611bd872afa6bc61d3e49568f2819d9bb88dd2279dafdb0b8d978".to_string(),
+    ] {
+        assert!(registry.contains(&recorded), "{}", registry);
+    }
+}
+
+#[test]
+fn test_missing_artifact_path_fails_before_any_rpc_call() {
+    // Arrange
+    let build_dir = tempfile::tempdir().unwrap();
+    let artifact_file = build_dir.path().join("token.o");
+    let cmd = artifact_path_cmd(&artifact_file, &[]);
+
+    // Act
+    let (result, rpc_methods, registry) =
+        install_against_node(&cmd, None, json!({ "hash": "d1" }));
+
+    // Assert
+    match result.unwrap_err() {
+        L1XVmContractInstallError::ArtifactIo { path, .. } => {
+            assert_eq!(path, artifact_file)
+        }
+        other => panic!("expected an artifact read error, got {:?}", other),
+    }
+    assert!(rpc_methods.is_empty());
+    assert_eq!(registry, None);
+}
@@ -0,0 +1,328 @@
+use crate::cli_error::L1XConfigError;
+use crate::contract_sub_txn::{read_function_payload, L1XVMType};
+use crate::forge_client::{ForgeClient, ForgeClientConfig};
+use l1x_common::{toolkit_config::ToolkitConfig, types::AddressStr};
+use l1x_rpc::rpc_model::SubmitTransactionRequest;
+
+use anyhow::Result;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::Semaphore, task::JoinSet};
+use tracing::Instrument;
+
+/// Submit many calls of a contract function and report how fast they confirm
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "vm-stress")]
+pub struct L1XVmStressCmd {
+    #[clap(long = "vm-type", default_value = "ebpf")]
+    vm_type: L1XVMType,
+
+    #[clap(long = "owner")]
+    owner: String,
+
+    /// Network of l1x_chain_config.yaml to stress, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+
+    #[clap(long = "artifact-id")]
+    artifact_id: String,
+
+    #[clap(long = "contract-id")]
+    contract_id: String,
+
+    /// Function of the contract to call, `""` leaving the dispatch to the
+    /// payload like --raw-payload
+    #[clap(long = "function", required_unless_present = "raw_payload")]
+    function: Option<String>,
+
+    /// Call with an empty function name, for contracts dispatching on the
+    /// function payload alone
+    #[clap(
+        long = "raw-payload",
+        default_value_t = false,
+        conflicts_with = "function"
+    )]
+    raw_payload: bool,
+
+    /// Hex function payload, `-` reading it from stdin
+    #[clap(long = "function-payload")]
+    function_payload: String,
+
+    /// Number of transactions to submit
+    #[clap(long = "count")]
+    count: u64,
+
+    /// Submitted transactions per second
+    #[clap(long = "rate")]
+    rate: f64,
+
+    /// Submissions awaiting a node response at the same time
+    #[clap(long = "max-in-flight", default_value_t = 16)]
+    max_in_flight: usize,
+
+    /// Seconds to wait for the receipts once every transaction is submitted
+    #[clap(long = "receipt-timeout", default_value_t = 120)]
+    receipt_timeout: u64,
+
+    #[clap(long = "fee_limit", default_value_t = 100)]
+    fee_limit: u128,
+
+    #[clap(long = "req_id", default_value_t = 1)]
+    req_id: u64,
+}
+
+/// Pacing of a stress run
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct L1XStressSettings {
+    pub rate: f64,
+    pub max_in_flight: usize,
+    pub receipt_timeout: Duration,
+}
+
+/// Outcome of a stress run
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1XStressSummary {
+    pub submitted: usize,
+    pub confirmed: usize,
+    /// Transactions the node rejected or never confirmed
+    pub failed: usize,
+    pub p50_latency: Option<Duration>,
+    pub p95_latency: Option<Duration>,
+}
+
+impl L1XStressSummary {
+    /// Summarize `submitted` transactions of which `failed` were rejected,
+    /// `latencies` holding the submit to receipt time of the confirmed ones
+    pub fn new(
+        submitted: usize,
+        failed: usize,
+        mut latencies: Vec<Duration>,
+    ) -> Self {
+        latencies.sort_unstable();
+
+        L1XStressSummary {
+            submitted,
+            confirmed: latencies.len(),
+            failed: failed + submitted.saturating_sub(failed + latencies.len()),
+            p50_latency: percentile(&latencies, 50),
+            p95_latency: percentile(&latencies, 95),
+        }
+    }
+}
+
+impl std::fmt::Display for L1XStressSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "submitted: {}", self.submitted)?;
+        writeln!(f, "confirmed: {}", self.confirmed)?;
+        writeln!(f, "failed: {}", self.failed)?;
+        writeln!(f, "p50 latency: {:?}", self.p50_latency)?;
+        write!(f, "p95 latency: {:?}", self.p95_latency)
+    }
+}
+
+/// Nearest-rank percentile of the ascending `sorted` durations
+fn percentile(sorted: &[Duration], percent: usize) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let rank = (sorted.len() * percent + 99) / 100;
+    sorted.get(rank.max(1) - 1).copied()
+}
+
+/// Submit the pre-signed `requests` at the configured rate, then poll for
+/// their receipts until all are confirmed or the receipt timeout expires
+pub(crate) async fn run_stress(
+    client: Arc<ForgeClient>,
+    requests: Vec<SubmitTransactionRequest>,
+    settings: L1XStressSettings,
+) -> L1XStressSummary {
+    let submitted = requests.len();
+    let in_flight = Arc::new(Semaphore::new(settings.max_in_flight.max(1)));
+    let mut ticker =
+        tokio::time::interval(Duration::from_secs_f64(1.0 / settings.rate));
+    let mut submissions = JoinSet::new();
+
+    for request in requests {
+        ticker.tick().await;
+        let permit = in_flight
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the in-flight semaphore is never closed");
+        let client = client.clone();
+
+        submissions.spawn(async move {
+            let submitted_at = Instant::now();
+            let response = client.submit_signed(&request).await;
+            drop(permit);
+            (submitted_at, response)
+        });
+    }
+
+    let mut pending = Vec::new();
+    let mut failed = 0;
+    while let Some(submission) = submissions.join_next().await {
+        match submission {
+            Ok((submitted_at, Ok(response))) => {
+                pending.push((response.hash, submitted_at))
+            }
+            Ok((_, Err(err_code))) => {
+                tracing::warn!("Stress Txn rejected :: {}", err_code);
+                failed += 1;
+            }
+            Err(err_code) => {
+                tracing::warn!("Stress Txn task failed :: {}", err_code);
+                failed += 1;
+            }
+        }
+    }
+
+    tracing::info!(
+        "{} of {} Stress Txns accepted | Waiting for receipts ...",
+        pending.len(),
+        submitted
+    );
+
+    let deadline = Instant::now() + settings.receipt_timeout;
+    let mut latencies = Vec::new();
+    loop {
+        let mut still_pending = Vec::new();
+        for (hash, submitted_at) in pending {
+            match client.transaction_block(&hash).await {
+                Ok(Some(_)) => latencies.push(submitted_at.elapsed()),
+                Ok(None) => still_pending.push((hash, submitted_at)),
+                Err(err_code) => {
+                    tracing::debug!(
+                        "Txn {} | No receipt :: {}",
+                        hash,
+                        err_code
+                    );
+                    still_pending.push((hash, submitted_at));
+                }
+            }
+        }
+        pending = still_pending;
+
+        if pending.is_empty() || Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(client.config().poll_interval).await;
+    }
+
+    for (hash, _) in &pending {
+        tracing::warn!(
+            "Txn {} | Not confirmed after {:?}",
+            hash,
+            settings.receipt_timeout
+        );
+    }
+
+    L1XStressSummary::new(submitted, failed, latencies)
+}
+
+impl L1XVmStressCmd {
+    /// Function name the calls carry, empty with --raw-payload
+    pub(crate) fn function_name(&self) -> &str {
+        self.function.as_deref().unwrap_or_default()
+    }
+
+    pub async fn exec(&self) -> Result<()> {
+        let span = tracing::info_span!(
+            "vm-stress",
+            artifact_id = %self.artifact_id,
+            contract_id = %self.contract_id,
+            req_id = self.req_id
+        );
+
+        async {
+            tracing::debug!("L1X VM Stress With Args :: {:#?}!", &self);
+
+            if self.rate.is_nan() || self.rate <= 0.0 {
+                anyhow::bail!("--rate must be greater than 0");
+            }
+
+            let client = Arc::new(self.client()?);
+            let contract_address = self.contract_address()?.to_hex();
+            let function_payload =
+                read_function_payload(&self.function_payload)?;
+
+            let first_nonce = client.next_nonce().await?;
+            let requests = (0..u128::from(self.count))
+                .map(|offset| {
+                    client.sign_function_call(
+                        &contract_address,
+                        self.function_name(),
+                        &function_payload,
+                        first_nonce + offset,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            tracing::info!(
+                "Signed {} Stress Txns from nonce {}",
+                requests.len(),
+                first_nonce
+            );
+
+            let summary = run_stress(
+                client,
+                requests,
+                L1XStressSettings {
+                    rate: self.rate,
+                    max_in_flight: self.max_in_flight,
+                    receipt_timeout: Duration::from_secs(self.receipt_timeout),
+                },
+            )
+            .await;
+
+            println!("{}", summary);
+
+            Ok(())
+        }
+        .instrument(span)
+        .await
+    }
+
+    fn client(&self) -> Result<ForgeClient, L1XConfigError> {
+        let toolkit =
+            ToolkitConfig::from_env()?.with_network(self.network.as_deref());
+        let end_point = toolkit.active_chain_json_rpc_endpoint()?;
+        let private_key = toolkit.wallet_priv_key(&self.owner)?;
+
+        let client_config = ForgeClientConfig {
+            fee_limit: self.fee_limit,
+            req_id: self.req_id,
+            ..ForgeClientConfig::new(&end_point, &private_key)
+        };
+
+        ForgeClient::new(client_config).map_err(|err_code| {
+            L1XConfigError(format!(
+                "Invalid private key for wallet '{}': {}",
+                &self.owner, err_code
+            ))
+        })
+    }
+
+    fn contract_address(&self) -> Result<AddressStr> {
+        let toolkit =
+            ToolkitConfig::from_env().map_err(L1XConfigError::from)?;
+        let contract_address = toolkit
+            .contract_address_for(
+                self.vm_type.into(),
+                &self.artifact_id,
+                Some(&self.contract_id),
+            )
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "L1X VM Stress Failed: Unknown Contract Address for {}",
+                    self.artifact_id
+                )
+            })?;
+
+        Ok(contract_address)
+    }
+}
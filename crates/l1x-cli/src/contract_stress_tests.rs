@@ -0,0 +1,151 @@
+use crate::contract_stress::*;
+use crate::forge_client::{ForgeClient, ForgeClientConfig};
+use crate::forge_client_tests::rpc_result;
+
+use clap::Parser;
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer};
+
+const TEST_PRIVATE_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    stress: L1XVmStressCmd,
+}
+
+fn millis(values: &[u64]) -> Vec<Duration> {
+    values.iter().copied().map(Duration::from_millis).collect()
+}
+
+#[test]
+fn test_stress_summary_percentiles() {
+    // Arrange
+    let latencies = millis(&[
+        100, 20, 30, 40, 50, 60, 70, 80, 90, 10, 110, 120, 130, 140, 150, 160,
+        170, 180, 190, 200,
+    ]);
+
+    // Act
+    let summary = L1XStressSummary::new(22, 1, latencies);
+
+    // Assert
+    assert_eq!(
+        summary,
+        L1XStressSummary {
+            submitted: 22,
+            confirmed: 20,
+            failed: 2,
+            p50_latency: Some(Duration::from_millis(100)),
+            p95_latency: Some(Duration::from_millis(190)),
+        }
+    );
+}
+
+#[test]
+fn test_stress_summary_without_confirmations() {
+    let summary = L1XStressSummary::new(3, 3, Vec::new());
+
+    assert_eq!(summary.confirmed, 0);
+    assert_eq!(summary.failed, 3);
+    assert_eq!(summary.p50_latency, None);
+}
+
+#[test]
+fn test_stress_needs_a_function_or_raw_payload() {
+    let args = [
+        "vm-stress",
+        "--owner",
+        "alice",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--function-payload",
+        "0x00ff",
+        "--count",
+        "3",
+        "--rate",
+        "10",
+    ];
+
+    let missing = TestCli::try_parse_from(args);
+    let function =
+        TestCli::try_parse_from(args.iter().chain(&["--function", "transfer"]));
+    let raw_payload =
+        TestCli::try_parse_from(args.iter().chain(&["--raw-payload"]));
+    let both = TestCli::try_parse_from(args.iter().chain(&[
+        "--raw-payload",
+        "--function",
+        "transfer",
+    ]));
+
+    assert!(missing.is_err());
+    assert_eq!(function.unwrap().stress.function_name(), "transfer");
+    assert_eq!(raw_payload.unwrap().stress.function_name(), "");
+    assert!(both.is_err());
+}
+
+#[tokio::test]
+async fn test_run_stress_submits_consecutive_nonces() {
+    // Arrange
+    let server = MockServer::start().await;
+    for nonce in ["7", "8", "9"] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "l1x_submitTransaction",
+                "params": { "request": { "nonce": nonce } }
+            })))
+            .respond_with(rpc_result(
+                json!({ "hash": format!("hash{}", nonce) }),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            json!({ "method": "l1x_getTransactionReceipt" }),
+        ))
+        .respond_with(rpc_result(json!({ "block_number": "12" })))
+        .mount(&server)
+        .await;
+    let client = Arc::new(
+        ForgeClient::new(ForgeClientConfig {
+            poll_interval: Duration::from_millis(10),
+            ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+        })
+        .unwrap(),
+    );
+    let requests = (7..10)
+        .map(|nonce| {
+            client.sign_function_call(
+                &"aa".repeat(20),
+                "transfer",
+                "7b7d",
+                nonce,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // Act
+    let summary = run_stress(
+        client,
+        requests,
+        L1XStressSettings {
+            rate: 1000.0,
+            max_in_flight: 2,
+            receipt_timeout: Duration::from_secs(5),
+        },
+    )
+    .await;
+
+    // Assert
+    assert_eq!(summary.submitted, 3);
+    assert_eq!(summary.confirmed, 3);
+    assert_eq!(summary.failed, 0);
+}
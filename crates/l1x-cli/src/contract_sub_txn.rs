@@ -1,28 +1,26 @@
-use l1x_common::toolkit_config;
-use l1x_rpc::{
-    json as l1x_rpc_json,
-    rpc_model::{
-        GetEventsRequest, GetEventsResponse, SmartContractReadOnlyCallRequest,
-        SmartContractReadOnlyCallResponse, SubmitTransactionRequest,
-        SubmitTransactionResponse,
-    },
+use crate::chain_check::check_active_chain_id;
+use crate::cli_error::L1XConfigError;
+use crate::forge_client::{
+    CallKind, CallResult, ForgeClient, ForgeClientConfig, ForgeClientError,
 };
+use crate::signer::{signer_private_key, L1XPrivateKeyArg};
+use l1x_common::call_args::{encode_call_args, L1XArgsEncoding};
+use l1x_common::fee::{self, ChosenFee, L1XFeeLimit};
+use l1x_common::transport::L1XProtocol;
+use l1x_common::{read_payload, rpc, PayloadSource, TxnBuildError};
+use l1x_common::{toolkit_config::ToolkitConfig, types::AddressStr};
+
+pub use l1x_common::toolkit_config::L1XVMType;
 
 use anyhow::Result;
-use reqwest::{Client, RequestBuilder};
-use secp256k1::{Secp256k1, SecretKey};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::{
-    env, error::Error, fmt::Display, fs::File, io::Read, process::Command,
-    sync::Arc,
-};
+use std::{sync::Arc, time::Duration};
 use tokio::sync::RwLock;
+use tracing::Instrument;
 
 #[derive(Debug, thiserror::Error)]
 pub enum L1XVmSubTxnError {
-    #[error("Hex parse error: {0}")]
-    HexParseError(String),
     #[error("Request Creation error: {0}")]
     RequestCreationError(String),
     #[error("Post JSON RPC error: {0}")]
@@ -33,390 +31,274 @@ pub enum L1XVmSubTxnError {
     InValidNonceError(String),
     #[error("Contract Deployment error: {0}")]
     ContractDeploymentError(String),
+    #[error("Invalid Payload error: {0}")]
+    InvalidPayloadError(String),
+    #[error("Signing error: {0}")]
+    SigningError(String),
+    #[error("{0}")]
+    EventsTimeout(#[from] rpc::EventsTimeout),
+    #[error("{0}")]
+    ReceiptTimeout(#[from] rpc::ReceiptTimeout),
+    #[error("Sub Txn {hash} failed on chain: {message}")]
+    TxFailed { hash: String, message: String },
+    #[error("{0}")]
+    ConfigError(#[from] L1XConfigError),
+}
+
+impl From<ForgeClientError> for L1XVmSubTxnError {
+    fn from(err_code: ForgeClientError) -> Self {
+        match err_code {
+            ForgeClientError::RequestCreationError(msg) => {
+                L1XVmSubTxnError::RequestCreationError(msg)
+            }
+            ForgeClientError::InvalidPrivateKey(msg)
+            | ForgeClientError::SigningError(msg) => {
+                L1XVmSubTxnError::SigningError(msg)
+            }
+            ForgeClientError::InvalidPayload(msg) => {
+                L1XVmSubTxnError::InvalidPayloadError(msg)
+            }
+            ForgeClientError::InValidNonceError(msg) => {
+                L1XVmSubTxnError::InValidNonceError(msg)
+            }
+            ForgeClientError::PostJsonRpcError(msg)
+            | ForgeClientError::ReqIdMismatch(msg)
+            | ForgeClientError::FinalityTimeout(msg) => {
+                L1XVmSubTxnError::PostJsonRpcError(msg)
+            }
+            ForgeClientError::JsonParseError(msg) => {
+                L1XVmSubTxnError::JsonParseError(msg)
+            }
+            ForgeClientError::EventsTimeout(timeout) => {
+                L1XVmSubTxnError::EventsTimeout(timeout)
+            }
+            ForgeClientError::ReceiptTimeout(timeout) => {
+                L1XVmSubTxnError::ReceiptTimeout(timeout)
+            }
+            ForgeClientError::TxFailed { hash, message } => {
+                L1XVmSubTxnError::TxFailed { hash, message }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct L1XVmTxnResponse {
     pub status: u8,
     pub message: String,
+    /// Fee limit the transaction was signed with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<ChosenFee>,
 }
 
 #[derive(Debug)]
 struct L1XVmTxnExecutorInternal {
-    cfg_ws_home: String,
-    cfg_cli_scripts_base: String,
-    json_client: RequestBuilder,
-    private_key: String,
-    secret_key: SecretKey,
+    client: ForgeClient,
+    toolkit: ToolkitConfig,
 }
 
 impl L1XVmTxnExecutorInternal {
-    fn new(txn_cmd: &L1XVmSubTxnCmd) -> Self {
-        let cfg_ws_home = env::var("L1X_CFG_WS_HOME")
-            .expect("The L1X_CFG_WS_HOME environment variable must be set");
-
-        let cfg_cli_scripts_base = env::var("L1X_CFG_CLI_SCRIPTS")
-            .expect("The L1X_CFG_CLI_SCRIPTS environment variable must be set");
+    async fn new(txn_cmd: &L1XVmSubTxnCmd) -> Result<Self, L1XConfigError> {
+        let toolkit =
+            ToolkitConfig::from_env()?.with_network(txn_cmd.network.as_deref());
+        let end_point = toolkit.active_chain_json_rpc_endpoint()?;
+        let protocol = match txn_cmd.protocol {
+            Some(protocol) => protocol,
+            None => toolkit.active_protocol()?,
+        };
 
-        let end_point = toolkit_config::get_active_chain_json_rpc_endpoint();
+        let private_key = signer_private_key(
+            &toolkit,
+            txn_cmd.owner.as_deref(),
+            txn_cmd.private_key.as_ref(),
+        )?;
 
-        let json_client = Client::new().post(&end_point);
+        let client_config = ForgeClientConfig {
+            fee_limit: txn_cmd.fee_limit.fallback(),
+            fee_estimation: txn_cmd
+                .fee_limit
+                .estimation(txn_cmd.fee_buffer_pct, txn_cmd.fee_per_byte),
+            req_id: txn_cmd.req_id,
+            event_poll_interval: Duration::from_millis(txn_cmd.poll_interval),
+            event_wait_timeout: Duration::from_secs(txn_cmd.wait_timeout),
+            receipt_method: txn_cmd.receipt_method.clone(),
+            protocol,
+            rpc_retry: rpc::RetryPolicy::new(
+                txn_cmd.rpc_retries,
+                Duration::from_millis(txn_cmd.rpc_retry_delay),
+            ),
+            nonce: txn_cmd.nonce,
+            ..ForgeClientConfig::new(&end_point, &private_key)
+        };
 
-        let private_key = toolkit_config::get_wallet_priv_key(&txn_cmd.owner);
+        let client = ForgeClient::new(client_config).map_err(|err_code| {
+            L1XConfigError(format!("Invalid signing key: {}", err_code))
+        })?;
 
-        let secret_key = SecretKey::from_slice(
-            &hex::decode(&private_key)
-            .map_err(|err_code| {
-                log::error!("L1X eBPF Deployment Failed: Unable to hex decode private_key :: {:#?} err :: {:#?}",
-                    &private_key, err_code
-                );
-                err_code
-            }).unwrap()
-        )
-        .map_err(|err_code| {
-            log::error!("L1X eBPF Deployment Failed: Failed to parse provided private_key :: {:#?} err :: {:#?}",
-                &private_key, err_code
-            )
-        }).unwrap();
-
-        Self {
-            cfg_ws_home,
-            cfg_cli_scripts_base,
-            json_client,
-            private_key,
-            secret_key,
+        // The chain id is only served over JSON-RPC
+        if !txn_cmd.skip_chain_check && protocol.is_json() {
+            check_active_chain_id(&toolkit, &end_point).await?;
         }
+
+        Ok(Self { client, toolkit })
     }
 }
 
+/// Hex function payload, without quotes or `0x`, of a `--function-payload`
+/// argument, `-` reading it from stdin
+pub(crate) fn read_function_payload(
+    arg: &str,
+) -> Result<String, TxnBuildError> {
+    let hex_payload = match arg {
+        "-" => read_payload(PayloadSource::Stdin)?,
+        hex_payload => hex_payload.to_string(),
+    };
+    let hex_payload = hex_payload.trim().trim_matches('"');
+
+    Ok(hex_payload.strip_prefix("0x").unwrap_or(hex_payload).to_string())
+}
+
 #[derive(Debug)]
 struct L1XVmTxnExecutor {
     txn_cmd: L1XVmSubTxnCmd,
+    function_payload: String,
     internal_installer: Arc<RwLock<L1XVmTxnExecutorInternal>>,
 }
 
 impl L1XVmTxnExecutor {
-    fn new(txn_cmd: &L1XVmSubTxnCmd) -> Self {
-        let install_init = L1XVmTxnExecutorInternal::new(txn_cmd);
+    async fn new(
+        txn_cmd: &L1XVmSubTxnCmd,
+        function_payload: String,
+    ) -> Result<Self, L1XConfigError> {
+        let install_init = L1XVmTxnExecutorInternal::new(txn_cmd).await?;
         let internal_installer = Arc::new(RwLock::new(install_init));
-        L1XVmTxnExecutor { txn_cmd: txn_cmd.clone(), internal_installer }
-    }
-
-    fn clean_string(address_to_clean: &str) -> String {
-        // Trim the string and remove any leading or trailing quotes.
-        let trimmed_address = address_to_clean.trim().trim_matches('"');
-
-        // Remove the "0x" prefix from the address, if it exists.
-        let clean_address =
-            trimmed_address.strip_prefix("0x").unwrap_or(trimmed_address);
-
-        // Return the clean address.
-        clean_address.to_string()
-    }
-
-    fn create_txn_function_call(
-        contract_address: &str,
-        function_payload: &str,
-    ) -> Result<l1x_common::types::Transaction, L1XVmSubTxnError> {
-        Ok(l1x_common::types::Transaction::SmartContractFunctionCall {
-            contract_instance_address: l1x_common::types::U8s::Hex(
-                contract_address.parse().map_err(|err_code| {
-                    L1XVmSubTxnError::HexParseError(format!(
-                        "Sub Txn Failed: Hex File Parse Error :: {:#?}",
-                        err_code
-                    ))
-                })?,
-            ),
-            function: l1x_common::types::U8s::Text(Default::default()),
-            arguments: l1x_common::types::U8s::Hex(
-                function_payload.parse().map_err(|err_code| {
-                    L1XVmSubTxnError::HexParseError(format!(
-                        "Sub Txn Failed: Hex File Parse Error :: {:#?}",
-                        err_code
-                    ))
-                })?,
-            ),
-        })
-    }
-
-    fn create_ronly_txn_function_call(
-        contract_address: &str,
-        function_payload: &str,
-    ) -> Result<
-        l1x_common::types::SmartContractReadOnlyFunctionCall,
-        L1XVmSubTxnError,
-    > {
-        Ok(l1x_common::types::SmartContractReadOnlyFunctionCall {
-            contract_instance_address: l1x_common::types::U8s::Hex(
-                contract_address.parse().map_err(|err_code| {
-                    L1XVmSubTxnError::HexParseError(format!(
-                        "Read-Only Txn Failed: Hex File Parse Error :: {:#?}",
-                        err_code
-                    ))
-                })?,
-            ),
-            function: l1x_common::types::U8s::Text(Default::default()),
-            arguments: l1x_common::types::U8s::Hex(
-                function_payload.parse().map_err(|err_code| {
-                    L1XVmSubTxnError::HexParseError(format!(
-                        "Read-Only Txn Failed: Hex File Parse Error :: {:#?}",
-                        err_code
-                    ))
-                })?,
-            ),
+        Ok(L1XVmTxnExecutor {
+            txn_cmd: txn_cmd.clone(),
+            function_payload,
+            internal_installer,
         })
     }
 
-    fn create_submit_txn_request(
-        private_key: &str,
-        fee_limit: u128,
-        nonce: u128,
-        txn_function_call: l1x_common::types::Transaction,
-    ) -> Result<SubmitTransactionRequest, L1XVmSubTxnError> {
-        l1x_common::get_submit_txn_req(
-            txn_function_call,
-            private_key,
-            fee_limit,
-            nonce + 1,
-        )
-        .map_err(|err_code| {
-            L1XVmSubTxnError::RequestCreationError(format!(
-                "Sub Txn Failed: Unable to get_submit_txn_req :: {:#?}",
-                err_code
-            ))
-        })
-    }
-
-    async fn post_submit_txn_request(
-        json_client: &RequestBuilder,
-        method: &str,
-        request_json: &serde_json::Value,
-    ) -> Result<l1x_rpc_json::JsonRpcResponse, L1XVmSubTxnError> {
-        l1x_rpc_json::post_json_rpc(
-            json_client
-                .try_clone()
-                .expect("Sub Txn Failed: Unable to clone RequestBuilder"),
-            "l1x_submitTransaction",
-            json!({ "request": request_json }),
-        )
-        .await
-        .map_err(|err_code| {
-            L1XVmSubTxnError::PostJsonRpcError(format!(
-                "Sub Txn Failed: Unable to post_json_rpc {:#?}",
-                err_code
-            ))
-        })
-    }
-
-    fn print_transaction_status(txn_response_message: &[u8]) {
+    /// Print the status of a call, with the fee limit of a submitted one
+    fn print_transaction_status(
+        txn_response_message: &[u8],
+        fee: Option<ChosenFee>,
+    ) {
         println!(
             "{}",
             json!({ "l1x-forge-txn-status":  L1XVmTxnResponse{
                 status: 0,
-                message: format!("{}", hex::encode(txn_response_message)),
+                message: hex::encode(txn_response_message),
+                fee,
             }})
         );
     }
 
-    async fn post_get_events_request(
-        json_client: &RequestBuilder,
-        method: &str,
-        tx_hash: &str,
-    ) -> Result<l1x_rpc_json::JsonRpcResponse, L1XVmSubTxnError> {
-        l1x_rpc_json::post_json_rpc(
-            json_client.try_clone().expect(
-                "Sub Txn Failed: Unable to clone RequestBuilder",
-            ),
-            "l1x_getEvents",
-            json!({"request": GetEventsRequest{tx_hash: tx_hash.to_string(), timestamp: 0u64}}),
-        )
-        .await
-        .map_err(|err_code| {
-            L1XVmSubTxnError::JsonParseError(format!(
-            "Sub Txn Failed: l1x_submitTransaction request failed {:#?}",
-            err_code
-            ))
-        })
-    }
-
     pub async fn l1x_vm_submit_txn(
         &self,
-        contract_address: &str,
+        contract_address: &AddressStr,
     ) -> Result<(), L1XVmSubTxnError> {
         let self_internal = self.internal_installer.read().await;
 
-        let clean_hex_contract_address = Self::clean_string(contract_address);
-
-        let clean_hex_function_payload =
-            Self::clean_string(&self.txn_cmd.function_payload);
-
-        let txn_function_call = Self::create_txn_function_call(
-            &clean_hex_contract_address,
-            &clean_hex_function_payload,
-        )?;
-
-        log::info!(
-            "Sub Txn Req for {:#?} => {:#?}",
-            &self.txn_cmd.artifact_id,
-            &txn_function_call
-        );
-
-        let nonce = l1x_rpc_json::get_nonce(
-            self_internal
-                .json_client
-                .try_clone()
-                .expect("Sub Txn Failed: Unable to clone RequestBuilder"),
-            &self_internal.secret_key,
-        )
-        .await
-        .map_err(|err_code| {
-            L1XVmSubTxnError::InValidNonceError(format!(
-                "Sub Txn Failed: Unable to get nounce {:#?}",
-                err_code
-            ))
-        })?;
-
-        let request = Self::create_submit_txn_request(
-            &self_internal.private_key,
-            self.txn_cmd.fee_limit,
-            nonce + 1,
-            txn_function_call,
-        )?;
-
-        let request_json =
-            serde_json::to_value(&request).map_err(|err_code| {
-                L1XVmSubTxnError::JsonParseError(format!(
-                "Sub Txn Failed: Can't serialize transaction to JSON :: {:#?}",
-                err_code
-                ))
-            })?;
-
-        let txn_response_result = Self::post_submit_txn_request(
-            &self_internal.json_client,
-            "l1x_submitTransaction",
-            &request_json,
-        )
-        .await?;
-
-        log::info!(
-            "Sub Txn Resp B4 Parsing for {:#?} => txn_response_result :: {:#?}",
+        tracing::info!(
+            "Sub Txn Req for {:#?} => {} :: function {:?}",
             &self.txn_cmd.artifact_id,
-            txn_response_result
+            contract_address,
+            self.txn_cmd.function_name()
         );
 
-        let txn_response = l1x_rpc_json::parse_response::<
-            SubmitTransactionResponse,
-        >(txn_response_result)
-        .map_err(|err_code| {
-            L1XVmSubTxnError::JsonParseError(format!(
-                "Sub Txn Failed: Unable to parse the response {:#?}",
-                err_code
-            ))
-        })?;
+        let txn_result = self_internal
+            .client
+            .call_contract(
+                &contract_address.to_hex(),
+                self.txn_cmd.function_name(),
+                &self.function_payload,
+                CallKind::SubTxn,
+            )
+            .await?;
 
-        log::info!(
+        tracing::trace!(
             "Sub Txn Resp for {:#?} => {:#?}",
             &self.txn_cmd.artifact_id,
-            txn_response
+            txn_result
         );
 
-        tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
-
-        let txn_event_response = Self::post_get_events_request(
-            &self_internal.json_client,
-            "l1x_getEvents",
-            &txn_response.hash,
-        )
-        .await?;
+        if let CallResult::Submitted(txn_response) = txn_result {
+            if !self.txn_cmd.no_wait {
+                self_internal
+                    .client
+                    .confirm_success(&txn_response.hash)
+                    .await?;
+            }
+            Self::print_transaction_status(
+                &txn_response.events.concat(),
+                Some(txn_response.fee),
+            );
+        }
 
-        log::info!(
-            "Sub Txn Event Resp B4 Parsing for {:#?} => txn_event_response :: {:#?}",
-            &self.txn_cmd.artifact_id,
-            txn_event_response
-        );
+        Ok(())
+    }
 
-        let txn_event_response_message: Vec<u8> = serde_json::from_value(
-            txn_event_response.result.unwrap()["events_data"].clone(),
-        )
-        .map_err(|err_code| {
-            L1XVmSubTxnError::JsonParseError(format!(
-                "Sub Txn Resp Failed: Unable to parse JSON Value {:#?}",
-                err_code
-            ))
-        })?;
+    /// Print the call of `contract_address` signed with the next nonce of
+    /// the account instead of submitting it
+    pub async fn l1x_vm_dry_run_txn(
+        &self,
+        contract_address: &AddressStr,
+    ) -> Result<(), L1XVmSubTxnError> {
+        let self_internal = self.internal_installer.read().await;
 
-        Self::print_transaction_status(&txn_event_response_message);
+        let txn = ForgeClient::create_txn_function_call(
+            &contract_address.to_hex(),
+            self.txn_cmd.function_name(),
+            &self.function_payload,
+        )?;
+        let nonce = self_internal.client.next_nonce().await?;
+        let dry_run =
+            self_internal.client.dry_run("Sub Txn", txn, nonce, None).await?;
 
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&dry_run).map_err(|err_code| {
+                L1XVmSubTxnError::JsonParseError(err_code.to_string())
+            })?
+        );
         Ok(())
     }
 
     pub async fn l1x_vm_read_only_call(
         &self,
-        contract_address: &str,
+        contract_address: &AddressStr,
     ) -> Result<(), L1XVmSubTxnError> {
         let self_internal = self.internal_installer.read().await;
 
-        let clean_hex_contract_address = Self::clean_string(contract_address);
-
-        let clean_hex_function_payload =
-            Self::clean_string(&self.txn_cmd.function_payload);
-
-        let ronly_function_call = Self::create_ronly_txn_function_call(
-            &clean_hex_contract_address,
-            &clean_hex_function_payload,
-        )?;
-
-        log::info!(
-            "Read-Only Txn Req for {:#?} => {:#?}",
+        tracing::info!(
+            "Read-Only Txn Req for {:#?} => {} :: function {:?}",
             &self.txn_cmd.artifact_id,
-            &ronly_function_call,
+            contract_address,
+            self.txn_cmd.function_name(),
         );
 
-        let ronly_function_call: l1x_rpc::rpc_model::SmartContractReadOnlyCallRequest =
-                ronly_function_call.try_into()
-                .map_err(|err_code| {
-                    L1XVmSubTxnError::RequestCreationError(format!(
-                        "Read-Only Txn Failed: Unable to create request :: {:#?}",
-                        err_code
-                    ))
-                })?;
-
-        let txn_result = l1x_rpc_json::post_json_rpc(
-            self_internal
-                .json_client
-                .try_clone()
-                .expect("Read-Only Txn Failed: Unable to clone RequestBuilder"),
-            "l1x_smartContractReadOnlyCall",
-            json!({ "request": ronly_function_call }),
-        )
-        .await
-        .map_err(|err_code| {
-            L1XVmSubTxnError::PostJsonRpcError(format!(
-                "Read-Only Txn Failed: Unable to post_json_rpc {:#?}",
-                err_code
-            ))
-        })?;
+        let txn_result = self_internal
+            .client
+            .call_contract(
+                &contract_address.to_hex(),
+                self.txn_cmd.function_name(),
+                &self.function_payload,
+                CallKind::ReadOnly,
+            )
+            .await?;
 
-        match txn_result.result {
-            Some(response_inner) => {
-                let response_message: Vec<u8> = serde_json::from_value(
-                    response_inner["result"].clone(),
-                )
-                .map_err(|err_code| {
-                    L1XVmSubTxnError::JsonParseError(format!(
-                                "Read-Only Txn Failed: Unable to parse JSON Value {:#?}",
-                                err_code
-                            ))
-                })?;
-
-                Self::print_transaction_status(&response_message);
+        match txn_result {
+            CallResult::ReadOnly(Some(response_message)) => {
+                Self::print_transaction_status(&response_message, None);
             }
-            None => {
+            _ => {
                 println!(
                     "{}",
                     json!({ "l1x-forge-txn-status":  L1XVmTxnResponse{
                         status: 1,
-                        message: format!("InValid Inner Response"),
+                        message: "InValid Inner Response".to_string(),
+                        fee: None,
                     }})
                 );
             }
@@ -426,24 +308,6 @@ impl L1XVmTxnExecutor {
     }
 }
 
-#[derive(Debug, Clone, Copy, clap::ValueEnum)]
-#[value(rename_all = "kebab-case")]
-pub enum L1XVMType {
-    #[clap(name = "ebpf")]
-    L1xVmEbpf,
-    #[clap(name = "evm")]
-    L1xVmEvm,
-}
-
-impl std::fmt::Display for L1XVMType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::L1xVmEbpf => write!(f, "L1xVmEbpf"),
-            Self::L1xVmEvm => write!(f, "L1xVmEvm"),
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, clap::ValueEnum)]
 #[value(rename_all = "kebab-case")]
 pub enum L1XCallType {
@@ -469,8 +333,19 @@ pub struct L1XVmSubTxnCmd {
     #[clap(long = "vm-type")]
     vm_type: L1XVMType,
 
+    /// Wallet signing the transactions, unless --private-key or
+    /// L1X_PRIVATE_KEY is given
     #[clap(long = "owner")]
-    owner: String,
+    owner: Option<String>,
+
+    /// Hex private key signing the transactions instead of the wallet
+    #[clap(long = "private-key")]
+    private_key: Option<L1XPrivateKeyArg>,
+
+    /// Network of l1x_chain_config.yaml to submit to, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
 
     #[clap(long = "artifact-id")]
     artifact_id: String,
@@ -481,43 +356,191 @@ pub struct L1XVmSubTxnCmd {
     #[clap(long = "call-type")]
     call_type: L1XCallType,
 
-    #[clap(long = "function-payload")]
-    function_payload: String,
-
-    #[clap(long = "fee_limit", default_value_t = 100)]
-    fee_limit: u128,
-
+    /// Function of the contract to call, `""` leaving the dispatch to the
+    /// payload like --raw-payload
+    #[clap(long = "function", required_unless_present = "raw_payload")]
+    function: Option<String>,
+
+    /// Call with an empty function name, for contracts dispatching on the
+    /// function payload alone
+    #[clap(
+        long = "raw-payload",
+        default_value_t = false,
+        conflicts_with = "function"
+    )]
+    raw_payload: bool,
+
+    /// Hex function payload, `-` reading it from stdin
+    #[clap(
+        long = "function-payload",
+        required_unless_present_any = ["args", "args_file"]
+    )]
+    function_payload: Option<String>,
+
+    /// JSON object or array of the call arguments, encoded with --encoding
+    /// instead of a hex --function-payload
+    #[clap(
+        long = "args",
+        conflicts_with_all = ["function_payload", "args_file"]
+    )]
+    args: Option<String>,
+
+    /// File holding the JSON arguments of --args, `-` reading them from stdin
+    #[clap(long = "args-file", conflicts_with = "function_payload")]
+    args_file: Option<String>,
+
+    /// Byte encoding of the --args or --args-file arguments
+    #[clap(long = "encoding", value_enum, default_value = "json")]
+    encoding: L1XArgsEncoding,
+
+    /// Fee limit of the transaction, `auto` to estimate it
+    #[clap(long = "fee_limit", default_value = "100")]
+    fee_limit: L1XFeeLimit,
+
+    /// Percentage added to the estimated fee limit of --fee_limit auto
+    #[clap(long = "fee-buffer-pct", default_value_t = fee::DEFAULT_FEE_BUFFER_PCT)]
+    fee_buffer_pct: u32,
+
+    /// Fee per byte of the transaction under --fee_limit auto, when the node
+    /// has no fee estimate
+    #[clap(long = "fee-per-byte", default_value_t = fee::DEFAULT_FEE_PER_BYTE)]
+    fee_per_byte: f64,
+
+    /// JSON-RPC id of the first request, each following request carries
+    /// the next id
     #[clap(long = "req_id", default_value_t = 1)]
     req_id: u64,
+
+    /// Milliseconds between two queries of the events of a transaction
+    #[clap(long = "poll-interval", default_value_t = 500)]
+    poll_interval: u64,
+
+    /// Seconds to wait for the events of a transaction
+    #[clap(long = "wait-timeout", default_value_t = 60)]
+    wait_timeout: u64,
+
+    /// Trust the node accepting the call instead of checking its receipt
+    #[clap(long = "no-wait", default_value_t = false)]
+    no_wait: bool,
+
+    /// JSON-RPC method returning the receipt of a transaction
+    #[clap(long = "receipt-method", default_value = rpc::DEFAULT_RECEIPT_METHOD)]
+    receipt_method: String,
+
+    /// Protocol to submit transactions with, the `protocol` of the network
+    /// otherwise
+    #[clap(long = "protocol", value_enum)]
+    protocol: Option<L1XProtocol>,
+
+    /// Retries of a JSON-RPC request failing on a timeout, a connection
+    /// error or a server error
+    #[clap(long = "rpc-retries", default_value_t = 3)]
+    rpc_retries: u32,
+
+    /// Milliseconds before the first retry of a JSON-RPC request, doubling
+    /// with every retry
+    #[clap(long = "rpc-retry-delay", default_value_t = 250)]
+    rpc_retry_delay: u64,
+
+    /// Submit without checking the chain id of the node against the network
+    #[clap(long = "skip-chain-check", default_value_t = false)]
+    skip_chain_check: bool,
+
+    /// Sign the call and print it as JSON instead of submitting it
+    #[clap(long = "dry-run", default_value_t = false)]
+    dry_run: bool,
+
+    /// Sign with this nonce instead of the next nonce of the account, to
+    /// recover from a nonce the node rejects
+    #[clap(long = "nonce")]
+    nonce: Option<u128>,
 }
 
 impl L1XVmSubTxnCmd {
+    /// Function name the call carries, empty with --raw-payload
+    pub(crate) fn function_name(&self) -> &str {
+        self.function.as_deref().unwrap_or_default()
+    }
+
+    /// Hex payload of the call, the --function-payload or the --args or
+    /// --args-file arguments encoded with --encoding
+    pub(crate) fn function_payload_hex(
+        &self,
+    ) -> Result<String, L1XVmSubTxnError> {
+        let invalid = L1XVmSubTxnError::InvalidPayloadError;
+
+        let (source, json_args) =
+            match (&self.function_payload, &self.args, &self.args_file) {
+                (Some(function_payload), _, _) => {
+                    return read_function_payload(function_payload)
+                        .map_err(|err_code| invalid(err_code.to_string()));
+                }
+                (None, Some(args), _) => ("--args", args.clone()),
+                (None, None, Some(args_file)) => (
+                    "--args-file",
+                    read_payload(PayloadSource::from_arg(args_file))
+                        .map_err(|err_code| invalid(err_code.to_string()))?,
+                ),
+                (None, None, None) => {
+                    return Err(invalid(
+                        "--function-payload, --args or --args-file is required"
+                            .to_string(),
+                    ));
+                }
+            };
+
+        let args = encode_call_args(&json_args, self.encoding).map_err(
+            |err_code| invalid(format!("Invalid {} :: {}", source, err_code)),
+        )?;
+        Ok(hex::encode(args))
+    }
+
     pub async fn exec(&self) -> Result<()> {
-        log::info!("Calling Submit Transactions With Args :: {:#?}!", &self);
-        self.l1x_vm_sub_txn().await?;
-        Ok(())
+        let span = tracing::info_span!(
+            "vm-sub-txn",
+            artifact_id = %self.artifact_id,
+            contract_id = %self.contract_id,
+            req_id = self.req_id
+        );
+
+        async {
+            tracing::debug!(
+                "Calling Submit Transactions With Args :: {:#?}!",
+                &self
+            );
+            self.l1x_vm_sub_txn().await?;
+            Ok(())
+        }
+        .instrument(span)
+        .await
     }
 }
 
 impl L1XVmSubTxnCmd {
     // Function to deploy and initialize a contract on ebpf VM
     async fn l1x_vm_sub_txn(&self) -> Result<(), L1XVmSubTxnError> {
+        if self.dry_run
+            && matches!(self.call_type, L1XCallType::L1xCallTypeReadOnly)
+        {
+            return Err(L1XVmSubTxnError::RequestCreationError(
+                "--dry-run only applies to --call-type sub-txn, read-only calls submit nothing"
+                    .to_string(),
+            ));
+        }
+
+        let function_payload = self.function_payload_hex()?;
+
         // Load executor settings
-        let txn_executor = L1XVmTxnExecutor::new(self);
-
-        let artifact_deploy_status = match self.vm_type {
-            L1XVMType::L1xVmEbpf => {
-                toolkit_config::get_toolkit_ebpf_contract_address_for(
-                    &self.artifact_id,
-                    Some(&self.contract_id),
-                )
-            }
-            L1XVMType::L1xVmEvm => {
-                toolkit_config::get_toolkit_evm_contract_address_for(
-                    &self.artifact_id,
-                    None,
-                )
-            }
+        let txn_executor =
+            L1XVmTxnExecutor::new(self, function_payload).await?;
+
+        let artifact_deploy_status = {
+            let self_internal = txn_executor.internal_installer.read().await;
+            self_internal.toolkit.contract_address_for(
+                self.vm_type.into(),
+                &self.artifact_id,
+                Some(&self.contract_id),
+            )
         };
 
         if artifact_deploy_status.is_err() {
@@ -526,6 +549,11 @@ impl L1XVmSubTxnCmd {
             )));
         } else {
             match self.call_type {
+                L1XCallType::L1xCallTypeSubTxn if self.dry_run => {
+                    txn_executor
+                        .l1x_vm_dry_run_txn(&artifact_deploy_status.unwrap())
+                        .await?;
+                }
                 L1XCallType::L1xCallTypeSubTxn => {
                     txn_executor
                         .l1x_vm_submit_txn(&artifact_deploy_status.unwrap())
@@ -0,0 +1,419 @@
+use crate::cli_error::L1XConfigError;
+use crate::contract_install_test::{
+    received_rpc_methods, start_mock_node, success_receipt, with_captured_logs,
+    with_temp_registry, write_broken_wallet, write_toolkit_config,
+    BROKEN_WALLET_KEY,
+};
+use crate::contract_sub_txn::*;
+
+use clap::Parser;
+use serde_json::{json, Value};
+use std::env;
+use wiremock::MockServer;
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    sub_txn: L1XVmSubTxnCmd,
+}
+
+#[test]
+fn test_sub_txn_unknown_owner_is_config_error() {
+    // Arrange
+    let cmd = TestCli::parse_from([
+        "vm-sub-txn",
+        "--vm-type",
+        "ebpf",
+        "--owner",
+        "alice",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--call-type",
+        "sub-txn",
+        "--function",
+        "transfer",
+        "--function-payload",
+        "payload.json",
+    ])
+    .sub_txn;
+
+    // Act
+    let err = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        env::set_var("L1X_CFG_CHAIN_TYPE", "local");
+
+        tokio::runtime::Runtime::new().unwrap().block_on(cmd.exec())
+    })
+    .unwrap_err();
+
+    // Assert
+    let config_err = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<L1XConfigError>())
+        .unwrap();
+    assert!(config_err.to_string().starts_with("wallet 'alice' not found"));
+}
+
+/// `vm-sub-txn --dry-run` of `call_type` on the `ft_main` instance of `ft`
+fn dry_run_cmd(call_type: &str) -> L1XVmSubTxnCmd {
+    TestCli::parse_from([
+        "vm-sub-txn",
+        "--vm-type",
+        "ebpf",
+        "--owner",
+        "super",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--call-type",
+        call_type,
+        "--function",
+        "transfer",
+        "--function-payload",
+        "0x00ff",
+        "--skip-chain-check",
+        "--dry-run",
+    ])
+    .sub_txn
+}
+
+/// Registry holding the `ft_main` instance of `ft`
+fn ft_main_registry() -> String {
+    format!(
+        "l1x_vm:\n  ft:\n    deploy_hash: d1\n    deploy_address: \"0x{}\"\n    instance:\n      ft_main:\n        inst_hash: i1\n        inst_address: \"0x{}\"\n",
+        "aa".repeat(20),
+        "bb".repeat(20)
+    )
+}
+
+#[test]
+fn test_sub_txn_dry_run_submits_nothing() {
+    // Arrange
+    let registry = ft_main_registry();
+    let cmd = dry_run_cmd("sub-txn");
+
+    // Act
+    let (result, rpc_methods) = with_temp_registry(
+        Some(&registry),
+        |ws_home| {
+            write_toolkit_config(ws_home);
+
+            tokio::runtime::Runtime::new().unwrap().block_on(async {
+                let server = start_mock_node(
+                    ws_home,
+                    vec![(
+                        "l1x_getAccountState",
+                        json!({ "account_state": { "balance": "5", "nonce": "7", "account_type": 0 } }),
+                    )],
+                )
+                .await;
+
+                let result = cmd.exec().await;
+                (result, received_rpc_methods(&server).await)
+            })
+        },
+    );
+
+    // Assert
+    result.unwrap();
+    assert_eq!(rpc_methods, vec!["l1x_getAccountState"]);
+}
+
+#[test]
+fn test_sub_txn_dry_run_rejects_read_only_calls() {
+    let cmd = dry_run_cmd("ronly");
+
+    let err = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(cmd.exec())
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Request Creation error: --dry-run only applies to --call-type sub-txn, read-only calls submit nothing"
+    );
+}
+
+#[test]
+fn test_sub_txn_malformed_wallet_key_is_a_clean_config_error() {
+    // Arrange
+    let cmd = TestCli::parse_from([
+        "vm-sub-txn",
+        "--vm-type",
+        "ebpf",
+        "--owner",
+        "broken",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--call-type",
+        "sub-txn",
+        "--function",
+        "transfer",
+        "--function-payload",
+        "0x00ff",
+        "--skip-chain-check",
+    ])
+    .sub_txn;
+
+    // Act
+    let (result, logs) = with_captured_logs(|| {
+        with_temp_registry(None, |ws_home| {
+            write_toolkit_config(ws_home);
+            write_broken_wallet(ws_home);
+
+            tokio::runtime::Runtime::new().unwrap().block_on(cmd.exec())
+        })
+    });
+
+    // Assert
+    let err = result.unwrap_err();
+    let config_err = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<L1XConfigError>())
+        .unwrap();
+    assert!(config_err
+        .to_string()
+        .starts_with("Invalid private key from wallet 'broken'"));
+    assert!(!format!("{:?}", err).contains(BROKEN_WALLET_KEY));
+    assert!(!logs.contains(&BROKEN_WALLET_KEY[..7]), "{}", logs);
+}
+
+/// Nonces of the transactions `server` received
+async fn submitted_nonces(server: &MockServer) -> Vec<String> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| request.body_json::<Value>().unwrap())
+        .filter(|body| body["method"] == "l1x_submitTransaction")
+        .map(|body| {
+            body["params"]["request"]["nonce"].as_str().unwrap().to_string()
+        })
+        .collect()
+}
+
+/// Submit a `vm-sub-txn` of the `ft_main` instance of `ft`, with `flags`, to
+/// a node reporting the account nonce 7 and `receipt` for the transaction.
+/// Returns the result, the JSON-RPC methods the node received and the
+/// nonces of the submitted transactions.
+fn submit_against_receipt(
+    flags: &[&str],
+    receipt: Value,
+) -> (anyhow::Result<()>, Vec<String>, Vec<String>) {
+    let args = [
+        "vm-sub-txn",
+        "--vm-type",
+        "ebpf",
+        "--owner",
+        "super",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--call-type",
+        "sub-txn",
+        "--function",
+        "transfer",
+        "--function-payload",
+        "0x00ff",
+        "--skip-chain-check",
+    ];
+    let cmd = TestCli::parse_from(args.iter().chain(flags)).sub_txn;
+
+    with_temp_registry(Some(&ft_main_registry()), |ws_home| {
+        write_toolkit_config(ws_home);
+
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let server = start_mock_node(
+                ws_home,
+                vec![
+                    ("l1x_submitTransaction", json!({ "hash": "s1" })),
+                    ("l1x_getEvents", json!({ "events_data": [[1]] })),
+                    ("l1x_getTransactionReceipt", receipt),
+                    (
+                        "l1x_getAccountState",
+                        json!({ "account_state": { "balance": "5", "nonce": "7", "account_type": 0 } }),
+                    ),
+                ],
+            )
+            .await;
+
+            let result = cmd.exec().await;
+            (
+                result,
+                received_rpc_methods(&server).await,
+                submitted_nonces(&server).await,
+            )
+        })
+    })
+}
+
+#[test]
+fn test_sub_txn_checks_the_receipt() {
+    let (result, rpc_methods, _) =
+        submit_against_receipt(&[], success_receipt());
+
+    result.unwrap();
+    assert_eq!(rpc_methods.last().unwrap(), "l1x_getTransactionReceipt");
+}
+
+#[test]
+fn test_sub_txn_failing_on_chain_is_an_error() {
+    // Act
+    let (result, _, _) = submit_against_receipt(
+        &[],
+        json!({ "block_number": 10, "status": "failed", "error": "contract trapped" }),
+    );
+
+    // Assert
+    let err = result.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<L1XVmSubTxnError>(),
+        Some(L1XVmSubTxnError::TxFailed { .. })
+    ));
+    assert_eq!(err.to_string(), "Sub Txn s1 failed on chain: contract trapped");
+}
+
+#[test]
+fn test_sub_txn_no_wait_skips_the_receipt() {
+    let (result, rpc_methods, _) = submit_against_receipt(
+        &["--no-wait"],
+        json!({ "status": "failed", "error": "contract trapped" }),
+    );
+
+    result.unwrap();
+    assert!(!rpc_methods.contains(&"l1x_getTransactionReceipt".to_string()));
+}
+
+#[test]
+fn test_sub_txn_submits_the_nonce_after_the_account_nonce() {
+    let (result, _, nonces) = submit_against_receipt(&[], success_receipt());
+
+    result.unwrap();
+    assert_eq!(nonces, vec!["8"]);
+}
+
+#[test]
+fn test_sub_txn_nonce_flag_overrides_the_account_nonce() {
+    // Act
+    let (result, rpc_methods, nonces) =
+        submit_against_receipt(&["--nonce", "42"], success_receipt());
+
+    // Assert
+    result.unwrap();
+    assert_eq!(nonces, vec!["42"]);
+    assert!(!rpc_methods.contains(&"l1x_getAccountState".to_string()));
+}
+
+#[test]
+fn test_sub_txn_needs_a_function_or_raw_payload() {
+    let args = [
+        "vm-sub-txn",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--call-type",
+        "sub-txn",
+        "--function-payload",
+        "0x00ff",
+    ];
+
+    let missing = TestCli::try_parse_from(args);
+    let raw_payload =
+        TestCli::try_parse_from(args.iter().chain(&["--raw-payload"]));
+    let both = TestCli::try_parse_from(args.iter().chain(&[
+        "--raw-payload",
+        "--function",
+        "transfer",
+    ]));
+
+    assert!(missing.is_err());
+    assert_eq!(raw_payload.unwrap().sub_txn.function_name(), "");
+    assert!(both.is_err());
+}
+
+/// `vm-sub-txn` of `transfer` on the `ft_main` instance of `ft`, with `flags`
+fn transfer_cmd(flags: &[&str]) -> L1XVmSubTxnCmd {
+    let args = [
+        "vm-sub-txn",
+        "--vm-type",
+        "ebpf",
+        "--artifact-id",
+        "ft",
+        "--contract-id",
+        "ft_main",
+        "--call-type",
+        "sub-txn",
+        "--function",
+        "transfer",
+    ];
+    TestCli::parse_from(args.iter().chain(flags)).sub_txn
+}
+
+#[test]
+fn test_sub_txn_args_are_encoded_into_the_payload() {
+    // Arrange
+    let args = r#"{"to":"ab","amount":5}"#;
+
+    // Act
+    let json = transfer_cmd(&["--args", args]).function_payload_hex();
+    let borsh = transfer_cmd(&["--args", args, "--encoding", "borsh"])
+        .function_payload_hex();
+
+    // Assert
+    assert_eq!(json.unwrap(), hex::encode(args));
+    assert_eq!(borsh.unwrap(), "0200000061620500000000000000");
+}
+
+#[test]
+fn test_sub_txn_args_file_is_encoded_into_the_payload() {
+    let args_file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(args_file.path(), "[true, \"ab\"]\n").unwrap();
+    let args_file = args_file.path().to_str().unwrap();
+
+    let borsh =
+        transfer_cmd(&["--args-file", args_file, "--encoding", "borsh"])
+            .function_payload_hex();
+
+    assert_eq!(borsh.unwrap(), "01020000006162");
+}
+
+#[test]
+fn test_sub_txn_invalid_args_are_a_payload_error() {
+    let payload = transfer_cmd(&["--args", "42"]).function_payload_hex();
+
+    assert!(matches!(
+        &payload,
+        Err(L1XVmSubTxnError::InvalidPayloadError(reason))
+            if reason.starts_with("Invalid --args :: expected a JSON object")
+    ));
+}
+
+#[test]
+fn test_sub_txn_args_conflict_with_the_function_payload() {
+    let args = ["vm-sub-txn", "--vm-type", "ebpf", "--artifact-id", "ft"];
+    let flags = [
+        "--contract-id",
+        "ft_main",
+        "--call-type",
+        "sub-txn",
+        "--raw-payload",
+        "--args",
+        "{}",
+        "--function-payload",
+        "0x00",
+    ];
+
+    let parsed = TestCli::try_parse_from(args.iter().chain(&flags));
+
+    assert!(parsed.is_err());
+}
@@ -0,0 +1,136 @@
+use crate::cli_error::L1XConfigError;
+use crate::contract_install::L1XVMType;
+use l1x_common::{toolkit_config, types::AddressStr};
+
+use anyhow::Result;
+
+/// Remove a contract (or one of its instances) from the contract address registry
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "vm-uninstall-contract")]
+pub struct L1XVmUninstallContractCmd {
+    #[clap(long = "vm-type", default_value = "ebpf")]
+    vm_type: L1XVMType,
+
+    #[clap(long = "artifact-id")]
+    artifact_id: String,
+
+    /// Instance of the eBPF or WASM deployment to remove, the whole artifact
+    /// entry when unset
+    #[clap(long = "contract-id")]
+    contract_id: Option<String>,
+
+    #[clap(long = "owner")]
+    owner: Option<String>,
+
+    /// Network of the registry entries, instead of L1X_CFG_CHAIN_TYPE or its
+    /// default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+
+    /// Also remove the contract on-chain, refused while no L1X transaction
+    /// type removes a deployed contract
+    #[clap(long = "on-chain", default_value_t = false)]
+    on_chain: bool,
+
+    #[clap(long = "dry-run", default_value_t = false)]
+    dry_run: bool,
+}
+
+impl L1XVmUninstallContractCmd {
+    fn remove_type(
+        &self,
+    ) -> Result<toolkit_config::L1XVMContractAddressRemoveType> {
+        let remove_type = match self.vm_type {
+            L1XVMType::L1xVmEbpf => {
+                toolkit_config::L1XVMContractAddressRemoveType::L1XEBPF_REMOVE {
+                    artifact_id: self.artifact_id.clone(),
+                    contract_id: self.contract_id.clone(),
+                }
+            }
+            L1XVMType::L1xVmWasm => {
+                toolkit_config::L1XVMContractAddressRemoveType::L1XWASM_REMOVE {
+                    artifact_id: self.artifact_id.clone(),
+                    contract_id: self.contract_id.clone(),
+                }
+            }
+            L1XVMType::L1xVmEvm => {
+                if self.contract_id.is_some() {
+                    anyhow::bail!(
+                        "EVM contracts have no instances, --contract-id is not supported for --vm-type evm"
+                    );
+                }
+                toolkit_config::L1XVMContractAddressRemoveType::L1XEVM_REMOVE {
+                    artifact_id: self.artifact_id.clone(),
+                }
+            }
+        };
+
+        Ok(remove_type)
+    }
+
+    /// Remove the registry entries of the artifact, or of its instance, from
+    /// the registry of `toolkit` and return them; with --dry-run the registry
+    /// is left unchanged
+    pub fn uninstall(
+        &self,
+        toolkit: &toolkit_config::ToolkitConfig,
+    ) -> Result<Vec<(String, AddressStr)>> {
+        // None of the L1X transaction types can remove or disable a deployed
+        // contract, refuse before the registry forgets its address
+        if self.on_chain {
+            anyhow::bail!(
+                "On-chain removal is not supported: no L1X transaction type removes a deployed {} contract, rerun without --on-chain to only update the registry",
+                self.vm_type
+            );
+        }
+
+        let removed = toolkit
+            .remove_contract_address_registry_entry(
+                self.remove_type()?,
+                self.dry_run,
+            )
+            .map_err(L1XConfigError::from)?;
+
+        if removed.is_empty() {
+            tracing::warn!(
+                "Nothing registered for artifact '{}'{}, registry is unchanged",
+                self.artifact_id,
+                self.contract_id
+                    .as_ref()
+                    .map(|cid| format!(" / contract '{}'", cid))
+                    .unwrap_or_default()
+            );
+        }
+
+        Ok(removed)
+    }
+
+    pub async fn exec(&self) -> Result<()> {
+        tracing::debug!("L1X VM Contract Uninstall With Args :: {:#?}!", &self);
+
+        let toolkit = toolkit_config::ToolkitConfig::from_env()
+            .map_err(L1XConfigError::from)?
+            .with_network(self.network.as_deref());
+
+        let removed = self.uninstall(&toolkit)?;
+        if removed.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(owner) = &self.owner {
+            tracing::info!(
+                "Registry teardown for '{}' requested by owner '{}'",
+                self.artifact_id,
+                owner
+            );
+        }
+
+        let action = if self.dry_run { "Would remove" } else { "Removed" };
+        removed.iter().for_each(|(entry, address)| {
+            println!("{} {} :: 0x{}", action, entry, address);
+        });
+        println!("On-chain state is untouched");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::contract_install_test::{toolkit_for, with_temp_registry};
+use crate::contract_uninstall::*;
+use crate::registry::registry_entries;
+
+use clap::Parser;
+use std::fs;
+
+const REGISTRY: &str = r#"
+l1x_vm:
+  ft:
+    deploy_hash: "d1"
+    deploy_address: "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+    instance:
+      ft_main:
+        inst_hash: "i1"
+        inst_address: "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+l1x_evm:
+  token:
+    deploy_hash: "d2"
+    deploy_address: "0xcccccccccccccccccccccccccccccccccccccccc"
+    instance: {}
+"#;
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    uninstall: L1XVmUninstallContractCmd,
+}
+
+fn uninstall_cmd(args: &[&str]) -> L1XVmUninstallContractCmd {
+    TestCli::parse_from(["vm-uninstall-contract"].iter().chain(args)).uninstall
+}
+
+/// Registry entries left in `ws_home`, as `artifact_id[/contract_id]`
+fn registered(ws_home: &std::path::Path) -> Vec<String> {
+    registry_entries(&toolkit_for(ws_home), None)
+        .unwrap()
+        .into_iter()
+        .map(|entry| match entry.contract_id {
+            Some(contract_id) => {
+                format!("{}/{}", entry.artifact_id, contract_id)
+            }
+            None => entry.artifact_id,
+        })
+        .collect()
+}
+
+#[test]
+fn test_uninstall_instance_keeps_the_deployment() {
+    // Arrange
+    let cmd =
+        uninstall_cmd(&["--artifact-id", "ft", "--contract-id", "ft_main"]);
+
+    // Act
+    let (removed, left) = with_temp_registry(Some(REGISTRY), |ws_home| {
+        (cmd.uninstall(&toolkit_for(ws_home)).unwrap(), registered(ws_home))
+    });
+
+    // Assert
+    assert_eq!(
+        removed,
+        vec![(
+            "l1x_vm.ft.instance.ft_main".to_string(),
+            "b".repeat(40).parse().unwrap()
+        )]
+    );
+    assert!(left.contains(&"ft".to_string()), "{:?}", left);
+    assert!(!left.contains(&"ft/ft_main".to_string()), "{:?}", left);
+}
+
+#[test]
+fn test_uninstall_artifact_removes_its_instances() {
+    // Arrange
+    let cmd = uninstall_cmd(&["--artifact-id", "ft"]);
+
+    // Act
+    let (removed, left) = with_temp_registry(Some(REGISTRY), |ws_home| {
+        (cmd.uninstall(&toolkit_for(ws_home)).unwrap(), registered(ws_home))
+    });
+
+    // Assert
+    let entries: Vec<&str> =
+        removed.iter().map(|(entry, _)| entry.as_str()).collect();
+    assert_eq!(entries, vec!["l1x_vm.ft.instance.ft_main", "l1x_vm.ft"]);
+    assert_eq!(left, vec!["token".to_string()]);
+}
+
+#[test]
+fn test_uninstall_evm_artifact() {
+    let cmd = uninstall_cmd(&["--vm-type", "evm", "--artifact-id", "token"]);
+
+    let (removed, left) = with_temp_registry(Some(REGISTRY), |ws_home| {
+        (cmd.uninstall(&toolkit_for(ws_home)).unwrap(), registered(ws_home))
+    });
+
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].0, "l1x_evm.token");
+    assert!(!left.contains(&"token".to_string()), "{:?}", left);
+}
+
+#[test]
+fn test_dry_run_leaves_the_registry_file_untouched() {
+    // Arrange
+    let cmd = uninstall_cmd(&["--artifact-id", "ft", "--dry-run"]);
+
+    // Act
+    let (removed, registry) = with_temp_registry(Some(REGISTRY), |ws_home| {
+        let removed = cmd.uninstall(&toolkit_for(ws_home)).unwrap();
+        let registry = fs::read_to_string(
+            ws_home.join("l1x-conf/config-contract-address-registry.yaml"),
+        )
+        .unwrap();
+        (removed, registry)
+    });
+
+    // Assert
+    assert_eq!(removed.len(), 2);
+    assert_eq!(registry, REGISTRY);
+}
+
+#[test]
+fn test_uninstall_of_unknown_entry_is_a_no_op() {
+    // Arrange
+    let cmd = uninstall_cmd(&["--artifact-id", "ft", "--contract-id", "nope"]);
+
+    // Act
+    let (removed, registry) = with_temp_registry(Some(REGISTRY), |ws_home| {
+        let removed = cmd.uninstall(&toolkit_for(ws_home)).unwrap();
+        let registry = fs::read_to_string(
+            ws_home.join("l1x-conf/config-contract-address-registry.yaml"),
+        )
+        .unwrap();
+        (removed, registry)
+    });
+
+    // Assert
+    assert!(removed.is_empty());
+    assert_eq!(registry, REGISTRY);
+}
+
+#[test]
+fn test_uninstall_without_registry_is_a_no_op() {
+    let cmd = uninstall_cmd(&["--artifact-id", "ft"]);
+
+    let removed = with_temp_registry(None, |ws_home| {
+        cmd.uninstall(&toolkit_for(ws_home))
+    });
+
+    assert!(removed.unwrap().is_empty());
+}
+
+#[test]
+fn test_evm_instance_uninstall_is_rejected() {
+    let cmd = uninstall_cmd(&[
+        "--vm-type",
+        "evm",
+        "--artifact-id",
+        "token",
+        "--contract-id",
+        "main",
+    ]);
+
+    let (uninstalled, left) = with_temp_registry(Some(REGISTRY), |ws_home| {
+        (cmd.uninstall(&toolkit_for(ws_home)), registered(ws_home))
+    });
+
+    assert!(uninstalled.unwrap_err().to_string().contains("no instances"));
+    assert!(left.contains(&"token".to_string()), "{:?}", left);
+}
+
+#[test]
+fn test_on_chain_uninstall_is_refused_before_touching_the_registry() {
+    // Arrange
+    let cmd = uninstall_cmd(&["--artifact-id", "ft", "--on-chain"]);
+
+    // Act
+    let (uninstalled, left) = with_temp_registry(Some(REGISTRY), |ws_home| {
+        (cmd.uninstall(&toolkit_for(ws_home)), registered(ws_home))
+    });
+
+    // Assert
+    assert!(uninstalled
+        .unwrap_err()
+        .to_string()
+        .contains("On-chain removal is not supported"));
+    assert_eq!(left.len(), 3);
+}
@@ -0,0 +1,307 @@
+use l1x_common::toolkit_config::{
+    L1XVMArtifactType, ToolkitConfig, CLI_SCRIPTS_ENV,
+};
+
+use anyhow::Result;
+use serde_json::json;
+use std::{env, future::Future, pin::Pin, time::Duration};
+
+/// Check the workspace configuration and the connection to the active chain
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "doctor")]
+pub struct L1XDoctorCmd {
+    /// Seconds to wait for the JSON-RPC endpoint
+    #[clap(long = "timeout", default_value_t = 5)]
+    timeout: u64,
+
+    /// Network of l1x_chain_config.yaml to check, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+}
+
+/// Outcome of a [`Check`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckOutcome {
+    Pass(String),
+    /// `hint` tells how to fix the failure
+    Fail {
+        reason: String,
+        hint: String,
+    },
+}
+
+impl CheckOutcome {
+    fn fail(reason: impl Into<String>, hint: impl Into<String>) -> Self {
+        CheckOutcome::Fail { reason: reason.into(), hint: hint.into() }
+    }
+
+    pub fn is_pass(&self) -> bool {
+        matches!(self, CheckOutcome::Pass(_))
+    }
+}
+
+type CheckFuture = Pin<Box<dyn Future<Output = CheckOutcome> + Send>>;
+
+/// A named check of `doctor`
+pub struct Check {
+    pub name: String,
+    run: Box<dyn Fn() -> CheckFuture + Send + Sync>,
+}
+
+impl Check {
+    pub fn new<F, Fut>(name: impl Into<String>, run: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CheckOutcome> + Send + 'static,
+    {
+        Check { name: name.into(), run: Box::new(move || Box::pin(run())) }
+    }
+
+    pub async fn run(&self) -> CheckOutcome {
+        (self.run)().await
+    }
+}
+
+/// Checks of `doctor` on `network`, or the active network when `None`, in
+/// the order they run
+pub fn default_checks(
+    timeout: Duration,
+    network: Option<String>,
+) -> Vec<Check> {
+    vec![
+        workspace_check(),
+        active_network_check(network.clone()),
+        optional_env_var_check(
+            CLI_SCRIPTS_ENV,
+            "only vm-install-contract --dump-payloads uses it",
+        ),
+        chain_config_check(),
+        wallet_config_check(),
+        registry_check(),
+        endpoint_check(timeout, network),
+        artifact_dir_check(L1XVMArtifactType::Ebpf),
+        artifact_dir_check(L1XVMArtifactType::Evm),
+    ]
+}
+
+/// Passes when `var` is set
+pub fn env_var_check(var: &'static str, hint: &'static str) -> Check {
+    Check::new(var, move || async move {
+        match env::var(var) {
+            Ok(value) => CheckOutcome::Pass(value),
+            Err(_) => CheckOutcome::fail(format!("{} is not set", var), hint),
+        }
+    })
+}
+
+/// Passes whether or not `var` is set, with `unset_note` as the detail
+/// when it is not
+pub fn optional_env_var_check(
+    var: &'static str,
+    unset_note: &'static str,
+) -> Check {
+    Check::new(var, move || async move {
+        match env::var(var) {
+            Ok(value) => CheckOutcome::Pass(value),
+            Err(_) => CheckOutcome::Pass(format!("not set, {}", unset_note)),
+        }
+    })
+}
+
+/// Passes when the workspace root resolves, from L1X_CFG_WS_HOME or the
+/// user configuration directory
+pub fn workspace_check() -> Check {
+    Check::new("workspace", || async {
+        match workspace_config() {
+            Ok(toolkit) => CheckOutcome::Pass(format!(
+                "{} ({})",
+                toolkit.paths().ws_home().display(),
+                toolkit.paths().ws_home_source()
+            )),
+            Err(outcome) => outcome,
+        }
+    })
+}
+
+/// Passes when `network`, else L1X_CFG_CHAIN_TYPE or the default_network of
+/// l1x_chain_config.yaml, names a network
+pub fn active_network_check(network: Option<String>) -> Check {
+    Check::new("active network", move || {
+        let network = network.clone();
+        async move {
+            let toolkit = match workspace_config() {
+                Ok(toolkit) => toolkit.with_network(network.as_deref()),
+                Err(outcome) => return outcome,
+            };
+
+            match toolkit.active_network() {
+                Ok(network) => CheckOutcome::Pass(network),
+                Err(err_code) => CheckOutcome::fail(
+                    err_code.to_string(),
+                    "Pass --network, export L1X_CFG_CHAIN_TYPE=<network> or set default_network in l1x_chain_config.yaml",
+                ),
+            }
+        }
+    })
+}
+
+/// Configuration of the workspace under check
+fn workspace_config() -> Result<ToolkitConfig, CheckOutcome> {
+    ToolkitConfig::from_env().map_err(|_| {
+        CheckOutcome::fail(
+            "L1X_CFG_WS_HOME is not set and there is no user configuration directory",
+            "export L1X_CFG_WS_HOME=<workspace>, then run `l1x-forge init-workspace`",
+        )
+    })
+}
+
+pub fn chain_config_check() -> Check {
+    Check::new("l1x_chain_config.yaml", || async {
+        let toolkit = match workspace_config() {
+            Ok(toolkit) => toolkit,
+            Err(outcome) => return outcome,
+        };
+
+        match toolkit.network_config() {
+            Ok(_) => CheckOutcome::Pass("loaded".to_string()),
+            Err(err_code) => CheckOutcome::fail(
+                err_code.to_string(),
+                "Fix l1x-conf/l1x_chain_config.yaml or recreate it with `l1x-forge init-workspace --force`",
+            ),
+        }
+    })
+}
+
+pub fn wallet_config_check() -> Check {
+    Check::new("l1x_dev_wallets.yaml", || async {
+        let toolkit = match workspace_config() {
+            Ok(toolkit) => toolkit,
+            Err(outcome) => return outcome,
+        };
+
+        match toolkit.wallet_config() {
+            Ok(_) => CheckOutcome::Pass("loaded".to_string()),
+            Err(err_code) => CheckOutcome::fail(
+                err_code.to_string(),
+                "Fix l1x-conf/l1x_dev_wallets.yaml or recreate it with `l1x-forge init-workspace --force`",
+            ),
+        }
+    })
+}
+
+pub fn registry_check() -> Check {
+    Check::new("config-contract-address-registry.yaml", || async {
+        let toolkit = match workspace_config() {
+            Ok(toolkit) => toolkit,
+            Err(outcome) => return outcome,
+        };
+
+        match toolkit.validate_contract_address_registry() {
+            Ok(_) => CheckOutcome::Pass("loaded".to_string()),
+            Err(err_code) => CheckOutcome::fail(
+                err_code.to_string(),
+                "Fix l1x-conf/config-contract-address-registry.yaml or recreate it with `l1x-forge init-workspace --force`",
+            ),
+        }
+    })
+}
+
+/// Passes when the endpoint of `network`, or of the active chain when
+/// `None`, answers a JSON-RPC request within `timeout`, whatever the answer
+pub fn endpoint_check(timeout: Duration, network: Option<String>) -> Check {
+    Check::new("json-rpc endpoint", move || {
+        let network = network.clone();
+        async move {
+            let toolkit = match workspace_config() {
+                Ok(toolkit) => toolkit.with_network(network.as_deref()),
+                Err(outcome) => return outcome,
+            };
+
+            let end_point = match toolkit.active_chain_json_rpc_endpoint() {
+                Ok(end_point) => end_point,
+                Err(err_code) => return CheckOutcome::fail(
+                    err_code.to_string(),
+                    "Pass --network or set L1X_CFG_CHAIN_TYPE to a network of l1x_chain_config.yaml",
+                ),
+            };
+
+            match probe_endpoint(&end_point, timeout).await {
+                Ok(()) => CheckOutcome::Pass(end_point),
+                Err(err_code) => CheckOutcome::fail(
+                    format!("{} is unreachable :: {}", end_point, err_code),
+                    "Start the node or fix the rpc_endpoint of the active network",
+                ),
+            }
+        }
+    })
+}
+
+async fn probe_endpoint(end_point: &str, timeout: Duration) -> Result<()> {
+    let response = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()?
+        .post(end_point)
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "method": "l1x_getChainState",
+            "params": { "request": {} },
+            "id": 1,
+        }))
+        .send()
+        .await?;
+
+    response.json::<serde_json::Value>().await?;
+
+    Ok(())
+}
+
+pub fn artifact_dir_check(vm_type: L1XVMArtifactType) -> Check {
+    let name = format!("{} artifact directory", vm_type.config_key());
+
+    Check::new(name, move || async move {
+        let toolkit = match workspace_config() {
+            Ok(toolkit) => toolkit,
+            Err(outcome) => return outcome,
+        };
+
+        let artifact_dir = toolkit.artifact_dir(vm_type);
+        if artifact_dir.is_dir() {
+            CheckOutcome::Pass(artifact_dir.display().to_string())
+        } else {
+            CheckOutcome::fail(
+                format!("{} does not exist", artifact_dir.display()),
+                format!("mkdir -p {}", artifact_dir.display()),
+            )
+        }
+    })
+}
+
+impl L1XDoctorCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let checks = default_checks(
+            Duration::from_secs(self.timeout),
+            self.network.clone(),
+        );
+
+        let mut failed = 0;
+        for check in &checks {
+            match check.run().await {
+                CheckOutcome::Pass(detail) => {
+                    println!("[PASS] {} :: {}", check.name, detail)
+                }
+                CheckOutcome::Fail { reason, hint } => {
+                    println!("[FAIL] {} :: {}", check.name, reason);
+                    println!("       hint: {}", hint);
+                    failed += 1;
+                }
+            }
+        }
+
+        if failed > 0 {
+            anyhow::bail!("{} of {} checks failed", failed, checks.len());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use crate::contract_install_test::{
+    with_temp_registry, write_chain_config, write_toolkit_config,
+};
+use crate::doctor::*;
+use l1x_common::toolkit_config::L1XVMArtifactType;
+
+use serde_json::json;
+use std::{env, fs, time::Duration};
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn run(check: &Check) -> CheckOutcome {
+    tokio::runtime::Runtime::new().unwrap().block_on(check.run())
+}
+
+/// [`write_toolkit_config`] with the `local` network pointing at `end_point`
+fn write_network(ws_home: &std::path::Path, end_point: &str) {
+    write_toolkit_config(ws_home);
+    write_chain_config(ws_home, 1, end_point);
+    env::set_var("L1X_CFG_CHAIN_TYPE", "local");
+}
+
+#[test]
+fn test_env_var_check() {
+    env::set_var("L1X_DOCTOR_TEST_SET", "yes");
+
+    let set = run(&env_var_check("L1X_DOCTOR_TEST_SET", "hint"));
+    let unset = run(&env_var_check("L1X_DOCTOR_TEST_UNSET", "export it"));
+
+    assert_eq!(set, CheckOutcome::Pass("yes".to_string()));
+    assert_eq!(
+        unset,
+        CheckOutcome::Fail {
+            reason: "L1X_DOCTOR_TEST_UNSET is not set".to_string(),
+            hint: "export it".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_optional_env_var_check_passes_when_unset() {
+    env::set_var("L1X_DOCTOR_TEST_OPTIONAL_SET", "/scripts");
+
+    let set = run(&optional_env_var_check("L1X_DOCTOR_TEST_OPTIONAL_SET", "n"));
+    let unset = run(&optional_env_var_check(
+        "L1X_DOCTOR_TEST_OPTIONAL_UNSET",
+        "only --dump-payloads uses it",
+    ));
+
+    assert_eq!(set, CheckOutcome::Pass("/scripts".to_string()));
+    assert_eq!(
+        unset,
+        CheckOutcome::Pass("not set, only --dump-payloads uses it".to_string())
+    );
+}
+
+#[test]
+fn test_config_checks_load_workspace_files() {
+    with_temp_registry(Some("l1x_vm: {}\nl1x_evm: {}\n"), |ws_home| {
+        write_toolkit_config(ws_home);
+
+        assert!(run(&chain_config_check()).is_pass());
+        assert!(run(&wallet_config_check()).is_pass());
+        assert!(run(&registry_check()).is_pass());
+    });
+}
+
+#[test]
+fn test_config_checks_fail_on_malformed_or_missing_files() {
+    with_temp_registry(None, |ws_home| {
+        fs::write(
+            ws_home.join("l1x-conf/l1x_chain_config.yaml"),
+            "networks: 1",
+        )
+        .unwrap();
+
+        assert!(!run(&chain_config_check()).is_pass());
+        assert!(!run(&wallet_config_check()).is_pass());
+        assert!(!run(&registry_check()).is_pass());
+    });
+}
+
+#[test]
+fn test_checks_fall_back_to_user_config_dir_without_ws_home() {
+    let config_home = tempfile::tempdir().unwrap();
+    let (workspace, artifact_dir) = with_temp_registry(None, |_| {
+        env::remove_var("L1X_CFG_WS_HOME");
+        let saved_config_home = env::var_os("XDG_CONFIG_HOME");
+        env::set_var("XDG_CONFIG_HOME", config_home.path());
+
+        let outcomes = (
+            run(&workspace_check()),
+            run(&artifact_dir_check(L1XVMArtifactType::Ebpf)),
+        );
+
+        match saved_config_home {
+            Some(saved_config_home) => {
+                env::set_var("XDG_CONFIG_HOME", saved_config_home)
+            }
+            None => env::remove_var("XDG_CONFIG_HOME"),
+        }
+        outcomes
+    });
+
+    let ws_home = config_home.path().join("l1x");
+    assert_eq!(
+        workspace,
+        CheckOutcome::Pass(format!(
+            "{} (user config directory)",
+            ws_home.display()
+        ))
+    );
+    assert!(matches!(
+        artifact_dir,
+        CheckOutcome::Fail { reason, .. } if reason.starts_with(&ws_home.display().to_string())
+    ));
+}
+
+#[test]
+fn test_endpoint_check_reachable() {
+    with_temp_registry(None, |ws_home| {
+        // Arrange
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start());
+        runtime.block_on(
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({"method": "l1x_getChainState"})))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": { "head_block_number": "7" },
+                })))
+                .expect(1)
+                .mount(&server),
+        );
+        write_network(ws_home, &server.uri());
+
+        // Act
+        let outcome = runtime
+            .block_on(endpoint_check(Duration::from_secs(5), None).run());
+
+        // Assert
+        assert_eq!(outcome, CheckOutcome::Pass(server.uri()));
+    });
+}
+
+#[test]
+fn test_endpoint_check_unreachable() {
+    with_temp_registry(None, |ws_home| {
+        write_network(ws_home, "http://127.0.0.1:1");
+
+        let outcome = run(&endpoint_check(Duration::from_secs(1), None));
+
+        assert!(matches!(
+            outcome,
+            CheckOutcome::Fail { reason, .. } if reason.contains("http://127.0.0.1:1 is unreachable")
+        ));
+    });
+}
+
+#[test]
+fn test_artifact_dir_check() {
+    with_temp_registry(None, |ws_home| {
+        fs::create_dir(ws_home.join("l1x-artifacts")).unwrap();
+
+        let ebpf = run(&artifact_dir_check(L1XVMArtifactType::Ebpf));
+        let evm = run(&artifact_dir_check(L1XVMArtifactType::Evm));
+
+        assert!(ebpf.is_pass());
+        assert!(!evm.is_pass());
+    });
+}
@@ -0,0 +1,154 @@
+use ethers::abi::{
+    self,
+    token::{LenientTokenizer, Tokenizer},
+    Abi,
+};
+use serde_json::Value;
+
+#[derive(Debug, thiserror::Error)]
+pub enum L1XEvmArtifactError {
+    #[error("Invalid artifact JSON: {0}")]
+    InvalidJson(String),
+    #[error("Invalid bytecode: {0}")]
+    InvalidBytecode(String),
+    #[error("Invalid ABI: {0}")]
+    InvalidAbi(String),
+    #[error("Invalid constructor arguments: {0}")]
+    InvalidConstructorArgs(String),
+}
+
+/// Creation bytecode of an EVM contract, with the ABI of a Foundry or
+/// Hardhat JSON artifact
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1XEvmArtifact {
+    pub bytecode: Vec<u8>,
+    /// Runtime bytecode the deployment leaves on chain, `None` for a raw hex
+    /// artifact
+    pub deployed_bytecode: Option<Vec<u8>>,
+    /// `None` for a raw hex artifact
+    pub abi: Option<Abi>,
+}
+
+/// Bytes of the hex string `hex_code`, with or without `0x`
+fn decode_bytecode(hex_code: &str) -> Result<Vec<u8>, L1XEvmArtifactError> {
+    let hex_code = hex_code.trim();
+    let hex_code = hex_code.strip_prefix("0x").unwrap_or(hex_code);
+
+    if hex_code.contains("__") {
+        return Err(L1XEvmArtifactError::InvalidBytecode(
+            "unlinked library placeholders, link the libraries first"
+                .to_string(),
+        ));
+    }
+    hex::decode(hex_code).map_err(|err_code| {
+        L1XEvmArtifactError::InvalidBytecode(err_code.to_string())
+    })
+}
+
+/// Hex string of the bytecode `field` of a JSON artifact, a string (Hardhat)
+/// or an object holding it as `object` (Foundry). `None` when missing or
+/// neither.
+fn bytecode_field<'a>(
+    artifact: &'a Value,
+    field: &str,
+) -> Result<Option<&'a str>, L1XEvmArtifactError> {
+    match &artifact[field] {
+        Value::String(hex_code) => Ok(Some(hex_code)),
+        Value::Object(bytecode) => match bytecode.get("object") {
+            Some(Value::String(hex_code)) => Ok(Some(hex_code)),
+            _ => Err(L1XEvmArtifactError::InvalidJson(format!(
+                "`{}` has no `object` string",
+                field
+            ))),
+        },
+        _ => Ok(None),
+    }
+}
+
+impl L1XEvmArtifact {
+    /// Artifact of the file `content`, a raw hex bytecode or a JSON artifact
+    /// whose `bytecode` is a string (Hardhat) or holds an `object` (Foundry)
+    pub fn parse(content: &str) -> Result<Self, L1XEvmArtifactError> {
+        if !content.trim_start().starts_with('{') {
+            return Ok(L1XEvmArtifact {
+                bytecode: decode_bytecode(content)?,
+                deployed_bytecode: None,
+                abi: None,
+            });
+        }
+
+        let artifact: Value =
+            serde_json::from_str(content).map_err(|err_code| {
+                L1XEvmArtifactError::InvalidJson(err_code.to_string())
+            })?;
+        let bytecode =
+            bytecode_field(&artifact, "bytecode")?.ok_or_else(|| {
+                L1XEvmArtifactError::InvalidJson(
+                    "missing `bytecode`".to_string(),
+                )
+            })?;
+        let deployed_bytecode = bytecode_field(&artifact, "deployedBytecode")?
+            .map(decode_bytecode)
+            .transpose()?;
+        let abi = match artifact.get("abi") {
+            Some(abi) => Some(serde_json::from_value(abi.clone()).map_err(
+                |err_code| {
+                    L1XEvmArtifactError::InvalidAbi(err_code.to_string())
+                },
+            )?),
+            None => None,
+        };
+
+        Ok(L1XEvmArtifact {
+            bytecode: decode_bytecode(bytecode)?,
+            deployed_bytecode,
+            abi,
+        })
+    }
+
+    /// ABI encoding of the constructor arguments `args`, each parsed as the
+    /// type of its constructor input. Empty when the constructor takes none.
+    pub fn encode_constructor_args(
+        &self,
+        args: &[String],
+    ) -> Result<Vec<u8>, L1XEvmArtifactError> {
+        let inputs = match self.abi.as_ref().and_then(Abi::constructor) {
+            Some(constructor) => constructor.inputs.as_slice(),
+            None if args.is_empty() => return Ok(Vec::new()),
+            None if self.abi.is_none() => {
+                return Err(L1XEvmArtifactError::InvalidConstructorArgs(
+                    "a raw hex artifact has no ABI, deploy the JSON artifact to pass constructor arguments".to_string(),
+                ))
+            }
+            None => {
+                return Err(L1XEvmArtifactError::InvalidConstructorArgs(
+                    "the ABI has no constructor".to_string(),
+                ))
+            }
+        };
+        if inputs.len() != args.len() {
+            return Err(L1XEvmArtifactError::InvalidConstructorArgs(format!(
+                "the constructor takes {} arguments, got {}",
+                inputs.len(),
+                args.len()
+            )));
+        }
+
+        let tokens = inputs
+            .iter()
+            .zip(args)
+            .map(|(input, arg)| {
+                LenientTokenizer::tokenize(&input.kind, arg).map_err(
+                    |err_code| {
+                        L1XEvmArtifactError::InvalidConstructorArgs(format!(
+                            "`{}` is not a {} for `{}`: {}",
+                            arg, input.kind, input.name, err_code
+                        ))
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(abi::encode(&tokens))
+    }
+}
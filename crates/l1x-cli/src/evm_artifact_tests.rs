@@ -0,0 +1,136 @@
+use crate::evm_artifact::*;
+
+/// Content of the artifact fixture `file_name`
+fn fixture(file_name: &str) -> String {
+    std::fs::read_to_string(format!(
+        "{}/fixtures/artifacts/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        file_name
+    ))
+    .unwrap()
+}
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn test_parse_foundry_hardhat_and_raw_hex_artifacts() {
+    let foundry = L1XEvmArtifact::parse(&fixture("Token.json")).unwrap();
+    let hardhat =
+        L1XEvmArtifact::parse(&fixture("Counter.hardhat.json")).unwrap();
+    let raw = L1XEvmArtifact::parse("0x6080604052\n").unwrap();
+
+    for artifact in [&foundry, &hardhat] {
+        assert_eq!(
+            hex::encode(artifact.deployed_bytecode.as_ref().unwrap()),
+            "6080604052600080fdfe"
+        );
+    }
+    assert_eq!(
+        hex::encode(&foundry.bytecode),
+        "6080604052348015600f57600080fd5b50603f80601d6000396000f3fe"
+    );
+    assert_eq!(foundry.abi.unwrap().constructor().unwrap().inputs.len(), 3);
+    assert_eq!(
+        hex::encode(&hardhat.bytecode),
+        "6080604052348015600f57600080fd5b50"
+    );
+    assert!(hardhat.abi.unwrap().constructor().is_none());
+    assert_eq!(
+        raw,
+        L1XEvmArtifact {
+            bytecode: vec![0x60, 0x80, 0x60, 0x40, 0x52],
+            deployed_bytecode: None,
+            abi: None
+        }
+    );
+}
+
+#[test]
+fn test_encode_uint_address_and_string_constructor_args() {
+    let artifact = L1XEvmArtifact::parse(&fixture("Token.json")).unwrap();
+
+    let encoded = artifact
+        .encode_constructor_args(&args(&[
+            "1000",
+            &format!("0x{}", "11".repeat(20)),
+            "L1X",
+        ]))
+        .unwrap();
+
+    assert_eq!(
+        hex::encode(encoded),
+        [
+            format!("{:064x}", 1000),
+            format!("{:0>64}", "11".repeat(20)),
+            format!("{:064x}", 0x60),
+            format!("{:064x}", 3),
+            format!("{:0<64}", hex::encode("L1X")),
+        ]
+        .concat()
+    );
+}
+
+#[test]
+fn test_constructor_args_are_empty_without_constructor() {
+    let hardhat =
+        L1XEvmArtifact::parse(&fixture("Counter.hardhat.json")).unwrap();
+    let raw = L1XEvmArtifact::parse("6080604052").unwrap();
+
+    assert_eq!(hardhat.encode_constructor_args(&[]).unwrap(), Vec::<u8>::new());
+    assert_eq!(raw.encode_constructor_args(&[]).unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn test_invalid_constructor_args_are_rejected() {
+    let token = L1XEvmArtifact::parse(&fixture("Token.json")).unwrap();
+    let hardhat =
+        L1XEvmArtifact::parse(&fixture("Counter.hardhat.json")).unwrap();
+    let raw = L1XEvmArtifact::parse("6080604052").unwrap();
+
+    let cases = [
+        (
+            token.encode_constructor_args(&args(&["1000"])),
+            "Invalid constructor arguments: the constructor takes 3 arguments, got 1",
+        ),
+        (
+            hardhat.encode_constructor_args(&args(&["1"])),
+            "Invalid constructor arguments: the ABI has no constructor",
+        ),
+        (
+            raw.encode_constructor_args(&args(&["1"])),
+            "Invalid constructor arguments: a raw hex artifact has no ABI, deploy the JSON artifact to pass constructor arguments",
+        ),
+    ];
+
+    for (result, message) in cases {
+        assert_eq!(result.unwrap_err().to_string(), message);
+    }
+    assert!(token
+        .encode_constructor_args(&args(&["many", "0x00", "L1X"]))
+        .unwrap_err()
+        .to_string()
+        .starts_with("Invalid constructor arguments: `many` is not a uint256 for `supply`"));
+}
+
+#[test]
+fn test_malformed_artifacts_are_rejected() {
+    for (content, message) in [
+        (r#"{"abi": []}"#, "Invalid artifact JSON: missing `bytecode`"),
+        (
+            r#"{"bytecode": {"sourceMap": ""}}"#,
+            "Invalid artifact JSON: `bytecode` has no `object` string",
+        ),
+        (
+            r#"{"bytecode": "0x73__$abc$__60"}"#,
+            "Invalid bytecode: unlinked library placeholders, link the libraries first",
+        ),
+    ] {
+        assert_eq!(
+            L1XEvmArtifact::parse(content).unwrap_err().to_string(),
+            message
+        );
+    }
+    assert!(L1XEvmArtifact::parse("0xzz").is_err());
+}
@@ -0,0 +1,266 @@
+use crate::balance::resolve_address;
+use crate::cli_error::L1XConfigError;
+use crate::forge_client::{ForgeClient, ForgeClientConfig, ForgeClientError};
+use l1x_common::{rpc, toolkit_config::ToolkitConfig, SigningScheme};
+
+use anyhow::Result;
+use std::time::Duration;
+
+/// Fund dev accounts from the faucet wallet of a local devnet
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "faucet")]
+pub struct L1XFaucetCmd {
+    /// Wallet name or hex address to fund
+    #[clap(
+        long = "to",
+        required_unless_present = "all",
+        conflicts_with = "all"
+    )]
+    to: Option<String>,
+
+    /// Fund every wallet of `l1x_dev_wallets.yaml` but the faucet
+    #[clap(long = "all", default_value_t = false)]
+    all: bool,
+
+    /// Amount sent to every recipient
+    #[clap(long = "amount")]
+    amount: u128,
+
+    /// Faucet wallet, the `faucet_account` of the wallet file by default
+    #[clap(long = "from")]
+    from: Option<String>,
+
+    #[clap(long = "fee-limit", default_value_t = 100)]
+    fee_limit: u128,
+
+    /// Network of l1x_chain_config.yaml to fund on, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+
+    /// Seconds to wait for each transfer to be included in a block
+    #[clap(long = "timeout", default_value_t = 60)]
+    timeout: u64,
+
+    /// Sign the transfers over the legacy native transfer payload, for nodes
+    /// rejecting canonically signed transfers
+    #[clap(long = "legacy-native-signing", default_value_t = false)]
+    legacy_native_signing: bool,
+}
+
+/// An account funded by the faucet
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1XFaucetRecipient {
+    /// Wallet name, or the address itself when not a wallet
+    pub name: String,
+    /// Lowercase hex address without `0x`
+    pub address: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum L1XFaucetOutcome {
+    Funded {
+        hash: String,
+    },
+    /// Skipped before submitting, the faucet has only `remaining` left
+    InsufficientBalance {
+        remaining: u128,
+    },
+    Failed(String),
+}
+
+impl std::fmt::Display for L1XFaucetOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            L1XFaucetOutcome::Funded { hash } => {
+                write!(f, "funded :: {}", hash)
+            }
+            L1XFaucetOutcome::InsufficientBalance { remaining } => write!(
+                f,
+                "skipped :: insufficient faucet balance, {} left",
+                remaining
+            ),
+            L1XFaucetOutcome::Failed(reason) => {
+                write!(f, "failed :: {}", reason)
+            }
+        }
+    }
+}
+
+/// Transfer `amount` from the client's account to every recipient, in order,
+/// with consecutive nonces from a single nonce query. Recipients
+/// `faucet_balance` no longer covers are skipped without submitting.
+pub async fn fund_recipients(
+    client: &ForgeClient,
+    faucet_balance: u128,
+    recipients: &[L1XFaucetRecipient],
+    amount: u128,
+    timeout: Duration,
+) -> Result<Vec<L1XFaucetOutcome>, ForgeClientError> {
+    let mut nonce = client.next_nonce().await?;
+    let mut remaining = faucet_balance;
+
+    let mut outcomes = Vec::new();
+    for recipient in recipients {
+        if remaining < amount {
+            outcomes.push(L1XFaucetOutcome::InsufficientBalance { remaining });
+            continue;
+        }
+
+        let submitted =
+            match client.sign_transfer(&recipient.address, amount, nonce) {
+                Ok(request) => client.submit_signed(&request).await,
+                Err(err_code) => Err(err_code),
+            };
+
+        match submitted {
+            Ok(response) => {
+                nonce += 1;
+                remaining -= amount;
+                outcomes.push(L1XFaucetOutcome::Funded { hash: response.hash });
+            }
+            Err(err_code) => {
+                outcomes.push(L1XFaucetOutcome::Failed(err_code.to_string()))
+            }
+        }
+    }
+
+    // Wait once every transfer is submitted, the nonces are already taken
+    for outcome in outcomes.iter_mut() {
+        let hash = match outcome {
+            L1XFaucetOutcome::Funded { hash } => hash.clone(),
+            _ => continue,
+        };
+
+        if let Err(err_code) = client.wait_for_finality(&hash, 0, timeout).await
+        {
+            *outcome = L1XFaucetOutcome::Failed(err_code.to_string());
+        }
+    }
+
+    Ok(outcomes)
+}
+
+impl L1XFaucetCmd {
+    fn recipients(
+        &self,
+        toolkit: &ToolkitConfig,
+        faucet: &str,
+    ) -> Result<Vec<L1XFaucetRecipient>, L1XConfigError> {
+        if !self.all {
+            let to = self.to.as_deref().unwrap_or_default();
+            let address = resolve_address(to)
+                .map_err(|err_code| L1XConfigError(err_code.to_string()))?;
+            return Ok(vec![L1XFaucetRecipient {
+                name: to.to_string(),
+                address,
+            }]);
+        }
+
+        let wallet_config = toolkit.wallet_config().map_err(|err_code| {
+            L1XConfigError(format!(
+                "Failed to load wallet yaml file: {}",
+                err_code
+            ))
+        })?;
+        let mut names = wallet_config
+            .dev_accounts()
+            .keys()
+            .filter(|name| name.as_str() != faucet)
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let address = resolve_address(&name)
+                    .map_err(|err_code| L1XConfigError(err_code.to_string()))?;
+                Ok(L1XFaucetRecipient { name, address })
+            })
+            .collect()
+    }
+
+    pub async fn exec(&self) -> Result<()> {
+        let toolkit = ToolkitConfig::from_env()
+            .map_err(L1XConfigError::from)?
+            .with_network(self.network.as_deref());
+        let faucet = match &self.from {
+            Some(from) => from.clone(),
+            None => toolkit
+                .wallet_config()
+                .map_err(|err_code| {
+                    L1XConfigError(format!(
+                        "Failed to load wallet yaml file: {}",
+                        err_code
+                    ))
+                })?
+                .faucet_account()
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    L1XConfigError(
+                        "No faucet wallet: pass --from or set faucet_account in l1x_dev_wallets.yaml"
+                            .to_string(),
+                    )
+                })?,
+        };
+
+        let recipients = self.recipients(&toolkit, &faucet)?;
+        let end_point = toolkit
+            .active_chain_json_rpc_endpoint()
+            .map_err(L1XConfigError::from)?;
+        let private_key =
+            toolkit.wallet_priv_key(&faucet).map_err(L1XConfigError::from)?;
+
+        let faucet_state = rpc::get_account_state(
+            &end_point,
+            &resolve_address(&faucet)?,
+            rpc::DEFAULT_RPC_TIMEOUT,
+        )
+        .await?;
+
+        let client = ForgeClient::new(ForgeClientConfig {
+            fee_limit: self.fee_limit,
+            signing_scheme: match self.legacy_native_signing {
+                true => SigningScheme::LegacyNativeTransfer,
+                false => SigningScheme::Canonical,
+            },
+            ..ForgeClientConfig::new(&end_point, &private_key)
+        })
+        .map_err(|err_code| {
+            L1XConfigError(format!(
+                "Invalid private key for wallet '{}': {}",
+                &faucet, err_code
+            ))
+        })?;
+
+        let outcomes = fund_recipients(
+            &client,
+            faucet_state.balance,
+            &recipients,
+            self.amount,
+            Duration::from_secs(self.timeout),
+        )
+        .await?;
+
+        let mut unfunded = 0;
+        for (recipient, outcome) in recipients.iter().zip(&outcomes) {
+            if !matches!(outcome, L1XFaucetOutcome::Funded { .. }) {
+                unfunded += 1;
+            }
+            println!(
+                "{} :: 0x{} :: {}",
+                recipient.name, recipient.address, outcome
+            );
+        }
+
+        match unfunded {
+            0 => Ok(()),
+            _ => anyhow::bail!(
+                "{} of {} recipients were not funded",
+                unfunded,
+                recipients.len()
+            ),
+        }
+    }
+}
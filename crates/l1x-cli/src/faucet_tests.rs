@@ -0,0 +1,109 @@
+use crate::contract_install_test::{with_temp_registry, write_toolkit_config};
+use crate::faucet::*;
+use crate::forge_client::{ForgeClient, ForgeClientConfig};
+use crate::forge_client_tests::{mock_rpc, rpc_result};
+use l1x_common::toolkit_config::ToolkitConfig;
+
+use serde_json::json;
+use std::{fs, time::Duration};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer};
+
+const FAUCET_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+
+fn recipient(name: &str, address_byte: &str) -> L1XFaucetRecipient {
+    L1XFaucetRecipient {
+        name: name.to_string(),
+        address: address_byte.repeat(20),
+    }
+}
+
+#[tokio::test]
+async fn test_fund_recipients_uses_sequential_nonces_until_balance_runs_out() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(
+        &server,
+        "l1x_getAccountState",
+        json!({ "account_state": { "balance": "25", "nonce": "7", "account_type": 0 } }),
+    )
+    .await;
+    for (nonce, hash) in [("8", "aa01"), ("9", "aa02")] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "l1x_submitTransaction",
+                "params": { "request": { "nonce": nonce } },
+            })))
+            .respond_with(rpc_result(
+                json!({ "hash": hash, "contract_address": null }),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    mock_rpc(
+        &server,
+        "l1x_getTransactionReceipt",
+        json!({ "block_number": 3 }),
+    )
+    .await;
+    mock_rpc(&server, "l1x_getChainState", json!({ "head_block_number": 3 }))
+        .await;
+    let client = ForgeClient::new(ForgeClientConfig {
+        poll_interval: Duration::from_millis(10),
+        ..ForgeClientConfig::new(&server.uri(), FAUCET_KEY)
+    })
+    .unwrap();
+    let recipients = [
+        recipient("ava", "01"),
+        recipient("bobby", "02"),
+        recipient("cy", "03"),
+    ];
+
+    // Act
+    let outcomes =
+        fund_recipients(&client, 25, &recipients, 10, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+    // Assert
+    assert_eq!(
+        outcomes,
+        [
+            L1XFaucetOutcome::Funded { hash: "aa01".to_string() },
+            L1XFaucetOutcome::Funded { hash: "aa02".to_string() },
+            L1XFaucetOutcome::InsufficientBalance { remaining: 5 },
+        ]
+    );
+}
+
+#[test]
+fn test_faucet_outcome_display() {
+    let skipped = L1XFaucetOutcome::InsufficientBalance { remaining: 5 };
+
+    assert_eq!(
+        skipped.to_string(),
+        "skipped :: insufficient faucet balance, 5 left"
+    );
+}
+
+#[test]
+fn test_wallet_config_faucet_account() {
+    with_temp_registry(None, |ws_home| {
+        // Arrange
+        write_toolkit_config(ws_home);
+        let wallet_file = ws_home.join("l1x-conf/l1x_dev_wallets.yaml");
+        let wallets = fs::read_to_string(&wallet_file).unwrap();
+
+        // Act
+        let unset = ToolkitConfig::from_env().unwrap().wallet_config().unwrap();
+        fs::write(&wallet_file, format!("faucet_account: super\n{}", wallets))
+            .unwrap();
+        let set = ToolkitConfig::from_env().unwrap().wallet_config().unwrap();
+
+        // Assert
+        assert_eq!(unset.faucet_account(), None);
+        assert_eq!(set.faucet_account(), Some("super"));
+    });
+}
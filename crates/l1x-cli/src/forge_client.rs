@@ -0,0 +1,1127 @@
+use l1x_common::{
+    fee::{self, ChosenFee},
+    nonce_manager::{self, NonceManager, NonceReservation},
+    parse_secret_key, rpc,
+    transport::{self, JsonRpcTransport, L1XProtocol, L1xTransport},
+    txn_builder::TransactionBuilder,
+    types, SigningScheme, TxnBuildError,
+};
+use l1x_rpc::rpc_model::{
+    SmartContractReadOnlyCallRequest, SubmitTransactionRequest,
+    SubmitTransactionResponse,
+};
+
+use secp256k1::SecretKey;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+/// Salt used for every eBPF contract deployment
+const EBPF_DEPLOY_SALT: &str = "00000000000000000000000000000000";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ForgeClientError {
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("Invalid Nonce error: {0}")]
+    InValidNonceError(String),
+    #[error("Request Creation error: {0}")]
+    RequestCreationError(String),
+    #[error("Post JSON RPC error: {0}")]
+    PostJsonRpcError(String),
+    /// The node answered a JSON-RPC request with the id of another request
+    #[error("JSON RPC request id mismatch: {0}")]
+    ReqIdMismatch(String),
+    #[error("JSON Parse error: {0}")]
+    JsonParseError(String),
+    #[error("Finality error: {0}")]
+    FinalityTimeout(String),
+    #[error("Invalid transaction payload: {0}")]
+    InvalidPayload(String),
+    #[error("Signing error: {0}")]
+    SigningError(String),
+    #[error("{0}")]
+    EventsTimeout(#[from] rpc::EventsTimeout),
+    #[error("{0}")]
+    ReceiptTimeout(#[from] rpc::ReceiptTimeout),
+    #[error("Transaction {hash} failed in its block: {message}")]
+    TxFailed { hash: String, message: String },
+}
+
+/// Failure of [`ForgeClient::deploy_reserved`], naming the step it failed at
+#[derive(Debug, thiserror::Error)]
+pub enum DeployError {
+    /// Choosing the fee of the transaction or signing it failed
+    #[error("{0}")]
+    Sign(ForgeClientError),
+    /// The JSON-RPC request `method` failed
+    #[error("{method} :: {source}")]
+    Rpc {
+        method: &'static str,
+        #[source]
+        source: ForgeClientError,
+    },
+    /// The node accepted the transaction `hash`, which did not reach finality
+    #[error("Txn {hash} did not reach finality :: {source}")]
+    Finality {
+        hash: String,
+        #[source]
+        source: ForgeClientError,
+    },
+}
+
+impl From<DeployError> for ForgeClientError {
+    fn from(err_code: DeployError) -> Self {
+        match err_code {
+            DeployError::Sign(source)
+            | DeployError::Rpc { source, .. }
+            | DeployError::Finality { source, .. } => source,
+        }
+    }
+}
+
+impl From<TxnBuildError> for ForgeClientError {
+    fn from(err_code: TxnBuildError) -> Self {
+        match err_code {
+            TxnBuildError::InvalidPrivateKey(msg) => {
+                ForgeClientError::InvalidPrivateKey(msg)
+            }
+            TxnBuildError::Signing(_) | TxnBuildError::InvalidSignature(_) => {
+                ForgeClientError::SigningError(err_code.to_string())
+            }
+            TxnBuildError::PayloadDeserialize { .. }
+            | TxnBuildError::PayloadSchema { .. }
+            | TxnBuildError::TypeConversion(_)
+            | TxnBuildError::PayloadValue(_)
+            | TxnBuildError::InvalidAddress(_)
+            | TxnBuildError::Incomplete(_)
+            | TxnBuildError::InvalidAmount(_)
+            | TxnBuildError::Io { .. } => {
+                ForgeClientError::InvalidPayload(err_code.to_string())
+            }
+        }
+    }
+}
+
+/// Everything a [`ForgeClient`] needs to talk to a node and sign transactions
+#[derive(Clone)]
+pub struct ForgeClientConfig {
+    /// Endpoint of the L1X node, served with `protocol`
+    pub endpoint: String,
+    /// Protocol transactions are submitted and read-only calls evaluated
+    /// with. Account and chain state are always queried with JSON-RPC.
+    pub protocol: L1XProtocol,
+    /// Hex encoded secp256k1 key signing every submitted transaction
+    pub private_key: String,
+    /// Fee limit of every transaction, the fallback of `fee_estimation`
+    pub fee_limit: u128,
+    /// Estimate the fee limit of each transaction instead, for
+    /// `--fee_limit auto`
+    pub fee_estimation: Option<fee::FeeEstimation>,
+    /// Delay between two event queries while awaiting the events of a
+    /// transaction
+    pub event_poll_interval: Duration,
+    /// How long to await the events of a transaction
+    pub event_wait_timeout: Duration,
+    /// Delay between two receipt / chain head queries while awaiting finality
+    pub poll_interval: Duration,
+    /// JSON-RPC method returning the receipt of a transaction, which differs
+    /// between node versions
+    pub receipt_method: String,
+    /// JSON-RPC `id` of the first request, each following request carries
+    /// the next id, to correlate them with the node logs
+    pub req_id: u64,
+    /// Signing scheme of native token transfers, for nodes predating
+    /// [`SigningScheme::Canonical`]
+    pub signing_scheme: SigningScheme,
+    /// Retries of requests failing on a timeout, a connection error or a
+    /// server error
+    pub rpc_retry: rpc::RetryPolicy,
+    /// Nonce signed instead of the next nonce of the account, to recover
+    /// from a nonce the node rejects by hand
+    pub nonce: Option<u128>,
+}
+
+impl ForgeClientConfig {
+    pub fn new(endpoint: &str, private_key: &str) -> Self {
+        ForgeClientConfig {
+            endpoint: endpoint.to_string(),
+            protocol: L1XProtocol::default(),
+            private_key: private_key.to_string(),
+            fee_limit: fee::DEFAULT_FEE_LIMIT,
+            fee_estimation: None,
+            event_poll_interval: rpc::DEFAULT_EVENT_POLL_INTERVAL,
+            event_wait_timeout: rpc::DEFAULT_EVENT_WAIT_TIMEOUT,
+            poll_interval: Duration::from_secs(1),
+            receipt_method: rpc::DEFAULT_RECEIPT_METHOD.to_string(),
+            req_id: 1,
+            signing_scheme: SigningScheme::Canonical,
+            rpc_retry: rpc::RetryPolicy::default(),
+            nonce: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ForgeClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForgeClientConfig")
+            .field("endpoint", &self.endpoint)
+            .field("protocol", &self.protocol)
+            .field("private_key", &"<redacted>")
+            .field("fee_limit", &self.fee_limit)
+            .field("fee_estimation", &self.fee_estimation)
+            .field("event_poll_interval", &self.event_poll_interval)
+            .field("event_wait_timeout", &self.event_wait_timeout)
+            .field("poll_interval", &self.poll_interval)
+            .field("receipt_method", &self.receipt_method)
+            .field("req_id", &self.req_id)
+            .field("signing_scheme", &self.signing_scheme)
+            .field("rpc_retry", &self.rpc_retry)
+            .field("nonce", &self.nonce)
+            .finish()
+    }
+}
+
+/// Result of a contract deployment or initialization
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeployResult {
+    pub hash: String,
+    pub address: Option<String>,
+    pub events: Vec<Vec<u8>>,
+    /// Fee limit the transaction was signed with
+    pub fee: ChosenFee,
+    /// Fee the receipt of the transaction reports as consumed, `None` when
+    /// its receipt was not checked or carries no fee
+    pub fee_used: Option<u128>,
+}
+
+/// How far [`ForgeClient::deploy_reserved`] follows a transaction the node
+/// accepted
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Confirmation {
+    /// Check in its receipt that the transaction succeeded in its block
+    pub receipt: bool,
+    /// Wait until the transaction is buried under this many blocks, for at
+    /// most the given timeout
+    pub finality: Option<(u64, Duration)>,
+}
+
+/// Result of a submitted transaction
+#[derive(Clone, Debug, PartialEq)]
+pub struct TxResult {
+    pub hash: String,
+    pub events: Vec<Vec<u8>>,
+    /// Fee limit the transaction was signed with
+    pub fee: ChosenFee,
+}
+
+/// A signed transaction a dry run prints instead of submitting it
+#[derive(Debug, Serialize)]
+pub struct DryRunTxn {
+    /// Install or call stage the transaction belongs to
+    pub stage: String,
+    /// Account address of the signing key
+    pub sender: types::AddressStr,
+    pub nonce: String,
+    pub fee_limit: String,
+    /// sha256 of the artifact file of a deployment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_sha256: Option<String>,
+    pub request: SubmitTransactionRequest,
+}
+
+/// On-chain state of an account
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountState {
+    pub balance: u128,
+    pub nonce: u128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CallKind {
+    /// Submit the call as a state changing transaction
+    SubTxn,
+    /// Evaluate the call on the node without submitting a transaction
+    ReadOnly,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CallResult {
+    Submitted(TxResult),
+    /// The read-only call result, `None` when the node returned no result
+    ReadOnly(Option<Vec<u8>>),
+}
+
+/// Programmatic access to the deploy / init / call flows used by the CLI
+#[derive(Debug)]
+pub struct ForgeClient {
+    config: ForgeClientConfig,
+    /// Submits transactions and reads their outcome
+    transport: Arc<dyn L1xTransport>,
+    /// Queries the account and chain state
+    json: JsonRpcTransport,
+    secret_key: SecretKey,
+}
+
+impl ForgeClient {
+    pub fn new(config: ForgeClientConfig) -> Result<Self, ForgeClientError> {
+        let req_ids = rpc::RequestIds::starting_at(config.req_id);
+        let transport = transport::transport_for(
+            config.protocol,
+            &config.endpoint,
+            req_ids.clone(),
+            config.rpc_retry,
+            &config.receipt_method,
+        );
+
+        Self::with_req_ids(config, transport, req_ids)
+    }
+
+    /// Client submitting transactions with `transport` rather than the
+    /// transport of the configured protocol
+    pub fn with_transport(
+        config: ForgeClientConfig,
+        transport: Arc<dyn L1xTransport>,
+    ) -> Result<Self, ForgeClientError> {
+        let req_ids = rpc::RequestIds::starting_at(config.req_id);
+
+        Self::with_req_ids(config, transport, req_ids)
+    }
+
+    /// Client whose JSON-RPC requests take their ids from `req_ids`, shared
+    /// with `transport`
+    fn with_req_ids(
+        config: ForgeClientConfig,
+        transport: Arc<dyn L1xTransport>,
+        req_ids: rpc::RequestIds,
+    ) -> Result<Self, ForgeClientError> {
+        let secret_key =
+            parse_secret_key(&config.private_key).map_err(|err_code| {
+                ForgeClientError::InvalidPrivateKey(err_code.to_string())
+            })?;
+
+        let json = JsonRpcTransport::new(
+            &config.endpoint,
+            req_ids,
+            config.rpc_retry,
+            &config.receipt_method,
+        );
+
+        Ok(ForgeClient { config, transport, json, secret_key })
+    }
+
+    pub fn config(&self) -> &ForgeClientConfig {
+        &self.config
+    }
+
+    /// Account address of the configured private key
+    pub fn account_address(&self) -> types::AddressStr {
+        let verifying_key =
+            self.secret_key.public_key(&secp256k1::Secp256k1::new());
+
+        l1x_common::account_address(&verifying_key.serialize())
+            .expect("a secp256k1 public key maps to an account address")
+            .into()
+    }
+
+    /// Deploy an eBPF contract object file as `access_type` and
+    /// `contract_type`
+    pub async fn deploy_ebpf(
+        &self,
+        artifact_bytes: Vec<u8>,
+        access_type: types::AccessType,
+        contract_type: types::ContractType,
+    ) -> Result<DeployResult, ForgeClientError> {
+        self.deploy_ebpf_with_nonce(
+            artifact_bytes,
+            access_type,
+            contract_type,
+            self.next_nonce().await?,
+        )
+        .await
+    }
+
+    /// [`ForgeClient::deploy_ebpf`] with the transaction nonce `nonce`, for
+    /// callers that predicted the contract address from it
+    pub async fn deploy_ebpf_with_nonce(
+        &self,
+        artifact_bytes: Vec<u8>,
+        access_type: types::AccessType,
+        contract_type: types::ContractType,
+        nonce: u128,
+    ) -> Result<DeployResult, ForgeClientError> {
+        let txn =
+            Self::ebpf_deploy_txn(artifact_bytes, access_type, contract_type)?;
+
+        self.deploy_transaction(txn, nonce).await
+    }
+
+    /// Deployment transaction of [`ForgeClient::deploy_ebpf`]
+    pub fn ebpf_deploy_txn(
+        artifact_bytes: Vec<u8>,
+        access_type: types::AccessType,
+        contract_type: types::ContractType,
+    ) -> Result<types::Transaction, ForgeClientError> {
+        Ok(TransactionBuilder::new()
+            .deploy_contract(
+                access_type,
+                contract_type,
+                types::U8s::Bytes(artifact_bytes),
+            )?
+            .salt(EBPF_DEPLOY_SALT)?
+            .build()?)
+    }
+
+    /// Initialize a new instance of a deployed eBPF contract
+    pub async fn init_contract(
+        &self,
+        address: &str,
+        init_args: types::U8s,
+    ) -> Result<DeployResult, ForgeClientError> {
+        self.init_contract_with_nonce(
+            address,
+            init_args,
+            self.next_nonce().await?,
+        )
+        .await
+    }
+
+    /// [`ForgeClient::init_contract`] with the transaction nonce `nonce`
+    pub async fn init_contract_with_nonce(
+        &self,
+        address: &str,
+        init_args: types::U8s,
+        nonce: u128,
+    ) -> Result<DeployResult, ForgeClientError> {
+        let txn = TransactionBuilder::new()
+            .init_contract(address, init_args)?
+            .build()?;
+
+        self.deploy_transaction(txn, nonce).await
+    }
+
+    /// Deploy EVM bytecode as `access_type`, `constructor_args` are
+    /// appended ABI encoded
+    pub async fn deploy_evm(
+        &self,
+        bytecode: Vec<u8>,
+        constructor_args: Vec<u8>,
+        salt: &str,
+        access_type: types::AccessType,
+    ) -> Result<DeployResult, ForgeClientError> {
+        let txn = Self::evm_deploy_txn(
+            bytecode,
+            constructor_args,
+            salt,
+            access_type,
+        )?;
+
+        self.deploy_transaction(txn, self.next_nonce().await?).await
+    }
+
+    /// Deployment transaction of [`ForgeClient::deploy_evm`]
+    pub fn evm_deploy_txn(
+        bytecode: Vec<u8>,
+        constructor_args: Vec<u8>,
+        salt: &str,
+        access_type: types::AccessType,
+    ) -> Result<types::Transaction, ForgeClientError> {
+        let mut contract_code = bytecode;
+        contract_code.extend(constructor_args);
+
+        Ok(TransactionBuilder::new()
+            .deploy_contract(
+                access_type,
+                types::ContractType::EVM,
+                types::U8s::Bytes(contract_code),
+            )?
+            .salt(salt)?
+            .build()?)
+    }
+
+    /// Call `function` of the contract at `address` with `payload`
+    pub async fn call_contract(
+        &self,
+        address: &str,
+        function: &str,
+        payload: &str,
+        kind: CallKind,
+    ) -> Result<CallResult, ForgeClientError> {
+        match kind {
+            CallKind::SubTxn => {
+                let txn =
+                    Self::create_txn_function_call(address, function, payload)?;
+                tracing::trace!("Sub Txn Req => {:#?}", &txn);
+
+                let (response, fee) = self.submit_with_fee(txn).await?;
+                let events = self.wait_for_events(&response.hash).await?;
+
+                Ok(CallResult::Submitted(TxResult {
+                    hash: response.hash,
+                    events,
+                    fee,
+                }))
+            }
+            CallKind::ReadOnly => {
+                let call = Self::create_ronly_txn_function_call(
+                    address, function, payload,
+                )?;
+                tracing::trace!("Read-Only Txn Req => {:#?}", &call);
+
+                self.read_only_call(call).await.map(CallResult::ReadOnly)
+            }
+        }
+    }
+
+    /// Transfer native tokens to the 20 byte hex address `to`
+    pub async fn transfer(
+        &self,
+        to: &str,
+        amount: u128,
+    ) -> Result<TxResult, ForgeClientError> {
+        let txn =
+            TransactionBuilder::new().native_transfer(to, amount)?.build()?;
+
+        let (response, fee) = self.submit_with_fee(txn).await?;
+        let events = self.wait_for_events(&response.hash).await?;
+
+        Ok(TxResult { hash: response.hash, events, fee })
+    }
+
+    pub(crate) fn create_txn_function_call(
+        contract_address: &str,
+        function: &str,
+        function_payload: &str,
+    ) -> Result<types::Transaction, ForgeClientError> {
+        Ok(TransactionBuilder::new()
+            .call(
+                contract_address,
+                function,
+                types::U8s::Hex(function_payload.to_string()),
+            )?
+            .build()?)
+    }
+
+    pub(crate) fn create_ronly_txn_function_call(
+        contract_address: &str,
+        function: &str,
+        function_payload: &str,
+    ) -> Result<SmartContractReadOnlyCallRequest, ForgeClientError> {
+        types::SmartContractReadOnlyFunctionCall {
+            contract_instance_address: types::U8s::Hex(
+                contract_address.to_string(),
+            ),
+            function: types::U8s::Text(function.to_string()),
+            arguments: types::U8s::Hex(function_payload.to_string()),
+        }
+        .try_into()
+        .map_err(|err_code| {
+            ForgeClientError::RequestCreationError(format!(
+                "Read-Only Txn Failed: Unable to create request :: {:#?}",
+                err_code
+            ))
+        })
+    }
+
+    /// Span of the requests of `rpc_method` sent with the transport, whose
+    /// JSON-RPC log lines carry the request id
+    fn rpc_span(&self, rpc_method: &str) -> tracing::Span {
+        tracing::info_span!(
+            "rpc",
+            method = rpc_method,
+            protocol = ?self.config.protocol
+        )
+    }
+
+    /// Post a JSON-RPC request with the next request id and return its
+    /// `result`, retrying transport and server failures with the configured
+    /// retry policy. Every retry posts the same `params` and id.
+    async fn post_rpc(
+        &self,
+        rpc_method: &str,
+        params: Value,
+    ) -> Result<Option<Value>, rpc::RpcError> {
+        let req_id = self.json.next_req_id();
+
+        self.json
+            .call_with_id(req_id, rpc_method, params)
+            .instrument(tracing::info_span!("rpc", method = rpc_method, req_id))
+            .await
+    }
+
+    /// Sign and submit the deployment or initialization `txn` with the
+    /// transaction nonce `nonce`, then wait for its events
+    pub async fn deploy_transaction(
+        &self,
+        txn: types::Transaction,
+        nonce: u128,
+    ) -> Result<DeployResult, ForgeClientError> {
+        let nonces = NonceManager::new();
+        let nonce = nonces
+            .reserve(|| async { Ok::<_, ForgeClientError>(nonce) })
+            .await?;
+
+        Ok(self.deploy_reserved(txn, nonce, Confirmation::default()).await?)
+    }
+
+    /// Sign `txn` with the reserved `nonce`, submit it, wait for its events
+    /// and follow it as far as `confirmation` asks. The reservation is
+    /// committed once the node accepted the transaction, and re-synced when
+    /// the node rejected its nonce.
+    pub async fn deploy_reserved(
+        &self,
+        txn: types::Transaction,
+        nonce: NonceReservation<'_>,
+        confirmation: Confirmation,
+    ) -> Result<DeployResult, DeployError> {
+        let (request, fee) = self
+            .sign_with_chosen_fee(txn, nonce.nonce())
+            .await
+            .map_err(DeployError::Sign)?;
+        let response = match self.submit_signed(&request).await {
+            Ok(response) => {
+                nonce.commit();
+                response
+            }
+            Err(err_code) => {
+                if nonce_manager::is_invalid_nonce(&err_code.to_string()) {
+                    tracing::warn!(
+                        "Nonce {} rejected, fetching it again for the next transaction",
+                        nonce.nonce()
+                    );
+                    nonce.resync();
+                }
+                return Err(DeployError::Rpc {
+                    method: "l1x_submitTransaction",
+                    source: err_code,
+                });
+            }
+        };
+        let events =
+            self.wait_for_events(&response.hash).await.map_err(|err_code| {
+                DeployError::Rpc { method: "l1x_getEvents", source: err_code }
+            })?;
+
+        let fee_used = match confirmation.receipt {
+            false => None,
+            true => {
+                let receipt = self
+                    .confirm_success(&response.hash)
+                    .await
+                    .map_err(|err_code| DeployError::Rpc {
+                        method: "l1x_getTransactionReceipt",
+                        source: err_code,
+                    })?;
+                if receipt.fee_used.is_none() {
+                    tracing::debug!(
+                        "The receipt of {} reports no fee used",
+                        response.hash
+                    );
+                }
+                receipt.fee_used
+            }
+        };
+
+        if let Some((confirmations, timeout)) = confirmation.finality {
+            let tx_block = self
+                .wait_for_finality(&response.hash, confirmations, timeout)
+                .await
+                .map_err(|err_code| DeployError::Finality {
+                    hash: response.hash.clone(),
+                    source: err_code,
+                })?;
+            tracing::info!(
+                "Txn {} | Final in block {} after {} confirmations",
+                response.hash,
+                tx_block,
+                confirmations
+            );
+        }
+
+        Ok(DeployResult {
+            hash: response.hash,
+            address: response.contract_address,
+            events,
+            fee,
+            fee_used,
+        })
+    }
+
+    /// Nonce the next transaction of the configured account must carry
+    pub async fn next_nonce(&self) -> Result<u128, ForgeClientError> {
+        if let Some(nonce) = self.config.nonce {
+            tracing::warn!("Signing with the nonce {} given by hand", nonce);
+            return Ok(nonce);
+        }
+
+        let nonce = self
+            .transport
+            .get_nonce(&self.account_address().to_hex())
+            .instrument(self.rpc_span("l1x_getAccountState"))
+            .await
+            .map_err(|err_code| match err_code {
+                rpc::RpcError::IdMismatch { .. } => {
+                    ForgeClientError::ReqIdMismatch(err_code.to_string())
+                }
+                err_code => ForgeClientError::InValidNonceError(format!(
+                    "L1X Submit Transaction Failed: Unable to get nounce {}",
+                    err_code
+                )),
+            })?
+            .ok_or_else(|| {
+                ForgeClientError::InValidNonceError(
+                    "L1X Submit Transaction Failed: Unable to get nounce, the node doesn't know the account"
+                        .to_string(),
+                )
+            })?;
+
+        Ok(nonce_manager::next_nonce(nonce))
+    }
+
+    /// Sign `txn` with the next account nonce and submit it
+    pub async fn submit_transaction(
+        &self,
+        txn: types::Transaction,
+    ) -> Result<SubmitTransactionResponse, ForgeClientError> {
+        self.submit_with_fee(txn).await.map(|(response, _)| response)
+    }
+
+    /// [`ForgeClient::submit_transaction`], with the fee limit `txn` was
+    /// signed with
+    async fn submit_with_fee(
+        &self,
+        txn: types::Transaction,
+    ) -> Result<(SubmitTransactionResponse, ChosenFee), ForgeClientError> {
+        let nonce = self.next_nonce().await?;
+
+        let (request, fee) = self.sign_with_chosen_fee(txn, nonce).await?;
+
+        Ok((self.submit_signed(&request).await?, fee))
+    }
+
+    /// Fee limit to sign `txn` carrying `nonce` with: the configured one or,
+    /// with a fee estimation, the estimate of the node plus the buffer. A
+    /// node without an estimate gets the payload size estimate, a failed
+    /// estimate the configured fee limit.
+    pub async fn fee_for(
+        &self,
+        txn: &types::Transaction,
+        nonce: u128,
+    ) -> Result<ChosenFee, ForgeClientError> {
+        let estimation = match self.config.fee_estimation {
+            Some(estimation) => estimation,
+            None => return Ok(ChosenFee::fixed(self.config.fee_limit)),
+        };
+
+        let request = self.sign_transaction_with_fee(
+            txn.clone(),
+            nonce,
+            self.config.fee_limit,
+        )?;
+        let payload_len = serde_json::to_vec(&request)
+            .map(|payload| payload.len())
+            .unwrap_or_default();
+        let node_estimate = match self
+            .post_rpc(fee::ESTIMATE_FEE_METHOD, json!({ "request": request }))
+            .await
+        {
+            Ok(result) => result
+                .as_ref()
+                .and_then(fee::estimate_of_result)
+                .map(Some)
+                .ok_or_else(|| {
+                    rpc::RpcError::InvalidResponse(format!(
+                        "{} returned no fee: {:?}",
+                        fee::ESTIMATE_FEE_METHOD,
+                        result
+                    ))
+                }),
+            Err(err_code) if err_code.is_method_not_found() => Ok(None),
+            Err(err_code) => Err(err_code),
+        };
+
+        Ok(fee::choose_fee(
+            node_estimate,
+            payload_len,
+            estimation,
+            self.config.fee_limit,
+        ))
+    }
+
+    /// Sign `txn` carrying `nonce` with the fee limit of
+    /// [`ForgeClient::fee_for`], to submit later with
+    /// [`ForgeClient::submit_signed`]
+    pub async fn sign_with_chosen_fee(
+        &self,
+        txn: types::Transaction,
+        nonce: u128,
+    ) -> Result<(SubmitTransactionRequest, ChosenFee), ForgeClientError> {
+        let fee = self.fee_for(&txn, nonce).await?;
+        tracing::info!("Fee limit of nonce {} :: {}", nonce, fee);
+
+        let request =
+            self.sign_transaction_with_fee(txn, nonce, fee.fee_limit)?;
+
+        Ok((request, fee))
+    }
+
+    /// Sign a call of `function` of the contract at `address` carrying
+    /// `nonce`, to submit later with [`ForgeClient::submit_signed`]
+    pub fn sign_function_call(
+        &self,
+        address: &str,
+        function: &str,
+        payload: &str,
+        nonce: u128,
+    ) -> Result<SubmitTransactionRequest, ForgeClientError> {
+        let txn = Self::create_txn_function_call(address, function, payload)?;
+
+        self.sign_transaction(txn, nonce)
+    }
+
+    /// Sign the transfer of `amount` native tokens to the 20 byte hex address
+    /// `to` carrying `nonce`, to submit later with
+    /// [`ForgeClient::submit_signed`]
+    pub fn sign_transfer(
+        &self,
+        to: &str,
+        amount: u128,
+        nonce: u128,
+    ) -> Result<SubmitTransactionRequest, ForgeClientError> {
+        let txn =
+            TransactionBuilder::new().native_transfer(to, amount)?.build()?;
+
+        self.sign_transaction(txn, nonce)
+    }
+
+    /// Sign `txn` carrying `nonce`, to submit later with
+    /// [`ForgeClient::submit_signed`]
+    pub fn sign_transaction(
+        &self,
+        txn: types::Transaction,
+        nonce: u128,
+    ) -> Result<SubmitTransactionRequest, ForgeClientError> {
+        self.sign_transaction_with_fee(txn, nonce, self.config.fee_limit)
+    }
+
+    /// Sign `txn` carrying `nonce` with `fee_limit` instead of the configured
+    /// one
+    pub fn sign_transaction_with_fee(
+        &self,
+        txn: types::Transaction,
+        nonce: u128,
+        fee_limit: u128,
+    ) -> Result<SubmitTransactionRequest, ForgeClientError> {
+        Ok(TransactionBuilder::new()
+            .transaction(txn)
+            .fee_limit(fee_limit)
+            .nonce(nonce)
+            .signing_scheme(self.config.signing_scheme)
+            .sign(&self.secret_key)?)
+    }
+
+    /// Sign `txn` with `nonce` like [`ForgeClient::deploy_transaction`] does,
+    /// without submitting it
+    pub async fn dry_run(
+        &self,
+        stage: &str,
+        txn: types::Transaction,
+        nonce: u128,
+        artifact_sha256: Option<String>,
+    ) -> Result<DryRunTxn, ForgeClientError> {
+        let (request, fee) = self.sign_with_chosen_fee(txn, nonce).await?;
+
+        Ok(DryRunTxn {
+            stage: stage.to_string(),
+            sender: self.account_address(),
+            nonce: nonce.to_string(),
+            fee_limit: fee.fee_limit.to_string(),
+            artifact_sha256,
+            request,
+        })
+    }
+
+    /// Submit a transaction signed by [`ForgeClient::sign_function_call`]
+    pub async fn submit_signed(
+        &self,
+        request: &SubmitTransactionRequest,
+    ) -> Result<SubmitTransactionResponse, ForgeClientError> {
+        self.transport
+            .submit(request.clone())
+            .instrument(self.rpc_span("l1x_submitTransaction"))
+            .await
+            .map_err(|err_code| match err_code {
+                rpc::RpcError::IdMismatch { .. } => {
+                    ForgeClientError::ReqIdMismatch(err_code.to_string())
+                }
+                rpc::RpcError::InvalidResponse(_) => {
+                    ForgeClientError::JsonParseError(format!(
+                        "L1X Submit Transaction Failed: Unable to parse the response {}",
+                        err_code
+                    ))
+                }
+                err_code => ForgeClientError::PostJsonRpcError(format!(
+                    "L1X Submit Transaction Failed: l1x_submitTransaction request failed {}",
+                    err_code
+                )),
+            })
+    }
+
+    /// Poll the events of `tx_hash` until the node reports some, for at
+    /// most the configured wait timeout
+    pub(crate) async fn wait_for_events(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Vec<Vec<u8>>, ForgeClientError> {
+        tracing::info!("Txn {} | Waiting for Event Data ...", tx_hash);
+
+        rpc::wait_for_events(
+            tx_hash,
+            self.config.event_poll_interval,
+            self.config.event_wait_timeout,
+            || self.get_events(tx_hash),
+        )
+        .await
+    }
+
+    pub async fn get_events(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Vec<Vec<u8>>, ForgeClientError> {
+        self.transport
+            .get_events(tx_hash)
+            .instrument(self.rpc_span("l1x_getEvents"))
+            .await
+            .map_err(|err_code| match err_code {
+                rpc::RpcError::IdMismatch { .. } => {
+                    ForgeClientError::ReqIdMismatch(err_code.to_string())
+                }
+                rpc::RpcError::InvalidResponse(_) => {
+                    ForgeClientError::JsonParseError(format!(
+                        "L1X Get Events Failed: Unable to parse the response {}",
+                        err_code
+                    ))
+                }
+                err_code => ForgeClientError::PostJsonRpcError(format!(
+                    "L1X Get Events Failed: l1x_getEvents request failed {}",
+                    err_code
+                )),
+            })
+    }
+
+    async fn read_only_call(
+        &self,
+        call: SmartContractReadOnlyCallRequest,
+    ) -> Result<Option<Vec<u8>>, ForgeClientError> {
+        self.transport
+            .read_only_call(call)
+            .instrument(self.rpc_span("l1x_smartContractReadOnlyCall"))
+            .await
+            .map_err(|err_code| match err_code {
+                rpc::RpcError::IdMismatch { .. } => {
+                    ForgeClientError::ReqIdMismatch(err_code.to_string())
+                }
+                rpc::RpcError::InvalidResponse(_) => {
+                    ForgeClientError::JsonParseError(format!(
+                        "Read-Only Txn Failed: Unable to parse JSON Value {}",
+                        err_code
+                    ))
+                }
+                err_code => ForgeClientError::PostJsonRpcError(format!(
+                    "Read-Only Txn Failed: Unable to post_json_rpc {}",
+                    err_code
+                )),
+            })
+    }
+
+    /// Receipt of `tx_hash` from the configured receipt method, `None` while
+    /// the node doesn't know the transaction
+    pub async fn transaction_receipt(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<rpc::TxReceipt>, ForgeClientError> {
+        self.transport
+            .get_receipt(tx_hash)
+            .instrument(self.rpc_span(&self.config.receipt_method))
+            .await
+            .map_err(|err_code| match err_code {
+                rpc::RpcError::IdMismatch { .. } => {
+                    ForgeClientError::ReqIdMismatch(err_code.to_string())
+                }
+                err_code => ForgeClientError::PostJsonRpcError(format!(
+                    "L1X Get Receipt Failed: {} request failed {}",
+                    self.config.receipt_method, err_code
+                )),
+            })
+    }
+
+    /// Block number `tx_hash` was included in, `None` while the node doesn't
+    /// know the transaction
+    pub async fn transaction_block(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<u128>, ForgeClientError> {
+        Ok(self
+            .transaction_receipt(tx_hash)
+            .await?
+            .and_then(|receipt| receipt.block_number))
+    }
+
+    /// Poll the receipt of `tx_hash` until the node knows the transaction,
+    /// for at most the configured event wait timeout, and check that it
+    /// succeeded in its block
+    pub async fn confirm_success(
+        &self,
+        tx_hash: &str,
+    ) -> Result<rpc::TxReceipt, ForgeClientError> {
+        tracing::info!("Txn {} | Waiting for the receipt ...", tx_hash);
+
+        let receipt = rpc::wait_for_receipt(
+            tx_hash,
+            self.config.poll_interval,
+            self.config.event_wait_timeout,
+            || self.transaction_receipt(tx_hash),
+        )
+        .await?;
+
+        match &receipt.status {
+            rpc::TxStatus::Success => Ok(receipt),
+            rpc::TxStatus::Failed(message) => Err(ForgeClientError::TxFailed {
+                hash: tx_hash.to_string(),
+                message: message.clone(),
+            }),
+        }
+    }
+
+    /// Balance and nonce of the account `address`, `None` when the node
+    /// doesn't know the account
+    pub async fn account_state(
+        &self,
+        address: &str,
+    ) -> Result<Option<AccountState>, ForgeClientError> {
+        let account_response = self.post_rpc(
+            "l1x_getAccountState",
+            json!({ "request": { "address": address } }),
+        )
+        .await
+        .map_err(|err_code| match err_code {
+            rpc::RpcError::IdMismatch { .. } => {
+                ForgeClientError::ReqIdMismatch(err_code.to_string())
+            }
+            err_code => ForgeClientError::PostJsonRpcError(format!(
+                "L1X Get Account State Failed: l1x_getAccountState request failed {}",
+                err_code
+            )),
+        })?;
+
+        let account_state = match account_response {
+            Some(response) if !response["account_state"].is_null() => {
+                response["account_state"].clone()
+            }
+            _ => return Ok(None),
+        };
+
+        match (
+            number_from(&account_state["balance"]),
+            number_from(&account_state["nonce"]),
+        ) {
+            (Some(balance), Some(nonce)) => {
+                Ok(Some(AccountState { balance, nonce }))
+            }
+            _ => Err(ForgeClientError::JsonParseError(
+                "L1X Get Account State Failed: Response has no balance or nonce"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Block number of the current chain head
+    pub async fn chain_head(&self) -> Result<u128, ForgeClientError> {
+        let chain_state_response = self.post_rpc(
+            "l1x_getChainState",
+            json!({ "request": {} }),
+        )
+        .await
+        .map_err(|err_code| match err_code {
+            rpc::RpcError::IdMismatch { .. } => {
+                ForgeClientError::ReqIdMismatch(err_code.to_string())
+            }
+            err_code => ForgeClientError::PostJsonRpcError(format!(
+                "L1X Get Chain State Failed: l1x_getChainState request failed {}",
+                err_code
+            )),
+        })?;
+
+        chain_state_response
+            .and_then(|chain_state| {
+                number_from(&chain_state["head_block_number"])
+            })
+            .ok_or_else(|| {
+                ForgeClientError::JsonParseError(
+                    "L1X Get Chain State Failed: Response has no head_block_number"
+                        .to_string(),
+                )
+            })
+    }
+
+    /// Wait until `tx_hash` is included in a block and the chain head is
+    /// `confirmations` blocks past it. Returns the inclusion block.
+    pub async fn wait_for_finality(
+        &self,
+        tx_hash: &str,
+        confirmations: u64,
+        timeout: Duration,
+    ) -> Result<u128, ForgeClientError> {
+        let deadline = Instant::now() + timeout;
+
+        let tx_block = loop {
+            if let Some(tx_block) = self.transaction_block(tx_hash).await? {
+                break tx_block;
+            }
+            if Instant::now() >= deadline {
+                return Err(ForgeClientError::FinalityTimeout(format!(
+                    "Transaction {} not found after {:?}",
+                    tx_hash, timeout
+                )));
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        };
+
+        tracing::info!(
+            "Txn {} | Included in block {} | Waiting for {} confirmations ...",
+            tx_hash,
+            tx_block,
+            confirmations
+        );
+
+        loop {
+            let head = self.chain_head().await?;
+            if head >= tx_block + u128::from(confirmations) {
+                return Ok(tx_block);
+            }
+            if Instant::now() >= deadline {
+                return Err(ForgeClientError::FinalityTimeout(format!(
+                    "Transaction {} in block {} has {} of {} confirmations after {:?}",
+                    tx_hash,
+                    tx_block,
+                    head.saturating_sub(tx_block),
+                    confirmations,
+                    timeout
+                )));
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+}
+
+/// Nodes report block numbers and amounts either as JSON numbers or as
+/// strings
+fn number_from(value: &Value) -> Option<u128> {
+    match value {
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(number) => number.parse().ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,855 @@
+use crate::forge_client::*;
+use l1x_common::fee::{ChosenFee, FeeEstimation, FeeSource};
+use l1x_common::transport::{L1XProtocol, L1xTransport};
+use l1x_common::{rpc, types};
+use l1x_rpc::rpc_model::{
+    SmartContractReadOnlyCallRequest, SubmitTransactionRequest,
+    SubmitTransactionResponse,
+};
+
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+
+/// JSON-RPC response `body`, echoing the id of the request like a node
+pub(crate) struct RpcResponse(pub Value);
+
+impl Respond for RpcResponse {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let mut body = self.0.clone();
+        body["id"] = serde_json::from_slice::<Value>(&request.body)
+            .map(|request| request["id"].clone())
+            .unwrap_or_default();
+        ResponseTemplate::new(200).set_body_json(body)
+    }
+}
+
+pub(crate) fn rpc_result(result: Value) -> RpcResponse {
+    RpcResponse(json!({ "jsonrpc": "2.0", "result": result }))
+}
+
+pub(crate) async fn mock_rpc(
+    server: &MockServer,
+    rpc_method: &str,
+    result: Value,
+) {
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": rpc_method })))
+        .respond_with(rpc_result(result))
+        .mount(server)
+        .await;
+}
+
+fn test_client(server: &MockServer) -> ForgeClient {
+    let config = ForgeClientConfig {
+        event_poll_interval: Duration::from_millis(10),
+        event_wait_timeout: Duration::from_millis(100),
+        poll_interval: Duration::from_millis(10),
+        rpc_retry: rpc::RetryPolicy::new(2, Duration::from_millis(1)),
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    };
+    ForgeClient::new(config).unwrap()
+}
+
+#[test]
+fn test_forge_client_rejects_invalid_private_key() {
+    // Arrange
+    let config = ForgeClientConfig::new("http://127.0.0.1:1", "not-a-hex-key");
+
+    // Act
+    let result = ForgeClient::new(config);
+
+    // Assert
+    assert!(matches!(result, Err(ForgeClientError::InvalidPrivateKey(_))));
+}
+
+#[test]
+fn test_forge_client_accepts_prefixed_private_key() {
+    // Arrange
+    let prefixed = format!(" 0x{}\n", TEST_PRIVATE_KEY);
+    let sign = |private_key: &str| {
+        ForgeClient::new(ForgeClientConfig::new(
+            "http://127.0.0.1:1",
+            private_key,
+        ))
+        .unwrap()
+        .sign_transfer(&"1".repeat(40), 5, 1)
+        .unwrap()
+    };
+
+    // Act
+    let prefixed_request = sign(&prefixed);
+    let request = sign(TEST_PRIVATE_KEY);
+
+    // Assert
+    assert_eq!(prefixed_request.signature, request.signature);
+    assert_eq!(prefixed_request.verifying_key, request.verifying_key);
+}
+
+#[test]
+fn test_account_address_vector() {
+    let config = ForgeClientConfig::new(
+        "http://127.0.0.1:1",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    );
+
+    let address = ForgeClient::new(config).unwrap().account_address();
+
+    assert_eq!(address.to_hex(), "7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+}
+
+#[test]
+fn test_sign_function_call_reports_invalid_payload() {
+    // Arrange
+    let config = ForgeClientConfig::new("http://127.0.0.1:1", TEST_PRIVATE_KEY);
+    let client = ForgeClient::new(config).unwrap();
+
+    // Act
+    let result = client.sign_function_call(&"1".repeat(40), "", "not hex", 1);
+
+    // Assert
+    assert!(matches!(result, Err(ForgeClientError::InvalidPayload(_))));
+}
+
+#[test]
+fn test_forge_client_config_debug_redacts_private_key() {
+    let config = ForgeClientConfig::new("http://127.0.0.1:1", TEST_PRIVATE_KEY);
+
+    assert!(!format!("{:?}", config).contains(TEST_PRIVATE_KEY));
+}
+
+#[tokio::test]
+async fn test_deploy_ebpf_returns_typed_result() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_submitTransaction",
+            "params": { "request": { "nonce": "6", "fee_limit": "100" } }
+        })))
+        .respond_with(rpc_result(
+            json!({ "hash": "deadbeef", "contract_address": "00aa" }),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_rpc(&server, "l1x_getEvents", json!({ "events_data": [[1, 2, 3]] }))
+        .await;
+
+    // Act
+    let result = test_client(&server)
+        .deploy_ebpf_with_nonce(
+            vec![0x7f, 0x45],
+            types::AccessType::PRIVATE,
+            types::ContractType::L1XVM,
+            6,
+        )
+        .await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        DeployResult {
+            hash: "deadbeef".to_string(),
+            address: Some("00aa".to_string()),
+            events: vec![vec![1, 2, 3]],
+            fee: ChosenFee::fixed(100),
+            fee_used: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_deploy_ebpf_times_out_without_events() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(
+        &server,
+        "l1x_getAccountState",
+        json!({ "account_state": { "balance": "5", "nonce": "5", "account_type": 0 } }),
+    )
+    .await;
+    mock_rpc(
+        &server,
+        "l1x_submitTransaction",
+        json!({ "hash": "deadbeef", "contract_address": "00aa" }),
+    )
+    .await;
+    mock_rpc(&server, "l1x_getEvents", json!({ "events_data": [] })).await;
+
+    // Act
+    let result = test_client(&server)
+        .deploy_ebpf(
+            vec![0x7f, 0x45],
+            types::AccessType::PRIVATE,
+            types::ContractType::L1XVM,
+        )
+        .await;
+
+    // Assert
+    match result {
+        Err(ForgeClientError::EventsTimeout(timeout)) => {
+            assert_eq!(timeout.tx_hash, "deadbeef");
+            assert!(timeout.waited >= Duration::from_millis(100));
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_call_contract_read_only_returns_result_bytes() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(
+        &server,
+        "l1x_smartContractReadOnlyCall",
+        json!({ "status": 0, "result": [104, 105] }),
+    )
+    .await;
+
+    // Act
+    let result = test_client(&server)
+        .call_contract("00aa", "balance_of", "7b7d", CallKind::ReadOnly)
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), CallResult::ReadOnly(Some(b"hi".to_vec())));
+}
+
+#[tokio::test]
+async fn test_transfer_fails_on_missing_account_state() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(&server, "l1x_getAccountState", json!({})).await;
+
+    // Act
+    let result = test_client(&server)
+        .transfer("0000000000000000000000000000000000000001", 10)
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(ForgeClientError::InValidNonceError(_))));
+}
+
+#[tokio::test]
+async fn test_wait_for_finality_waits_for_confirmations() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            json!({ "method": "l1x_getTransactionReceipt" }),
+        ))
+        .respond_with(rpc_result(Value::Null))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    mock_rpc(
+        &server,
+        "l1x_getTransactionReceipt",
+        json!({ "block_number": "10" }),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "l1x_getChainState" })))
+        .respond_with(rpc_result(json!({ "head_block_number": 11 })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    mock_rpc(&server, "l1x_getChainState", json!({ "head_block_number": 12 }))
+        .await;
+
+    // Act
+    let result = test_client(&server)
+        .wait_for_finality("deadbeef", 2, Duration::from_secs(5))
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), 10);
+}
+
+#[tokio::test]
+async fn test_wait_for_finality_times_out_for_dropped_transaction() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(&server, "l1x_getTransactionReceipt", Value::Null).await;
+
+    // Act
+    let result = test_client(&server)
+        .wait_for_finality("deadbeef", 2, Duration::from_millis(50))
+        .await;
+
+    // Assert
+    match result {
+        Err(ForgeClientError::FinalityTimeout(msg)) => {
+            assert!(msg.contains("deadbeef"))
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_rpc_requests_carry_req_id() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "l1x_getEvents", "id": 42 })))
+        .respond_with(rpc_result(json!({ "events_data": [] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let config = ForgeClientConfig {
+        req_id: 42,
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    };
+
+    // Act
+    let events = ForgeClient::new(config).unwrap().get_events("deadbeef").await;
+
+    // Assert
+    assert_eq!(events.unwrap(), Vec::<Vec<u8>>::new());
+}
+
+#[tokio::test]
+async fn test_rpc_requests_increment_the_req_id() {
+    // Arrange
+    let server = MockServer::start().await;
+    for (id, rpc_method, result) in [
+        (42, "l1x_getAccountState", json!({ "account_state": null })),
+        (43, "l1x_getEvents", json!({ "events_data": [] })),
+        (44, "l1x_getChainState", json!({ "head_block_number": "9" })),
+    ] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": rpc_method, "id": id })))
+            .respond_with(rpc_result(result))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    let client = ForgeClient::new(ForgeClientConfig {
+        req_id: 42,
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    })
+    .unwrap();
+
+    // Act
+    let account_state = client.account_state(&"22".repeat(20)).await;
+    let events = client.get_events("deadbeef").await;
+    let chain_head = client.chain_head().await;
+
+    // Assert
+    assert_eq!(account_state.unwrap(), None);
+    assert_eq!(events.unwrap(), Vec::<Vec<u8>>::new());
+    assert_eq!(chain_head.unwrap(), 9);
+}
+
+#[tokio::test]
+async fn test_rpc_response_with_another_id_is_a_req_id_mismatch() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "result": { "events_data": [] },
+            "id": 1
+        })))
+        .mount(&server)
+        .await;
+    let config = ForgeClientConfig {
+        req_id: 5,
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    };
+
+    // Act
+    let events = ForgeClient::new(config).unwrap().get_events("deadbeef").await;
+
+    // Assert
+    assert!(matches!(
+        events,
+        Err(ForgeClientError::ReqIdMismatch(msg))
+            if msg.contains("with id 5 carries the id 1")
+    ));
+}
+
+#[tokio::test]
+async fn test_rpc_error_response_is_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(RpcResponse(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32000, "message": "unknown transaction" },
+        })))
+        .mount(&server)
+        .await;
+
+    let result = test_client(&server).get_events("deadbeef").await;
+
+    assert!(
+        matches!(result, Err(ForgeClientError::PostJsonRpcError(msg)) if msg.contains("unknown transaction"))
+    );
+}
+
+#[tokio::test]
+async fn test_submit_retries_resend_the_same_signed_request() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": "l1x_submitTransaction" })))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    mock_rpc(
+        &server,
+        "l1x_submitTransaction",
+        json!({ "hash": "deadbeef", "contract_address": null }),
+    )
+    .await;
+    let client = test_client(&server);
+    let request = client.sign_transfer(&"1".repeat(40), 5, 8).unwrap();
+
+    // Act
+    let response = client.submit_signed(&request).await;
+
+    // Assert
+    assert_eq!(response.unwrap().hash, "deadbeef");
+    let bodies: Vec<Value> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|received| received.body_json().unwrap())
+        .collect();
+    assert_eq!(bodies.len(), 3);
+    assert!(bodies.iter().all(|body| body == &bodies[0]));
+    assert_eq!(bodies[0]["params"]["request"]["nonce"], "8");
+}
+
+#[tokio::test]
+async fn test_rpc_retries_are_exhausted_on_persistent_server_errors() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    // Act
+    let result = test_client(&server).next_nonce().await;
+
+    // Assert
+    match result {
+        Err(ForgeClientError::InValidNonceError(msg)) => {
+            assert!(msg.contains("HTTP status 503"), "{}", msg)
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_rpc_retries_recover_the_nonce_fetch() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    mock_rpc(
+        &server,
+        "l1x_getAccountState",
+        json!({ "account_state": { "balance": "5", "nonce": "7", "account_type": 0 } }),
+    )
+    .await;
+
+    // Act
+    let nonce = test_client(&server).next_nonce().await;
+
+    // Assert
+    assert_eq!(nonce.unwrap(), 8);
+}
+
+#[tokio::test]
+async fn test_nonce_given_by_hand_is_not_fetched() {
+    // Arrange
+    let server = MockServer::start().await;
+    let client = ForgeClient::new(ForgeClientConfig {
+        nonce: Some(3),
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    })
+    .unwrap();
+
+    // Act
+    let nonce = client.next_nonce().await;
+
+    // Assert
+    assert_eq!(nonce.unwrap(), 3);
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_dry_run_signs_like_a_submission() {
+    let client = ForgeClient::new(ForgeClientConfig {
+        fee_limit: 250,
+        ..ForgeClientConfig::new("http://127.0.0.1:1", TEST_PRIVATE_KEY)
+    })
+    .unwrap();
+    let txn = || {
+        ForgeClient::ebpf_deploy_txn(
+            vec![0x7f, 0x45],
+            types::AccessType::PRIVATE,
+            types::ContractType::L1XVM,
+        )
+        .unwrap()
+    };
+
+    let dry_run = client
+        .dry_run("eBPF Contract Deploy", txn(), 7, Some("ab".repeat(32)))
+        .await
+        .unwrap();
+
+    let dry_run_json = serde_json::to_value(&dry_run).unwrap();
+    assert_eq!(
+        dry_run_json["sender"],
+        format!("0x{}", client.account_address().to_hex())
+    );
+    assert_eq!(dry_run_json["stage"], "eBPF Contract Deploy");
+    assert_eq!(dry_run_json["nonce"], "7");
+    assert_eq!(dry_run_json["fee_limit"], "250");
+    assert_eq!(dry_run_json["artifact_sha256"], "ab".repeat(32));
+    assert_eq!(
+        dry_run_json["request"],
+        serde_json::to_value(client.sign_transaction(txn(), 7).unwrap())
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_confirm_success_waits_for_the_receipt() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            json!({ "method": "l1x_getTransactionReceipt" }),
+        ))
+        .respond_with(rpc_result(Value::Null))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    mock_rpc(
+        &server,
+        "l1x_getTransactionReceipt",
+        json!({ "block_number": "10", "status": 1 }),
+    )
+    .await;
+
+    // Act
+    let receipt = test_client(&server).confirm_success("deadbeef").await;
+
+    // Assert
+    assert_eq!(
+        receipt.unwrap(),
+        rpc::TxReceipt {
+            block_number: Some(10),
+            status: rpc::TxStatus::Success,
+            fee_used: None,
+        }
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_confirm_success_reports_the_on_chain_error() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(
+        &server,
+        "l1x_getTransactionReceipt",
+        json!({ "block_number": 10, "status": "failed", "error": "contract trapped: unreachable" }),
+    )
+    .await;
+
+    // Act
+    let result = test_client(&server).confirm_success("deadbeef").await;
+
+    // Assert
+    match result {
+        Err(err_code @ ForgeClientError::TxFailed { .. }) => assert_eq!(
+            err_code.to_string(),
+            "Transaction deadbeef failed in its block: contract trapped: unreachable"
+        ),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_confirm_success_times_out_without_receipt() {
+    let server = MockServer::start().await;
+    mock_rpc(&server, "l1x_getTransactionReceipt", Value::Null).await;
+
+    let result = test_client(&server).confirm_success("deadbeef").await;
+
+    assert!(
+        matches!(&result, Err(ForgeClientError::ReceiptTimeout(timeout)) if timeout.tx_hash == "deadbeef"),
+        "{:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_receipt_method_is_configurable() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(&server, "l1x_getTxStatus", json!({ "status": true })).await;
+    let config = ForgeClientConfig {
+        receipt_method: "l1x_getTxStatus".to_string(),
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    };
+
+    // Act
+    let receipt =
+        ForgeClient::new(config).unwrap().confirm_success("deadbeef").await;
+
+    // Assert
+    assert_eq!(receipt.unwrap().status, rpc::TxStatus::Success);
+}
+
+/// Transport of a node answering every submit with `hash`, recording the
+/// nonces submitted
+#[derive(Debug, Default)]
+struct FakeTransport {
+    submitted: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl L1xTransport for FakeTransport {
+    async fn submit(
+        &self,
+        request: SubmitTransactionRequest,
+    ) -> Result<SubmitTransactionResponse, rpc::RpcError> {
+        self.submitted.lock().unwrap().push(request.nonce);
+        Ok(SubmitTransactionResponse {
+            hash: "deadbeef".to_string(),
+            contract_address: None,
+        })
+    }
+
+    async fn get_events(
+        &self,
+        _tx_hash: &str,
+    ) -> Result<Vec<Vec<u8>>, rpc::RpcError> {
+        Ok(vec![vec![1]])
+    }
+
+    async fn read_only_call(
+        &self,
+        _call: SmartContractReadOnlyCallRequest,
+    ) -> Result<Option<Vec<u8>>, rpc::RpcError> {
+        Ok(Some(b"hi".to_vec()))
+    }
+
+    async fn get_nonce(
+        &self,
+        _address: &str,
+    ) -> Result<Option<u128>, rpc::RpcError> {
+        Ok(Some(4))
+    }
+
+    async fn get_receipt(
+        &self,
+        _tx_hash: &str,
+    ) -> Result<Option<rpc::TxReceipt>, rpc::RpcError> {
+        Err(rpc::RpcError::Rpc { code: 5, message: "not found".to_string() })
+    }
+}
+
+#[tokio::test]
+async fn test_transactions_go_through_the_transport() {
+    // Arrange
+    let transport = Arc::new(FakeTransport::default());
+    let client = ForgeClient::with_transport(
+        ForgeClientConfig::new("http://127.0.0.1:1", TEST_PRIVATE_KEY),
+        transport.clone(),
+    )
+    .unwrap();
+
+    // Act
+    let transfer = client.transfer(&"1".repeat(40), 10).await;
+    let read_only = client
+        .call_contract(&"1".repeat(40), "", "00", CallKind::ReadOnly)
+        .await;
+    let receipt = client.transaction_receipt("deadbeef").await;
+
+    // Assert
+    assert_eq!(
+        transfer.unwrap(),
+        TxResult {
+            hash: "deadbeef".to_string(),
+            events: vec![vec![1]],
+            fee: ChosenFee::fixed(100),
+        }
+    );
+    assert_eq!(*transport.submitted.lock().unwrap(), vec!["5"]);
+    assert_eq!(read_only.unwrap(), CallResult::ReadOnly(Some(b"hi".to_vec())));
+    assert!(
+        matches!(&receipt, Err(ForgeClientError::PostJsonRpcError(msg)) if msg.contains("not found")),
+        "{:?}",
+        receipt
+    );
+}
+
+#[test]
+fn test_forge_client_config_defaults_to_json_rpc() {
+    let config = ForgeClientConfig::new("http://127.0.0.1:1", TEST_PRIVATE_KEY);
+
+    assert_eq!(config.protocol, L1XProtocol::Json);
+}
+
+fn auto_fee_client(server: &MockServer) -> ForgeClient {
+    ForgeClient::new(ForgeClientConfig {
+        fee_limit: 150,
+        fee_estimation: Some(FeeEstimation {
+            buffer_pct: 20,
+            fee_per_byte: 0.01,
+        }),
+        event_poll_interval: Duration::from_millis(10),
+        event_wait_timeout: Duration::from_millis(100),
+        rpc_retry: rpc::RetryPolicy::NONE,
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    })
+    .unwrap()
+}
+
+fn call_txn() -> types::Transaction {
+    ForgeClient::create_txn_function_call(&"1".repeat(40), "", "00").unwrap()
+}
+
+#[test]
+fn test_function_call_carries_the_function_name() {
+    let txn = ForgeClient::create_txn_function_call(
+        &"1".repeat(40),
+        "transfer",
+        "00ff",
+    );
+
+    assert!(
+        matches!(
+            &txn,
+            Ok(types::Transaction::SmartContractFunctionCall {
+                function: types::U8s::Text(function),
+                ..
+            }) if function == "transfer"
+        ),
+        "{:?}",
+        txn
+    );
+}
+
+#[test]
+fn test_read_only_call_carries_the_function_name() {
+    let call = ForgeClient::create_ronly_txn_function_call(
+        &"1".repeat(40),
+        "balance_of",
+        "00ff",
+    );
+
+    let call = call.unwrap().call.unwrap();
+    assert_eq!(call.function_name, b"balance_of".to_vec());
+    assert_eq!(call.arguments, vec![0x00, 0xff]);
+}
+
+#[tokio::test]
+async fn test_auto_fee_limit_signs_the_node_estimate_plus_buffer() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(
+        &server,
+        "l1x_getAccountState",
+        json!({ "account_state": { "balance": "5", "nonce": "4" } }),
+    )
+    .await;
+    mock_rpc(&server, "l1x_estimateFee", json!({ "fee": "200" })).await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_submitTransaction",
+            "params": { "request": { "nonce": "5", "fee_limit": "240" } }
+        })))
+        .respond_with(rpc_result(json!({ "hash": "deadbeef" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_rpc(&server, "l1x_getEvents", json!({ "events_data": [[1]] })).await;
+
+    // Act
+    let transfer = auto_fee_client(&server).transfer(&"1".repeat(40), 10).await;
+
+    // Assert
+    assert_eq!(
+        transfer.unwrap().fee,
+        ChosenFee { fee_limit: 240, source: FeeSource::Node }
+    );
+}
+
+#[tokio::test]
+async fn test_auto_fee_limit_without_node_estimate_uses_the_payload_size() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(RpcResponse(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32601, "message": "Method not found" },
+        })))
+        .mount(&server)
+        .await;
+
+    // Act
+    let fee = auto_fee_client(&server).fee_for(&call_txn(), 1).await;
+
+    // Assert
+    assert_eq!(
+        fee.unwrap(),
+        ChosenFee { fee_limit: 120, source: FeeSource::PayloadSize }
+    );
+}
+
+#[tokio::test]
+async fn test_auto_fee_limit_falls_back_when_the_estimate_fails() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    // Act
+    let fee = auto_fee_client(&server).fee_for(&call_txn(), 1).await;
+
+    // Assert
+    assert_eq!(
+        fee.unwrap(),
+        ChosenFee { fee_limit: 150, source: FeeSource::Fallback }
+    );
+}
+
+#[tokio::test]
+async fn test_fixed_fee_limit_queries_no_estimate() {
+    // Arrange
+    let server = MockServer::start().await;
+    let client = ForgeClient::new(ForgeClientConfig {
+        fee_limit: 250,
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    })
+    .unwrap();
+
+    // Act
+    let fee = client.fee_for(&call_txn(), 1).await;
+
+    // Assert
+    assert_eq!(fee.unwrap(), ChosenFee::fixed(250));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,180 @@
+use crate::contract_install::{
+    L1XAccessType, L1XInstallFailure, L1XInstallOutcome,
+};
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, thiserror::Error)]
+pub enum L1XInstallManifestError {
+    #[error("Unable to read the manifest {path}: {reason}")]
+    FileError { path: String, reason: String },
+    #[error("Invalid manifest {path}: {reason}")]
+    Invalid { path: String, reason: String },
+}
+
+/// One contract instance of a `--manifest`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct L1XInstallManifestEntry {
+    pub artifact_id: String,
+    pub contract_id: String,
+    /// Init arguments, a string is passed as is, any other value as JSON
+    #[serde(default)]
+    pub init_args: Option<serde_json::Value>,
+    #[serde(default)]
+    pub access_type: Option<L1XAccessType>,
+    /// Wallet signing the initialization, the --owner of the command
+    /// otherwise
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+impl L1XInstallManifestEntry {
+    /// `--init-args` of the entry, `None` when it has none
+    pub fn init_args_text(&self) -> Option<String> {
+        self.init_args.as_ref().map(|init_args| match init_args {
+            serde_json::Value::String(text) => text.clone(),
+            init_args => init_args.to_string(),
+        })
+    }
+}
+
+/// Contract instances `vm-install-contract --manifest` installs, in order
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct L1XInstallManifest {
+    pub contracts: Vec<L1XInstallManifestEntry>,
+}
+
+impl L1XInstallManifest {
+    /// Manifest of the YAML `content`, read from `path`. An instance listed
+    /// twice, or an artifact listed with two access types, is rejected.
+    pub fn parse(
+        path: &str,
+        content: &str,
+    ) -> Result<Self, L1XInstallManifestError> {
+        let invalid = |reason: String| L1XInstallManifestError::Invalid {
+            path: path.to_string(),
+            reason,
+        };
+
+        let manifest: L1XInstallManifest = serde_yaml::from_str(content)
+            .map_err(|err_code| invalid(err_code.to_string()))?;
+        if manifest.contracts.is_empty() {
+            return Err(invalid("no contracts listed".to_string()));
+        }
+
+        let mut instances = HashSet::new();
+        let mut access_types = HashMap::new();
+        for (index, entry) in manifest.contracts.iter().enumerate() {
+            if entry.artifact_id.trim().is_empty()
+                || entry.contract_id.trim().is_empty()
+            {
+                return Err(invalid(format!(
+                    "contracts[{}]: empty artifact_id or contract_id",
+                    index
+                )));
+            }
+            if !instances.insert((&entry.artifact_id, &entry.contract_id)) {
+                return Err(invalid(format!(
+                    "contracts[{}]: {} of {} is listed twice",
+                    index, entry.contract_id, entry.artifact_id
+                )));
+            }
+            let access_type = access_types
+                .entry(&entry.artifact_id)
+                .or_insert(entry.access_type);
+            if *access_type != entry.access_type {
+                return Err(invalid(format!(
+                    "contracts[{}]: {} is deployed once, give all its entries the same access_type",
+                    index, entry.artifact_id
+                )));
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Manifest of the YAML file `path`
+    pub fn load(path: &Path) -> Result<Self, L1XInstallManifestError> {
+        let content = std::fs::read_to_string(path).map_err(|err_code| {
+            L1XInstallManifestError::FileError {
+                path: path.display().to_string(),
+                reason: err_code.to_string(),
+            }
+        })?;
+
+        Self::parse(&path.display().to_string(), &content)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum L1XManifestEntryStatus {
+    Installed,
+    /// Already initialized, nothing submitted
+    Skipped,
+    Failed,
+}
+
+/// Outcome of one entry of a `--manifest` install
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct L1XManifestEntryOutcome {
+    pub artifact_id: String,
+    pub contract_id: String,
+    pub status: L1XManifestEntryStatus,
+    pub outcome: Option<L1XInstallOutcome>,
+    pub error: Option<L1XInstallFailure>,
+}
+
+impl L1XManifestEntryOutcome {
+    pub fn new(
+        entry: &L1XInstallManifestEntry,
+        result: Result<L1XInstallOutcome, L1XInstallFailure>,
+    ) -> Self {
+        let (status, outcome, error) = match result {
+            Ok(outcome) if outcome.init_tx_hash.is_none() => {
+                (L1XManifestEntryStatus::Skipped, Some(outcome), None)
+            }
+            Ok(outcome) => {
+                (L1XManifestEntryStatus::Installed, Some(outcome), None)
+            }
+            Err(failure) => {
+                (L1XManifestEntryStatus::Failed, None, Some(failure))
+            }
+        };
+
+        L1XManifestEntryOutcome {
+            artifact_id: entry.artifact_id.clone(),
+            contract_id: entry.contract_id.clone(),
+            status,
+            outcome,
+            error,
+        }
+    }
+}
+
+impl std::fmt::Display for L1XManifestEntryOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let instance_address =
+            self.outcome.as_ref().and_then(|outcome| outcome.instance_address);
+        let status = match self.status {
+            L1XManifestEntryStatus::Installed => "installed",
+            L1XManifestEntryStatus::Skipped => "skipped, already initialized",
+            L1XManifestEntryStatus::Failed => "failed",
+        };
+        write!(
+            f,
+            "{} of {} :: {}",
+            self.contract_id, self.artifact_id, status
+        )?;
+
+        match (&self.error, instance_address) {
+            (Some(failure), _) => write!(f, " :: {}", failure.message),
+            (None, Some(address)) => write!(f, " :: {}", address),
+            (None, None) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::contract_install::L1XAccessType;
+use crate::install_manifest::*;
+
+use serde_json::json;
+
+#[test]
+fn test_parse_manifest_entries() {
+    let manifest = L1XInstallManifest::parse(
+        "contracts.yaml",
+        r#"
+contracts:
+  - artifact_id: ft
+    contract_id: ft_usd
+    init_args: { symbol: "USD", decimals: 6 }
+    access_type: public
+  - artifact_id: ft
+    contract_id: ft_eur
+    init_args: '{"symbol": "EUR"}'
+    access_type: public
+    owner: bobby
+  - artifact_id: nft
+    contract_id: nft_main
+"#,
+    )
+    .unwrap();
+
+    let entries = &manifest.contracts;
+    assert_eq!(entries.len(), 3);
+    assert_eq!(
+        entries[0].init_args,
+        Some(json!({ "symbol": "USD", "decimals": 6 }))
+    );
+    assert_eq!(entries[0].access_type, Some(L1XAccessType::Public));
+    assert_eq!(entries[0].owner, None);
+    assert_eq!(
+        entries[1].init_args_text().as_deref(),
+        Some(r#"{"symbol": "EUR"}"#)
+    );
+    assert_eq!(entries[1].owner.as_deref(), Some("bobby"));
+    assert_eq!(entries[2].init_args_text(), None);
+    assert_eq!(entries[2].access_type, None);
+}
+
+#[test]
+fn test_init_args_object_is_passed_as_json() {
+    let manifest = L1XInstallManifest::parse(
+        "contracts.yaml",
+        "contracts:\n  - artifact_id: ft\n    contract_id: ft_main\n    init_args: { symbol: USD }\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        manifest.contracts[0].init_args_text().as_deref(),
+        Some(r#"{"symbol":"USD"}"#)
+    );
+}
+
+#[test]
+fn test_invalid_manifests_are_rejected() {
+    let cases = [
+        ("contracts: []\n", "no contracts listed"),
+        (
+            "contracts:\n  - artifact_id: ft\n    contract_id: \"\"\n",
+            "contracts[0]: empty artifact_id or contract_id",
+        ),
+        (
+            "contracts:\n  - artifact_id: ft\n    contract_id: ft_main\n  - artifact_id: ft\n    contract_id: ft_main\n",
+            "contracts[1]: ft_main of ft is listed twice",
+        ),
+        (
+            "contracts:\n  - artifact_id: ft\n    contract_id: ft_a\n    access_type: public\n  - artifact_id: ft\n    contract_id: ft_b\n",
+            "contracts[1]: ft is deployed once, give all its entries the same access_type",
+        ),
+    ];
+
+    for (content, reason) in cases {
+        assert_eq!(
+            L1XInstallManifest::parse("contracts.yaml", content)
+                .unwrap_err()
+                .to_string(),
+            format!("Invalid manifest contracts.yaml: {}", reason)
+        );
+    }
+    assert!(L1XInstallManifest::parse(
+        "contracts.yaml",
+        "contracts:\n  - artifact_id: ft\n    contract_id: ft_main\n    salt: x\n",
+    )
+    .is_err());
+}
+
+#[test]
+fn test_missing_manifest_is_a_file_error() {
+    let err = L1XInstallManifest::load(std::path::Path::new(
+        "/nonexistent/contracts.yaml",
+    ))
+    .unwrap_err();
+
+    assert!(matches!(err, L1XInstallManifestError::FileError { .. }));
+    assert!(err.to_string().starts_with(
+        "Unable to read the manifest /nonexistent/contracts.yaml"
+    ));
+}
@@ -0,0 +1,63 @@
+use crate::peer_id::secp256k1_key_hex;
+use l1x_common::KeyScheme;
+
+use anyhow::Result;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum L1XKeyScheme {
+    #[clap(name = "secp256k1")]
+    Secp256k1,
+    #[clap(name = "ed25519")]
+    Ed25519,
+}
+
+impl From<L1XKeyScheme> for KeyScheme {
+    fn from(scheme: L1XKeyScheme) -> Self {
+        match scheme {
+            L1XKeyScheme::Secp256k1 => KeyScheme::Secp256k1,
+            L1XKeyScheme::Ed25519 => KeyScheme::Ed25519,
+        }
+    }
+}
+
+/// Generate a node identity, or print the keys of an existing one
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "keygen")]
+pub struct L1XKeygenCmd {
+    #[clap(long = "scheme", value_enum, default_value_t = L1XKeyScheme::Secp256k1)]
+    scheme: L1XKeyScheme,
+
+    /// Hex private key to derive the public key and PeerId of
+    #[clap(long = "private-key")]
+    private_key: Option<String>,
+}
+
+impl L1XKeygenCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let private_key = match (self.scheme, self.private_key.as_deref()) {
+            (L1XKeyScheme::Secp256k1, Some(private_key)) => {
+                Some(secp256k1_key_hex(private_key)?)
+            }
+            (L1XKeyScheme::Ed25519, Some(private_key)) => {
+                let private_key = private_key.trim();
+                Some(
+                    private_key
+                        .strip_prefix("0x")
+                        .unwrap_or(private_key)
+                        .to_string(),
+                )
+            }
+            (_, None) => None,
+        };
+
+        let keys =
+            l1x_common::generate_node_keys(self.scheme.into(), private_key)?;
+
+        println!("private_key: {}", keys.privkey);
+        println!("public_key: {}", keys.pubkey);
+        println!("peer_id: {}", keys.peer_id);
+
+        Ok(())
+    }
+}
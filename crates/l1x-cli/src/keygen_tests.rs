@@ -0,0 +1,53 @@
+use l1x_common::{generate_node_keys, CredsError, KeyScheme};
+
+// RFC 8032 ed25519 test vector 1
+const ED25519_PRIV_KEY: &str =
+    "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const ED25519_PUB_KEY: &str =
+    "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+
+#[test]
+fn test_generate_node_keys_round_trip() {
+    for scheme in [KeyScheme::Secp256k1, KeyScheme::Ed25519] {
+        let generated = generate_node_keys(scheme, None).unwrap();
+
+        let decoded =
+            generate_node_keys(scheme, Some(generated.privkey.clone()))
+                .unwrap();
+
+        assert_eq!(decoded, generated, "{:?}", scheme);
+    }
+}
+
+#[test]
+fn test_generate_node_keys_decodes_ed25519_key() {
+    let keys =
+        generate_node_keys(KeyScheme::Ed25519, Some(ED25519_PRIV_KEY.into()))
+            .unwrap();
+
+    assert_eq!(keys.privkey, ED25519_PRIV_KEY);
+    assert_eq!(keys.pubkey, ED25519_PUB_KEY);
+    // ed25519 PeerIds are identity multihashes of the protobuf encoded key
+    assert!(keys.peer_id.to_string().starts_with("12D3KooW"));
+}
+
+#[test]
+fn test_secp256k1_creds_matches_generate_node_keys() {
+    let key = "0".repeat(63) + "1";
+
+    let (privkey, pubkey, peer_id) =
+        l1x_common::secp256k1_creds(Some(key.clone())).unwrap();
+    let keys = generate_node_keys(KeyScheme::Secp256k1, Some(key)).unwrap();
+
+    assert_eq!(
+        (privkey, pubkey, peer_id),
+        (keys.privkey, keys.pubkey, keys.peer_id)
+    );
+}
+
+#[test]
+fn test_generate_node_keys_rejects_short_ed25519_key() {
+    let result = generate_node_keys(KeyScheme::Ed25519, Some("abcd".into()));
+
+    assert!(matches!(result, Err(CredsError::InvalidKey(_))));
+}
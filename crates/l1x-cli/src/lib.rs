@@ -1,5 +1,149 @@
+mod account;
+#[cfg(test)]
+mod account_tests;
+mod address;
+mod address_of;
+#[cfg(test)]
+mod address_of_tests;
+#[cfg(test)]
+mod address_tests;
+mod artifacts;
+#[cfg(test)]
+mod artifacts_tests;
+mod balance;
+#[cfg(test)]
+mod balance_tests;
+mod chain_check;
+#[cfg(test)]
+mod chain_check_tests;
+mod cli_error;
+#[cfg(test)]
+mod cli_error_tests;
+mod config;
+#[cfg(test)]
+mod config_tests;
+mod confirm;
+#[cfg(test)]
+mod confirm_tests;
+mod contract_cosign;
+#[cfg(test)]
+mod contract_cosign_tests;
 mod contract_install;
+#[cfg(test)]
+mod contract_install_test;
+mod contract_stress;
+#[cfg(test)]
+mod contract_stress_tests;
 mod contract_sub_txn;
+#[cfg(test)]
+mod contract_sub_txn_tests;
+mod contract_uninstall;
+#[cfg(test)]
+mod contract_uninstall_tests;
+mod doctor;
+#[cfg(test)]
+mod doctor_tests;
+mod evm_artifact;
+#[cfg(test)]
+mod evm_artifact_tests;
+mod faucet;
+#[cfg(test)]
+mod faucet_tests;
+mod forge_client;
+#[cfg(test)]
+mod forge_client_tests;
+mod install_manifest;
+#[cfg(test)]
+mod install_manifest_tests;
+mod keygen;
+#[cfg(test)]
+mod keygen_tests;
+mod message;
+#[cfg(test)]
+mod message_tests;
+mod multisend;
+#[cfg(test)]
+mod multisend_tests;
+mod nonce;
+#[cfg(test)]
+mod nonce_tests;
+mod payload;
+#[cfg(test)]
+mod payload_tests;
+mod peer_id;
+#[cfg(test)]
+mod peer_id_tests;
+mod registry;
+#[cfg(test)]
+mod registry_tests;
+mod signer;
+#[cfg(test)]
+mod signer_tests;
+mod staking;
+#[cfg(test)]
+mod staking_tests;
+mod transfer;
+#[cfg(test)]
+mod transfer_tests;
+mod tx;
+#[cfg(test)]
+mod tx_tests;
+mod verify;
+#[cfg(test)]
+mod verify_tests;
+mod workspace_init;
+#[cfg(test)]
+mod workspace_init_tests;
 
-pub use contract_install::L1XVmInstallContractCmd;
+pub use account::{
+    L1XAccountCmd, L1XAccountInfo, L1XCreatedAccount, L1XKeyExportFormat,
+};
+pub use address::L1XAddressCmd;
+pub use address_of::{L1XAddressInfo, L1XAddressOfCmd};
+pub use artifacts::L1XArtifactsCmd;
+pub use balance::L1XBalanceCmd;
+pub use cli_error::L1XConfigError;
+pub use config::{L1XConfigCmd, L1XConfigPath, L1XNetworkInfo};
+pub use confirm::{
+    L1XConfirmError, L1XPrompt, L1XSubmitSummary, L1XTerminalPrompt,
+};
+pub use contract_cosign::{
+    L1XTxnEnvelope, L1XTxnEnvelopeError, L1XTxnEnvelopeSignature,
+    L1XVmCosignCmd, L1XVmSendRawCmd, L1XVmSignTxnCmd,
+};
+pub use contract_install::{
+    L1XFeeUsedTotal, L1XInstallErrorKind, L1XInstallFailure, L1XInstallOutcome,
+    L1XInstallTimings, L1XOutputFormat, L1XVmContractInstallError,
+    L1XVmInstallContractCmd,
+};
+pub use contract_stress::{L1XStressSummary, L1XVmStressCmd};
 pub use contract_sub_txn::L1XVmSubTxnCmd;
+pub use contract_uninstall::L1XVmUninstallContractCmd;
+pub use doctor::{Check, CheckOutcome, L1XDoctorCmd};
+pub use evm_artifact::{L1XEvmArtifact, L1XEvmArtifactError};
+pub use faucet::{L1XFaucetCmd, L1XFaucetOutcome, L1XFaucetRecipient};
+pub use forge_client::{
+    AccountState, CallKind, CallResult, DeployResult, DryRunTxn, ForgeClient,
+    ForgeClientConfig, ForgeClientError, TxResult,
+};
+pub use install_manifest::{
+    L1XInstallManifest, L1XInstallManifestEntry, L1XInstallManifestError,
+    L1XManifestEntryOutcome, L1XManifestEntryStatus,
+};
+pub use keygen::{L1XKeyScheme, L1XKeygenCmd};
+pub use message::{
+    L1XMessageSignature, L1XSignMessageCmd, L1XVerifyMessageCmd,
+};
+pub use multisend::{L1XBatchError, L1XBatchOutcome, L1XBatchRecipient};
+pub use nonce::L1XNonceCmd;
+pub use payload::L1XPayloadCmd;
+pub use peer_id::{L1XPeerIdCmd, L1XPeerIdInfo};
+pub use registry::{L1XRegistryCmd, L1XRegistryPruneOutcome};
+pub use signer::L1XPrivateKeyArg;
+pub use staking::{
+    L1XCreatePoolCmd, L1XStakeCmd, L1XStakingTxnArgs, L1XUnstakeCmd,
+};
+pub use transfer::{L1XTransferCmd, L1XTransferError};
+pub use tx::{L1XSignedTxn, L1XTxCmd, L1XTxError, L1XUnsignedTxn};
+pub use verify::{L1XEvmCodeMode, L1XVerifyCmd, L1XVerifyOutcome};
+pub use workspace_init::L1XInitWorkspaceCmd;
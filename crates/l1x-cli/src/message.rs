@@ -0,0 +1,163 @@
+use crate::address_of::parse_pub_key;
+use crate::cli_error::L1XConfigError;
+use l1x_common::{message, toolkit_config::ToolkitConfig, types::AddressStr};
+
+use anyhow::Result;
+use secp256k1::Secp256k1;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Message given inline or read from a file
+#[derive(Clone, Debug, clap::Args)]
+#[clap(group(clap::ArgGroup::new("input").required(true).args(["message", "file"])))]
+pub struct L1XMessageInput {
+    #[clap(long = "message")]
+    message: Option<String>,
+
+    /// Sign or verify the bytes of this file
+    #[clap(long = "file")]
+    file: Option<PathBuf>,
+}
+
+impl L1XMessageInput {
+    fn bytes(&self) -> Result<Vec<u8>> {
+        match (&self.message, &self.file) {
+            (Some(message), _) => Ok(message.as_bytes().to_vec()),
+            (_, Some(file)) => std::fs::read(file).map_err(|err| {
+                anyhow::anyhow!("Failed to read '{}': {}", file.display(), err)
+            }),
+            (None, None) => anyhow::bail!("--message or --file is required"),
+        }
+    }
+}
+
+/// Sign a message with a dev wallet key
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "sign-message")]
+pub struct L1XSignMessageCmd {
+    #[clap(long = "owner")]
+    owner: String,
+
+    #[clap(flatten)]
+    input: L1XMessageInput,
+
+    #[clap(long = "json", default_value_t = false)]
+    json: bool,
+}
+
+/// Compact signature of a message and the compressed key verifying it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct L1XMessageSignature {
+    pub signature: String,
+    pub verifying_key: String,
+}
+
+/// Check a message signature against a public key or an account address
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "verify-message")]
+#[clap(group(clap::ArgGroup::new("signer").required(true).args(["address", "pubkey"])))]
+pub struct L1XVerifyMessageCmd {
+    #[clap(long = "address")]
+    address: Option<String>,
+
+    #[clap(long = "pubkey")]
+    pubkey: Option<String>,
+
+    #[clap(flatten)]
+    input: L1XMessageInput,
+
+    /// Hex compact signature printed by sign-message
+    #[clap(long = "signature")]
+    signature: String,
+
+    #[clap(long = "json", default_value_t = false)]
+    json: bool,
+}
+
+/// Sign `data` with the key of the wallet `owner`
+pub fn sign_wallet_message(
+    owner: &str,
+    data: &[u8],
+) -> Result<L1XMessageSignature> {
+    let priv_key = ToolkitConfig::from_env()
+        .and_then(|toolkit| toolkit.wallet_priv_key(owner))
+        .map_err(L1XConfigError::from)?;
+    let secret_key = crate::account::parse_private_key(&priv_key)?;
+
+    Ok(L1XMessageSignature {
+        signature: hex::encode(message::sign_message(&secret_key, data)),
+        verifying_key: hex::encode(
+            secret_key.public_key(&Secp256k1::new()).serialize(),
+        ),
+    })
+}
+
+/// Check `signature` of `data` against the `pubkey` or the `address` of the
+/// signer, all hex with or without `0x`
+pub fn verify_message_signature(
+    address: Option<&str>,
+    pubkey: Option<&str>,
+    data: &[u8],
+    signature: &str,
+) -> Result<bool> {
+    let clean_signature = signature.trim();
+    let signature = hex::decode(
+        clean_signature.strip_prefix("0x").unwrap_or(clean_signature),
+    )
+    .map_err(|err| anyhow::anyhow!("Invalid signature hex: {}", err))?;
+
+    match (pubkey, address) {
+        (Some(pubkey), _) => message::verify_message(
+            &parse_pub_key(pubkey)?.serialize(),
+            data,
+            &signature,
+        ),
+        (_, Some(address)) => {
+            let address = address.parse::<AddressStr>()?;
+
+            message::verify_message_from_address(
+                address.as_bytes(),
+                data,
+                &signature,
+            )
+        }
+        (None, None) => anyhow::bail!("--address or --pubkey is required"),
+    }
+}
+
+impl L1XSignMessageCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let signature = sign_wallet_message(&self.owner, &self.input.bytes()?)?;
+
+        match self.json {
+            true => println!("{}", serde_json::to_string_pretty(&signature)?),
+            false => {
+                println!("signature: {}", signature.signature);
+                println!("verifying_key: {}", signature.verifying_key);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl L1XVerifyMessageCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let valid = verify_message_signature(
+            self.address.as_deref(),
+            self.pubkey.as_deref(),
+            &self.input.bytes()?,
+            &self.signature,
+        )?;
+
+        match self.json {
+            true => println!("{}", serde_json::json!({ "valid": valid })),
+            false => println!("valid: {}", valid),
+        }
+
+        match valid {
+            true => Ok(()),
+            false => anyhow::bail!("The signature does not match the message"),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::contract_install_test::{with_temp_registry, write_toolkit_config};
+use crate::message::*;
+
+const ADDRESS_ONE: &str = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
+#[test]
+fn test_sign_wallet_message_verifies_by_pubkey_and_address() {
+    with_temp_registry(None, |ws_home| {
+        // Arrange
+        write_toolkit_config(ws_home);
+
+        // Act
+        let signed = sign_wallet_message("ava", b"login nonce 42").unwrap();
+        let by_pubkey = verify_message_signature(
+            None,
+            Some(&signed.verifying_key),
+            b"login nonce 42",
+            &format!("0x{}", signed.signature),
+        );
+        let by_address = verify_message_signature(
+            Some(ADDRESS_ONE),
+            None,
+            b"login nonce 42",
+            &signed.signature,
+        );
+
+        // Assert
+        assert!(by_pubkey.unwrap());
+        assert!(by_address.unwrap());
+    });
+}
+
+#[test]
+fn test_verify_message_signature_rejects_other_message() {
+    with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        let signed = sign_wallet_message("ava", b"login nonce 42").unwrap();
+
+        let valid = verify_message_signature(
+            Some(ADDRESS_ONE),
+            None,
+            b"login nonce 43",
+            &signed.signature,
+        );
+
+        assert!(!valid.unwrap());
+    });
+}
+
+#[test]
+fn test_verify_message_signature_rejects_bad_hex() {
+    let result =
+        verify_message_signature(Some(ADDRESS_ONE), None, b"hi", "0xnothex");
+
+    assert!(result.unwrap_err().to_string().contains("Invalid signature hex"));
+}
+
+#[test]
+fn test_sign_wallet_message_unknown_owner() {
+    with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+
+        let result = sign_wallet_message("ghost", b"hi");
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("wallet 'ghost' not found"));
+    });
+}
@@ -0,0 +1,269 @@
+use crate::forge_client::{ForgeClient, ForgeClientError};
+use l1x_common::toolkit_config;
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+#[derive(Debug, thiserror::Error)]
+pub enum L1XBatchError {
+    #[error("{path}:{line}: {reason}")]
+    InvalidLine { path: String, line: usize, reason: String },
+    #[error("Batch file error: {0}")]
+    FileError(String),
+}
+
+/// One `address,amount` line of a `--batch` recipients file
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1XBatchRecipient {
+    /// Lowercase hex address without `0x`
+    pub address: String,
+    pub amount: u128,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum L1XBatchOutcome {
+    Sent {
+        hash: String,
+    },
+    /// Sent by an earlier run according to the progress file
+    AlreadySent {
+        hash: String,
+    },
+    Failed(String),
+}
+
+impl L1XBatchOutcome {
+    pub fn hash(&self) -> Option<&str> {
+        match self {
+            L1XBatchOutcome::Sent { hash }
+            | L1XBatchOutcome::AlreadySent { hash } => Some(hash),
+            L1XBatchOutcome::Failed(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for L1XBatchOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            L1XBatchOutcome::Sent { hash } => write!(f, "sent :: {}", hash),
+            L1XBatchOutcome::AlreadySent { hash } => {
+                write!(f, "skipped :: already sent in {}", hash)
+            }
+            L1XBatchOutcome::Failed(reason) => {
+                write!(f, "failed :: {}", reason)
+            }
+        }
+    }
+}
+
+/// Recipients of the `address,amount` lines of `content`, read from `path`.
+/// Blank lines, `#` comments and an `address,amount` header are skipped, an
+/// address listed twice is rejected.
+pub fn parse_batch(
+    path: &str,
+    content: &str,
+) -> Result<Vec<L1XBatchRecipient>, L1XBatchError> {
+    let invalid = |line: usize, reason: String| L1XBatchError::InvalidLine {
+        path: path.to_string(),
+        line,
+        reason,
+    };
+
+    let mut recipients = Vec::new();
+    let mut addresses = HashSet::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.eq_ignore_ascii_case("address,amount")
+        {
+            continue;
+        }
+
+        let (address, amount) = match line.split(',').collect::<Vec<_>>()[..] {
+            [address, amount] => (address.trim(), amount.trim()),
+            _ => {
+                return Err(invalid(
+                    line_number,
+                    format!("expected 'address,amount', got '{}'", line),
+                ))
+            }
+        };
+
+        let address = toolkit_config::normalize_address(address)
+            .map_err(|reason| invalid(line_number, reason))?;
+        let amount = match amount.parse::<u128>() {
+            Ok(0) => {
+                return Err(invalid(line_number, "amount is 0".to_string()))
+            }
+            Ok(amount) => amount,
+            Err(err_code) => {
+                return Err(invalid(
+                    line_number,
+                    format!("invalid amount '{}': {}", amount, err_code),
+                ))
+            }
+        };
+        if !addresses.insert(address.clone()) {
+            return Err(invalid(
+                line_number,
+                format!("0x{} is listed twice", address),
+            ));
+        }
+
+        recipients.push(L1XBatchRecipient { address, amount });
+    }
+
+    Ok(recipients)
+}
+
+/// Progress file of the recipients file `batch_path`, next to it
+pub fn progress_path(batch_path: &Path) -> PathBuf {
+    let mut file_name = batch_path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".progress.json");
+    batch_path.with_file_name(file_name)
+}
+
+/// Hash of the transfer every address of the progress file `path` was sent,
+/// empty when the file doesn't exist yet
+pub fn load_progress(
+    path: &Path,
+) -> Result<BTreeMap<String, String>, L1XBatchError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err_code) if err_code.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(BTreeMap::new())
+        }
+        Err(err_code) => {
+            return Err(L1XBatchError::FileError(format!(
+                "Unable to read {}: {}",
+                path.display(),
+                err_code
+            )))
+        }
+    };
+
+    serde_json::from_str(&content).map_err(|err_code| {
+        L1XBatchError::FileError(format!(
+            "Unable to parse {}: {}",
+            path.display(),
+            err_code
+        ))
+    })
+}
+
+/// Record the sent transfers of `outcomes` in the progress file `path`
+pub fn save_progress(
+    path: &Path,
+    recipients: &[L1XBatchRecipient],
+    outcomes: &[L1XBatchOutcome],
+) -> Result<(), L1XBatchError> {
+    let progress: BTreeMap<&str, &str> = recipients
+        .iter()
+        .zip(outcomes)
+        .filter_map(|(recipient, outcome)| {
+            Some((recipient.address.as_str(), outcome.hash()?))
+        })
+        .collect();
+
+    let content =
+        serde_json::to_string_pretty(&progress).map_err(|err_code| {
+            L1XBatchError::FileError(format!(
+                "Unable to serialize {}: {}",
+                path.display(),
+                err_code
+            ))
+        })?;
+    std::fs::write(path, content).map_err(|err_code| {
+        L1XBatchError::FileError(format!(
+            "Unable to write {}: {}",
+            path.display(),
+            err_code
+        ))
+    })
+}
+
+/// Transfer to every recipient but those in `progress`, with consecutive
+/// nonces from a single nonce query. At most `max_in_flight` transfers are
+/// submitted and awaited at once, a failed transfer doesn't stop the others.
+/// Outcomes are in the order of `recipients`.
+pub async fn multisend(
+    client: Arc<ForgeClient>,
+    recipients: &[L1XBatchRecipient],
+    progress: &BTreeMap<String, String>,
+    max_in_flight: usize,
+    timeout: Duration,
+) -> Result<Vec<L1XBatchOutcome>, ForgeClientError> {
+    let mut outcomes = vec![None; recipients.len()];
+    let mut requests = Vec::new();
+    let mut nonce = client.next_nonce().await?;
+
+    for (index, recipient) in recipients.iter().enumerate() {
+        if let Some(hash) = progress.get(&recipient.address) {
+            outcomes[index] =
+                Some(L1XBatchOutcome::AlreadySent { hash: hash.clone() });
+            continue;
+        }
+
+        match client.sign_transfer(&recipient.address, recipient.amount, nonce)
+        {
+            Ok(request) => {
+                nonce += 1;
+                requests.push((index, request));
+            }
+            Err(err_code) => {
+                outcomes[index] =
+                    Some(L1XBatchOutcome::Failed(err_code.to_string()))
+            }
+        }
+    }
+
+    let in_flight = Arc::new(Semaphore::new(max_in_flight.max(1)));
+    let mut transfers = JoinSet::new();
+    for (index, request) in requests {
+        let permit = in_flight
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the in-flight semaphore is never closed");
+        let client = client.clone();
+
+        transfers.spawn(async move {
+            let outcome = match client.submit_signed(&request).await {
+                Ok(response) => client
+                    .wait_for_finality(&response.hash, 0, timeout)
+                    .await
+                    .map(|_| L1XBatchOutcome::Sent { hash: response.hash }),
+                Err(err_code) => Err(err_code),
+            };
+            drop(permit);
+            (index, outcome)
+        });
+    }
+
+    while let Some(transfer) = transfers.join_next().await {
+        match transfer {
+            Ok((index, outcome)) => {
+                outcomes[index] = Some(outcome.unwrap_or_else(|err_code| {
+                    L1XBatchOutcome::Failed(err_code.to_string())
+                }))
+            }
+            Err(err_code) => {
+                tracing::warn!("Batch transfer task failed :: {}", err_code)
+            }
+        }
+    }
+
+    Ok(outcomes
+        .into_iter()
+        .map(|outcome| {
+            outcome.unwrap_or_else(|| {
+                L1XBatchOutcome::Failed("transfer task failed".to_string())
+            })
+        })
+        .collect())
+}
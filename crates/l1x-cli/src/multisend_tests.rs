@@ -0,0 +1,152 @@
+use crate::forge_client::{ForgeClient, ForgeClientConfig};
+use crate::forge_client_tests::{mock_rpc, rpc_result};
+use crate::multisend::*;
+use l1x_common::rpc;
+
+use serde_json::json;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+
+fn recipient(address_byte: &str, amount: u128) -> L1XBatchRecipient {
+    L1XBatchRecipient { address: address_byte.repeat(20), amount }
+}
+
+fn invalid_line(content: &str) -> (usize, String) {
+    match parse_batch("recipients.csv", content) {
+        Err(L1XBatchError::InvalidLine { line, reason, .. }) => (line, reason),
+        other => panic!("expected an invalid line, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_batch_skips_header_comments_and_blank_lines() {
+    let content = format!(
+        "address,amount\n# team wallets\n\n0x{},10\n {} , 20 \n",
+        "AB".repeat(20),
+        "cd".repeat(20)
+    );
+
+    let recipients = parse_batch("recipients.csv", &content).unwrap();
+
+    assert_eq!(recipients, vec![recipient("ab", 10), recipient("cd", 20)]);
+}
+
+#[test]
+fn test_parse_batch_reports_invalid_lines() {
+    let address = "ab".repeat(20);
+
+    assert_eq!(invalid_line(&format!("{},10,3", address)).0, 1);
+    assert_eq!(invalid_line(&address).0, 1);
+    assert_eq!(invalid_line("# header\n0x7e5f45zz,10").0, 2);
+    assert_eq!(
+        invalid_line(&format!("{},0", address)),
+        (1, "amount is 0".to_string())
+    );
+    assert_eq!(invalid_line(&format!("{},-5", address)).0, 1);
+    assert_eq!(invalid_line(&format!("{},1.5", address)).0, 1);
+    assert_eq!(
+        invalid_line(&format!("{},10\n0x{},20", address, address)),
+        (2, format!("0x{} is listed twice", address))
+    );
+}
+
+#[test]
+fn test_invalid_line_error_names_file_and_line() {
+    let err = parse_batch("recipients.csv", "\n\nnot-an-address").unwrap_err();
+
+    assert!(err.to_string().starts_with("recipients.csv:3: "));
+}
+
+#[test]
+fn test_progress_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = progress_path(&dir.path().join("recipients.csv"));
+    let recipients = [recipient("01", 1), recipient("02", 2)];
+    let outcomes = [
+        L1XBatchOutcome::Sent { hash: "aa01".to_string() },
+        L1XBatchOutcome::Failed("timeout".to_string()),
+    ];
+
+    let missing = load_progress(&path).unwrap();
+    save_progress(&path, &recipients, &outcomes).unwrap();
+    let progress = load_progress(&path).unwrap();
+
+    assert!(missing.is_empty());
+    assert_eq!(path.file_name().unwrap(), "recipients.csv.progress.json");
+    assert_eq!(
+        progress,
+        BTreeMap::from([("01".repeat(20), "aa01".to_string())])
+    );
+}
+
+#[tokio::test]
+async fn test_multisend_continues_past_failures_and_skips_sent_recipients() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(
+        &server,
+        "l1x_getAccountState",
+        json!({ "account_state": { "balance": "500", "nonce": "7", "account_type": 0 } }),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_submitTransaction",
+            "params": { "request": { "nonce": "8" } },
+        })))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_submitTransaction",
+            "params": { "request": { "nonce": "9" } },
+        })))
+        .respond_with(rpc_result(
+            json!({ "hash": "aa03", "contract_address": null }),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_rpc(
+        &server,
+        "l1x_getTransactionReceipt",
+        json!({ "block_number": 3 }),
+    )
+    .await;
+    mock_rpc(&server, "l1x_getChainState", json!({ "head_block_number": 3 }))
+        .await;
+    let client = ForgeClient::new(ForgeClientConfig {
+        poll_interval: Duration::from_millis(10),
+        rpc_retry: rpc::RetryPolicy::NONE,
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    })
+    .unwrap();
+    let recipients =
+        [recipient("01", 10), recipient("02", 20), recipient("03", 30)];
+    let progress = BTreeMap::from([("01".repeat(20), "aa01".to_string())]);
+
+    // Act
+    let outcomes = multisend(
+        Arc::new(client),
+        &recipients,
+        &progress,
+        2,
+        Duration::from_secs(5),
+    )
+    .await
+    .unwrap();
+
+    // Assert
+    assert_eq!(
+        outcomes[0],
+        L1XBatchOutcome::AlreadySent { hash: "aa01".to_string() }
+    );
+    assert!(matches!(outcomes[1], L1XBatchOutcome::Failed(_)));
+    assert_eq!(outcomes[2], L1XBatchOutcome::Sent { hash: "aa03".to_string() });
+}
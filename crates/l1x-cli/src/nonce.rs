@@ -0,0 +1,65 @@
+use crate::balance::resolve_address;
+use crate::cli_error::L1XConfigError;
+use l1x_common::{
+    nonce_manager, rpc,
+    toolkit_config::{self, ToolkitConfig},
+};
+
+use anyhow::Result;
+use std::time::Duration;
+
+/// Print the nonce the active chain reports for a wallet or address
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "nonce")]
+#[clap(group(clap::ArgGroup::new("account").required(true).args(["owner", "address"])))]
+pub struct L1XNonceCmd {
+    #[clap(long = "owner")]
+    owner: Option<String>,
+
+    /// Hex account address, with or without `0x`
+    #[clap(long = "address")]
+    address: Option<String>,
+
+    /// Network of l1x_chain_config.yaml to query, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+
+    /// Seconds to wait for the node response
+    #[clap(long = "timeout", default_value_t = 10)]
+    timeout: u64,
+}
+
+impl L1XNonceCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let address = match (&self.owner, &self.address) {
+            (Some(owner), _) => resolve_address(owner)?,
+            (_, Some(address)) => toolkit_config::normalize_address(address)
+                .map_err(rpc::RpcError::InvalidAddress)?,
+            (None, None) => {
+                anyhow::bail!("One of --owner or --address is required")
+            }
+        };
+        let end_point = ToolkitConfig::from_env()
+            .and_then(|toolkit| {
+                toolkit
+                    .with_network(self.network.as_deref())
+                    .active_chain_json_rpc_endpoint()
+            })
+            .map_err(L1XConfigError::from)?;
+
+        let nonce = rpc::fetch_nonce(
+            &end_point,
+            &address,
+            Duration::from_secs(self.timeout),
+        )
+        .await?;
+
+        println!("address: 0x{}", address);
+        println!("nonce: {}", nonce);
+        // The next transaction of the account must carry this nonce
+        println!("next_nonce: {}", nonce_manager::next_nonce(nonce));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use l1x_common::rpc::{self, RpcError};
+
+use serde_json::json;
+use std::time::Duration;
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const ADDRESS_ONE: &str = "7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
+#[tokio::test]
+async fn test_fetch_nonce() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_getAccountState",
+            "params": { "request": { "address": ADDRESS_ONE } },
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "account_state": { "balance": "0", "nonce": "41", "account_type": 0 },
+            },
+        })))
+        .mount(&server)
+        .await;
+
+    // Act
+    let nonce = rpc::fetch_nonce(
+        &server.uri(),
+        &format!("0x{}", ADDRESS_ONE),
+        rpc::DEFAULT_RPC_TIMEOUT,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(nonce.unwrap(), 41);
+}
+
+#[tokio::test]
+async fn test_fetch_nonce_maps_rpc_error() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32602, "message": "invalid address" },
+        })))
+        .mount(&server)
+        .await;
+
+    // Act
+    let result =
+        rpc::fetch_nonce(&server.uri(), ADDRESS_ONE, rpc::DEFAULT_RPC_TIMEOUT)
+            .await;
+
+    // Assert
+    match result {
+        Err(RpcError::Rpc { code, message }) => {
+            assert_eq!(code, -32602);
+            assert_eq!(message, "invalid address");
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_nonce_times_out() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(
+                    json!({ "jsonrpc": "2.0", "id": 1, "result": {} }),
+                )
+                .set_delay(Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+
+    // Act
+    let result =
+        rpc::fetch_nonce(&server.uri(), ADDRESS_ONE, Duration::from_millis(50))
+            .await;
+
+    // Assert
+    assert!(matches!(result, Err(RpcError::Transport { .. })));
+}
+
+#[tokio::test]
+async fn test_fetch_nonce_rejects_invalid_address() {
+    let result = rpc::fetch_nonce(
+        "http://127.0.0.1:1",
+        "0x01",
+        rpc::DEFAULT_RPC_TIMEOUT,
+    )
+    .await;
+
+    assert!(matches!(result, Err(RpcError::InvalidAddress(_))));
+}
@@ -0,0 +1,155 @@
+use crate::cli_error::L1XConfigError;
+use l1x_common::{payload_schema, toolkit_config::ToolkitConfig, types};
+
+use anyhow::Result;
+use std::fs;
+
+/// Build transaction payload files for `load_submit_txn_req`
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "payload")]
+pub struct L1XPayloadCmd {
+    #[clap(subcommand)]
+    command: L1XPayloadSubCmd,
+}
+
+#[derive(Clone, Debug, clap::Subcommand)]
+enum L1XPayloadSubCmd {
+    /// Nest a call to a target contract in a call to an outer contract
+    #[command(name = "build-cross-call")]
+    BuildCrossCall(L1XPayloadBuildCrossCallCmd),
+    /// Check a transaction payload file, `-` reading it from stdin
+    #[command(name = "validate")]
+    Validate(L1XPayloadValidateCmd),
+    /// Print a template payload of a transaction type
+    #[command(name = "example")]
+    Example(L1XPayloadExampleCmd),
+}
+
+impl L1XPayloadCmd {
+    pub async fn exec(&self) -> Result<()> {
+        match &self.command {
+            L1XPayloadSubCmd::BuildCrossCall(build_cmd) => build_cmd.exec(),
+            L1XPayloadSubCmd::Validate(validate_cmd) => validate_cmd.exec(),
+            L1XPayloadSubCmd::Example(example_cmd) => example_cmd.exec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct L1XPayloadBuildCrossCallCmd {
+    /// Contract receiving the transaction [ address | artifact-id | artifact-id/contract-id ]
+    #[clap(long = "outer-alias")]
+    outer_alias: String,
+
+    #[clap(long = "outer-function")]
+    outer_function: String,
+
+    /// Contract called by the outer contract [ address | artifact-id | artifact-id/contract-id ]
+    #[clap(long = "target-alias")]
+    target_alias: String,
+
+    #[clap(long = "inner-function")]
+    inner_function: String,
+
+    /// JSON arguments of the inner function
+    #[clap(long = "inner-args", default_value = "{}")]
+    inner_args: String,
+
+    #[clap(long = "out")]
+    out: String,
+
+    /// Network whose registry resolves the aliases, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+}
+
+impl L1XPayloadBuildCrossCallCmd {
+    pub fn exec(&self) -> Result<()> {
+        tracing::debug!(
+            "L1X Payload Build Cross Call With Args :: {:#?}!",
+            &self
+        );
+
+        let toolkit = ToolkitConfig::from_env()
+            .map_err(L1XConfigError::from)?
+            .with_network(self.network.as_deref());
+        let outer_address = toolkit
+            .resolve_contract_address(&self.outer_alias)
+            .map_err(L1XConfigError::from)?;
+        let target_address = toolkit
+            .resolve_contract_address(&self.target_alias)
+            .map_err(L1XConfigError::from)?;
+
+        let inner_args: serde_json::Value =
+            serde_json::from_str(&self.inner_args).map_err(|err_code| {
+                anyhow::anyhow!("--inner-args is not valid JSON: {}", err_code)
+            })?;
+
+        let txn = types::CrossContractCall::new(
+            &target_address.to_hex(),
+            &self.inner_function,
+            &inner_args,
+        )?
+        .into_transaction(&outer_address.to_hex(), &self.outer_function)?;
+
+        let payload = serde_json::to_string_pretty(&txn)?;
+        l1x_common::validate_transaction_payload(&payload)
+            .map_err(|err_code| anyhow::anyhow!("{}", err_code))?;
+
+        fs::write(&self.out, payload)?;
+        println!(
+            "Wrote cross call payload {} :: {} -> {}",
+            self.out, outer_address, target_address
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct L1XPayloadValidateCmd {
+    /// Payload file, parsed as YAML or TOML by its extension and as JSON
+    /// otherwise
+    payload_file: String,
+}
+
+impl L1XPayloadValidateCmd {
+    pub fn exec(&self) -> Result<()> {
+        l1x_common::load_transaction_payload(&self.payload_file, None)
+            .map_err(|err_code| anyhow::anyhow!("{}", err_code))?;
+
+        println!("{} is a valid transaction payload", self.payload_file);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct L1XPayloadExampleCmd {
+    /// Transaction type of the template
+    #[clap(value_parser = clap::builder::PossibleValuesParser::new(
+        payload_schema::TRANSACTION_VARIANTS.iter().copied(),
+    ))]
+    variant: String,
+}
+
+impl L1XPayloadExampleCmd {
+    pub fn exec(&self) -> Result<()> {
+        println!("{}", example_payload(&self.variant)?);
+        Ok(())
+    }
+}
+
+/// Pretty JSON template payload of the transaction `variant`
+pub fn example_payload(variant: &str) -> Result<String> {
+    let txn =
+        payload_schema::example_transaction(variant).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown transaction type '{}', expected one of {}",
+                variant,
+                payload_schema::TRANSACTION_VARIANTS.join(", ")
+            )
+        })?;
+
+    Ok(serde_json::to_string_pretty(&txn)?)
+}
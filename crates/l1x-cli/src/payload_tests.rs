@@ -0,0 +1,135 @@
+use crate::payload::*;
+use l1x_common::types::{CrossContractCallArguments, Transaction, U8s};
+
+use clap::Parser;
+use std::fs;
+
+const OUTER_ADDRESS: &str = "0x1111111111111111111111111111111111111111";
+const TARGET_ADDRESS: &str = "2222222222222222222222222222222222222222";
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    payload: L1XPayloadCmd,
+}
+
+#[tokio::test]
+async fn test_build_cross_call_payload_round_trips() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("cross_call.json");
+    let cli = TestCli::parse_from([
+        "payload",
+        "build-cross-call",
+        "--outer-alias",
+        OUTER_ADDRESS,
+        "--outer-function",
+        "swap",
+        "--target-alias",
+        TARGET_ADDRESS,
+        "--inner-function",
+        "transfer",
+        "--inner-args",
+        r#"{"amount": 10}"#,
+        "--out",
+        out.to_str().unwrap(),
+    ]);
+
+    // Act
+    cli.payload.exec().await.unwrap();
+
+    // Assert
+    let txn = l1x_common::validate_transaction_payload(
+        &fs::read_to_string(&out).unwrap(),
+    )
+    .unwrap();
+    let (contract_instance_address, function, arguments) = match txn {
+        Transaction::SmartContractFunctionCall {
+            contract_instance_address,
+            function,
+            arguments: U8s::Text(arguments),
+        } => (contract_instance_address, function, arguments),
+        _ => panic!("Expected a function call payload"),
+    };
+    assert_eq!(contract_instance_address, U8s::Hex("1".repeat(40)));
+    assert_eq!(function, U8s::Text("swap".to_string()));
+    let arguments: CrossContractCallArguments =
+        serde_json::from_str(&arguments).unwrap();
+    assert_eq!(arguments.cross_contract_call.contract_address, TARGET_ADDRESS);
+    assert_eq!(arguments.cross_contract_call.function, "transfer");
+    assert_eq!(
+        hex::decode(arguments.cross_contract_call.arguments).unwrap(),
+        br#"{"amount":10}"#
+    );
+}
+
+#[tokio::test]
+async fn test_build_cross_call_rejects_invalid_inner_args() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("cross_call.json");
+    let cli = TestCli::parse_from([
+        "payload",
+        "build-cross-call",
+        "--outer-alias",
+        OUTER_ADDRESS,
+        "--outer-function",
+        "swap",
+        "--target-alias",
+        TARGET_ADDRESS,
+        "--inner-function",
+        "transfer",
+        "--inner-args",
+        "not json",
+        "--out",
+        out.to_str().unwrap(),
+    ]);
+
+    assert!(cli.payload.exec().await.is_err());
+    assert!(!out.exists());
+}
+
+#[tokio::test]
+async fn test_validate_reports_the_offending_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let valid = dir.path().join("valid.json");
+    fs::write(&valid, example_payload("stake").unwrap()).unwrap();
+    let invalid = dir.path().join("invalid.yaml");
+    fs::write(
+        &invalid,
+        "stake:\n  pool_address: \"b1c2d3e4f5061728394a5b6c7d8e9f0123456789\"\n  amount: 5\n",
+    )
+    .unwrap();
+    let validate = |path: &std::path::Path| {
+        TestCli::parse_from(["payload", "validate", path.to_str().unwrap()])
+    };
+
+    let valid_result = validate(&valid).payload.exec().await;
+    let invalid_result = validate(&invalid).payload.exec().await;
+
+    assert!(valid_result.is_ok());
+    let message = invalid_result.unwrap_err().to_string();
+    assert!(
+        message.ends_with(":: `stake.pool_address`: expected object with `hex`, `bytes`, `file`, `text` or `base64` key, got string"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn test_example_payloads_parse_back() {
+    for variant in
+        ["native_token_transfer", "smart_contract_function_call", "un_stake"]
+    {
+        let payload = example_payload(variant).unwrap();
+
+        assert!(
+            l1x_common::validate_transaction_payload(&payload).is_ok(),
+            "{}",
+            variant
+        );
+    }
+    assert!(example_payload("transfer").is_err());
+    assert!(
+        TestCli::try_parse_from(["payload", "example", "transfer"]).is_err()
+    );
+}
@@ -0,0 +1,88 @@
+use l1x_common::{CredsError, TxnBuildError};
+
+use anyhow::Result;
+
+/// Derive the libp2p PeerId of a validator key
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "peer-id")]
+pub struct L1XPeerIdCmd {
+    /// Hex secp256k1 private key, a new key is generated when neither it nor
+    /// --pubkey is given
+    #[clap(long = "private-key", conflicts_with = "pubkey")]
+    private_key: Option<String>,
+
+    /// Hex compressed or uncompressed secp256k1 public key
+    #[clap(long = "pubkey")]
+    pubkey: Option<String>,
+}
+
+/// Keys and PeerId of a validator, `private_key` is only set for newly
+/// generated keys
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1XPeerIdInfo {
+    pub private_key: Option<String>,
+    pub public_key: String,
+    pub peer_id: String,
+}
+
+fn strip_hex_prefix(key: &str) -> &str {
+    let key = key.trim();
+    key.strip_prefix("0x").unwrap_or(key)
+}
+
+/// Unprefixed hex of the secp256k1 `private_key`, checked by
+/// [`l1x_common::parse_secret_key`]
+pub(crate) fn secp256k1_key_hex(
+    private_key: &str,
+) -> Result<String, CredsError> {
+    l1x_common::parse_secret_key(private_key)
+        .map(|secret_key| hex::encode(secret_key.secret_bytes()))
+        .map_err(|err_code| {
+            CredsError::InvalidKey(match err_code {
+                TxnBuildError::InvalidPrivateKey(reason) => reason,
+                err_code => err_code.to_string(),
+            })
+        })
+}
+
+/// [`L1XPeerIdInfo`] of `private_key`, `pubkey` or of a new key
+pub fn peer_id_info(
+    private_key: Option<&str>,
+    pubkey: Option<&str>,
+) -> Result<L1XPeerIdInfo, CredsError> {
+    if let Some(pubkey) = pubkey {
+        let pubkey = strip_hex_prefix(pubkey);
+        let peer_id = l1x_common::peer_id_from_pub_key(&hex::decode(pubkey)?)?;
+
+        return Ok(L1XPeerIdInfo {
+            private_key: None,
+            public_key: pubkey.to_lowercase(),
+            peer_id: peer_id.to_string(),
+        });
+    }
+
+    let (generated_key, public_key, peer_id) = l1x_common::secp256k1_creds(
+        private_key.map(secp256k1_key_hex).transpose()?,
+    )?;
+
+    Ok(L1XPeerIdInfo {
+        private_key: private_key.is_none().then_some(generated_key),
+        public_key,
+        peer_id: peer_id.to_string(),
+    })
+}
+
+impl L1XPeerIdCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let info =
+            peer_id_info(self.private_key.as_deref(), self.pubkey.as_deref())?;
+
+        if let Some(private_key) = &info.private_key {
+            println!("private_key: {}", private_key);
+        }
+        println!("public_key: {}", info.public_key);
+        println!("peer_id: {}", info.peer_id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::peer_id::*;
+
+// secp256k1 key 1, its compressed public key and the PeerId of the
+// identity multihash of that key
+const PRIV_KEY_ONE: &str =
+    "0x0000000000000000000000000000000000000000000000000000000000000001";
+const COMPRESSED_PUB_KEY_ONE: &str =
+    "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const UNCOMPRESSED_PUB_KEY_ONE: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+const PEER_ID_ONE: &str =
+    "16Uiu2HAm3cuhhRL2msUuLF62KRSfneFDx94RsuouyW25Ho42cFMq";
+
+#[test]
+fn test_peer_id_of_private_key() {
+    let info = peer_id_info(Some(PRIV_KEY_ONE), None).unwrap();
+
+    assert_eq!(
+        info,
+        L1XPeerIdInfo {
+            private_key: None,
+            public_key: COMPRESSED_PUB_KEY_ONE.to_string(),
+            peer_id: PEER_ID_ONE.to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_peer_id_of_compressed_and_uncompressed_pubkey() {
+    let compressed =
+        peer_id_info(None, Some(&format!("0x{}", COMPRESSED_PUB_KEY_ONE)))
+            .unwrap();
+    let uncompressed =
+        peer_id_info(None, Some(UNCOMPRESSED_PUB_KEY_ONE)).unwrap();
+
+    assert_eq!(compressed.peer_id, PEER_ID_ONE);
+    assert_eq!(uncompressed.peer_id, PEER_ID_ONE);
+    assert_eq!(compressed.private_key, None);
+}
+
+#[test]
+fn test_peer_id_of_generated_key_prints_private_key() {
+    let info = peer_id_info(None, None).unwrap();
+
+    let private_key = info.private_key.unwrap();
+    let derived = peer_id_info(Some(&private_key), None).unwrap();
+    assert_eq!(derived.peer_id, info.peer_id);
+}
+
+#[test]
+fn test_peer_id_rejects_malformed_hex() {
+    let result = peer_id_info(Some("0xnothex"), None);
+    let short = peer_id_info(Some("0xabcd"), None);
+
+    match result {
+        Err(l1x_common::CredsError::InvalidKey(reason)) => {
+            assert_eq!(reason, "character 0 is not hex");
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    match short {
+        Err(l1x_common::CredsError::InvalidKey(reason)) => {
+            assert_eq!(
+                reason,
+                "expected 32 bytes (64 hex chars), got 4 hex chars"
+            );
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(matches!(
+        peer_id_info(None, Some("02abcd")),
+        Err(l1x_common::CredsError::InvalidKey(_))
+    ));
+}
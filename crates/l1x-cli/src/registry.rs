@@ -0,0 +1,380 @@
+use crate::cli_error::L1XConfigError;
+use crate::contract_install::L1XVMType;
+use l1x_common::{
+    rpc,
+    toolkit_config::{
+        L1XVMContractAddressRemoveType, L1XVMRegistryEntry,
+        L1XVMRegistryImportMode, L1XVMRegistryManifest, ToolkitConfig,
+    },
+    types::AddressStr,
+};
+
+use anyhow::Result;
+use std::{fs, path::PathBuf};
+
+/// Inspect and edit the contract address registry
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "registry")]
+pub struct L1XRegistryCmd {
+    /// Network of the registry entries, instead of L1X_CFG_CHAIN_TYPE or its
+    /// default_network
+    #[clap(long = "network", global = true)]
+    network: Option<String>,
+
+    #[clap(subcommand)]
+    command: L1XRegistrySubCmd,
+}
+
+#[derive(Clone, Debug, clap::Subcommand)]
+enum L1XRegistrySubCmd {
+    /// Print the registered deployments and instances
+    #[command(name = "show")]
+    Show {
+        #[clap(long = "artifact-id")]
+        artifact_id: Option<String>,
+
+        /// Print the entries as a JSON array
+        #[clap(long = "json", default_value_t = false)]
+        json: bool,
+    },
+    /// Delete a stale deployment, or only one of its instances
+    #[command(name = "remove")]
+    Remove {
+        #[clap(long = "artifact-id")]
+        artifact_id: String,
+
+        #[clap(long = "contract-id")]
+        contract_id: Option<String>,
+
+        /// Registry section to remove from, both when unset
+        #[clap(long = "vm-type")]
+        vm_type: Option<L1XVMType>,
+    },
+    /// Register a contract deployed outside the toolkit
+    #[command(name = "set")]
+    Set {
+        #[clap(long = "artifact-id")]
+        artifact_id: String,
+
+        /// Register an instance of the eBPF or WASM deployment `artifact-id`
+        #[clap(long = "contract-id")]
+        contract_id: Option<String>,
+
+        /// Hex contract address, with or without `0x`
+        #[clap(long = "address")]
+        address: String,
+
+        #[clap(long = "vm-type")]
+        vm_type: L1XVMType,
+    },
+    /// Write the registry as a JSON manifest to share with other workspaces
+    #[command(name = "export")]
+    Export {
+        /// Manifest file, stdout when unset
+        #[clap(long = "out")]
+        out: Option<PathBuf>,
+    },
+    /// Load a JSON manifest written by `registry export`
+    #[command(name = "import")]
+    Import {
+        manifest_file: PathBuf,
+
+        /// Add the entries of the manifest, failing on the entries registered
+        /// at another address. The default.
+        #[clap(long = "merge", default_value_t = false)]
+        merge: bool,
+
+        /// Make the manifest the registry of the network
+        #[clap(
+            long = "replace",
+            default_value_t = false,
+            conflicts_with = "merge"
+        )]
+        replace: bool,
+    },
+    /// Remove the contracts the node no longer knows, e.g. after a devnet
+    /// reset
+    #[command(name = "prune")]
+    Prune {
+        /// List the dead contracts without removing them
+        #[clap(long = "dry-run", default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+/// Outcome of [`prune_registry`]
+#[derive(Debug, Default, PartialEq)]
+pub struct L1XRegistryPruneOutcome {
+    /// Entries whose address the node doesn't know, removed unless dry run
+    pub removed: Vec<L1XVMRegistryEntry>,
+    /// Entries the node couldn't be asked about, with the reason, kept
+    pub skipped: Vec<(L1XVMRegistryEntry, String)>,
+}
+
+/// Ask the node of the active network of `toolkit` about every registry
+/// entry and remove those it doesn't know. Entries whose check fails are
+/// kept.
+pub async fn prune_registry(
+    toolkit: &ToolkitConfig,
+    dry_run: bool,
+) -> Result<L1XRegistryPruneOutcome, L1XConfigError> {
+    let end_point = toolkit.active_chain_json_rpc_endpoint()?;
+
+    let mut dead = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in registry_entries(toolkit, None)? {
+        match rpc::account_exists(
+            &end_point,
+            &entry.address.to_hex(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        )
+        .await
+        {
+            Ok(true) => {}
+            Ok(false) => dead.push(entry),
+            Err(err_code) => {
+                tracing::warn!("Skipping {} :: {}", entry, err_code);
+                skipped.push((entry, err_code.to_string()));
+            }
+        }
+    }
+
+    let removed = toolkit.prune_contract_address_registry(&dead, dry_run)?;
+
+    Ok(L1XRegistryPruneOutcome { removed, skipped })
+}
+
+/// Registry entries of `toolkit`, only those of `artifact_id` when given
+pub fn registry_entries(
+    toolkit: &ToolkitConfig,
+    artifact_id: Option<&str>,
+) -> Result<Vec<L1XVMRegistryEntry>, L1XConfigError> {
+    let mut entries = toolkit.contract_address_registry_entries()?;
+    if let Some(artifact_id) = artifact_id {
+        entries.retain(|entry| entry.artifact_id == artifact_id);
+    }
+
+    Ok(entries)
+}
+
+/// JSON form of `manifest`, the same bytes for the same registry
+pub fn manifest_json(manifest: &L1XVMRegistryManifest) -> Result<String> {
+    Ok(serde_json::to_string_pretty(manifest)? + "\n")
+}
+
+impl L1XRegistryCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let toolkit = ToolkitConfig::from_env()
+            .map_err(L1XConfigError::from)?
+            .with_network(self.network.as_deref());
+
+        match &self.command {
+            L1XRegistrySubCmd::Show { artifact_id, json } => {
+                let entries =
+                    registry_entries(&toolkit, artifact_id.as_deref())?;
+
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&entries)?);
+                } else {
+                    for entry in &entries {
+                        println!("{}", entry);
+                    }
+                }
+            }
+            L1XRegistrySubCmd::Remove { artifact_id, contract_id, vm_type } => {
+                let remove_types = match (vm_type, contract_id) {
+                    (Some(L1XVMType::L1xVmEvm), Some(_)) => anyhow::bail!(
+                        "EVM contracts have no instances, --contract-id is not supported for --vm-type evm"
+                    ),
+                    (Some(L1XVMType::L1xVmEbpf), _) => {
+                        vec![L1XVMContractAddressRemoveType::L1XEBPF_REMOVE {
+                            artifact_id: artifact_id.clone(),
+                            contract_id: contract_id.clone(),
+                        }]
+                    }
+                    (Some(L1XVMType::L1xVmWasm), _) => {
+                        vec![L1XVMContractAddressRemoveType::L1XWASM_REMOVE {
+                            artifact_id: artifact_id.clone(),
+                            contract_id: contract_id.clone(),
+                        }]
+                    }
+                    (None, Some(_)) => vec![
+                        L1XVMContractAddressRemoveType::L1XEBPF_REMOVE {
+                            artifact_id: artifact_id.clone(),
+                            contract_id: contract_id.clone(),
+                        },
+                        L1XVMContractAddressRemoveType::L1XWASM_REMOVE {
+                            artifact_id: artifact_id.clone(),
+                            contract_id: contract_id.clone(),
+                        },
+                    ],
+                    (Some(L1XVMType::L1xVmEvm), None) => {
+                        vec![L1XVMContractAddressRemoveType::L1XEVM_REMOVE {
+                            artifact_id: artifact_id.clone(),
+                        }]
+                    }
+                    (None, None) => vec![
+                        L1XVMContractAddressRemoveType::L1XEBPF_REMOVE {
+                            artifact_id: artifact_id.clone(),
+                            contract_id: None,
+                        },
+                        L1XVMContractAddressRemoveType::L1XEVM_REMOVE {
+                            artifact_id: artifact_id.clone(),
+                        },
+                        L1XVMContractAddressRemoveType::L1XWASM_REMOVE {
+                            artifact_id: artifact_id.clone(),
+                            contract_id: None,
+                        },
+                    ],
+                };
+
+                let mut removed = Vec::new();
+                for remove_type in remove_types {
+                    removed.extend(
+                        toolkit
+                            .remove_contract_address_registry_entry(
+                                remove_type,
+                                false,
+                            )
+                            .map_err(L1XConfigError::from)?,
+                    );
+                }
+
+                if removed.is_empty() {
+                    anyhow::bail!(
+                        "Nothing registered for artifact '{}'{}",
+                        artifact_id,
+                        contract_id
+                            .as_ref()
+                            .map(|cid| format!(" / contract '{}'", cid))
+                            .unwrap_or_default()
+                    );
+                }
+                removed.iter().for_each(|(entry, address)| {
+                    println!("Removed {} :: {}", entry, address);
+                });
+            }
+            L1XRegistrySubCmd::Set {
+                artifact_id,
+                contract_id,
+                address,
+                vm_type,
+            } => {
+                if matches!(vm_type, L1XVMType::L1xVmEvm)
+                    && contract_id.is_some()
+                {
+                    anyhow::bail!(
+                        "EVM contracts have no instances, --contract-id is not supported for --vm-type evm"
+                    );
+                }
+                let address = address
+                    .parse::<AddressStr>()
+                    .map_err(|err_code| L1XConfigError(err_code.to_string()))?;
+
+                toolkit
+                    .set_contract_address(
+                        (*vm_type).into(),
+                        artifact_id,
+                        contract_id.as_deref(),
+                        address,
+                    )
+                    .map_err(L1XConfigError::from)?;
+
+                println!(
+                    "Registered {}{} :: 0x{}",
+                    artifact_id,
+                    contract_id
+                        .as_ref()
+                        .map(|cid| format!("/{}", cid))
+                        .unwrap_or_default(),
+                    address
+                );
+            }
+            L1XRegistrySubCmd::Export { out } => {
+                let manifest = toolkit
+                    .export_contract_address_registry()
+                    .map_err(L1XConfigError::from)?;
+                let manifest_json = manifest_json(&manifest)?;
+
+                match out {
+                    Some(out) => {
+                        fs::write(out, manifest_json).map_err(|err_code| {
+                            anyhow::anyhow!(
+                                "Unable to write {} :: {}",
+                                out.display(),
+                                err_code
+                            )
+                        })?;
+                        println!(
+                            "Exported {} eBPF, {} EVM and {} WASM deployments to {}",
+                            manifest.ebpf.len(),
+                            manifest.evm.len(),
+                            manifest.wasm.len(),
+                            out.display()
+                        );
+                    }
+                    None => print!("{}", manifest_json),
+                }
+            }
+            L1XRegistrySubCmd::Import { manifest_file, replace, .. } => {
+                let manifest_json =
+                    fs::read_to_string(manifest_file).map_err(|err_code| {
+                        anyhow::anyhow!(
+                            "Unable to read {} :: {}",
+                            manifest_file.display(),
+                            err_code
+                        )
+                    })?;
+                let manifest: L1XVMRegistryManifest = serde_json::from_str(
+                    &manifest_json,
+                )
+                .map_err(|err_code| {
+                    anyhow::anyhow!(
+                        "Invalid registry manifest {} :: {}",
+                        manifest_file.display(),
+                        err_code
+                    )
+                })?;
+                let mode = if *replace {
+                    L1XVMRegistryImportMode::Replace
+                } else {
+                    L1XVMRegistryImportMode::Merge
+                };
+
+                toolkit
+                    .import_contract_address_registry(&manifest, mode)
+                    .map_err(L1XConfigError::from)?;
+
+                println!(
+                    "Imported {} eBPF, {} EVM and {} WASM deployments from {}",
+                    manifest.ebpf.len(),
+                    manifest.evm.len(),
+                    manifest.wasm.len(),
+                    manifest_file.display()
+                );
+            }
+            L1XRegistrySubCmd::Prune { dry_run } => {
+                let outcome = prune_registry(&toolkit, *dry_run).await?;
+
+                for entry in &outcome.removed {
+                    if *dry_run {
+                        println!("Dead {}", entry);
+                    } else {
+                        println!("Removed {}", entry);
+                    }
+                }
+                for (entry, reason) in &outcome.skipped {
+                    println!("Skipped {} :: {}", entry, reason);
+                }
+                println!(
+                    "{} {} dead contracts, skipped {}",
+                    if *dry_run { "Found" } else { "Removed" },
+                    outcome.removed.len(),
+                    outcome.skipped.len()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
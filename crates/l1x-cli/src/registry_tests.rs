@@ -0,0 +1,485 @@
+use crate::contract_install_test::{
+    toolkit_for, with_temp_registry, write_chain_config, write_toolkit_config,
+};
+use crate::registry::*;
+use l1x_common::toolkit_config::{
+    L1XVMContractAddressUpdateType, L1XVMRegistryEntry,
+};
+use l1x_common::types::AddressStr;
+
+use clap::Parser;
+use serde_json::{json, Value};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    registry: L1XRegistryCmd,
+}
+
+fn exec(args: &[&str]) -> anyhow::Result<()> {
+    let cmd = TestCli::parse_from(["registry"].iter().chain(args)).registry;
+    tokio::runtime::Runtime::new().unwrap().block_on(cmd.exec())
+}
+
+fn entry(
+    vm_type: &str,
+    artifact_id: &str,
+    contract_id: Option<&str>,
+    address: String,
+    hash: &str,
+) -> L1XVMRegistryEntry {
+    L1XVMRegistryEntry {
+        vm_type: vm_type.to_string(),
+        artifact_id: artifact_id.to_string(),
+        contract_id: contract_id.map(str::to_string),
+        address: address.parse().unwrap(),
+        hash: hash.to_string(),
+        metadata: Default::default(),
+    }
+}
+
+/// Display form of the address `byte` repeated
+fn checksummed(byte: &str) -> String {
+    byte.repeat(40).parse::<AddressStr>().unwrap().to_string()
+}
+
+#[test]
+fn test_show_normalizes_written_addresses() {
+    // Arrange
+    let entries = with_temp_registry(None, |ws_home| {
+        let toolkit = toolkit_for(ws_home).with_chain_type("local");
+        toolkit
+            .update_contract_address_registry(
+                L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
+                    artifact_id: "ft".to_string(),
+                    response_hash: "d1".to_string(),
+                    response_address: "a".repeat(40).parse().unwrap(),
+                },
+            )
+            .unwrap();
+        toolkit
+            .update_contract_address_registry(
+                L1XVMContractAddressUpdateType::L1XEBPF_INIT {
+                    artifact_id: "ft".to_string(),
+                    contract_id: "ft_main".to_string(),
+                    response_hash: "i1".to_string(),
+                    response_address: "b".repeat(40).parse().unwrap(),
+                },
+            )
+            .unwrap();
+        exec(&["show", "--json"]).unwrap();
+
+        // Act
+        (
+            registry_entries(&toolkit, None).unwrap(),
+            registry_entries(&toolkit, Some("nft")).unwrap(),
+        )
+    });
+
+    // Assert
+    assert_eq!(
+        entries.0,
+        [
+            entry("ebpf", "ft", None, "a".repeat(40), "d1"),
+            entry("ebpf", "ft", Some("ft_main"), "b".repeat(40), "i1"),
+        ]
+    );
+    assert_eq!(
+        entries.0[1].to_string(),
+        format!("ebpf ft/ft_main :: {} :: i1", checksummed("b"))
+    );
+    assert!(entries.1.is_empty());
+}
+
+#[test]
+fn test_set_registers_external_contracts() {
+    let entries = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        let address = format!("0x{}", "C".repeat(40));
+
+        exec(&[
+            "set",
+            "--artifact-id",
+            "erc20",
+            "--address",
+            &address,
+            "--vm-type",
+            "evm",
+        ])
+        .unwrap();
+        exec(&[
+            "set",
+            "--artifact-id",
+            "ft",
+            "--address",
+            &"d".repeat(40),
+            "--vm-type",
+            "ebpf",
+        ])
+        .unwrap();
+        exec(&[
+            "set",
+            "--artifact-id",
+            "ft",
+            "--contract-id",
+            "ft_main",
+            "--address",
+            &"e".repeat(40),
+            "--vm-type",
+            "ebpf",
+        ])
+        .unwrap();
+
+        registry_entries(&toolkit_for(ws_home).with_chain_type("local"), None)
+            .unwrap()
+    });
+
+    assert_eq!(
+        entries,
+        [
+            entry("ebpf", "ft", None, "d".repeat(40), ""),
+            entry("ebpf", "ft", Some("ft_main"), "e".repeat(40), ""),
+            entry("evm", "erc20", None, "c".repeat(40), ""),
+        ]
+    );
+    assert_eq!(
+        entries[2].to_string(),
+        format!("evm erc20 :: {}", checksummed("c"))
+    );
+}
+
+#[test]
+fn test_set_rejects_bad_input() {
+    let (bad_address, evm_instance) = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        (
+            exec(&[
+                "set",
+                "--artifact-id",
+                "erc20",
+                "--address",
+                "0x1234",
+                "--vm-type",
+                "evm",
+            ])
+            .unwrap_err(),
+            exec(&[
+                "set",
+                "--artifact-id",
+                "erc20",
+                "--contract-id",
+                "main",
+                "--address",
+                &"c".repeat(40),
+                "--vm-type",
+                "evm",
+            ])
+            .unwrap_err(),
+        )
+    });
+
+    assert!(bad_address.to_string().starts_with("Invalid address '0x1234'"));
+    assert!(evm_instance
+        .to_string()
+        .contains("EVM contracts have no instances"));
+}
+
+#[test]
+fn test_remove_entries() {
+    // Arrange
+    let (after_instance, after_artifact, missing) =
+        with_temp_registry(None, |ws_home| {
+            write_toolkit_config(ws_home);
+            let toolkit = toolkit_for(ws_home).with_chain_type("local");
+            for (artifact_id, contract_id, byte) in
+                [("ft", None, "a"), ("ft", Some("ft_main"), "b")]
+            {
+                exec(
+                    &[
+                        &[
+                            "set",
+                            "--artifact-id",
+                            artifact_id,
+                            "--vm-type",
+                            "ebpf",
+                        ][..],
+                        &["--address", &byte.repeat(40)],
+                        &contract_id
+                            .map(|cid| vec!["--contract-id", cid])
+                            .unwrap_or_default(),
+                    ]
+                    .concat(),
+                )
+                .unwrap();
+            }
+            exec(&[
+                "set",
+                "--artifact-id",
+                "ft",
+                "--address",
+                &"c".repeat(40),
+                "--vm-type",
+                "evm",
+            ])
+            .unwrap();
+
+            // Act
+            exec(&[
+                "remove",
+                "--artifact-id",
+                "ft",
+                "--contract-id",
+                "ft_main",
+            ])
+            .unwrap();
+            let after_instance = registry_entries(&toolkit, None).unwrap();
+            exec(&["remove", "--artifact-id", "ft"]).unwrap();
+            let after_artifact = registry_entries(&toolkit, None).unwrap();
+
+            (
+                after_instance,
+                after_artifact,
+                exec(&["remove", "--artifact-id", "ft"]).unwrap_err(),
+            )
+        });
+
+    // Assert
+    assert_eq!(
+        after_instance,
+        [
+            entry("ebpf", "ft", None, "a".repeat(40), ""),
+            entry("evm", "ft", None, "c".repeat(40), ""),
+        ]
+    );
+    assert!(after_artifact.is_empty());
+    assert_eq!(missing.to_string(), "Nothing registered for artifact 'ft'");
+}
+
+/// Workspace registry with an eBPF deployment, its `ft_main` instance at
+/// `instance_byte` repeated and an EVM deployment
+fn seed_registry(ws_home: &std::path::Path, instance_byte: &str) {
+    write_toolkit_config(ws_home);
+    let set = |artifact_id: &str, extra: &[&str], byte: &str, vm_type: &str| {
+        let address = byte.repeat(40);
+        let args = ["set", "--artifact-id", artifact_id, "--address", &address];
+        exec(&[&args[..], extra, &["--vm-type", vm_type]].concat()).unwrap();
+    };
+
+    set("ft", &[], "a", "ebpf");
+    set("ft", &["--contract-id", "ft_main"], instance_byte, "ebpf");
+    set("erc20", &[], "c", "evm");
+}
+
+fn exported_manifest(ws_home: &std::path::Path) -> String {
+    let out = ws_home.join("deployments.json");
+    exec(&["export", "--out", out.to_str().unwrap()]).unwrap();
+    std::fs::read_to_string(out).unwrap()
+}
+
+#[test]
+fn test_export_import_round_trip_is_byte_identical() {
+    // Arrange
+    let exported = with_temp_registry(None, |ws_home| {
+        seed_registry(ws_home, "b");
+        exported_manifest(ws_home)
+    });
+
+    // Act
+    let (reexported, entries) = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        let manifest_file = ws_home.join("shared.json");
+        std::fs::write(&manifest_file, &exported).unwrap();
+        exec(&["import", manifest_file.to_str().unwrap()]).unwrap();
+
+        (
+            exported_manifest(ws_home),
+            registry_entries(
+                &toolkit_for(ws_home).with_chain_type("local"),
+                None,
+            )
+            .unwrap(),
+        )
+    });
+
+    // Assert
+    assert_eq!(reexported, exported);
+    assert_eq!(
+        entries,
+        [
+            entry("ebpf", "ft", None, "a".repeat(40), ""),
+            entry("ebpf", "ft", Some("ft_main"), "b".repeat(40), ""),
+            entry("evm", "erc20", None, "c".repeat(40), ""),
+        ]
+    );
+    let manifest: serde_json::Value = serde_json::from_str(&exported).unwrap();
+    assert_eq!(manifest["version"], 1);
+    assert_eq!(
+        manifest["ebpf"][0]["instances"][0]["address"],
+        format!("0x{}", "b".repeat(40))
+    );
+}
+
+#[test]
+fn test_merge_import_reports_conflicts_and_replace_overwrites() {
+    // Arrange
+    let exported = with_temp_registry(None, |ws_home| {
+        seed_registry(ws_home, "d");
+        exported_manifest(ws_home)
+    });
+
+    let (conflict_err, after_conflict, after_replace) =
+        with_temp_registry(None, |ws_home| {
+            seed_registry(ws_home, "b");
+            let toolkit = toolkit_for(ws_home).with_chain_type("local");
+            let manifest_file = ws_home.join("shared.json");
+            std::fs::write(&manifest_file, &exported).unwrap();
+            let manifest_file = manifest_file.to_str().unwrap();
+
+            // Act
+            let conflict_err =
+                exec(&["import", manifest_file, "--merge"]).unwrap_err();
+            let after_conflict = registry_entries(&toolkit, None).unwrap();
+            exec(&["import", manifest_file, "--replace"]).unwrap();
+
+            (
+                conflict_err,
+                after_conflict,
+                registry_entries(&toolkit, None).unwrap(),
+            )
+        });
+
+    // Assert
+    assert_eq!(
+        conflict_err.to_string(),
+        format!(
+            "The manifest conflicts with the registry of network 'local':\n\
+             - l1x_vm.ft.instance.ft_main: 0x{}\n\
+             + l1x_vm.ft.instance.ft_main: 0x{}\n\
+             hint: pass --replace to overwrite the registry of the network with the manifest, or remove the conflicting entries with `l1x-forge registry remove`",
+            "b".repeat(40),
+            "d".repeat(40)
+        )
+    );
+    assert_eq!(after_conflict[1].address.to_hex(), "b".repeat(40));
+    assert_eq!(after_replace[1].address.to_hex(), "d".repeat(40));
+}
+
+#[test]
+fn test_import_rejects_invalid_manifests() {
+    let manifest = |instances: &str, address: &str| {
+        format!(
+            r#"{{"version":1,"ebpf":[{{"artifact_id":"ft","address":"{}","hash":"","instances":[{}]}}],"evm":[]}}"#,
+            address, instances
+        )
+    };
+    let instance = format!(
+        r#"{{"contract_id":"ft_main","address":"0x{}","hash":""}}"#,
+        "b".repeat(40)
+    );
+
+    let errors = with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        let manifest_file = ws_home.join("shared.json");
+        [
+            manifest("", "0x1234"),
+            manifest(&format!("{},{}", instance, instance), &"a".repeat(40)),
+        ]
+        .iter()
+        .map(|manifest_json| {
+            std::fs::write(&manifest_file, manifest_json).unwrap();
+            exec(&["import", manifest_file.to_str().unwrap()])
+                .unwrap_err()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+    });
+
+    assert_eq!(
+        errors,
+        [
+            "Invalid registry manifest :: Invalid address '0x1234': expected 40 hex chars, got 4 of ebpf artifact 'ft'",
+            "Invalid registry manifest :: duplicate instance 'ft_main' of ebpf artifact 'ft'",
+        ]
+    );
+}
+
+/// Answer the `l1x_getAccountState` of the address `byte` repeated with
+/// `response`
+async fn mock_account_state(server: &MockServer, byte: &str, response: Value) {
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_getAccountState",
+            "params": { "request": { "address": byte.repeat(40) } },
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .mount(server)
+        .await;
+}
+
+#[test]
+fn test_prune_removes_only_dead_contracts() {
+    // Arrange
+    let dead_state = json!({ "jsonrpc": "2.0", "id": 1, "result": {} });
+    let live_state = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {
+            "account_state": { "balance": "0", "nonce": "0", "account_type": 0 },
+        },
+    });
+    let rpc_failure = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "error": { "code": -32000, "message": "node busy" },
+    });
+
+    let (dry_run, kept, pruned, entries) =
+        with_temp_registry(None, |ws_home| {
+            seed_registry(ws_home, "b");
+            let nft = "d".repeat(40);
+            let args = ["set", "--artifact-id", "nft", "--address", &nft];
+            exec(&[&args[..], &["--vm-type", "ebpf"]].concat()).unwrap();
+            let toolkit = toolkit_for(ws_home).with_chain_type("local");
+
+            tokio::runtime::Runtime::new().unwrap().block_on(async {
+                let server = MockServer::start().await;
+                write_chain_config(ws_home, 1, &server.uri());
+                mock_account_state(&server, "a", dead_state.clone()).await;
+                mock_account_state(&server, "b", dead_state).await;
+                mock_account_state(&server, "c", rpc_failure).await;
+                mock_account_state(&server, "d", live_state).await;
+
+                // Act
+                let dry_run = prune_registry(&toolkit, true).await.unwrap();
+                let kept = registry_entries(&toolkit, None).unwrap().len();
+                let pruned = prune_registry(&toolkit, false).await.unwrap();
+
+                (
+                    dry_run,
+                    kept,
+                    pruned,
+                    registry_entries(&toolkit, None).unwrap(),
+                )
+            })
+        });
+
+    // Assert
+    let dead = [
+        entry("ebpf", "ft", Some("ft_main"), "b".repeat(40), ""),
+        entry("ebpf", "ft", None, "a".repeat(40), ""),
+    ];
+    assert_eq!(dry_run.removed, dead);
+    assert_eq!(kept, 4);
+    assert_eq!(pruned.removed, dead);
+    assert_eq!(pruned.skipped.len(), 1);
+    assert_eq!(pruned.skipped[0].0.artifact_id, "erc20");
+    assert_eq!(
+        entries,
+        [
+            entry("ebpf", "nft", None, "d".repeat(40), ""),
+            entry("evm", "erc20", None, "c".repeat(40), ""),
+        ]
+    );
+}
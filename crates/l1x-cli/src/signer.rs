@@ -0,0 +1,68 @@
+use crate::cli_error::L1XConfigError;
+use l1x_common::{toolkit_config::ToolkitConfig, types::AddressStr};
+
+use secp256k1::SecretKey;
+use std::str::FromStr;
+
+/// A `--private-key` value, redacted from `Debug` output
+#[derive(Clone, PartialEq, Eq)]
+pub struct L1XPrivateKeyArg(String);
+
+impl FromStr for L1XPrivateKeyArg {
+    type Err = std::convert::Infallible;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(L1XPrivateKeyArg(input.to_string()))
+    }
+}
+
+impl std::fmt::Debug for L1XPrivateKeyArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+/// Hex private key of [`ToolkitConfig::resolve_signer`], for a
+/// `ForgeClientConfig`
+pub(crate) fn signer_private_key(
+    toolkit: &ToolkitConfig,
+    owner: Option<&str>,
+    private_key: Option<&L1XPrivateKeyArg>,
+) -> Result<String, L1XConfigError> {
+    let secret_key = signer_secret_key(toolkit, owner, private_key)?;
+    let secret_bytes = secret_key.secret_bytes();
+
+    // Only where the key came from and its length, never the key itself
+    tracing::debug!(
+        "Signer :: owner {} | {} byte key",
+        owner.unwrap_or("<none>"),
+        secret_bytes.len()
+    );
+    Ok(hex::encode(secret_bytes))
+}
+
+/// Secret key of [`ToolkitConfig::resolve_signer`]
+pub(crate) fn signer_secret_key(
+    toolkit: &ToolkitConfig,
+    owner: Option<&str>,
+    private_key: Option<&L1XPrivateKeyArg>,
+) -> Result<SecretKey, L1XConfigError> {
+    Ok(toolkit.resolve_signer(
+        owner,
+        private_key.map(|private_key| private_key.0.as_str()),
+    )?)
+}
+
+/// Account address of the signer of [`signer_secret_key`]
+pub(crate) fn signer_address(
+    toolkit: &ToolkitConfig,
+    owner: Option<&str>,
+    private_key: Option<&L1XPrivateKeyArg>,
+) -> Result<AddressStr, L1XConfigError> {
+    let secret_key = signer_secret_key(toolkit, owner, private_key)?;
+    let verifying_key = secret_key.public_key(&secp256k1::Secp256k1::new());
+
+    Ok(l1x_common::account_address(&verifying_key.serialize())
+        .expect("a secp256k1 public key maps to an account address")
+        .into())
+}
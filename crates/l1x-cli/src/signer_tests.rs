@@ -0,0 +1,112 @@
+use crate::contract_install_test::{with_temp_registry, write_toolkit_config};
+use crate::signer::*;
+use l1x_common::toolkit_config::{ToolkitConfig, PRIVATE_KEY_ENV};
+
+use std::env;
+
+// `write_toolkit_config` wallets all use the key 1
+const WALLET_KEY: &str =
+    "0000000000000000000000000000000000000000000000000000000000000001";
+const ENV_KEY: &str =
+    "0000000000000000000000000000000000000000000000000000000000000002";
+const FLAG_KEY: &str =
+    "0x0000000000000000000000000000000000000000000000000000000000000003";
+
+fn resolved_key(owner: Option<&str>, key_flag: Option<&str>) -> String {
+    hex::encode(
+        ToolkitConfig::from_env()
+            .unwrap()
+            .resolve_signer(owner, key_flag)
+            .unwrap()
+            .secret_bytes(),
+    )
+}
+
+/// Run `test` with the wallet config written and `L1X_PRIVATE_KEY` set to
+/// `env_key`
+fn with_signer_env(env_key: Option<&str>, test: impl FnOnce()) {
+    with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        match env_key {
+            Some(env_key) => env::set_var(PRIVATE_KEY_ENV, env_key),
+            None => env::remove_var(PRIVATE_KEY_ENV),
+        }
+
+        test();
+
+        env::remove_var(PRIVATE_KEY_ENV);
+    });
+}
+
+#[test]
+fn test_resolve_signer_flag_wins_over_env_and_wallet() {
+    with_signer_env(Some(ENV_KEY), || {
+        assert_eq!(
+            resolved_key(Some("ava"), Some(FLAG_KEY)),
+            FLAG_KEY.trim_start_matches("0x")
+        );
+        assert_eq!(
+            resolved_key(None, Some(FLAG_KEY)),
+            FLAG_KEY.trim_start_matches("0x")
+        );
+    });
+}
+
+#[test]
+fn test_resolve_signer_env_wins_over_wallet() {
+    with_signer_env(Some(ENV_KEY), || {
+        assert_eq!(resolved_key(Some("ava"), None), ENV_KEY);
+        assert_eq!(resolved_key(None, None), ENV_KEY);
+    });
+}
+
+#[test]
+fn test_resolve_signer_falls_back_to_wallet() {
+    with_signer_env(None, || {
+        assert_eq!(resolved_key(Some("ava"), None), WALLET_KEY);
+    });
+}
+
+#[test]
+fn test_resolve_signer_without_any_key() {
+    with_signer_env(None, || {
+        let err = ToolkitConfig::from_env()
+            .unwrap()
+            .resolve_signer(None, None)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("--private-key"), "{}", err);
+        assert!(err.contains(PRIVATE_KEY_ENV), "{}", err);
+    });
+}
+
+#[test]
+fn test_resolve_signer_errors_never_echo_the_key() {
+    with_signer_env(Some("deadbeefzz"), || {
+        let bad_flag = "0xabad1dea";
+
+        let flag_err = ToolkitConfig::from_env()
+            .unwrap()
+            .resolve_signer(None, Some(bad_flag))
+            .unwrap_err()
+            .to_string();
+        let env_err = ToolkitConfig::from_env()
+            .unwrap()
+            .resolve_signer(None, None)
+            .unwrap_err()
+            .to_string();
+
+        assert!(flag_err.contains("--private-key"), "{}", flag_err);
+        assert!(!flag_err.contains("abad1dea"), "{}", flag_err);
+        assert!(env_err.contains(PRIVATE_KEY_ENV), "{}", env_err);
+        assert!(!env_err.contains("deadbeef"), "{}", env_err);
+    });
+}
+
+#[test]
+fn test_private_key_arg_debug_is_redacted() {
+    let private_key: L1XPrivateKeyArg = FLAG_KEY.parse().unwrap();
+
+    assert_eq!(format!("{:?}", private_key), "<redacted>");
+}
@@ -0,0 +1,285 @@
+use crate::cli_error::L1XConfigError;
+use crate::confirm::{self, L1XSubmitSummary, L1XTerminalPrompt};
+use crate::forge_client::{ForgeClient, ForgeClientConfig, ForgeClientError};
+use crate::signer::{signer_private_key, L1XPrivateKeyArg};
+use l1x_common::{
+    toolkit_config::ToolkitConfig, txn_builder::TransactionBuilder,
+    types::AddressStr,
+};
+
+use anyhow::Result;
+use serde_json::json;
+use std::time::Duration;
+
+/// Signer, network and fee of a staking transaction
+#[derive(Clone, Debug, clap::Args)]
+pub struct L1XStakingTxnArgs {
+    /// Wallet signing the transaction, unless --private-key or
+    /// L1X_PRIVATE_KEY is given
+    #[clap(long = "owner")]
+    owner: Option<String>,
+
+    /// Hex private key signing the transaction instead of the wallet
+    #[clap(long = "private-key")]
+    private_key: Option<L1XPrivateKeyArg>,
+
+    #[clap(long = "fee-limit", default_value_t = 100)]
+    fee_limit: u128,
+
+    /// Network of l1x_chain_config.yaml to submit to, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+
+    /// Seconds to wait for the transaction to be included in a block
+    #[clap(long = "timeout", default_value_t = 60)]
+    timeout: u64,
+
+    /// Print the outcome as JSON
+    #[clap(long = "json", default_value_t = false)]
+    json: bool,
+
+    /// Submit to a network that is not marked is_dev without asking for
+    /// confirmation
+    #[clap(long = "yes", visible_alias = "confirm", default_value_t = false)]
+    yes: bool,
+}
+
+impl L1XStakingTxnArgs {
+    fn toolkit(&self) -> Result<ToolkitConfig, L1XConfigError> {
+        Ok(ToolkitConfig::from_env()?.with_network(self.network.as_deref()))
+    }
+
+    /// Confirm submitting with `client` to a network that is not marked
+    /// is_dev on the terminal, unless --yes
+    fn confirm_network(
+        &self,
+        toolkit: &ToolkitConfig,
+        client: &ForgeClient,
+    ) -> Result<()> {
+        confirm::confirm_network(
+            toolkit,
+            self.yes,
+            || {
+                L1XSubmitSummary::of_toolkit(
+                    toolkit,
+                    client.account_address(),
+                    self.fee_limit,
+                    None,
+                )
+            },
+            &mut L1XTerminalPrompt,
+        )
+    }
+
+    fn client(
+        &self,
+        toolkit: &ToolkitConfig,
+    ) -> Result<ForgeClient, L1XConfigError> {
+        let end_point = toolkit.active_chain_json_rpc_endpoint()?;
+        let private_key = signer_private_key(
+            toolkit,
+            self.owner.as_deref(),
+            self.private_key.as_ref(),
+        )?;
+
+        ForgeClient::new(ForgeClientConfig {
+            fee_limit: self.fee_limit,
+            ..ForgeClientConfig::new(&end_point, &private_key)
+        })
+        .map_err(|err_code| {
+            L1XConfigError(format!("Invalid signing key: {}", err_code))
+        })
+    }
+
+    /// Submit the transaction of `builder` with the next nonce and print its
+    /// hash
+    async fn exec(&self, builder: TransactionBuilder) -> Result<()> {
+        let toolkit = self.toolkit()?;
+        let client = self.client(&toolkit)?;
+        self.confirm_network(&toolkit, &client)?;
+        let nonce = client.next_nonce().await?;
+
+        let hash = submit_staking_txn(
+            &client,
+            builder,
+            nonce,
+            Duration::from_secs(self.timeout),
+        )
+        .await?;
+
+        match self.json {
+            true => println!("{}", json!({ "hash": hash })),
+            false => println!("{}", hash),
+        }
+
+        Ok(())
+    }
+}
+
+/// Address of the staking pool `owner` creates on `cluster` with `nonce`
+pub fn predict_pool_address(
+    owner: &AddressStr,
+    cluster: &AddressStr,
+    nonce: u128,
+) -> AddressStr {
+    l1x_common::pool_address(owner.as_bytes(), cluster.as_bytes(), nonce).into()
+}
+
+/// Sign `builder` with `nonce`, submit it and wait up to `timeout` for its
+/// inclusion. Returns the transaction hash.
+pub async fn submit_staking_txn(
+    client: &ForgeClient,
+    builder: TransactionBuilder,
+    nonce: u128,
+    timeout: Duration,
+) -> Result<String, ForgeClientError> {
+    let request = client.sign_transaction(builder.build()?, nonce)?;
+    let response = client.submit_signed(&request).await?;
+
+    tracing::info!("Txn {} | Waiting for inclusion ...", &response.hash);
+    let block = client.wait_for_finality(&response.hash, 0, timeout).await?;
+    tracing::info!("Txn {} | Included in block {}", &response.hash, block);
+
+    Ok(response.hash)
+}
+
+/// Stake native tokens in a staking pool
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "stake")]
+pub struct L1XStakeCmd {
+    /// Hex address of the staking pool, with or without `0x`
+    #[clap(long = "pool")]
+    pool: String,
+
+    #[clap(long = "amount")]
+    amount: u128,
+
+    #[clap(flatten)]
+    txn: L1XStakingTxnArgs,
+}
+
+/// Withdraw staked native tokens from a staking pool
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "unstake")]
+pub struct L1XUnstakeCmd {
+    /// Hex address of the staking pool, with or without `0x`
+    #[clap(long = "pool")]
+    pool: String,
+
+    #[clap(long = "amount")]
+    amount: u128,
+
+    #[clap(flatten)]
+    txn: L1XStakingTxnArgs,
+}
+
+/// Create a staking pool
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "create-pool")]
+pub struct L1XCreatePoolCmd {
+    /// Hex address of the contract instance the pool belongs to
+    #[clap(long = "contract")]
+    contract: Option<String>,
+
+    #[clap(long = "min-stake")]
+    min_stake: Option<u128>,
+
+    #[clap(long = "max-stake")]
+    max_stake: Option<u128>,
+
+    #[clap(long = "min-pool-balance")]
+    min_pool_balance: Option<u128>,
+
+    #[clap(long = "max-pool-balance")]
+    max_pool_balance: Option<u128>,
+
+    /// Blocks a stake stays locked for
+    #[clap(long = "staking-period")]
+    staking_period: Option<u128>,
+
+    #[clap(flatten)]
+    txn: L1XStakingTxnArgs,
+}
+
+impl L1XStakeCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let builder = TransactionBuilder::new()
+            .stake(&self.pool, self.amount)
+            .map_err(ForgeClientError::from)?;
+
+        self.txn.exec(builder).await
+    }
+}
+
+impl L1XUnstakeCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let builder = TransactionBuilder::new()
+            .unstake(&self.pool, self.amount)
+            .map_err(ForgeClientError::from)?;
+
+        self.txn.exec(builder).await
+    }
+}
+
+impl L1XCreatePoolCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let builder = TransactionBuilder::new()
+            .create_staking_pool(
+                self.contract.as_deref(),
+                self.min_stake,
+                self.max_stake,
+                self.min_pool_balance,
+                self.max_pool_balance,
+                self.staking_period,
+            )
+            .map_err(ForgeClientError::from)?;
+        let toolkit = self.txn.toolkit()?;
+        let client = self.txn.client(&toolkit)?;
+        self.txn.confirm_network(&toolkit, &client)?;
+        let nonce = client.next_nonce().await?;
+
+        let pool_address = toolkit
+            .active_cluster_address()
+            .map_err(L1XConfigError::from)?
+            .map(|cluster| {
+                predict_pool_address(&client.account_address(), &cluster, nonce)
+            });
+        match pool_address {
+            Some(pool_address) => {
+                tracing::info!(
+                    "Create Pool :: Expected pool :: {}",
+                    pool_address
+                );
+            }
+            None => {
+                tracing::warn!(
+                    "No cluster_address in l1x_chain_config.yaml, the pool address is not predicted"
+                );
+            }
+        }
+
+        let hash = submit_staking_txn(
+            &client,
+            builder,
+            nonce,
+            Duration::from_secs(self.txn.timeout),
+        )
+        .await?;
+
+        match self.txn.json {
+            true => println!(
+                "{}",
+                json!({ "hash": hash, "pool_address": pool_address })
+            ),
+            false => {
+                println!("hash: {}", hash);
+                if let Some(pool_address) = pool_address {
+                    println!("pool_address: {}", pool_address);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
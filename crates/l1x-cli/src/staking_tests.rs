@@ -0,0 +1,67 @@
+use crate::forge_client::{ForgeClient, ForgeClientConfig};
+use crate::forge_client_tests::{mock_rpc, rpc_result};
+use crate::staking::*;
+use l1x_common::{txn_builder::TransactionBuilder, types::AddressStr};
+
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer};
+
+const TEST_PRIVATE_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+
+// keccak256(owner || cluster || nonce as 16 big endian bytes)[12..]
+#[test]
+fn test_predict_pool_address_vector() {
+    let owner: AddressStr =
+        "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf".parse().unwrap();
+    let cluster: AddressStr =
+        "2b5ad5c4795c026514f8317c7a215e218dccd6cf".parse().unwrap();
+
+    let pool_address = predict_pool_address(&owner, &cluster, 5);
+
+    assert_eq!(
+        pool_address.to_hex(),
+        "c1c9b088c0f9f0ae2eab88a4115f8bd8b281fa16"
+    );
+}
+
+#[tokio::test]
+async fn test_submit_staking_txn_signs_with_given_nonce() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_submitTransaction",
+            "params": { "request": { "nonce": "3", "fee_limit": "100" } },
+        })))
+        .respond_with(rpc_result(
+            json!({ "hash": "5a4e", "contract_address": null }),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_rpc(
+        &server,
+        "l1x_getTransactionReceipt",
+        json!({ "block_number": 9 }),
+    )
+    .await;
+    mock_rpc(&server, "l1x_getChainState", json!({ "head_block_number": 9 }))
+        .await;
+    let client = ForgeClient::new(ForgeClientConfig {
+        poll_interval: Duration::from_millis(10),
+        ..ForgeClientConfig::new(&server.uri(), TEST_PRIVATE_KEY)
+    })
+    .unwrap();
+    let builder =
+        TransactionBuilder::new().stake(&"ab".repeat(20), 500).unwrap();
+
+    // Act
+    let hash =
+        submit_staking_txn(&client, builder, 3, Duration::from_secs(5)).await;
+
+    // Assert
+    assert_eq!(hash.unwrap(), "5a4e");
+}
@@ -0,0 +1,314 @@
+use crate::cli_error::L1XConfigError;
+use crate::confirm::{self, L1XSubmitSummary, L1XTerminalPrompt};
+use crate::forge_client::{ForgeClient, ForgeClientConfig, ForgeClientError};
+use crate::multisend::{self, L1XBatchError, L1XBatchOutcome};
+use l1x_common::{
+    rpc,
+    toolkit_config::{self, ToolkitConfig},
+    txn_builder::TransactionBuilder,
+    SigningScheme,
+};
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, thiserror::Error)]
+pub enum L1XTransferError {
+    #[error("Invalid recipient: {0}")]
+    InvalidRecipient(String),
+    #[error(
+        "Insufficient balance: wallet '{owner}' holds {balance}, the transfer needs {amount}"
+    )]
+    InsufficientBalance { owner: String, balance: u128, amount: u128 },
+    #[error("{0}")]
+    BatchError(#[from] L1XBatchError),
+    #[error("{0}")]
+    ClientError(#[from] ForgeClientError),
+    #[error("{0}")]
+    ConfigError(#[from] L1XConfigError),
+}
+
+/// Transfer native tokens from a dev wallet to an address
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "transfer")]
+pub struct L1XTransferCmd {
+    /// Wallet signing and paying for the transfer
+    #[clap(long = "from")]
+    from: String,
+
+    /// Hex account address of the recipient, with or without `0x`
+    #[clap(
+        long = "to",
+        required_unless_present = "batch",
+        conflicts_with = "batch"
+    )]
+    to: Option<String>,
+
+    #[clap(
+        long = "amount",
+        required_unless_present = "batch",
+        conflicts_with = "batch"
+    )]
+    amount: Option<u128>,
+
+    /// CSV file of `address,amount` lines, one transfer per recipient
+    #[clap(long = "batch", conflicts_with = "preflight")]
+    batch: Option<PathBuf>,
+
+    /// Skip the recipients of --batch its progress file records as sent
+    #[clap(long = "resume", default_value_t = false, requires = "batch")]
+    resume: bool,
+
+    /// Maximum number of --batch transfers submitted at once
+    #[clap(long = "max-in-flight", default_value_t = 8, requires = "batch")]
+    max_in_flight: usize,
+
+    #[clap(long = "fee-limit", default_value_t = 100)]
+    fee_limit: u128,
+
+    /// Network of l1x_chain_config.yaml to transfer on, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+
+    /// Check the balance of the sender before submitting the transfer
+    #[clap(long = "preflight", default_value_t = false)]
+    preflight: bool,
+
+    /// Seconds to wait for the transfer to be included in a block
+    #[clap(long = "timeout", default_value_t = 60)]
+    timeout: u64,
+
+    /// Print the transaction hash, or the --batch outcomes, as JSON
+    #[clap(long = "json", default_value_t = false)]
+    json: bool,
+
+    /// Sign the transfer over the legacy native transfer payload, for nodes
+    /// rejecting canonically signed transfers
+    #[clap(long = "legacy-native-signing", default_value_t = false)]
+    legacy_native_signing: bool,
+
+    /// Submit to a network that is not marked is_dev without asking for
+    /// confirmation
+    #[clap(long = "yes", visible_alias = "confirm", default_value_t = false)]
+    yes: bool,
+}
+
+/// Fail when the wallet `owner` holds less than `amount`
+pub fn check_balance(
+    owner: &str,
+    balance: u128,
+    amount: u128,
+) -> Result<(), L1XTransferError> {
+    match balance < amount {
+        true => Err(L1XTransferError::InsufficientBalance {
+            owner: owner.to_string(),
+            balance,
+            amount,
+        }),
+        false => Ok(()),
+    }
+}
+
+/// Submit the transfer of `amount` to `to` signed by the client's account and
+/// wait up to `timeout` for its inclusion. Returns the transaction hash.
+pub async fn transfer(
+    client: &ForgeClient,
+    to: &str,
+    amount: u128,
+    timeout: Duration,
+) -> Result<String, L1XTransferError> {
+    let recipient = toolkit_config::normalize_address(to)
+        .map_err(L1XTransferError::InvalidRecipient)?;
+
+    let txn = TransactionBuilder::new()
+        .native_transfer(&recipient, amount)
+        .and_then(TransactionBuilder::build)
+        .map_err(ForgeClientError::from)?;
+    let response = client.submit_transaction(txn).await?;
+
+    tracing::info!("Txn {} | Waiting for inclusion ...", &response.hash);
+    let block = client.wait_for_finality(&response.hash, 0, timeout).await?;
+    tracing::info!("Txn {} | Included in block {}", &response.hash, block);
+
+    Ok(response.hash)
+}
+
+/// Print the outcome of every recipient of a batch, as a table or as JSON
+fn print_batch_summary(
+    recipients: &[multisend::L1XBatchRecipient],
+    outcomes: &[L1XBatchOutcome],
+    json: bool,
+) {
+    match json {
+        true => {
+            let summary: Vec<_> = recipients
+                .iter()
+                .zip(outcomes)
+                .map(|(recipient, outcome)| {
+                    let (status, hash, error) = match outcome {
+                        L1XBatchOutcome::Sent { hash } => {
+                            ("sent", Some(hash), None)
+                        }
+                        L1XBatchOutcome::AlreadySent { hash } => {
+                            ("skipped", Some(hash), None)
+                        }
+                        L1XBatchOutcome::Failed(reason) => {
+                            ("failed", None, Some(reason))
+                        }
+                    };
+                    serde_json::json!({
+                        "address": format!("0x{}", recipient.address),
+                        "amount": recipient.amount.to_string(),
+                        "status": status,
+                        "hash": hash,
+                        "error": error,
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::Value::Array(summary));
+        }
+        false => {
+            for (recipient, outcome) in recipients.iter().zip(outcomes) {
+                println!(
+                    "0x{:<40}  {:>20}  {}",
+                    recipient.address, recipient.amount, outcome
+                );
+            }
+        }
+    }
+}
+
+impl L1XTransferCmd {
+    pub async fn exec(&self) -> Result<()> {
+        let toolkit = ToolkitConfig::from_env()
+            .map_err(L1XConfigError::from)?
+            .with_network(self.network.as_deref());
+        let end_point = toolkit
+            .active_chain_json_rpc_endpoint()
+            .map_err(L1XConfigError::from)?;
+        let private_key = toolkit
+            .wallet_priv_key(&self.from)
+            .map_err(L1XConfigError::from)?;
+
+        let client = ForgeClient::new(ForgeClientConfig {
+            fee_limit: self.fee_limit,
+            signing_scheme: match self.legacy_native_signing {
+                true => SigningScheme::LegacyNativeTransfer,
+                false => SigningScheme::Canonical,
+            },
+            ..ForgeClientConfig::new(&end_point, &private_key)
+        })
+        .map_err(|err_code| {
+            L1XConfigError(format!(
+                "Invalid private key for wallet '{}': {}",
+                &self.from, err_code
+            ))
+        })?;
+        confirm::confirm_network(
+            &toolkit,
+            self.yes,
+            || {
+                L1XSubmitSummary::of_toolkit(
+                    &toolkit,
+                    client.account_address(),
+                    self.fee_limit,
+                    None,
+                )
+            },
+            &mut L1XTerminalPrompt,
+        )?;
+
+        if let Some(batch) = &self.batch {
+            return self.exec_batch(client, batch).await;
+        }
+
+        // clap requires both without --batch
+        let (to, amount) = match (&self.to, self.amount) {
+            (Some(to), Some(amount)) => (to, amount),
+            _ => {
+                anyhow::bail!("--to and --amount are required without --batch")
+            }
+        };
+
+        // Reject a bad recipient before querying the node
+        toolkit_config::normalize_address(to)
+            .map_err(L1XTransferError::InvalidRecipient)?;
+
+        if self.preflight {
+            let sender = crate::balance::resolve_address(&self.from)?;
+            let account_state = rpc::get_account_state(
+                &end_point,
+                &sender,
+                rpc::DEFAULT_RPC_TIMEOUT,
+            )
+            .await?;
+            check_balance(&self.from, account_state.balance, amount)?;
+        }
+
+        let hash =
+            transfer(&client, to, amount, Duration::from_secs(self.timeout))
+                .await?;
+
+        match self.json {
+            true => println!("{}", serde_json::json!({ "hash": hash })),
+            false => println!("{}", hash),
+        }
+
+        Ok(())
+    }
+
+    async fn exec_batch(
+        &self,
+        client: ForgeClient,
+        batch: &Path,
+    ) -> Result<()> {
+        let content = std::fs::read_to_string(batch).map_err(|err_code| {
+            L1XBatchError::FileError(format!(
+                "Unable to read {}: {}",
+                batch.display(),
+                err_code
+            ))
+        })?;
+        let recipients =
+            multisend::parse_batch(&batch.display().to_string(), &content)
+                .map_err(L1XTransferError::from)?;
+
+        let progress_path = multisend::progress_path(batch);
+        let progress = match self.resume {
+            true => multisend::load_progress(&progress_path)
+                .map_err(L1XTransferError::from)?,
+            false => Default::default(),
+        };
+
+        let outcomes = multisend::multisend(
+            Arc::new(client),
+            &recipients,
+            &progress,
+            self.max_in_flight,
+            Duration::from_secs(self.timeout),
+        )
+        .await
+        .map_err(L1XTransferError::from)?;
+
+        multisend::save_progress(&progress_path, &recipients, &outcomes)
+            .map_err(L1XTransferError::from)?;
+        print_batch_summary(&recipients, &outcomes, self.json);
+
+        let failed = outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome, L1XBatchOutcome::Failed(_)))
+            .count();
+        if failed > 0 {
+            anyhow::bail!(
+                "{} of {} transfers failed, rerun with --resume to retry them",
+                failed,
+                recipients.len()
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::forge_client::{ForgeClient, ForgeClientConfig};
+use crate::forge_client_tests::{mock_rpc, rpc_result};
+use crate::transfer::*;
+
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer};
+
+const TEST_PRIVATE_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+const RECIPIENT: &str = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
+fn test_client(end_point: &str) -> ForgeClient {
+    ForgeClient::new(ForgeClientConfig {
+        poll_interval: Duration::from_millis(10),
+        ..ForgeClientConfig::new(end_point, TEST_PRIVATE_KEY)
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_check_balance() {
+    assert!(check_balance("ava", 100, 100).is_ok());
+
+    let err = check_balance("ava", 99, 100).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Insufficient balance: wallet 'ava' holds 99, the transfer needs 100"
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_rejects_invalid_recipient() {
+    let client = test_client("http://127.0.0.1:1");
+
+    let result =
+        transfer(&client, "0x7e5f45zz", 10, Duration::from_millis(50)).await;
+
+    assert!(matches!(result, Err(L1XTransferError::InvalidRecipient(_))));
+}
+
+#[tokio::test]
+async fn test_transfer_waits_for_inclusion() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(
+        &server,
+        "l1x_getAccountState",
+        json!({ "account_state": { "balance": "500", "nonce": "2", "account_type": 0 } }),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_submitTransaction",
+            "params": { "request": { "nonce": "3", "fee_limit": "100" } }
+        })))
+        .respond_with(rpc_result(
+            json!({ "hash": "deadbeef", "contract_address": null }),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_rpc(
+        &server,
+        "l1x_getTransactionReceipt",
+        json!({ "block_number": 4 }),
+    )
+    .await;
+    mock_rpc(&server, "l1x_getChainState", json!({ "head_block_number": 4 }))
+        .await;
+
+    // Act
+    let result = transfer(
+        &test_client(&server.uri()),
+        RECIPIENT,
+        10,
+        Duration::from_secs(5),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "deadbeef");
+}
@@ -0,0 +1,368 @@
+use crate::balance::resolve_address;
+use crate::cli_error::L1XConfigError;
+use crate::signer::{signer_secret_key, L1XPrivateKeyArg};
+use l1x_common::{
+    load_transaction_payload, rpc, toolkit_config::ToolkitConfig,
+    txn_builder::TransactionBuilder, types, TxnBuildError,
+};
+use l1x_rpc::{
+    json as l1x_rpc_json,
+    rpc_model::{SubmitTransactionRequest, SubmitTransactionResponse},
+};
+
+use anyhow::Result;
+use reqwest::Client;
+use secp256k1::SecretKey;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+
+#[derive(Debug, thiserror::Error)]
+pub enum L1XTxError {
+    #[error("Transaction file error: {0}")]
+    FileError(String),
+    #[error(
+        "{path} targets chain id {file_chain_id}, network '{network}' has chain id {network_chain_id}"
+    )]
+    ChainIdMismatch {
+        path: String,
+        file_chain_id: u32,
+        network: String,
+        network_chain_id: u32,
+    },
+    #[error("Broadcast error: {0}")]
+    BroadcastError(String),
+    #[error("{path} does not verify: {reason}")]
+    InvalidSignature { path: String, reason: String },
+    #[error("{0}")]
+    BuildError(#[from] TxnBuildError),
+    #[error("{0}")]
+    ConfigError(#[from] L1XConfigError),
+}
+
+/// A transaction built on a connected machine, to be signed offline by
+/// `tx sign`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L1XUnsignedTxn {
+    /// Chain id of the network the transaction was built for
+    pub chain_id: u32,
+    pub payload: types::Transaction,
+    pub nonce: u128,
+    pub fee_limit: u128,
+}
+
+/// A signed transaction request, to be submitted by `tx broadcast`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L1XSignedTxn {
+    /// Chain id of the network the transaction was built for, broadcasting
+    /// to another chain is refused
+    pub chain_id: u32,
+    pub request: SubmitTransactionRequest,
+}
+
+impl L1XUnsignedTxn {
+    /// Sign the transaction with `secret_key`, without contacting any node
+    pub fn sign(
+        &self,
+        secret_key: &SecretKey,
+    ) -> Result<L1XSignedTxn, TxnBuildError> {
+        let request = TransactionBuilder::new()
+            .transaction(self.payload.clone())
+            .fee_limit(self.fee_limit)
+            .nonce(self.nonce)
+            .sign(secret_key)?;
+
+        Ok(L1XSignedTxn { chain_id: self.chain_id, request })
+    }
+}
+
+/// Content of the transaction file `path`
+pub fn read_tx_file<T: DeserializeOwned>(path: &str) -> Result<T, L1XTxError> {
+    let content = fs::read_to_string(path).map_err(|err_code| {
+        L1XTxError::FileError(format!("Unable to read {}: {}", path, err_code))
+    })?;
+
+    serde_json::from_str(&content).map_err(|err_code| {
+        L1XTxError::FileError(format!("Unable to parse {}: {}", path, err_code))
+    })
+}
+
+/// Write `content` to the transaction file `path`
+pub fn write_tx_file<T: Serialize>(
+    path: &str,
+    content: &T,
+) -> Result<(), L1XTxError> {
+    let content =
+        serde_json::to_string_pretty(content).map_err(|err_code| {
+            L1XTxError::FileError(format!(
+                "Unable to serialize {}: {}",
+                path, err_code
+            ))
+        })?;
+
+    fs::write(path, content).map_err(|err_code| {
+        L1XTxError::FileError(format!("Unable to write {}: {}", path, err_code))
+    })
+}
+
+/// Chain id of the active network of `toolkit`
+fn active_chain_id(toolkit: &ToolkitConfig) -> Result<u32, L1XConfigError> {
+    let network = toolkit.active_network()?;
+
+    toolkit
+        .network_config()?
+        .networks()
+        .get(&network)
+        .map(|config_network| config_network.chain_id())
+        .ok_or_else(|| {
+            L1XConfigError(format!(
+                "Network '{}' not found in {}",
+                network,
+                toolkit.paths().chain_config_file().display()
+            ))
+        })
+}
+
+/// Unsigned transaction of `payload` for the active network of `toolkit`
+pub fn build_unsigned(
+    toolkit: &ToolkitConfig,
+    payload: types::Transaction,
+    nonce: u128,
+    fee_limit: u128,
+) -> Result<L1XUnsignedTxn, L1XTxError> {
+    Ok(L1XUnsignedTxn {
+        chain_id: active_chain_id(toolkit)?,
+        payload,
+        nonce,
+        fee_limit,
+    })
+}
+
+/// Submit the signed transaction `signed`, read from `path`, to the active
+/// network of `toolkit` and return its hash
+pub async fn broadcast_signed(
+    toolkit: &ToolkitConfig,
+    signed: &L1XSignedTxn,
+    path: &str,
+) -> Result<String, L1XTxError> {
+    let network_chain_id = active_chain_id(toolkit)?;
+    if signed.chain_id != network_chain_id {
+        return Err(L1XTxError::ChainIdMismatch {
+            path: path.to_string(),
+            file_chain_id: signed.chain_id,
+            network: toolkit.active_network().map_err(L1XConfigError::from)?,
+            network_chain_id,
+        });
+    }
+
+    let end_point = toolkit
+        .active_chain_json_rpc_endpoint()
+        .map_err(L1XConfigError::from)?;
+    let request =
+        serde_json::to_value(&signed.request).map_err(|err_code| {
+            L1XTxError::BroadcastError(format!(
+                "Unable to serialize the request of {}: {}",
+                path, err_code
+            ))
+        })?;
+    let result = l1x_rpc_json::post_json_rpc(
+        Client::new().post(end_point),
+        "l1x_submitTransaction",
+        json!({ "request": request }),
+    )
+    .await
+    .map_err(|err_code| {
+        L1XTxError::BroadcastError(format!("{:#}", err_code))
+    })?;
+
+    let response =
+        l1x_rpc_json::parse_response::<SubmitTransactionResponse>(result)
+            .map_err(|err_code| {
+                L1XTxError::BroadcastError(format!("{:#}", err_code))
+            })?;
+
+    Ok(response.hash)
+}
+
+/// `0x` prefixed address of the account that signed `signed`, read from
+/// `path`, an error when its signature doesn't verify
+pub fn verify_signed(
+    signed: &L1XSignedTxn,
+    path: &str,
+) -> Result<String, L1XTxError> {
+    let signer = l1x_common::verify_submit_txn_req(&signed.request).map_err(
+        |err_code| L1XTxError::InvalidSignature {
+            path: path.to_string(),
+            reason: err_code.to_string(),
+        },
+    )?;
+
+    Ok(format!("0x{}", hex::encode(signer)))
+}
+
+/// Build, sign offline and broadcast transactions in separate steps
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "tx")]
+pub struct L1XTxCmd {
+    #[clap(subcommand)]
+    command: L1XTxSubCmd,
+}
+
+#[derive(Clone, Debug, clap::Subcommand)]
+enum L1XTxSubCmd {
+    /// Write the unsigned transaction of a payload file
+    #[command(name = "build")]
+    Build {
+        /// Transaction payload [ json | yaml | toml ], `-` reading JSON from
+        /// stdin
+        #[clap(long = "payload-file")]
+        payload_file: String,
+
+        /// Wallet or address whose next nonce the transaction carries
+        #[clap(long = "from")]
+        from: Option<String>,
+
+        /// Nonce of the transaction instead of the next nonce of --from
+        #[clap(long = "nonce")]
+        nonce: Option<u128>,
+
+        #[clap(long = "fee-limit", default_value_t = 100)]
+        fee_limit: u128,
+
+        /// Network of l1x_chain_config.yaml the transaction is built for,
+        /// instead of L1X_CFG_CHAIN_TYPE or its default_network
+        #[clap(long = "network")]
+        network: Option<String>,
+
+        #[clap(long = "out")]
+        out: String,
+    },
+    /// Sign an unsigned transaction without contacting any node
+    #[command(name = "sign")]
+    Sign {
+        /// Unsigned transaction file written by `tx build`
+        file: String,
+
+        #[clap(long = "owner", required_unless_present = "private_key")]
+        owner: Option<String>,
+
+        /// Hex private key signing the transaction instead of the wallet
+        #[clap(long = "private-key")]
+        private_key: Option<L1XPrivateKeyArg>,
+
+        #[clap(long = "out")]
+        out: String,
+    },
+    /// Submit a signed transaction and print its hash
+    #[command(name = "broadcast")]
+    Broadcast {
+        /// Signed transaction file written by `tx sign`
+        file: String,
+
+        /// Network of l1x_chain_config.yaml to submit to, instead of
+        /// L1X_CFG_CHAIN_TYPE or its default_network
+        #[clap(long = "network")]
+        network: Option<String>,
+
+        /// Print the transaction hash as JSON
+        #[clap(long = "json", default_value_t = false)]
+        json: bool,
+    },
+    /// Check the signature of a signed transaction and print its signer
+    #[command(name = "verify")]
+    Verify {
+        /// Signed transaction file written by `tx sign`
+        file: String,
+
+        /// Print the signer address as JSON
+        #[clap(long = "json", default_value_t = false)]
+        json: bool,
+    },
+}
+
+impl L1XTxCmd {
+    pub async fn exec(&self) -> Result<()> {
+        tracing::debug!("L1X Tx With Args :: {:#?}!", &self);
+
+        match &self.command {
+            L1XTxSubCmd::Build {
+                payload_file,
+                from,
+                nonce,
+                fee_limit,
+                network,
+                out,
+            } => {
+                let toolkit = ToolkitConfig::from_env()
+                    .map_err(L1XConfigError::from)?
+                    .with_network(network.as_deref());
+                let payload = load_transaction_payload(payload_file, None)?;
+
+                let nonce = match (nonce, from) {
+                    (Some(nonce), _) => *nonce,
+                    (None, Some(from)) => {
+                        let end_point = toolkit
+                            .active_chain_json_rpc_endpoint()
+                            .map_err(L1XConfigError::from)?;
+                        rpc::fetch_nonce(
+                            &end_point,
+                            &resolve_address(from)?,
+                            rpc::DEFAULT_RPC_TIMEOUT,
+                        )
+                        .await?
+                            + 1
+                    }
+                    (None, None) => {
+                        anyhow::bail!(
+                            "--nonce is required when --from is not set"
+                        )
+                    }
+                };
+
+                let unsigned =
+                    build_unsigned(&toolkit, payload, nonce, *fee_limit)?;
+                write_tx_file(out, &unsigned)?;
+                println!(
+                    "{}: nonce {} for chain id {}",
+                    out, unsigned.nonce, unsigned.chain_id
+                );
+            }
+            L1XTxSubCmd::Sign { file, owner, private_key, out } => {
+                let unsigned: L1XUnsignedTxn = read_tx_file(file)?;
+                let secret_key = signer_secret_key(
+                    &ToolkitConfig::from_env().map_err(L1XConfigError::from)?,
+                    owner.as_deref(),
+                    private_key.as_ref(),
+                )?;
+
+                write_tx_file(out, &unsigned.sign(&secret_key)?)?;
+                println!("{}: signed for chain id {}", out, unsigned.chain_id);
+            }
+            L1XTxSubCmd::Broadcast { file, network, json } => {
+                let toolkit = ToolkitConfig::from_env()
+                    .map_err(L1XConfigError::from)?
+                    .with_network(network.as_deref());
+                let signed: L1XSignedTxn = read_tx_file(file)?;
+
+                let hash = broadcast_signed(&toolkit, &signed, file).await?;
+
+                match json {
+                    true => println!("{}", json!({ "hash": hash })),
+                    false => println!("{}", hash),
+                }
+            }
+            L1XTxSubCmd::Verify { file, json } => {
+                let signed: L1XSignedTxn = read_tx_file(file)?;
+
+                let signer = verify_signed(&signed, file)?;
+
+                match json {
+                    true => println!("{}", json!({ "signer": signer })),
+                    false => println!("{}: signed by {}", file, signer),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
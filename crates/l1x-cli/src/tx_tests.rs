@@ -0,0 +1,153 @@
+use crate::contract_install_test::toolkit_on;
+use crate::tx::*;
+use l1x_common::types::{Transaction, U8s};
+
+use secp256k1::SecretKey;
+use serde_json::json;
+use std::fs;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str =
+    "6d657bbe6f7604fb53bc22e0b5285d3e2ad17f64441b2dc19b648933850f9b46";
+
+fn function_call() -> Transaction {
+    Transaction::SmartContractFunctionCall {
+        contract_instance_address: U8s::Hex("00".repeat(20)),
+        function: U8s::Text("transfer".to_string()),
+        arguments: U8s::Text("{}".to_string()),
+    }
+}
+
+fn secret_key() -> SecretKey {
+    SecretKey::from_slice(&hex::decode(TEST_PRIVATE_KEY).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_build_sign_broadcast_round_trip() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_submitTransaction",
+            "params": { "request": { "nonce": "7", "fee_limit": "250" } }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "hash": "deadbeef", "contract_address": null },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = toolkit_on(ws_home.path(), 1076, &server.uri());
+    let unsigned_path = ws_home.path().join("unsigned.json");
+    let unsigned_path = unsigned_path.to_str().unwrap();
+    let signed_path = ws_home.path().join("signed.json");
+    let signed_path = signed_path.to_str().unwrap();
+
+    // Act
+    let unsigned = build_unsigned(&toolkit, function_call(), 7, 250).unwrap();
+    write_tx_file(unsigned_path, &unsigned).unwrap();
+    let signed = read_tx_file::<L1XUnsignedTxn>(unsigned_path)
+        .unwrap()
+        .sign(&secret_key())
+        .unwrap();
+    write_tx_file(signed_path, &signed).unwrap();
+    let loaded: L1XSignedTxn = read_tx_file(signed_path).unwrap();
+    let hash = broadcast_signed(&toolkit, &loaded, signed_path).await;
+
+    // Assert
+    assert_eq!(unsigned.chain_id, 1076);
+    assert_eq!(loaded, signed);
+    assert_eq!(loaded.chain_id, 1076);
+    assert_eq!(hash.unwrap(), "deadbeef");
+}
+
+#[tokio::test]
+async fn test_broadcast_refuses_other_chain() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let ws_home = tempfile::tempdir().unwrap();
+    let signed = build_unsigned(
+        &toolkit_on(ws_home.path(), 1076, &server.uri()),
+        function_call(),
+        7,
+        250,
+    )
+    .unwrap()
+    .sign(&secret_key())
+    .unwrap();
+    let other_ws_home = tempfile::tempdir().unwrap();
+    let mainnet = toolkit_on(other_ws_home.path(), 1, &server.uri());
+
+    // Act
+    let result = broadcast_signed(&mainnet, &signed, "signed.json").await;
+
+    // Assert
+    let err = result.unwrap_err();
+    assert!(matches!(
+        err,
+        L1XTxError::ChainIdMismatch {
+            file_chain_id: 1076,
+            network_chain_id: 1,
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "signed.json targets chain id 1076, network 'local' has chain id 1"
+    );
+}
+
+#[test]
+fn test_verify_signed_names_signer_and_rejects_tampering() {
+    let ws_home = tempfile::tempdir().unwrap();
+    let signed = build_unsigned(
+        &toolkit_on(ws_home.path(), 1076, "http://127.0.0.1:1"),
+        function_call(),
+        7,
+        250,
+    )
+    .unwrap()
+    .sign(&secret_key())
+    .unwrap();
+    let mut tampered = signed.clone();
+    tampered.request.fee_limit = "1".to_string();
+
+    let signer = verify_signed(&signed, "signed.json").unwrap();
+    let err = verify_signed(&tampered, "tampered.json").unwrap_err();
+
+    assert_eq!(
+        signer,
+        format!(
+            "0x{}",
+            hex::encode(
+                l1x_common::account_address(
+                    &secret_key()
+                        .public_key(&secp256k1::Secp256k1::new())
+                        .serialize()
+                )
+                .unwrap()
+            )
+        )
+    );
+    assert!(err.to_string().starts_with("tampered.json does not verify: "));
+}
+
+#[test]
+fn test_read_tx_file_reports_malformed_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("signed.json");
+    fs::write(&path, r#"{"chain_id": 1"#).unwrap();
+
+    let result = read_tx_file::<L1XSignedTxn>(path.to_str().unwrap());
+
+    assert!(matches!(result, Err(L1XTxError::FileError(_))));
+}
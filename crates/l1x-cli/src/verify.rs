@@ -0,0 +1,233 @@
+use crate::cli_error::L1XConfigError;
+use crate::contract_install::L1XVMType;
+use crate::evm_artifact::L1XEvmArtifact;
+use l1x_common::{
+    rpc,
+    toolkit_config::{L1XVMArtifactType, ToolkitConfig},
+    types::AddressStr,
+};
+
+use anyhow::Result;
+use secp256k1::hashes::{sha256, Hash};
+use serde::Serialize;
+
+/// How the code of an EVM contract is compared with its artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum L1XEvmCodeMode {
+    /// The node reports the runtime bytecode, compared with the
+    /// `deployedBytecode` of the artifact
+    Runtime,
+    /// The node reports the creation bytecode with the constructor arguments
+    /// appended, stripped before comparing with the `bytecode` of the
+    /// artifact
+    Creation,
+}
+
+/// Check that the contract registered for an artifact runs the local
+/// artifact, exits 1 when it does not
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "verify")]
+pub struct L1XVerifyCmd {
+    #[clap(long = "artifact-id")]
+    artifact_id: String,
+
+    /// Instance of the eBPF or WASM deployment to check, the deployment
+    /// itself when unset
+    #[clap(long = "contract-id")]
+    contract_id: Option<String>,
+
+    #[clap(long = "vm-type", default_value = "ebpf")]
+    vm_type: L1XVMType,
+
+    /// How the code of an EVM contract is compared with the artifact
+    #[clap(long = "evm-code", value_enum, default_value = "runtime")]
+    evm_code: L1XEvmCodeMode,
+
+    /// Record the sha256 of matching code in the registry metadata of the
+    /// deployment
+    #[clap(long = "update-registry", default_value_t = false)]
+    update_registry: bool,
+
+    /// Print the outcome as JSON
+    #[clap(long = "json", default_value_t = false)]
+    json: bool,
+
+    /// Network of l1x_chain_config.yaml to verify on, instead of
+    /// L1X_CFG_CHAIN_TYPE or its default_network
+    #[clap(long = "network")]
+    network: Option<String>,
+}
+
+/// Outcome of [`L1XVerifyCmd::verify`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct L1XVerifyOutcome {
+    pub artifact_id: String,
+    pub contract_id: Option<String>,
+    pub address: AddressStr,
+    /// Hex sha256 of the code of the local artifact
+    pub local_sha256: String,
+    /// Hex sha256 of the code the node reports
+    pub onchain_sha256: String,
+    pub matches: bool,
+}
+
+impl std::fmt::Display for L1XVerifyOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.contract_id {
+            Some(contract_id) => {
+                write!(f, "{}/{}", self.artifact_id, contract_id)?
+            }
+            None => write!(f, "{}", self.artifact_id)?,
+        }
+        write!(
+            f,
+            " :: {} :: {}\n  local:    {}\n  on-chain: {}",
+            self.address,
+            if self.matches { "match" } else { "MISMATCH" },
+            self.local_sha256,
+            self.onchain_sha256
+        )
+    }
+}
+
+/// Code of the artifact file `artifact_content` the node should report for
+/// `vm_type`, and the `onchain_code` to compare with it. In
+/// [`L1XEvmCodeMode::Creation`] the constructor arguments appended to the
+/// on-chain creation bytecode are stripped.
+pub fn comparable_code(
+    vm_type: L1XVMType,
+    evm_code: L1XEvmCodeMode,
+    artifact_content: Vec<u8>,
+    mut onchain_code: Vec<u8>,
+) -> Result<(Vec<u8>, Vec<u8>), L1XConfigError> {
+    if vm_type != L1XVMType::L1xVmEvm {
+        return Ok((artifact_content, onchain_code));
+    }
+
+    let artifact = std::str::from_utf8(&artifact_content)
+        .map_err(|err_code| err_code.to_string())
+        .and_then(|content| {
+            L1XEvmArtifact::parse(content)
+                .map_err(|err_code| err_code.to_string())
+        })
+        .map_err(|reason| {
+            L1XConfigError(format!("Invalid EVM artifact: {}", reason))
+        })?;
+
+    match evm_code {
+        L1XEvmCodeMode::Runtime => {
+            let runtime = artifact.deployed_bytecode.ok_or_else(|| {
+                L1XConfigError(
+                    "The EVM artifact has no deployedBytecode, verify a JSON artifact or use --evm-code creation".to_string(),
+                )
+            })?;
+            Ok((runtime, onchain_code))
+        }
+        L1XEvmCodeMode::Creation => {
+            if onchain_code.starts_with(&artifact.bytecode) {
+                onchain_code.truncate(artifact.bytecode.len());
+            }
+            Ok((artifact.bytecode, onchain_code))
+        }
+    }
+}
+
+/// Hex sha256 of `code`
+fn sha256_hex(code: &[u8]) -> String {
+    sha256::Hash::hash(code).to_string()
+}
+
+impl L1XVerifyCmd {
+    /// Compare the code the node of `toolkit` reports at the registered
+    /// address with the local artifact, and record the sha256 of matching
+    /// code with --update-registry
+    pub async fn verify(
+        &self,
+        toolkit: &ToolkitConfig,
+    ) -> Result<L1XVerifyOutcome> {
+        if self.vm_type == L1XVMType::L1xVmEvm && self.contract_id.is_some() {
+            anyhow::bail!(
+                "EVM contracts have no instances, --contract-id is not supported for --vm-type evm"
+            );
+        }
+        let vm_type: L1XVMArtifactType = self.vm_type.into();
+        let address = toolkit
+            .contract_address_for(
+                vm_type,
+                &self.artifact_id,
+                self.contract_id.as_deref(),
+            )
+            .map_err(L1XConfigError::from)?;
+
+        let artifact_file =
+            toolkit.artifact_dir(vm_type).join(&self.artifact_id);
+        let artifact_content =
+            std::fs::read(&artifact_file).map_err(|err_code| {
+                L1XConfigError(format!(
+                    "Unable to read the artifact {} :: {}",
+                    artifact_file.display(),
+                    err_code
+                ))
+            })?;
+
+        let end_point = toolkit
+            .active_chain_json_rpc_endpoint()
+            .map_err(L1XConfigError::from)?;
+        let onchain_code = rpc::get_contract_code(
+            &end_point,
+            &address.to_hex(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        )
+        .await?
+        .ok_or_else(|| {
+            anyhow::anyhow!("The node reports no contract code at {}", address)
+        })?;
+
+        let (local_code, onchain_code) = comparable_code(
+            self.vm_type,
+            self.evm_code,
+            artifact_content,
+            onchain_code,
+        )?;
+        let local_sha256 = sha256_hex(&local_code);
+        let onchain_sha256 = sha256_hex(&onchain_code);
+        let matches = local_sha256 == onchain_sha256;
+
+        if matches && self.update_registry {
+            toolkit
+                .set_verified_sha256(vm_type, &self.artifact_id, &local_sha256)
+                .map_err(L1XConfigError::from)?;
+        }
+
+        Ok(L1XVerifyOutcome {
+            artifact_id: self.artifact_id.clone(),
+            contract_id: self.contract_id.clone(),
+            address,
+            local_sha256,
+            onchain_sha256,
+            matches,
+        })
+    }
+
+    pub async fn exec(&self) -> Result<()> {
+        let toolkit = ToolkitConfig::from_env()
+            .map_err(L1XConfigError::from)?
+            .with_network(self.network.as_deref());
+
+        let outcome = self.verify(&toolkit).await?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&outcome)?);
+        } else {
+            println!("{}", outcome);
+        }
+
+        match outcome.matches {
+            true => Ok(()),
+            false => anyhow::bail!(
+                "The code at {} does not match the artifact {}",
+                outcome.address,
+                self.artifact_id
+            ),
+        }
+    }
+}
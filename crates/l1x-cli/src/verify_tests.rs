@@ -0,0 +1,191 @@
+use crate::contract_install::L1XVMType;
+use crate::contract_install_test::{
+    toolkit_for, with_temp_registry, write_chain_config, write_toolkit_config,
+};
+use crate::registry::registry_entries;
+use crate::verify::*;
+use l1x_common::toolkit_config::L1XVMArtifactType;
+
+use clap::Parser;
+use secp256k1::hashes::{sha256, Hash};
+use serde_json::{json, Value};
+use std::{future::Future, path::PathBuf};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    verify: L1XVerifyCmd,
+}
+
+fn verify_cmd(args: &[&str]) -> L1XVerifyCmd {
+    TestCli::parse_from(["verify", "--artifact-id", "ft"].iter().chain(args))
+        .verify
+}
+
+const ARTIFACT: &[u8] = b"\x7fELF object of ft";
+
+/// Register the eBPF artifact `ft` at the address `a` repeated, with its
+/// artifact file, on a node answering `l1x_getContractCode` with `code`
+fn with_deployed_code<T, Fut>(
+    code: Value,
+    test: impl FnOnce(PathBuf) -> Fut,
+) -> T
+where
+    Fut: Future<Output = T>,
+{
+    with_temp_registry(None, |ws_home| {
+        write_toolkit_config(ws_home);
+        std::fs::create_dir_all(ws_home.join("l1x-artifacts")).unwrap();
+        std::fs::write(ws_home.join("l1x-artifacts/ft"), ARTIFACT).unwrap();
+        toolkit_for(ws_home)
+            .with_chain_type("local")
+            .set_contract_address(
+                L1XVMArtifactType::Ebpf,
+                "ft",
+                None,
+                "a".repeat(40).parse().unwrap(),
+            )
+            .unwrap();
+
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let server = MockServer::start().await;
+            write_chain_config(ws_home, 1, &server.uri());
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "method": "l1x_getContractCode",
+                    "params": { "request": { "address": "a".repeat(40) } },
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(
+                    json!({ "jsonrpc": "2.0", "id": 1, "result": { "code": code } }),
+                ))
+                .mount(&server)
+                .await;
+
+            test(ws_home.to_path_buf()).await
+        })
+    })
+}
+
+fn verified_sha256(ws_home: &std::path::Path) -> Option<String> {
+    let toolkit = toolkit_for(ws_home).with_chain_type("local");
+    registry_entries(&toolkit, Some("ft")).unwrap()[0]
+        .metadata
+        .verified_sha256
+        .clone()
+}
+
+#[test]
+fn test_verify_matching_code_stamps_the_registry() {
+    // Arrange
+    let cmd = verify_cmd(&["--update-registry"]);
+
+    // Act
+    let (outcome, verified) = with_deployed_code(
+        json!(hex::encode(ARTIFACT)),
+        |ws_home| async move {
+            let toolkit = toolkit_for(&ws_home).with_chain_type("local");
+            let outcome = cmd.verify(&toolkit).await;
+            (outcome.unwrap(), verified_sha256(&ws_home))
+        },
+    );
+
+    // Assert
+    let sha256 = sha256::Hash::hash(ARTIFACT).to_string();
+    assert!(outcome.matches);
+    assert_eq!(outcome.local_sha256, sha256);
+    assert_eq!(outcome.onchain_sha256, sha256);
+    assert_eq!(outcome.address, "a".repeat(40).parse().unwrap());
+    assert_eq!(verified, Some(sha256));
+}
+
+#[test]
+fn test_verify_mismatching_code_fails_and_leaves_the_registry() {
+    // Arrange
+    let cmd = verify_cmd(&["--update-registry"]);
+
+    // Act
+    let (exec_status, verified) = with_deployed_code(
+        json!([0x7f, 0x45, 0x4c, 0x46]),
+        |ws_home| async move { (cmd.exec().await, verified_sha256(&ws_home)) },
+    );
+
+    // Assert
+    assert!(exec_status
+        .unwrap_err()
+        .to_string()
+        .contains("does not match the artifact ft"));
+    assert_eq!(verified, None);
+}
+
+#[test]
+fn test_verify_without_code_on_chain_fails() {
+    let cmd = verify_cmd(&[]);
+
+    let exec_status =
+        with_deployed_code(Value::Null, |_| async move { cmd.exec().await });
+
+    assert!(exec_status.unwrap_err().to_string().contains("no contract code"));
+}
+
+/// Content of the artifact fixture `file_name`
+fn fixture(file_name: &str) -> Vec<u8> {
+    std::fs::read(format!(
+        "{}/fixtures/artifacts/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        file_name
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_evm_runtime_code_is_the_deployed_bytecode() {
+    let runtime = hex::decode("6080604052600080fdfe").unwrap();
+
+    let (local, onchain) = comparable_code(
+        L1XVMType::L1xVmEvm,
+        L1XEvmCodeMode::Runtime,
+        fixture("Token.json"),
+        runtime.clone(),
+    )
+    .unwrap();
+
+    assert_eq!((local, onchain), (runtime.clone(), runtime));
+}
+
+#[test]
+fn test_evm_creation_code_strips_the_constructor_args() {
+    // Arrange
+    let creation = hex::decode(
+        "6080604052348015600f57600080fd5b50603f80601d6000396000f3fe",
+    )
+    .unwrap();
+    let mut deployed = creation.clone();
+    deployed.extend([0u8; 32]);
+
+    // Act
+    let (local, onchain) = comparable_code(
+        L1XVMType::L1xVmEvm,
+        L1XEvmCodeMode::Creation,
+        fixture("Token.json"),
+        deployed,
+    )
+    .unwrap();
+
+    // Assert
+    assert_eq!(local, creation);
+    assert_eq!(onchain, creation);
+}
+
+#[test]
+fn test_evm_runtime_code_of_a_raw_hex_artifact_is_rejected() {
+    let compared = comparable_code(
+        L1XVMType::L1xVmEvm,
+        L1XEvmCodeMode::Runtime,
+        b"0x6080604052".to_vec(),
+        Vec::new(),
+    );
+
+    assert!(compared.unwrap_err().0.contains("no deployedBytecode"));
+}
@@ -0,0 +1,163 @@
+use crate::cli_error::L1XConfigError;
+use l1x_common::toolkit_config::{
+    self, L1XVMArtifactType, ToolkitPaths, WsHomeSource,
+};
+
+use anyhow::Result;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Network of the generated `l1x_chain_config.yaml`
+pub const DEFAULT_CHAIN_TYPE: &str = "local_devnet";
+
+/// Account of the generated `l1x_dev_wallets.yaml`
+pub const DEFAULT_DEV_ACCOUNT: &str = "dev";
+
+/// Create the `l1x-conf` files and artifact directories of a workspace
+#[derive(Clone, Debug, clap::Args)]
+#[clap(name = "init-workspace")]
+pub struct L1XInitWorkspaceCmd {
+    /// Workspace directory, `L1X_CFG_WS_HOME` or `$XDG_CONFIG_HOME/l1x` by
+    /// default
+    #[clap(long = "ws-home")]
+    ws_home: Option<PathBuf>,
+
+    /// Overwrite the existing configuration files
+    #[clap(long = "force", default_value_t = false)]
+    force: bool,
+}
+
+fn chain_config() -> String {
+    format!(
+        r#"# Networks of the workspace, selected with L1X_CFG_CHAIN_TYPE
+networks:
+  {DEFAULT_CHAIN_TYPE}:
+    chain_id: 1
+    host_ip: "127.0.0.1"
+    rpc_port: 50051
+    rpc_endpoint: "http://127.0.0.1:50051"
+    # Development network, other networks ask before submitting unless --yes
+    is_dev: true
+    # Cluster of the node, lets the installer predict contract addresses
+    # cluster_address: "<cluster address>"
+
+# Build artifact directories, relative to L1X_CFG_WS_HOME unless absolute
+# artifact_dirs:
+#   ebpf: {}
+#   evm: {}
+"#,
+        L1XVMArtifactType::Ebpf.default_dir(),
+        L1XVMArtifactType::Evm.default_dir()
+    )
+}
+
+fn dev_wallets(priv_key: &str, pub_key: &str) -> String {
+    format!(
+        r#"# Development accounts, referred to by name with --owner
+# Never use these keys outside of a development network
+dev_accounts:
+  {DEFAULT_DEV_ACCOUNT}:
+    priv_key: "{priv_key}"
+    pub_key: "{pub_key}"
+"#
+    )
+}
+
+const CONTRACT_ADDRESS_REGISTRY: &str = r#"# Contracts installed by vm-install-contract per network, maintained by
+# l1x-forge
+networks: {}
+"#;
+
+/// Write the configuration files and create the artifact directories of the
+/// workspace `ws_home`, refusing to overwrite any existing configuration file
+/// unless `force` is set. Returns the created paths.
+pub fn init_workspace(ws_home: &Path, force: bool) -> Result<Vec<PathBuf>> {
+    let conf_dir = ws_home.join("l1x-conf");
+
+    let (priv_key, pub_key, _) =
+        l1x_common::secp256k1_creds(None).map_err(|err_code| {
+            anyhow::anyhow!("Failed to generate a dev account :: {}", err_code)
+        })?;
+
+    let files = [
+        (conf_dir.join("l1x_chain_config.yaml"), chain_config()),
+        (
+            conf_dir.join("l1x_dev_wallets.yaml"),
+            dev_wallets(&priv_key, &pub_key),
+        ),
+        (
+            conf_dir.join("config-contract-address-registry.yaml"),
+            CONTRACT_ADDRESS_REGISTRY.to_string(),
+        ),
+    ];
+
+    let existing_files = files
+        .iter()
+        .filter(|(path, _)| path.exists())
+        .map(|(path, _)| path.display().to_string())
+        .collect::<Vec<_>>();
+    if !force && !existing_files.is_empty() {
+        anyhow::bail!(
+            "Workspace already initialized, use --force to overwrite: {}",
+            existing_files.join(", ")
+        );
+    }
+
+    let mut created = Vec::new();
+    let dirs = [
+        conf_dir.clone(),
+        ws_home.join(L1XVMArtifactType::Ebpf.default_dir()),
+        ws_home.join(L1XVMArtifactType::Evm.default_dir()),
+    ];
+    for dir in dirs {
+        if !dir.is_dir() {
+            fs::create_dir_all(&dir)?;
+            created.push(dir);
+        }
+    }
+
+    for (path, content) in files {
+        fs::write(&path, content).map_err(|err_code| {
+            anyhow::anyhow!(
+                "Unable to write {} :: {}",
+                path.display(),
+                err_code
+            )
+        })?;
+        toolkit_config::invalidate_config_file(&path);
+        created.push(path);
+    }
+
+    Ok(created)
+}
+
+impl L1XInitWorkspaceCmd {
+    pub async fn exec(&self) -> Result<()> {
+        tracing::debug!("Init Workspace With Args :: {:#?}!", &self);
+
+        let paths = ToolkitPaths::resolve(self.ws_home.clone())
+            .map_err(L1XConfigError::from)?;
+        for path in init_workspace(paths.ws_home(), self.force)? {
+            println!("Created {}", path.display());
+        }
+
+        // The user configuration directory is found without L1X_CFG_WS_HOME
+        let ws_home_export = match paths.ws_home_source() {
+            WsHomeSource::UserConfigDir => String::new(),
+            WsHomeSource::Explicit | WsHomeSource::Env => format!(
+                "\n  export L1X_CFG_WS_HOME={}",
+                paths.ws_home().display()
+            ),
+        };
+        println!(
+            "Workspace ready, use it with:{}\n  export L1X_CFG_CHAIN_TYPE={}\n  --owner {}",
+            ws_home_export,
+            DEFAULT_CHAIN_TYPE,
+            DEFAULT_DEV_ACCOUNT
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::contract_install_test::with_temp_registry;
+use crate::workspace_init::*;
+use l1x_common::toolkit_config::ToolkitConfig;
+
+use std::{env, fs};
+
+#[test]
+fn test_init_workspace_files_load() {
+    with_temp_registry(None, |ws_home| {
+        // Arrange
+        env::set_var("L1X_CFG_CHAIN_TYPE", DEFAULT_CHAIN_TYPE);
+
+        // Act
+        let created = init_workspace(ws_home, false).unwrap();
+
+        // Assert
+        assert!(created.contains(&ws_home.join("l1x-artifacts")));
+        assert!(created.contains(&ws_home.join("l1x-evm-artifacts")));
+        assert!(ToolkitConfig::from_env().unwrap().network_config().is_ok());
+        assert_eq!(
+            ToolkitConfig::from_env()
+                .unwrap()
+                .active_chain_json_rpc_endpoint()
+                .unwrap(),
+            "http://127.0.0.1:50051"
+        );
+        assert_eq!(
+            ToolkitConfig::from_env()
+                .unwrap()
+                .wallet_priv_key(DEFAULT_DEV_ACCOUNT)
+                .unwrap()
+                .len(),
+            64
+        );
+        assert_eq!(
+            ToolkitConfig::from_env()
+                .unwrap()
+                .ebpf_contract_address_for("ft", None)
+                .unwrap_err()
+                .to_string(),
+            "Artifact 'ft' not found in the contract registry"
+        );
+    });
+}
+
+#[test]
+fn test_init_workspace_refuses_to_overwrite() {
+    with_temp_registry(Some("l1x_vm: {}\nl1x_evm: {}\n"), |ws_home| {
+        let result = init_workspace(ws_home, false);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("config-contract-address-registry.yaml"));
+        assert!(!ws_home.join("l1x-conf/l1x_dev_wallets.yaml").exists());
+    });
+}
+
+#[test]
+fn test_init_workspace_force_overwrites() {
+    with_temp_registry(Some("stale"), |ws_home| {
+        let result = init_workspace(ws_home, true);
+
+        assert!(result.is_ok());
+        assert!(fs::read_to_string(
+            ws_home.join("l1x-conf/config-contract-address-registry.yaml")
+        )
+        .unwrap()
+        .contains("networks: {}"));
+    });
+}
@@ -0,0 +1,147 @@
+use serde::de::{
+    self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess,
+    Visitor,
+};
+
+/// Byte encoding of the JSON arguments of a contract call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum L1XArgsEncoding {
+    /// The JSON text itself, as eBPF and WASM contracts read their arguments
+    #[default]
+    Json,
+    /// Borsh of the JSON value: object fields in the order written,
+    /// top-level array items as a tuple, nested arrays as a `Vec`, strings
+    /// as a `String`, booleans as a `bool` and numbers as a `u64`, an `i64`
+    /// when negative or an `f64` otherwise
+    Borsh,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CallArgsError {
+    #[error("not JSON, {0}")]
+    InvalidJson(serde_json::Error),
+    #[error("expected a JSON object or array of arguments, got {0}")]
+    NotArguments(String),
+    #[error("no borsh encoding, {0}")]
+    Borsh(serde_json::Error),
+}
+
+/// Bytes of the JSON object or array of call arguments `json_args` in
+/// `encoding`
+pub fn encode_call_args(
+    json_args: &str,
+    encoding: L1XArgsEncoding,
+) -> Result<Vec<u8>, CallArgsError> {
+    let json_args = json_args.trim();
+    let value = serde_json::from_str::<serde_json::Value>(json_args)
+        .map_err(CallArgsError::InvalidJson)?;
+    if !value.is_object() && !value.is_array() {
+        return Err(CallArgsError::NotArguments(value.to_string()));
+    }
+
+    match encoding {
+        // The text rather than `value`, which loses the precision of
+        // numbers beyond u64 and the order of the fields
+        L1XArgsEncoding::Json => Ok(json_args.as_bytes().to_vec()),
+        L1XArgsEncoding::Borsh => {
+            let mut args = Vec::new();
+            let mut deserializer =
+                serde_json::Deserializer::from_str(json_args);
+            BorshSeed { out: &mut args, nested: false }
+                .deserialize(&mut deserializer)
+                .and_then(|_| deserializer.end())
+                .map_err(CallArgsError::Borsh)?;
+            Ok(args)
+        }
+    }
+}
+
+/// Writes the borsh encoding of the JSON value it deserializes to `out`,
+/// straight from the JSON text to keep the order of the object fields
+struct BorshSeed<'a> {
+    out: &'a mut Vec<u8>,
+    /// Arrays below the top level are length prefixed
+    nested: bool,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for BorshSeed<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for BorshSeed<'a> {
+    type Value = ();
+
+    fn expecting(
+        &self,
+        formatter: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        formatter.write_str("JSON contract arguments")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<(), E> {
+        self.out.push(u8::from(value));
+        Ok(())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<(), E> {
+        self.out.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<(), E> {
+        self.out.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<(), E> {
+        self.out.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<(), E> {
+        let len = u32::try_from(value.len()).map_err(E::custom)?;
+        self.out.extend_from_slice(&len.to_le_bytes());
+        self.out.extend_from_slice(value.as_bytes());
+        Ok(())
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Err(E::custom("null has no borsh encoding, leave the argument out"))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let out = self.out;
+        let len_at = out.len();
+        if self.nested {
+            out.extend_from_slice(&[0; 4]);
+        }
+
+        let mut len: u32 = 0;
+        while seq
+            .next_element_seed(BorshSeed { out: &mut *out, nested: true })?
+            .is_some()
+        {
+            len += 1;
+        }
+
+        if self.nested {
+            out[len_at..len_at + 4].copy_from_slice(&len.to_le_bytes());
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let out = self.out;
+        while map.next_key::<IgnoredAny>()?.is_some() {
+            map.next_value_seed(BorshSeed { out: &mut *out, nested: true })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::call_args::*;
+
+#[test]
+fn test_json_args_are_sent_as_written() {
+    let args =
+        r#"{"to": "ab", "amount": "340282366920938463463374607431768211455"}"#;
+
+    let encoded =
+        encode_call_args(&format!(" {}\n", args), L1XArgsEncoding::Json);
+
+    assert_eq!(encoded.unwrap(), args.as_bytes().to_vec());
+}
+
+#[test]
+fn test_borsh_object_encodes_its_fields_in_order() {
+    // Arrange
+    let args = r#"{"to": "ab", "amount": 5, "memo": [1, 2], "ok": true}"#;
+
+    // Act
+    let encoded = encode_call_args(args, L1XArgsEncoding::Borsh);
+
+    // Assert
+    let mut expected = vec![2, 0, 0, 0, b'a', b'b'];
+    expected.extend(5u64.to_le_bytes());
+    expected.extend([2, 0, 0, 0]);
+    expected.extend(1u64.to_le_bytes());
+    expected.extend(2u64.to_le_bytes());
+    expected.push(1);
+    assert_eq!(encoded.unwrap(), expected);
+}
+
+#[test]
+fn test_borsh_keeps_the_field_order_written() {
+    let encoded =
+        encode_call_args(r#"{"b": false, "a": true}"#, L1XArgsEncoding::Borsh);
+
+    assert_eq!(encoded.unwrap(), vec![0, 1]);
+}
+
+#[test]
+fn test_borsh_top_level_array_is_a_tuple() {
+    // Act
+    let encoded =
+        encode_call_args(r#"["é", -1, 0.5, []]"#, L1XArgsEncoding::Borsh);
+
+    // Assert
+    let mut expected = vec![2, 0, 0, 0, 0xc3, 0xa9];
+    expected.extend((-1i64).to_le_bytes());
+    expected.extend(0.5f64.to_le_bytes());
+    expected.extend([0, 0, 0, 0]);
+    assert_eq!(encoded.unwrap(), expected);
+}
+
+#[test]
+fn test_borsh_rejects_null() {
+    let encoded = encode_call_args(r#"{"memo": null}"#, L1XArgsEncoding::Borsh);
+
+    assert!(matches!(encoded, Err(CallArgsError::Borsh(_))));
+    assert!(encoded.unwrap_err().to_string().contains("null has no borsh"));
+}
+
+#[test]
+fn test_args_must_be_an_object_or_array() {
+    for encoding in [L1XArgsEncoding::Json, L1XArgsEncoding::Borsh] {
+        assert!(matches!(
+            encode_call_args("42", encoding),
+            Err(CallArgsError::NotArguments(_))
+        ));
+        assert!(matches!(
+            encode_call_args("{\"to\":", encoding),
+            Err(CallArgsError::InvalidJson(_))
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::rpc::RpcError;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Fee limit of a transaction unless `--fee_limit` is given
+pub const DEFAULT_FEE_LIMIT: u128 = 100;
+
+/// JSON-RPC method returning the fee the node expects for a transaction
+pub const ESTIMATE_FEE_METHOD: &str = "l1x_estimateFee";
+
+/// Percentage added to a fee estimate by default
+pub const DEFAULT_FEE_BUFFER_PCT: u32 = 20;
+
+/// Fee per byte of the signed request, for nodes without a fee estimate
+pub const DEFAULT_FEE_PER_BYTE: f64 = 0.01;
+
+/// `--fee_limit` of a command, a fee limit or `auto` to estimate one for
+/// each transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L1XFeeLimit {
+    Auto,
+    Fixed(u128),
+}
+
+impl L1XFeeLimit {
+    /// Fee limit signed when nothing is estimated, or the estimate failed
+    pub fn fallback(&self) -> u128 {
+        match self {
+            L1XFeeLimit::Auto => DEFAULT_FEE_LIMIT,
+            L1XFeeLimit::Fixed(fee_limit) => *fee_limit,
+        }
+    }
+
+    /// Fee estimation of `auto` with `buffer_pct` and `fee_per_byte`, `None`
+    /// for a fixed fee limit
+    pub fn estimation(
+        &self,
+        buffer_pct: u32,
+        fee_per_byte: f64,
+    ) -> Option<FeeEstimation> {
+        match self {
+            L1XFeeLimit::Auto => {
+                Some(FeeEstimation { buffer_pct, fee_per_byte })
+            }
+            L1XFeeLimit::Fixed(_) => None,
+        }
+    }
+}
+
+impl Default for L1XFeeLimit {
+    fn default() -> Self {
+        L1XFeeLimit::Fixed(DEFAULT_FEE_LIMIT)
+    }
+}
+
+impl std::str::FromStr for L1XFeeLimit {
+    type Err = String;
+
+    fn from_str(fee_limit: &str) -> Result<Self, Self::Err> {
+        match fee_limit.trim() {
+            "auto" => Ok(L1XFeeLimit::Auto),
+            fee_limit => {
+                fee_limit.parse().map(L1XFeeLimit::Fixed).map_err(|_| {
+                    format!(
+                        "'{}' is neither `auto` nor a decimal fee limit",
+                        fee_limit
+                    )
+                })
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for L1XFeeLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            L1XFeeLimit::Auto => write!(f, "auto"),
+            L1XFeeLimit::Fixed(fee_limit) => write!(f, "{}", fee_limit),
+        }
+    }
+}
+
+/// How the fee limit of a transaction is estimated under `--fee_limit auto`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeEstimation {
+    /// Percentage added to the estimate
+    pub buffer_pct: u32,
+    /// Fee per byte of the signed request, when the node has no estimate
+    pub fee_per_byte: f64,
+}
+
+impl Default for FeeEstimation {
+    fn default() -> Self {
+        FeeEstimation {
+            buffer_pct: DEFAULT_FEE_BUFFER_PCT,
+            fee_per_byte: DEFAULT_FEE_PER_BYTE,
+        }
+    }
+}
+
+/// Where the fee limit of a transaction comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeSource {
+    /// `--fee_limit` or its default
+    Fixed,
+    /// Estimate of the node, plus the buffer
+    Node,
+    /// Size of the signed request, plus the buffer
+    PayloadSize,
+    /// `--fee_limit auto` whose estimate failed
+    Fallback,
+}
+
+/// Fee limit a transaction is signed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChosenFee {
+    pub fee_limit: u128,
+    pub source: FeeSource,
+}
+
+impl ChosenFee {
+    pub fn fixed(fee_limit: u128) -> Self {
+        ChosenFee { fee_limit, source: FeeSource::Fixed }
+    }
+}
+
+impl std::fmt::Display for ChosenFee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match self.source {
+            FeeSource::Fixed => "fixed",
+            FeeSource::Node => "node estimate",
+            FeeSource::PayloadSize => "payload size estimate",
+            FeeSource::Fallback => "fallback, the estimate failed",
+        };
+        write!(f, "{} ({})", self.fee_limit, source)
+    }
+}
+
+/// `estimate` plus `buffer_pct` percent of it, rounded up
+pub fn with_buffer(estimate: u128, buffer_pct: u32) -> u128 {
+    let buffer =
+        estimate.saturating_mul(u128::from(buffer_pct)).saturating_add(99)
+            / 100;
+
+    estimate.saturating_add(buffer)
+}
+
+/// Fee of a signed request of `payload_len` bytes at `fee_per_byte`, at
+/// least [`DEFAULT_FEE_LIMIT`]
+pub fn payload_size_estimate(payload_len: usize, fee_per_byte: f64) -> u128 {
+    let estimate = (payload_len as f64 * fee_per_byte.max(0.0)).ceil() as u128;
+
+    estimate.max(DEFAULT_FEE_LIMIT)
+}
+
+/// Fee of the result of [`ESTIMATE_FEE_METHOD`], a number or a decimal
+/// string, alone or as its `fee` or `estimated_fee`
+pub fn estimate_of_result(result: &Value) -> Option<u128> {
+    let fee_of = |value: &Value| match value {
+        Value::Number(fee) => fee.as_u64().map(u128::from),
+        Value::String(fee) => fee.parse().ok(),
+        _ => None,
+    };
+
+    fee_of(result)
+        .or_else(|| fee_of(&result["fee"]))
+        .or_else(|| fee_of(&result["estimated_fee"]))
+}
+
+/// Fee limit of a transaction of `payload_len` bytes under `estimation`,
+/// plus the buffer: the `node_estimate`, or the payload size estimate when
+/// the node has none. A failed estimate falls back to `fallback` with a
+/// warning.
+pub fn choose_fee(
+    node_estimate: Result<Option<u128>, RpcError>,
+    payload_len: usize,
+    estimation: FeeEstimation,
+    fallback: u128,
+) -> ChosenFee {
+    match node_estimate {
+        Ok(Some(estimate)) => ChosenFee {
+            fee_limit: with_buffer(estimate, estimation.buffer_pct),
+            source: FeeSource::Node,
+        },
+        Ok(None) => ChosenFee {
+            fee_limit: with_buffer(
+                payload_size_estimate(payload_len, estimation.fee_per_byte),
+                estimation.buffer_pct,
+            ),
+            source: FeeSource::PayloadSize,
+        },
+        Err(err_code) => {
+            log::warn!(
+                "Fee estimation failed, using the fee limit {} :: {}",
+                fallback,
+                err_code
+            );
+            ChosenFee { fee_limit: fallback, source: FeeSource::Fallback }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::fee::*;
+use crate::rpc::RpcError;
+
+use serde_json::json;
+
+const ESTIMATION: FeeEstimation =
+    FeeEstimation { buffer_pct: 20, fee_per_byte: 0.5 };
+
+#[test]
+fn test_with_buffer_rounds_up() {
+    assert_eq!(with_buffer(200, 20), 240);
+    assert_eq!(with_buffer(101, 10), 112);
+    assert_eq!(with_buffer(200, 0), 200);
+    assert_eq!(with_buffer(u128::MAX, 20), u128::MAX);
+}
+
+#[test]
+fn test_payload_size_estimate_is_at_least_the_default_fee_limit() {
+    assert_eq!(payload_size_estimate(1_000, 0.5), 500);
+    assert_eq!(payload_size_estimate(1_001, 0.5), 501);
+    assert_eq!(payload_size_estimate(10, 0.5), DEFAULT_FEE_LIMIT);
+}
+
+#[test]
+fn test_choose_fee_adds_the_buffer_to_the_node_estimate() {
+    let fee = choose_fee(Ok(Some(200)), 1_000, ESTIMATION, 100);
+
+    assert_eq!(fee, ChosenFee { fee_limit: 240, source: FeeSource::Node });
+}
+
+#[test]
+fn test_choose_fee_without_node_estimate_uses_the_payload_size() {
+    let fee = choose_fee(Ok(None), 1_000, ESTIMATION, 100);
+
+    assert_eq!(
+        fee,
+        ChosenFee { fee_limit: 600, source: FeeSource::PayloadSize }
+    );
+}
+
+#[test]
+fn test_choose_fee_falls_back_when_the_estimate_fails() {
+    let failure = RpcError::Transport {
+        endpoint: "http://127.0.0.1:1".to_string(),
+        reason: "connection refused".to_string(),
+    };
+
+    let fee = choose_fee(Err(failure), 1_000, ESTIMATION, 150);
+
+    assert_eq!(fee, ChosenFee { fee_limit: 150, source: FeeSource::Fallback });
+}
+
+#[test]
+fn test_fee_limit_of_the_command_line() {
+    assert_eq!("auto".parse::<L1XFeeLimit>(), Ok(L1XFeeLimit::Auto));
+    assert_eq!("250".parse::<L1XFeeLimit>(), Ok(L1XFeeLimit::Fixed(250)));
+    assert!("lots".parse::<L1XFeeLimit>().is_err());
+
+    assert_eq!(L1XFeeLimit::Auto.fallback(), DEFAULT_FEE_LIMIT);
+    assert_eq!(L1XFeeLimit::Auto.estimation(20, 0.5), Some(ESTIMATION));
+    assert_eq!(L1XFeeLimit::Fixed(250).estimation(20, 0.5), None);
+}
+
+#[test]
+fn test_estimate_of_result() {
+    assert_eq!(estimate_of_result(&json!(200)), Some(200));
+    assert_eq!(estimate_of_result(&json!("200")), Some(200));
+    assert_eq!(estimate_of_result(&json!({ "fee": "200" })), Some(200));
+    assert_eq!(estimate_of_result(&json!({ "estimated_fee": 7 })), Some(7));
+    assert_eq!(estimate_of_result(&json!({ "gas": 7 })), None);
+}
+
+#[test]
+fn test_chosen_fee_json() {
+    assert_eq!(
+        serde_json::to_value(ChosenFee {
+            fee_limit: 240,
+            source: FeeSource::PayloadSize
+        })
+        .unwrap(),
+        json!({ "fee_limit": 240, "source": "payload_size" })
+    );
+}
@@ -1,4 +1,4 @@
-use anyhow::{anyhow, Context, Result};
+use anyhow::{Context, Result};
 use l1x_rpc::rpc_model::SubmitTransactionRequest;
 use libp2p::PeerId;
 use primitives::*;
@@ -7,16 +7,118 @@ use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fs::File;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 
 mod account;
+pub mod call_args;
+#[cfg(test)]
+mod call_args_tests;
+pub mod fee;
+#[cfg(test)]
+mod fee_tests;
 // mod json;
+pub mod message;
+#[cfg(test)]
+mod message_tests;
+pub mod nonce_manager;
+#[cfg(test)]
+mod nonce_manager_tests;
+pub mod payload_schema;
+#[cfg(test)]
+mod payload_schema_tests;
 mod primitives;
+pub mod rpc;
+#[cfg(test)]
+mod rpc_tests;
 pub mod toolkit_config;
+#[cfg(test)]
+mod toolkit_config_tests;
+pub mod transport;
+#[cfg(test)]
+mod transport_tests;
+#[cfg(test)]
+mod txn_build_tests;
+pub mod txn_builder;
+#[cfg(test)]
+mod txn_builder_tests;
 pub mod types;
+#[cfg(test)]
+mod types_tests;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TransactionTypeNativeTX {
-    NativeTokenTransfer(Address, String),
+    NativeTokenTransfer(Address, #[serde(with = "native_amount")] Balance),
+}
+
+/// Parse the decimal native token amount `amount`, digits only without
+/// leading zeros, so the amount signed is the amount written
+pub fn parse_native_amount(amount: &str) -> Result<Balance, TxnBuildError> {
+    let invalid = |reason: &str| {
+        TxnBuildError::InvalidAmount(format!("'{}' {}", amount, reason))
+    };
+
+    if amount.is_empty() {
+        return Err(invalid("is empty"));
+    }
+    if !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(invalid("is not a decimal number of digits only"));
+    }
+    if amount.len() > 1 && amount.starts_with('0') {
+        return Err(invalid("has leading zeros"));
+    }
+
+    amount.parse().map_err(|_| invalid("overflows a 128 bit balance"))
+}
+
+/// The amount of a [`TransactionTypeNativeTX`] is signed as a decimal
+/// string, payloads recorded with a string or a number amount deserialize
+mod native_amount {
+    use super::{parse_native_amount, Balance};
+    use serde::{de, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        amount: &Balance,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&amount.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Balance, D::Error> {
+        deserializer.deserialize_any(AmountVisitor)
+    }
+
+    struct AmountVisitor;
+
+    impl<'de> de::Visitor<'de> for AmountVisitor {
+        type Value = Balance;
+
+        fn expecting(
+            &self,
+            formatter: &mut std::fmt::Formatter,
+        ) -> std::fmt::Result {
+            formatter.write_str("a native token amount, as a string or number")
+        }
+
+        fn visit_str<E: de::Error>(self, amount: &str) -> Result<Balance, E> {
+            parse_native_amount(amount).map_err(E::custom)
+        }
+
+        fn visit_u64<E: de::Error>(self, amount: u64) -> Result<Balance, E> {
+            Ok(amount.into())
+        }
+
+        fn visit_u128<E: de::Error>(self, amount: u128) -> Result<Balance, E> {
+            Ok(amount)
+        }
+
+        fn visit_i64<E: de::Error>(self, amount: i64) -> Result<Balance, E> {
+            Balance::try_from(amount).map_err(|_| {
+                E::custom(format!("amount {} is negative", amount))
+            })
+        }
+    }
 }
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NativeTokenTransferPayload {
@@ -25,132 +127,670 @@ pub struct NativeTokenTransferPayload {
     pub fee_limit: Balance,
 }
 
-/// Functionality to both json and grpc clis
+/// How a transaction signature is computed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SigningScheme {
+    /// Every transaction type is signed by `l1x_rpc::sign`. The default,
+    /// native token transfers used to be signed over the legacy payload:
+    /// nodes predating it need [`SigningScheme::LegacyNativeTransfer`] set
+    /// explicitly, e.g. with `--legacy-native-signing`
+    #[default]
+    Canonical,
+    /// Native token transfers are signed over a [`NativeTokenTransferPayload`]
+    /// with a stringified amount, as expected by older nodes. Every other
+    /// transaction type is signed as [`SigningScheme::Canonical`]
+    LegacyNativeTransfer,
+}
+
+/// Failure to build or sign a transaction request
+#[derive(Debug, thiserror::Error)]
+pub enum TxnBuildError {
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error(
+        "Failed to deserialize {format} transaction payload {path} :: {source}"
+    )]
+    PayloadDeserialize {
+        path: String,
+        format: PayloadFormat,
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
+    #[error("Invalid {format} transaction payload {path} :: {source}")]
+    PayloadSchema {
+        path: String,
+        format: PayloadFormat,
+        #[source]
+        source: payload_schema::PayloadSchemaError,
+    },
+    #[error("Invalid transaction payload: {0}")]
+    TypeConversion(String),
+    #[error("Invalid transaction payload: {0}")]
+    PayloadValue(#[from] types::U8sError),
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("Incomplete transaction: {0}")]
+    Incomplete(String),
+    #[error("Failed to sign the transaction: {0}")]
+    Signing(#[from] secp256k1::Error),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Unable to read {path} :: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Format of a transaction payload file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl PayloadFormat {
+    /// Format of the payload file `path` by its extension, JSON when the
+    /// extension is unknown
+    pub fn of_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml") | Some("yml") => PayloadFormat::Yaml,
+            Some("toml") => PayloadFormat::Toml,
+            _ => PayloadFormat::Json,
+        }
+    }
+}
+
+impl std::fmt::Display for PayloadFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayloadFormat::Json => write!(f, "JSON"),
+            PayloadFormat::Yaml => write!(f, "YAML"),
+            PayloadFormat::Toml => write!(f, "TOML"),
+        }
+    }
+}
+
+/// Functionality to both json and grpc clis, `payload_file_path` of `-`
+/// reading the payload from stdin. The payload is parsed as `format`, or by
+/// the extension of `payload_file_path` when unset
 pub fn load_submit_txn_req(
     payload_file_path: &str,
+    format: Option<PayloadFormat>,
     private_key: &str,
     fee_limit: Balance,
     nonce: Nonce,
-) -> Result<SubmitTransactionRequest, Box<dyn Error>> {
-    let mut file = File::open(payload_file_path)
-        .with_context(|| "Failed to open payload file")?;
-    let mut file_content = String::new();
-    file.read_to_string(&mut file_content)
-        .with_context(|| "Failed to read the file")?;
-    let txn: types::Transaction = serde_json::from_str(&file_content)
-        .with_context(|| "Failed to deserialize transaction payload")?;
+) -> Result<SubmitTransactionRequest, TxnBuildError> {
+    let txn = load_transaction_payload(payload_file_path, format)?;
 
-    get_submit_txn_req(txn, private_key, fee_limit, nonce)
+    get_submit_txn_req(
+        txn,
+        private_key,
+        fee_limit,
+        nonce,
+        SigningScheme::Canonical,
+    )
 }
 
+/// Transaction of the payload file `payload_file_path` as
+/// [`load_submit_txn_req`] reads it, without signing it
+pub fn load_transaction_payload(
+    payload_file_path: &str,
+    format: Option<PayloadFormat>,
+) -> Result<types::Transaction, TxnBuildError> {
+    let source = PayloadSource::from_arg(payload_file_path);
+    let format = format.unwrap_or_else(|| match &source {
+        PayloadSource::Path(path) => PayloadFormat::of_path(path),
+        PayloadSource::Stdin => PayloadFormat::Json,
+    });
+    let file_content = read_payload(source.clone())?;
+
+    parse_transaction_payload(
+        &source.to_string(),
+        format,
+        &file_content,
+        &source.base_dir(),
+    )
+}
+
+/// Parse a transaction payload and check it converts to an RPC transaction
+pub fn validate_transaction_payload(
+    payload: &str,
+) -> Result<types::Transaction, TxnBuildError> {
+    parse_transaction_payload(
+        "<payload>",
+        PayloadFormat::Json,
+        payload,
+        Path::new(""),
+    )
+}
+
+/// [`validate_transaction_payload`] of the content of the file `path` in
+/// `format`, its relative file values resolved against `base_dir`. The
+/// payload is checked against [`payload_schema::transaction_schema`] first,
+/// for errors pointing at the offending value.
+fn parse_transaction_payload(
+    path: &str,
+    format: PayloadFormat,
+    payload: &str,
+    base_dir: &Path,
+) -> Result<types::Transaction, TxnBuildError> {
+    let value: Result<serde_json::Value, Box<dyn Error + Send + Sync>> =
+        match format {
+            PayloadFormat::Json => {
+                serde_json::from_str(payload).map_err(Into::into)
+            }
+            PayloadFormat::Yaml => {
+                serde_yaml::from_str(payload).map_err(Into::into)
+            }
+            PayloadFormat::Toml => toml::from_str(payload).map_err(Into::into),
+        };
+    let value = value.map_err(|source| TxnBuildError::PayloadDeserialize {
+        path: path.to_string(),
+        format,
+        source,
+    })?;
+    payload_schema::validate_transaction(&value).map_err(|source| {
+        TxnBuildError::PayloadSchema { path: path.to_string(), format, source }
+    })?;
+
+    // Deserialized from the payload itself rather than `value`, which loses
+    // the precision of amounts beyond u64
+    let parsed: Result<types::Transaction, Box<dyn Error + Send + Sync>> =
+        match format {
+            PayloadFormat::Json => {
+                serde_json::from_str(payload).map_err(Into::into)
+            }
+            PayloadFormat::Yaml => {
+                serde_yaml::from_str(payload).map_err(Into::into)
+            }
+            PayloadFormat::Toml => toml::from_str(payload).map_err(Into::into),
+        };
+    let txn = parsed
+        .map_err(|source| TxnBuildError::PayloadDeserialize {
+            path: path.to_string(),
+            format,
+            source,
+        })?
+        .relative_to(base_dir);
+
+    l1x_rpc::rpc_model::submit_transaction_request::TransactionType::try_from(
+        txn.clone(),
+    )
+    .map_err(conversion_error)?;
+
+    Ok(txn)
+}
+
+/// [`TxnBuildError`] of a transaction that doesn't convert to an RPC
+/// transaction
+fn conversion_error(err_code: anyhow::Error) -> TxnBuildError {
+    match err_code.downcast::<types::U8sError>() {
+        Ok(err_code) => TxnBuildError::PayloadValue(err_code),
+        Err(err_code) => TxnBuildError::TypeConversion(err_code.to_string()),
+    }
+}
+
+/// Secret key of the hex encoded `private_key`, `0x` prefixed or not.
+/// Errors describe what is wrong without repeating the key.
+pub fn parse_secret_key(private_key: &str) -> Result<SecretKey, TxnBuildError> {
+    let private_key = private_key.trim();
+    let hex_key = private_key
+        .strip_prefix("0x")
+        .or_else(|| private_key.strip_prefix("0X"))
+        .unwrap_or(private_key);
+
+    if let Some(position) =
+        hex_key.chars().position(|character| !character.is_ascii_hexdigit())
+    {
+        return Err(TxnBuildError::InvalidPrivateKey(format!(
+            "character {} is not hex",
+            position
+        )));
+    }
+    if hex_key.len() != 64 {
+        return Err(TxnBuildError::InvalidPrivateKey(format!(
+            "expected 32 bytes (64 hex chars), got {} hex chars",
+            hex_key.len()
+        )));
+    }
+
+    let mut key_bytes = [0u8; 32];
+    hex::decode_to_slice(hex_key, &mut key_bytes)
+        .expect("64 hex digits decode to 32 bytes");
+    SecretKey::from_slice(&key_bytes).map_err(|_| {
+        TxnBuildError::InvalidPrivateKey(
+            "not a secp256k1 secret key, it must be non-zero and below the curve order"
+                .to_string(),
+        )
+    })
+}
+
+/// Transaction request of `txn` signed by `private_key` under `scheme`
 pub fn get_submit_txn_req(
     txn: types::Transaction,
     private_key: &str,
     fee_limit: Balance,
     nonce: Nonce,
-) -> Result<SubmitTransactionRequest, Box<dyn Error>> {
-    let secret_key = SecretKey::from_slice(&hex::decode(private_key)?)
-        .with_context(|| "Failed to parse provided private_key")?;
+    scheme: SigningScheme,
+) -> Result<SubmitTransactionRequest, TxnBuildError> {
+    signed_submit_txn_req(
+        txn,
+        &parse_secret_key(private_key)?,
+        fee_limit,
+        nonce,
+        scheme,
+    )
+}
+
+/// [`get_submit_txn_req`] of an already parsed `secret_key`
+pub(crate) fn signed_submit_txn_req(
+    txn: types::Transaction,
+    secret_key: &SecretKey,
+    fee_limit: Balance,
+    nonce: Nonce,
+    scheme: SigningScheme,
+) -> Result<SubmitTransactionRequest, TxnBuildError> {
     let secp = Secp256k1::new();
     let verifying_key = secret_key.public_key(&secp);
 
     let txn_type: l1x_rpc::rpc_model::submit_transaction_request::TransactionType =
-        txn.clone().try_into()?;
+        txn.try_into().map_err(conversion_error)?;
 
-    let is_native_token_transfer = match txn_type {
-        l1x_rpc::rpc_model::submit_transaction_request::TransactionType::NativeTokenTransfer(_) => {
-            true
-        }
-        _ => false,
+    Ok(SubmitTransactionRequest {
+        nonce: nonce.to_string(),
+        fee_limit: fee_limit.to_string(), // FIXME,
+        signature: sign_txn(secret_key, &txn_type, fee_limit, nonce, scheme)?,
+        verifying_key: verifying_key.serialize().to_vec(),
+        transaction_type: Some(txn_type),
+    })
+}
+
+/// Payload hashed by `l1x_rpc::sign`
+#[derive(Serialize)]
+struct TxnSignPayload<'a> {
+    nonce: Nonce,
+    transaction_type:
+        &'a l1x_rpc::rpc_model::submit_transaction_request::TransactionType,
+    fee_limit: Balance,
+}
+
+/// Payload a native token transfer is signed over under
+/// [`SigningScheme::LegacyNativeTransfer`], returns `None` for every other
+/// transaction type or scheme
+fn legacy_native_transfer_payload(
+    txn_type: &l1x_rpc::rpc_model::submit_transaction_request::TransactionType,
+    fee_limit: Balance,
+    nonce: Nonce,
+    scheme: SigningScheme,
+) -> Result<Option<NativeTokenTransferPayload>, TxnBuildError> {
+    let l1x_rpc::rpc_model::submit_transaction_request::TransactionType::NativeTokenTransfer(
+        l1x_rpc::rpc_model::NativeTokenTransfer { address, amount },
+    ) = txn_type
+    else {
+        return Ok(None);
     };
+    if scheme != SigningScheme::LegacyNativeTransfer {
+        return Ok(None);
+    }
 
-    // TODO: Refactor this
-    if is_native_token_transfer {
-        let txn_type2 = txn_type.clone();
-        let native_token = match txn_type {
-            l1x_rpc::rpc_model::submit_transaction_request::TransactionType::NativeTokenTransfer(l1x_rpc::rpc_model::NativeTokenTransfer { address, amount }) => {
-                TransactionTypeNativeTX::NativeTokenTransfer(address.try_into().map_err(|_| anyhow::anyhow!("Failed to convert NativeTokenAddress Address vec<u8> to array"))?, amount.to_string())
-            }
+    let address: Address = address.clone().try_into().map_err(|_| {
+        TxnBuildError::TypeConversion(
+            "Failed to convert NativeTokenAddress Address vec<u8> to array"
+                .to_string(),
+        )
+    })?;
 
-            _ => TransactionTypeNativeTX::NativeTokenTransfer(Address::default(), "0".to_string()),
-        };
+    Ok(Some(NativeTokenTransferPayload {
+        nonce,
+        transaction_type: TransactionTypeNativeTX::NativeTokenTransfer(
+            address,
+            parse_native_amount(amount)?,
+        ),
+        fee_limit,
+    }))
+}
 
-        let obj = NativeTokenTransferPayload {
+/// The message a transaction signature is computed over under `scheme`
+pub fn signing_message(
+    txn_type: &l1x_rpc::rpc_model::submit_transaction_request::TransactionType,
+    fee_limit: Balance,
+    nonce: Nonce,
+    scheme: SigningScheme,
+) -> Result<Message, TxnBuildError> {
+    let json_str = match legacy_native_transfer_payload(
+        txn_type, fee_limit, nonce, scheme,
+    )? {
+        Some(obj) => serde_json::to_string(&obj),
+        None => serde_json::to_string(&TxnSignPayload {
             nonce,
-            transaction_type: native_token,
+            transaction_type: txn_type,
             fee_limit,
-        };
-        println!("is_native_token_transfer: {:?}", is_native_token_transfer);
-        let json_str = serde_json::to_string(&obj)?;
-        let message =
-            Message::from_hashed_data::<sha256::Hash>(json_str.as_bytes());
-        let sig = secret_key.sign_ecdsa(message);
-
-        Ok(SubmitTransactionRequest {
-            nonce: nonce.to_string(),
-            fee_limit: fee_limit.to_string(), // FIXME,
-            signature: sig.serialize_compact().to_vec(),
-            verifying_key: verifying_key.serialize().to_vec(),
-            transaction_type: Some(txn_type2),
-        })
-    } else {
-        Ok(SubmitTransactionRequest {
-            nonce: nonce.to_string(),
-            fee_limit: fee_limit.to_string(), // FIXME,
-            signature: l1x_rpc::sign(
-                secret_key,
-                txn_type.clone(),
-                fee_limit,
-                nonce,
-            )?,
-            verifying_key: verifying_key.serialize().to_vec(),
-            transaction_type: Some(txn_type),
-        })
+        }),
     }
+    .map_err(|err_code| TxnBuildError::TypeConversion(err_code.to_string()))?;
+
+    Ok(Message::from_hashed_data::<sha256::Hash>(json_str.as_bytes()))
 }
 
-pub fn secp256k1_creds(
+/// Sign a transaction, every signer of the same transaction, fee limit and
+/// nonce signs identical bytes.
+pub fn sign_txn(
+    secret_key: &SecretKey,
+    txn_type: &l1x_rpc::rpc_model::submit_transaction_request::TransactionType,
+    fee_limit: Balance,
+    nonce: Nonce,
+    scheme: SigningScheme,
+) -> Result<Vec<u8>, TxnBuildError> {
+    if legacy_native_transfer_payload(txn_type, fee_limit, nonce, scheme)?
+        .is_some()
+    {
+        let message = signing_message(txn_type, fee_limit, nonce, scheme)?;
+        return Ok(secret_key.sign_ecdsa(message).serialize_compact().to_vec());
+    }
+
+    l1x_rpc::sign(*secret_key, txn_type.clone(), fee_limit, nonce).map_err(
+        |err_code| match err_code.downcast::<secp256k1::Error>() {
+            Ok(err_code) => TxnBuildError::Signing(err_code),
+            Err(err_code) => {
+                TxnBuildError::TypeConversion(err_code.to_string())
+            }
+        },
+    )
+}
+
+/// Check a compact signature produced by [`sign_txn`]
+pub fn verify_txn_signature(
+    verifying_key: &[u8],
+    signature: &[u8],
+    txn_type: &l1x_rpc::rpc_model::submit_transaction_request::TransactionType,
+    fee_limit: Balance,
+    nonce: Nonce,
+    scheme: SigningScheme,
+) -> Result<bool, Box<dyn Error>> {
+    let verifying_key = secp256k1::PublicKey::from_slice(verifying_key)
+        .with_context(|| "Failed to parse verifying_key")?;
+    let signature = secp256k1::ecdsa::Signature::from_compact(signature)
+        .with_context(|| "Failed to parse compact signature")?;
+    let message = signing_message(txn_type, fee_limit, nonce, scheme)?;
+
+    Ok(Secp256k1::verification_only()
+        .verify_ecdsa(&message, &signature, &verifying_key)
+        .is_ok())
+}
+
+/// Check the signature of `request` against its own verifying key, the way
+/// the node does, and return the address of the signer. Native transfers
+/// signed under either [`SigningScheme`] verify.
+pub fn verify_submit_txn_req(
+    request: &SubmitTransactionRequest,
+) -> Result<Address, TxnBuildError> {
+    let txn_type = request.transaction_type.as_ref().ok_or_else(|| {
+        TxnBuildError::Incomplete("the request has no transaction".to_string())
+    })?;
+    let fee_limit: Balance = request.fee_limit.parse().map_err(|_| {
+        TxnBuildError::TypeConversion(format!(
+            "fee_limit '{}' is not a number",
+            request.fee_limit
+        ))
+    })?;
+    let nonce: Nonce = request.nonce.parse().map_err(|_| {
+        TxnBuildError::TypeConversion(format!(
+            "nonce '{}' is not a number",
+            request.nonce
+        ))
+    })?;
+    let verifying_key = secp256k1::PublicKey::from_slice(
+        &request.verifying_key,
+    )
+    .map_err(|err_code| {
+        TxnBuildError::InvalidSignature(format!(
+            "verifying_key is not a secp256k1 public key :: {}",
+            err_code
+        ))
+    })?;
+    let signature = secp256k1::ecdsa::Signature::from_compact(
+        &request.signature,
+    )
+    .map_err(|err_code| {
+        TxnBuildError::InvalidSignature(format!(
+            "signature is not a compact ECDSA signature :: {}",
+            err_code
+        ))
+    })?;
+
+    let verifier = Secp256k1::verification_only();
+    for scheme in
+        [SigningScheme::Canonical, SigningScheme::LegacyNativeTransfer]
+    {
+        let message = signing_message(txn_type, fee_limit, nonce, scheme)?;
+        if verifier.verify_ecdsa(&message, &signature, &verifying_key).is_ok() {
+            return account_address(&verifying_key.serialize()).map_err(
+                |err_code| {
+                    TxnBuildError::InvalidSignature(err_code.to_string())
+                },
+            );
+        }
+    }
+
+    Err(TxnBuildError::InvalidSignature(
+        "the signature does not match the transaction, nonce and fee_limit under any signing scheme".to_string(),
+    ))
+}
+
+/// Account address of a compressed or uncompressed secp256k1 verifying key
+pub fn account_address(verifying_key: &[u8]) -> Result<[u8; 20]> {
+    account::Account::address(&verifying_key.to_vec())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CredsError {
+    #[error("Invalid key hex: {0}")]
+    InvalidHex(#[from] hex::FromHexError),
+    #[error("Invalid secp256k1 key: {0}")]
+    InvalidKey(String),
+}
+
+/// Address of the contract `account_address` deploys to `cluster_address`
+/// with the transaction nonce `nonce`
+pub fn contract_address(
+    account_address: &[u8; 20],
+    cluster_address: &[u8; 20],
+    nonce: Nonce,
+) -> [u8; 20] {
+    account::Account::contract_address(account_address, cluster_address, nonce)
+}
+
+/// Address of the instance of `contract_address` `account_address`
+/// initializes on `cluster_address` with the transaction nonce `nonce`
+pub fn contract_instance_address(
+    account_address: &[u8; 20],
+    contract_address: &[u8; 20],
+    cluster_address: &[u8; 20],
+    nonce: Nonce,
+) -> [u8; 20] {
+    account::Account::contract_instance_address(
+        account_address,
+        contract_address,
+        cluster_address,
+        nonce,
+    )
+}
+
+/// Address of the staking pool `account_address` creates on
+/// `cluster_address` with the transaction nonce `nonce`
+pub fn pool_address(
+    account_address: &[u8; 20],
+    cluster_address: &[u8; 20],
+    nonce: Nonce,
+) -> [u8; 20] {
+    account::Account::pool_address(account_address, cluster_address, nonce)
+}
+
+/// Signature scheme of a libp2p node identity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyScheme {
+    Secp256k1,
+    Ed25519,
+}
+
+/// Hex encoded keys and `PeerId` of a node identity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeKeys {
+    pub privkey: String,
+    /// Compressed secp256k1 or raw ed25519 public key
+    pub pubkey: String,
+    pub peer_id: PeerId,
+}
+
+/// Keys of the hex encoded `privkey` of `scheme`, or of a newly generated key
+pub fn generate_node_keys(
+    scheme: KeyScheme,
     privkey: Option<String>,
-) -> Result<(String, String, PeerId), Box<dyn Error>> {
-    let (keypair, privkey) = match privkey {
-        Some(privkey) => {
-            let mut keypair_bytes: Vec<u8> = hex::decode(&privkey)?;
+) -> Result<NodeKeys, CredsError> {
+    let invalid_key =
+        |err: &dyn std::fmt::Display| CredsError::InvalidKey(err.to_string());
 
-            let keypair = libp2p::identity::secp256k1::SecretKey::from_bytes(
-                &mut keypair_bytes,
-            )
-            .map(|sk| {
-                libp2p::identity::Keypair::Secp256k1(
-                    libp2p::identity::secp256k1::Keypair::from(sk),
+    let keypair: libp2p::identity::Keypair = match (scheme, privkey) {
+        (KeyScheme::Secp256k1, Some(privkey)) => {
+            let secret_key =
+                libp2p::identity::secp256k1::SecretKey::try_from_bytes(
+                    hex::decode(privkey)?,
+                )
+                .map_err(|err| invalid_key(&err))?;
+            libp2p::identity::secp256k1::Keypair::from(secret_key).into()
+        }
+        (KeyScheme::Secp256k1, None) => {
+            libp2p::identity::Keypair::generate_secp256k1()
+        }
+        (KeyScheme::Ed25519, Some(privkey)) => {
+            let secret_key =
+                libp2p::identity::ed25519::SecretKey::try_from_bytes(
+                    hex::decode(privkey)?,
                 )
-            })?;
-            (keypair, privkey)
+                .map_err(|err| invalid_key(&err))?;
+            libp2p::identity::ed25519::Keypair::from(secret_key).into()
         }
-        None => {
-            let keypair = libp2p::identity::Keypair::generate_secp256k1();
-            let privkey_bytes =
-                keypair.clone().try_into_secp256k1()?.secret().to_bytes();
-            let privkey = hex::encode(privkey_bytes);
-            (keypair, privkey)
+        (KeyScheme::Ed25519, None) => {
+            libp2p::identity::Keypair::generate_ed25519()
         }
     };
 
-    let pubkey = match keypair.public() {
-        libp2p::identity::PublicKey::Secp256k1(pubkey) => pubkey,
-        _ => return Err(anyhow!("Invalid key").into()),
+    let (privkey, pubkey) = match scheme {
+        KeyScheme::Secp256k1 => {
+            let keypair = keypair
+                .clone()
+                .try_into_secp256k1()
+                .map_err(|err| invalid_key(&err))?;
+            (
+                hex::encode(keypair.secret().to_bytes()),
+                hex::encode(keypair.public().to_bytes()),
+            )
+        }
+        KeyScheme::Ed25519 => {
+            let keypair = keypair
+                .clone()
+                .try_into_ed25519()
+                .map_err(|err| invalid_key(&err))?;
+            (
+                hex::encode(keypair.secret()),
+                hex::encode(keypair.public().to_bytes()),
+            )
+        }
     };
-    let pubkey_bytes = pubkey.to_bytes().to_vec();
-    let pubkey = hex::encode(&pubkey_bytes);
-    let peer_id = keypair.public().to_peer_id();
 
-    Ok((privkey, pubkey, peer_id))
+    Ok(NodeKeys { privkey, pubkey, peer_id: keypair.public().to_peer_id() })
+}
+
+/// Hex private key, hex compressed public key and libp2p `PeerId` of the
+/// secp256k1 key `privkey`, or of a newly generated key
+pub fn secp256k1_creds(
+    privkey: Option<String>,
+) -> Result<(String, String, PeerId), CredsError> {
+    let keys = generate_node_keys(KeyScheme::Secp256k1, privkey)?;
+
+    Ok((keys.privkey, keys.pubkey, keys.peer_id))
+}
+
+/// libp2p `PeerId` of a compressed or uncompressed secp256k1 public key
+pub fn peer_id_from_pub_key(pubkey: &[u8]) -> Result<PeerId, CredsError> {
+    let compressed = secp256k1::PublicKey::from_slice(pubkey)
+        .map_err(|err| CredsError::InvalidKey(err.to_string()))?
+        .serialize();
+    let pubkey =
+        libp2p::identity::secp256k1::PublicKey::try_from_bytes(&compressed)
+            .map_err(|err| CredsError::InvalidKey(err.to_string()))?;
+
+    Ok(libp2p::identity::PublicKey::from(pubkey).to_peer_id())
+}
+
+/// Where a transaction payload is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadSource {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl PayloadSource {
+    /// Source of a command line payload argument, `-` meaning stdin
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => PayloadSource::Stdin,
+            path => PayloadSource::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// Directory the relative file values of the payload are read from, the
+    /// directory of the payload file or the working directory for stdin
+    pub fn base_dir(&self) -> PathBuf {
+        match self {
+            PayloadSource::Path(path) => {
+                path.parent().map(Path::to_path_buf).unwrap_or_default()
+            }
+            PayloadSource::Stdin => PathBuf::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for PayloadSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayloadSource::Path(path) => write!(f, "{}", path.display()),
+            PayloadSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Content of the payload `source`
+pub fn read_payload(source: PayloadSource) -> Result<String, TxnBuildError> {
+    read_payload_from(source, std::io::stdin().lock())
 }
 
-pub fn read_file(payload_file_path: String) -> String {
-    let mut file =
-        File::open(payload_file_path).expect("Failed to open payload file");
-    let mut file_content = String::new();
-    file.read_to_string(&mut file_content).expect("Failed to read the file");
+/// [`read_payload`] with `stdin` standing in for the process stdin
+pub(crate) fn read_payload_from(
+    source: PayloadSource,
+    mut stdin: impl Read,
+) -> Result<String, TxnBuildError> {
+    let io_err = |source_err| TxnBuildError::Io {
+        path: source.to_string(),
+        source: source_err,
+    };
+
+    let mut content = String::new();
+    match &source {
+        PayloadSource::Path(path) => {
+            File::open(path)
+                .and_then(|mut file| file.read_to_string(&mut content))
+                .map_err(io_err)?;
+        }
+        PayloadSource::Stdin => {
+            stdin.read_to_string(&mut content).map_err(io_err)?;
+        }
+    }
 
-    file_content
+    Ok(content)
 }
@@ -0,0 +1,66 @@
+use anyhow::{anyhow, Result};
+use secp256k1::{
+    ecdsa::Signature, hashes::sha256, Message, PublicKey, Secp256k1, SecretKey,
+};
+
+/// Message signed for `data`, the sha256 digest transactions are signed over
+pub fn message_hash(data: &[u8]) -> Message {
+    Message::from_hashed_data::<sha256::Hash>(data)
+}
+
+/// Compact signature of the sha256 digest of `data`
+pub fn sign_message(secret_key: &SecretKey, data: &[u8]) -> [u8; 64] {
+    secret_key.sign_ecdsa(message_hash(data)).serialize_compact()
+}
+
+/// Check the compact `signature` of `data` against a compressed or
+/// uncompressed `verifying_key`
+pub fn verify_message(
+    verifying_key: &[u8],
+    data: &[u8],
+    signature: &[u8],
+) -> Result<bool> {
+    let verifying_key = PublicKey::from_slice(verifying_key)
+        .map_err(|err| anyhow!("Invalid verifying key: {}", err))?;
+    let signature = Signature::from_compact(signature)
+        .map_err(|err| anyhow!("Invalid compact signature: {}", err))?;
+
+    Ok(Secp256k1::verification_only()
+        .verify_ecdsa(&message_hash(data), &signature, &verifying_key)
+        .is_ok())
+}
+
+/// Check the compact `signature` of `data` was made by the key of the
+/// account `address`. A compact signature carries no recovery id, so both
+/// candidate verifying keys are recovered and compared.
+pub fn verify_message_from_address(
+    address: &[u8; 20],
+    data: &[u8],
+    signature: &[u8],
+) -> Result<bool> {
+    let k256_signature = k256::ecdsa::Signature::from_slice(signature)
+        .map_err(|err| anyhow!("Invalid compact signature: {}", err))?;
+    let digest = message_hash(data);
+
+    for recovery_id in 0..2 {
+        let recovery_id = k256::ecdsa::RecoveryId::from_byte(recovery_id)
+            .expect("0 and 1 are valid recovery ids");
+        let verifying_key =
+            match k256::ecdsa::VerifyingKey::recover_from_prehash(
+                digest.as_ref(),
+                &k256_signature,
+                recovery_id,
+            ) {
+                Ok(verifying_key) => verifying_key,
+                Err(_) => continue,
+            };
+
+        let verifying_key_bytes =
+            verifying_key.to_encoded_point(true).as_bytes().to_vec();
+        if &crate::account_address(&verifying_key_bytes)? == address {
+            return verify_message(&verifying_key_bytes, data, signature);
+        }
+    }
+
+    Ok(false)
+}
@@ -0,0 +1,78 @@
+use crate::message::*;
+
+use secp256k1::{Secp256k1, SecretKey};
+
+// RFC 6979 signature of `MESSAGE` by the secp256k1 key 1, as k256 computes it
+const MESSAGE: &[u8] = b"hello l1x";
+const SIGNATURE_ONE: &str = "49166d26a8c1cbcfe3e5f92d3b2e982e481e4745b38cd073349a40c6f027ee8b6c35a9ddb2a43f29d9a82d385a40e4c65c27062fc3be9ab65a29b928c7981d88";
+const COMPRESSED_PUB_KEY_ONE: &str =
+    "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const ADDRESS_ONE: &str = "7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+const ADDRESS_TWO: &str = "2b5ad5c4795c026514f8317c7a215e218dccd6cf";
+
+fn secret_key(last_byte: u8) -> SecretKey {
+    let mut key_bytes = [0u8; 32];
+    key_bytes[31] = last_byte;
+    SecretKey::from_slice(&key_bytes).unwrap()
+}
+
+fn address(address: &str) -> [u8; 20] {
+    hex::decode(address).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn test_sign_message_vector() {
+    let signature = sign_message(&secret_key(1), MESSAGE);
+
+    assert_eq!(hex::encode(signature), SIGNATURE_ONE);
+}
+
+#[test]
+fn test_verify_message_with_compressed_and_uncompressed_key() {
+    // Arrange
+    let signature = hex::decode(SIGNATURE_ONE).unwrap();
+    let pub_key = secret_key(1).public_key(&Secp256k1::new());
+
+    // Act
+    let compressed = verify_message(
+        &hex::decode(COMPRESSED_PUB_KEY_ONE).unwrap(),
+        MESSAGE,
+        &signature,
+    );
+    let uncompressed =
+        verify_message(&pub_key.serialize_uncompressed(), MESSAGE, &signature);
+    let tampered = verify_message(
+        &hex::decode(COMPRESSED_PUB_KEY_ONE).unwrap(),
+        b"hello l1y",
+        &signature,
+    );
+
+    // Assert
+    assert!(compressed.unwrap());
+    assert!(uncompressed.unwrap());
+    assert!(!tampered.unwrap());
+}
+
+#[test]
+fn test_verify_message_from_address() {
+    let signature = hex::decode(SIGNATURE_ONE).unwrap();
+
+    let signer =
+        verify_message_from_address(&address(ADDRESS_ONE), MESSAGE, &signature);
+    let other =
+        verify_message_from_address(&address(ADDRESS_TWO), MESSAGE, &signature);
+
+    assert!(signer.unwrap());
+    assert!(!other.unwrap());
+}
+
+#[test]
+fn test_verify_message_rejects_malformed_signature() {
+    let result = verify_message(
+        &hex::decode(COMPRESSED_PUB_KEY_ONE).unwrap(),
+        MESSAGE,
+        &[0u8; 10],
+    );
+
+    assert!(result.is_err());
+}
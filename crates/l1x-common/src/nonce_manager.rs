@@ -0,0 +1,103 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{Mutex as AsyncMutex, MutexGuard};
+
+/// Nonce the next transaction of an account must carry, when the node
+/// reports `account_nonce` in its account state
+pub fn next_nonce(account_nonce: u128) -> u128 {
+    account_nonce + 1
+}
+
+/// Hands out the transaction nonces of one account to the concurrent
+/// submissions of this process. The nonce is fetched from the node once,
+/// then each accepted submission advances it by one.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    /// Nonce of the next submission, `None` until fetched or after a re-sync
+    next: AsyncMutex<Option<u128>>,
+}
+
+/// Nonce reserved for one submission. Other submissions of the account wait
+/// until it is committed, re-synced or dropped; a dropped reservation leaves
+/// its nonce to the next one.
+#[derive(Debug)]
+pub struct NonceReservation<'a> {
+    nonce: u128,
+    next: MutexGuard<'a, Option<u128>>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve the next nonce, fetched with `fetch_next` when it isn't known
+    pub async fn reserve<F, Fut, E>(
+        &self,
+        fetch_next: F,
+    ) -> Result<NonceReservation<'_>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<u128, E>>,
+    {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => {
+                let nonce = fetch_next().await?;
+                *next = Some(nonce);
+                nonce
+            }
+        };
+
+        Ok(NonceReservation { nonce, next })
+    }
+}
+
+impl NonceReservation<'_> {
+    pub fn nonce(&self) -> u128 {
+        self.nonce
+    }
+
+    /// The node accepted the transaction, the next reservation gets the
+    /// following nonce
+    pub fn commit(mut self) {
+        *self.next = Some(self.nonce + 1);
+    }
+
+    /// The node rejected the nonce, the next reservation fetches it again
+    pub fn resync(mut self) {
+        *self.next = None;
+    }
+}
+
+/// [`NonceManager`] of every account submitting from this process
+#[derive(Debug, Default)]
+pub struct NonceManagers {
+    accounts: Mutex<HashMap<String, Arc<NonceManager>>>,
+}
+
+impl NonceManagers {
+    /// Nonce manager of the hex account address `account`
+    pub fn of(&self, account: &str) -> Arc<NonceManager> {
+        self.accounts
+            .lock()
+            .expect("the nonce managers lock is never poisoned")
+            .entry(account.to_lowercase())
+            .or_default()
+            .clone()
+    }
+}
+
+/// Whether the node error `message` rejects a transaction for its nonce
+pub fn is_invalid_nonce(message: &str) -> bool {
+    let message = message.to_lowercase();
+
+    message.contains("nonce")
+        && ["invalid", "too low", "too high", "mismatch", "already"]
+            .iter()
+            .any(|reason| message.contains(reason))
+}
@@ -0,0 +1,126 @@
+use crate::nonce_manager::*;
+
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+async fn fetch(fetches: &AtomicU32, nonce: u128) -> Result<u128, String> {
+    fetches.fetch_add(1, Ordering::SeqCst);
+    Ok(nonce)
+}
+
+#[tokio::test]
+async fn test_committed_reservations_hand_out_sequential_nonces() {
+    // Arrange
+    let nonces = NonceManager::new();
+    let fetches = AtomicU32::new(0);
+
+    // Act
+    let mut reserved = Vec::new();
+    for _ in 0..3 {
+        let reservation = nonces.reserve(|| fetch(&fetches, 5)).await.unwrap();
+        reserved.push(reservation.nonce());
+        reservation.commit();
+    }
+
+    // Assert
+    assert_eq!(reserved, vec![5, 6, 7]);
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_dropped_reservation_leaves_its_nonce_to_the_next() {
+    let nonces = NonceManager::new();
+    let fetches = AtomicU32::new(0);
+
+    let dropped = nonces.reserve(|| fetch(&fetches, 5)).await.unwrap().nonce();
+    let next = nonces.reserve(|| fetch(&fetches, 9)).await.unwrap().nonce();
+
+    assert_eq!((dropped, next), (5, 5));
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_resync_fetches_the_nonce_again() {
+    // Arrange
+    let nonces = NonceManager::new();
+    let fetches = AtomicU32::new(0);
+    nonces.reserve(|| fetch(&fetches, 5)).await.unwrap().resync();
+
+    // Act
+    let reservation = nonces.reserve(|| fetch(&fetches, 8)).await.unwrap();
+
+    // Assert
+    assert_eq!(reservation.nonce(), 8);
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_failed_fetch_is_retried_by_the_next_reservation() {
+    let nonces = NonceManager::new();
+
+    let failed = nonces
+        .reserve(|| async { Err::<u128, _>("unreachable".to_string()) })
+        .await;
+    let next = nonces.reserve(|| async { Ok::<_, String>(3) }).await;
+
+    assert_eq!(failed.unwrap_err(), "unreachable");
+    assert_eq!(next.unwrap().nonce(), 3);
+}
+
+#[tokio::test]
+async fn test_concurrent_reservations_never_share_a_nonce() {
+    // Arrange
+    let nonces = Arc::new(NonceManager::new());
+
+    // Act
+    let mut tasks = tokio::task::JoinSet::new();
+    for _ in 0..16 {
+        let nonces = nonces.clone();
+        tasks.spawn(async move {
+            let reservation =
+                nonces.reserve(|| async { Ok::<_, String>(1) }).await.unwrap();
+            let nonce = reservation.nonce();
+            tokio::task::yield_now().await;
+            reservation.commit();
+            nonce
+        });
+    }
+    let mut reserved = Vec::new();
+    while let Some(nonce) = tasks.join_next().await {
+        reserved.push(nonce.unwrap());
+    }
+
+    // Assert
+    reserved.sort_unstable();
+    assert_eq!(reserved, (1..=16).collect::<Vec<u128>>());
+}
+
+#[test]
+fn test_next_nonce_follows_the_account_nonce() {
+    assert_eq!(next_nonce(0), 1);
+    assert_eq!(next_nonce(7), 8);
+}
+
+#[test]
+fn test_nonce_managers_are_shared_per_account() {
+    let managers = NonceManagers::default();
+
+    assert!(Arc::ptr_eq(&managers.of("ab12"), &managers.of("AB12")));
+    assert!(!Arc::ptr_eq(&managers.of("ab12"), &managers.of("cd34")));
+}
+
+#[test]
+fn test_invalid_nonce_errors() {
+    for message in [
+        "Node returned error -32000: Invalid nonce 5, expected 7",
+        "nonce too low",
+        "Nonce mismatch",
+    ] {
+        assert!(is_invalid_nonce(message), "{}", message);
+    }
+    for message in ["insufficient balance", "invalid signature"] {
+        assert!(!is_invalid_nonce(message), "{}", message);
+    }
+}
@@ -0,0 +1,437 @@
+use crate::types::{AccessType, ContractType, Transaction, U8s};
+
+use schemars::schema::{
+    ArrayValidation, InstanceType, ObjectValidation, RootSchema, Schema,
+    SchemaObject, SingleOrVec,
+};
+use serde_json::Value;
+
+/// Where and why a payload doesn't match the schema of
+/// [`Transaction`]. `path` is empty at the root of the payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadSchemaError {
+    /// Dotted path of the offending value, array items indexed, e.g.
+    /// `smart_contract_deployment[2]`
+    pub path: String,
+    pub reason: String,
+}
+
+impl std::error::Error for PayloadSchemaError {}
+
+impl std::fmt::Display for PayloadSchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.reason),
+            false => write!(f, "`{}`: {}", self.path, self.reason),
+        }
+    }
+}
+
+/// JSON Schema of a transaction payload file
+pub fn transaction_schema() -> RootSchema {
+    schemars::schema_for!(Transaction)
+}
+
+/// Check `payload` against [`transaction_schema`]. String patterns are not
+/// checked, deserializing the payload reports them.
+pub fn validate_transaction(payload: &Value) -> Result<(), PayloadSchemaError> {
+    let root = transaction_schema();
+
+    Validator { root: &root }.check_object(&root.schema, payload, "")
+}
+
+/// Variants [`example_transaction`] has a template of
+pub const TRANSACTION_VARIANTS: &[&str] = &[
+    "native_token_transfer",
+    "smart_contract_deployment",
+    "smart_contract_init",
+    "smart_contract_function_call",
+    "create_staking_pool",
+    "stake",
+    "un_stake",
+];
+
+/// Template payload of the transaction `variant`, one of
+/// [`TRANSACTION_VARIANTS`]
+pub fn example_transaction(variant: &str) -> Option<Transaction> {
+    let address = || U8s::Hex("00".repeat(20));
+
+    Some(match variant {
+        "native_token_transfer" => {
+            Transaction::NativeTokenTransfer(address(), 0)
+        }
+        "smart_contract_deployment" => Transaction::SmartContractDeployment(
+            AccessType::PUBLIC,
+            ContractType::L1XVM,
+            U8s::File("target/contract.o".into()),
+            0,
+            U8s::Text("salt".to_string()),
+        ),
+        "smart_contract_init" => Transaction::SmartContractInit(
+            address(),
+            U8s::Text("{}".to_string()),
+        ),
+        "smart_contract_function_call" => {
+            Transaction::SmartContractFunctionCall {
+                contract_instance_address: address(),
+                function: U8s::Text("function_name".to_string()),
+                arguments: U8s::Text("{}".to_string()),
+            }
+        }
+        "create_staking_pool" => Transaction::CreateStakingPool {
+            contract_instance_address: None,
+            min_stake: Some(0),
+            max_stake: None,
+            min_pool_balance: None,
+            max_pool_balance: None,
+            staking_period: None,
+        },
+        "stake" => Transaction::Stake { pool_address: address(), amount: 0 },
+        "un_stake" => {
+            Transaction::UnStake { pool_address: address(), amount: 0 }
+        }
+        _ => return None,
+    })
+}
+
+struct Validator<'a> {
+    root: &'a RootSchema,
+}
+
+impl Validator<'_> {
+    fn check(
+        &self,
+        schema: &Schema,
+        value: &Value,
+        path: &str,
+    ) -> Result<(), PayloadSchemaError> {
+        match schema {
+            Schema::Bool(true) => Ok(()),
+            Schema::Bool(false) => Err(error(path, "no value is allowed here")),
+            Schema::Object(schema) => self.check_object(schema, value, path),
+        }
+    }
+
+    fn check_object(
+        &self,
+        schema: &SchemaObject,
+        value: &Value,
+        path: &str,
+    ) -> Result<(), PayloadSchemaError> {
+        if let Some(reference) = &schema.reference {
+            self.check(self.resolve(reference, path)?, value, path)?;
+        }
+
+        if let Some(instance_type) = &schema.instance_type {
+            let allowed = match instance_type {
+                SingleOrVec::Single(instance_type) => vec![**instance_type],
+                SingleOrVec::Vec(instance_types) => instance_types.clone(),
+            };
+            if !allowed.iter().any(|allowed| is_instance_of(*allowed, value)) {
+                return Err(error(
+                    path,
+                    format!(
+                        "expected {}, got {}",
+                        list_or(allowed.iter().map(type_name)),
+                        describe(value)
+                    ),
+                ));
+            }
+        }
+
+        if let Some(enum_values) = &schema.enum_values {
+            if !enum_values.contains(value) {
+                return Err(error(
+                    path,
+                    format!(
+                        "expected {}, got {}",
+                        list_or(enum_values.iter().map(Value::to_string)),
+                        value
+                    ),
+                ));
+            }
+        }
+
+        if let Some(subschemas) = &schema.subschemas {
+            for subschema in subschemas.all_of.iter().flatten() {
+                self.check(subschema, value, path)?;
+            }
+            if let Some(alternatives) =
+                subschemas.one_of.as_ref().or(subschemas.any_of.as_ref())
+            {
+                self.check_alternatives(alternatives, value, path)?;
+            }
+        }
+
+        if let (Some(number), Some(actual)) = (&schema.number, value.as_f64()) {
+            if let Some(minimum) = number.minimum.filter(|min| actual < *min) {
+                return Err(error(
+                    path,
+                    format!("expected at least {}, got {}", minimum, value),
+                ));
+            }
+            if let Some(maximum) = number.maximum.filter(|max| actual > *max) {
+                return Err(error(
+                    path,
+                    format!("expected at most {}, got {}", maximum, value),
+                ));
+            }
+        }
+
+        if let (Some(array), Value::Array(items)) = (&schema.array, value) {
+            self.check_items(array, items, path)?;
+        }
+
+        if let (Some(object), Value::Object(fields)) = (&schema.object, value) {
+            self.check_fields(object, fields, path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Externally tagged enums are matched on the variant key of the value,
+    /// so that the errors point inside the variant the payload is written as
+    fn check_alternatives(
+        &self,
+        alternatives: &[Schema],
+        value: &Value,
+        path: &str,
+    ) -> Result<(), PayloadSchemaError> {
+        let tags: Vec<&str> = alternatives
+            .iter()
+            .filter_map(|alternative| self.variant_tag(alternative))
+            .collect();
+
+        if tags.len() == alternatives.len() {
+            let key = match value {
+                Value::Object(fields) if fields.len() == 1 => {
+                    fields.keys().next()
+                }
+                _ => None,
+            };
+            return match key.and_then(|key| {
+                tags.iter().position(|tag| *tag == key.as_str())
+            }) {
+                Some(position) => {
+                    self.check(&alternatives[position], value, path)
+                }
+                None => Err(error(
+                    path,
+                    format!(
+                        "expected object with {} key, got {}",
+                        list_or(tags.iter().map(|tag| format!("`{}`", tag))),
+                        describe(value)
+                    ),
+                )),
+            };
+        }
+
+        let mut first_err = None;
+        for alternative in alternatives {
+            match self.check(alternative, value, path) {
+                Ok(()) => return Ok(()),
+                Err(err_code) => {
+                    first_err.get_or_insert(err_code);
+                }
+            }
+        }
+        first_err.map_or(Ok(()), Err)
+    }
+
+    /// Key of the single key object `schema` describes, the way an externally
+    /// tagged enum variant is written
+    fn variant_tag<'s>(&self, schema: &'s Schema) -> Option<&'s str> {
+        let object = match schema {
+            Schema::Object(SchemaObject { object: Some(object), .. }) => object,
+            _ => return None,
+        };
+        match (object.required.len(), object.properties.len()) {
+            (1, 1) => object.properties.keys().next().map(String::as_str),
+            _ => None,
+        }
+    }
+
+    fn check_items(
+        &self,
+        array: &ArrayValidation,
+        items: &[Value],
+        path: &str,
+    ) -> Result<(), PayloadSchemaError> {
+        let expected = match (array.min_items, array.max_items) {
+            (Some(min), Some(max))
+                if min == max && items.len() != min as usize =>
+            {
+                Some(min.to_string())
+            }
+            (Some(min), _) if items.len() < min as usize => {
+                Some(format!("at least {}", min))
+            }
+            (_, Some(max)) if items.len() > max as usize => {
+                Some(format!("at most {}", max))
+            }
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            return Err(error(
+                path,
+                format!("expected {} items, got {}", expected, items.len()),
+            ));
+        }
+
+        match &array.items {
+            Some(SingleOrVec::Single(schema)) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.check(schema, item, &item_path(path, index))?;
+                }
+            }
+            Some(SingleOrVec::Vec(schemas)) => {
+                for (index, (schema, item)) in
+                    schemas.iter().zip(items).enumerate()
+                {
+                    self.check(schema, item, &item_path(path, index))?;
+                }
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    fn check_fields(
+        &self,
+        object: &ObjectValidation,
+        fields: &serde_json::Map<String, Value>,
+        path: &str,
+    ) -> Result<(), PayloadSchemaError> {
+        if let Some(missing) =
+            object.required.iter().find(|key| !fields.contains_key(*key))
+        {
+            return Err(error(path, format!("missing `{}`", missing)));
+        }
+
+        for (key, value) in fields {
+            let field_path = field_path(path, key);
+            match (object.properties.get(key), &object.additional_properties) {
+                (Some(schema), _) => self.check(schema, value, &field_path)?,
+                (None, Some(additional)) => match additional.as_ref() {
+                    Schema::Bool(false) => {
+                        return Err(error(
+                            &field_path,
+                            format!(
+                                "unknown key, expected {}",
+                                list_or(
+                                    object
+                                        .properties
+                                        .keys()
+                                        .map(|key| format!("`{}`", key))
+                                )
+                            ),
+                        ))
+                    }
+                    additional => self.check(additional, value, &field_path)?,
+                },
+                (None, None) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve(
+        &self,
+        reference: &str,
+        path: &str,
+    ) -> Result<&Schema, PayloadSchemaError> {
+        reference
+            .strip_prefix("#/definitions/")
+            .and_then(|name| self.root.definitions.get(name))
+            .ok_or_else(|| {
+                error(path, format!("unknown schema reference {}", reference))
+            })
+    }
+}
+
+fn error(path: &str, reason: impl Into<String>) -> PayloadSchemaError {
+    PayloadSchemaError { path: path.to_string(), reason: reason.into() }
+}
+
+fn field_path(path: &str, key: &str) -> String {
+    match path.is_empty() {
+        true => key.to_string(),
+        false => format!("{}.{}", path, key),
+    }
+}
+
+fn item_path(path: &str, index: usize) -> String {
+    format!("{}[{}]", path, index)
+}
+
+fn is_instance_of(instance_type: InstanceType, value: &Value) -> bool {
+    match instance_type {
+        InstanceType::Null => value.is_null(),
+        InstanceType::Boolean => value.is_boolean(),
+        InstanceType::Object => value.is_object(),
+        InstanceType::Array => value.is_array(),
+        InstanceType::Number => value.is_number(),
+        InstanceType::String => value.is_string(),
+        InstanceType::Integer => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().map_or(false, |number| number.fract() == 0.0)
+        }
+    }
+}
+
+fn type_name(instance_type: &InstanceType) -> String {
+    match instance_type {
+        InstanceType::Null => "null",
+        InstanceType::Boolean => "boolean",
+        InstanceType::Object => "object",
+        InstanceType::Array => "array",
+        InstanceType::Number => "number",
+        InstanceType::String => "string",
+        InstanceType::Integer => "integer",
+    }
+    .to_string()
+}
+
+/// Short description of what `value` is, for error messages
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(_) => "boolean".to_string(),
+        Value::Number(_) => "number".to_string(),
+        Value::String(_) => "string".to_string(),
+        Value::Array(items) => format!("array of {} items", items.len()),
+        Value::Object(fields) if fields.is_empty() => {
+            "empty object".to_string()
+        }
+        Value::Object(fields) => format!(
+            "object with {} {}",
+            list_and(fields.keys().map(|key| format!("`{}`", key))),
+            match fields.len() {
+                1 => "key",
+                _ => "keys",
+            }
+        ),
+    }
+}
+
+/// `a`, `a or b`, `a, b or c`
+fn list_or(items: impl Iterator<Item = String>) -> String {
+    join_last(items.collect(), "or")
+}
+
+fn list_and(items: impl Iterator<Item = String>) -> String {
+    join_last(items.collect(), "and")
+}
+
+fn join_last(mut items: Vec<String>, last_separator: &str) -> String {
+    match items.pop() {
+        None => String::new(),
+        Some(last) if items.is_empty() => last,
+        Some(last) => {
+            format!("{} {} {}", items.join(", "), last_separator, last)
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::payload_schema::*;
+use crate::{load_transaction_payload, PayloadFormat, TxnBuildError};
+
+/// Path of the payload fixture `file_name`
+fn fixture(file_name: &str) -> String {
+    format!("{}/fixtures/payloads/{}", env!("CARGO_MANIFEST_DIR"), file_name)
+}
+
+/// Schema error of the malformed payload fixture `file_name`
+fn schema_error(file_name: &str) -> (PayloadFormat, PayloadSchemaError) {
+    let path = fixture(&format!("invalid/{}", file_name));
+
+    match load_transaction_payload(&path, None) {
+        Err(TxnBuildError::PayloadSchema {
+            path: err_path,
+            format,
+            source,
+        }) => {
+            assert_eq!(err_path, path);
+            (format, source)
+        }
+        other => panic!("expected a schema error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_valid_payload_fixtures_match_the_schema() {
+    for payload in [
+        "function_call",
+        "function_call_base64",
+        "deployment",
+        "deployment_file",
+        "stake",
+        "unstake",
+        "create_staking_pool",
+    ] {
+        let content =
+            std::fs::read_to_string(fixture(&format!("{}.json", payload)))
+                .unwrap();
+
+        validate_transaction(&serde_json::from_str(&content).unwrap())
+            .unwrap_or_else(|err_code| panic!("{}: {}", payload, err_code));
+    }
+}
+
+#[test]
+fn test_malformed_payloads_point_at_the_offending_value() {
+    let cases = [
+        (
+            "deployment_code_string.json",
+            "smart_contract_deployment[2]",
+            "expected object with `hex`, `bytes`, `file`, `text` or `base64` key, got string",
+        ),
+        (
+            "deployment_lowercase_access.yaml",
+            "smart_contract_deployment[0]",
+            r#"expected "PRIVATE", "PUBLIC" or "RESTICTED", got "public""#,
+        ),
+        (
+            "deployment_missing_salt.json",
+            "smart_contract_deployment",
+            "expected 5 items, got 4",
+        ),
+        (
+            "function_call_missing_arguments.toml",
+            "smart_contract_function_call",
+            "missing `arguments`",
+        ),
+        (
+            "function_call_unknown_encoding.json",
+            "smart_contract_function_call.function",
+            "expected object with `hex`, `bytes`, `file`, `text` or `base64` key, got object with `utf8` key",
+        ),
+        (
+            "stake_amount_string.json",
+            "stake.amount",
+            "expected integer, got string",
+        ),
+    ];
+
+    for (file_name, path, reason) in cases {
+        let (_, err) = schema_error(file_name);
+
+        assert_eq!(err.path, path, "{}", file_name);
+        assert_eq!(err.reason, reason, "{}", file_name);
+    }
+}
+
+#[test]
+fn test_unknown_variant_lists_the_transaction_variants() {
+    let (format, err) = schema_error("unknown_variant.json");
+
+    assert_eq!(format, PayloadFormat::Json);
+    assert_eq!(err.path, "");
+    assert_eq!(
+        err.to_string(),
+        "expected object with `native_token_transfer`, `smart_contract_deployment`, `smart_contract_init`, `smart_contract_function_call`, `create_staking_pool`, `stake` or `un_stake` key, got object with `smart_contract_call` key"
+    );
+}
+
+#[test]
+fn test_schema_error_message_names_file_and_path() {
+    let path = fixture("invalid/stake_amount_string.json");
+
+    let err = load_transaction_payload(&path, None).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Invalid JSON transaction payload {} :: `stake.amount`: expected integer, got string",
+            path
+        )
+    );
+}
+
+#[test]
+fn test_examples_of_every_variant_match_the_schema() {
+    for variant in TRANSACTION_VARIANTS {
+        let example = example_transaction(variant).unwrap();
+        let value = serde_json::to_value(&example).unwrap();
+
+        validate_transaction(&value)
+            .unwrap_or_else(|err_code| panic!("{}: {}", variant, err_code));
+        assert!(value.get(variant).is_some(), "{}", variant);
+    }
+    assert!(example_transaction("smart_contract_call").is_none());
+}
@@ -0,0 +1,685 @@
+use crate::toolkit_config;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Timeout of the requests of [`fetch_nonce`] and [`get_account_state`]
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay between two `l1x_getEvents` queries of [`wait_for_events`]
+pub const DEFAULT_EVENT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long [`wait_for_events`] waits for the events of a transaction
+pub const DEFAULT_EVENT_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// JSON-RPC method of [`get_transaction_receipt`] on current nodes
+pub const DEFAULT_RECEIPT_METHOD: &str = "l1x_getTransactionReceipt";
+
+/// Retries of a request failing with a retryable error
+pub const DEFAULT_RPC_RETRIES: u32 = 3;
+
+/// Delay before the first retry of a request, doubling with every retry
+pub const DEFAULT_RPC_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// Longest delay between two attempts of a request
+const MAX_RPC_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum RpcError {
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("Request to {endpoint} failed: {reason}")]
+    Transport { endpoint: String, reason: String },
+    #[error("Request to {endpoint} failed with HTTP status {status}")]
+    HttpStatus { endpoint: String, status: u16 },
+    #[error("Node returned error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+    #[error(
+        "Response to the {method} request with id {sent} carries the id {received}"
+    )]
+    IdMismatch { method: String, sent: u64, received: String },
+    #[error(
+        "Node {endpoint} is on chain id {reported}, the network is configured with chain id {expected}"
+    )]
+    ChainIdMismatch { endpoint: String, expected: u64, reported: u64 },
+    #[error("{0}")]
+    EventsTimeout(#[from] EventsTimeout),
+    #[error("{0}")]
+    ReceiptTimeout(#[from] ReceiptTimeout),
+}
+
+impl RpcError {
+    /// Whether the request may succeed when sent again: timeouts, connection
+    /// errors and server errors. Errors the node answered with are final.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RpcError::Transport { .. } => true,
+            RpcError::HttpStatus { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Whether the node doesn't implement the method called
+    pub fn is_method_not_found(&self) -> bool {
+        matches!(self, RpcError::Rpc { code, .. } if *code == METHOD_NOT_FOUND)
+    }
+}
+
+/// How often and how fast [`with_retries`] retries a failed request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    /// Delay before the first retry, doubling with every retry
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Give up on the first failure
+    pub const NONE: RetryPolicy =
+        RetryPolicy { retries: 0, delay: Duration::ZERO };
+
+    pub fn new(retries: u32, delay: Duration) -> Self {
+        RetryPolicy { retries, delay }
+    }
+
+    /// Delay before the retry `retry`, counting from 1: between half and all
+    /// of `delay * 2^(retry - 1)`, at most [`MAX_RPC_RETRY_DELAY`]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let ceiling = self
+            .delay
+            .saturating_mul(1 << retry.saturating_sub(1).min(16))
+            .min(MAX_RPC_RETRY_DELAY);
+
+        ceiling / 2 + ceiling.mul_f64(secp256k1::rand::random::<f64>() / 2.0)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_DELAY)
+    }
+}
+
+/// Ids of the JSON-RPC requests of one command: the first request carries
+/// the `--req_id` of the command, each following request the next id. Clones
+/// share the sequence.
+#[derive(Debug, Clone)]
+pub struct RequestIds {
+    next: Arc<AtomicU64>,
+}
+
+impl RequestIds {
+    pub fn starting_at(first: u64) -> Self {
+        RequestIds { next: Arc::new(AtomicU64::new(first)) }
+    }
+
+    /// Id of the next request
+    pub fn next_id(&self) -> u64 {
+        self.next.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// Check that the JSON-RPC `response` to the request `method` echoes the id
+/// `sent`. A null id is only accepted on errors, the node couldn't read the
+/// id of the request.
+pub fn check_response_id(
+    method: &str,
+    sent: u64,
+    response: &Value,
+) -> Result<(), RpcError> {
+    match response.get("id") {
+        Some(id) if id.as_u64() == Some(sent) => Ok(()),
+        Some(Value::Null)
+            if response
+                .get("error")
+                .map_or(false, |error| !error.is_null()) =>
+        {
+            Ok(())
+        }
+        id => Err(RpcError::IdMismatch {
+            method: method.to_string(),
+            sent,
+            received: id.map_or_else(|| "none".to_string(), Value::to_string),
+        }),
+    }
+}
+
+/// Run `attempt` until it succeeds, fails with an error `is_retryable`
+/// rejects, or the retries of `policy` are spent. `attempt` must send the
+/// same request every time, e.g. the same signed transaction.
+pub async fn with_retries<F, Fut, T, E>(
+    label: &str,
+    policy: RetryPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    mut attempt: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let mut retry = 0;
+
+    loop {
+        match attempt().await {
+            Err(err) if retry < policy.retries && is_retryable(&err) => {
+                retry += 1;
+                let backoff = policy.backoff(retry);
+                log::warn!(
+                    "{} failed, retry {} of {} in {:?} :: {}",
+                    label,
+                    retry,
+                    policy.retries,
+                    backoff,
+                    err
+                );
+                tokio::time::sleep(backoff).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// The node reported no events of a transaction within the wait timeout
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("No events of transaction {tx_hash} after {waited:?}")]
+pub struct EventsTimeout {
+    pub tx_hash: String,
+    pub waited: Duration,
+}
+
+/// The node reported no receipt of a transaction within the wait timeout
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("No receipt of transaction {tx_hash} after {waited:?}")]
+pub struct ReceiptTimeout {
+    pub tx_hash: String,
+    pub waited: Duration,
+}
+
+/// Outcome of a transaction in its block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    Success,
+    /// Failed in the block, out of fee or trapped, with the error the node
+    /// reported
+    Failed(String),
+}
+
+/// Receipt of a transaction included in a block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxReceipt {
+    pub block_number: Option<u128>,
+    pub status: TxStatus,
+    /// Fee the transaction consumed, or its gas used on nodes reporting gas,
+    /// `None` when the node reports neither
+    pub fee_used: Option<u128>,
+}
+
+/// Amount of a receipt field, a number, a decimal string or a `0x` hex
+/// string as in EVM receipts
+fn receipt_amount(value: &Value) -> Option<u128> {
+    match value {
+        Value::Number(amount) => amount.as_u64().map(u128::from),
+        Value::String(amount) => match amount.strip_prefix("0x") {
+            Some(hex_amount) => u128::from_str_radix(hex_amount, 16).ok(),
+            None => amount.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+impl TxReceipt {
+    /// Receipt of the `result` of a receipt query, `None` while the node
+    /// doesn't know the transaction. `status` is a boolean, a string such as
+    /// `success` or `failed`, or a number where `1` is a success like in EVM
+    /// receipts. Nodes reporting no status only report included
+    /// transactions that succeeded.
+    pub fn from_result(result: &Value) -> Option<TxReceipt> {
+        if !result.is_object() {
+            return None;
+        }
+
+        let succeeded = match &result["status"] {
+            Value::Null => true,
+            Value::Bool(succeeded) => *succeeded,
+            Value::Number(status) => status.as_u64() == Some(1),
+            Value::String(status) => matches!(
+                status.to_ascii_lowercase().as_str(),
+                "success" | "succeeded" | "ok" | "1" | "true"
+            ),
+            _ => false,
+        };
+        let status = match succeeded {
+            true => TxStatus::Success,
+            false => TxStatus::Failed(
+                ["error", "error_message", "message"]
+                    .iter()
+                    .find_map(|field| result[*field].as_str())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("status {}", result["status"])),
+            ),
+        };
+
+        Some(TxReceipt {
+            block_number: match &result["block_number"] {
+                Value::Number(number) => number.as_u64().map(u128::from),
+                Value::String(number) => number.parse().ok(),
+                _ => None,
+            },
+            status,
+            fee_used: ["fee_used", "gas_used", "gasUsed"]
+                .iter()
+                .find_map(|field| receipt_amount(&result[*field])),
+        })
+    }
+}
+
+/// JSON-RPC error code of a method the node doesn't implement
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Balance, nonce and type of an account as reported by the node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccountStateInfo {
+    /// Lowercase hex account address, without `0x`
+    pub address: String,
+    pub balance: u128,
+    pub nonce: u128,
+    pub account_type: String,
+}
+
+/// Id of the requests of [`call`], which serve no command with a `--req_id`
+const CALL_REQ_ID: u64 = 1;
+
+/// Post the JSON-RPC request `method` to `endpoint` and return its `result`
+pub async fn call(
+    endpoint: &str,
+    method: &str,
+    params: Value,
+    timeout: Duration,
+) -> Result<Value, RpcError> {
+    let transport_err = |err: reqwest::Error| RpcError::Transport {
+        endpoint: endpoint.to_string(),
+        reason: err.to_string(),
+    };
+
+    log::debug!(
+        "JSON RPC {} req_id {} PARAMS: {}",
+        method,
+        CALL_REQ_ID,
+        params
+    );
+
+    let response = reqwest::Client::new()
+        .post(endpoint)
+        .timeout(timeout)
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": CALL_REQ_ID,
+        }))
+        .send()
+        .await
+        .map_err(transport_err)?;
+    let response: Value = error_for_server_status(endpoint, response)?
+        .json()
+        .await
+        .map_err(transport_err)?;
+    check_response_id(method, CALL_REQ_ID, &response)?;
+
+    match &response["error"] {
+        Value::Null => Ok(response["result"].clone()),
+        error => Err(rpc_error(error)),
+    }
+}
+
+/// `response` unless the node failed with a 5xx status
+pub fn error_for_server_status(
+    endpoint: &str,
+    response: reqwest::Response,
+) -> Result<reqwest::Response, RpcError> {
+    match response.status() {
+        status if status.is_server_error() => Err(RpcError::HttpStatus {
+            endpoint: endpoint.to_string(),
+            status: status.as_u16(),
+        }),
+        _ => Ok(response),
+    }
+}
+
+/// [`RpcError::Rpc`] of the JSON-RPC `error` object of a response
+pub fn rpc_error(error: &Value) -> RpcError {
+    RpcError::Rpc {
+        code: error["code"].as_i64().unwrap_or_default(),
+        message: match &error["message"] {
+            Value::String(message) => message.clone(),
+            _ => error.to_string(),
+        },
+    }
+}
+
+/// Query the `l1x_getAccountState` of `address`. Accounts the chain doesn't
+/// know yet report a zero balance and nonce.
+pub async fn get_account_state(
+    endpoint: &str,
+    address: &str,
+    timeout: Duration,
+) -> Result<AccountStateInfo, RpcError> {
+    let address = toolkit_config::normalize_address(address)
+        .map_err(RpcError::InvalidAddress)?;
+
+    let result = call(
+        endpoint,
+        "l1x_getAccountState",
+        json!({ "request": { "address": address } }),
+        timeout,
+    )
+    .await?;
+
+    let account_state = match &result["account_state"] {
+        Value::Null => {
+            return Ok(AccountStateInfo {
+                address,
+                balance: 0,
+                nonce: 0,
+                account_type: "unknown".to_string(),
+            })
+        }
+        account_state => account_state,
+    };
+
+    let number_from = |field: &str| match &account_state[field] {
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(number) => number.parse().ok(),
+        _ => None,
+    };
+    let account_type = match &account_state["account_type"] {
+        Value::String(account_type) => account_type.clone(),
+        Value::Null => "unknown".to_string(),
+        account_type => account_type.to_string(),
+    };
+
+    Ok(AccountStateInfo {
+        balance: number_from("balance").ok_or_else(|| {
+            RpcError::InvalidResponse(
+                "Account state has no valid balance".to_string(),
+            )
+        })?,
+        nonce: number_from("nonce").ok_or_else(|| {
+            RpcError::InvalidResponse(
+                "Account state has no valid nonce".to_string(),
+            )
+        })?,
+        address,
+        account_type,
+    })
+}
+
+/// Whether the chain knows the account or contract at `address`, false once
+/// a devnet reset dropped it
+pub async fn account_exists(
+    endpoint: &str,
+    address: &str,
+    timeout: Duration,
+) -> Result<bool, RpcError> {
+    let address = toolkit_config::normalize_address(address)
+        .map_err(RpcError::InvalidAddress)?;
+
+    let result = call(
+        endpoint,
+        "l1x_getAccountState",
+        json!({ "request": { "address": address } }),
+        timeout,
+    )
+    .await?;
+
+    Ok(!result["account_state"].is_null())
+}
+
+/// Nonce of the last transaction of `address`, `0` for accounts the chain
+/// doesn't know yet
+pub async fn fetch_nonce(
+    endpoint: &str,
+    address: &str,
+    timeout: Duration,
+) -> Result<u128, RpcError> {
+    get_account_state(endpoint, address, timeout)
+        .await
+        .map(|account_state| account_state.nonce)
+}
+
+/// Outcome of [`verify_chain_id`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainIdCheck {
+    Match,
+    /// The node doesn't report its chain id, nothing was checked
+    Unsupported,
+}
+
+/// Chain id reported by the node at `endpoint` in its `l1x_getChainState`,
+/// `None` when it doesn't report one. Queried once per endpoint and process.
+pub async fn node_chain_id(
+    endpoint: &str,
+    timeout: Duration,
+) -> Result<Option<u64>, RpcError> {
+    static NODE_CHAIN_IDS: OnceLock<Mutex<HashMap<String, Option<u64>>>> =
+        OnceLock::new();
+    let node_chain_ids = NODE_CHAIN_IDS.get_or_init(Default::default);
+
+    if let Some(chain_id) = node_chain_ids
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(endpoint)
+    {
+        return Ok(*chain_id);
+    }
+
+    let chain_id = match call(
+        endpoint,
+        "l1x_getChainState",
+        json!({ "request": {} }),
+        timeout,
+    )
+    .await
+    {
+        Ok(result) => match &result["chain_id"] {
+            Value::Number(chain_id) => chain_id.as_u64(),
+            Value::String(chain_id) => chain_id.parse().ok(),
+            _ => None,
+        },
+        Err(RpcError::Rpc { code: METHOD_NOT_FOUND, .. }) => None,
+        Err(err) => return Err(err),
+    };
+
+    node_chain_ids
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(endpoint.to_string(), chain_id);
+
+    Ok(chain_id)
+}
+
+/// Check that the node at `endpoint` is on the chain `chain_id`, so that
+/// transactions don't land on another chain than the configured one
+pub async fn verify_chain_id(
+    endpoint: &str,
+    chain_id: u32,
+    timeout: Duration,
+) -> Result<ChainIdCheck, RpcError> {
+    match node_chain_id(endpoint, timeout).await? {
+        Some(reported) if reported == u64::from(chain_id) => {
+            Ok(ChainIdCheck::Match)
+        }
+        Some(reported) => Err(RpcError::ChainIdMismatch {
+            endpoint: endpoint.to_string(),
+            expected: chain_id.into(),
+            reported,
+        }),
+        None => Ok(ChainIdCheck::Unsupported),
+    }
+}
+
+/// JSON-RPC method returning the code deployed at a contract address
+pub const CONTRACT_CODE_METHOD: &str = "l1x_getContractCode";
+
+/// Code deployed at the contract `address` as reported by
+/// [`CONTRACT_CODE_METHOD`], a hex string or a byte array. `None` when the
+/// node knows no contract there.
+pub async fn get_contract_code(
+    endpoint: &str,
+    address: &str,
+    timeout: Duration,
+) -> Result<Option<Vec<u8>>, RpcError> {
+    let address = toolkit_config::normalize_address(address)
+        .map_err(RpcError::InvalidAddress)?;
+
+    let result = call(
+        endpoint,
+        CONTRACT_CODE_METHOD,
+        json!({ "request": { "address": address } }),
+        timeout,
+    )
+    .await?;
+
+    let invalid_code = |reason: String| {
+        RpcError::InvalidResponse(format!("Invalid contract code: {}", reason))
+    };
+    match &result["code"] {
+        Value::Null => Ok(None),
+        Value::String(code) => {
+            let code = code.trim();
+            hex::decode(code.strip_prefix("0x").unwrap_or(code))
+                .map(Some)
+                .map_err(|err| invalid_code(err.to_string()))
+        }
+        code => serde_json::from_value(code.clone())
+            .map(Some)
+            .map_err(|err| invalid_code(err.to_string())),
+    }
+}
+
+/// Events data of the transaction `tx_hash` as reported by `l1x_getEvents`,
+/// empty while the node has none
+pub async fn get_events(
+    endpoint: &str,
+    tx_hash: &str,
+    timeout: Duration,
+) -> Result<Vec<Vec<u8>>, RpcError> {
+    let result = call(
+        endpoint,
+        "l1x_getEvents",
+        json!({ "request": { "tx_hash": tx_hash, "timestamp": 0 } }),
+        timeout,
+    )
+    .await?;
+
+    match &result["events_data"] {
+        Value::Null => Ok(Vec::new()),
+        events_data => {
+            serde_json::from_value(events_data.clone()).map_err(|err| {
+                RpcError::InvalidResponse(format!(
+                    "Invalid events_data: {}",
+                    err
+                ))
+            })
+        }
+    }
+}
+
+/// Query the events of `tx_hash` with `get_events` every `poll_interval`
+/// until the node reports some, for at most `wait_timeout`. Query errors
+/// are returned as is, running out of time as [`EventsTimeout`].
+pub async fn wait_for_events<F, Fut, E>(
+    tx_hash: &str,
+    poll_interval: Duration,
+    wait_timeout: Duration,
+    mut get_events: F,
+) -> Result<Vec<Vec<u8>>, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Vec<Vec<u8>>, E>>,
+    E: From<EventsTimeout>,
+{
+    let started = Instant::now();
+
+    loop {
+        let events = get_events().await?;
+        if !events.is_empty() {
+            return Ok(events);
+        }
+
+        let waited = started.elapsed();
+        if waited >= wait_timeout {
+            return Err(
+                EventsTimeout { tx_hash: tx_hash.to_string(), waited }.into()
+            );
+        }
+        log::debug!("Txn {} | No events yet after {:?}", tx_hash, waited);
+        tokio::time::sleep(poll_interval.min(wait_timeout - waited)).await;
+    }
+}
+
+/// Receipt of the transaction `tx_hash` queried with the JSON-RPC `method`,
+/// [`DEFAULT_RECEIPT_METHOD`] on current nodes. `None` while the node doesn't
+/// know the transaction.
+pub async fn get_transaction_receipt(
+    endpoint: &str,
+    method: &str,
+    tx_hash: &str,
+    timeout: Duration,
+) -> Result<Option<TxReceipt>, RpcError> {
+    let result = call(
+        endpoint,
+        method,
+        json!({ "request": { "hash": tx_hash } }),
+        timeout,
+    )
+    .await?;
+
+    Ok(TxReceipt::from_result(&result))
+}
+
+/// Query the receipt of `tx_hash` with `get_receipt` every `poll_interval`
+/// until the node knows the transaction, for at most `wait_timeout`. Query
+/// errors are returned as is, running out of time as [`ReceiptTimeout`].
+pub async fn wait_for_receipt<F, Fut, E>(
+    tx_hash: &str,
+    poll_interval: Duration,
+    wait_timeout: Duration,
+    mut get_receipt: F,
+) -> Result<TxReceipt, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<TxReceipt>, E>>,
+    E: From<ReceiptTimeout>,
+{
+    let started = Instant::now();
+
+    loop {
+        if let Some(receipt) = get_receipt().await? {
+            return Ok(receipt);
+        }
+
+        let waited = started.elapsed();
+        if waited >= wait_timeout {
+            return Err(ReceiptTimeout {
+                tx_hash: tx_hash.to_string(),
+                waited,
+            }
+            .into());
+        }
+        log::debug!("Txn {} | No receipt yet after {:?}", tx_hash, waited);
+        tokio::time::sleep(poll_interval.min(wait_timeout - waited)).await;
+    }
+}
@@ -0,0 +1,446 @@
+use crate::rpc::*;
+
+use serde_json::{json, Value};
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+fn events_response(events_data: Value) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "jsonrpc": "2.0",
+        "result": { "events_data": events_data },
+        "id": 1,
+    }))
+}
+
+fn get_events_request() -> Mock {
+    Mock::given(method("POST")).and(body_partial_json(json!({
+        "method": "l1x_getEvents",
+        "params": { "request": { "tx_hash": "deadbeef" } },
+    })))
+}
+
+async fn wait(
+    server: &MockServer,
+    wait_timeout: Duration,
+) -> Result<Vec<Vec<u8>>, RpcError> {
+    let endpoint = server.uri();
+    let endpoint = endpoint.as_str();
+
+    wait_for_events("deadbeef", POLL_INTERVAL, wait_timeout, || {
+        get_events(endpoint, "deadbeef", DEFAULT_RPC_TIMEOUT)
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_wait_for_events_returns_available_events() {
+    // Arrange
+    let server = MockServer::start().await;
+    get_events_request()
+        .respond_with(events_response(json!([[1, 2, 3]])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Act
+    let events = wait(&server, Duration::from_secs(5)).await;
+
+    // Assert
+    assert_eq!(events.unwrap(), vec![vec![1, 2, 3]]);
+}
+
+#[tokio::test]
+async fn test_wait_for_events_polls_until_events_arrive() {
+    // Arrange
+    let server = MockServer::start().await;
+    get_events_request()
+        .respond_with(events_response(json!([])))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .expect(2)
+        .mount(&server)
+        .await;
+    get_events_request()
+        .respond_with(events_response(json!([[4], [5, 6]])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Act
+    let events = wait(&server, Duration::from_secs(5)).await;
+
+    // Assert
+    assert_eq!(events.unwrap(), vec![vec![4], vec![5, 6]]);
+}
+
+#[tokio::test]
+async fn test_wait_for_events_times_out() {
+    // Arrange
+    let server = MockServer::start().await;
+    get_events_request()
+        .respond_with(events_response(json!([])))
+        .mount(&server)
+        .await;
+    let wait_timeout = Duration::from_millis(50);
+
+    // Act
+    let result = wait(&server, wait_timeout).await;
+
+    // Assert
+    match result {
+        Err(RpcError::EventsTimeout(timeout)) => {
+            assert_eq!(timeout.tx_hash, "deadbeef");
+            assert!(timeout.waited >= wait_timeout);
+        }
+        other => panic!("expected an events timeout, got {:?}", other),
+    }
+    assert!(server.received_requests().await.unwrap().len() > 1);
+}
+
+#[tokio::test]
+async fn test_wait_for_events_returns_query_errors() {
+    let server = MockServer::start().await;
+    get_events_request()
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32000, "message": "unknown transaction" },
+            "id": 1,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = wait(&server, Duration::from_secs(5)).await;
+
+    assert!(matches!(result, Err(RpcError::Rpc { code: -32000, .. })));
+}
+
+const RETRY_DELAY: Duration = Duration::from_millis(1);
+
+async fn get_events_with_retries(
+    server: &MockServer,
+    retries: u32,
+) -> Result<Vec<Vec<u8>>, RpcError> {
+    let endpoint = server.uri();
+    let endpoint = endpoint.as_str();
+
+    with_retries(
+        "l1x_getEvents",
+        RetryPolicy::new(retries, RETRY_DELAY),
+        RpcError::is_retryable,
+        || get_events(endpoint, "deadbeef", DEFAULT_RPC_TIMEOUT),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_with_retries_succeeds_after_server_errors() {
+    // Arrange
+    let server = MockServer::start().await;
+    get_events_request()
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    get_events_request()
+        .respond_with(events_response(json!([[1, 2]])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Act
+    let result = get_events_with_retries(&server, 3).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![vec![1, 2]]);
+}
+
+#[tokio::test]
+async fn test_with_retries_gives_up_after_the_last_retry() {
+    // Arrange
+    let server = MockServer::start().await;
+    get_events_request()
+        .respond_with(ResponseTemplate::new(502))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    // Act
+    let result = get_events_with_retries(&server, 2).await;
+
+    // Assert
+    assert!(matches!(result, Err(RpcError::HttpStatus { status: 502, .. })));
+}
+
+#[tokio::test]
+async fn test_with_retries_does_not_retry_node_errors() {
+    let server = MockServer::start().await;
+    get_events_request()
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32000, "message": "unknown transaction" },
+            "id": 1,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = get_events_with_retries(&server, 3).await;
+
+    assert!(matches!(result, Err(RpcError::Rpc { code: -32000, .. })));
+}
+
+#[tokio::test]
+async fn test_with_retries_retries_connection_errors() {
+    let attempts = std::sync::atomic::AtomicU32::new(0);
+
+    let result = with_retries(
+        "l1x_getEvents",
+        RetryPolicy::new(2, RETRY_DELAY),
+        RpcError::is_retryable,
+        || {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            get_events("http://127.0.0.1:1", "deadbeef", DEFAULT_RPC_TIMEOUT)
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(RpcError::Transport { .. })));
+    assert_eq!(attempts.into_inner(), 3);
+}
+
+#[test]
+fn test_retry_backoff_doubles_with_jitter_up_to_the_cap() {
+    let policy = RetryPolicy::new(5, Duration::from_millis(100));
+
+    for _ in 0..20 {
+        let first = policy.backoff(1);
+        let third = policy.backoff(3);
+        let capped = policy.backoff(20);
+
+        assert!(first >= Duration::from_millis(50), "{:?}", first);
+        assert!(first <= Duration::from_millis(100), "{:?}", first);
+        assert!(third >= Duration::from_millis(200), "{:?}", third);
+        assert!(third <= Duration::from_millis(400), "{:?}", third);
+        assert!(capped <= Duration::from_secs(10), "{:?}", capped);
+    }
+    assert_eq!(RetryPolicy::NONE.backoff(1), Duration::ZERO);
+}
+
+#[test]
+fn test_receipt_status_of_node_versions() {
+    let failed = |message: &str| TxStatus::Failed(message.to_string());
+
+    for (result, status) in [
+        (json!({ "block_number": 10 }), Some(TxStatus::Success)),
+        (json!({ "status": "Success" }), Some(TxStatus::Success)),
+        (json!({ "status": true }), Some(TxStatus::Success)),
+        (json!({ "status": 1 }), Some(TxStatus::Success)),
+        (
+            json!({ "status": "failed", "error": "out of fee" }),
+            Some(failed("out of fee")),
+        ),
+        (json!({ "status": 0, "error_message": "trap" }), Some(failed("trap"))),
+        (json!({ "status": false }), Some(failed("status false"))),
+        (Value::Null, None),
+    ] {
+        assert_eq!(
+            TxReceipt::from_result(&result).map(|receipt| receipt.status),
+            status,
+            "{}",
+            result
+        );
+    }
+}
+
+#[test]
+fn test_receipt_fee_used() {
+    for (result, fee_used) in [
+        (json!({ "status": "success", "fee_used": 42 }), Some(42)),
+        (json!({ "status": "success", "fee_used": "42" }), Some(42)),
+        (json!({ "status": "success", "gas_used": 21000 }), Some(21000)),
+        (json!({ "status": 1, "gasUsed": "0x5208" }), Some(21000)),
+        (json!({ "status": "success" }), None),
+        (json!({ "status": "success", "fee_used": "lots" }), None),
+    ] {
+        assert_eq!(
+            TxReceipt::from_result(&result).unwrap().fee_used,
+            fee_used,
+            "{}",
+            result
+        );
+    }
+}
+
+fn get_receipt_request() -> Mock {
+    Mock::given(method("POST")).and(body_partial_json(json!({
+        "method": "l1x_getTxReceipt",
+        "params": { "request": { "hash": "deadbeef" } },
+    })))
+}
+
+fn receipt_response(result: Value) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "jsonrpc": "2.0",
+        "result": result,
+        "id": 1,
+    }))
+}
+
+async fn wait_receipt(
+    server: &MockServer,
+    wait_timeout: Duration,
+) -> Result<TxReceipt, RpcError> {
+    let endpoint = server.uri();
+    let endpoint = endpoint.as_str();
+
+    wait_for_receipt("deadbeef", POLL_INTERVAL, wait_timeout, || {
+        get_transaction_receipt(
+            endpoint,
+            "l1x_getTxReceipt",
+            "deadbeef",
+            DEFAULT_RPC_TIMEOUT,
+        )
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_wait_for_receipt_polls_until_the_transaction_is_known() {
+    // Arrange
+    let server = MockServer::start().await;
+    get_receipt_request()
+        .respond_with(receipt_response(Value::Null))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    get_receipt_request()
+        .respond_with(receipt_response(
+            json!({ "block_number": "12", "status": "failed", "message": "trap" }),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Act
+    let receipt = wait_receipt(&server, Duration::from_secs(5)).await;
+
+    // Assert
+    assert_eq!(
+        receipt.unwrap(),
+        TxReceipt {
+            block_number: Some(12),
+            status: TxStatus::Failed("trap".to_string()),
+            fee_used: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_wait_for_receipt_times_out() {
+    let server = MockServer::start().await;
+    get_receipt_request()
+        .respond_with(receipt_response(Value::Null))
+        .mount(&server)
+        .await;
+    let wait_timeout = Duration::from_millis(50);
+
+    let result = wait_receipt(&server, wait_timeout).await;
+
+    match result {
+        Err(RpcError::ReceiptTimeout(timeout)) => {
+            assert_eq!(timeout.tx_hash, "deadbeef");
+            assert!(timeout.waited >= wait_timeout);
+        }
+        other => panic!("expected a receipt timeout, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_contract_code_of_hex_byte_array_and_unknown_contracts() {
+    // Arrange
+    let server = MockServer::start().await;
+    for (byte, code) in
+        [("a", json!("0x7f45")), ("b", json!([127, 69])), ("c", Value::Null)]
+    {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": CONTRACT_CODE_METHOD,
+                "params": { "request": { "address": byte.repeat(40) } },
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "result": { "code": code },
+                "id": 1,
+            })))
+            .mount(&server)
+            .await;
+    }
+    let endpoint = server.uri();
+
+    // Act
+    let mut codes = Vec::new();
+    for byte in ["a", "b", "c"] {
+        let address = byte.repeat(40);
+        codes.push(
+            get_contract_code(&endpoint, &address, DEFAULT_RPC_TIMEOUT).await,
+        );
+    }
+
+    // Assert
+    let codes: Vec<_> = codes.into_iter().map(Result::unwrap).collect();
+    assert_eq!(codes, [Some(vec![0x7f, 0x45]), Some(vec![0x7f, 0x45]), None]);
+}
+
+#[tokio::test]
+async fn test_call_response_with_another_id_is_an_id_mismatch() {
+    // Arrange
+    let server = MockServer::start().await;
+    get_events_request()
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "result": { "events_data": [] },
+            "id": "1",
+        })))
+        .mount(&server)
+        .await;
+
+    // Act
+    let events =
+        get_events(&server.uri(), "deadbeef", DEFAULT_RPC_TIMEOUT).await;
+
+    // Assert
+    assert!(matches!(
+        events,
+        Err(RpcError::IdMismatch { sent: 1, received, .. }) if received == "\"1\""
+    ));
+}
+
+#[test]
+fn test_response_ids_of_requests() {
+    let result = |id: Value| json!({ "jsonrpc": "2.0", "result": 1, "id": id });
+    let error = |id: Value| json!({ "jsonrpc": "2.0", "error": { "code": -32700 }, "id": id });
+
+    assert!(check_response_id("m", 5, &result(json!(5))).is_ok());
+    assert!(check_response_id("m", 5, &error(Value::Null)).is_ok());
+    for response in [
+        result(json!(6)),
+        result(Value::Null),
+        json!({ "jsonrpc": "2.0", "result": 1 }),
+        error(json!(6)),
+    ] {
+        assert!(
+            matches!(
+                check_response_id("m", 5, &response),
+                Err(RpcError::IdMismatch { sent: 5, .. })
+            ),
+            "{}",
+            response
+        );
+    }
+}
@@ -1,381 +1,2892 @@
 use std::{
+    any::{Any, TypeId},
     collections::{BTreeMap, HashMap},
     env,
     error::Error,
     fs,
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use config::{Config, File};
+use config::{Config, Environment, File, FileFormat};
+use fs2::FileExt;
 use serde::{Deserialize, Serialize};
 
+use crate::transport::L1XProtocol;
+use crate::types::AddressStr;
+
+/// Workspace root holding `l1x-conf/`, [`user_config_ws_home`] when unset
+pub const WS_HOME_ENV: &str = "L1X_CFG_WS_HOME";
+
+/// Directory of the workspace under the user's configuration directory
+pub const USER_CONFIG_DIR_NAME: &str = "l1x";
+
+/// Name of the active network of `l1x_chain_config.yaml`
+pub const CHAIN_TYPE_ENV: &str = "L1X_CFG_CHAIN_TYPE";
+
+/// Directory `vm-install-contract --dump-payloads` writes payloads under
+pub const CLI_SCRIPTS_ENV: &str = "L1X_CFG_CLI_SCRIPTS";
+
+/// Prefix of the environment variables overriding fields of the network and
+/// wallet configuration, `__` separates the prefix and the nested keys, e.g.
+/// `L1X__NETWORKS__LOCAL_DEVNET__RPC_ENDPOINT`
+pub const ENV_OVERRIDE_PREFIX: &str = "L1X";
+
+const ENV_OVERRIDE_SEPARATOR: &str = "__";
+
+/// Failure to find, read or look up the toolkit configuration files
+#[derive(Debug, thiserror::Error)]
+pub enum ToolkitConfigError {
+    #[error("The {0} environment variable must be set")]
+    MissingEnvVar(&'static str),
+    #[error("Configuration file {0} not found")]
+    MissingConfigFile(String),
+    #[error("Failed to load {path} :: {source}")]
+    ConfigParse {
+        path: String,
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
+    #[error("{path} is not valid :: {reason}")]
+    InvalidConfig { path: String, reason: String },
+    #[error("Key '{key}' not found in {path}")]
+    MissingConfigKey { key: String, path: String },
+    #[error("Invalid value for '{key}' :: {reason}")]
+    InvalidConfigValue { key: String, reason: String },
+    #[error("Unable to write {path} :: {source}")]
+    ConfigWrite {
+        path: String,
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
+    #[error(
+        "chain type '{network}' not found; available chain types: {available}"
+    )]
+    UnknownNetwork { network: String, available: String },
+    #[error("network '{network}' of {path} is invalid :: {reason}")]
+    InvalidNetwork { network: String, path: String, reason: String },
+    #[error(
+        "network '{network}' already exists in {path}, use --force to replace it"
+    )]
+    NetworkExists { network: String, path: String },
+    #[error(
+        "No network selected: pass --network, set {} or default_network in l1x_chain_config.yaml; available chain types: {available}",
+        CHAIN_TYPE_ENV
+    )]
+    NoActiveNetwork { available: String },
+    #[error("wallet '{owner}' not found; available wallets: {available}")]
+    UnknownOwner { owner: String, available: String },
+    #[error(
+        "wallet '{owner}' already exists in {path}, use --force to replace it"
+    )]
+    OwnerExists { owner: String, path: String },
+    #[error("Artifact '{0}' not found in the contract registry")]
+    RegistryMissingArtifact(String),
+    #[error(
+        "Contract instance '{contract_id}' not found for artifact '{artifact_id}'"
+    )]
+    RegistryMissingInstance { artifact_id: String, contract_id: String },
+    #[error("Invalid registry manifest :: {0}")]
+    InvalidRegistryManifest(String),
+    #[error(
+        "The manifest conflicts with the registry of network '{network}':\n{conflicts}"
+    )]
+    RegistryImportConflict { network: String, conflicts: String },
+    #[error("Invalid private key from {0}: expected 32 hex encoded bytes")]
+    InvalidPrivateKey(String),
+    #[error(
+        "No signing key: pass --owner or --private-key, or set {}",
+        PRIVATE_KEY_ENV
+    )]
+    MissingSigningKey,
+}
+
+impl ToolkitConfigError {
+    fn parse(
+        path: &str,
+        source: impl Into<Box<dyn Error + Send + Sync>>,
+    ) -> Self {
+        ToolkitConfigError::ConfigParse {
+            path: path.to_string(),
+            source: source.into(),
+        }
+    }
+
+    fn write(
+        path: &str,
+        source: impl Into<Box<dyn Error + Send + Sync>>,
+    ) -> Self {
+        ToolkitConfigError::ConfigWrite {
+            path: path.to_string(),
+            source: source.into(),
+        }
+    }
+}
+
+/// Value of the environment variable `name`, an error when unset or empty
+fn required_env_var(name: &'static str) -> Result<String, ToolkitConfigError> {
+    match env::var(name) {
+        Ok(value) if !value.is_empty() => Ok(value),
+        _ => Err(ToolkitConfigError::MissingEnvVar(name)),
+    }
+}
+
+/// `$XDG_CONFIG_HOME/l1x`, else `l1x` under the platform configuration
+/// directory, e.g. `~/.config/l1x` on Linux. `None` without a home directory.
+pub fn user_config_ws_home() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|config_dir| config_dir.is_absolute())
+        .or_else(|| {
+            directories::BaseDirs::new()
+                .map(|base_dirs| base_dirs.config_dir().to_path_buf())
+        })
+        .map(|config_dir| config_dir.join(USER_CONFIG_DIR_NAME))
+}
+
+/// Where the workspace root of [`ToolkitPaths`] comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WsHomeSource {
+    /// Given by the caller, e.g. with `--ws-home`
+    Explicit,
+    /// `$L1X_CFG_WS_HOME`
+    Env,
+    /// [`user_config_ws_home`]
+    UserConfigDir,
+}
+
+impl std::fmt::Display for WsHomeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WsHomeSource::Explicit => f.write_str("explicit"),
+            WsHomeSource::Env => f.write_str(WS_HOME_ENV),
+            WsHomeSource::UserConfigDir => f.write_str("user config directory"),
+        }
+    }
+}
+
+/// Location of the configuration files of a workspace
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ToolkitPaths {
+    ws_home: PathBuf,
+    source: WsHomeSource,
+}
+
+impl ToolkitPaths {
+    /// Paths of the workspace rooted at `ws_home`
+    pub fn new(ws_home: PathBuf) -> Self {
+        ToolkitPaths { ws_home, source: WsHomeSource::Explicit }
+    }
+
+    /// Paths of the workspace of `$L1X_CFG_WS_HOME`, falling back to
+    /// [`user_config_ws_home`] when unset
+    pub fn from_env() -> Result<Self, ToolkitConfigError> {
+        Self::resolve(None)
+    }
+
+    /// Paths of the workspace `ws_home` when given, else of
+    /// [`ToolkitPaths::from_env`]
+    pub fn resolve(
+        ws_home: Option<PathBuf>,
+    ) -> Result<Self, ToolkitConfigError> {
+        Self::resolve_from(
+            ws_home,
+            required_env_var(WS_HOME_ENV).ok(),
+            user_config_ws_home(),
+        )
+    }
+
+    /// [`ToolkitPaths::resolve`] on the given `$L1X_CFG_WS_HOME` and user
+    /// configuration directory
+    pub(crate) fn resolve_from(
+        ws_home: Option<PathBuf>,
+        env_ws_home: Option<String>,
+        user_config_ws_home: Option<PathBuf>,
+    ) -> Result<Self, ToolkitConfigError> {
+        match (ws_home, env_ws_home, user_config_ws_home) {
+            (Some(ws_home), _, _) => Ok(Self::new(ws_home)),
+            (None, Some(ws_home), _) => Ok(ToolkitPaths {
+                ws_home: ws_home.into(),
+                source: WsHomeSource::Env,
+            }),
+            (None, None, Some(ws_home)) => Ok(ToolkitPaths {
+                ws_home,
+                source: WsHomeSource::UserConfigDir,
+            }),
+            (None, None, None) => {
+                Err(ToolkitConfigError::MissingEnvVar(WS_HOME_ENV))
+            }
+        }
+    }
+
+    pub fn ws_home(&self) -> &Path {
+        &self.ws_home
+    }
+
+    pub fn ws_home_source(&self) -> WsHomeSource {
+        self.source
+    }
+
+    /// Path of the configuration file `file_name` of `l1x-conf/`
+    pub fn config_file(&self, file_name: &str) -> PathBuf {
+        self.ws_home.join("l1x-conf").join(file_name)
+    }
+
+    /// Configuration file `file_stem` of `l1x-conf/` in TOML when it exists,
+    /// else in YAML
+    fn probed_config_file(&self, file_stem: &str) -> PathBuf {
+        let toml_file = self.config_file(&format!("{}.toml", file_stem));
+        if toml_file.is_file() {
+            toml_file
+        } else {
+            self.config_file(&format!("{}.yaml", file_stem))
+        }
+    }
+
+    pub fn chain_config_file(&self) -> PathBuf {
+        self.probed_config_file("l1x_chain_config")
+    }
+
+    pub fn wallet_config_file(&self) -> PathBuf {
+        self.probed_config_file("l1x_dev_wallets")
+    }
+
+    pub fn template_config_file(&self) -> PathBuf {
+        self.config_file("l1x_templates.yaml")
+    }
+
+    pub fn contract_address_registry_file(&self) -> PathBuf {
+        self.probed_config_file("config-contract-address-registry")
+    }
+}
+
+/// Toolkit configuration of one workspace. The getters load the files on
+/// every call, parsing them again once they change, so a handle always sees
+/// the current workspace state.
+#[derive(Clone, Debug)]
+pub struct ToolkitConfig {
+    paths: ToolkitPaths,
+    /// Active network, `None` falls back to the `default_network` of
+    /// `l1x_chain_config.yaml`
+    chain_type: Option<String>,
+    env_overrides: EnvOverrides,
+}
+
+/// Variables the `L1X__` overrides are read from, the process environment
+/// unless replaced
+#[derive(Clone, Default)]
+struct EnvOverrides(Option<HashMap<String, String>>);
+
+impl EnvOverrides {
+    fn environment(&self) -> Environment {
+        Environment::with_prefix(ENV_OVERRIDE_PREFIX)
+            .prefix_separator(ENV_OVERRIDE_SEPARATOR)
+            .separator(ENV_OVERRIDE_SEPARATOR)
+            .source(self.0.clone())
+    }
+
+    /// Whether any override is set, to explain the naming when they don't
+    /// deserialize
+    fn any(&self) -> bool {
+        let prefix =
+            format!("{}{}", ENV_OVERRIDE_PREFIX, ENV_OVERRIDE_SEPARATOR);
+        match &self.0 {
+            Some(vars) => vars.keys().any(|key| key.starts_with(&prefix)),
+            None => env::vars_os().any(|(key, _)| {
+                key.to_str().map_or(false, |key| key.starts_with(&prefix))
+            }),
+        }
+    }
+}
+
+// The overrides may carry private keys
+impl std::fmt::Debug for EnvOverrides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(_) => f.write_str("EnvOverrides(<redacted>)"),
+            None => f.write_str("EnvOverrides(process)"),
+        }
+    }
+}
+
+impl ToolkitConfig {
+    pub fn new(paths: ToolkitPaths) -> Self {
+        ToolkitConfig {
+            paths,
+            chain_type: None,
+            env_overrides: EnvOverrides::default(),
+        }
+    }
+
+    /// Configuration of the workspace of [`ToolkitPaths::from_env`] on the
+    /// `$L1X_CFG_CHAIN_TYPE` network
+    pub fn from_env() -> Result<Self, ToolkitConfigError> {
+        Ok(ToolkitConfig {
+            paths: ToolkitPaths::from_env()?,
+            chain_type: required_env_var(CHAIN_TYPE_ENV).ok(),
+            env_overrides: EnvOverrides::default(),
+        })
+    }
+
+    pub fn with_chain_type(self, chain_type: &str) -> Self {
+        ToolkitConfig { chain_type: Some(chain_type.to_string()), ..self }
+    }
+
+    /// Switch to the `--network` of a command when given, keeping the
+    /// current chain type otherwise
+    pub fn with_network(self, network: Option<&str>) -> Self {
+        match network {
+            Some(network) => self.with_chain_type(network),
+            None => self,
+        }
+    }
+
+    /// Read the `L1X__` overrides from `vars` instead of the process
+    /// environment
+    pub fn with_env_overrides(self, vars: HashMap<String, String>) -> Self {
+        ToolkitConfig { env_overrides: EnvOverrides(Some(vars)), ..self }
+    }
+
+    pub fn paths(&self) -> &ToolkitPaths {
+        &self.paths
+    }
+
+    pub fn chain_type(&self) -> Option<&str> {
+        self.chain_type.as_deref()
+    }
+}
+
+/// Parsed configuration files, reused while the modification time and the
+/// length of the file are unchanged
+#[derive(Default)]
+struct ConfigFileCache {
+    entries: HashMap<(PathBuf, TypeId), CachedConfigFile>,
+    /// Number of times each file was parsed into the cache
+    reads: HashMap<PathBuf, usize>,
+}
+
+struct CachedConfigFile {
+    modified: SystemTime,
+    len: u64,
+    config: Arc<dyn Any + Send + Sync>,
+}
+
+fn config_file_cache() -> std::sync::MutexGuard<'static, ConfigFileCache> {
+    static CONFIG_FILE_CACHE: OnceLock<Mutex<ConfigFileCache>> =
+        OnceLock::new();
+
+    CONFIG_FILE_CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// Drop the parsed `file_path` from the cache, for the writers of the file
+pub fn invalidate_config_file(file_path: &Path) {
+    config_file_cache()
+        .entries
+        .retain(|(cached_path, _), _| cached_path != file_path);
+}
+
+/// Number of times `file_path` was read, to check the cache
+#[cfg(test)]
+pub(crate) fn config_file_reads(file_path: &Path) -> usize {
+    config_file_cache().reads.get(file_path).copied().unwrap_or_default()
+}
+
+/// Format of the configuration file `file_path`, TOML for a `.toml` file
+fn config_file_format(file_path: &Path) -> FileFormat {
+    match file_path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => FileFormat::Toml,
+        _ => FileFormat::Yaml,
+    }
+}
+
+/// Deserialize `file_content` of the configuration file `file_path` in the
+/// format of the file
+fn from_config_str<T: serde::de::DeserializeOwned>(
+    file_path: &str,
+    file_content: &str,
+) -> Result<T, Box<dyn Error + Send + Sync>> {
+    match config_file_format(Path::new(file_path)) {
+        FileFormat::Toml => Ok(toml::from_str(file_content)?),
+        _ => Ok(serde_yaml::from_str(file_content)?),
+    }
+}
+
+/// Deserialize the YAML or TOML configuration file `file_path`, layered with
+/// `env_overrides` when given. Parsed files are cached until they change,
+/// those layered with overrides always get loaded.
+fn load_config_file<T>(
+    file_path: &Path,
+    required: bool,
+    env_overrides: Option<&EnvOverrides>,
+) -> Result<T, ToolkitConfigError>
+where
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let file_state = fs::metadata(file_path)
+        .ok()
+        .and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+    let (modified, len) = match file_state {
+        Some(file_state) if !env_overrides.map_or(false, EnvOverrides::any) => {
+            file_state
+        }
+        _ => return parse_config_file(file_path, required, env_overrides),
+    };
+
+    let key = (file_path.to_path_buf(), TypeId::of::<T>());
+    let mut cache = config_file_cache();
+    if let Some(cached) = cache.entries.get(&key) {
+        if cached.modified == modified && cached.len == len {
+            if let Some(config) = cached.config.downcast_ref::<T>() {
+                return Ok(config.clone());
+            }
+        }
+    }
+
+    // Parse under the lock, concurrent loads of a changed file read it once
+    *cache.reads.entry(file_path.to_path_buf()).or_default() += 1;
+    let config: T = parse_config_file(file_path, required, None)?;
+    cache.entries.insert(
+        key,
+        CachedConfigFile { modified, len, config: Arc::new(config.clone()) },
+    );
+
+    Ok(config)
+}
+
+fn parse_config_file<T: serde::de::DeserializeOwned>(
+    file_path: &Path,
+    required: bool,
+    env_overrides: Option<&EnvOverrides>,
+) -> Result<T, ToolkitConfigError> {
+    let file_format = config_file_format(file_path);
+    let file_path = &file_path.display().to_string();
+    let mut builder = Config::builder();
+    // Load the YAML or TOML configuration files.
+    builder = match fs::read_to_string(file_path) {
+        Ok(file_content) => {
+            builder.add_source(File::from_str(&file_content, file_format))
+        }
+        Err(err_code)
+            if required && err_code.kind() == std::io::ErrorKind::NotFound =>
+        {
+            return Err(ToolkitConfigError::MissingConfigFile(
+                file_path.clone(),
+            ))
+        }
+        Err(_) => {
+            builder.add_source(File::with_name(file_path).required(required))
+        }
+    };
+    if let Some(env_overrides) = env_overrides {
+        builder = builder.add_source(env_overrides.environment());
+    }
+    let settings = builder
+        .build()
+        .map_err(|err_code| ToolkitConfigError::parse(file_path, err_code))?;
+
+    settings.try_deserialize().map_err(|err_code| {
+        log::error!("Failed to deserialize YAML configuration file :: {:#?} :: err {:#?}", file_path, err_code );
+        match env_overrides {
+            Some(env_overrides) if env_overrides.any() => {
+                ToolkitConfigError::parse(
+                    file_path,
+                    format!(
+                        "{}; environment overrides are named L1X__<KEY>__<NESTED_KEY>, e.g. L1X__NETWORKS__LOCAL__RPC_ENDPOINT",
+                        err_code
+                    ),
+                )
+            }
+            _ => ToolkitConfigError::parse(file_path, err_code),
+        }
+    })
+}
+
 // Define structs to represent the configuration files.
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct WalletConfig {
-    dev_accounts: HashMap<String, DevAccount>,
-}
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct WalletConfig {
+    dev_accounts: HashMap<String, DevAccount>,
+    /// Wallet funding the other dev accounts with `faucet`, usually the
+    /// genesis account of a local devnet
+    #[serde(default)]
+    faucet_account: Option<String>,
+}
+
+impl WalletConfig {
+    pub fn dev_accounts(&self) -> &HashMap<String, DevAccount> {
+        &self.dev_accounts
+    }
+
+    pub fn faucet_account(&self) -> Option<&str> {
+        self.faucet_account.as_deref()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DevAccount {
+    priv_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub_key: Option<String>,
+}
+
+impl DevAccount {
+    pub fn new(priv_key: &str, pub_key: Option<&str>) -> Self {
+        DevAccount {
+            priv_key: priv_key.to_string(),
+            pub_key: pub_key.map(str::to_string),
+        }
+    }
+
+    pub fn priv_key(&self) -> &str {
+        &self.priv_key
+    }
+
+    pub fn pub_key(&self) -> Option<&str> {
+        self.pub_key.as_deref()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct NetworkConfig {
+    networks: HashMap<String, Network>,
+    /// Artifact directory per VM type, relative to L1X_CFG_WS_HOME unless
+    /// absolute
+    #[serde(default)]
+    artifact_dirs: HashMap<String, String>,
+    /// Network used when neither --network nor L1X_CFG_CHAIN_TYPE is given
+    #[serde(default)]
+    default_network: Option<String>,
+}
+
+impl NetworkConfig {
+    pub fn networks(&self) -> &HashMap<String, Network> {
+        &self.networks
+    }
+
+    pub fn default_network(&self) -> Option<&str> {
+        self.default_network.as_deref()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Network {
+    chain_id: u32,
+    host_ip: String,
+    rpc_port: u32,
+    /// Made of `host_ip` and `rpc_port` when empty
+    #[serde(default)]
+    rpc_endpoint: String,
+    /// Cluster contracts are deployed to, needed to predict their addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cluster_address: Option<AddressStr>,
+    /// Protocol `rpc_endpoint` serves transactions with
+    #[serde(default, skip_serializing_if = "L1XProtocol::is_json")]
+    protocol: L1XProtocol,
+    /// Development network, commands submit to it without confirmation
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    is_dev: bool,
+}
+
+/// Parse `endpoint` as the URL of a JSON-RPC endpoint, an error message when
+/// it isn't an http(s) URL with a host
+pub fn parse_rpc_endpoint(endpoint: &str) -> Result<reqwest::Url, String> {
+    let url = reqwest::Url::parse(endpoint).map_err(|err_code| {
+        format!("rpc_endpoint '{}' is not a URL: {}", endpoint, err_code)
+    })?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!(
+            "rpc_endpoint '{}' must use http or https, not {}",
+            endpoint,
+            url.scheme()
+        ));
+    }
+    if url.host_str().is_none() {
+        return Err(format!("rpc_endpoint '{}' has no host", endpoint));
+    }
+
+    Ok(url)
+}
+
+impl Network {
+    pub fn new(
+        chain_id: u32,
+        host_ip: &str,
+        rpc_port: u32,
+        rpc_endpoint: &str,
+    ) -> Self {
+        Network {
+            chain_id,
+            host_ip: host_ip.to_string(),
+            rpc_port,
+            rpc_endpoint: rpc_endpoint.to_string(),
+            cluster_address: None,
+            protocol: L1XProtocol::default(),
+            is_dev: false,
+        }
+    }
+
+    pub fn chain_id(&self) -> u32 {
+        self.chain_id
+    }
+
+    pub fn host_ip(&self) -> &str {
+        &self.host_ip
+    }
+
+    pub fn rpc_port(&self) -> u32 {
+        self.rpc_port
+    }
+
+    pub fn rpc_endpoint(&self) -> &str {
+        &self.rpc_endpoint
+    }
+
+    pub fn cluster_address(&self) -> Option<AddressStr> {
+        self.cluster_address
+    }
+
+    pub fn protocol(&self) -> L1XProtocol {
+        self.protocol
+    }
+
+    pub fn is_dev(&self) -> bool {
+        self.is_dev
+    }
+
+    /// Fill in an empty `rpc_endpoint` from `host_ip` and `rpc_port`, then
+    /// check it is an http(s) URL
+    fn normalize(&mut self) -> Result<(), String> {
+        self.rpc_endpoint = self.rpc_endpoint.trim().to_string();
+
+        if self.rpc_endpoint.is_empty() {
+            // IPv6 addresses are bracketed in URLs
+            let host = if self.host_ip.contains(':')
+                && !self.host_ip.starts_with('[')
+            {
+                format!("[{}]", self.host_ip)
+            } else {
+                self.host_ip.clone()
+            };
+            self.rpc_endpoint = format!("http://{}:{}", host, self.rpc_port);
+        }
+
+        parse_rpc_endpoint(&self.rpc_endpoint).map(|_| ())
+    }
+}
+
+impl ToolkitConfig {
+    /// The networks of `l1x_chain_config.yaml`, with their endpoints
+    /// normalized and validated
+    pub fn network_config(&self) -> Result<NetworkConfig, ToolkitConfigError> {
+        let chain_config_file = self.paths.chain_config_file();
+        let mut config_network: NetworkConfig = load_config_file(
+            &chain_config_file,
+            true,
+            Some(&self.env_overrides),
+        )?;
+
+        for (name, network) in config_network.networks.iter_mut() {
+            network.normalize().map_err(|reason| {
+                ToolkitConfigError::InvalidNetwork {
+                    network: name.clone(),
+                    path: chain_config_file.display().to_string(),
+                    reason,
+                }
+            })?;
+        }
+
+        Ok(config_network)
+    }
+
+    pub fn wallet_config(&self) -> Result<WalletConfig, ToolkitConfigError> {
+        load_config_file(
+            &self.paths.wallet_config_file(),
+            true,
+            Some(&self.env_overrides),
+        )
+    }
+}
+
+/// Change of `l1x_chain_config.yaml` applied by
+/// [`ToolkitConfig::update_network_config`]
+#[derive(Clone, Debug)]
+pub enum L1XNetworkUpdateType {
+    /// Register `network` as `name`, replacing an existing entry only with
+    /// `force`
+    Add {
+        name: String,
+        network: Network,
+        force: bool,
+    },
+    /// Drop `name`, and the `default_network` when it names it
+    Remove {
+        name: String,
+    },
+    SetDefault {
+        name: String,
+    },
+}
+
+impl ToolkitConfig {
+    /// Apply `update_type` to `l1x_chain_config.yaml`, creating the file when
+    /// missing.
+    ///
+    /// Works on the YAML mapping so unrelated keys and the order of the
+    /// networks survive; comments are lost, serde_yaml doesn't keep them.
+    pub fn update_network_config(
+        &self,
+        update_type: L1XNetworkUpdateType,
+    ) -> Result<(), ToolkitConfigError> {
+        let chain_config_file_path =
+            self.paths.chain_config_file().display().to_string();
+        let invalid_config = |reason: &str| ToolkitConfigError::InvalidConfig {
+            path: chain_config_file_path.clone(),
+            reason: reason.to_string(),
+        };
+
+        let mut config: serde_yaml::Value =
+            match fs::read_to_string(&chain_config_file_path) {
+                Ok(file_content) => {
+                    from_config_str(&chain_config_file_path, &file_content)
+                        .map_err(|err_code| {
+                            ToolkitConfigError::parse(
+                                &chain_config_file_path,
+                                err_code,
+                            )
+                        })?
+                }
+                Err(_) => serde_yaml::Value::Null,
+            };
+        if config.is_null() {
+            config = serde_yaml::Mapping::new().into();
+        }
+
+        let config_mapping = config
+            .as_mapping_mut()
+            .ok_or_else(|| invalid_config("not a YAML mapping"))?;
+        let networks = config_mapping
+            .entry("networks".into())
+            .or_insert(serde_yaml::Value::Null);
+        if networks.is_null() {
+            *networks = serde_yaml::Mapping::new().into();
+        }
+        let networks = networks
+            .as_mapping_mut()
+            .ok_or_else(|| invalid_config("networks is not a YAML mapping"))?;
+
+        let unknown_network = |name: &str, networks: &serde_yaml::Mapping| {
+            let mut names: Vec<&str> =
+                networks.keys().filter_map(serde_yaml::Value::as_str).collect();
+            names.sort_unstable();
+            ToolkitConfigError::UnknownNetwork {
+                network: name.to_string(),
+                available: names.join(", "),
+            }
+        };
+
+        match update_type {
+            L1XNetworkUpdateType::Add { name, network, force } => {
+                if networks.contains_key(name.as_str()) && !force {
+                    return Err(ToolkitConfigError::NetworkExists {
+                        network: name,
+                        path: chain_config_file_path,
+                    });
+                }
+
+                let network =
+                    serde_yaml::to_value(network).map_err(|err_code| {
+                        ToolkitConfigError::write(
+                            &chain_config_file_path,
+                            err_code,
+                        )
+                    })?;
+                networks.insert(name.into(), network);
+            }
+            L1XNetworkUpdateType::Remove { name } => {
+                if networks.remove(name.as_str()).is_none() {
+                    return Err(unknown_network(&name, networks));
+                }
+
+                let default_network = config_mapping.get("default_network");
+                if default_network.and_then(serde_yaml::Value::as_str)
+                    == Some(name.as_str())
+                {
+                    log::info!("Unsetting the default network {}", name);
+                    config_mapping.remove("default_network");
+                }
+            }
+            L1XNetworkUpdateType::SetDefault { name } => {
+                if !networks.contains_key(name.as_str()) {
+                    return Err(unknown_network(&name, networks));
+                }
+
+                config_mapping.insert("default_network".into(), name.into());
+            }
+        }
+
+        write_config_file(&chain_config_file_path, &config)
+    }
+}
+
+/// Template of `$L1X_CFG_WS_HOME/l1x-conf/l1x_templates.yaml`
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct TemplateHubEntry {
+    pub url: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl ToolkitConfig {
+    /// Load the templates of `l1x-conf/l1x_templates.yaml`, none when the
+    /// file is missing
+    pub fn template_config(
+        &self,
+    ) -> Result<HashMap<String, TemplateHubEntry>, ToolkitConfigError> {
+        load_config_file(&self.paths.template_config_file(), false, None)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L1XVMArtifactType {
+    Ebpf,
+    Evm,
+    Wasm,
+}
+
+impl L1XVMArtifactType {
+    /// Key of the `artifact_dirs` map in `l1x_chain_config.yaml`
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            Self::Ebpf => "ebpf",
+            Self::Evm => "evm",
+            Self::Wasm => "wasm",
+        }
+    }
+
+    /// Artifact type of the [`L1XVMArtifactType::config_key`] `key`
+    pub fn from_config_key(key: &str) -> Option<Self> {
+        [Self::Ebpf, Self::Evm, Self::Wasm]
+            .into_iter()
+            .find(|vm_type| vm_type.config_key() == key)
+    }
+
+    /// Artifact directory, relative to L1X_CFG_WS_HOME, without `artifact_dirs`
+    pub fn default_dir(&self) -> &'static str {
+        match self {
+            Self::Ebpf | Self::Wasm => "l1x-artifacts",
+            Self::Evm => "l1x-evm-artifacts",
+        }
+    }
+}
+
+/// VM a contract of `vm-install-contract` and `vm-sub-txn` targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum L1XVMType {
+    #[clap(name = "ebpf")]
+    L1xVmEbpf,
+    #[clap(name = "evm")]
+    L1xVmEvm,
+    #[clap(name = "wasm")]
+    L1xVmWasm,
+}
+
+impl From<L1XVMType> for L1XVMArtifactType {
+    fn from(vm_type: L1XVMType) -> Self {
+        match vm_type {
+            L1XVMType::L1xVmEbpf => Self::Ebpf,
+            L1XVMType::L1xVmEvm => Self::Evm,
+            L1XVMType::L1xVmWasm => Self::Wasm,
+        }
+    }
+}
+
+impl std::fmt::Display for L1XVMType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::L1xVmEbpf => write!(f, "L1xVmEbpf"),
+            Self::L1xVmEvm => write!(f, "L1xVmEvm"),
+            Self::L1xVmWasm => write!(f, "L1xVmWasm"),
+        }
+    }
+}
+
+impl ToolkitConfig {
+    /// Get the directory holding the build artifacts of `vm_type`
+    pub fn artifact_dir(&self, vm_type: L1XVMArtifactType) -> PathBuf {
+        let configured_dir = if self.paths.chain_config_file().exists() {
+            self.network_config()
+                .map_err(|err_code| {
+                    log::warn!(
+                        "Using the default artifact directories :: {:#?}",
+                        err_code
+                    );
+                })
+                .ok()
+                .and_then(|config_network| {
+                    config_network
+                        .artifact_dirs
+                        .get(vm_type.config_key())
+                        .cloned()
+                })
+        } else {
+            None
+        };
+
+        // `join` keeps absolute directories as they are
+        self.paths.ws_home.join(
+            configured_dir.unwrap_or_else(|| vm_type.default_dir().to_string()),
+        )
+    }
+}
+
+/// Comma separated, sorted names of `entries` for error messages
+fn available_names<V>(entries: &HashMap<String, V>) -> String {
+    let mut names: Vec<&str> = entries.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    names.join(", ")
+}
+
+impl ToolkitConfig {
+    /// Name of the active network: the chain type of the handle, i.e.
+    /// `--network` then `$L1X_CFG_CHAIN_TYPE`, else the `default_network` of
+    /// `l1x_chain_config.yaml`
+    pub fn active_network(&self) -> Result<String, ToolkitConfigError> {
+        if let Some(chain_type) = &self.chain_type {
+            return Ok(chain_type.clone());
+        }
+
+        let config_network = self.network_config()?;
+        config_network.default_network.clone().ok_or_else(|| {
+            ToolkitConfigError::NoActiveNetwork {
+                available: available_names(&config_network.networks),
+            }
+        })
+    }
+
+    pub fn active_chain_json_rpc_endpoint(
+        &self,
+    ) -> Result<String, ToolkitConfigError> {
+        self.chain_json_rpc_endpoint(&self.active_network()?)
+    }
+
+    /// `cluster_address` of the active network, `None` when it isn't set
+    pub fn active_cluster_address(
+        &self,
+    ) -> Result<Option<AddressStr>, ToolkitConfigError> {
+        let network = self.active_network()?;
+        let config_network = self.network_config()?;
+
+        config_network
+            .networks
+            .get(&network)
+            .map(Network::cluster_address)
+            .ok_or_else(|| ToolkitConfigError::UnknownNetwork {
+                available: available_names(&config_network.networks),
+                network,
+            })
+    }
+
+    /// Whether the active network is marked `is_dev`
+    pub fn active_network_is_dev(&self) -> Result<bool, ToolkitConfigError> {
+        let network = self.active_network()?;
+        let config_network = self.network_config()?;
+
+        config_network.networks.get(&network).map(Network::is_dev).ok_or_else(
+            || ToolkitConfigError::UnknownNetwork {
+                available: available_names(&config_network.networks),
+                network,
+            },
+        )
+    }
+
+    /// `protocol` of the active network, JSON-RPC when it isn't set
+    pub fn active_protocol(&self) -> Result<L1XProtocol, ToolkitConfigError> {
+        let network = self.active_network()?;
+        let config_network = self.network_config()?;
+
+        config_network.networks.get(&network).map(Network::protocol).ok_or_else(
+            || ToolkitConfigError::UnknownNetwork {
+                available: available_names(&config_network.networks),
+                network,
+            },
+        )
+    }
+
+    /// JSON-RPC endpoint of the network `network` of
+    /// `l1x_chain_config.yaml`
+    pub fn chain_json_rpc_endpoint(
+        &self,
+        network: &str,
+    ) -> Result<String, ToolkitConfigError> {
+        let config_network = self.network_config()?;
+
+        config_network
+            .networks
+            .get(network)
+            .map(|network| network.rpc_endpoint.clone())
+            .ok_or_else(|| ToolkitConfigError::UnknownNetwork {
+                available: available_names(&config_network.networks),
+                network: network.to_string(),
+            })
+    }
+
+    /// Get the wallet entry of `owner_id`
+    pub fn wallet_account(
+        &self,
+        owner_id: &str,
+    ) -> Result<DevAccount, ToolkitConfigError> {
+        let config_wallet = self.wallet_config()?;
+
+        config_wallet.dev_accounts.get(owner_id).cloned().ok_or_else(|| {
+            ToolkitConfigError::UnknownOwner {
+                owner: owner_id.to_string(),
+                available: available_names(&config_wallet.dev_accounts),
+            }
+        })
+    }
+
+    pub fn wallet_priv_key(
+        &self,
+        owner_id: &str,
+    ) -> Result<String, ToolkitConfigError> {
+        self.wallet_account(owner_id).map(|account| account.priv_key)
+    }
+}
+
+/// Hex private key signing transactions when no `--private-key` is given,
+/// ahead of the `--owner` wallet
+pub const PRIVATE_KEY_ENV: &str = "L1X_PRIVATE_KEY";
+
+impl ToolkitConfig {
+    /// Key signing the transactions of a command: `key_flag`, else
+    /// [`PRIVATE_KEY_ENV`], else the key of the wallet `owner`. Errors name where
+    /// the key came from, never the key itself.
+    pub fn resolve_signer(
+        &self,
+        owner: Option<&str>,
+        key_flag: Option<&str>,
+    ) -> Result<secp256k1::SecretKey, ToolkitConfigError> {
+        let parse_key = |source: &str, key: &str| {
+            crate::parse_secret_key(key).map_err(|_| {
+                ToolkitConfigError::InvalidPrivateKey(source.to_string())
+            })
+        };
+
+        if let Some(key) = key_flag {
+            return parse_key("--private-key", key);
+        }
+
+        match (env::var(PRIVATE_KEY_ENV), owner) {
+            (Ok(key), _) if !key.trim().is_empty() => {
+                parse_key(PRIVATE_KEY_ENV, &key)
+            }
+            (_, Some(owner)) => parse_key(
+                &format!("wallet '{}'", owner),
+                &self.wallet_priv_key(owner)?,
+            ),
+            (_, None) => Err(ToolkitConfigError::MissingSigningKey),
+        }
+    }
+
+    /// Add `account` as `owner_id` to the wallet YAML file, keeping the other
+    /// entries in their order. An existing `owner_id` is only replaced when
+    /// `force` is set.
+    pub fn add_wallet_account(
+        &self,
+        owner_id: &str,
+        account: &DevAccount,
+        force: bool,
+    ) -> Result<(), ToolkitConfigError> {
+        let wallet_config_file_path =
+            self.paths.wallet_config_file().display().to_string();
+
+        // Work on the YAML mapping, a `WalletConfig` would reorder the accounts
+        let mut config: serde_yaml::Value =
+            match fs::read_to_string(&wallet_config_file_path) {
+                Ok(file_content) => {
+                    from_config_str(&wallet_config_file_path, &file_content)
+                        .map_err(|err_code| {
+                            ToolkitConfigError::parse(
+                                &wallet_config_file_path,
+                                err_code,
+                            )
+                        })?
+                }
+                Err(_) => serde_yaml::Value::Null,
+            };
+        if config.is_null() {
+            config = serde_yaml::Mapping::new().into();
+        }
+
+        let config_mapping = config.as_mapping_mut().ok_or_else(|| {
+            ToolkitConfigError::InvalidConfig {
+                path: wallet_config_file_path.clone(),
+                reason: "not a YAML mapping".to_string(),
+            }
+        })?;
+        let dev_accounts = config_mapping
+            .entry("dev_accounts".into())
+            .or_insert(serde_yaml::Value::Null);
+        if dev_accounts.is_null() {
+            *dev_accounts = serde_yaml::Mapping::new().into();
+        }
+        let dev_accounts = dev_accounts.as_mapping_mut().ok_or_else(|| {
+            ToolkitConfigError::InvalidConfig {
+                path: wallet_config_file_path.clone(),
+                reason: "dev_accounts is not a YAML mapping".to_string(),
+            }
+        })?;
+
+        if dev_accounts.contains_key(owner_id) && !force {
+            return Err(ToolkitConfigError::OwnerExists {
+                owner: owner_id.to_string(),
+                path: wallet_config_file_path,
+            });
+        }
+
+        let account = serde_yaml::to_value(account).map_err(|err_code| {
+            ToolkitConfigError::write(&wallet_config_file_path, err_code)
+        })?;
+        dev_accounts.insert(owner_id.into(), account);
+
+        write_config_file(&wallet_config_file_path, &config)
+    }
+}
+
+// ================================================================================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum L1XVMContractAddressUpdateType {
+    L1XEBPF_DEPLOY {
+        artifact_id: String,
+        response_hash: String,
+        response_address: AddressStr,
+    },
+    L1XEBPF_INIT {
+        artifact_id: String,
+        contract_id: String,
+        response_hash: String,
+        response_address: AddressStr,
+    },
+    L1XEVM_DEPLOY {
+        artifact_id: String,
+        response_hash: String,
+        response_address: AddressStr,
+    },
+    L1XWASM_DEPLOY {
+        artifact_id: String,
+        response_hash: String,
+        response_address: AddressStr,
+    },
+    L1XWASM_INIT {
+        artifact_id: String,
+        contract_id: String,
+        response_hash: String,
+        response_address: AddressStr,
+    },
+}
+
+/// Contracts deployed to one network
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct L1XVMContractAddressRegistry {
+    l1x_vm: BTreeMap<String, L1XVMContractInfo>,
+    l1x_evm: BTreeMap<String, L1XVMContractInfo>,
+    /// Absent from the registries written before WASM deployments
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    l1x_wasm: BTreeMap<String, L1XVMContractInfo>,
+}
+
+impl L1XVMContractAddressRegistry {
+    /// Deployments of `vm_type`
+    fn section(
+        &self,
+        vm_type: L1XVMArtifactType,
+    ) -> &BTreeMap<String, L1XVMContractInfo> {
+        match vm_type {
+            L1XVMArtifactType::Ebpf => &self.l1x_vm,
+            L1XVMArtifactType::Evm => &self.l1x_evm,
+            L1XVMArtifactType::Wasm => &self.l1x_wasm,
+        }
+    }
+
+    fn section_mut(
+        &mut self,
+        vm_type: L1XVMArtifactType,
+    ) -> &mut BTreeMap<String, L1XVMContractInfo> {
+        match vm_type {
+            L1XVMArtifactType::Ebpf => &mut self.l1x_vm,
+            L1XVMArtifactType::Evm => &mut self.l1x_evm,
+            L1XVMArtifactType::Wasm => &mut self.l1x_wasm,
+        }
+    }
+}
+
+/// Layout of `config-contract-address-registry.yaml`, keyed by network name
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct L1XVMNetworkRegistries {
+    networks: BTreeMap<String, L1XVMContractAddressRegistry>,
+}
+
+impl L1XVMNetworkRegistries {
+    /// Registries of `registry_file`, a flat registry moving under `network`
+    fn rehome(
+        registry_file: Option<L1XVMContractAddressRegistryFile>,
+        network: &str,
+    ) -> Self {
+        match registry_file {
+            Some(L1XVMContractAddressRegistryFile::PerNetwork(registries)) => {
+                registries
+            }
+            Some(L1XVMContractAddressRegistryFile::Flat(registry)) => {
+                log::info!(
+                    "Moving the contract address registry under network {}",
+                    network
+                );
+                L1XVMNetworkRegistries {
+                    networks: BTreeMap::from([(network.to_string(), registry)]),
+                }
+            }
+            None => L1XVMNetworkRegistries::default(),
+        }
+    }
+}
+
+/// A registry file, in the per-network layout or the flat one of the
+/// workspaces created before it
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+enum L1XVMContractAddressRegistryFile {
+    PerNetwork(L1XVMNetworkRegistries),
+    /// Serves every network until the first update moves it under the
+    /// active network
+    Flat(L1XVMContractAddressRegistry),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct L1XVMContractInfo {
+    deploy_hash: String,
+    deploy_address: AddressStr,
+    #[serde(flatten)]
+    metadata: L1XVMDeployMetadata,
+    instance: BTreeMap<String, L1XVMInstanceInfo>,
+    /// Deployments replaced by a redeploy, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    previous: Vec<L1XVMPreviousDeployInfo>,
+}
+
+/// What a deployment of the toolkit put on chain, to audit the live bytecode.
+/// Registries written before it and contracts registered by hand have none.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1XVMDeployMetadata {
+    /// ISO-8601 UTC time of the registry update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    /// Wallet signing the deployment, none for a private key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Decimal, as the transaction requests carry it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_limit: Option<String>,
+    /// Fee the receipt of the deployment reports as consumed, decimal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_used: Option<String>,
+    /// Hex sha256 of the artifact file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_sha256: Option<String>,
+    /// Absolute path of an artifact file deployed from outside the artifact
+    /// directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_path: Option<String>,
+    /// Hex sha256 of the code `verify --update-registry` found on chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_sha256: Option<String>,
+}
+
+impl std::fmt::Display for L1XVMDeployMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields = [
+            ("deployed_at", &self.deployed_at),
+            ("network", &self.network),
+            ("owner", &self.owner),
+            ("fee_limit", &self.fee_limit),
+            ("fee_used", &self.fee_used),
+            ("artifact_sha256", &self.artifact_sha256),
+            ("artifact_path", &self.artifact_path),
+            ("verified_sha256", &self.verified_sha256),
+        ];
+
+        let mut separator = "";
+        for (name, value) in fields {
+            if let Some(value) = value {
+                write!(f, "{}{}={}", separator, name, value)?;
+                separator = " ";
+            }
+        }
+        Ok(())
+    }
+}
+
+/// ISO-8601 UTC form of `unix_secs`, e.g. `2023-11-14T22:13:20Z`
+pub(crate) fn iso8601_utc(unix_secs: u64) -> String {
+    // Civil date of the day count, after H. Hinnant's `civil_from_days`
+    let days = (unix_secs / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524
+        - day_of_era / 146096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month =
+        if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let secs_of_day = unix_secs % 86400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct L1XVMInstanceInfo {
+    inst_hash: String,
+    inst_address: AddressStr,
+    /// Instances replaced by a forced re-initialization, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    previous: Vec<L1XVMPreviousInstanceInfo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct L1XVMPreviousInstanceInfo {
+    inst_hash: String,
+    inst_address: AddressStr,
+}
+
+/// Deployment replaced by a redeploy, with the instances initialized on it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct L1XVMPreviousDeployInfo {
+    deploy_hash: String,
+    deploy_address: AddressStr,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    instance: BTreeMap<String, L1XVMInstanceInfo>,
+}
+
+impl ToolkitConfig {
+    /// Load the contract address registry from a YAML configuration file.
+    fn load_contract_address_registry_file(
+        &self,
+    ) -> Result<L1XVMContractAddressRegistryFile, ToolkitConfigError> {
+        load_config_file(
+            &self.paths.contract_address_registry_file(),
+            true,
+            None,
+        )
+    }
+
+    /// Contracts of the active network, all of a flat registry
+    fn load_contract_address_registry(
+        &self,
+    ) -> Result<L1XVMContractAddressRegistry, ToolkitConfigError> {
+        match self.load_contract_address_registry_file()? {
+            L1XVMContractAddressRegistryFile::PerNetwork(mut registries) => {
+                Ok(registries
+                    .networks
+                    .remove(&self.active_network()?)
+                    .unwrap_or_default())
+            }
+            L1XVMContractAddressRegistryFile::Flat(registry) => Ok(registry),
+        }
+    }
+
+    /// Hold the advisory lock of the registry until the returned file is
+    /// dropped, so concurrent l1x-forge runs update it one after the other
+    fn lock_contract_address_registry(
+        &self,
+    ) -> Result<fs::File, ToolkitConfigError> {
+        let lock_file_path = format!(
+            "{}.lock",
+            self.paths.contract_address_registry_file().display()
+        );
+
+        let lock_file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(&lock_file_path)
+            .map_err(|err_code| {
+                ToolkitConfigError::write(&lock_file_path, err_code)
+            })?;
+        lock_file.lock_exclusive().map_err(|err_code| {
+            ToolkitConfigError::write(&lock_file_path, err_code)
+        })?;
+
+        Ok(lock_file)
+    }
+
+    /// Read the registry file to update it, `None` when it doesn't exist. A
+    /// corrupted file is copied to `*.bak` and left for the user to fix.
+    fn read_contract_address_registry_file(
+        &self,
+        file_path: &str,
+    ) -> Result<Option<L1XVMContractAddressRegistryFile>, ToolkitConfigError>
+    {
+        let file_content = match fs::read_to_string(file_path) {
+            Ok(file_content) => file_content,
+            Err(_) => return Ok(None),
+        };
+
+        from_config_str(file_path, &file_content).map(Some).map_err(
+            |err_code| {
+                log::error!(
+                    "Failed! Yaml to L1XVMContractAddressRegistry obj :: {}",
+                    err_code
+                );
+                let backup_file_path = format!("{}.bak", file_path);
+                match fs::write(&backup_file_path, &file_content) {
+                    Ok(()) => ToolkitConfigError::parse(
+                        file_path,
+                        format!(
+                            "{}; saved a copy to {}",
+                            err_code, backup_file_path
+                        ),
+                    ),
+                    Err(_) => ToolkitConfigError::parse(file_path, err_code),
+                }
+            },
+        )
+    }
+
+    /// Check that the contract address registry exists and deserializes
+    pub fn validate_contract_address_registry(
+        &self,
+    ) -> Result<(), ToolkitConfigError> {
+        self.load_contract_address_registry_file().map(|_| ())
+    }
+
+    /// Get the EBPF contract address for the given artifact and contract ID.
+    pub fn ebpf_contract_address_for(
+        &self,
+        artifact_id: &str,
+        contract_id: Option<&str>,
+    ) -> Result<AddressStr, ToolkitConfigError> {
+        self.instance_contract_address_for(
+            L1XVMArtifactType::Ebpf,
+            artifact_id,
+            contract_id,
+        )
+    }
+
+    /// Get the WASM contract address for the given artifact and contract ID.
+    pub fn wasm_contract_address_for(
+        &self,
+        artifact_id: &str,
+        contract_id: Option<&str>,
+    ) -> Result<AddressStr, ToolkitConfigError> {
+        self.instance_contract_address_for(
+            L1XVMArtifactType::Wasm,
+            artifact_id,
+            contract_id,
+        )
+    }
+
+    /// Address of the deployment `artifact_id` of `vm_type`, or of its
+    /// `contract_id` instance when given
+    fn instance_contract_address_for(
+        &self,
+        vm_type: L1XVMArtifactType,
+        artifact_id: &str,
+        contract_id: Option<&str>,
+    ) -> Result<AddressStr, ToolkitConfigError> {
+        let config_address_registry = self.load_contract_address_registry()?;
+
+        if let Some(contract_info) =
+            config_address_registry.section(vm_type).get(artifact_id)
+        {
+            if let Some(cid) = contract_id {
+                if let Some(contract_instance_info) =
+                    contract_info.instance.get(cid)
+                {
+                    log::info!("S1 => {}", contract_instance_info.inst_address);
+                    Ok(contract_instance_info.inst_address)
+                } else {
+                    Err(ToolkitConfigError::RegistryMissingInstance {
+                        artifact_id: artifact_id.to_string(),
+                        contract_id: cid.to_string(),
+                    })
+                }
+            } else {
+                log::info!("S2 => {}", contract_info.deploy_address);
+                Ok(contract_info.deploy_address)
+            }
+        } else {
+            Err(ToolkitConfigError::RegistryMissingArtifact(
+                artifact_id.to_string(),
+            ))
+        }
+    }
+
+    /// Get the EVM contract address for the given artifact and contract ID.
+    pub fn evm_contract_address_for(
+        &self,
+        artifact_id: &str,
+        contract_id: Option<&str>,
+    ) -> Result<AddressStr, ToolkitConfigError> {
+        let config_address_registry = self.load_contract_address_registry()?;
+
+        if let Some(contract_info) =
+            config_address_registry.l1x_evm.get(artifact_id)
+        {
+            Ok(contract_info.deploy_address)
+        } else {
+            Err(ToolkitConfigError::RegistryMissingArtifact(
+                artifact_id.to_string(),
+            ))
+        }
+    }
+
+    /// Address `vm-sub-txn` calls for `vm_type`: the `contract_id` instance
+    /// of an eBPF or WASM deployment, the deployment itself for EVM
+    pub fn contract_address_for(
+        &self,
+        vm_type: L1XVMArtifactType,
+        artifact_id: &str,
+        contract_id: Option<&str>,
+    ) -> Result<AddressStr, ToolkitConfigError> {
+        match vm_type {
+            L1XVMArtifactType::Evm => {
+                self.evm_contract_address_for(artifact_id, None)
+            }
+            vm_type => self.instance_contract_address_for(
+                vm_type,
+                artifact_id,
+                contract_id,
+            ),
+        }
+    }
+
+    /// Resolve a contract reference to its address. `alias` is either a hex
+    /// address, an `artifact_id` or an `artifact_id/contract_id` eBPF or WASM
+    /// instance.
+    pub fn resolve_contract_address(
+        &self,
+        alias: &str,
+    ) -> Result<AddressStr, ToolkitConfigError> {
+        if let Ok(address) = alias.parse() {
+            return Ok(address);
+        }
+
+        match alias.split_once('/') {
+            Some((artifact_id, contract_id)) => self
+                .ebpf_contract_address_for(artifact_id, Some(contract_id))
+                .or_else(|_| {
+                    self.wasm_contract_address_for(
+                        artifact_id,
+                        Some(contract_id),
+                    )
+                }),
+            None => self
+                .evm_contract_address_for(alias, None)
+                .or_else(|_| self.ebpf_contract_address_for(alias, None))
+                .or_else(|_| self.wasm_contract_address_for(alias, None)),
+        }
+    }
+}
+
+/// A contract of the registry
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct L1XVMRegistryEntry {
+    /// `ebpf`, `evm` or `wasm`
+    pub vm_type: String,
+    pub artifact_id: String,
+    /// eBPF or WASM instance, `None` for the deployment
+    pub contract_id: Option<String>,
+    pub address: AddressStr,
+    /// Hash of the deploy or init transaction, empty when registered by hand
+    pub hash: String,
+    /// Recorded for the deployments only
+    #[serde(flatten)]
+    pub metadata: L1XVMDeployMetadata,
+}
+
+impl std::fmt::Display for L1XVMRegistryEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.contract_id {
+            Some(contract_id) => write!(
+                f,
+                "{} {}/{} :: {}",
+                self.vm_type, self.artifact_id, contract_id, self.address
+            )?,
+            None => write!(
+                f,
+                "{} {} :: {}",
+                self.vm_type, self.artifact_id, self.address
+            )?,
+        }
+        if !self.hash.is_empty() {
+            write!(f, " :: {}", self.hash)?;
+        }
+        if self.metadata != L1XVMDeployMetadata::default() {
+            write!(f, " :: {}", self.metadata)?;
+        }
+        Ok(())
+    }
+}
+
+impl ToolkitConfig {
+    /// Contracts of the active network, the eBPF deployments each followed
+    /// by their instances, then the EVM deployments, then the WASM ones with
+    /// their instances, sorted by artifact id
+    pub fn contract_address_registry_entries(
+        &self,
+    ) -> Result<Vec<L1XVMRegistryEntry>, ToolkitConfigError> {
+        let config_address_registry = self.load_contract_address_registry()?;
+        let entry =
+            |vm_type: L1XVMArtifactType,
+             artifact_id: &str,
+             contract_id: Option<&str>,
+             address: &AddressStr,
+             hash: &str,
+             metadata: &L1XVMDeployMetadata| L1XVMRegistryEntry {
+                vm_type: vm_type.config_key().to_string(),
+                artifact_id: artifact_id.to_string(),
+                contract_id: contract_id.map(str::to_string),
+                address: *address,
+                hash: hash.to_string(),
+                metadata: metadata.clone(),
+            };
+
+        let mut entries = Vec::new();
+        for vm_type in [
+            L1XVMArtifactType::Ebpf,
+            L1XVMArtifactType::Evm,
+            L1XVMArtifactType::Wasm,
+        ] {
+            for (artifact_id, contract_info) in
+                config_address_registry.section(vm_type)
+            {
+                entries.push(entry(
+                    vm_type,
+                    artifact_id,
+                    None,
+                    &contract_info.deploy_address,
+                    &contract_info.deploy_hash,
+                    &contract_info.metadata,
+                ));
+                for (contract_id, instance_info) in &contract_info.instance {
+                    entries.push(entry(
+                        vm_type,
+                        artifact_id,
+                        Some(contract_id),
+                        &instance_info.inst_address,
+                        &instance_info.inst_hash,
+                        &L1XVMDeployMetadata::default(),
+                    ));
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Register a contract deployed outside the toolkit at `address`, as the
+    /// `contract_id` instance of an eBPF or WASM deployment when given. EVM
+    /// contracts have no instances, `contract_id` is ignored for them.
+    pub fn set_contract_address(
+        &self,
+        vm_type: L1XVMArtifactType,
+        artifact_id: &str,
+        contract_id: Option<&str>,
+        address: AddressStr,
+    ) -> Result<(), ToolkitConfigError> {
+        let response_address = address;
+        // No transaction of the toolkit created the contract
+        let response_hash = String::new();
+
+        let update_type = match (vm_type, contract_id) {
+            (L1XVMArtifactType::Ebpf, None) => {
+                L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
+                    artifact_id: artifact_id.to_string(),
+                    response_hash,
+                    response_address,
+                }
+            }
+            (L1XVMArtifactType::Ebpf, Some(contract_id)) => {
+                L1XVMContractAddressUpdateType::L1XEBPF_INIT {
+                    artifact_id: artifact_id.to_string(),
+                    contract_id: contract_id.to_string(),
+                    response_hash,
+                    response_address,
+                }
+            }
+            (L1XVMArtifactType::Evm, _) => {
+                L1XVMContractAddressUpdateType::L1XEVM_DEPLOY {
+                    artifact_id: artifact_id.to_string(),
+                    response_hash,
+                    response_address,
+                }
+            }
+            (L1XVMArtifactType::Wasm, None) => {
+                L1XVMContractAddressUpdateType::L1XWASM_DEPLOY {
+                    artifact_id: artifact_id.to_string(),
+                    response_hash,
+                    response_address,
+                }
+            }
+            (L1XVMArtifactType::Wasm, Some(contract_id)) => {
+                L1XVMContractAddressUpdateType::L1XWASM_INIT {
+                    artifact_id: artifact_id.to_string(),
+                    contract_id: contract_id.to_string(),
+                    response_hash,
+                    response_address,
+                }
+            }
+        };
+
+        self.update_contract_address_registry(update_type)
+    }
+}
+
+/// Version of the [`L1XVMRegistryManifest`] schema
+pub const REGISTRY_MANIFEST_VERSION: u32 = 1;
+
+/// Contracts of a network in a machine independent form, to share them
+/// between workspaces. Entries are sorted by artifact id and contract id,
+/// addresses are `0x` and lowercase hex.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1XVMRegistryManifest {
+    pub version: u32,
+    pub ebpf: Vec<L1XVMManifestDeployment>,
+    pub evm: Vec<L1XVMManifestDeployment>,
+    /// Absent from the manifests written before WASM deployments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wasm: Vec<L1XVMManifestDeployment>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1XVMManifestDeployment {
+    pub artifact_id: String,
+    pub address: String,
+    pub hash: String,
+    #[serde(flatten)]
+    pub metadata: L1XVMDeployMetadata,
+    /// Instances of an eBPF or WASM deployment, EVM deployments have none
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instances: Vec<L1XVMManifestInstance>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1XVMManifestInstance {
+    pub contract_id: String,
+    pub address: String,
+    pub hash: String,
+}
+
+/// How an imported manifest combines with the registry of the network
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L1XVMRegistryImportMode {
+    /// Add the entries of the manifest. An entry of both at another address
+    /// is a conflict, nothing is imported then.
+    Merge,
+    /// The manifest becomes the registry of the network
+    Replace,
+}
+
+impl L1XVMContractAddressRegistry {
+    fn manifest(&self) -> L1XVMRegistryManifest {
+        let deployment =
+            |(artifact_id, contract_info): (&String, &L1XVMContractInfo)| {
+                L1XVMManifestDeployment {
+                    artifact_id: artifact_id.clone(),
+                    address: stored_address(&contract_info.deploy_address),
+                    hash: contract_info.deploy_hash.clone(),
+                    metadata: contract_info.metadata.clone(),
+                    instances: contract_info
+                        .instance
+                        .iter()
+                        .map(|(contract_id, instance_info)| {
+                            L1XVMManifestInstance {
+                                contract_id: contract_id.clone(),
+                                address: stored_address(
+                                    &instance_info.inst_address,
+                                ),
+                                hash: instance_info.inst_hash.clone(),
+                            }
+                        })
+                        .collect(),
+                }
+            };
+
+        L1XVMRegistryManifest {
+            version: REGISTRY_MANIFEST_VERSION,
+            ebpf: self.l1x_vm.iter().map(deployment).collect(),
+            evm: self.l1x_evm.iter().map(deployment).collect(),
+            wasm: self.l1x_wasm.iter().map(deployment).collect(),
+        }
+    }
+
+    /// Registry of `manifest`, an error naming the first invalid address or
+    /// duplicated id
+    fn from_manifest(manifest: &L1XVMRegistryManifest) -> Result<Self, String> {
+        if manifest.version != REGISTRY_MANIFEST_VERSION {
+            return Err(format!(
+                "unsupported version {}, expected {}",
+                manifest.version, REGISTRY_MANIFEST_VERSION
+            ));
+        }
+
+        let mut registry = L1XVMContractAddressRegistry::default();
+        for (section_key, section, deployments) in [
+            ("ebpf", &mut registry.l1x_vm, &manifest.ebpf),
+            ("evm", &mut registry.l1x_evm, &manifest.evm),
+            ("wasm", &mut registry.l1x_wasm, &manifest.wasm),
+        ] {
+            for deployment in deployments {
+                let artifact_id = &deployment.artifact_id;
+                if section_key == "evm" && !deployment.instances.is_empty() {
+                    return Err(format!(
+                        "evm artifact '{}' has instances, only eBPF and WASM deployments do",
+                        artifact_id
+                    ));
+                }
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct DevAccount {
-    priv_key: String,
-    pub_key: String,
+                let mut instance = BTreeMap::new();
+                for instance_info in &deployment.instances {
+                    let inst_address = instance_info
+                        .address
+                        .parse::<AddressStr>()
+                        .map_err(|reason| {
+                            format!(
+                                "{} of instance '{}' of {} artifact '{}'",
+                                reason,
+                                instance_info.contract_id,
+                                section_key,
+                                artifact_id
+                            )
+                        })?;
+                    let replaced = instance.insert(
+                        instance_info.contract_id.clone(),
+                        L1XVMInstanceInfo {
+                            inst_hash: instance_info.hash.clone(),
+                            inst_address,
+                            previous: Vec::new(),
+                        },
+                    );
+                    if replaced.is_some() {
+                        return Err(format!(
+                            "duplicate instance '{}' of {} artifact '{}'",
+                            instance_info.contract_id, section_key, artifact_id
+                        ));
+                    }
+                }
+
+                let deploy_address = deployment
+                    .address
+                    .parse::<AddressStr>()
+                    .map_err(|reason| {
+                    format!(
+                        "{} of {} artifact '{}'",
+                        reason, section_key, artifact_id
+                    )
+                })?;
+                let replaced = section.insert(
+                    artifact_id.clone(),
+                    L1XVMContractInfo {
+                        deploy_hash: deployment.hash.clone(),
+                        deploy_address,
+                        metadata: deployment.metadata.clone(),
+                        instance,
+                        previous: Vec::new(),
+                    },
+                );
+                if replaced.is_some() {
+                    return Err(format!(
+                        "duplicate {} artifact '{}'",
+                        section_key, artifact_id
+                    ));
+                }
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Add the entries of `imported` missing from the registry, returns the
+    /// entries of both at different addresses as `-` registry / `+` manifest
+    /// diff lines
+    fn merge(&mut self, imported: L1XVMContractAddressRegistry) -> Vec<String> {
+        let conflict =
+            |entry: String, registered: &AddressStr, imported: &AddressStr| {
+                format!(
+                    "- {}: {}\n+ {}: {}",
+                    entry,
+                    stored_address(registered),
+                    entry,
+                    stored_address(imported)
+                )
+            };
+
+        let mut conflicts = Vec::new();
+        for (section_key, section, imported_section) in [
+            ("l1x_vm", &mut self.l1x_vm, imported.l1x_vm),
+            ("l1x_evm", &mut self.l1x_evm, imported.l1x_evm),
+            ("l1x_wasm", &mut self.l1x_wasm, imported.l1x_wasm),
+        ] {
+            for (artifact_id, imported_info) in imported_section {
+                let contract_info = match section.get_mut(&artifact_id) {
+                    Some(contract_info) => contract_info,
+                    None => {
+                        section.insert(artifact_id, imported_info);
+                        continue;
+                    }
+                };
+
+                if contract_info.deploy_address != imported_info.deploy_address
+                {
+                    conflicts.push(conflict(
+                        format!("{}.{}", section_key, artifact_id),
+                        &contract_info.deploy_address,
+                        &imported_info.deploy_address,
+                    ));
+                }
+                for (contract_id, imported_instance) in imported_info.instance {
+                    match contract_info.instance.get(&contract_id) {
+                        Some(instance_info)
+                            if instance_info.inst_address
+                                != imported_instance.inst_address =>
+                        {
+                            conflicts.push(conflict(
+                                format!(
+                                    "{}.{}.instance.{}",
+                                    section_key, artifact_id, contract_id
+                                ),
+                                &instance_info.inst_address,
+                                &imported_instance.inst_address,
+                            ));
+                        }
+                        Some(_) => {}
+                        None => {
+                            contract_info
+                                .instance
+                                .insert(contract_id, imported_instance);
+                        }
+                    }
+                }
+            }
+        }
+
+        conflicts
+    }
 }
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct NetworkConfig {
-    networks: HashMap<String, Network>,
+impl ToolkitConfig {
+    /// Manifest of the contracts of the active network
+    pub fn export_contract_address_registry(
+        &self,
+    ) -> Result<L1XVMRegistryManifest, ToolkitConfigError> {
+        Ok(self.load_contract_address_registry()?.manifest())
+    }
+
+    /// Import `manifest` into the registry of the active network
+    pub fn import_contract_address_registry(
+        &self,
+        manifest: &L1XVMRegistryManifest,
+        mode: L1XVMRegistryImportMode,
+    ) -> Result<(), ToolkitConfigError> {
+        let imported = L1XVMContractAddressRegistry::from_manifest(manifest)
+            .map_err(ToolkitConfigError::InvalidRegistryManifest)?;
+
+        let config_address_registry_file_path =
+            self.paths.contract_address_registry_file().display().to_string();
+
+        let _registry_lock = self.lock_contract_address_registry()?;
+
+        let registry_file = self.read_contract_address_registry_file(
+            &config_address_registry_file_path,
+        )?;
+        let network = self.active_network()?;
+        let mut registries =
+            L1XVMNetworkRegistries::rehome(registry_file, &network);
+        let config = registries.networks.entry(network.clone()).or_default();
+
+        match mode {
+            L1XVMRegistryImportMode::Replace => *config = imported,
+            L1XVMRegistryImportMode::Merge => {
+                let conflicts = config.merge(imported);
+                if !conflicts.is_empty() {
+                    return Err(ToolkitConfigError::RegistryImportConflict {
+                        network,
+                        conflicts: conflicts.join("\n"),
+                    });
+                }
+            }
+        }
+
+        write_config_file(&config_address_registry_file_path, &registries)
+    }
 }
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct Network {
-    chain_id: u32,
-    host_ip: String,
-    rpc_port: u32,
-    rpc_endpoint: String,
+/// Form of `address` stored in the registry and its manifests, `0x` and
+/// lowercase hex
+fn stored_address(address: &AddressStr) -> String {
+    format!("0x{}", address.to_hex())
 }
 
-pub fn get_toolkit_network_config() -> Result<NetworkConfig, config::ConfigError>
-{
-    let l1x_cfg_ws_home = env::var("L1X_CFG_WS_HOME")
-        .expect("The L1X_CFG_WS_HOME environment variable must be set");
+/// Lowercase hex form, without `0x`, of the 20 byte account address
+/// `address`
+pub fn normalize_address(address: &str) -> Result<String, String> {
+    address
+        .parse::<AddressStr>()
+        .map(|address| address.to_hex())
+        .map_err(|err_code| err_code.to_string())
+}
 
-    let network_config_file_path =
-        format!("{}/l1x-conf/l1x_chain_config.yaml", l1x_cfg_ws_home);
+impl ToolkitConfig {
+    /// Record `update_type` in the registry of the active network, moving a
+    /// flat registry under it
+    pub fn update_contract_address_registry(
+        &self,
+        update_type: L1XVMContractAddressUpdateType,
+    ) -> Result<(), ToolkitConfigError> {
+        self.update_registry(update_type, L1XVMDeployMetadata::default())
+    }
 
-    // Create a new configuration object.
-    let mut settings = Config::builder()
-        // Load the YAML configuration files.
-        .add_source(File::with_name(&network_config_file_path))
-        .build()?;
+    /// Record the deployment `update_type` with its `metadata`, stamped with
+    /// the update time and the active network. Instances have no metadata,
+    /// it is dropped for an `L1XEBPF_INIT`.
+    pub fn update_contract_address_registry_with_metadata(
+        &self,
+        update_type: L1XVMContractAddressUpdateType,
+        metadata: L1XVMDeployMetadata,
+    ) -> Result<(), ToolkitConfigError> {
+        let deployed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| iso8601_utc(since_epoch.as_secs()))
+            .ok();
+        let metadata = L1XVMDeployMetadata {
+            deployed_at: metadata.deployed_at.or(deployed_at),
+            network: Some(self.active_network()?),
+            ..metadata
+        };
 
-    let network_settings: NetworkConfig = settings.try_deserialize()
-        .map_err(|err_code| {
-            log::error!("Failed to deserialize YAML configuration file :: {:#?} :: err {:#?}", network_config_file_path, err_code );
-            err_code
-        })?;
+        self.update_registry(update_type, metadata)
+    }
 
-    Ok(network_settings)
-}
+    fn update_registry(
+        &self,
+        update_type: L1XVMContractAddressUpdateType,
+        metadata: L1XVMDeployMetadata,
+    ) -> Result<(), ToolkitConfigError> {
+        let config_address_registry_file_path =
+            self.paths.contract_address_registry_file().display().to_string();
 
-pub fn get_toolkit_wallet_config() -> Result<WalletConfig, config::ConfigError>
-{
-    // Create a new configuration object.
-    let l1x_cfg_ws_home = env::var("L1X_CFG_WS_HOME")
-        .expect("The L1X_CFG_WS_HOME environment variable must be set");
+        let _registry_lock = self.lock_contract_address_registry()?;
 
-    let default_wallet_config_file_path =
-        format!("{}/l1x-conf/l1x_dev_wallets.yaml", l1x_cfg_ws_home);
+        // Read the existing YAML file or create a new empty config if it doesn't exist
+        let registry_file = self.read_contract_address_registry_file(
+            &config_address_registry_file_path,
+        )?;
+        let network = self.active_network()?;
+        let mut registries =
+            L1XVMNetworkRegistries::rehome(registry_file, &network);
+        let config = registries.networks.entry(network).or_default();
 
-    let mut settings = Config::builder()
-        // Load the YAML configuration files.
-        .add_source(File::with_name(&default_wallet_config_file_path))
-        .build()?;
+        match update_type {
+            L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
+                artifact_id,
+                response_hash,
+                response_address,
+            } => {
+                log::info!("L1XEBPF_DEPLOY :: {:#?}", response_address.clone());
+                record_deployment(
+                    &mut config.l1x_vm,
+                    artifact_id,
+                    response_hash,
+                    response_address,
+                    metadata,
+                );
+            }
+            L1XVMContractAddressUpdateType::L1XEBPF_INIT {
+                artifact_id,
+                contract_id,
+                response_hash,
+                response_address,
+            } => {
+                log::info!("L1XEBPF_INIT :: {:#?}", response_address.clone());
+                record_instance(
+                    &mut config.l1x_vm,
+                    artifact_id,
+                    contract_id,
+                    response_hash,
+                    response_address,
+                )?;
+            }
+            L1XVMContractAddressUpdateType::L1XEVM_DEPLOY {
+                artifact_id,
+                response_hash,
+                response_address,
+            } => {
+                log::info!("L1XEVM_DEPLOY :: {:#?}", response_address);
+                // Update the YAML structure with the response data
+                let contract_info = L1XVMContractInfo {
+                    deploy_hash: response_hash.to_string(),
+                    deploy_address: response_address,
+                    metadata,
+                    instance: BTreeMap::new(),
+                    previous: Vec::new(),
+                };
 
-    let wallet_settings: WalletConfig = settings.try_deserialize()
-        .map_err(|err_code| {
-            log::error!("Failed to deserialize YAML configuration file :: {:#?} :: err {:#?}", default_wallet_config_file_path, err_code );
-            err_code
-        })?;
+                // Add or update the contract info in the YAML structure
+                config.l1x_evm.insert(artifact_id.clone(), contract_info); // Use artifact_id as a key
+            }
+            L1XVMContractAddressUpdateType::L1XWASM_DEPLOY {
+                artifact_id,
+                response_hash,
+                response_address,
+            } => {
+                log::info!("L1XWASM_DEPLOY :: {:#?}", response_address);
+                record_deployment(
+                    &mut config.l1x_wasm,
+                    artifact_id,
+                    response_hash,
+                    response_address,
+                    metadata,
+                );
+            }
+            L1XVMContractAddressUpdateType::L1XWASM_INIT {
+                artifact_id,
+                contract_id,
+                response_hash,
+                response_address,
+            } => {
+                log::info!("L1XWASM_INIT :: {:#?}", response_address);
+                record_instance(
+                    &mut config.l1x_wasm,
+                    artifact_id,
+                    contract_id,
+                    response_hash,
+                    response_address,
+                )?;
+            }
+        }
 
-    Ok(wallet_settings)
+        // Serialize the updated YAML structure back to the file
+        write_config_file(&config_address_registry_file_path, &registries)
+    }
 }
 
-pub fn get_active_chain_json_rpc_endpoint() -> String {
-    let l1x_cfg_chain_type = env::var("L1X_CFG_CHAIN_TYPE")
-        .expect("The L1X_CFG_CHAIN_TYPE environment variable must be set");
+/// Record the deployment `artifact_id` in `section` without instances. The
+/// deployment it replaces moves to its history with its instances, which
+/// belong to the old deploy address.
+fn record_deployment(
+    section: &mut BTreeMap<String, L1XVMContractInfo>,
+    artifact_id: String,
+    response_hash: String,
+    response_address: AddressStr,
+    metadata: L1XVMDeployMetadata,
+) {
+    // Keep track of the deployment being replaced
+    let previous = match section.remove(&artifact_id) {
+        Some(mut old_info) => {
+            old_info.previous.push(L1XVMPreviousDeployInfo {
+                deploy_hash: old_info.deploy_hash,
+                deploy_address: old_info.deploy_address,
+                instance: old_info.instance,
+            });
+            old_info.previous
+        }
+        None => Vec::new(),
+    };
 
-    let config_network: NetworkConfig = get_toolkit_network_config().expect(
-        &format!("Failed to get yaml network config for the active chain"),
+    section.insert(
+        artifact_id,
+        L1XVMContractInfo {
+            deploy_hash: response_hash,
+            deploy_address: response_address,
+            metadata,
+            instance: BTreeMap::new(),
+            previous,
+        },
     );
-
-    let config_network_params: Network = config_network
-        .networks
-        .get(&l1x_cfg_chain_type)
-        .unwrap_or_else(|| {
-            panic!(
-            "Failed to get default network config params for chain type :: {}",
-            l1x_cfg_chain_type
-        )
-        })
-        .clone();
-
-    config_network_params.rpc_endpoint
 }
 
-pub fn get_wallet_priv_key(owner_id: &str) -> String {
-    let config_wallet: WalletConfig = get_toolkit_wallet_config()
-        .expect(&format!("Failed to get yaml wallet config"));
+/// Record the `contract_id` instance of the deployment `artifact_id` of
+/// `section`, the instance it replaces moving to its history
+fn record_instance(
+    section: &mut BTreeMap<String, L1XVMContractInfo>,
+    artifact_id: String,
+    contract_id: String,
+    response_hash: String,
+    response_address: AddressStr,
+) -> Result<(), ToolkitConfigError> {
+    let contract_info = section
+        .get_mut(&artifact_id)
+        .ok_or(ToolkitConfigError::RegistryMissingArtifact(artifact_id))?;
 
-    let account_info: DevAccount = config_wallet
-        .dev_accounts
-        .get(owner_id)
-        .unwrap_or_else(|| {
-            panic!(
-                "Failed to get default account info for owner ID :: {}",
-                owner_id
-            )
-        })
-        .clone();
+    // Keep track of the instance being replaced
+    let previous = match contract_info.instance.remove(&contract_id) {
+        Some(mut old_instance) => {
+            old_instance.previous.push(L1XVMPreviousInstanceInfo {
+                inst_hash: old_instance.inst_hash,
+                inst_address: old_instance.inst_address,
+            });
+            old_instance.previous
+        }
+        None => Vec::new(),
+    };
 
-    account_info.priv_key
-}
+    contract_info.instance.insert(
+        contract_id,
+        L1XVMInstanceInfo {
+            inst_hash: response_hash,
+            inst_address: response_address,
+            previous,
+        },
+    );
 
-// ================================================================================
+    Ok(())
+}
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum L1XVMContractAddressUpdateType {
-    L1XEBPF_DEPLOY {
+#[allow(non_camel_case_types)]
+#[derive(Clone, Debug)]
+pub enum L1XVMContractAddressRemoveType {
+    /// Remove the whole eBPF artifact entry, or only the given instance
+    L1XEBPF_REMOVE {
         artifact_id: String,
-        response_hash: String,
-        response_address: String,
+        contract_id: Option<String>,
     },
-    L1XEBPF_INIT {
+    L1XEVM_REMOVE {
         artifact_id: String,
-        contract_id: String,
-        response_hash: String,
-        response_address: String,
     },
-    L1XEVM_DEPLOY {
+    /// Remove the whole WASM artifact entry, or only the given instance
+    L1XWASM_REMOVE {
         artifact_id: String,
-        response_hash: String,
-        response_address: String,
+        contract_id: Option<String>,
     },
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct L1XVMContractAddressRegistry {
-    l1x_vm: BTreeMap<String, L1XVMContractInfo>,
-    l1x_evm: BTreeMap<String, L1XVMContractInfo>,
+impl ToolkitConfig {
+    /// Remove an artifact or a single contract instance from the registry.
+    ///
+    /// Returns the `(entry, address)` pairs that were removed. Nothing is
+    /// written when `dry_run` is set or when no matching entry exists.
+    pub fn remove_contract_address_registry_entry(
+        &self,
+        remove_type: L1XVMContractAddressRemoveType,
+        dry_run: bool,
+    ) -> Result<Vec<(String, AddressStr)>, ToolkitConfigError> {
+        let config_address_registry_file_path =
+            self.paths.contract_address_registry_file().display().to_string();
+
+        let _registry_lock = self.lock_contract_address_registry()?;
+
+        let registry_file = match self.read_contract_address_registry_file(
+            &config_address_registry_file_path,
+        )? {
+            Some(registry_file) => registry_file,
+            None => return Ok(Vec::new()),
+        };
+        let network = self.active_network()?;
+        let mut registries =
+            L1XVMNetworkRegistries::rehome(Some(registry_file), &network);
+        let config = registries.networks.entry(network).or_default();
+
+        let removed = match remove_type {
+            L1XVMContractAddressRemoveType::L1XEBPF_REMOVE {
+                artifact_id,
+                contract_id,
+            } => remove_instance_entries(
+                "l1x_vm",
+                &mut config.l1x_vm,
+                &artifact_id,
+                contract_id.as_deref(),
+            ),
+            L1XVMContractAddressRemoveType::L1XEVM_REMOVE { artifact_id } => {
+                config
+                    .l1x_evm
+                    .remove(&artifact_id)
+                    .map(|contract_info| {
+                        vec![(
+                            format!("l1x_evm.{}", artifact_id),
+                            contract_info.deploy_address,
+                        )]
+                    })
+                    .unwrap_or_default()
+            }
+            L1XVMContractAddressRemoveType::L1XWASM_REMOVE {
+                artifact_id,
+                contract_id,
+            } => remove_instance_entries(
+                "l1x_wasm",
+                &mut config.l1x_wasm,
+                &artifact_id,
+                contract_id.as_deref(),
+            ),
+        };
+
+        if dry_run || removed.is_empty() {
+            return Ok(removed);
+        }
+
+        write_config_file(&config_address_registry_file_path, &registries)?;
+
+        Ok(removed)
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct L1XVMContractInfo {
-    deploy_hash: String,
-    deploy_address: String,
-    instance: BTreeMap<String, L1XVMInstanceInfo>,
+impl ToolkitConfig {
+    /// Record `verified_sha256` in the metadata of the `vm_type` deployment
+    /// `artifact_id` of the active network
+    pub fn set_verified_sha256(
+        &self,
+        vm_type: L1XVMArtifactType,
+        artifact_id: &str,
+        verified_sha256: &str,
+    ) -> Result<(), ToolkitConfigError> {
+        let config_address_registry_file_path =
+            self.paths.contract_address_registry_file().display().to_string();
+
+        let _registry_lock = self.lock_contract_address_registry()?;
+
+        let registry_file = self.read_contract_address_registry_file(
+            &config_address_registry_file_path,
+        )?;
+        let network = self.active_network()?;
+        let mut registries =
+            L1XVMNetworkRegistries::rehome(registry_file, &network);
+        let contract_info = registries
+            .networks
+            .entry(network)
+            .or_default()
+            .section_mut(vm_type)
+            .get_mut(artifact_id)
+            .ok_or_else(|| {
+                ToolkitConfigError::RegistryMissingArtifact(
+                    artifact_id.to_string(),
+                )
+            })?;
+        contract_info.metadata.verified_sha256 =
+            Some(verified_sha256.to_string());
+
+        write_config_file(&config_address_registry_file_path, &registries)
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct L1XVMInstanceInfo {
-    inst_hash: String,
-    inst_address: String,
+/// Remove the `contract_id` instance of the deployment `artifact_id` from
+/// `section`, or the deployment with all its instances. Returns the removed
+/// `(entry, address)` pairs, entries prefixed with `section_key`.
+fn remove_instance_entries(
+    section_key: &str,
+    section: &mut BTreeMap<String, L1XVMContractInfo>,
+    artifact_id: &str,
+    contract_id: Option<&str>,
+) -> Vec<(String, AddressStr)> {
+    let instance_entry = |contract_id: &str| {
+        format!("{}.{}.instance.{}", section_key, artifact_id, contract_id)
+    };
+
+    match contract_id {
+        Some(contract_id) => section
+            .get_mut(artifact_id)
+            .and_then(|contract_info| {
+                contract_info.instance.remove(contract_id)
+            })
+            .map(|instance_info| {
+                vec![(instance_entry(contract_id), instance_info.inst_address)]
+            })
+            .unwrap_or_default(),
+        None => section
+            .remove(artifact_id)
+            .map(|contract_info| {
+                let mut removed: Vec<(String, AddressStr)> = contract_info
+                    .instance
+                    .iter()
+                    .map(|(contract_id, instance_info)| {
+                        (
+                            instance_entry(contract_id),
+                            instance_info.inst_address,
+                        )
+                    })
+                    .collect();
+                removed.push((
+                    format!("{}.{}", section_key, artifact_id),
+                    contract_info.deploy_address,
+                ));
+                removed
+            })
+            .unwrap_or_default(),
+    }
 }
 
-/// Load the contract address registry from a YAML configuration file.
-fn load_contract_address_registry(
-) -> Result<L1XVMContractAddressRegistry, config::ConfigError> {
-    // Create a new configuration object.
-    let l1x_cfg_ws_home = env::var("L1X_CFG_WS_HOME")
-        .expect("The L1X_CFG_WS_HOME environment variable must be set");
+impl ToolkitConfig {
+    /// Remove the `dead` entries of
+    /// [`ToolkitConfig::contract_address_registry_entries`] from the registry
+    /// of the active network in a single write. An entry is only removed while
+    /// it is still registered at the same address, an eBPF or WASM deployment
+    /// only once none of its instances is left.
+    ///
+    /// Returns the removed entries. Nothing is written when `dry_run` is set.
+    pub fn prune_contract_address_registry(
+        &self,
+        dead: &[L1XVMRegistryEntry],
+        dry_run: bool,
+    ) -> Result<Vec<L1XVMRegistryEntry>, ToolkitConfigError> {
+        let config_address_registry_file_path =
+            self.paths.contract_address_registry_file().display().to_string();
 
-    // Define the file path for the contract address registry
-    let config_address_registry_file_path = format!(
-        "{}/l1x-conf/config-contract-address-registry.yaml",
-        l1x_cfg_ws_home
-    );
+        let _registry_lock = self.lock_contract_address_registry()?;
 
-    let mut settings = Config::builder()
-        // Load the YAML configuration files.
-        .add_source(File::with_name(&config_address_registry_file_path))
-        .build()?;
+        let registry_file = match self.read_contract_address_registry_file(
+            &config_address_registry_file_path,
+        )? {
+            Some(registry_file) => registry_file,
+            None => return Ok(Vec::new()),
+        };
+        let network = self.active_network()?;
+        let mut registries =
+            L1XVMNetworkRegistries::rehome(Some(registry_file), &network);
+        let config = registries.networks.entry(network).or_default();
 
-    let contract_address_registry: L1XVMContractAddressRegistry = settings.try_deserialize()
-        .map_err(|err_code| {
-            log::error!(
-                "Failed to deserialize YAML configuration file :: {:#?} :: err {:#?}",
-                config_address_registry_file_path,
-                err_code
-            );
-            err_code
-        })?;
+        let mut removed = Vec::new();
+        let dead = dead.iter().filter_map(|entry| {
+            L1XVMArtifactType::from_config_key(&entry.vm_type)
+                .map(|vm_type| (vm_type, entry))
+        });
+
+        // Instances first, their deployments may only go once they are empty
+        for (vm_type, entry) in dead.clone() {
+            let (contract_info, contract_id) = match (
+                config.section_mut(vm_type).get_mut(&entry.artifact_id),
+                &entry.contract_id,
+            ) {
+                (Some(contract_info), Some(contract_id)) => {
+                    (contract_info, contract_id)
+                }
+                _ => continue,
+            };
+            let still_registered = contract_info
+                .instance
+                .get(contract_id)
+                .map_or(false, |instance_info| {
+                    instance_info.inst_address == entry.address
+                });
+            if still_registered {
+                contract_info.instance.remove(contract_id);
+                removed.push(entry.clone());
+            }
+        }
+
+        for (vm_type, entry) in
+            dead.filter(|(_, entry)| entry.contract_id.is_none())
+        {
+            let contracts = config.section_mut(vm_type);
+            let removable =
+                contracts.get(&entry.artifact_id).map_or(false, |info| {
+                    info.deploy_address == entry.address
+                        && info.instance.is_empty()
+                });
+            if removable {
+                contracts.remove(&entry.artifact_id);
+                removed.push(entry.clone());
+            }
+        }
+
+        if dry_run || removed.is_empty() {
+            return Ok(removed);
+        }
+
+        write_config_file(&config_address_registry_file_path, &registries)?;
 
-    Ok(contract_address_registry)
+        Ok(removed)
+    }
 }
 
-/// Get the EBPF contract address for the given artifact and contract ID.
-pub fn get_toolkit_ebpf_contract_address_for(
-    artifact_id: &str,
-    contract_id: Option<&str>,
-) -> Result<String, String> {
-    let config_address_registry =
-        load_contract_address_registry().map_err(|err| {
-            format!("Failed to load contract registry yaml file: {:?}", err)
-        })?;
+/// Configuration file of a workspace read and written key by key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L1XConfigFile {
+    Chain,
+    Wallet,
+    Registry,
+}
 
-    if let Some(contract_info) = config_address_registry.l1x_vm.get(artifact_id)
-    {
-        if let Some(cid) = contract_id {
-            if let Some(contract_instance_info) =
-                contract_info.instance.get(cid)
-            {
-                let clean_hex_string =
-                    clean_address_string(&contract_instance_info.inst_address);
-                log::info!("S1 => {}", &clean_hex_string);
-                Ok(clean_hex_string.to_string())
-            } else {
-                Err(format!(
-                    "Contract instance '{}' not found for artifact '{}'",
-                    cid, artifact_id
+impl ToolkitPaths {
+    pub fn file_of(&self, config_file: L1XConfigFile) -> PathBuf {
+        match config_file {
+            L1XConfigFile::Chain => self.chain_config_file(),
+            L1XConfigFile::Wallet => self.wallet_config_file(),
+            L1XConfigFile::Registry => self.contract_address_registry_file(),
+        }
+    }
+}
+
+/// Value at the dotted `key` of `config`, e.g. `networks.devnet.rpc_port`.
+/// Numeric segments index sequences.
+pub fn config_value_at<'a>(
+    config: &'a serde_yaml::Value,
+    key: &str,
+) -> Option<&'a serde_yaml::Value> {
+    key.split('.').try_fold(config, |value, segment| match value {
+        serde_yaml::Value::Mapping(mapping) => mapping.get(segment),
+        serde_yaml::Value::Sequence(sequence) => {
+            sequence.get(segment.parse::<usize>().ok()?)
+        }
+        _ => None,
+    })
+}
+
+/// Set the dotted `key` of `config` to `raw_value`, creating the missing
+/// mappings on the way. The value keeps the type of the one it replaces, a
+/// new key gets the YAML type `raw_value` parses as.
+pub fn set_config_value_at(
+    config: &mut serde_yaml::Value,
+    key: &str,
+    raw_value: &str,
+) -> Result<(), String> {
+    if key.split('.').any(str::is_empty) {
+        return Err("empty key segment".to_string());
+    }
+
+    let mut value = config;
+    let mut parent_key = Vec::new();
+    for segment in key.split('.') {
+        if value.is_null() {
+            *value = serde_yaml::Mapping::new().into();
+        }
+        value = match value {
+            serde_yaml::Value::Mapping(mapping) => {
+                mapping.entry(segment.into()).or_insert(serde_yaml::Value::Null)
+            }
+            serde_yaml::Value::Sequence(sequence) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| sequence.get_mut(index))
+                .ok_or_else(|| {
+                    format!(
+                        "'{}' has no element {}",
+                        parent_key.join("."),
+                        segment
+                    )
+                })?,
+            _ => {
+                return Err(format!(
+                    "'{}' is not a mapping",
+                    parent_key.join(".")
                 ))
             }
-        } else {
-            let clean_hex_string =
-                clean_address_string(&contract_info.deploy_address);
-            log::info!("S2 => {}", &clean_hex_string);
-            Ok(clean_hex_string)
-        }
-    } else {
-        Err(format!(
-            "Artifact '{}' not found in the contract registry",
-            artifact_id
-        ))
+        };
+        parent_key.push(segment);
     }
+
+    *value = typed_config_value(value, raw_value)?;
+    Ok(())
 }
 
-/// Get the EVM contract address for the given artifact and contract ID.
-pub fn get_toolkit_evm_contract_address_for(
-    artifact_id: &str,
-    contract_id: Option<&str>,
-) -> Result<String, String> {
-    let config_address_registry =
-        load_contract_address_registry().map_err(|err| {
-            format!("Failed to load contract registry yaml file: {:?}", err)
-        })?;
+/// `raw_value` as a value of the type of `current`
+fn typed_config_value(
+    current: &serde_yaml::Value,
+    raw_value: &str,
+) -> Result<serde_yaml::Value, String> {
+    if current.is_string() {
+        return Ok(raw_value.into());
+    }
 
-    if let Some(contract_info) =
-        config_address_registry.l1x_evm.get(artifact_id)
+    let value: serde_yaml::Value = serde_yaml::from_str(raw_value)
+        .map_err(|err_code| err_code.to_string())?;
+    if current.is_null()
+        || std::mem::discriminant(&value) == std::mem::discriminant(current)
     {
-        let clean_hex_string =
-            clean_address_string(&contract_info.deploy_address);
-        Ok(clean_hex_string)
+        Ok(value)
     } else {
         Err(format!(
-            "Artifact '{}' not found in the contract registry",
-            artifact_id
+            "expected {} like the current value, got '{}'",
+            yaml_type_name(current),
+            raw_value
         ))
     }
 }
 
-fn clean_address_string(address_to_clean: &str) -> String {
-    // Trim the string and remove any leading or trailing quotes.
-    let trimmed_address = address_to_clean.trim().trim_matches('"');
+fn yaml_type_name(value: &serde_yaml::Value) -> &'static str {
+    match value {
+        serde_yaml::Value::Null => "null",
+        serde_yaml::Value::Bool(_) => "a boolean",
+        serde_yaml::Value::Number(_) => "a number",
+        serde_yaml::Value::String(_) => "a string",
+        serde_yaml::Value::Sequence(_) => "a sequence",
+        serde_yaml::Value::Mapping(_) => "a mapping",
+        serde_yaml::Value::Tagged(_) => "a tagged value",
+    }
+}
 
-    // Remove the "0x" prefix from the address, if it exists.
-    let clean_address =
-        trimmed_address.strip_prefix("0x").unwrap_or(trimmed_address);
+impl ToolkitConfig {
+    /// Untyped content of `config_file`, `None` when it doesn't exist
+    fn read_config_value(
+        &self,
+        config_file: L1XConfigFile,
+    ) -> Result<Option<serde_yaml::Value>, ToolkitConfigError> {
+        let file_path = self.paths.file_of(config_file).display().to_string();
+        let file_content = match fs::read_to_string(&file_path) {
+            Ok(file_content) => file_content,
+            Err(_) => return Ok(None),
+        };
 
-    // Return the clean address.
-    clean_address.to_string()
-}
+        from_config_str(&file_path, &file_content)
+            .map(Some)
+            .map_err(|err_code| ToolkitConfigError::parse(&file_path, err_code))
+    }
 
-pub fn update_toolkit_contract_address_registry(
-    update_type: L1XVMContractAddressUpdateType,
-) -> Result<(), String> {
-    // Ensure L1X_CFG_WS_HOME environment variable is set
-    let l1x_cfg_ws_home = env::var("L1X_CFG_WS_HOME")
-        .expect("The L1X_CFG_WS_HOME environment variable must be set");
-
-    // Define the file path for the contract address registry
-    let config_address_registry_file_path = format!(
-        "{}/l1x-conf/config-contract-address-registry.yaml",
-        l1x_cfg_ws_home
-    );
+    /// Value at the dotted `key` of `config_file`, see [`config_value_at`]
+    pub fn config_value(
+        &self,
+        config_file: L1XConfigFile,
+        key: &str,
+    ) -> Result<serde_yaml::Value, ToolkitConfigError> {
+        let file_path = self.paths.file_of(config_file).display().to_string();
+        let config = self.read_config_value(config_file)?.ok_or_else(|| {
+            ToolkitConfigError::MissingConfigFile(file_path.clone())
+        })?;
 
-    // Read the existing YAML file or create a new empty config if it doesn't exist
-    let mut config: L1XVMContractAddressRegistry =
-        match fs::read_to_string(&config_address_registry_file_path) {
-            Ok(yaml_content) => serde_yaml::from_str(&yaml_content)
-                .map_err(|err_code| {
-                    log::error!(
-                    "Failed! Yaml to L1XVMContractAddressRegistry obj :: {}",
-                    err_code
-                );
-                    err_code
-                })
-                .unwrap_or_else(|_| {
-                    panic!("Failed! Yaml to L1XVMContractAddressRegistry obj!")
-                }),
-            Err(_) => L1XVMContractAddressRegistry {
-                l1x_vm: BTreeMap::new(),
-                l1x_evm: BTreeMap::new(),
-            },
-        };
+        config_value_at(&config, key).cloned().ok_or_else(|| {
+            ToolkitConfigError::MissingConfigKey {
+                key: key.to_string(),
+                path: file_path,
+            }
+        })
+    }
 
-    match update_type {
-        L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
-            artifact_id,
-            response_hash,
-            response_address,
-        } => {
-            log::info!("L1XEBPF_DEPLOY :: {:#?}", response_address.clone());
-            // Update the YAML structure with the response data
-            let contract_info = L1XVMContractInfo {
-                deploy_hash: response_hash.clone(),
-                deploy_address: format!("\"0x{}\"", response_address.clone()),
-                instance: BTreeMap::new(),
+    /// Set the dotted `key` of `config_file` to `raw_value`, see
+    /// [`set_config_value_at`], creating the file when missing. The other
+    /// keys are kept, comments are lost. The file must still load afterwards.
+    pub fn set_config_value(
+        &self,
+        config_file: L1XConfigFile,
+        key: &str,
+        raw_value: &str,
+    ) -> Result<(), ToolkitConfigError> {
+        let file_path = self.paths.file_of(config_file).display().to_string();
+        let invalid_value =
+            |reason: String| ToolkitConfigError::InvalidConfigValue {
+                key: key.to_string(),
+                reason,
             };
 
-            // Add or update the contract info in the YAML structure
-            config.l1x_vm.insert(artifact_id.clone(), contract_info); // Use artifact_id as a key
-        }
-        L1XVMContractAddressUpdateType::L1XEBPF_INIT {
-            artifact_id,
-            contract_id,
-            response_hash,
-            response_address,
-        } => {
-            if let Some(contract_info) = config.l1x_vm.get_mut(&artifact_id) {
-                log::info!("L1XEBPF_INIT :: {:#?}", response_address.clone());
-                // Update the YAML structure with the response data
-                let instance_info = L1XVMInstanceInfo {
-                    inst_hash: response_hash.clone(),
-                    inst_address: format!("\"0x{}\"", response_address.clone()),
-                };
+        let _registry_lock = match config_file {
+            L1XConfigFile::Registry => {
+                Some(self.lock_contract_address_registry()?)
+            }
+            L1XConfigFile::Chain | L1XConfigFile::Wallet => None,
+        };
+        let mut config = self
+            .read_config_value(config_file)?
+            .unwrap_or(serde_yaml::Value::Null);
 
-                contract_info
-                    .instance
-                    .insert(contract_id.clone(), instance_info);
-            } else {
-                panic!("Failed to update Contract Address Registry :: Contract Deploy Info is missing for :: {}", artifact_id);
+        set_config_value_at(&mut config, key, raw_value)
+            .map_err(invalid_value)?;
+
+        let loads = match config_file {
+            L1XConfigFile::Chain => {
+                serde_yaml::from_value::<NetworkConfig>(config.clone())
+                    .map(drop)
             }
-        }
-        L1XVMContractAddressUpdateType::L1XEVM_DEPLOY {
-            artifact_id,
-            response_hash,
-            response_address,
-        } => {
-            let response_address_clean =
-                clean_address_string(&response_address);
-
-            log::info!("L1XEVM_DEPLOY :: {:#?}", response_address_clean);
-            // Update the YAML structure with the response data
-            let contract_info = L1XVMContractInfo {
-                deploy_hash: response_hash.to_string(),
-                deploy_address: format!("\"0x{}\"", response_address_clean),
-                instance: BTreeMap::new(),
-            };
+            L1XConfigFile::Wallet => {
+                serde_yaml::from_value::<WalletConfig>(config.clone()).map(drop)
+            }
+            L1XConfigFile::Registry => serde_yaml::from_value::<
+                L1XVMContractAddressRegistryFile,
+            >(config.clone())
+            .map(drop),
+        };
+        loads.map_err(|err_code| invalid_value(err_code.to_string()))?;
 
-            // Add or update the contract info in the YAML structure
-            config.l1x_evm.insert(artifact_id.clone(), contract_info); // Use artifact_id as a key
-        }
+        write_config_file(&file_path, &config)
     }
+}
+
+/// Write `config` in the format of `file_path` to a sibling temp file and
+/// rename it over the original, so readers never observe a partially written
+/// configuration file.
+fn write_config_file<T: Serialize>(
+    file_path: &str,
+    config: &T,
+) -> Result<(), ToolkitConfigError> {
+    let tmp_file_path = format!("{}.tmp", file_path);
 
-    // Serialize the updated YAML structure back to the file
-    let yaml_file_handle = fs::File::create(&config_address_registry_file_path)
-        .expect("Unable to Create The file");
+    let file_content = match config_file_format(Path::new(file_path)) {
+        FileFormat::Toml => toml::to_string(config).map_err(|err_code| {
+            ToolkitConfigError::write(&tmp_file_path, err_code)
+        })?,
+        _ => serde_yaml::to_string(config).map_err(|err_code| {
+            ToolkitConfigError::write(&tmp_file_path, err_code)
+        })?,
+    };
+
+    let config_file_handle =
+        fs::File::create(&tmp_file_path).map_err(|err_code| {
+            ToolkitConfigError::write(&tmp_file_path, err_code)
+        })?;
 
-    let mut buff_writer = std::io::BufWriter::new(yaml_file_handle);
-    serde_yaml::to_writer(&mut buff_writer, &config)
-        .expect("Unable to update Yaml File");
+    let mut buff_writer = std::io::BufWriter::new(config_file_handle);
+    buff_writer.write_all(file_content.as_bytes()).map_err(|err_code| {
+        ToolkitConfigError::write(&tmp_file_path, err_code)
+    })?;
 
-    buff_writer.flush().expect("Failed to flush BufWriter");
+    buff_writer.flush().map_err(|err_code| {
+        ToolkitConfigError::write(&tmp_file_path, err_code)
+    })?;
 
     // Close the file handle to release resources
-    let _ = buff_writer.into_inner().map(|file| file.sync_all()).unwrap();
+    buff_writer
+        .into_inner()
+        .map_err(|err_code| {
+            ToolkitConfigError::write(&tmp_file_path, err_code.into_error())
+        })?
+        .sync_all()
+        .map_err(|err_code| {
+            ToolkitConfigError::write(&tmp_file_path, err_code)
+        })?;
+
+    fs::rename(&tmp_file_path, file_path)
+        .map_err(|err_code| ToolkitConfigError::write(file_path, err_code))?;
+    invalidate_config_file(Path::new(file_path));
 
     Ok(())
 }
 
 // ================================================================================
+
+// Free functions of the workspace of `$L1X_CFG_WS_HOME`, each building a
+// `ToolkitConfig::from_env()` handle
+
+#[deprecated(note = "use `ToolkitConfig::network_config`")]
+pub fn get_toolkit_network_config() -> Result<NetworkConfig, ToolkitConfigError>
+{
+    ToolkitConfig::from_env()?.network_config()
+}
+
+#[deprecated(note = "use `ToolkitConfig::wallet_config`")]
+pub fn get_toolkit_wallet_config() -> Result<WalletConfig, ToolkitConfigError> {
+    ToolkitConfig::from_env()?.wallet_config()
+}
+
+/// None when the workspace or the file is missing
+#[deprecated(note = "use `ToolkitConfig::template_config`")]
+pub fn get_toolkit_template_config(
+) -> Result<HashMap<String, TemplateHubEntry>, ToolkitConfigError> {
+    match ToolkitConfig::from_env() {
+        Ok(toolkit) => toolkit.template_config(),
+        Err(_) => Ok(HashMap::new()),
+    }
+}
+
+#[deprecated(note = "use `ToolkitConfig::artifact_dir`")]
+pub fn get_artifact_dir(
+    vm_type: L1XVMArtifactType,
+) -> Result<PathBuf, ToolkitConfigError> {
+    Ok(ToolkitConfig::from_env()?.artifact_dir(vm_type))
+}
+
+#[deprecated(note = "use `ToolkitConfig::active_chain_json_rpc_endpoint`")]
+pub fn get_active_chain_json_rpc_endpoint() -> Result<String, ToolkitConfigError>
+{
+    ToolkitConfig::from_env()?.active_chain_json_rpc_endpoint()
+}
+
+#[deprecated(note = "use `ToolkitConfig::chain_json_rpc_endpoint`")]
+pub fn get_chain_json_rpc_endpoint(
+    network: &str,
+) -> Result<String, ToolkitConfigError> {
+    ToolkitConfig::from_env()?.chain_json_rpc_endpoint(network)
+}
+
+#[deprecated(note = "use `ToolkitConfig::wallet_account`")]
+pub fn get_wallet_account(
+    owner_id: &str,
+) -> Result<DevAccount, ToolkitConfigError> {
+    ToolkitConfig::from_env()?.wallet_account(owner_id)
+}
+
+#[deprecated(note = "use `ToolkitConfig::wallet_priv_key`")]
+pub fn get_wallet_priv_key(
+    owner_id: &str,
+) -> Result<String, ToolkitConfigError> {
+    ToolkitConfig::from_env()?.wallet_priv_key(owner_id)
+}
+
+#[deprecated(note = "use `ToolkitConfig::resolve_signer`")]
+pub fn resolve_signer(
+    owner: Option<&str>,
+    key_flag: Option<&str>,
+) -> Result<secp256k1::SecretKey, ToolkitConfigError> {
+    ToolkitConfig::from_env()?.resolve_signer(owner, key_flag)
+}
+
+#[deprecated(note = "use `ToolkitConfig::add_wallet_account`")]
+pub fn add_wallet_account(
+    owner_id: &str,
+    account: &DevAccount,
+    force: bool,
+) -> Result<(), ToolkitConfigError> {
+    ToolkitConfig::from_env()?.add_wallet_account(owner_id, account, force)
+}
+
+#[deprecated(note = "use `ToolkitConfig::validate_contract_address_registry`")]
+pub fn validate_contract_address_registry() -> Result<(), ToolkitConfigError> {
+    ToolkitConfig::from_env()?.validate_contract_address_registry()
+}
+
+#[deprecated(note = "use `ToolkitConfig::ebpf_contract_address_for`")]
+pub fn get_toolkit_ebpf_contract_address_for(
+    artifact_id: &str,
+    contract_id: Option<&str>,
+) -> Result<AddressStr, ToolkitConfigError> {
+    ToolkitConfig::from_env()?
+        .ebpf_contract_address_for(artifact_id, contract_id)
+}
+
+#[deprecated(note = "use `ToolkitConfig::evm_contract_address_for`")]
+pub fn get_toolkit_evm_contract_address_for(
+    artifact_id: &str,
+    contract_id: Option<&str>,
+) -> Result<AddressStr, ToolkitConfigError> {
+    ToolkitConfig::from_env()?
+        .evm_contract_address_for(artifact_id, contract_id)
+}
+
+#[deprecated(note = "use `ToolkitConfig::resolve_contract_address`")]
+pub fn resolve_contract_address(
+    alias: &str,
+) -> Result<AddressStr, ToolkitConfigError> {
+    ToolkitConfig::from_env()?.resolve_contract_address(alias)
+}
+
+#[deprecated(note = "use `ToolkitConfig::update_contract_address_registry`")]
+pub fn update_toolkit_contract_address_registry(
+    update_type: L1XVMContractAddressUpdateType,
+) -> Result<(), ToolkitConfigError> {
+    ToolkitConfig::from_env()?.update_contract_address_registry(update_type)
+}
+
+#[deprecated(
+    note = "use `ToolkitConfig::remove_contract_address_registry_entry`"
+)]
+pub fn remove_toolkit_contract_address_registry_entry(
+    remove_type: L1XVMContractAddressRemoveType,
+    dry_run: bool,
+) -> Result<Vec<(String, AddressStr)>, ToolkitConfigError> {
+    ToolkitConfig::from_env()?
+        .remove_contract_address_registry_entry(remove_type, dry_run)
+}
@@ -0,0 +1,1456 @@
+use crate::toolkit_config::*;
+use crate::transport::L1XProtocol;
+
+use std::{fs, path::Path};
+
+/// Workspace whose `local` network listens on `rpc_port` and whose wallet
+/// `ava` holds the key `key_byte`
+fn write_workspace(ws_home: &Path, rpc_port: u16, key_byte: u8) {
+    fs::create_dir_all(ws_home.join("l1x-conf")).unwrap();
+    fs::write(
+        ws_home.join("l1x-conf/l1x_chain_config.yaml"),
+        format!(
+            "networks:\n  local:\n    chain_id: 1\n    host_ip: \"127.0.0.1\"\n    rpc_port: {rpc_port}\n    rpc_endpoint: \"http://127.0.0.1:{rpc_port}\"\n"
+        ),
+    )
+    .unwrap();
+    fs::write(
+        ws_home.join("l1x-conf/l1x_dev_wallets.yaml"),
+        format!("dev_accounts:\n  ava:\n    priv_key: \"{:064x}\"\n", key_byte),
+    )
+    .unwrap();
+}
+
+fn workspace(ws_home: &Path) -> ToolkitConfig {
+    ToolkitConfig::new(ToolkitPaths::new(ws_home.to_path_buf()))
+        .with_chain_type("local")
+}
+
+#[test]
+fn test_paths_of_workspace() {
+    let paths = ToolkitPaths::new("/ws".into());
+
+    assert_eq!(paths.ws_home(), Path::new("/ws"));
+    assert_eq!(
+        paths.chain_config_file(),
+        Path::new("/ws/l1x-conf/l1x_chain_config.yaml")
+    );
+    assert_eq!(
+        paths.contract_address_registry_file(),
+        Path::new("/ws/l1x-conf/config-contract-address-registry.yaml")
+    );
+}
+
+#[test]
+fn test_explicit_ws_home_overrides_env_and_user_config_dir() {
+    let paths = ToolkitPaths::resolve_from(
+        Some("/explicit".into()),
+        Some("/env".to_string()),
+        Some("/home/dev/.config/l1x".into()),
+    )
+    .unwrap();
+
+    assert_eq!(paths.ws_home(), Path::new("/explicit"));
+    assert_eq!(paths.ws_home_source(), WsHomeSource::Explicit);
+}
+
+#[test]
+fn test_env_ws_home_overrides_user_config_dir() {
+    let paths = ToolkitPaths::resolve_from(
+        None,
+        Some("/env".to_string()),
+        Some("/home/dev/.config/l1x".into()),
+    )
+    .unwrap();
+
+    assert_eq!(paths.ws_home(), Path::new("/env"));
+    assert_eq!(paths.ws_home_source(), WsHomeSource::Env);
+    assert_eq!(
+        paths.wallet_config_file(),
+        Path::new("/env/l1x-conf/l1x_dev_wallets.yaml")
+    );
+}
+
+#[test]
+fn test_unset_ws_home_falls_back_to_user_config_dir() {
+    let paths = ToolkitPaths::resolve_from(
+        None,
+        None,
+        Some("/home/dev/.config/l1x".into()),
+    )
+    .unwrap();
+    let err = ToolkitPaths::resolve_from(None, None, None).unwrap_err();
+
+    assert_eq!(paths.ws_home(), Path::new("/home/dev/.config/l1x"));
+    assert_eq!(paths.ws_home_source(), WsHomeSource::UserConfigDir);
+    assert!(matches!(err, ToolkitConfigError::MissingEnvVar(WS_HOME_ENV)));
+}
+
+#[test]
+fn test_missing_config_file_names_searched_path() {
+    let ws_home = tempfile::tempdir().unwrap();
+    let chain_config_file =
+        ws_home.path().join("l1x-conf/l1x_chain_config.yaml");
+
+    let err = workspace(ws_home.path()).network_config().unwrap_err();
+
+    assert!(matches!(err, ToolkitConfigError::MissingConfigFile(_)));
+    assert!(
+        err.to_string().contains(&chain_config_file.display().to_string()),
+        "{}",
+        err
+    );
+}
+
+/// Networks `local` and `testnet`, `default_network` naming `testnet`
+fn write_networks(ws_home: &Path) {
+    fs::create_dir_all(ws_home.join("l1x-conf")).unwrap();
+    fs::write(
+        ws_home.join("l1x-conf/l1x_chain_config.yaml"),
+        r#"
+default_network: testnet
+networks:
+  local:
+    chain_id: 1
+    host_ip: "127.0.0.1"
+    rpc_port: 50051
+    rpc_endpoint: "http://127.0.0.1:50051"
+  testnet:
+    chain_id: 1076
+    host_ip: "10.0.0.1"
+    rpc_port: 50052
+    rpc_endpoint: "http://10.0.0.1:50052"
+"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_active_cluster_address() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    fs::write(
+        ws_home.path().join("l1x-conf/l1x_chain_config.yaml"),
+        r#"
+networks:
+  local:
+    chain_id: 1
+    host_ip: "127.0.0.1"
+    rpc_port: 50051
+  testnet:
+    chain_id: 1076
+    host_ip: "10.0.0.1"
+    rpc_port: 50052
+    cluster_address: "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF"
+"#,
+    )
+    .unwrap();
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+
+    // Act
+    let local =
+        toolkit.clone().with_chain_type("local").active_cluster_address();
+    let testnet = toolkit.with_chain_type("testnet").active_cluster_address();
+
+    // Assert
+    assert_eq!(local.unwrap(), None);
+    assert_eq!(
+        testnet.unwrap().map(|address| address.to_hex()),
+        Some("2b5ad5c4795c026514f8317c7a215e218dccd6cf".to_string())
+    );
+}
+
+#[test]
+fn test_active_protocol() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    fs::write(
+        ws_home.path().join("l1x-conf/l1x_chain_config.yaml"),
+        r#"
+networks:
+  local:
+    chain_id: 1
+    host_ip: "127.0.0.1"
+    rpc_port: 50051
+  testnet:
+    chain_id: 1076
+    host_ip: "10.0.0.1"
+    rpc_port: 50052
+    protocol: grpc
+"#,
+    )
+    .unwrap();
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+
+    // Act
+    let local = toolkit.clone().with_chain_type("local").active_protocol();
+    let testnet = toolkit.with_chain_type("testnet").active_protocol();
+
+    // Assert
+    assert_eq!(local.unwrap(), L1XProtocol::Json);
+    assert_eq!(testnet.unwrap(), L1XProtocol::Grpc);
+}
+
+#[test]
+fn test_active_network_is_dev() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    fs::write(
+        ws_home.path().join("l1x-conf/l1x_chain_config.yaml"),
+        r#"
+networks:
+  local:
+    chain_id: 1
+    host_ip: "127.0.0.1"
+    rpc_port: 50051
+    is_dev: true
+  testnet:
+    chain_id: 1076
+    host_ip: "10.0.0.1"
+    rpc_port: 50052
+"#,
+    )
+    .unwrap();
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+
+    // Act
+    let local =
+        toolkit.clone().with_chain_type("local").active_network_is_dev();
+    let testnet = toolkit.clone().with_chain_type("testnet");
+    let mainnet = toolkit.with_chain_type("mainnet").active_network_is_dev();
+
+    // Assert
+    assert!(local.unwrap());
+    assert!(!testnet.active_network_is_dev().unwrap());
+    assert!(mainnet.is_err());
+}
+
+#[test]
+fn test_network_precedence() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+    // What `from_env` builds with `L1X_CFG_CHAIN_TYPE=local`
+    let with_env = toolkit.clone().with_chain_type("local");
+
+    // Act
+    let endpoints = [
+        toolkit.active_chain_json_rpc_endpoint().unwrap(),
+        with_env
+            .clone()
+            .with_network(None)
+            .active_chain_json_rpc_endpoint()
+            .unwrap(),
+        with_env
+            .with_network(Some("testnet"))
+            .active_chain_json_rpc_endpoint()
+            .unwrap(),
+    ];
+
+    // Assert
+    assert_eq!(
+        endpoints,
+        [
+            "http://10.0.0.1:50052",
+            "http://127.0.0.1:50051",
+            "http://10.0.0.1:50052",
+        ]
+    );
+    assert_eq!(
+        toolkit.chain_json_rpc_endpoint("local").unwrap(),
+        "http://127.0.0.1:50051"
+    );
+}
+
+#[test]
+fn test_unknown_network_lists_available() {
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()))
+        .with_network(Some("mainnet"));
+
+    let err = toolkit.active_chain_json_rpc_endpoint().unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "chain type 'mainnet' not found; available chain types: local, testnet"
+    );
+}
+
+#[test]
+fn test_handle_without_chain_type() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_workspace(ws_home.path(), 50051, 1);
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+
+    // Act
+    let err = toolkit.active_chain_json_rpc_endpoint().unwrap_err();
+
+    // Assert
+    assert!(matches!(
+        &err,
+        ToolkitConfigError::NoActiveNetwork { available } if available == "local"
+    ));
+    assert_eq!(toolkit.wallet_priv_key("ava").unwrap(), format!("{:064x}", 1));
+}
+
+#[test]
+fn test_handles_on_two_workspaces_run_concurrently() {
+    // Arrange
+    let ws_homes = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+    write_workspace(ws_homes[0].path(), 50051, 1);
+    write_workspace(ws_homes[1].path(), 60061, 2);
+
+    // Act
+    std::thread::scope(|scope| {
+        for (index, ws_home) in ws_homes.iter().enumerate() {
+            scope.spawn(move || {
+                let toolkit = workspace(ws_home.path());
+                for round in 0..20 {
+                    toolkit
+                        .update_contract_address_registry(
+                            L1XVMContractAddressUpdateType::L1XEVM_DEPLOY {
+                                artifact_id: format!("erc20_{index}_{round}"),
+                                response_hash: format!("h{round}"),
+                                response_address: format!("{:040x}", index)
+                                    .parse()
+                                    .unwrap(),
+                            },
+                        )
+                        .unwrap();
+
+                    assert_eq!(
+                        toolkit
+                            .evm_contract_address_for(
+                                &format!("erc20_{index}_{round}"),
+                                None
+                            )
+                            .unwrap()
+                            .to_hex(),
+                        format!("{:040x}", index)
+                    );
+                }
+            });
+        }
+    });
+
+    // Assert
+    let (first, second) =
+        (workspace(ws_homes[0].path()), workspace(ws_homes[1].path()));
+    assert_eq!(
+        first.active_chain_json_rpc_endpoint().unwrap(),
+        "http://127.0.0.1:50051"
+    );
+    assert_eq!(
+        second.active_chain_json_rpc_endpoint().unwrap(),
+        "http://127.0.0.1:60061"
+    );
+    assert_eq!(first.wallet_priv_key("ava").unwrap(), format!("{:064x}", 1));
+    assert_eq!(second.wallet_priv_key("ava").unwrap(), format!("{:064x}", 2));
+    assert!(first.evm_contract_address_for("erc20_1_0", None).is_err());
+    assert!(second.evm_contract_address_for("erc20_0_19", None).is_err());
+    assert!(second.evm_contract_address_for("erc20_1_19", None).is_ok());
+}
+
+#[test]
+fn test_update_network_config_round_trip() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let chain_config_file =
+        ws_home.path().join("l1x-conf/l1x_chain_config.yaml");
+    let mut chain_config = fs::read_to_string(&chain_config_file).unwrap();
+    chain_config.push_str("artifact_dirs:\n  ebpf: \"out/ebpf\"\n");
+    fs::write(&chain_config_file, chain_config).unwrap();
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+    let devnet = Network::new(7, "10.0.0.7", 8545, "http://10.0.0.7:8545");
+
+    // Act
+    toolkit
+        .update_network_config(L1XNetworkUpdateType::Add {
+            name: "devnet".to_string(),
+            network: devnet.clone(),
+            force: false,
+        })
+        .unwrap();
+    toolkit
+        .update_network_config(L1XNetworkUpdateType::SetDefault {
+            name: "devnet".to_string(),
+        })
+        .unwrap();
+    toolkit
+        .update_network_config(L1XNetworkUpdateType::Remove {
+            name: "testnet".to_string(),
+        })
+        .unwrap();
+
+    // Assert
+    let config_network = toolkit.network_config().unwrap();
+    let mut names: Vec<&String> = config_network.networks().keys().collect();
+    names.sort();
+    assert_eq!(names, ["devnet", "local"]);
+    assert_eq!(config_network.networks()["devnet"], devnet);
+    assert_eq!(config_network.default_network(), Some("devnet"));
+    assert_eq!(
+        toolkit.artifact_dir(L1XVMArtifactType::Ebpf),
+        ws_home.path().join("out/ebpf")
+    );
+    assert!(!ws_home
+        .path()
+        .join("l1x-conf/l1x_chain_config.yaml.tmp")
+        .exists());
+}
+
+#[test]
+fn test_update_network_config_creates_missing_file() {
+    let ws_home = tempfile::tempdir().unwrap();
+    fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+
+    toolkit
+        .update_network_config(L1XNetworkUpdateType::Add {
+            name: "local".to_string(),
+            network: Network::new(
+                1,
+                "127.0.0.1",
+                50051,
+                "http://127.0.0.1:50051",
+            ),
+            force: false,
+        })
+        .unwrap();
+
+    assert_eq!(
+        toolkit.chain_json_rpc_endpoint("local").unwrap(),
+        "http://127.0.0.1:50051"
+    );
+}
+
+#[test]
+fn test_remove_default_network_unsets_it() {
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+
+    toolkit
+        .update_network_config(L1XNetworkUpdateType::Remove {
+            name: "testnet".to_string(),
+        })
+        .unwrap();
+
+    assert_eq!(toolkit.network_config().unwrap().default_network(), None);
+    assert!(matches!(
+        toolkit.active_network().unwrap_err(),
+        ToolkitConfigError::NoActiveNetwork { available } if available == "local"
+    ));
+}
+
+#[test]
+fn test_update_network_config_rejects_conflicts() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+    let local = Network::new(2, "127.0.0.1", 50061, "http://127.0.0.1:50061");
+
+    // Act
+    let add_err = toolkit
+        .update_network_config(L1XNetworkUpdateType::Add {
+            name: "local".to_string(),
+            network: local.clone(),
+            force: false,
+        })
+        .unwrap_err();
+    let remove_err = toolkit
+        .update_network_config(L1XNetworkUpdateType::Remove {
+            name: "mainnet".to_string(),
+        })
+        .unwrap_err();
+    let default_err = toolkit
+        .update_network_config(L1XNetworkUpdateType::SetDefault {
+            name: "mainnet".to_string(),
+        })
+        .unwrap_err();
+    toolkit
+        .update_network_config(L1XNetworkUpdateType::Add {
+            name: "local".to_string(),
+            network: local.clone(),
+            force: true,
+        })
+        .unwrap();
+
+    // Assert
+    assert!(matches!(add_err, ToolkitConfigError::NetworkExists { .. }));
+    for err in [remove_err, default_err] {
+        assert!(matches!(
+            &err,
+            ToolkitConfigError::UnknownNetwork { network, available }
+                if network == "mainnet" && available == "local, testnet"
+        ));
+    }
+    assert_eq!(toolkit.network_config().unwrap().networks()["local"], local);
+}
+
+/// Workspace whose only network is `network_yaml`, indented under `networks`
+fn write_network_entry(ws_home: &Path, network_yaml: &str) -> ToolkitConfig {
+    fs::create_dir_all(ws_home.join("l1x-conf")).unwrap();
+    fs::write(
+        ws_home.join("l1x-conf/l1x_chain_config.yaml"),
+        format!("networks:\n  edge:\n{network_yaml}"),
+    )
+    .unwrap();
+    ToolkitConfig::new(ToolkitPaths::new(ws_home.into()))
+        .with_chain_type("edge")
+}
+
+#[test]
+fn test_empty_endpoint_is_synthesized() {
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = write_network_entry(
+        ws_home.path(),
+        "    chain_id: 1\n    host_ip: \"10.0.0.1\"\n    rpc_port: 50051\n    rpc_endpoint: \"\"\n",
+    );
+
+    assert_eq!(
+        toolkit.active_chain_json_rpc_endpoint().unwrap(),
+        "http://10.0.0.1:50051"
+    );
+}
+
+#[test]
+fn test_missing_endpoint_of_ipv6_host_is_bracketed() {
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = write_network_entry(
+        ws_home.path(),
+        "    chain_id: 1\n    host_ip: \"::1\"\n    rpc_port: 50051\n",
+    );
+
+    assert_eq!(
+        toolkit.active_chain_json_rpc_endpoint().unwrap(),
+        "http://[::1]:50051"
+    );
+}
+
+#[test]
+fn test_ipv6_endpoint_is_accepted() {
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = write_network_entry(
+        ws_home.path(),
+        "    chain_id: 1\n    host_ip: \"fd00::1\"\n    rpc_port: 50051\n    rpc_endpoint: \" http://[fd00::1]:50051 \"\n",
+    );
+
+    assert_eq!(
+        toolkit.active_chain_json_rpc_endpoint().unwrap(),
+        "http://[fd00::1]:50051"
+    );
+}
+
+#[test]
+fn test_bad_endpoint_scheme_names_the_network() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = write_network_entry(
+        ws_home.path(),
+        "    chain_id: 1\n    host_ip: \"10.0.0.1\"\n    rpc_port: 50051\n    rpc_endpoint: \"ftp://10.0.0.1:50051\"\n",
+    );
+
+    // Act
+    let err = toolkit.active_chain_json_rpc_endpoint().unwrap_err();
+
+    // Assert
+    match &err {
+        ToolkitConfigError::InvalidNetwork { network, path, reason } => {
+            assert_eq!(network, "edge");
+            assert!(
+                path.ends_with("l1x-conf/l1x_chain_config.yaml"),
+                "{}",
+                path
+            );
+            assert_eq!(
+                reason,
+                "rpc_endpoint 'ftp://10.0.0.1:50051' must use http or https, not ftp"
+            );
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn test_endpoint_without_scheme_is_rejected() {
+    let ws_home = tempfile::tempdir().unwrap();
+    let toolkit = write_network_entry(
+        ws_home.path(),
+        "    chain_id: 1\n    host_ip: \"10.0.0.1\"\n    rpc_port: 50051\n    rpc_endpoint: \"10.0.0.1:50051\"\n",
+    );
+
+    let err = toolkit.network_config().unwrap_err();
+
+    assert!(
+        matches!(&err, ToolkitConfigError::InvalidNetwork { network, .. } if network == "edge"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_env_overrides_replace_file_values() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_workspace(ws_home.path(), 50051, 1);
+    let toolkit = workspace(ws_home.path()).with_env_overrides(
+        [
+            ("L1X__NETWORKS__LOCAL__RPC_ENDPOINT", "http://10.1.2.3:40041"),
+            ("L1X__DEV_ACCOUNTS__AVA__PRIV_KEY", &format!("{:064x}", 9)),
+            ("L1X_CFG_CHAIN_TYPE", "ignored"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect(),
+    );
+
+    // Act
+    let end_point = toolkit.active_chain_json_rpc_endpoint().unwrap();
+    let priv_key = toolkit.wallet_priv_key("ava").unwrap();
+
+    // Assert
+    assert_eq!(end_point, "http://10.1.2.3:40041");
+    assert_eq!(priv_key, format!("{:064x}", 9));
+    assert_eq!(
+        toolkit.network_config().unwrap().networks()["local"].rpc_port(),
+        50051
+    );
+}
+
+#[test]
+fn test_env_override_of_unknown_network_explains_naming() {
+    let ws_home = tempfile::tempdir().unwrap();
+    write_workspace(ws_home.path(), 50051, 1);
+    let toolkit = workspace(ws_home.path()).with_env_overrides(
+        [(
+            "L1X__NETWORKS__CI__RPC_ENDPOINT".to_string(),
+            "http://10.1.2.3:40041".to_string(),
+        )]
+        .into_iter()
+        .collect(),
+    );
+
+    let err = toolkit.network_config().unwrap_err();
+
+    assert!(matches!(err, ToolkitConfigError::ConfigParse { .. }));
+    assert!(
+        err.to_string().contains(
+            "environment overrides are named L1X__<KEY>__<NESTED_KEY>"
+        ),
+        "{}",
+        err
+    );
+}
+
+fn evm_deploy(
+    artifact_id: &str,
+    address_byte: u8,
+) -> L1XVMContractAddressUpdateType {
+    L1XVMContractAddressUpdateType::L1XEVM_DEPLOY {
+        artifact_id: artifact_id.to_string(),
+        response_hash: format!("h{address_byte}"),
+        response_address: format!("{:040x}", address_byte).parse().unwrap(),
+    }
+}
+
+#[test]
+fn test_registry_lookups_are_isolated_per_network() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let toolkit = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()));
+    let local = toolkit.clone().with_chain_type("local");
+    let testnet = toolkit.clone().with_chain_type("testnet");
+
+    // Act
+    local.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+    testnet.update_contract_address_registry(evm_deploy("erc20", 2)).unwrap();
+    local.update_contract_address_registry(evm_deploy("nft", 3)).unwrap();
+
+    // Assert
+    assert_eq!(
+        local.evm_contract_address_for("erc20", None).unwrap().to_hex(),
+        format!("{:040x}", 1)
+    );
+    assert_eq!(
+        testnet.evm_contract_address_for("erc20", None).unwrap().to_hex(),
+        format!("{:040x}", 2)
+    );
+    assert!(matches!(
+        testnet.evm_contract_address_for("nft", None).unwrap_err(),
+        ToolkitConfigError::RegistryMissingArtifact(_)
+    ));
+    // Without a chain type, the default network `testnet`
+    assert_eq!(
+        toolkit.resolve_contract_address("erc20").unwrap().to_hex(),
+        format!("{:040x}", 2)
+    );
+}
+
+#[test]
+fn test_flat_registry_moves_under_network_on_first_write() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let registry_file =
+        ws_home.path().join("l1x-conf/config-contract-address-registry.yaml");
+    fs::write(
+        &registry_file,
+        format!(
+            "l1x_vm:\n  ft:\n    deploy_hash: \"d1\"\n    deploy_address: \"0x{}\"\n    instance: {{}}\nl1x_evm: {{}}\n",
+            "a".repeat(40)
+        ),
+    )
+    .unwrap();
+    let local = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()))
+        .with_chain_type("local");
+    let testnet = local.clone().with_chain_type("testnet");
+
+    // Act
+    let before_write =
+        testnet.ebpf_contract_address_for("ft", None).unwrap().to_hex();
+    local.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+
+    // Assert
+    assert_eq!(before_write, "a".repeat(40));
+    assert_eq!(
+        local.ebpf_contract_address_for("ft", None).unwrap().to_hex(),
+        "a".repeat(40)
+    );
+    assert!(matches!(
+        testnet.ebpf_contract_address_for("ft", None).unwrap_err(),
+        ToolkitConfigError::RegistryMissingArtifact(_)
+    ));
+    let registry: serde_yaml::Value =
+        serde_yaml::from_str(&fs::read_to_string(&registry_file).unwrap())
+            .unwrap();
+    assert!(registry.get("l1x_vm").is_none());
+    assert_eq!(
+        registry["networks"]["local"]["l1x_evm"]["erc20"]["deploy_hash"],
+        "h1"
+    );
+}
+
+#[test]
+fn test_removal_only_touches_the_active_network() {
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let local = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()))
+        .with_chain_type("local");
+    let testnet = local.clone().with_chain_type("testnet");
+    local.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+    testnet.update_contract_address_registry(evm_deploy("erc20", 2)).unwrap();
+
+    let removed = testnet
+        .remove_contract_address_registry_entry(
+            L1XVMContractAddressRemoveType::L1XEVM_REMOVE {
+                artifact_id: "erc20".to_string(),
+            },
+            false,
+        )
+        .unwrap();
+
+    assert_eq!(
+        removed,
+        [("l1x_evm.erc20".to_string(), format!("{:040x}", 2).parse().unwrap())]
+    );
+    assert!(testnet.evm_contract_address_for("erc20", None).is_err());
+    assert_eq!(
+        local.evm_contract_address_for("erc20", None).unwrap().to_hex(),
+        format!("{:040x}", 1)
+    );
+}
+
+#[test]
+fn test_ebpf_removal_of_instance_or_whole_artifact() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_workspace(ws_home.path(), 50051, 1);
+    let local = workspace(ws_home.path());
+    local
+        .update_contract_address_registry(
+            L1XVMContractAddressUpdateType::L1XEBPF_DEPLOY {
+                artifact_id: "ft".to_string(),
+                response_hash: "d1".to_string(),
+                response_address: "a".repeat(40).parse().unwrap(),
+            },
+        )
+        .unwrap();
+    for (contract_id, address_byte) in [("ft_main", "b"), ("ft_second", "c")] {
+        local
+            .update_contract_address_registry(
+                L1XVMContractAddressUpdateType::L1XEBPF_INIT {
+                    artifact_id: "ft".to_string(),
+                    contract_id: contract_id.to_string(),
+                    response_hash: format!("i{}", address_byte),
+                    response_address: address_byte.repeat(40).parse().unwrap(),
+                },
+            )
+            .unwrap();
+    }
+    let remove = |contract_id: Option<&str>| {
+        local
+            .remove_contract_address_registry_entry(
+                L1XVMContractAddressRemoveType::L1XEBPF_REMOVE {
+                    artifact_id: "ft".to_string(),
+                    contract_id: contract_id.map(str::to_string),
+                },
+                false,
+            )
+            .unwrap()
+    };
+
+    // Act
+    let instance_removed = remove(Some("ft_main"));
+    let deployment_after_instance =
+        local.ebpf_contract_address_for("ft", None).unwrap().to_hex();
+    let artifact_removed = remove(None);
+
+    // Assert
+    assert_eq!(
+        instance_removed,
+        [(
+            "l1x_vm.ft.instance.ft_main".to_string(),
+            "b".repeat(40).parse().unwrap()
+        )]
+    );
+    assert_eq!(deployment_after_instance, "a".repeat(40));
+    assert_eq!(
+        artifact_removed,
+        [
+            (
+                "l1x_vm.ft.instance.ft_second".to_string(),
+                "c".repeat(40).parse().unwrap()
+            ),
+            ("l1x_vm.ft".to_string(), "a".repeat(40).parse().unwrap()),
+        ]
+    );
+    assert!(matches!(
+        local.ebpf_contract_address_for("ft", None).unwrap_err(),
+        ToolkitConfigError::RegistryMissingArtifact(_)
+    ));
+    assert!(remove(None).is_empty());
+    assert!(!ws_home
+        .path()
+        .join("l1x-conf/config-contract-address-registry.yaml.tmp")
+        .exists());
+}
+
+#[test]
+fn test_failed_registry_write_keeps_the_previous_registry() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_workspace(ws_home.path(), 50051, 1);
+    let local = workspace(ws_home.path());
+    local.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+    let registry_file =
+        ws_home.path().join("l1x-conf/config-contract-address-registry.yaml");
+    let before = fs::read_to_string(&registry_file).unwrap();
+    // The temp file the registry is written to can't be created
+    fs::create_dir(
+        ws_home
+            .path()
+            .join("l1x-conf/config-contract-address-registry.yaml.tmp"),
+    )
+    .unwrap();
+
+    // Act
+    let err = local
+        .update_contract_address_registry(evm_deploy("usdc", 2))
+        .unwrap_err();
+
+    // Assert
+    assert!(matches!(err, ToolkitConfigError::ConfigWrite { .. }));
+    assert_eq!(fs::read_to_string(&registry_file).unwrap(), before);
+    assert_eq!(
+        local.evm_contract_address_for("erc20", None).unwrap().to_hex(),
+        format!("{:040x}", 1)
+    );
+}
+
+#[test]
+fn test_concurrent_registry_updates_all_survive() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_workspace(ws_home.path(), 50051, 1);
+
+    // Act
+    std::thread::scope(|scope| {
+        for task in 0..8u8 {
+            let toolkit = workspace(ws_home.path());
+            scope.spawn(move || {
+                for round in 0..10u8 {
+                    toolkit
+                        .update_contract_address_registry(evm_deploy(
+                            &format!("erc20_{task}_{round}"),
+                            task * 10 + round,
+                        ))
+                        .unwrap();
+                }
+            });
+        }
+    });
+
+    // Assert
+    let toolkit = workspace(ws_home.path());
+    for task in 0..8u8 {
+        for round in 0..10u8 {
+            assert_eq!(
+                toolkit
+                    .evm_contract_address_for(
+                        &format!("erc20_{task}_{round}"),
+                        None
+                    )
+                    .unwrap()
+                    .to_hex(),
+                format!("{:040x}", task * 10 + round)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_corrupted_registry_is_backed_up_not_replaced() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_workspace(ws_home.path(), 50051, 1);
+    let registry_file =
+        ws_home.path().join("l1x-conf/config-contract-address-registry.yaml");
+    fs::write(&registry_file, "l1x_vm: [").unwrap();
+
+    // Act
+    let err = workspace(ws_home.path())
+        .update_contract_address_registry(evm_deploy("erc20", 1))
+        .unwrap_err();
+
+    // Assert
+    assert!(matches!(err, ToolkitConfigError::ConfigParse { .. }));
+    assert!(err.to_string().contains("saved a copy to"), "{}", err);
+    assert_eq!(fs::read_to_string(&registry_file).unwrap(), "l1x_vm: [");
+    assert_eq!(
+        fs::read_to_string(
+            ws_home
+                .path()
+                .join("l1x-conf/config-contract-address-registry.yaml.bak")
+        )
+        .unwrap(),
+        "l1x_vm: ["
+    );
+}
+
+#[test]
+fn test_iso8601_utc() {
+    assert_eq!(iso8601_utc(0), "1970-01-01T00:00:00Z");
+    assert_eq!(iso8601_utc(951_782_400), "2000-02-29T00:00:00Z");
+    assert_eq!(iso8601_utc(1_700_000_000), "2023-11-14T22:13:20Z");
+}
+
+#[test]
+fn test_deploy_metadata_is_recorded_and_optional() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let local = ToolkitConfig::new(ToolkitPaths::new(ws_home.path().into()))
+        .with_chain_type("local");
+    local.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+    let metadata = L1XVMDeployMetadata {
+        owner: Some("ava".to_string()),
+        fee_limit: Some((u128::from(u64::MAX) + 1).to_string()),
+        fee_used: Some("37".to_string()),
+        artifact_sha256: Some("ab".repeat(32)),
+        ..Default::default()
+    };
+
+    // Act
+    local
+        .update_contract_address_registry_with_metadata(
+            evm_deploy("usdc", 2),
+            metadata.clone(),
+        )
+        .unwrap();
+    let entries = local.contract_address_registry_entries().unwrap();
+
+    // Assert
+    assert_eq!(entries[0].artifact_id, "erc20");
+    assert_eq!(entries[0].metadata, L1XVMDeployMetadata::default());
+    assert_eq!(entries[1].artifact_id, "usdc");
+    let deployed_at = entries[1].metadata.deployed_at.clone().unwrap();
+    assert_eq!(deployed_at.len(), "2023-11-14T22:13:20Z".len());
+    assert_eq!(
+        entries[1].metadata,
+        L1XVMDeployMetadata {
+            deployed_at: Some(deployed_at.clone()),
+            network: Some("local".to_string()),
+            ..metadata
+        }
+    );
+    assert!(entries[1].to_string().ends_with(&format!(
+        ":: deployed_at={} network=local owner=ava fee_limit=18446744073709551616 fee_used=37 artifact_sha256={}",
+        deployed_at,
+        "ab".repeat(32)
+    )));
+}
+
+#[test]
+fn test_legacy_quoted_addresses_are_read_and_rewritten_clean() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let registry_file =
+        ws_home.path().join("l1x-conf/config-contract-address-registry.yaml");
+    fs::write(
+        &registry_file,
+        format!(
+            r#"networks:
+  local:
+    l1x_vm:
+      ft:
+        deploy_hash: d1
+        deploy_address: '"0x{}"'
+        instance:
+          ft_main:
+            inst_hash: i1
+            inst_address: '"0x{}"'
+            previous:
+            - inst_hash: i0
+              inst_address: '"0x{}"'
+    l1x_evm: {{}}
+"#,
+            "A".repeat(40),
+            "b".repeat(40),
+            "c".repeat(40)
+        ),
+    )
+    .unwrap();
+    let local = workspace(ws_home.path());
+
+    // Act
+    let legacy_addresses = (
+        local.ebpf_contract_address_for("ft", None).unwrap().to_hex(),
+        local
+            .ebpf_contract_address_for("ft", Some("ft_main"))
+            .unwrap()
+            .to_hex(),
+    );
+    local.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+
+    // Assert
+    assert_eq!(legacy_addresses, ("a".repeat(40), "b".repeat(40)));
+    let registry_yaml = fs::read_to_string(&registry_file).unwrap();
+    assert!(!registry_yaml.contains('"'), "{}", registry_yaml);
+    let registry: serde_yaml::Value =
+        serde_yaml::from_str(&registry_yaml).unwrap();
+    let local_registry = &registry["networks"]["local"];
+    let instance = &local_registry["l1x_vm"]["ft"]["instance"]["ft_main"];
+    assert_eq!(
+        local_registry["l1x_vm"]["ft"]["deploy_address"],
+        format!("0x{}", "a".repeat(40))
+    );
+    assert_eq!(instance["inst_address"], format!("0x{}", "b".repeat(40)));
+    assert_eq!(
+        instance["previous"][0]["inst_address"],
+        format!("0x{}", "c".repeat(40))
+    );
+    assert_eq!(
+        local_registry["l1x_evm"]["erc20"]["deploy_address"],
+        format!("0x{:040x}", 1)
+    );
+}
+
+#[test]
+fn test_wasm_contracts_are_registered_apart_from_ebpf() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let registry_file =
+        ws_home.path().join("l1x-conf/config-contract-address-registry.yaml");
+    fs::write(
+        &registry_file,
+        format!(
+            "networks:\n  local:\n    l1x_vm:\n      ft:\n        deploy_hash: d1\n        deploy_address: '0x{}'\n    l1x_evm: {{}}\n",
+            "a".repeat(40)
+        ),
+    )
+    .unwrap();
+    let local = workspace(ws_home.path());
+
+    // Act
+    let before = local.wasm_contract_address_for("ft", None).is_err();
+    local
+        .update_contract_address_registry(
+            L1XVMContractAddressUpdateType::L1XWASM_DEPLOY {
+                artifact_id: "ft".to_string(),
+                response_hash: "w1".to_string(),
+                response_address: "c".repeat(40).parse().unwrap(),
+            },
+        )
+        .unwrap();
+    local
+        .update_contract_address_registry(
+            L1XVMContractAddressUpdateType::L1XWASM_INIT {
+                artifact_id: "ft".to_string(),
+                contract_id: "ft_main".to_string(),
+                response_hash: "w2".to_string(),
+                response_address: "d".repeat(40).parse().unwrap(),
+            },
+        )
+        .unwrap();
+    let removed = local
+        .remove_contract_address_registry_entry(
+            L1XVMContractAddressRemoveType::L1XWASM_REMOVE {
+                artifact_id: "ft".to_string(),
+                contract_id: Some("ft_main".to_string()),
+            },
+            true,
+        )
+        .unwrap();
+
+    // Assert
+    assert!(before);
+    assert_eq!(
+        local
+            .contract_address_for(L1XVMArtifactType::Wasm, "ft", None)
+            .unwrap()
+            .to_hex(),
+        "c".repeat(40)
+    );
+    assert_eq!(
+        local
+            .contract_address_for(
+                L1XVMArtifactType::Wasm,
+                "ft",
+                Some("ft_main")
+            )
+            .unwrap()
+            .to_hex(),
+        "d".repeat(40)
+    );
+    assert_eq!(
+        local.ebpf_contract_address_for("ft", None).unwrap().to_hex(),
+        "a".repeat(40)
+    );
+    assert!(local.ebpf_contract_address_for("ft", Some("ft_main")).is_err());
+    assert_eq!(
+        removed,
+        vec![(
+            "l1x_wasm.ft.instance.ft_main".to_string(),
+            "d".repeat(40).parse().unwrap()
+        )]
+    );
+    let entries = local.contract_address_registry_entries().unwrap();
+    assert_eq!(
+        entries
+            .iter()
+            .map(|entry| (entry.vm_type.as_str(), entry.contract_id.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![("ebpf", None), ("wasm", None), ("wasm", Some("ft_main"))]
+    );
+    let registry: serde_yaml::Value =
+        serde_yaml::from_str(&fs::read_to_string(&registry_file).unwrap())
+            .unwrap();
+    assert_eq!(
+        registry["networks"]["local"]["l1x_wasm"]["ft"]["deploy_address"],
+        format!("0x{}", "c".repeat(40))
+    );
+}
+
+#[test]
+fn test_unchanged_registry_is_read_once() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let local = workspace(ws_home.path());
+    local.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+    let registry_file = local.paths().contract_address_registry_file();
+
+    // Act
+    for _ in 0..1000 {
+        local.evm_contract_address_for("erc20", None).unwrap();
+    }
+
+    // Assert
+    assert_eq!(config_file_reads(&registry_file), 1);
+}
+
+#[test]
+fn test_registry_changes_are_seen_mid_process() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let local = workspace(ws_home.path());
+    local.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+    let registry_file = local.paths().contract_address_registry_file();
+    let before_update =
+        local.evm_contract_address_for("erc20", None).unwrap().to_hex();
+
+    // Act
+    local.update_contract_address_registry(evm_deploy("erc20", 2)).unwrap();
+    let after_update =
+        local.evm_contract_address_for("erc20", None).unwrap().to_hex();
+    // Another process rewriting the file
+    let registry_yaml = fs::read_to_string(&registry_file).unwrap();
+    fs::write(&registry_file, registry_yaml.replace("erc20", "usdc")).unwrap();
+    let after_rewrite = local.evm_contract_address_for("usdc", None);
+
+    // Assert
+    assert_eq!(before_update, format!("{:040x}", 1));
+    assert_eq!(after_update, format!("{:040x}", 2));
+    assert_eq!(after_rewrite.unwrap().to_hex(), format!("{:040x}", 2));
+    assert_eq!(config_file_reads(&registry_file), 3);
+}
+
+const CHAIN_CONFIG_YAML: &str = r#"
+default_network: local
+networks:
+  local:
+    chain_id: 1
+    host_ip: "127.0.0.1"
+    rpc_port: 50051
+    rpc_endpoint: "http://127.0.0.1:50051"
+"#;
+
+const CHAIN_CONFIG_TOML: &str = r#"
+default_network = "local"
+
+[networks.local]
+chain_id = 1
+host_ip = "127.0.0.1"
+rpc_port = 50051
+rpc_endpoint = "http://127.0.0.1:50051"
+"#;
+
+const WALLETS_YAML: &str = r#"
+dev_accounts:
+  ava:
+    priv_key: "0000000000000000000000000000000000000000000000000000000000000001"
+"#;
+
+const WALLETS_TOML: &str = r#"
+[dev_accounts.ava]
+priv_key = "0000000000000000000000000000000000000000000000000000000000000001"
+"#;
+
+const REGISTRY_YAML: &str = r#"
+networks:
+  local:
+    l1x_vm:
+      ft:
+        deploy_hash: d1
+        deploy_address: "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+        instance:
+          ft_main:
+            inst_hash: i1
+            inst_address: "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+    l1x_evm: {}
+"#;
+
+const REGISTRY_TOML: &str = r#"
+[networks.local.l1x_vm.ft]
+deploy_hash = "d1"
+deploy_address = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+
+[networks.local.l1x_vm.ft.instance.ft_main]
+inst_hash = "i1"
+inst_address = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+
+[networks.local.l1x_evm]
+"#;
+
+/// Workspace with the config files `files`, as `(file name, content)`
+fn write_conf_files(ws_home: &Path, files: &[(&str, &str)]) -> ToolkitConfig {
+    fs::create_dir_all(ws_home.join("l1x-conf")).unwrap();
+    for (file_name, content) in files {
+        fs::write(ws_home.join("l1x-conf").join(file_name), content).unwrap();
+    }
+    ToolkitConfig::new(ToolkitPaths::new(ws_home.to_path_buf()))
+}
+
+#[test]
+fn test_toml_and_yaml_configs_parse_identically() {
+    // Arrange
+    let yaml_home = tempfile::tempdir().unwrap();
+    let toml_home = tempfile::tempdir().unwrap();
+    let yaml_workspace = write_conf_files(
+        yaml_home.path(),
+        &[
+            ("l1x_chain_config.yaml", CHAIN_CONFIG_YAML),
+            ("l1x_dev_wallets.yaml", WALLETS_YAML),
+            ("config-contract-address-registry.yaml", REGISTRY_YAML),
+        ],
+    );
+    let toml_workspace = write_conf_files(
+        toml_home.path(),
+        &[
+            ("l1x_chain_config.toml", CHAIN_CONFIG_TOML),
+            ("l1x_dev_wallets.toml", WALLETS_TOML),
+            ("config-contract-address-registry.toml", REGISTRY_TOML),
+        ],
+    );
+
+    // Act
+    let parse = |workspace: &ToolkitConfig| {
+        (
+            workspace.network_config().unwrap(),
+            workspace.wallet_config().unwrap(),
+            workspace.contract_address_registry_entries().unwrap(),
+        )
+    };
+
+    // Assert
+    assert_eq!(parse(&toml_workspace), parse(&yaml_workspace));
+    assert!(toml_workspace
+        .paths()
+        .chain_config_file()
+        .ends_with("l1x-conf/l1x_chain_config.toml"));
+}
+
+#[test]
+fn test_mixed_format_workspace() {
+    let ws_home = tempfile::tempdir().unwrap();
+    let workspace = write_conf_files(
+        ws_home.path(),
+        &[
+            ("l1x_chain_config.toml", CHAIN_CONFIG_TOML),
+            ("l1x_dev_wallets.yaml", WALLETS_YAML),
+        ],
+    );
+
+    assert_eq!(
+        workspace.active_chain_json_rpc_endpoint().unwrap(),
+        "http://127.0.0.1:50051"
+    );
+    assert_eq!(workspace.wallet_priv_key("ava").unwrap(), "0".repeat(63) + "1");
+}
+
+#[test]
+fn test_registry_writer_keeps_toml_format() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    let workspace = write_conf_files(
+        ws_home.path(),
+        &[
+            ("l1x_chain_config.toml", CHAIN_CONFIG_TOML),
+            ("config-contract-address-registry.toml", REGISTRY_TOML),
+        ],
+    );
+
+    // Act
+    workspace.update_contract_address_registry(evm_deploy("erc20", 1)).unwrap();
+
+    // Assert
+    let conf_dir = ws_home.path().join("l1x-conf");
+    assert!(!conf_dir.join("config-contract-address-registry.yaml").exists());
+    let registry: toml::Value = toml::from_str(
+        &fs::read_to_string(
+            conf_dir.join("config-contract-address-registry.toml"),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        registry["networks"]["local"]["l1x_evm"]["erc20"]["deploy_address"]
+            .as_str(),
+        Some(format!("0x{:040x}", 1).as_str())
+    );
+    assert_eq!(
+        workspace
+            .ebpf_contract_address_for("ft", Some("ft_main"))
+            .unwrap()
+            .to_hex(),
+        "b".repeat(40)
+    );
+}
+
+#[test]
+fn test_config_value_at_nested_paths() {
+    let config: serde_yaml::Value = serde_yaml::from_str(
+        "networks:\n  devnet:\n    rpc_port: 50051\n    peers: [a, b]\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config_value_at(&config, "networks.devnet.rpc_port"),
+        Some(&serde_yaml::Value::from(50051))
+    );
+    assert_eq!(
+        config_value_at(&config, "networks.devnet.peers.1"),
+        Some(&serde_yaml::Value::from("b"))
+    );
+    assert_eq!(config_value_at(&config, "networks.testnet.rpc_port"), None);
+    assert_eq!(config_value_at(&config, "networks.devnet.rpc_port.x"), None);
+}
+
+#[test]
+fn test_set_config_value_at_preserves_types() {
+    let mut config: serde_yaml::Value = serde_yaml::from_str(
+        "networks:\n  devnet:\n    chain_id: 1\n    host_ip: \"127.0.0.1\"\n",
+    )
+    .unwrap();
+
+    set_config_value_at(&mut config, "networks.devnet.chain_id", "1076")
+        .unwrap();
+    set_config_value_at(&mut config, "networks.devnet.host_ip", "10").unwrap();
+    set_config_value_at(&mut config, "networks.ci.rpc_port", "40041").unwrap();
+    let err = set_config_value_at(&mut config, "networks.devnet.chain_id", "x")
+        .unwrap_err();
+
+    assert_eq!(config["networks"]["devnet"]["chain_id"], 1076);
+    assert_eq!(config["networks"]["devnet"]["host_ip"], "10");
+    assert_eq!(config["networks"]["ci"]["rpc_port"], 40041);
+    assert!(err.contains("expected a number"), "{}", err);
+}
+
+#[test]
+fn test_set_config_value_at_rejects_scalar_parent() {
+    let mut config: serde_yaml::Value =
+        serde_yaml::from_str("default_network: devnet\n").unwrap();
+
+    let err = set_config_value_at(&mut config, "default_network.rpc_port", "1")
+        .unwrap_err();
+
+    assert!(err.contains("'default_network' is not a mapping"), "{}", err);
+}
+
+#[test]
+fn test_set_config_value_keeps_unrelated_content() {
+    // Arrange
+    let ws_home = tempfile::tempdir().unwrap();
+    write_networks(ws_home.path());
+    let toolkit = workspace(ws_home.path());
+
+    // Act
+    toolkit
+        .set_config_value(
+            L1XConfigFile::Chain,
+            "networks.local.rpc_endpoint",
+            "http://127.0.0.1:50052",
+        )
+        .unwrap();
+    let missing = toolkit
+        .config_value(L1XConfigFile::Chain, "networks.devnet.rpc_endpoint")
+        .unwrap_err();
+    let invalid = toolkit
+        .set_config_value(L1XConfigFile::Chain, "networks.local.chain_id", "")
+        .unwrap_err();
+
+    // Assert
+    let config_network = toolkit.network_config().unwrap();
+    assert_eq!(
+        config_network.networks()["local"].rpc_endpoint(),
+        "http://127.0.0.1:50052"
+    );
+    assert_eq!(config_network.networks()["local"].rpc_port(), 50051);
+    assert_eq!(config_network.networks()["testnet"].chain_id(), 1076);
+    assert_eq!(config_network.default_network(), Some("testnet"));
+    assert!(matches!(missing, ToolkitConfigError::MissingConfigKey { .. }));
+    assert!(matches!(invalid, ToolkitConfigError::InvalidConfigValue { .. }));
+}
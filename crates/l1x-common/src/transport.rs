@@ -0,0 +1,485 @@
+use crate::rpc::{self, RequestIds, RetryPolicy, RpcError, TxReceipt};
+
+use l1x_rpc::rpc_model::{
+    node_client::NodeClient, GetAccountStateRequest, GetEventsRequest,
+    GetEventsResponse, GetTransactionReceiptRequest,
+    SmartContractReadOnlyCallRequest, SubmitTransactionRequest,
+    SubmitTransactionResponse,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{future::Future, sync::Arc};
+use tonic::transport::{Channel, Endpoint};
+
+/// Protocol a node serves its API with
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum L1XProtocol {
+    /// JSON-RPC over HTTP
+    #[default]
+    Json,
+    Grpc,
+}
+
+impl L1XProtocol {
+    pub fn is_json(&self) -> bool {
+        *self == L1XProtocol::Json
+    }
+}
+
+/// Requests the toolkit sends to submit transactions and read their
+/// outcome, whatever the protocol of the node
+#[async_trait::async_trait]
+pub trait L1xTransport: std::fmt::Debug + Send + Sync {
+    async fn submit(
+        &self,
+        request: SubmitTransactionRequest,
+    ) -> Result<SubmitTransactionResponse, RpcError>;
+
+    /// Events data of `tx_hash`, empty while the node has none
+    async fn get_events(&self, tx_hash: &str)
+        -> Result<Vec<Vec<u8>>, RpcError>;
+
+    /// Result of the read-only `call`, `None` when the node returned none
+    async fn read_only_call(
+        &self,
+        call: SmartContractReadOnlyCallRequest,
+    ) -> Result<Option<Vec<u8>>, RpcError>;
+
+    /// Nonce of the last transaction of the hex `address`, `None` for an
+    /// account the node doesn't know
+    async fn get_nonce(&self, address: &str) -> Result<Option<u128>, RpcError>;
+
+    /// Receipt of `tx_hash`, `None` while the node doesn't know it
+    async fn get_receipt(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<TxReceipt>, RpcError>;
+}
+
+/// Transport of `protocol` to the node at `endpoint`, retrying with `retry`.
+/// JSON-RPC requests take their ids from `req_ids` and query receipts with
+/// `receipt_method`.
+pub fn transport_for(
+    protocol: L1XProtocol,
+    endpoint: &str,
+    req_ids: RequestIds,
+    retry: RetryPolicy,
+    receipt_method: &str,
+) -> Arc<dyn L1xTransport> {
+    match protocol {
+        L1XProtocol::Json => Arc::new(JsonRpcTransport::new(
+            endpoint,
+            req_ids,
+            retry,
+            receipt_method,
+        )),
+        L1XProtocol::Grpc => Arc::new(GrpcTransport::new(endpoint, retry)),
+    }
+}
+
+/// `T` of the JSON `value` of a response
+fn parse_response<T: DeserializeOwned>(
+    label: &str,
+    value: Value,
+) -> Result<T, RpcError> {
+    serde_json::from_value(value).map_err(|err_code| {
+        RpcError::InvalidResponse(format!("Invalid {}: {}", label, err_code))
+    })
+}
+
+/// Nonce of an account state response, `None` for an unknown account
+fn account_nonce(response: &Value) -> Result<Option<u128>, RpcError> {
+    let account_state = match &response["account_state"] {
+        Value::Null => return Ok(None),
+        account_state => account_state,
+    };
+
+    match &account_state["nonce"] {
+        Value::Number(nonce) => nonce.as_u64().map(u128::from),
+        Value::String(nonce) => nonce.parse().ok(),
+        _ => None,
+    }
+    .map(Some)
+    .ok_or_else(|| {
+        RpcError::InvalidResponse(
+            "Account state has no valid nonce".to_string(),
+        )
+    })
+}
+
+/// JSON-RPC transport, the API every node serves over HTTP
+#[derive(Debug, Clone)]
+pub struct JsonRpcTransport {
+    endpoint: String,
+    req_ids: RequestIds,
+    retry: RetryPolicy,
+    receipt_method: String,
+    client: reqwest::Client,
+}
+
+impl JsonRpcTransport {
+    pub fn new(
+        endpoint: &str,
+        req_ids: RequestIds,
+        retry: RetryPolicy,
+        receipt_method: &str,
+    ) -> Self {
+        JsonRpcTransport {
+            endpoint: endpoint.to_string(),
+            req_ids,
+            retry,
+            receipt_method: receipt_method.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Id of the next request
+    pub fn next_req_id(&self) -> u64 {
+        self.req_ids.next_id()
+    }
+
+    /// Post the JSON-RPC request `method` with the next request id and
+    /// return its `result`, see [`JsonRpcTransport::call_with_id`]
+    pub async fn call(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<Value>, RpcError> {
+        self.call_with_id(self.next_req_id(), method, params).await
+    }
+
+    /// Post the JSON-RPC request `method` with the id `req_id` and return its
+    /// `result`, retrying transport and server failures with the retry
+    /// policy. Every retry posts the same `params` and id, a response that
+    /// doesn't echo the id is an [`RpcError::IdMismatch`].
+    pub async fn call_with_id(
+        &self,
+        req_id: u64,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<Value>, RpcError> {
+        rpc::with_retries(method, self.retry, RpcError::is_retryable, || {
+            self.call_once(req_id, method, &params)
+        })
+        .await
+    }
+
+    async fn call_once(
+        &self,
+        req_id: u64,
+        method: &str,
+        params: &Value,
+    ) -> Result<Option<Value>, RpcError> {
+        let transport_err =
+            |err_code: reqwest::Error| match err_code.is_decode() {
+                true => RpcError::InvalidResponse(err_code.to_string()),
+                false => RpcError::Transport {
+                    endpoint: self.endpoint.clone(),
+                    reason: err_code.to_string(),
+                },
+            };
+
+        log::trace!(
+            "JSON-RPC {} req_id {} Req params :: {}",
+            method,
+            req_id,
+            params
+        );
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "method": method,
+                "params": params,
+                "id": req_id,
+            }))
+            .send()
+            .await
+            .map_err(transport_err)?;
+        let response: Value =
+            rpc::error_for_server_status(&self.endpoint, response)?
+                .json()
+                .await
+                .map_err(transport_err)?;
+
+        log::trace!(
+            "JSON-RPC {} req_id {} Resp :: {}",
+            method,
+            req_id,
+            &response
+        );
+        rpc::check_response_id(method, req_id, &response)?;
+
+        match response.get("error") {
+            Some(error) if !error.is_null() => Err(rpc::rpc_error(error)),
+            _ => Ok(response.get("result").cloned()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl L1xTransport for JsonRpcTransport {
+    async fn submit(
+        &self,
+        request: SubmitTransactionRequest,
+    ) -> Result<SubmitTransactionResponse, RpcError> {
+        let request = serde_json::to_value(&request).map_err(|err_code| {
+            RpcError::InvalidResponse(format!(
+                "Unable to serialize the transaction to JSON: {}",
+                err_code
+            ))
+        })?;
+
+        let result = self
+            .call("l1x_submitTransaction", json!({ "request": request }))
+            .await?;
+        parse_response("submit response", result.unwrap_or_default())
+    }
+
+    async fn get_events(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Vec<Vec<u8>>, RpcError> {
+        let result = self
+            .call(
+                "l1x_getEvents",
+                json!({ "request": GetEventsRequest {
+                    tx_hash: tx_hash.to_string(),
+                    timestamp: 0,
+                } }),
+            )
+            .await?;
+
+        parse_response::<GetEventsResponse>(
+            "events response",
+            result.unwrap_or_default(),
+        )
+        .map(|response| response.events_data)
+    }
+
+    async fn read_only_call(
+        &self,
+        call: SmartContractReadOnlyCallRequest,
+    ) -> Result<Option<Vec<u8>>, RpcError> {
+        let result = self
+            .call("l1x_smartContractReadOnlyCall", json!({ "request": call }))
+            .await?;
+
+        result
+            .map(|response| {
+                parse_response("read-only result", response["result"].clone())
+            })
+            .transpose()
+    }
+
+    async fn get_nonce(&self, address: &str) -> Result<Option<u128>, RpcError> {
+        let result = self
+            .call(
+                "l1x_getAccountState",
+                json!({ "request": { "address": address } }),
+            )
+            .await?;
+
+        account_nonce(&result.unwrap_or_default())
+    }
+
+    async fn get_receipt(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<TxReceipt>, RpcError> {
+        let result = self
+            .call(
+                &self.receipt_method,
+                json!({ "request": { "hash": tx_hash } }),
+            )
+            .await?;
+
+        Ok(result.and_then(|receipt| TxReceipt::from_result(&receipt)))
+    }
+}
+
+/// gRPC transport, for nodes that only serve the `l1x_rpc` gRPC API
+#[derive(Debug)]
+pub struct GrpcTransport {
+    endpoint: String,
+    retry: RetryPolicy,
+    channel: tokio::sync::OnceCell<Channel>,
+}
+
+impl GrpcTransport {
+    /// Transport to `endpoint`, connected on the first request
+    pub fn new(endpoint: &str, retry: RetryPolicy) -> Self {
+        GrpcTransport {
+            endpoint: endpoint.to_string(),
+            retry,
+            channel: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    fn transport_err(&self, reason: String) -> RpcError {
+        RpcError::Transport { endpoint: self.endpoint.clone(), reason }
+    }
+
+    /// Transport errors for a node that can't be reached, the others as
+    /// errors the node answered with
+    fn status_err(&self, status: tonic::Status) -> RpcError {
+        match status.code() {
+            tonic::Code::Unavailable | tonic::Code::DeadlineExceeded => {
+                self.transport_err(status.to_string())
+            }
+            code => RpcError::Rpc {
+                code: code as i64,
+                message: status.message().to_string(),
+            },
+        }
+    }
+
+    async fn client(&self) -> Result<NodeClient<Channel>, RpcError> {
+        let channel = self
+            .channel
+            .get_or_try_init(|| async {
+                Endpoint::from_shared(self.endpoint.clone())
+                    .map_err(|err_code| {
+                        self.transport_err(err_code.to_string())
+                    })?
+                    .connect()
+                    .await
+                    .map_err(|err_code| {
+                        self.transport_err(err_code.to_string())
+                    })
+            })
+            .await?;
+
+        Ok(NodeClient::new(channel.clone()))
+    }
+
+    /// Send the gRPC request `method` with `send`, retrying transport
+    /// failures with the retry policy
+    async fn call<T, F, Fut>(
+        &self,
+        method: &str,
+        send: F,
+    ) -> Result<T, RpcError>
+    where
+        F: Fn(NodeClient<Channel>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<T>, tonic::Status>>,
+    {
+        let send = &send;
+
+        rpc::with_retries(
+            method,
+            self.retry,
+            RpcError::is_retryable,
+            || async {
+                let client = self.client().await?;
+                send(client)
+                    .await
+                    .map(tonic::Response::into_inner)
+                    .map_err(|status| self.status_err(status))
+            },
+        )
+        .await
+    }
+
+    /// JSON form of a gRPC `response`, read like the JSON-RPC result
+    fn response_json<T: Serialize>(response: &T) -> Result<Value, RpcError> {
+        serde_json::to_value(response)
+            .map_err(|err_code| RpcError::InvalidResponse(err_code.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl L1xTransport for GrpcTransport {
+    async fn submit(
+        &self,
+        request: SubmitTransactionRequest,
+    ) -> Result<SubmitTransactionResponse, RpcError> {
+        self.call("SubmitTransaction", |mut client| {
+            let request = request.clone();
+            async move { client.submit_transaction(request).await }
+        })
+        .await
+    }
+
+    async fn get_events(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Vec<Vec<u8>>, RpcError> {
+        let response = self
+            .call("GetEvents", |mut client| {
+                let request = GetEventsRequest {
+                    tx_hash: tx_hash.to_string(),
+                    timestamp: 0,
+                };
+                async move { client.get_events(request).await }
+            })
+            .await?;
+
+        Ok(response.events_data)
+    }
+
+    async fn read_only_call(
+        &self,
+        call: SmartContractReadOnlyCallRequest,
+    ) -> Result<Option<Vec<u8>>, RpcError> {
+        let response = self
+            .call("SmartContractReadOnlyCall", |mut client| {
+                let call = call.clone();
+                async move { client.smart_contract_read_only_call(call).await }
+            })
+            .await?;
+
+        let response = Self::response_json(&response)?;
+        parse_response("read-only result", response["result"].clone()).map(Some)
+    }
+
+    async fn get_nonce(&self, address: &str) -> Result<Option<u128>, RpcError> {
+        let response = self
+            .call("GetAccountState", |mut client| {
+                let request =
+                    GetAccountStateRequest { address: address.to_string() };
+                async move { client.get_account_state(request).await }
+            })
+            .await?;
+
+        account_nonce(&Self::response_json(&response)?)
+    }
+
+    async fn get_receipt(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<TxReceipt>, RpcError> {
+        let response = self
+            .call("GetTransactionReceipt", |mut client| {
+                let request =
+                    GetTransactionReceiptRequest { hash: tx_hash.to_string() };
+                async move { client.get_transaction_receipt(request).await }
+            })
+            .await;
+
+        match response {
+            Ok(response) => {
+                Ok(TxReceipt::from_result(&Self::response_json(&response)?))
+            }
+            Err(RpcError::Rpc { code, .. })
+                if code == tonic::Code::NotFound as i64 =>
+            {
+                Ok(None)
+            }
+            Err(err_code) => Err(err_code),
+        }
+    }
+}
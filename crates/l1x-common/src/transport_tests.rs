@@ -0,0 +1,330 @@
+use crate::rpc::{
+    RequestIds, RetryPolicy, RpcError, TxStatus, DEFAULT_RECEIPT_METHOD,
+};
+use crate::transport::*;
+use crate::types;
+
+use l1x_rpc::rpc_model::{
+    SmartContractReadOnlyCallRequest, SubmitTransactionRequest,
+};
+use serde_json::{json, Value};
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// JSON-RPC response `body`, echoing the id of the request
+struct RpcResponse(Value);
+
+impl Respond for RpcResponse {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let mut body = self.0.clone();
+        body["id"] = serde_json::from_slice::<Value>(&request.body)
+            .map(|request| request["id"].clone())
+            .unwrap_or_default();
+        ResponseTemplate::new(200).set_body_json(body)
+    }
+}
+
+fn rpc_result(result: Value) -> RpcResponse {
+    RpcResponse(json!({ "jsonrpc": "2.0", "result": result }))
+}
+
+async fn mock_rpc(server: &MockServer, rpc_method: &str, result: Value) {
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({ "method": rpc_method })))
+        .respond_with(rpc_result(result))
+        .mount(server)
+        .await;
+}
+
+fn json_transport(server: &MockServer) -> JsonRpcTransport {
+    JsonRpcTransport::new(
+        &server.uri(),
+        RequestIds::starting_at(7),
+        RetryPolicy::NONE,
+        DEFAULT_RECEIPT_METHOD,
+    )
+}
+
+fn submit_request() -> SubmitTransactionRequest {
+    SubmitTransactionRequest {
+        nonce: "3".to_string(),
+        fee_limit: "100".to_string(),
+        signature: vec![1, 2],
+        verifying_key: vec![3, 4],
+        transaction_type: None,
+    }
+}
+
+fn read_only_request() -> SmartContractReadOnlyCallRequest {
+    types::SmartContractReadOnlyFunctionCall {
+        contract_instance_address: types::U8s::Hex("11".repeat(20)),
+        function: types::U8s::Text(Default::default()),
+        arguments: types::U8s::Hex("00".to_string()),
+    }
+    .try_into()
+    .unwrap()
+}
+
+#[test]
+fn test_protocol_of_network_config() {
+    assert_eq!(
+        serde_yaml::from_str::<L1XProtocol>("grpc").unwrap(),
+        L1XProtocol::Grpc
+    );
+    assert_eq!(
+        serde_yaml::from_str::<L1XProtocol>("json").unwrap(),
+        L1XProtocol::Json
+    );
+    assert!(serde_yaml::from_str::<L1XProtocol>("websocket").is_err());
+    assert_eq!(L1XProtocol::default(), L1XProtocol::Json);
+}
+
+#[tokio::test]
+async fn test_json_submit_posts_the_request_with_req_id() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "l1x_submitTransaction",
+            "params": { "request": { "nonce": "3", "fee_limit": "100" } },
+            "id": 7,
+        })))
+        .respond_with(rpc_result(
+            json!({ "hash": "deadbeef", "contract_address": null }),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Act
+    let response = json_transport(&server).submit(submit_request()).await;
+
+    // Assert
+    assert_eq!(response.unwrap().hash, "deadbeef");
+}
+
+#[tokio::test]
+async fn test_json_submit_without_result_is_an_invalid_response() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(&server, "l1x_submitTransaction", Value::Null).await;
+
+    // Act
+    let response = json_transport(&server).submit(submit_request()).await;
+
+    // Assert
+    assert!(matches!(response, Err(RpcError::InvalidResponse(_))));
+}
+
+#[tokio::test]
+async fn test_json_events_read_only_call_and_nonce() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(&server, "l1x_getEvents", json!({ "events_data": [[1, 2]] }))
+        .await;
+    mock_rpc(
+        &server,
+        "l1x_smartContractReadOnlyCall",
+        json!({ "result": [104, 105] }),
+    )
+    .await;
+    mock_rpc(
+        &server,
+        "l1x_getAccountState",
+        json!({ "account_state": { "balance": "5", "nonce": "7" } }),
+    )
+    .await;
+    let transport = json_transport(&server);
+
+    // Act
+    let events = transport.get_events("deadbeef").await;
+    let read_only = transport.read_only_call(read_only_request()).await;
+    let nonce = transport.get_nonce(&"22".repeat(20)).await;
+
+    // Assert
+    assert_eq!(events.unwrap(), vec![vec![1, 2]]);
+    assert_eq!(read_only.unwrap(), Some(b"hi".to_vec()));
+    assert_eq!(nonce.unwrap(), Some(7));
+}
+
+#[tokio::test]
+async fn test_json_nonce_of_an_unknown_account_is_none() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(&server, "l1x_getAccountState", json!({})).await;
+
+    // Act
+    let nonce = json_transport(&server).get_nonce(&"22".repeat(20)).await;
+
+    // Assert
+    assert_eq!(nonce.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_json_receipt_uses_the_configured_method() {
+    // Arrange
+    let server = MockServer::start().await;
+    mock_rpc(
+        &server,
+        "l1x_getTxStatus",
+        json!({ "block_number": 9, "status": "failed", "error": "reverted" }),
+    )
+    .await;
+    let transport = JsonRpcTransport::new(
+        &server.uri(),
+        RequestIds::starting_at(1),
+        RetryPolicy::NONE,
+        "l1x_getTxStatus",
+    );
+
+    // Act
+    let receipt = transport.get_receipt("deadbeef").await.unwrap().unwrap();
+
+    // Assert
+    assert_eq!(receipt.block_number, Some(9));
+    assert_eq!(receipt.status, TxStatus::Failed("reverted".to_string()));
+}
+
+#[tokio::test]
+async fn test_json_error_response_is_an_rpc_error() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(RpcResponse(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32000, "message": "nonce too low" },
+        })))
+        .mount(&server)
+        .await;
+
+    // Act
+    let response = json_transport(&server).submit(submit_request()).await;
+
+    // Assert
+    assert!(matches!(
+        response,
+        Err(RpcError::Rpc { code: -32000, message }) if message == "nonce too low"
+    ));
+}
+
+#[tokio::test]
+async fn test_json_requests_increment_the_req_id() {
+    // Arrange
+    let server = MockServer::start().await;
+    for id in [7, 8, 9] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "id": id })))
+            .respond_with(rpc_result(json!({ "events_data": [] })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    let transport = json_transport(&server);
+
+    // Act
+    let mut events = Vec::new();
+    for tx_hash in ["aa", "bb", "cc"] {
+        events.push(transport.get_events(tx_hash).await);
+    }
+
+    // Assert
+    assert!(events.into_iter().all(|events| events.unwrap().is_empty()));
+}
+
+#[tokio::test]
+async fn test_json_transports_sharing_req_ids_never_reuse_an_id() {
+    // Arrange
+    let server = MockServer::start().await;
+    for id in [3, 4] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "id": id })))
+            .respond_with(rpc_result(json!({ "events_data": [] })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    let req_ids = RequestIds::starting_at(3);
+    let transport_for_ids = || {
+        JsonRpcTransport::new(
+            &server.uri(),
+            req_ids.clone(),
+            RetryPolicy::NONE,
+            DEFAULT_RECEIPT_METHOD,
+        )
+    };
+
+    // Act
+    let first = transport_for_ids().get_events("aa").await;
+    let second = transport_for_ids().get_events("bb").await;
+
+    // Assert
+    assert!(first.is_ok() && second.is_ok());
+}
+
+#[tokio::test]
+async fn test_json_response_with_another_id_is_an_id_mismatch() {
+    // Arrange
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "result": { "events_data": [] },
+            "id": 99
+        })))
+        .mount(&server)
+        .await;
+
+    // Act
+    let events = json_transport(&server).get_events("deadbeef").await;
+
+    // Assert
+    assert!(matches!(
+        events,
+        Err(RpcError::IdMismatch { method, sent: 7, received })
+            if method == "l1x_getEvents" && received == "99"
+    ));
+}
+
+#[tokio::test]
+async fn test_json_error_without_id_is_an_rpc_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32700, "message": "Parse error" },
+            "id": null
+        })))
+        .mount(&server)
+        .await;
+
+    let events = json_transport(&server).get_events("deadbeef").await;
+
+    assert!(matches!(events, Err(RpcError::Rpc { code: -32700, .. })));
+}
+
+#[tokio::test]
+async fn test_grpc_unreachable_node_is_a_retryable_transport_error() {
+    // Arrange
+    let transport = transport_for(
+        L1XProtocol::Grpc,
+        "http://127.0.0.1:1",
+        RequestIds::starting_at(1),
+        RetryPolicy::new(1, Duration::from_millis(1)),
+        DEFAULT_RECEIPT_METHOD,
+    );
+
+    // Act
+    let response = transport.submit(submit_request()).await;
+    let nonce = transport.get_nonce(&"22".repeat(20)).await;
+
+    // Assert
+    for err_code in [response.unwrap_err(), nonce.unwrap_err()] {
+        assert!(
+            matches!(&err_code, RpcError::Transport { endpoint, .. } if endpoint == "http://127.0.0.1:1"),
+            "{}",
+            err_code
+        );
+        assert!(err_code.is_retryable());
+    }
+}
@@ -0,0 +1,648 @@
+use crate::types::{Transaction, U8s};
+use crate::*;
+
+use std::fs;
+use std::path::Path;
+
+const KEY_ONE: &str =
+    "0000000000000000000000000000000000000000000000000000000000000001";
+
+fn function_call(address: &str) -> Transaction {
+    Transaction::SmartContractFunctionCall {
+        contract_instance_address: U8s::Hex(address.to_string()),
+        function: U8s::Text("get".to_string()),
+        arguments: U8s::Text("{}".to_string()),
+    }
+}
+
+#[test]
+fn test_get_submit_txn_req_signs_valid_transaction() {
+    let request = get_submit_txn_req(
+        function_call(&"1".repeat(40)),
+        KEY_ONE,
+        100,
+        7,
+        SigningScheme::Canonical,
+    )
+    .unwrap();
+
+    assert_eq!(request.nonce, "7");
+    assert_eq!(request.fee_limit, "100");
+    assert_eq!(request.signature.len(), 64);
+}
+
+#[test]
+fn test_get_submit_txn_req_rejects_truncated_and_invalid_keys() {
+    let truncated = get_submit_txn_req(
+        function_call(&"1".repeat(40)),
+        &KEY_ONE[..62],
+        1,
+        1,
+        SigningScheme::Canonical,
+    )
+    .unwrap_err();
+    let not_hex = get_submit_txn_req(
+        function_call(&"1".repeat(40)),
+        "zz",
+        1,
+        1,
+        SigningScheme::Canonical,
+    )
+    .unwrap_err();
+    let zero_key = get_submit_txn_req(
+        function_call(&"1".repeat(40)),
+        &"0".repeat(64),
+        1,
+        1,
+        SigningScheme::Canonical,
+    )
+    .unwrap_err();
+
+    assert!(matches!(truncated, TxnBuildError::InvalidPrivateKey(_)));
+    assert!(matches!(not_hex, TxnBuildError::InvalidPrivateKey(_)));
+    assert!(matches!(zero_key, TxnBuildError::InvalidPrivateKey(_)));
+}
+
+#[test]
+fn test_parse_secret_key_accepts_prefixed_and_padded_keys() {
+    let expected = parse_secret_key(KEY_ONE).unwrap();
+
+    for private_key in [
+        format!("0x{}", KEY_ONE),
+        format!("0X{}", KEY_ONE),
+        format!("  {}\n", KEY_ONE),
+        format!(" 0x{}\t", KEY_ONE.to_uppercase()),
+    ] {
+        assert_eq!(parse_secret_key(&private_key).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_parse_secret_key_errors_name_the_problem_not_the_key() {
+    let secret = "ab".repeat(31);
+
+    let short = parse_secret_key(&format!("0x{}", secret)).unwrap_err();
+    let long = parse_secret_key(&format!("{}00", KEY_ONE)).unwrap_err();
+    let not_hex = parse_secret_key(&format!("{}zz", secret)).unwrap_err();
+    let zero_key = parse_secret_key(&"0".repeat(64)).unwrap_err();
+
+    assert_eq!(
+        short.to_string(),
+        "Invalid private key: expected 32 bytes (64 hex chars), got 62 hex chars"
+    );
+    assert!(long.to_string().contains("got 66 hex chars"), "{}", long);
+    assert_eq!(
+        not_hex.to_string(),
+        "Invalid private key: character 62 is not hex"
+    );
+    assert!(zero_key.to_string().contains("non-zero"), "{}", zero_key);
+    for err in [short, long, not_hex] {
+        assert!(!err.to_string().contains(&secret), "{}", err);
+    }
+}
+
+#[test]
+fn test_get_submit_txn_req_rejects_unconvertible_transaction() {
+    let err = get_submit_txn_req(
+        function_call("not hex"),
+        KEY_ONE,
+        1,
+        1,
+        SigningScheme::Canonical,
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, TxnBuildError::TypeConversion(_)), "{}", err);
+}
+
+#[test]
+fn test_load_submit_txn_req_reports_payload_errors() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let malformed = dir.path().join("malformed.json");
+    fs::write(&malformed, r#"{"smart_contract_function_call": "#).unwrap();
+    let bad_address = dir.path().join("bad_address.json");
+    fs::write(
+        &bad_address,
+        serde_json::to_string(&function_call("abc")).unwrap(),
+    )
+    .unwrap();
+    let missing = dir.path().join("missing.json");
+
+    // Act
+    let load = |path: &std::path::Path| {
+        load_submit_txn_req(path.to_str().unwrap(), None, KEY_ONE, 1, 1)
+            .unwrap_err()
+    };
+
+    // Assert
+    match load(&malformed) {
+        TxnBuildError::PayloadDeserialize { path, format, .. } => {
+            assert_eq!(path, malformed.to_str().unwrap());
+            assert_eq!(format, PayloadFormat::Json);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert!(matches!(load(&bad_address), TxnBuildError::TypeConversion(_)));
+    match load(&missing) {
+        TxnBuildError::Io { path, source } => {
+            assert_eq!(path, missing.to_str().unwrap());
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+/// Transfer of 1000 to the address 0x2222..22
+fn native_transfer(
+) -> l1x_rpc::rpc_model::submit_transaction_request::TransactionType {
+    Transaction::NativeTokenTransfer(U8s::Hex("22".repeat(20)), 1000)
+        .try_into()
+        .unwrap()
+}
+
+fn secret_key_one() -> SecretKey {
+    SecretKey::from_slice(&hex::decode(KEY_ONE).unwrap()).unwrap()
+}
+
+#[test]
+fn test_legacy_native_transfer_signature_vector() {
+    // sha256 of {"nonce":7,"transaction_type":{"NativeTokenTransfer":
+    // [[34,..,34],"1000"]},"fee_limit":100} signed with RFC 6979 nonces
+    let expected = "fdb4b939c50b0e35cac1a07399b9d99e3632c2cb0d0a6e01dfe95bd6eac5084c\
+                    0cc21d6e666d8f0367c18638fb47dd589279fb6e2b054d35546218f803641d51";
+
+    let signature = sign_txn(
+        &secret_key_one(),
+        &native_transfer(),
+        100,
+        7,
+        SigningScheme::LegacyNativeTransfer,
+    )
+    .unwrap();
+
+    assert_eq!(hex::encode(signature), expected);
+}
+
+#[test]
+fn test_canonical_signature_vectors() {
+    // sha256 of {"nonce":7,"transaction_type":{"NativeTokenTransfer":
+    // {"address":[34,..,34],"amount":"1000"}},"fee_limit":100} and of
+    // {"nonce":7,"transaction_type":{"SmartContractFunctionCall":
+    // {"contract_address":[17,..,17],"function_name":[103,101,116],
+    // "arguments":[123,125]}},"fee_limit":100} signed with RFC 6979 nonces
+    let function_call: l1x_rpc::rpc_model::submit_transaction_request::TransactionType =
+        function_call(&"1".repeat(40)).try_into().unwrap();
+    let verifying_key =
+        secret_key_one().public_key(&Secp256k1::new()).serialize();
+
+    for (txn_type, expected) in [
+        (
+            native_transfer(),
+            "491cae3e3861fe6457d1b2c40f488c70e45c2abbfbf7899e9628c10caa188e22\
+             444e40dfd01dfdbcbd08f9485546248d335f1c306dfc877bfc6738ebb9e5b39d",
+        ),
+        (
+            function_call,
+            "225c4703bdf4cac983d1e4f5ce4a6d8613fa70d905cb9cf468ca281801d933fb\
+             2b4686f3f4d8ec4ae9d53959395b1bf9e70610f758ee345ac1d558c8da446238",
+        ),
+    ] {
+        let signature = sign_txn(
+            &secret_key_one(),
+            &txn_type,
+            100,
+            7,
+            SigningScheme::Canonical,
+        )
+        .unwrap();
+
+        assert_eq!(hex::encode(&signature), expected);
+        assert!(verify_txn_signature(
+            &verifying_key,
+            &signature,
+            &txn_type,
+            100,
+            7,
+            SigningScheme::Canonical,
+        )
+        .unwrap());
+    }
+}
+
+#[test]
+fn test_verify_submit_txn_req_returns_signer_under_every_scheme() {
+    let transfer =
+        Transaction::NativeTokenTransfer(U8s::Hex("22".repeat(20)), 1000);
+
+    for (txn, scheme) in [
+        (transfer.clone(), SigningScheme::Canonical),
+        (transfer, SigningScheme::LegacyNativeTransfer),
+        (function_call(&"1".repeat(40)), SigningScheme::Canonical),
+    ] {
+        let request = get_submit_txn_req(txn, KEY_ONE, 100, 7, scheme).unwrap();
+
+        let signer = verify_submit_txn_req(&request).unwrap();
+
+        assert_eq!(
+            hex::encode(signer),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf",
+            "{:?}",
+            scheme
+        );
+    }
+}
+
+#[test]
+fn test_verify_submit_txn_req_rejects_tampered_requests() {
+    let request = get_submit_txn_req(
+        function_call(&"1".repeat(40)),
+        KEY_ONE,
+        100,
+        7,
+        SigningScheme::Canonical,
+    )
+    .unwrap();
+    let mut corrupted = request.clone();
+    corrupted.signature[10] ^= 0xff;
+    let mut renonced = request.clone();
+    renonced.nonce = "8".to_string();
+    let mut unsigned = request;
+    unsigned.signature = vec![0; 12];
+
+    for tampered in [corrupted, renonced, unsigned] {
+        let err = verify_submit_txn_req(&tampered).unwrap_err();
+
+        assert!(matches!(err, TxnBuildError::InvalidSignature(_)), "{}", err);
+    }
+}
+
+#[test]
+fn test_parse_native_amount() {
+    assert_eq!(parse_native_amount("0").unwrap(), 0);
+    assert_eq!(parse_native_amount("1000").unwrap(), 1000);
+    assert_eq!(parse_native_amount(&u128::MAX.to_string()).unwrap(), u128::MAX);
+
+    for amount in [
+        "",
+        "1_000",
+        "1e18",
+        "+5",
+        "-5",
+        " 5",
+        "007",
+        "340282366920938463463374607431768211456",
+    ] {
+        assert!(
+            matches!(
+                parse_native_amount(amount),
+                Err(TxnBuildError::InvalidAmount(_))
+            ),
+            "{:?}",
+            amount
+        );
+    }
+}
+
+#[test]
+fn test_legacy_native_transfer_rejects_invalid_amount() {
+    let txn_type =
+        l1x_rpc::rpc_model::submit_transaction_request::TransactionType::NativeTokenTransfer(
+            l1x_rpc::rpc_model::NativeTokenTransfer {
+                address: vec![0x22; 20],
+                amount: "1e18".to_string(),
+            },
+        );
+
+    let result = sign_txn(
+        &secret_key_one(),
+        &txn_type,
+        100,
+        7,
+        SigningScheme::LegacyNativeTransfer,
+    );
+
+    assert!(matches!(result, Err(TxnBuildError::InvalidAmount(_))));
+}
+
+#[test]
+fn test_native_transfer_payload_serde_accepts_string_and_number_amounts() {
+    let address = format!("[{}]", vec!["34"; 20].join(","));
+    let recorded = format!(
+        r#"{{"nonce":7,"transaction_type":{{"NativeTokenTransfer":[{},"1000"]}},"fee_limit":100}}"#,
+        address
+    );
+    let numeric = recorded.replace(r#""1000""#, "1000");
+    let expected = TransactionTypeNativeTX::NativeTokenTransfer([34; 20], 1000);
+
+    let from_recorded: NativeTokenTransferPayload =
+        serde_json::from_str(&recorded).unwrap();
+    let from_numeric: NativeTokenTransferPayload =
+        serde_json::from_str(&numeric).unwrap();
+    let invalid = serde_json::from_str::<NativeTokenTransferPayload>(
+        &recorded.replace("1000", "1_000"),
+    );
+
+    assert_eq!(from_recorded.transaction_type, expected);
+    assert_eq!(from_numeric.transaction_type, expected);
+    // Amounts are written back as the strings the legacy scheme signs
+    assert_eq!(serde_json::to_string(&from_recorded).unwrap(), recorded);
+    assert_eq!(serde_json::to_string(&from_numeric).unwrap(), recorded);
+    assert!(invalid.unwrap_err().to_string().contains("1_000"));
+}
+
+#[test]
+fn test_signing_schemes_differ_only_for_native_transfers() {
+    let function_call: l1x_rpc::rpc_model::submit_transaction_request::TransactionType =
+        function_call(&"1".repeat(40)).try_into().unwrap();
+    let sign = |txn_type, scheme| {
+        sign_txn(&secret_key_one(), txn_type, 100, 7, scheme).unwrap()
+    };
+
+    assert_ne!(
+        sign(&native_transfer(), SigningScheme::Canonical),
+        sign(&native_transfer(), SigningScheme::LegacyNativeTransfer)
+    );
+    assert_eq!(
+        sign(&function_call, SigningScheme::Canonical),
+        sign(&function_call, SigningScheme::LegacyNativeTransfer)
+    );
+}
+
+/// Path of the payload fixture `file_name`
+fn fixture(file_name: &str) -> String {
+    format!("{}/fixtures/payloads/{}", env!("CARGO_MANIFEST_DIR"), file_name)
+}
+
+#[test]
+fn test_payload_fixtures_sign_identically_in_every_format() {
+    for payload in [
+        "function_call",
+        "deployment",
+        "stake",
+        "unstake",
+        "create_staking_pool",
+    ] {
+        let load = |extension: &str| {
+            load_submit_txn_req(
+                &fixture(&format!("{}.{}", payload, extension)),
+                None,
+                KEY_ONE,
+                100,
+                7,
+            )
+            .unwrap()
+        };
+
+        let json = load("json");
+
+        assert_eq!(load("yaml"), json, "{}", payload);
+        assert_eq!(load("toml"), json, "{}", payload);
+    }
+}
+
+#[test]
+fn test_file_and_base64_payloads_sign_like_their_inline_form() {
+    for (payload, inline) in [
+        ("deployment_file", "deployment"),
+        ("function_call_base64", "function_call"),
+    ] {
+        let load = |payload: &str, extension: &str| {
+            load_submit_txn_req(
+                &fixture(&format!("{}.{}", payload, extension)),
+                None,
+                KEY_ONE,
+                100,
+                7,
+            )
+            .unwrap()
+        };
+
+        let expected = load(inline, "json");
+
+        for extension in ["json", "yaml", "toml"] {
+            assert_eq!(load(payload, extension), expected, "{}", payload);
+        }
+    }
+}
+
+#[test]
+fn test_payload_file_values_are_relative_to_the_payload() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("artifacts")).unwrap();
+    fs::write(dir.path().join("artifacts/code.o"), [1u8, 2, 3]).unwrap();
+    let path = dir.path().join("deploy.json");
+    fs::write(
+        &path,
+        r#"{"smart_contract_deployment": ["PUBLIC", "L1XVM", {"file": "artifacts/code.o"}, 0, {"text": "salt"}]}"#,
+    )
+    .unwrap();
+
+    let txn = load_transaction_payload(path.to_str().unwrap(), None).unwrap();
+
+    match txn {
+        Transaction::SmartContractDeployment(_, _, code, _, _) => {
+            assert_eq!(code, U8s::File(dir.path().join("artifacts/code.o")));
+            assert_eq!(code.into_bytes("contract_code").unwrap(), [1, 2, 3]);
+        }
+        other => panic!("expected a deployment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_base64_and_missing_file_name_the_field() {
+    let sign = |txn: Transaction| {
+        get_submit_txn_req(txn, KEY_ONE, 1, 1, SigningScheme::Canonical)
+            .unwrap_err()
+    };
+
+    let invalid_base64 = sign(Transaction::SmartContractFunctionCall {
+        contract_instance_address: U8s::Hex("1".repeat(40)),
+        function: U8s::Text("get".to_string()),
+        arguments: U8s::Base64("not base64!".to_string()),
+    });
+    let missing_file = sign(Transaction::SmartContractDeployment(
+        types::AccessType::PUBLIC,
+        types::ContractType::L1XVM,
+        U8s::File("/nonexistent/code.o".into()),
+        0,
+        U8s::Text("salt".to_string()),
+    ));
+
+    match invalid_base64 {
+        TxnBuildError::PayloadValue(types::U8sError::InvalidBase64 {
+            field,
+            ..
+        }) => assert_eq!(field, "arguments"),
+        other => panic!("expected invalid base64, got {:?}", other),
+    }
+    match missing_file {
+        TxnBuildError::PayloadValue(types::U8sError::File {
+            field,
+            path,
+            source,
+        }) => {
+            assert_eq!(field, "contract_code");
+            assert_eq!(path, Path::new("/nonexistent/code.o"));
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        other => panic!("expected a missing file, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_staking_payloads_convert_to_rpc_transactions() {
+    use l1x_rpc::rpc_model::submit_transaction_request::TransactionType;
+
+    let convert = |payload: &str| {
+        let path = fixture(&format!("{}.json", payload));
+        TransactionType::try_from(
+            load_transaction_payload(&path, None).unwrap(),
+        )
+        .unwrap()
+    };
+    let pool_address =
+        hex::decode("b1c2d3e4f5061728394a5b6c7d8e9f0123456789").unwrap();
+    let contract_address =
+        hex::decode("a1b2c3d4e5f60718293a4b5c6d7e8f9012345678").unwrap();
+
+    match convert("stake") {
+        TransactionType::Stake(stake) => {
+            assert_eq!(stake.pool_address, pool_address);
+            assert_eq!(stake.amount, "5000");
+        }
+        other => panic!("expected a stake, got {:?}", other),
+    }
+    match convert("unstake") {
+        TransactionType::Unstake(unstake) => {
+            assert_eq!(unstake.pool_address, pool_address);
+            assert_eq!(unstake.amount, "2000");
+        }
+        other => panic!("expected an unstake, got {:?}", other),
+    }
+    match convert("create_staking_pool") {
+        TransactionType::CreateStakingPool(pool) => {
+            assert_eq!(pool.contract_instance_address, Some(contract_address));
+            assert_eq!(pool.min_stake.as_deref(), Some("100"));
+            assert_eq!(pool.max_stake.as_deref(), Some("100000"));
+            assert_eq!(pool.min_pool_balance, None);
+            assert_eq!(pool.max_pool_balance, None);
+            assert_eq!(pool.staking_period.as_deref(), Some("50"));
+        }
+        other => panic!("expected a staking pool, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_pool_address_vector() {
+    // keccak256(account || cluster || nonce as 16 big endian bytes)[12..]
+    let account: [u8; 20] =
+        hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let cluster: [u8; 20] =
+        hex::decode("2b5ad5c4795c026514f8317c7a215e218dccd6cf")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+    assert_eq!(
+        hex::encode(pool_address(&account, &cluster, 5)),
+        "c1c9b088c0f9f0ae2eab88a4115f8bd8b281fa16"
+    );
+}
+
+#[test]
+fn test_explicit_payload_format_overrides_extension() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let payload = dir.path().join("payload.txt");
+    fs::copy(fixture("function_call.yaml"), &payload).unwrap();
+    let path = payload.to_str().unwrap();
+
+    // Act
+    let as_yaml =
+        load_submit_txn_req(path, Some(PayloadFormat::Yaml), KEY_ONE, 100, 7);
+    let as_toml =
+        load_submit_txn_req(path, Some(PayloadFormat::Toml), KEY_ONE, 100, 7);
+
+    // Assert
+    assert_eq!(
+        as_yaml.unwrap(),
+        load_submit_txn_req(
+            &fixture("function_call.json"),
+            None,
+            KEY_ONE,
+            100,
+            7
+        )
+        .unwrap()
+    );
+    let message = as_toml.unwrap_err().to_string();
+    assert!(
+        message.starts_with(&format!(
+            "Failed to deserialize TOML transaction payload {}",
+            path
+        )),
+        "{}",
+        message
+    );
+    assert!(message.contains("line 2"), "{}", message);
+}
+
+#[test]
+fn test_payload_format_of_path() {
+    let format_of = |path: &str| PayloadFormat::of_path(Path::new(path));
+
+    assert_eq!(format_of("deploy.json"), PayloadFormat::Json);
+    assert_eq!(format_of("deploy.yaml"), PayloadFormat::Yaml);
+    assert_eq!(format_of("deploy.yml"), PayloadFormat::Yaml);
+    assert_eq!(format_of("deploy.toml"), PayloadFormat::Toml);
+    assert_eq!(format_of("deploy"), PayloadFormat::Json);
+}
+
+#[test]
+fn test_read_payload_reports_missing_file() {
+    let err =
+        read_payload(PayloadSource::Path("/nonexistent/payload.json".into()))
+            .unwrap_err();
+
+    assert!(matches!(err, TxnBuildError::Io { .. }));
+    assert!(err
+        .to_string()
+        .starts_with("Unable to read /nonexistent/payload.json"));
+}
+
+#[test]
+fn test_read_payload_reports_unreadable_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let err = read_payload(PayloadSource::Path(dir.path().to_path_buf()))
+        .unwrap_err();
+
+    match err {
+        TxnBuildError::Io { path, .. } => {
+            assert_eq!(path, dir.path().display().to_string())
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_payload_from_stdin() {
+    let payload = r#"{"smart_contract_function_call": {}}"#;
+
+    let content = read_payload_from(
+        PayloadSource::from_arg("-"),
+        std::io::Cursor::new(payload),
+    )
+    .unwrap();
+
+    assert_eq!(content, payload);
+    assert_eq!(PayloadSource::from_arg("-").to_string(), "<stdin>");
+    assert_eq!(
+        PayloadSource::from_arg("payload.json"),
+        PayloadSource::Path("payload.json".into())
+    );
+}
@@ -0,0 +1,246 @@
+use crate::primitives::{Balance, BlockNumber, Nonce};
+use crate::types::{self, AccessType, AddressStr, ContractType, U8s};
+use crate::{signed_submit_txn_req, SigningScheme, TxnBuildError};
+
+use l1x_rpc::rpc_model::SubmitTransactionRequest;
+use secp256k1::SecretKey;
+
+/// Fee limit of a [`TransactionBuilder`] until [`TransactionBuilder::fee_limit`]
+/// is set, the default `--fee_limit` of the CLI
+pub const DEFAULT_FEE_LIMIT: Balance = 100;
+
+/// Build a transaction and the signed request submitting it, addresses and
+/// hex arguments are validated as each part is set
+///
+/// ```ignore
+/// let request = TransactionBuilder::new()
+///     .call(&contract_address, "", U8s::Hex(function_payload))?
+///     .fee_limit(100)
+///     .nonce(7)
+///     .sign(&secret_key)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    txn: Option<types::Transaction>,
+    fee_limit: Balance,
+    nonce: Nonce,
+    scheme: SigningScheme,
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> Self {
+        TransactionBuilder {
+            txn: None,
+            fee_limit: DEFAULT_FEE_LIMIT,
+            nonce: 0,
+            scheme: SigningScheme::default(),
+        }
+    }
+}
+
+impl TransactionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deploy the contract `code` carrying no value and an empty salt, see
+    /// [`TransactionBuilder::salt`]
+    pub fn deploy_contract(
+        self,
+        access: AccessType,
+        contract_type: ContractType,
+        code: U8s,
+    ) -> Result<Self, TxnBuildError> {
+        let code = checked_bytes("contract code", code)?;
+
+        Ok(self.transaction(types::Transaction::SmartContractDeployment(
+            access,
+            contract_type,
+            code,
+            0,
+            U8s::Text(String::new()),
+        )))
+    }
+
+    /// Salt of the deployment set by [`TransactionBuilder::deploy_contract`]
+    pub fn salt(mut self, salt: &str) -> Result<Self, TxnBuildError> {
+        match &mut self.txn {
+            Some(types::Transaction::SmartContractDeployment(
+                _,
+                _,
+                _,
+                _,
+                deploy_salt,
+            )) => {
+                *deploy_salt = U8s::Text(salt.to_string());
+                Ok(self)
+            }
+            _ => Err(TxnBuildError::Incomplete(
+                "a salt needs a contract deployment".to_string(),
+            )),
+        }
+    }
+
+    /// Initialize a new instance of the contract deployed at `address`
+    pub fn init_contract(
+        self,
+        address: &str,
+        arguments: U8s,
+    ) -> Result<Self, TxnBuildError> {
+        let address = checked_address(address)?;
+        let arguments = checked_bytes("init arguments", arguments)?;
+
+        Ok(self.transaction(types::Transaction::SmartContractInit(
+            address, arguments,
+        )))
+    }
+
+    /// Call `function` of the contract instance at `address`
+    pub fn call(
+        self,
+        address: &str,
+        function: &str,
+        arguments: U8s,
+    ) -> Result<Self, TxnBuildError> {
+        let contract_instance_address = checked_address(address)?;
+        let arguments = checked_bytes("function arguments", arguments)?;
+
+        Ok(self.transaction(types::Transaction::SmartContractFunctionCall {
+            contract_instance_address,
+            function: U8s::Text(function.to_string()),
+            arguments,
+        }))
+    }
+
+    /// Transfer `amount` native tokens to the account `to`
+    pub fn native_transfer(
+        self,
+        to: &str,
+        amount: Balance,
+    ) -> Result<Self, TxnBuildError> {
+        let to = checked_address(to)?;
+
+        Ok(self
+            .transaction(types::Transaction::NativeTokenTransfer(to, amount)))
+    }
+
+    /// Create a staking pool, of the contract instance at
+    /// `contract_instance_address` if any. Limits left `None` are up to the
+    /// node.
+    pub fn create_staking_pool(
+        self,
+        contract_instance_address: Option<&str>,
+        min_stake: Option<Balance>,
+        max_stake: Option<Balance>,
+        min_pool_balance: Option<Balance>,
+        max_pool_balance: Option<Balance>,
+        staking_period: Option<BlockNumber>,
+    ) -> Result<Self, TxnBuildError> {
+        let contract_instance_address = contract_instance_address
+            .map(|address| {
+                address.parse::<AddressStr>().map_err(|err_code| {
+                    TxnBuildError::InvalidAddress(err_code.to_string())
+                })
+            })
+            .transpose()?;
+
+        Ok(self.transaction(types::Transaction::CreateStakingPool {
+            contract_instance_address,
+            min_stake,
+            max_stake,
+            min_pool_balance,
+            max_pool_balance,
+            staking_period,
+        }))
+    }
+
+    /// Stake `amount` native tokens in the pool at `pool_address`
+    pub fn stake(
+        self,
+        pool_address: &str,
+        amount: Balance,
+    ) -> Result<Self, TxnBuildError> {
+        let pool_address = checked_address(pool_address)?;
+
+        Ok(self.transaction(types::Transaction::Stake { pool_address, amount }))
+    }
+
+    /// Withdraw `amount` staked native tokens from the pool at `pool_address`
+    pub fn unstake(
+        self,
+        pool_address: &str,
+        amount: Balance,
+    ) -> Result<Self, TxnBuildError> {
+        let pool_address = checked_address(pool_address)?;
+
+        Ok(self
+            .transaction(types::Transaction::UnStake { pool_address, amount }))
+    }
+
+    /// Build on an already constructed `txn`, as read from a payload file
+    pub fn transaction(mut self, txn: types::Transaction) -> Self {
+        self.txn = Some(txn);
+        self
+    }
+
+    pub fn fee_limit(mut self, fee_limit: Balance) -> Self {
+        self.fee_limit = fee_limit;
+        self
+    }
+
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn signing_scheme(mut self, scheme: SigningScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// The transaction, to be signed with the next nonce of the account
+    pub fn build(self) -> Result<types::Transaction, TxnBuildError> {
+        self.txn.ok_or_else(|| {
+            TxnBuildError::Incomplete("no transaction was set".to_string())
+        })
+    }
+
+    /// The request submitting the transaction signed by `secret_key`
+    pub fn sign(
+        self,
+        secret_key: &SecretKey,
+    ) -> Result<SubmitTransactionRequest, TxnBuildError> {
+        let (fee_limit, nonce, scheme) =
+            (self.fee_limit, self.nonce, self.scheme);
+
+        signed_submit_txn_req(
+            self.build()?,
+            secret_key,
+            fee_limit,
+            nonce,
+            scheme,
+        )
+    }
+}
+
+/// Hex of the 20 byte account or contract `address`, with or without `0x`
+fn checked_address(address: &str) -> Result<U8s, TxnBuildError> {
+    address
+        .parse::<AddressStr>()
+        .map(U8s::from)
+        .map_err(|err_code| TxnBuildError::InvalidAddress(err_code.to_string()))
+}
+
+/// `value` once its hex, if any, decodes
+fn checked_bytes(field: &str, value: U8s) -> Result<U8s, TxnBuildError> {
+    if let U8s::Hex(hex_value) = &value {
+        hex::decode(hex_value).map_err(|err_code| {
+            TxnBuildError::TypeConversion(format!(
+                "{} '{}' is not hex :: {}",
+                field, hex_value, err_code
+            ))
+        })?;
+    }
+
+    Ok(value)
+}
@@ -0,0 +1,201 @@
+use crate::txn_builder::*;
+use crate::types::{AccessType, ContractType, Transaction, U8s};
+use crate::{get_submit_txn_req, SigningScheme, TxnBuildError};
+
+use secp256k1::SecretKey;
+
+const KEY_ONE: &str =
+    "0000000000000000000000000000000000000000000000000000000000000001";
+
+fn secret_key_one() -> SecretKey {
+    SecretKey::from_slice(&hex::decode(KEY_ONE).unwrap()).unwrap()
+}
+
+/// Request of the manually constructed `txn` the builder must reproduce
+fn manual_request(
+    txn: Transaction,
+    scheme: SigningScheme,
+) -> l1x_rpc::rpc_model::SubmitTransactionRequest {
+    get_submit_txn_req(txn, KEY_ONE, 250, 7, scheme).unwrap()
+}
+
+fn sign(
+    builder: TransactionBuilder,
+) -> l1x_rpc::rpc_model::SubmitTransactionRequest {
+    builder.fee_limit(250).nonce(7).sign(&secret_key_one()).unwrap()
+}
+
+#[test]
+fn test_deploy_contract_matches_manual_construction() {
+    let manual = Transaction::SmartContractDeployment(
+        AccessType::PUBLIC,
+        ContractType::EVM,
+        U8s::Bytes(vec![0x60, 0x80, 0x60, 0x40]),
+        0,
+        U8s::Text("counter".to_string()),
+    );
+
+    let built = TransactionBuilder::new()
+        .deploy_contract(
+            AccessType::PUBLIC,
+            ContractType::EVM,
+            U8s::Bytes(vec![0x60, 0x80, 0x60, 0x40]),
+        )
+        .unwrap()
+        .salt("counter")
+        .unwrap();
+
+    assert_eq!(sign(built), manual_request(manual, SigningScheme::Canonical));
+}
+
+#[test]
+fn test_init_contract_matches_manual_construction() {
+    let address = "ab".repeat(20);
+    let manual = Transaction::SmartContractInit(
+        U8s::Hex(address.clone()),
+        U8s::Text("{}".to_string()),
+    );
+
+    let built = TransactionBuilder::new()
+        .init_contract(&format!("0x{}", address), U8s::Text("{}".to_string()))
+        .unwrap();
+
+    assert_eq!(sign(built), manual_request(manual, SigningScheme::Canonical));
+}
+
+#[test]
+fn test_staking_matches_manual_construction() {
+    let pool = "ef".repeat(20);
+    let stake = Transaction::Stake {
+        pool_address: U8s::Hex(pool.clone()),
+        amount: 500,
+    };
+    let unstake = Transaction::UnStake {
+        pool_address: U8s::Hex(pool.clone()),
+        amount: 200,
+    };
+    let create_pool = Transaction::CreateStakingPool {
+        contract_instance_address: Some(pool.parse().unwrap()),
+        min_stake: Some(10),
+        max_stake: None,
+        min_pool_balance: None,
+        max_pool_balance: Some(1000),
+        staking_period: Some(50),
+    };
+
+    let built_stake = TransactionBuilder::new().stake(&pool, 500).unwrap();
+    let built_unstake =
+        TransactionBuilder::new().unstake(&format!("0x{}", pool), 200).unwrap();
+    let built_create_pool = TransactionBuilder::new()
+        .create_staking_pool(
+            Some(&pool),
+            Some(10),
+            None,
+            None,
+            Some(1000),
+            Some(50),
+        )
+        .unwrap();
+
+    assert_eq!(
+        sign(built_stake),
+        manual_request(stake, SigningScheme::Canonical)
+    );
+    assert_eq!(
+        sign(built_unstake),
+        manual_request(unstake, SigningScheme::Canonical)
+    );
+    assert_eq!(
+        sign(built_create_pool),
+        manual_request(create_pool, SigningScheme::Canonical)
+    );
+}
+
+#[test]
+fn test_staking_rejects_invalid_pool_address() {
+    let stake = TransactionBuilder::new().stake("0x1234", 500);
+    let create_pool = TransactionBuilder::new().create_staking_pool(
+        Some("zz"),
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    assert!(matches!(stake, Err(TxnBuildError::InvalidAddress(_))));
+    assert!(matches!(create_pool, Err(TxnBuildError::InvalidAddress(_))));
+}
+
+#[test]
+fn test_call_matches_manual_construction() {
+    let address = "cd".repeat(20);
+    let manual = Transaction::SmartContractFunctionCall {
+        contract_instance_address: U8s::Hex(address.clone()),
+        function: U8s::Text(String::new()),
+        arguments: U8s::Hex("7b7d".to_string()),
+    };
+
+    let built = TransactionBuilder::new()
+        .call(&address, "", U8s::Hex("7b7d".to_string()))
+        .unwrap();
+
+    assert_eq!(sign(built), manual_request(manual, SigningScheme::Canonical));
+}
+
+#[test]
+fn test_native_transfer_matches_manual_construction_in_both_schemes() {
+    let address = "22".repeat(20);
+
+    for scheme in
+        [SigningScheme::Canonical, SigningScheme::LegacyNativeTransfer]
+    {
+        let manual =
+            Transaction::NativeTokenTransfer(U8s::Hex(address.clone()), 1000);
+
+        let built = TransactionBuilder::new()
+            .native_transfer(&address, 1000)
+            .unwrap()
+            .signing_scheme(scheme);
+
+        assert_eq!(sign(built), manual_request(manual, scheme));
+    }
+}
+
+#[test]
+fn test_builder_validates_each_step() {
+    let short = TransactionBuilder::new().native_transfer("00aa", 1);
+    let not_hex = TransactionBuilder::new()
+        .init_contract(&"zz".repeat(20), U8s::Text("{}".to_string()));
+    let bad_arguments = TransactionBuilder::new().call(
+        &"cd".repeat(20),
+        "",
+        U8s::Hex("not hex".to_string()),
+    );
+    let salt_without_deployment = TransactionBuilder::new()
+        .native_transfer(&"22".repeat(20), 1)
+        .unwrap()
+        .salt("counter");
+    let empty = TransactionBuilder::new().sign(&secret_key_one());
+
+    assert!(matches!(short, Err(TxnBuildError::InvalidAddress(_))));
+    assert!(matches!(not_hex, Err(TxnBuildError::InvalidAddress(_))));
+    assert!(matches!(bad_arguments, Err(TxnBuildError::TypeConversion(_))));
+    assert!(matches!(
+        salt_without_deployment,
+        Err(TxnBuildError::Incomplete(_))
+    ));
+    assert!(matches!(empty, Err(TxnBuildError::Incomplete(_))));
+}
+
+#[test]
+fn test_builder_defaults_to_cli_fee_limit() {
+    let request = TransactionBuilder::new()
+        .native_transfer(&"22".repeat(20), 1)
+        .unwrap()
+        .sign(&secret_key_one())
+        .unwrap();
+
+    assert_eq!(request.fee_limit, DEFAULT_FEE_LIMIT.to_string());
+    assert_eq!(request.nonce, "0");
+}
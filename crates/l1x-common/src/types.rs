@@ -1,11 +1,18 @@
 use crate::primitives::{Address, Balance, BlockNumber, Salt};
 use anyhow::{anyhow, Error};
+use base64::Engine;
 use l1x_rpc;
-use serde::{Deserialize, Serialize};
+use schemars::schema::{InstanceType, Schema, SchemaObject, StringValidation};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Keccak256};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// Serde wrappers for Transaction types, used to construct transaction payloads in the CLI
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema,
+)]
 #[repr(i8)]
 pub enum ContractType {
     L1XVM = 0,
@@ -13,7 +20,9 @@ pub enum ContractType {
     XTALK = 2,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema,
+)]
 #[repr(i8)]
 pub enum AccessType {
     PRIVATE = 0,
@@ -22,27 +31,76 @@ pub enum AccessType {
                     * specified addresses. */
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum U8s {
     Hex(String),
     Bytes(Vec<u8>),
-    File(String),
+    /// Content of a file. A relative path read from a payload file is
+    /// relative to the directory of that payload file.
+    File(PathBuf),
     Text(String),
+    /// Standard base64, padded
+    Base64(String),
 }
 
-impl TryFrom<U8s> for Vec<u8> {
-    type Error = Error;
+/// Why a [`U8s`] field of a transaction payload has no bytes
+#[derive(Debug, thiserror::Error)]
+pub enum U8sError {
+    #[error("{field} is not valid base64 :: {source}")]
+    InvalidBase64 {
+        field: String,
+        #[source]
+        source: base64::DecodeError,
+    },
+    #[error("Unable to read {path} for {field} :: {source}")]
+    File {
+        field: String,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
 
-    fn try_from(value: U8s) -> Result<Self, Self::Error> {
-        Ok(match value {
+impl U8s {
+    /// Bytes of the value of the payload field `field`
+    pub fn into_bytes(self, field: &str) -> Result<Vec<u8>, Error> {
+        Ok(match self {
             U8s::Hex(s) if &s == "" => vec![],
             U8s::Hex(s) => hex::decode(&s)?,
             U8s::Bytes(v) => v,
-            U8s::File(f) => std::fs::read(f)?,
+            U8s::File(path) => std::fs::read(&path).map_err(|source| {
+                U8sError::File { field: field.to_string(), path, source }
+            })?,
             U8s::Text(t) => t.into_bytes(),
+            U8s::Base64(s) => base64::engine::general_purpose::STANDARD
+                .decode(s.trim())
+                .map_err(|source| U8sError::InvalidBase64 {
+                    field: field.to_string(),
+                    source,
+                })?,
         })
     }
+
+    /// The value with a relative file path resolved against `base_dir`
+    pub fn relative_to(self, base_dir: &Path) -> U8s {
+        match self {
+            U8s::File(path) if path.is_relative() => {
+                U8s::File(base_dir.join(path))
+            }
+            value => value,
+        }
+    }
+}
+
+impl TryFrom<U8s> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(value: U8s) -> Result<Self, Self::Error> {
+        value.into_bytes("value")
+    }
 }
 
 impl TryFrom<U8s> for Address {
@@ -60,7 +118,150 @@ impl TryFrom<U8s> for Address {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Why a string is not a 20 byte account or contract address
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AddressParseError {
+    #[error("Invalid address '{input}': expected 40 hex chars, got {got}")]
+    Length { input: String, got: usize },
+    #[error(
+        "Invalid address '{input}': '{character}' at position {position} is not hex"
+    )]
+    NotHex { input: String, character: char, position: usize },
+}
+
+/// A 20 byte account or contract address.
+///
+/// Parses from hex with or without `0x`, in any case, ignoring surrounding
+/// whitespace and the quotes of the legacy registry values. Displays in the
+/// EIP-55 checksummed `0x` form and serializes as `0x` and lowercase hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AddressStr(Address);
+
+impl AddressStr {
+    pub fn as_bytes(&self) -> &Address {
+        &self.0
+    }
+
+    /// Lowercase hex without `0x`, the form the RPC requests carry
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+}
+
+impl From<Address> for AddressStr {
+    fn from(address: Address) -> Self {
+        AddressStr(address)
+    }
+}
+
+impl From<AddressStr> for Address {
+    fn from(address: AddressStr) -> Self {
+        address.0
+    }
+}
+
+impl From<AddressStr> for U8s {
+    fn from(address: AddressStr) -> Self {
+        U8s::Hex(address.to_hex())
+    }
+}
+
+impl FromStr for AddressStr {
+    type Err = AddressParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim().trim_matches('"');
+        let hex_address = trimmed
+            .strip_prefix("0x")
+            .or_else(|| trimmed.strip_prefix("0X"))
+            .unwrap_or(trimmed);
+
+        if let Some((position, character)) = hex_address
+            .chars()
+            .enumerate()
+            .find(|(_, character)| !character.is_ascii_hexdigit())
+        {
+            return Err(AddressParseError::NotHex {
+                input: input.to_string(),
+                character,
+                position,
+            });
+        }
+        if hex_address.len() != 40 {
+            return Err(AddressParseError::Length {
+                input: input.to_string(),
+                got: hex_address.len(),
+            });
+        }
+
+        let mut address = [0u8; 20];
+        hex::decode_to_slice(hex_address, &mut address)
+            .expect("40 hex digits decode to 20 bytes");
+        Ok(AddressStr(address))
+    }
+}
+
+impl std::fmt::Display for AddressStr {
+    /// EIP-55: a letter is uppercase when the matching nibble of the
+    /// keccak256 of the lowercase hex is 8 or more
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hex_address = self.to_hex();
+        let hash = Keccak256::digest(hex_address.as_bytes());
+
+        f.write_str("0x")?;
+        for (position, character) in hex_address.chars().enumerate() {
+            let nibble = match position % 2 {
+                0 => hash[position / 2] >> 4,
+                _ => hash[position / 2] & 0x0f,
+            };
+            match nibble >= 8 {
+                true => write!(f, "{}", character.to_ascii_uppercase())?,
+                false => write!(f, "{}", character)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for AddressStr {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", self.to_hex()))
+    }
+}
+
+impl schemars::JsonSchema for AddressStr {
+    fn schema_name() -> String {
+        "AddressStr".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^\\s*(0[xX])?[0-9a-fA-F]{40}\\s*$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl<'de> Deserialize<'de> for AddressStr {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let address = String::deserialize(deserializer)?;
+        address.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum Transaction {
     NativeTokenTransfer(U8s, Balance),
@@ -72,7 +273,7 @@ pub enum Transaction {
         arguments: U8s,
     },
     CreateStakingPool {
-        contract_instance_address: Option<Address>,
+        contract_instance_address: Option<AddressStr>,
         min_stake: Option<Balance>,
         max_stake: Option<Balance>,
         min_pool_balance: Option<Balance>,
@@ -89,6 +290,61 @@ pub enum Transaction {
     },
 }
 
+impl Transaction {
+    /// The transaction with the relative file paths of its values resolved
+    /// against `base_dir`
+    pub fn relative_to(self, base_dir: &Path) -> Transaction {
+        match self {
+            Transaction::NativeTokenTransfer(address, balance) => {
+                Transaction::NativeTokenTransfer(
+                    address.relative_to(base_dir),
+                    balance,
+                )
+            }
+            Transaction::SmartContractDeployment(
+                access_type,
+                contract_type,
+                code,
+                value,
+                salt,
+            ) => Transaction::SmartContractDeployment(
+                access_type,
+                contract_type,
+                code.relative_to(base_dir),
+                value,
+                salt.relative_to(base_dir),
+            ),
+            Transaction::SmartContractInit(address, arguments) => {
+                Transaction::SmartContractInit(
+                    address.relative_to(base_dir),
+                    arguments.relative_to(base_dir),
+                )
+            }
+            Transaction::SmartContractFunctionCall {
+                contract_instance_address,
+                function,
+                arguments,
+            } => Transaction::SmartContractFunctionCall {
+                contract_instance_address: contract_instance_address
+                    .relative_to(base_dir),
+                function: function.relative_to(base_dir),
+                arguments: arguments.relative_to(base_dir),
+            },
+            Transaction::Stake { pool_address, amount } => Transaction::Stake {
+                pool_address: pool_address.relative_to(base_dir),
+                amount,
+            },
+            Transaction::UnStake { pool_address, amount } => {
+                Transaction::UnStake {
+                    pool_address: pool_address.relative_to(base_dir),
+                    amount,
+                }
+            }
+            txn @ Transaction::CreateStakingPool { .. } => txn,
+        }
+    }
+}
+
 impl TryFrom<Transaction>
     for l1x_rpc::rpc_model::submit_transaction_request::TransactionType
 {
@@ -99,7 +355,7 @@ impl TryFrom<Transaction>
             Transaction::NativeTokenTransfer(address, balance) => {
                 l1x_rpc::rpc_model::submit_transaction_request::TransactionType::NativeTokenTransfer(
                     l1x_rpc::rpc_model::NativeTokenTransfer {
-                        address: TryInto::<Vec<u8>>::try_into(address)?,
+                        address: address.into_bytes("address")?,
                         amount: balance.to_string(),
                     },
                 )
@@ -119,17 +375,17 @@ impl TryFrom<Transaction>
                     l1x_rpc::rpc_model::SmartContractDeployment {
                         access_type: access_type.into(),
                         contract_type: contract_type.into(),
-                        contract_code: code.try_into()?,
+                        contract_code: code.into_bytes("contract_code")?,
                         value: value as u64,
-                        salt: salt.try_into()?,
+                        salt: salt.into_bytes("salt")?,
                     },
                 )
             }
             Transaction::SmartContractInit(address, arguments) => {
                 l1x_rpc::rpc_model::submit_transaction_request::TransactionType::SmartContractInit(
                     l1x_rpc::rpc_model::SmartContractInit {
-                        address: TryInto::<Vec<u8>>::try_into(address)?,
-                        arguments: arguments.try_into()?,
+                        address: address.into_bytes("address")?,
+                        arguments: arguments.into_bytes("arguments")?,
                     },
                 )
             }
@@ -139,9 +395,9 @@ impl TryFrom<Transaction>
                 arguments,
             } => l1x_rpc::rpc_model::submit_transaction_request::TransactionType::SmartContractFunctionCall(
                 l1x_rpc::rpc_model::SmartContractFunctionCall {
-                    contract_address: TryInto::<Vec<u8>>::try_into(contract_instance_address)?,
-                    function_name: TryInto::<Vec<u8>>::try_into(function)?,
-                    arguments: arguments.try_into()?,
+                    contract_address: contract_instance_address.into_bytes("contract_instance_address")?,
+                    function_name: function.into_bytes("function")?,
+                    arguments: arguments.into_bytes("arguments")?,
                 },
             ),
             Transaction::CreateStakingPool {
@@ -152,9 +408,8 @@ impl TryFrom<Transaction>
                 max_pool_balance,
                 staking_period,
             } => {
-                let contract_instance_address: Option<Vec<u8>> = contract_instance_address
-                    .map(TryInto::try_into)
-                    .transpose()?;
+                let contract_instance_address = contract_instance_address
+                    .map(|address| address.as_bytes().to_vec());
                 l1x_rpc::rpc_model::submit_transaction_request::TransactionType::CreateStakingPool(
                     l1x_rpc::rpc_model::CreateStakingPool {
                         contract_instance_address,
@@ -170,14 +425,14 @@ impl TryFrom<Transaction>
                 pool_address,
                 amount,
             } => l1x_rpc::rpc_model::submit_transaction_request::TransactionType::Stake(l1x_rpc::rpc_model::Stake {
-                pool_address: TryInto::<Vec<u8>>::try_into(pool_address)?,
+                pool_address: pool_address.into_bytes("pool_address")?,
                 amount: amount.to_string()
             }),
             Transaction::UnStake {
                 pool_address,
                 amount,
             } => l1x_rpc::rpc_model::submit_transaction_request::TransactionType::Unstake(l1x_rpc::rpc_model::UnStake {
-                pool_address: TryInto::<Vec<u8>>::try_into(pool_address)?,
+                pool_address: pool_address.into_bytes("pool_address")?,
                 amount: amount.to_string(),
             }),
         })
@@ -216,12 +471,65 @@ impl TryFrom<SmartContractReadOnlyFunctionCall>
     ) -> Result<Self, Self::Error> {
         Ok(l1x_rpc::rpc_model::SmartContractReadOnlyCallRequest {
             call: Some(l1x_rpc::rpc_model::SmartContractFunctionCall {
-                contract_address: TryInto::<Vec<u8>>::try_into(
-                    value.contract_instance_address,
-                )?,
-                function_name: TryInto::<Vec<u8>>::try_into(value.function)?,
-                arguments: value.arguments.try_into()?,
+                contract_address: value
+                    .contract_instance_address
+                    .into_bytes("contract_instance_address")?,
+                function_name: value.function.into_bytes("function")?,
+                arguments: value.arguments.into_bytes("arguments")?,
             }),
         })
     }
 }
+
+/// Arguments of the outer function call carrying a [`CrossContractCall`], as
+/// read by the cross-chain-swap contracts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrossContractCallArguments {
+    pub cross_contract_call: CrossContractCall,
+}
+
+/// Call a contract makes on another contract on behalf of the sender
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrossContractCall {
+    /// Hex encoded address of the target contract
+    pub contract_address: String,
+    pub function: String,
+    /// Hex encoded JSON arguments of the target function
+    pub arguments: String,
+}
+
+fn clean_contract_address(contract_address: &str) -> Result<String, Error> {
+    Ok(contract_address.parse::<AddressStr>()?.to_hex())
+}
+
+impl CrossContractCall {
+    pub fn new(
+        contract_address: &str,
+        function: &str,
+        arguments: &serde_json::Value,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            contract_address: clean_contract_address(contract_address)?,
+            function: function.to_string(),
+            arguments: hex::encode(serde_json::to_vec(arguments)?),
+        })
+    }
+
+    /// Wrap the call into `outer_function` of the contract at `outer_address`
+    pub fn into_transaction(
+        self,
+        outer_address: &str,
+        outer_function: &str,
+    ) -> Result<Transaction, Error> {
+        let arguments =
+            CrossContractCallArguments { cross_contract_call: self };
+
+        Ok(Transaction::SmartContractFunctionCall {
+            contract_instance_address: U8s::Hex(clean_contract_address(
+                outer_address,
+            )?),
+            function: U8s::Text(outer_function.to_string()),
+            arguments: U8s::Text(serde_json::to_string(&arguments)?),
+        })
+    }
+}
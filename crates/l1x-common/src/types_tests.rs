@@ -0,0 +1,95 @@
+use crate::types::*;
+
+use secp256k1::rand::random;
+
+/// Checksummed addresses of the EIP-55 specification
+const EIP55_ADDRESSES: [&str; 4] = [
+    "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+    "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+    "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+    "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+];
+
+#[test]
+fn test_address_displays_eip55_checksum() {
+    for checksummed in EIP55_ADDRESSES {
+        let address: AddressStr = checksummed.to_lowercase().parse().unwrap();
+
+        assert_eq!(address.to_string(), checksummed);
+    }
+}
+
+#[test]
+fn test_address_parses_prefixed_unprefixed_and_mixed_case() {
+    let expected: AddressStr = [0xab; 20].into();
+
+    for input in [
+        "ab".repeat(20),
+        format!("0x{}", "ab".repeat(20)),
+        format!("0X{}", "AB".repeat(20)),
+        format!("0x{}", "aB".repeat(20)),
+        format!(" \"0x{}\" ", "ab".repeat(20)),
+    ] {
+        assert_eq!(input.parse::<AddressStr>(), Ok(expected), "{}", input);
+    }
+}
+
+#[test]
+fn test_address_parse_errors_say_what_is_wrong() {
+    let short = format!("0x{}", "a".repeat(39));
+    let long = "a".repeat(42);
+    let not_hex = format!("0x{}zz", "a".repeat(38));
+
+    assert_eq!(
+        short.parse::<AddressStr>().unwrap_err().to_string(),
+        format!("Invalid address '{}': expected 40 hex chars, got 39", short)
+    );
+    assert_eq!(
+        long.parse::<AddressStr>(),
+        Err(AddressParseError::Length { input: long.clone(), got: 42 })
+    );
+    assert_eq!(
+        not_hex.parse::<AddressStr>(),
+        Err(AddressParseError::NotHex {
+            input: not_hex.clone(),
+            character: 'z',
+            position: 38,
+        })
+    );
+    assert!("".parse::<AddressStr>().is_err());
+}
+
+#[test]
+fn test_address_serializes_as_lowercase_hex() {
+    let address: AddressStr = EIP55_ADDRESSES[0].parse().unwrap();
+
+    let json = serde_json::to_string(&address).unwrap();
+
+    assert_eq!(json, format!("\"{}\"", EIP55_ADDRESSES[0].to_lowercase()));
+    assert!(serde_json::from_str::<AddressStr>("\"0x1234\"").is_err());
+}
+
+#[test]
+fn test_random_addresses_round_trip() {
+    for _ in 0..1000 {
+        let address = AddressStr::from(random::<[u8; 20]>());
+
+        let displayed: AddressStr = address.to_string().parse().unwrap();
+        let hex: AddressStr = address.to_hex().parse().unwrap();
+        let upper: AddressStr =
+            address.to_hex().to_uppercase().parse().unwrap();
+        let deserialized: AddressStr =
+            serde_json::from_str(&serde_json::to_string(&address).unwrap())
+                .unwrap();
+
+        assert_eq!(displayed, address);
+        assert_eq!(hex, address);
+        assert_eq!(upper, address);
+        assert_eq!(deserialized, address);
+        assert_eq!(
+            displayed.to_string(),
+            address.to_string(),
+            "the checksum only depends on the address"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Name of the manifest at the root of every template
+pub const TEMPLATE_MANIFEST: &str = "l1x-forge.toml";
+
+const TEMPLATE_MANIFEST_KEYS: &str = "[build]
+command = \"<shell command building the contract>\"
+artifact = \"<path of the built artifact, relative to the project root>\"";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TemplateManifest {
+    pub build: TemplateBuild,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TemplateBuild {
+    pub command: String,
+    pub artifact: PathBuf,
+}
+
+impl TemplateManifest {
+    pub fn parse(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|err| {
+            anyhow::anyhow!(
+                "Malformed {}: {}\nExpected keys:\n{}",
+                TEMPLATE_MANIFEST,
+                err.message(),
+                TEMPLATE_MANIFEST_KEYS
+            )
+        })
+    }
+
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let manifest_path = project_dir.join(TEMPLATE_MANIFEST);
+        let content = fs::read_to_string(&manifest_path).map_err(|err| {
+            anyhow::anyhow!(
+                "Unable to read {}: {}\nExpected keys:\n{}",
+                manifest_path.display(),
+                err,
+                TEMPLATE_MANIFEST_KEYS
+            )
+        })?;
+
+        Self::parse(&content)
+    }
+}
+
+/// Run the build command of the project manifest and copy the artifact to
+/// `artifacts_dir`. Returns the artifact id to install.
+pub fn build_project(
+    project_dir: &Path,
+    artifacts_dir: &Path,
+) -> Result<String> {
+    let manifest = TemplateManifest::load(project_dir)?;
+
+    tracing::info!(
+        "Building '{}' with `{}`",
+        project_dir.display(),
+        manifest.build.command
+    );
+
+    let status = Command::new("sh")
+        .args(["-c", &manifest.build.command])
+        .current_dir(project_dir)
+        .status()
+        .with_context(|| {
+            format!("Failed to run `{}`", manifest.build.command)
+        })?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Build command `{}` failed with {}",
+            manifest.build.command,
+            status
+        );
+    }
+
+    let artifact_path = project_dir.join(&manifest.build.artifact);
+    let artifact_id = artifact_path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid artifact path '{}' in {}",
+                manifest.build.artifact.display(),
+                TEMPLATE_MANIFEST
+            )
+        })?;
+
+    fs::create_dir_all(artifacts_dir)?;
+    fs::copy(&artifact_path, artifacts_dir.join(&artifact_id)).with_context(
+        || {
+            format!(
+                "Failed to copy the build artifact '{}'",
+                artifact_path.display()
+            )
+        },
+    )?;
+
+    Ok(artifact_id)
+}
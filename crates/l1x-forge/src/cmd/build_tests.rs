@@ -0,0 +1,95 @@
+use crate::cmd::build::*;
+
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn test_template_manifest_parse_valid() {
+    // Arrange
+    let content = r#"
+        [build]
+        command = "./build.sh"
+        artifact = "target/l1x/release/my_contract.o"
+    "#;
+
+    // Act
+    let manifest = TemplateManifest::parse(content).unwrap();
+
+    // Assert
+    assert_eq!(manifest.build.command, "./build.sh");
+    assert_eq!(
+        manifest.build.artifact,
+        PathBuf::from("target/l1x/release/my_contract.o")
+    );
+}
+
+#[test]
+fn test_template_manifest_parse_missing_key_lists_expected_keys() {
+    // Arrange
+    let content = r#"
+        [build]
+        command = "./build.sh"
+    "#;
+
+    // Act
+    let result = TemplateManifest::parse(content);
+
+    // Assert
+    let message = result.err().unwrap().to_string();
+    assert!(message.contains("artifact"));
+    assert!(message.contains("Expected keys"));
+}
+
+#[test]
+fn test_template_manifest_load_missing_file() {
+    let project_dir = tempfile::tempdir().unwrap();
+
+    let result = TemplateManifest::load(project_dir.path());
+
+    let message = result.err().unwrap().to_string();
+    assert!(message.contains(TEMPLATE_MANIFEST));
+    assert!(message.contains("Expected keys"));
+}
+
+#[test]
+fn test_build_project_copies_artifact() {
+    // Arrange
+    let project_dir = tempfile::tempdir().unwrap();
+    let artifacts_dir = tempfile::tempdir().unwrap();
+    fs::write(
+        project_dir.path().join(TEMPLATE_MANIFEST),
+        r#"
+            [build]
+            command = "mkdir -p out && printf 'ebpf' > out/my_contract.o"
+            artifact = "out/my_contract.o"
+        "#,
+    )
+    .unwrap();
+
+    // Act
+    let artifact_id =
+        build_project(project_dir.path(), artifacts_dir.path()).unwrap();
+
+    // Assert
+    assert_eq!(artifact_id, "my_contract.o");
+    assert_eq!(
+        fs::read(artifacts_dir.path().join("my_contract.o")).unwrap(),
+        b"ebpf"
+    );
+}
+
+#[test]
+fn test_build_project_fails_on_build_error() {
+    let project_dir = tempfile::tempdir().unwrap();
+    let artifacts_dir = tempfile::tempdir().unwrap();
+    fs::write(
+        project_dir.path().join(TEMPLATE_MANIFEST),
+        "[build]\ncommand = \"exit 3\"\nartifact = \"out/my_contract.o\"\n",
+    )
+    .unwrap();
+
+    let result = build_project(project_dir.path(), artifacts_dir.path());
+
+    assert!(result.is_err());
+    assert!(!artifacts_dir.path().join("my_contract.o").exists());
+}
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use clap::CommandFactory;
+
+pub use clap_complete::Shell;
+
+/// Print a shell completion script for `l1x-forge`
+#[derive(Debug, clap::Args)]
+#[clap(name = "completions")]
+pub struct CompletionsCommand {
+    /// Shell the script is written for
+    #[clap(value_enum)]
+    shell: Shell,
+}
+
+/// Completion script of `cmd` for `shell`
+pub fn generate(shell: Shell, cmd: &mut clap::Command) -> String {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, cmd, "l1x-forge", &mut script);
+
+    String::from_utf8_lossy(&script).into_owned()
+}
+
+impl CompletionsCommand {
+    pub async fn exec(&self) -> Result<()> {
+        print!("{}", generate(self.shell, &mut crate::Cli::command()));
+
+        Ok(())
+    }
+}
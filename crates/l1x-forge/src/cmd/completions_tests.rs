@@ -0,0 +1,24 @@
+use crate::cmd::completions::*;
+use crate::Cli;
+
+use clap::CommandFactory;
+
+#[test]
+fn test_bash_completions_cover_cli_subcommands() {
+    // Act
+    let script = generate(Shell::Bash, &mut Cli::command());
+
+    // Assert
+    assert!(script.contains("vm-install-contract"));
+    assert!(script.contains("--vm-type"));
+}
+
+#[test]
+fn test_completions_for_every_shell() {
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+        let script = generate(shell, &mut Cli::command());
+
+        assert!(script.contains("vm-sub-txn"), "{:?}", shell);
+        assert!(script.contains("vm-type"), "{:?}", shell);
+    }
+}
@@ -1,5 +1,17 @@
+pub(crate) mod build;
+#[cfg(test)]
+mod build_tests;
+pub(crate) mod completions;
+#[cfg(test)]
+mod completions_tests;
 pub(crate) mod new;
 #[cfg(test)]
 mod new_tests;
+pub(crate) mod templates;
+#[cfg(test)]
+mod templates_tests;
 
+pub(crate) use build::build_project;
+pub(crate) use completions::CompletionsCommand;
 pub(crate) use new::{new_contract_project, NewCommand};
+pub(crate) use templates::TemplatesCommand;
@@ -1,30 +1,104 @@
 use std::{
-    collections::BTreeMap, env, error::Error, ffi::OsStr, fmt::Display, fs,
-    path::PathBuf, process::Command, str::FromStr,
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
 };
 
 use anyhow::Result;
+use git2::build::{CheckoutBuilder, RepoBuilder};
+use git2::{
+    Cred, CredentialType, ErrorClass, ErrorCode, FetchOptions, Oid,
+    RemoteCallbacks, Repository,
+};
+use l1x_common::toolkit_config;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
-pub struct CloneError(String);
+pub enum CloneError {
+    /// The template repository couldn't be reached
+    Network(String),
+    /// The template repository rejected or asked for credentials
+    Authentication(String),
+    /// The template URL doesn't point to a git repository
+    InvalidUrl(String),
+    Other(String),
+}
 
 impl CloneError {
     pub fn new(message: String) -> Self {
-        CloneError(message)
+        CloneError::Other(message)
+    }
+
+    /// Prefix the message with `context`, keeping the kind of failure
+    pub fn context(self, context: &str) -> Self {
+        let with_context =
+            |message: String| format!("{}: {}", context, message);
+        match self {
+            CloneError::Network(message) => {
+                CloneError::Network(with_context(message))
+            }
+            CloneError::Authentication(message) => {
+                CloneError::Authentication(with_context(message))
+            }
+            CloneError::InvalidUrl(message) => {
+                CloneError::InvalidUrl(with_context(message))
+            }
+            CloneError::Other(message) => {
+                CloneError::Other(with_context(message))
+            }
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            CloneError::Network(message)
+            | CloneError::Authentication(message)
+            | CloneError::InvalidUrl(message)
+            | CloneError::Other(message) => message,
+        }
     }
 }
 
 impl Display for CloneError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.0)
+        f.write_str(self.message())
     }
 }
 
 impl Error for CloneError {}
 
+impl From<git2::Error> for CloneError {
+    /// Classify a libgit2 failure by its code and class
+    fn from(err_code: git2::Error) -> Self {
+        let message = err_code.message().to_string();
+        match (err_code.code(), err_code.class()) {
+            (ErrorCode::Auth | ErrorCode::Certificate, _) => {
+                CloneError::Authentication(message)
+            }
+            (ErrorCode::NotFound, ErrorClass::Repository)
+            | (_, ErrorClass::Invalid) => CloneError::InvalidUrl(message),
+            (
+                _,
+                ErrorClass::Net
+                | ErrorClass::Os
+                | ErrorClass::Http
+                | ErrorClass::Ssl,
+            ) => CloneError::Network(message),
+            _ => CloneError::Other(message),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
-struct Template {
+pub(crate) struct Template {
     url: String,
+    /// Branch, tag or commit to check out instead of the default branch
+    reference: Option<String>,
 }
 
 impl FromStr for Template {
@@ -32,7 +106,7 @@ impl FromStr for Template {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match L1XContractTemplateHub::default().repo.get(s) {
-            Some(url) => Ok(Template { url: url.to_string() }),
+            Some(info) => Ok(Template::new(&info.url)),
             None => {
                 Err(CloneError::new(format!("Invalid project template: {}", s)))
             }
@@ -40,61 +114,227 @@ impl FromStr for Template {
     }
 }
 
+impl Template {
+    /// Template cloned from `url`, which isn't validated
+    pub fn new(url: &str) -> Self {
+        Template { url: url.to_string(), reference: None }
+    }
+
+    /// Check out `reference`, a branch, tag or full commit hash, instead of
+    /// the default branch
+    pub fn with_reference(self, reference: Option<String>) -> Self {
+        Template { reference, ..self }
+    }
+
+    /// Template cloned from an arbitrary `https://`, `ssh://` or scp-like
+    /// `git@host:path` repository URL
+    pub fn from_url(url: &str) -> Result<Self, CloneError> {
+        let url = url.trim();
+
+        let host_and_path = ["https://", "http://", "ssh://", "git://"]
+            .iter()
+            .find_map(|scheme| url.strip_prefix(scheme))
+            .or_else(|| {
+                url.split_once('@')
+                    .filter(|(user, _)| !user.contains('/'))
+                    .and_then(|(_, host_and_path)| {
+                        host_and_path.split_once(':').and_then(
+                            |(host, path)| {
+                                (!host.is_empty() && !path.is_empty())
+                                    .then_some(host_and_path)
+                            },
+                        )
+                    })
+            });
+
+        match host_and_path {
+            Some(host_and_path)
+                if host_and_path.contains('/')
+                    && !host_and_path.starts_with('/') =>
+            {
+                Ok(Template::new(url))
+            }
+            _ => Err(CloneError::InvalidUrl(format!(
+                "Invalid template URL '{}': expected an https, ssh or git@host:path repository URL",
+                url
+            ))),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn reference(&self) -> Option<&str> {
+        self.reference.as_deref()
+    }
+}
+
+/// Fetch options logging the progress reported by the remote. Credentials
+/// are never prompted for, ssh remotes authenticate with the ssh agent.
+fn fetch_options<'a>() -> FetchOptions<'a> {
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.sideband_progress(|progress| {
+        // Progress reports are `\r` separated
+        String::from_utf8_lossy(progress)
+            .split(|c| c == '\r' || c == '\n')
+            .filter(|line| !line.trim().is_empty())
+            .for_each(|line| tracing::info!("git: {}", line.trim()));
+        true
+    });
+    let mut asked_agent = false;
+    callbacks.credentials(move |_url, username, allowed| {
+        if allowed.contains(CredentialType::SSH_KEY) && !asked_agent {
+            asked_agent = true;
+            return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+        }
+        Err(git2::Error::new(
+            ErrorCode::Auth,
+            ErrorClass::Net,
+            "the remote asked for credentials, which are never prompted for",
+        ))
+    });
+
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+    fetch_options
+}
+
+/// Commit of `reference` in `repo`: a branch of `origin`, a tag or a full
+/// hex commit hash
+fn find_reference<'r>(
+    repo: &'r Repository,
+    reference: &str,
+) -> Result<git2::Commit<'r>, git2::Error> {
+    let object = match Oid::from_str(reference) {
+        Ok(oid) if reference.len() == 40 => repo.find_object(oid, None),
+        _ => repo
+            .revparse_single(&format!("refs/remotes/origin/{}", reference))
+            .or_else(|_| {
+                repo.revparse_single(&format!("refs/tags/{}", reference))
+            }),
+    }?;
+
+    object.peel_to_commit()
+}
+
+/// A template of the hub
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TemplateInfo {
+    pub name: String,
+    pub url: String,
+    pub description: String,
+}
+
 // Define a struct to represent the L1X smart contract template hosted in GitHub structure
-struct L1XContractTemplateHub {
-    repo: BTreeMap<String, String>,
+pub(crate) struct L1XContractTemplateHub {
+    repo: BTreeMap<String, TemplateInfo>,
 }
 
 impl Default for L1XContractTemplateHub {
+    /// Built-in templates, overridden and extended by the templates of
+    /// `$L1X_CFG_WS_HOME/l1x-conf/l1x_templates.yaml`
     fn default() -> Self {
-        let mut repo = BTreeMap::new();
-
-        repo.insert(
-            "l1x-ft".to_string(),
-            "https://github.com/L1X-Foundation-Consensus/l1x-templ-ft.git"
-                .to_string(),
-        );
+        let mut hub = Self::builtin();
 
-        repo.insert(
-            "l1x-nft".to_string(),
-            "https://github.com/L1X-Foundation-Consensus/l1x-templ-nft.git"
-                .to_string(),
-        );
+        // Only the built-in templates outside of a workspace
+        let user_templates = match toolkit_config::ToolkitConfig::from_env() {
+            Ok(toolkit) => toolkit.template_config(),
+            Err(_) => Ok(Default::default()),
+        };
+        match user_templates {
+            Ok(user_templates) => hub.merge(user_templates),
+            Err(err_code) => tracing::warn!(
+                "Ignoring l1x_templates.yaml, using the built-in templates :: {}",
+                err_code
+            ),
+        }
 
-        repo.insert(
-            "l1x-erc20-cc-call".to_string(),
-            "https://github.com/L1X-Foundation-Consensus/l1x-templ-erc20-cross-contract-call.git"
-                .to_string(),
-        );
+        hub
+    }
+}
 
-        repo.insert(
-            "l1x-cross-chain-swap".to_string(),
-            "https://github.com/L1X-Foundation-Consensus/l1x-templ-cross-chain-swap.git"
-                .to_string(),
-        );
+impl L1XContractTemplateHub {
+    /// Templates shipped with `l1x-forge`
+    fn builtin() -> Self {
+        let mut repo = BTreeMap::new();
 
-        repo.insert(
-            "l1x-balancer-v2".to_string(),
-            "https://github.com/L1X-Foundation-Consensus/l1x-templ-balancer-pool.git"
-                .to_string(),
-        );
+        for (name, url, description) in [
+            (
+                "l1x-ft",
+                "https://github.com/L1X-Foundation-Consensus/l1x-templ-ft.git",
+                "Fungible token contract",
+            ),
+            (
+                "l1x-nft",
+                "https://github.com/L1X-Foundation-Consensus/l1x-templ-nft.git",
+                "Non-fungible token contract",
+            ),
+            (
+                "l1x-erc20-cc-call",
+                "https://github.com/L1X-Foundation-Consensus/l1x-templ-erc20-cross-contract-call.git",
+                "Contract calling an ERC20 token through cross-contract calls",
+            ),
+            (
+                "l1x-cross-chain-swap",
+                "https://github.com/L1X-Foundation-Consensus/l1x-templ-cross-chain-swap.git",
+                "Cross-chain token swap",
+            ),
+            (
+                "l1x-balancer-v2",
+                "https://github.com/L1X-Foundation-Consensus/l1x-templ-balancer-pool.git",
+                "Balancer V2 style liquidity pool",
+            ),
+        ] {
+            repo.insert(
+                name.to_string(),
+                TemplateInfo {
+                    name: name.to_string(),
+                    url: url.to_string(),
+                    description: description.to_string(),
+                },
+            );
+        }
 
         Self { repo }
     }
-}
 
-impl L1XContractTemplateHub {
+    /// Add `templates` to the hub, replacing the templates of the same name
+    fn merge(
+        &mut self,
+        templates: impl IntoIterator<
+            Item = (String, toolkit_config::TemplateHubEntry),
+        >,
+    ) {
+        for (name, entry) in templates {
+            tracing::debug!("Template {} :: {}", name, entry.url);
+            self.repo.insert(
+                name.clone(),
+                TemplateInfo {
+                    name,
+                    url: entry.url,
+                    description: entry.description,
+                },
+            );
+        }
+    }
+
+    /// Templates of the hub sorted by name
+    pub fn list(&self) -> Vec<TemplateInfo> {
+        self.repo.values().cloned().collect()
+    }
+
     fn print_templates(&self) {
         println!("Supported Templates ...");
-        self.repo.iter().for_each(|(key, val)| {
-            println!("{} :: {}", key, val);
+        self.list().iter().for_each(|template| {
+            println!("{} :: {}", template.name, template.url);
         });
     }
 
     pub fn get_template(&self, template_name: &str) -> Result<Template> {
         self.repo
             .get(template_name)
-            .map(|url| Template { url: url.clone() })
+            .map(|info| Template::new(&info.url))
             .ok_or_else(|| {
                 self.print_templates();
                 CloneError::new(format!(
@@ -110,52 +350,171 @@ impl L1XContractTemplateHub {
         project_template: Template,
         out_path: PathBuf,
     ) -> Result<()> {
-        log::info!(
+        self.clone_template(&project_template, &out_path)?;
+
+        // Remove the `.git` folder and initialize a new git repository.
+        tracing::info!("Removing `.git` folder");
+        fs::remove_dir_all(out_path.join(".git"))?;
+
+        Self::init_repo(&out_path)
+    }
+
+    /// Clone `project_template` to `out_path`, keeping its `.git` folder
+    fn clone_template(
+        &self,
+        project_template: &Template,
+        out_path: &Path,
+    ) -> Result<()> {
+        tracing::info!(
             "Cloning template '{}' to '{}'",
-            project_template.url,
+            project_template.url(),
             out_path.display()
         );
 
-        Command::new("git")
-            .args([
-                OsStr::new("clone"),
-                OsStr::new("--depth"),
-                OsStr::new("1"),
-                OsStr::new(&project_template.url),
-                out_path.as_os_str(),
-            ])
-            .output()
-            .map_err(|e| {
-                CloneError::new(format!(
-                    "Failed to clone template repository: {:?}",
-                    e
-                ))
-            })?;
+        let url = project_template.url();
+        let clone_failed = |err: CloneError| {
+            err.context(&format!(
+                "Failed to clone template repository '{}'",
+                url
+            ))
+        };
 
-        // Remove the `.git` folder and initialize a new git repository.
-        log::info!("Removing `.git` folder");
-        fs::remove_dir_all(out_path.join(".git"))?;
-        log::info!("Initializing new git repository");
-        Command::new("git")
-            .args([OsStr::new("-C"), out_path.as_os_str(), OsStr::new("init")])
-            .output()
-            .map_err(|_| {
-                CloneError::new(format!(
-                    "Failed to init repo '{:#?}'",
-                    out_path.as_os_str(),
-                ))
-            })?;
+        // libgit2 reports a missing local template as an unsupported URL
+        let local_path = Path::new(url);
+        if !url.contains("://") && !url.contains('@') && !local_path.exists() {
+            return Err(clone_failed(CloneError::InvalidUrl(format!(
+                "'{}' does not exist",
+                local_path.display()
+            )))
+            .into());
+        }
+
+        let repo = RepoBuilder::new()
+            .fetch_options(fetch_options())
+            .clone(url, out_path)
+            .map_err(|err| clone_failed(err.into()))?;
+
+        if let Some(reference) = project_template.reference() {
+            find_reference(&repo, reference)
+                .and_then(|commit| {
+                    repo.checkout_tree(
+                        commit.as_object(),
+                        Some(CheckoutBuilder::new().force()),
+                    )?;
+                    repo.set_head_detached(commit.id())
+                })
+                .map_err(|err| match CloneError::from(err) {
+                    CloneError::Other(message) => CloneError::new(format!(
+                        "Template ref '{}' not found in '{}': {}",
+                        reference,
+                        project_template.url(),
+                        message
+                    )),
+                    err => clone_failed(err),
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Copy the local template checkout `template_dir` without its `.git`
+    /// and `target` directories, then initialize a new git repository.
+    pub fn copy_local_template(
+        &self,
+        template_dir: &Path,
+        out_path: PathBuf,
+    ) -> Result<()> {
+        tracing::info!(
+            "Copying template '{}' to '{}'",
+            template_dir.display(),
+            out_path.display()
+        );
+
+        if !template_dir.is_dir() {
+            return Err(CloneError::new(format!(
+                "Template path '{}' is not a directory",
+                template_dir.display()
+            ))
+            .into());
+        }
+
+        Self::copy_dir(template_dir, &out_path)?;
+
+        Self::init_repo(&out_path)
+    }
+
+    fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+        fs::create_dir_all(to)?;
+
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            if file_name == ".git" || file_name == "target" {
+                continue;
+            }
+
+            let source = entry.path();
+            let target = to.join(&file_name);
+            let file_type = fs::symlink_metadata(&source)?.file_type();
+
+            if file_type.is_symlink() {
+                Self::copy_symlink(&source, &target)?;
+            } else if file_type.is_dir() {
+                Self::copy_dir(&source, &target)?;
+            } else {
+                fs::copy(&source, &target)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recreate the symlink `source` as `target`, pointing to the same path
+    #[cfg(unix)]
+    fn copy_symlink(source: &Path, target: &Path) -> Result<()> {
+        std::os::unix::fs::symlink(fs::read_link(source)?, target)?;
+        Ok(())
+    }
+
+    /// Copy what the symlink `source` points to, where symlinks need extra
+    /// privileges
+    #[cfg(not(unix))]
+    fn copy_symlink(source: &Path, target: &Path) -> Result<()> {
+        if source.is_dir() {
+            Self::copy_dir(source, target)
+        } else {
+            fs::copy(source, target)?;
+            Ok(())
+        }
+    }
+
+    fn init_repo(out_path: &Path) -> Result<()> {
+        tracing::info!("Initializing new git repository");
+        Repository::init(out_path).map_err(|err| {
+            CloneError::from(err).context(&format!(
+                "Failed to init repo '{}'",
+                out_path.display()
+            ))
+        })?;
 
         Ok(())
     }
 }
 
-/// Creates a new contract project from the template.
+/// Template of projects created without `--template`
+const DEFAULT_TEMPLATE: &str = "l1x-ft";
+
+/// Creates a new contract project from the template, cloned to the
+/// [`TemplateCache`] unless cached already or `refresh_template` is set. A
+/// non-empty project directory is only replaced when `force` is set.
 pub fn new_contract_project<P>(
     name: &str,
     template_name: Option<String>,
+    template_ref: Option<String>,
     proj_base_path: Option<P>,
-) -> Result<()>
+    force: bool,
+    refresh_template: bool,
+) -> Result<Option<TemplateManifest>>
 where
     P: AsRef<std::path::Path>,
 {
@@ -164,11 +523,47 @@ where
 
     // Get the project template name. If no template name is specified, use the default template name.
     let project_template_name =
-        template_name.unwrap_or_else(|| String::from("l1x-ft"));
+        template_name.unwrap_or_else(|| String::from(DEFAULT_TEMPLATE));
 
     // Get the contract template from the template hub.
-    let project_template =
-        l1x_template_hub.get_template(&project_template_name)?;
+    let project_template = l1x_template_hub
+        .get_template(&project_template_name)?
+        .with_reference(template_ref);
+
+    create_contract_project(
+        name,
+        TemplateSource::Hub {
+            name: project_template_name,
+            template: project_template,
+            refresh: refresh_template,
+        },
+        proj_base_path,
+        force,
+    )
+}
+
+/// Where the files of a new project come from
+pub(crate) enum TemplateSource {
+    /// Repository cloned with `git`
+    Git(Template),
+    /// Hub template copied from the [`TemplateCache`]
+    Hub { name: String, template: Template, refresh: bool },
+    /// Local template checkout
+    Local(PathBuf),
+}
+
+/// Creates a new contract project from `project_template`, which may not be
+/// part of the hub. Returns the [`TEMPLATE_METADATA`] of the template, if any.
+pub(crate) fn create_contract_project<P>(
+    name: &str,
+    project_template: TemplateSource,
+    proj_base_path: Option<P>,
+    force: bool,
+) -> Result<Option<TemplateManifest>>
+where
+    P: AsRef<std::path::Path>,
+{
+    let l1x_template_hub = L1XContractTemplateHub::default();
 
     // Check if the contract name is valid. A contract name can only contain alphanumeric characters
     // and underscores, and it must begin with an alphabetic character.
@@ -188,22 +583,671 @@ where
         .map_or(env::current_dir()?, |p| p.as_ref().to_path_buf())
         .join(name);
 
-    // Check if the output directory already exists. If it does, bail out.
-    if out_dir.join("Cargo.toml").exists() {
-        anyhow::bail!("A Cargo package already exists in {}", name);
+    // An existing empty directory is reused, anything else is only replaced
+    // with `--force`.
+    if out_dir.exists() && !is_empty_dir(&out_dir)? {
+        if !force {
+            if out_dir.join("Cargo.toml").exists() {
+                anyhow::bail!("A Cargo package already exists in {}", name);
+            }
+            anyhow::bail!(
+                "Directory '{}' is not empty, use --force to overwrite it",
+                out_dir.display()
+            );
+        }
+
+        tracing::info!("Overwriting '{}'", out_dir.display());
+        if out_dir.is_dir() {
+            fs::remove_dir_all(&out_dir)?;
+        } else {
+            fs::remove_file(&out_dir)?;
+        }
     }
 
     // If the output directory does not exist, create it.
-    if !out_dir.exists() {
+    let created_out_dir = !out_dir.exists();
+    if created_out_dir {
         fs::create_dir(&out_dir)?;
     }
 
+    let scaffolded =
+        scaffold_project(&l1x_template_hub, name, project_template, &out_dir);
+    if scaffolded.is_err() && created_out_dir {
+        // Don't leave a half scaffolded project behind.
+        if let Err(remove_err) = fs::remove_dir_all(&out_dir) {
+            tracing::warn!(
+                "Failed to remove '{}': {}",
+                out_dir.display(),
+                remove_err
+            );
+        }
+    }
+
+    scaffolded
+}
+
+/// Copy `project_template` to `out_dir`, name the project after the contract
+/// and apply the [`TEMPLATE_METADATA`] of the template
+fn scaffold_project(
+    l1x_template_hub: &L1XContractTemplateHub,
+    name: &str,
+    project_template: TemplateSource,
+    out_dir: &Path,
+) -> Result<Option<TemplateManifest>> {
     // Copy the contract template to the output directory.
-    l1x_template_hub.copy_template(project_template, out_dir)?;
+    match project_template {
+        TemplateSource::Git(template) => {
+            l1x_template_hub.copy_template(template, out_dir.to_path_buf())?
+        }
+        TemplateSource::Hub { name, template, refresh } => {
+            let checkout = TemplateCache::from_env()?.checkout(
+                l1x_template_hub,
+                &name,
+                &template,
+                refresh,
+            )?;
+            l1x_template_hub
+                .copy_local_template(&checkout, out_dir.to_path_buf())?
+        }
+        TemplateSource::Local(template_dir) => l1x_template_hub
+            .copy_local_template(&template_dir, out_dir.to_path_buf())?,
+    }
+
+    let manifest = TemplateManifest::load(out_dir)?;
+    if let Some(manifest) = &manifest {
+        manifest.check_requirements()?;
+    }
+
+    // Name the project after the contract instead of the template.
+    for modified_file in substitute_project_name(out_dir, name)? {
+        println!("Updated {}", modified_file.display());
+    }
+
+    if let Some(manifest) = &manifest {
+        for renamed_file in manifest.apply_rename_rules(out_dir, name)? {
+            println!("Renamed {}", renamed_file.display());
+        }
+        manifest.run_post_commands(out_dir)?;
+
+        if let Some(description) = &manifest.description {
+            println!("{}", description);
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Overrides the directory where the hub templates are cached
+pub const TEMPLATE_CACHE_ENV: &str = "L1X_FORGE_TEMPLATE_CACHE";
+
+/// `url` and `ref` a cache entry was cloned from
+const TEMPLATE_CACHE_SOURCE: &str = "source";
+
+/// Clones of the hub templates, so that `new` works offline. Every entry
+/// holds the `template` checkout, without `.git`, and the
+/// [`TEMPLATE_CACHE_SOURCE`] it was cloned from.
+pub(crate) struct TemplateCache {
+    root: PathBuf,
+}
+
+impl TemplateCache {
+    pub fn new(root: PathBuf) -> Self {
+        TemplateCache { root }
+    }
+
+    /// `$L1X_FORGE_TEMPLATE_CACHE`, else `~/.cache/l1x-forge/templates`
+    pub fn from_env() -> Result<Self> {
+        if let Some(root) = env::var_os(TEMPLATE_CACHE_ENV) {
+            return Ok(Self::new(PathBuf::from(root)));
+        }
+
+        let cache_home = env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME")
+                    .map(|home| PathBuf::from(home).join(".cache"))
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unable to locate the template cache, set {}",
+                    TEMPLATE_CACHE_ENV
+                )
+            })?;
+
+        Ok(Self::new(cache_home.join("l1x-forge").join("templates")))
+    }
+
+    fn entry_dir(&self, name: &str, template: &Template) -> PathBuf {
+        match template.reference() {
+            Some(reference) => self.root.join(format!(
+                "{}@{}",
+                name,
+                reference.replace(['/', '\\'], "_")
+            )),
+            None => self.root.join(name),
+        }
+    }
+
+    fn source_of(template: &Template) -> String {
+        format!(
+            "{}\n{}\n",
+            template.url(),
+            template.reference().unwrap_or_default()
+        )
+    }
+
+    /// Checkout of `template`, `None` unless it is cached from the same source
+    fn cached(&self, name: &str, template: &Template) -> Option<PathBuf> {
+        let entry_dir = self.entry_dir(name, template);
+        let source =
+            fs::read_to_string(entry_dir.join(TEMPLATE_CACHE_SOURCE)).ok()?;
+        let checkout = entry_dir.join("template");
+
+        (source == Self::source_of(template) && checkout.is_dir())
+            .then_some(checkout)
+    }
+
+    /// Checkout of `template`, cloned first when it is missing, stale or
+    /// corrupt, or when `refresh` is set
+    pub fn checkout(
+        &self,
+        hub: &L1XContractTemplateHub,
+        name: &str,
+        template: &Template,
+        refresh: bool,
+    ) -> Result<PathBuf> {
+        if !refresh {
+            if let Some(checkout) = self.cached(name, template) {
+                tracing::info!(
+                    "Using cached template '{}' from '{}'",
+                    name,
+                    checkout.display()
+                );
+                return Ok(checkout);
+            }
+
+            if self.entry_dir(name, template).exists() {
+                tracing::warn!(
+                    "Cached template '{}' is stale or corrupt, cloning it again",
+                    name
+                );
+            }
+        }
+
+        self.refresh(hub, name, template)
+    }
+
+    /// Clone `template` to the cache, replacing its previous entry once the
+    /// clone succeeded
+    pub fn refresh(
+        &self,
+        hub: &L1XContractTemplateHub,
+        name: &str,
+        template: &Template,
+    ) -> Result<PathBuf> {
+        let entry_dir = self.entry_dir(name, template);
+        let mut partial_dir = entry_dir.clone().into_os_string();
+        partial_dir.push(".partial");
+        let partial_dir = PathBuf::from(partial_dir);
+
+        if partial_dir.exists() {
+            fs::remove_dir_all(&partial_dir)?;
+        }
+        fs::create_dir_all(&partial_dir)?;
+
+        let cloned = hub
+            .clone_template(template, &partial_dir.join("template"))
+            .and_then(|_| {
+                fs::remove_dir_all(partial_dir.join("template/.git"))?;
+                fs::write(
+                    partial_dir.join(TEMPLATE_CACHE_SOURCE),
+                    Self::source_of(template),
+                )?;
+                Ok(())
+            });
+        if let Err(err) = cloned {
+            if let Err(remove_err) = fs::remove_dir_all(&partial_dir) {
+                tracing::warn!(
+                    "Failed to remove '{}': {}",
+                    partial_dir.display(),
+                    remove_err
+                );
+            }
+            return Err(err);
+        }
+
+        if entry_dir.exists() {
+            fs::remove_dir_all(&entry_dir)?;
+        }
+        fs::rename(&partial_dir, &entry_dir)?;
+
+        Ok(entry_dir.join("template"))
+    }
+
+    /// Clone again the hub templates cached for their default branch,
+    /// returning the outcome per template name
+    pub fn update_all(
+        &self,
+        hub: &L1XContractTemplateHub,
+    ) -> Vec<(String, Result<PathBuf>)> {
+        hub.list()
+            .into_iter()
+            .map(|info| (info.name, Template::new(&info.url)))
+            .filter(|(name, template)| self.entry_dir(name, template).exists())
+            .map(|(name, template)| {
+                let updated = self.refresh(hub, &name, &template);
+                (name, updated)
+            })
+            .collect()
+    }
+}
+
+/// Optional metadata file at the root of a template
+pub const TEMPLATE_METADATA: &str = "l1x-template.toml";
+
+/// Content of the [`TEMPLATE_METADATA`] of a template
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct TemplateManifest {
+    pub name: Option<String>,
+    /// Printed once the project is created
+    pub description: Option<String>,
+    /// Oldest `l1x-forge` version able to create the project
+    pub min_forge_version: Option<String>,
+    /// Environment variables that must be set to create the project
+    #[serde(default)]
+    pub required_env: Vec<String>,
+    /// Contract id used instead of the project name by `new --install`
+    pub default_contract_id: Option<String>,
+    #[serde(default)]
+    pub rename: Vec<TemplateRenameRule>,
+    /// Shell commands run in the project directory once it is created
+    #[serde(default)]
+    pub post_commands: Vec<String>,
+    /// The contract is built for `wasm32-unknown-unknown`
+    #[serde(default)]
+    pub wasm: bool,
+}
+
+/// Move a template file or directory, both paths relative to the project
+/// root. `to` may contain [`PROJECT_NAME_PLACEHOLDER`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TemplateRenameRule {
+    pub from: PathBuf,
+    pub to: String,
+}
+
+impl TemplateManifest {
+    pub fn parse(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|err| {
+            anyhow::anyhow!(
+                "Malformed {}: {}",
+                TEMPLATE_METADATA,
+                err.message()
+            )
+        })
+    }
+
+    /// Load the manifest of `project_dir`, `None` without one
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        let manifest_path = project_dir.join(TEMPLATE_METADATA);
+        if !manifest_path.is_file() {
+            return Ok(None);
+        }
+
+        Self::parse(&fs::read_to_string(manifest_path)?).map(Some)
+    }
+
+    fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("template")
+    }
+
+    /// Fail unless this `l1x-forge` and its environment can create the project
+    pub fn check_requirements(&self) -> Result<()> {
+        if let Some(min_forge_version) = &self.min_forge_version {
+            let forge_version = env!("CARGO_PKG_VERSION");
+            let required = parse_version(min_forge_version).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid min_forge_version '{}' in {} of '{}', expected MAJOR.MINOR.PATCH",
+                    min_forge_version,
+                    TEMPLATE_METADATA,
+                    self.display_name()
+                )
+            })?;
+
+            if parse_version(forge_version) < Some(required) {
+                anyhow::bail!(
+                    "Template '{}' requires l1x-forge {} or newer, this is l1x-forge {}",
+                    self.display_name(),
+                    min_forge_version,
+                    forge_version
+                );
+            }
+        }
+
+        let missing_env = self
+            .required_env
+            .iter()
+            .filter(|var| env::var_os(var).is_none())
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !missing_env.is_empty() {
+            anyhow::bail!(
+                "Template '{}' requires the environment variables: {}",
+                self.display_name(),
+                missing_env.join(", ")
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Apply the rename rules in `project_dir`, returning the new paths
+    pub fn apply_rename_rules(
+        &self,
+        project_dir: &Path,
+        name: &str,
+    ) -> Result<Vec<PathBuf>> {
+        let mut renamed_files = Vec::new();
+
+        for rule in &self.rename {
+            let to =
+                PathBuf::from(rule.to.replace(PROJECT_NAME_PLACEHOLDER, name));
+            if rule.from.is_absolute()
+                || to.is_absolute()
+                || rule
+                    .from
+                    .components()
+                    .chain(to.components())
+                    .any(|c| c == std::path::Component::ParentDir)
+            {
+                anyhow::bail!(
+                    "Rename rule '{}' -> '{}' must stay inside the project",
+                    rule.from.display(),
+                    rule.to
+                );
+            }
+
+            let from_path = project_dir.join(&rule.from);
+            if !from_path.exists() {
+                anyhow::bail!(
+                    "Rename rule source '{}' not found in the template",
+                    rule.from.display()
+                );
+            }
+
+            let to_path = project_dir.join(&to);
+            if let Some(parent) = to_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(&from_path, &to_path)?;
+            renamed_files.push(to);
+        }
+
+        Ok(renamed_files)
+    }
+
+    /// Run the post commands in `project_dir`, stopping at the first failure
+    pub fn run_post_commands(&self, project_dir: &Path) -> Result<()> {
+        for command in &self.post_commands {
+            tracing::info!("Running `{}`", command);
+
+            let status = Command::new("sh")
+                .args(["-c", command])
+                .current_dir(project_dir)
+                .status()
+                .map_err(|err| {
+                    anyhow::anyhow!("Failed to run `{}`: {}", command, err)
+                })?;
+
+            if !status.success() {
+                anyhow::bail!(
+                    "Post command `{}` failed with {}",
+                    command,
+                    status
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Run `cargo check` in `project_dir`, or build it for
+/// `wasm32-unknown-unknown` when its manifest says it is a wasm contract. The
+/// cargo output is streamed, the project is kept when it fails.
+pub fn check_project(
+    project_dir: &Path,
+    manifest: Option<&TemplateManifest>,
+) -> Result<()> {
+    let args: &[&str] = match manifest {
+        Some(manifest) if manifest.wasm => {
+            &["build", "--target", "wasm32-unknown-unknown"]
+        }
+        _ => &["check"],
+    };
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    tracing::info!(
+        "Running `cargo {}` in '{}'",
+        args.join(" "),
+        project_dir.display()
+    );
+
+    let status = Command::new(cargo)
+        .args(args)
+        .current_dir(project_dir)
+        .status()
+        .map_err(|err| anyhow::anyhow!("Failed to run cargo: {}", err))?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "`cargo {}` failed with {}, the project is left in '{}' for inspection",
+            args.join(" "),
+            status,
+            project_dir.display()
+        );
+    }
 
     Ok(())
 }
 
+/// Parse a `MAJOR.MINOR.PATCH` version, ignoring any pre-release suffix
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim();
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((major, minor, patch))
+}
+
+/// Whether `path` is a directory without any entries
+fn is_empty_dir(path: &Path) -> Result<bool> {
+    if !path.is_dir() {
+        return Ok(false);
+    }
+
+    Ok(fs::read_dir(path)?.next().is_none())
+}
+
+/// Placeholder for the project name templates may use in any text file
+pub const PROJECT_NAME_PLACEHOLDER: &str = "{{project_name}}";
+
+/// Rename the package of the `Cargo.toml` of `project_dir` to `name`, then
+/// replace the former package name, as a whole word, and
+/// [`PROJECT_NAME_PLACEHOLDER`] by `name` in every other text file. Returns
+/// the modified files, relative to `project_dir`.
+pub fn substitute_project_name(
+    project_dir: &Path,
+    name: &str,
+) -> Result<Vec<PathBuf>> {
+    let mut modified_files = Vec::new();
+
+    let manifest_path = project_dir.join("Cargo.toml");
+    let template_name = if manifest_path.is_file() {
+        let template_name = rename_package(&manifest_path, name)?;
+        if template_name.as_deref() != Some(name) {
+            modified_files.push(PathBuf::from("Cargo.toml"));
+        }
+        template_name
+    } else {
+        None
+    };
+
+    // `my-token` is referred to as `my_token` in the Rust sources.
+    let mut replaced_names = vec![PROJECT_NAME_PLACEHOLDER.to_string()];
+    if let Some(template_name) = template_name.filter(|n| n != name) {
+        if template_name.contains('-') {
+            replaced_names.push(template_name.replace('-', "_"));
+        }
+        replaced_names.push(template_name);
+    }
+
+    substitute_in_dir(
+        project_dir,
+        project_dir,
+        name,
+        &replaced_names,
+        &mut modified_files,
+    )?;
+    modified_files.sort();
+
+    Ok(modified_files)
+}
+
+/// Set `package.name`, and `lib.name` when present, of the manifest at
+/// `manifest_path` to `name`. Returns the former package name.
+fn rename_package(manifest_path: &Path, name: &str) -> Result<Option<String>> {
+    let mut manifest: toml_edit::Document =
+        fs::read_to_string(manifest_path)?.parse().map_err(|err| {
+            anyhow::anyhow!(
+                "Malformed template manifest {}: {}",
+                manifest_path.display(),
+                err
+            )
+        })?;
+
+    let template_name = manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|package_name| package_name.as_str())
+        .map(str::to_string);
+
+    let template_name = match template_name {
+        Some(template_name) => template_name,
+        None => return Ok(None),
+    };
+    if template_name == name {
+        return Ok(Some(template_name));
+    }
+
+    for table in ["package", "lib"] {
+        // Keep the comments and spacing around the renamed values.
+        if let Some(value) = manifest
+            .get_mut(table)
+            .and_then(|table| table.get_mut("name"))
+            .and_then(|name| name.as_value_mut())
+        {
+            let decor = value.decor().clone();
+            *value = toml_edit::Value::from(name);
+            *value.decor_mut() = decor;
+        }
+    }
+    fs::write(manifest_path, manifest.to_string())?;
+
+    Ok(Some(template_name))
+}
+
+fn substitute_in_dir(
+    project_dir: &Path,
+    dir: &Path,
+    name: &str,
+    replaced_names: &[String],
+    modified_files: &mut Vec<PathBuf>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_name == ".git"
+            || file_name == "target"
+            || file_type.is_symlink()
+        {
+            continue;
+        }
+        if file_type.is_dir() {
+            substitute_in_dir(
+                project_dir,
+                &path,
+                name,
+                replaced_names,
+                modified_files,
+            )?;
+            continue;
+        }
+
+        let relative_path = path.strip_prefix(project_dir)?.to_path_buf();
+        if relative_path == Path::new("Cargo.toml") {
+            continue;
+        }
+
+        // Binary files are neither valid UTF-8 nor free of NUL bytes.
+        let content = match String::from_utf8(fs::read(&path)?) {
+            Ok(content) if !content.contains('\0') => content,
+            _ => continue,
+        };
+
+        let substituted = replaced_names.iter().fold(
+            content.clone(),
+            |substituted, replaced_name| {
+                replace_word(&substituted, replaced_name, name)
+            },
+        );
+
+        if substituted != content {
+            fs::write(&path, substituted)?;
+            modified_files.push(relative_path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace the occurrences of `from` in `content` that aren't part of a
+/// longer identifier or package name
+fn replace_word(content: &str, from: &str, to: &str) -> String {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+
+    let mut replaced = String::with_capacity(content.len());
+    let mut copied_up_to = 0;
+    for (start, _) in content.match_indices(from) {
+        let end = start + from.len();
+        let whole_word = from == PROJECT_NAME_PLACEHOLDER
+            || (!content[..start]
+                .chars()
+                .next_back()
+                .map_or(false, is_name_char)
+                && !content[end..].chars().next().map_or(false, is_name_char));
+
+        if whole_word {
+            replaced.push_str(&content[copied_up_to..start]);
+            replaced.push_str(to);
+            copied_up_to = end;
+        }
+    }
+    replaced.push_str(&content[copied_up_to..]);
+
+    replaced
+}
+
 /// Setup and create a new L1X smart contract project
 #[derive(Debug, clap::Args)]
 #[clap(name = "new")]
@@ -214,19 +1258,118 @@ pub struct NewCommand {
     /// The optional source contract template name
     #[clap(long = "template", value_parser)]
     template_name: Option<String>,
+    /// Git URL (https or ssh) of a template outside of the template hub
+    #[clap(long = "template-url", conflicts_with = "template_name")]
+    template_url: Option<String>,
+    /// Branch, tag or full commit hash of the template to check out
+    #[clap(long = "template-ref", conflicts_with = "template_path")]
+    template_ref: Option<String>,
+    /// Local template directory, copied without its `.git` and `target`
+    #[clap(
+        long = "template-path",
+        conflicts_with_all = ["template_name", "template_url"]
+    )]
+    template_path: Option<PathBuf>,
     /// The optional target directory for the contract project
     #[clap(long = "base-path", value_parser)]
     target_dir: Option<PathBuf>,
+    /// Replace the contract project directory if it isn't empty
+    #[clap(long = "force", default_value_t = false)]
+    force: bool,
+    /// Clone the hub template again instead of using its cached copy
+    #[clap(
+        long = "refresh-template",
+        default_value_t = false,
+        conflicts_with_all = ["template_url", "template_path"]
+    )]
+    refresh_template: bool,
+    /// Run `cargo check` on the created project
+    #[clap(long = "check", default_value_t = false)]
+    check: bool,
+    /// Build the project with the command of its `l1x-forge.toml`
+    #[clap(long = "build", default_value_t = false)]
+    build: bool,
+    /// Build the project and install its artifact to L1X VM
+    #[clap(long = "install", default_value_t = false, requires = "owner")]
+    install: bool,
+    /// The wallet owning the installed contract
+    #[clap(long = "owner")]
+    owner: Option<String>,
 }
 
 impl NewCommand {
-    pub fn exec(&self) -> Result<()> {
-        super::new_contract_project(
-            &self.name,
-            self.template_name.clone(),
-            self.target_dir.as_ref(),
-        )?;
+    pub async fn exec(&self) -> Result<()> {
+        let manifest = match (&self.template_url, &self.template_path) {
+            (Some(template_url), _) => create_contract_project(
+                &self.name,
+                TemplateSource::Git(
+                    Template::from_url(template_url)?
+                        .with_reference(self.template_ref.clone()),
+                ),
+                self.target_dir.as_ref(),
+                self.force,
+            )?,
+            (None, Some(template_path)) => create_contract_project(
+                &self.name,
+                TemplateSource::Local(template_path.clone()),
+                self.target_dir.as_ref(),
+                self.force,
+            )?,
+            (None, None) => super::new_contract_project(
+                &self.name,
+                self.template_name.clone(),
+                self.template_ref.clone(),
+                self.target_dir.as_ref(),
+                self.force,
+                self.refresh_template,
+            )?,
+        };
         println!("Created contract {}", self.name);
+
+        let project_dir = self
+            .target_dir
+            .clone()
+            .map_or_else(env::current_dir, Ok)?
+            .join(&self.name);
+
+        if self.check {
+            check_project(&project_dir, manifest.as_ref())?;
+            println!("Checked contract {}", self.name);
+        }
+
+        if self.build || self.install {
+            let toolkit = toolkit_config::ToolkitConfig::from_env()
+                .map_err(l1x_cli::L1XConfigError::from)?;
+            let artifacts_dir =
+                toolkit.artifact_dir(toolkit_config::L1XVMArtifactType::Ebpf);
+
+            let artifact_id =
+                super::build_project(&project_dir, &artifacts_dir)?;
+            println!("Built artifact {}", artifact_id);
+
+            if let (true, Some(owner)) = (self.install, &self.owner) {
+                let contract_id = manifest
+                    .and_then(|manifest| manifest.default_contract_id)
+                    .unwrap_or_else(|| self.name.clone());
+
+                l1x_cli::L1XVmInstallContractCmd::ebpf(
+                    &artifact_id,
+                    &contract_id,
+                    owner,
+                )
+                .exec()
+                .await?;
+
+                let contract_address = toolkit
+                    .ebpf_contract_address_for(&artifact_id, Some(&contract_id))
+                    .map_err(l1x_cli::L1XConfigError::from)?;
+                println!(
+                    "Installed contract {} :: {}",
+                    contract_id, contract_address
+                );
+            }
+        }
+
         Ok(())
     }
 }
@@ -10,7 +10,14 @@ fn test_new_contract_project_valid_name() {
     let proj_base_path: Option<PathBuf> = None;
 
     // Act
-    let result = new_contract_project(name, template_name, proj_base_path);
+    let result = new_contract_project(
+        name,
+        template_name,
+        None,
+        proj_base_path,
+        false,
+        false,
+    );
 
     // Assert
     assert!(result.is_ok());
@@ -24,7 +31,14 @@ fn test_new_contract_project_invalid_name() {
     let proj_base_path: Option<PathBuf> = None;
 
     // Act
-    let result = new_contract_project(name, template_name, proj_base_path);
+    let result = new_contract_project(
+        name,
+        template_name,
+        None,
+        proj_base_path,
+        false,
+        false,
+    );
 
     // Assert
     assert!(result.is_err());
@@ -42,7 +56,14 @@ fn test_new_contract_project_existing_project_dir() {
     let proj_base_path = Some(PathBuf::from(".")); // this directory already contains a Cargo.toml file
 
     // Act
-    let result = new_contract_project(name, template_name, proj_base_path);
+    let result = new_contract_project(
+        name,
+        template_name,
+        None,
+        proj_base_path,
+        false,
+        false,
+    );
 
     // Assert
     assert!(result.is_err());
@@ -51,3 +72,876 @@ fn test_new_contract_project_existing_project_dir() {
         "A Cargo package already exists in ."
     );
 }
+
+#[test]
+fn test_template_from_url_accepts_git_urls() {
+    for url in [
+        "https://github.com/me/l1x-templ-ft.git",
+        "ssh://git@github.com/me/l1x-templ-ft.git",
+        "git@github.com:me/l1x-templ-ft.git",
+    ] {
+        let template = Template::from_url(url).unwrap();
+
+        assert_eq!(template.url(), url);
+    }
+}
+
+#[test]
+fn test_template_from_url_rejects_invalid_urls() {
+    for url in ["l1x-ft", "ftp://example.com/t.git", "https://", "git@host:"] {
+        let result = Template::from_url(url);
+
+        assert!(result.is_err(), "{} should be rejected", url);
+    }
+}
+
+#[derive(clap::Parser)]
+struct TestCli {
+    #[command(flatten)]
+    new: NewCommand,
+}
+
+#[test]
+fn test_new_command_template_and_template_url_conflict() {
+    // Act
+    let result = <TestCli as clap::Parser>::try_parse_from([
+        "new",
+        "--name",
+        "my_contract",
+        "--template",
+        "l1x-ft",
+        "--template-url",
+        "https://github.com/me/l1x-templ-ft.git",
+    ]);
+
+    // Assert
+    assert_eq!(
+        result.err().unwrap().kind(),
+        clap::error::ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn test_copy_local_template_skips_git_and_target() {
+    // Arrange
+    let template_dir = tempfile::tempdir().unwrap();
+    let out_dir = tempfile::tempdir().unwrap();
+    let template = template_dir.path();
+    std::fs::create_dir_all(template.join("src/nested")).unwrap();
+    std::fs::create_dir_all(template.join(".git")).unwrap();
+    std::fs::write(template.join(".git/template-marker"), "").unwrap();
+    std::fs::create_dir_all(template.join("target/release")).unwrap();
+    std::fs::write(template.join("Cargo.toml"), "[package]").unwrap();
+    std::fs::write(template.join("src/nested/mod.rs"), "// nested").unwrap();
+    std::fs::write(template.join("target/release/big.o"), "ebpf").unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("Cargo.toml", template.join("manifest.toml"))
+        .unwrap();
+    let project_dir = out_dir.path().join("my_contract");
+
+    // Act
+    let result = L1XContractTemplateHub::default()
+        .copy_local_template(template, project_dir.clone());
+
+    // Assert
+    assert!(result.is_ok());
+    assert_eq!(
+        std::fs::read_to_string(project_dir.join("src/nested/mod.rs")).unwrap(),
+        "// nested"
+    );
+    assert!(!project_dir.join("target").exists());
+    assert!(!project_dir.join(".git/template-marker").exists());
+    assert!(project_dir.join(".git/HEAD").exists());
+    #[cfg(unix)]
+    assert_eq!(
+        std::fs::read_link(project_dir.join("manifest.toml")).unwrap(),
+        PathBuf::from("Cargo.toml")
+    );
+}
+
+#[test]
+fn test_copy_local_template_rejects_missing_dir() {
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let result = L1XContractTemplateHub::default().copy_local_template(
+        &out_dir.path().join("missing"),
+        out_dir.path().join("my_contract"),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_substitute_project_name_renames_template() {
+    // Arrange
+    let project_dir = tempfile::tempdir().unwrap();
+    let project = project_dir.path();
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"l1x-ft\" # the template\nversion = \"0.1.0\"\n\n[lib]\nname = \"l1x_ft\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        project.join("src/lib.rs"),
+        "//! {{project_name}} contract\nuse l1x_ft::state;\nuse l1x_ft_sdk::x;\n",
+    )
+    .unwrap();
+    std::fs::write(project.join("README.md"), "# l1x-ft\n").unwrap();
+    std::fs::write(project.join("NOTES.md"), "nothing to rename\n").unwrap();
+    std::fs::write(project.join("logo.png"), b"l1x-ft\0\x89PNG").unwrap();
+
+    // Act
+    let modified_files = substitute_project_name(project, "my_token").unwrap();
+
+    // Assert
+    assert_eq!(
+        modified_files,
+        [
+            PathBuf::from("Cargo.toml"),
+            PathBuf::from("README.md"),
+            PathBuf::from("src/lib.rs")
+        ]
+    );
+    assert_eq!(
+        std::fs::read_to_string(project.join("Cargo.toml")).unwrap(),
+        "[package]\nname = \"my_token\" # the template\nversion = \"0.1.0\"\n\n[lib]\nname = \"my_token\"\n",
+    );
+    assert_eq!(
+        std::fs::read_to_string(project.join("src/lib.rs")).unwrap(),
+        "//! my_token contract\nuse my_token::state;\nuse l1x_ft_sdk::x;\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(project.join("README.md")).unwrap(),
+        "# my_token\n"
+    );
+    assert_eq!(
+        std::fs::read(project.join("logo.png")).unwrap(),
+        b"l1x-ft\0\x89PNG"
+    );
+}
+
+#[test]
+fn test_substitute_project_name_without_manifest() {
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(project_dir.path().join("main.c"), "int l1x_ft;\n").unwrap();
+
+    let modified_files =
+        substitute_project_name(project_dir.path(), "my_token").unwrap();
+
+    assert!(modified_files.is_empty());
+}
+
+/// Git repository with a `v1` tag on its first commit, returning the hash of
+/// that commit
+fn template_repo_with_tag(repo_dir: &std::path::Path) -> String {
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(["-c", "user.name=forge", "-c", "user.email=forge@l1x"])
+            .args(args)
+            .current_dir(repo_dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    git(&["init", "-q"]);
+    std::fs::write(repo_dir.join("VERSION"), "v1").unwrap();
+    git(&["add", "VERSION"]);
+    git(&["commit", "-q", "-m", "v1"]);
+    git(&["tag", "v1"]);
+    let commit = git(&["rev-parse", "HEAD"]).trim().to_string();
+    std::fs::write(repo_dir.join("VERSION"), "v2").unwrap();
+    git(&["commit", "-q", "-am", "v2"]);
+
+    commit
+}
+
+#[test]
+fn test_copy_template_checks_out_tag_and_commit() {
+    // Arrange
+    let repo_dir = tempfile::tempdir().unwrap();
+    let out_dir = tempfile::tempdir().unwrap();
+    let commit = template_repo_with_tag(repo_dir.path());
+    let url = repo_dir.path().to_str().unwrap();
+    let hub = L1XContractTemplateHub::default();
+
+    for reference in [None, Some("v1".to_string()), Some(commit)] {
+        let project_dir = out_dir.path().join(format!("{:?}", reference));
+
+        // Act
+        let expected = if reference.is_some() { "v1" } else { "v2" };
+        hub.copy_template(
+            Template::new(url).with_reference(reference),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(
+            std::fs::read_to_string(project_dir.join("VERSION")).unwrap(),
+            expected
+        );
+        assert!(project_dir.join(".git/HEAD").exists());
+    }
+}
+
+#[test]
+fn test_create_contract_project_missing_ref_cleans_up() {
+    // Arrange
+    let repo_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    template_repo_with_tag(repo_dir.path());
+    let template = Template::new(repo_dir.path().to_str().unwrap())
+        .with_reference(Some("v9".to_string()));
+
+    // Act
+    let result = create_contract_project(
+        "my_contract",
+        TemplateSource::Git(template),
+        Some(base_dir.path()),
+        false,
+    );
+
+    // Assert
+    let message = result.err().unwrap().to_string();
+    assert!(message.contains("Template ref 'v9' not found"));
+    assert!(message.contains(repo_dir.path().to_str().unwrap()));
+    assert!(!base_dir.path().join("my_contract").exists());
+}
+
+#[test]
+fn test_clone_error_from_git2_error() {
+    let cases = [
+        (git2::ErrorCode::GenericError, git2::ErrorClass::Os, "Network"),
+        (git2::ErrorCode::GenericError, git2::ErrorClass::Net, "Network"),
+        (git2::ErrorCode::Auth, git2::ErrorClass::Http, "Authentication"),
+        (git2::ErrorCode::Certificate, git2::ErrorClass::Ssl, "Authentication"),
+        (git2::ErrorCode::NotFound, git2::ErrorClass::Repository, "InvalidUrl"),
+        (git2::ErrorCode::NotFound, git2::ErrorClass::Reference, "Other"),
+    ];
+
+    for (code, class, kind) in cases {
+        let err = CloneError::from(git2::Error::new(code, class, "failed"));
+
+        assert!(format!("{:?}", err).starts_with(kind), "{:?}", err);
+        assert_eq!(err.to_string(), "failed");
+    }
+}
+
+#[test]
+fn test_copy_template_unreachable_url_is_network_error() {
+    for url in [
+        "https://127.0.0.1:1/l1x-templ-ft.git",
+        "https://nowhere.invalid/l1x-templ-ft.git",
+    ] {
+        // Arrange
+        let out_dir = tempfile::tempdir().unwrap();
+        let template = Template::from_url(url).unwrap();
+
+        // Act
+        let result = L1XContractTemplateHub::default()
+            .copy_template(template, out_dir.path().join("my_contract"));
+
+        // Assert
+        let err = result.err().unwrap();
+        assert!(
+            matches!(
+                err.downcast_ref::<CloneError>(),
+                Some(CloneError::Network(_))
+            ),
+            "{:?}",
+            err
+        );
+        assert!(err.to_string().contains(url));
+    }
+}
+
+#[test]
+fn test_copy_template_missing_or_non_repository_path_is_invalid_url() {
+    // Arrange
+    let plain_dir = tempfile::tempdir().unwrap();
+    let out_dir = tempfile::tempdir().unwrap();
+    let missing = plain_dir.path().join("missing");
+
+    for template_dir in [missing.as_path(), plain_dir.path()] {
+        // Act
+        let result = L1XContractTemplateHub::default().copy_template(
+            Template::new(template_dir.to_str().unwrap()),
+            out_dir.path().join("my_contract"),
+        );
+
+        // Assert
+        let err = result.err().unwrap();
+        assert!(
+            matches!(
+                err.downcast_ref::<CloneError>(),
+                Some(CloneError::InvalidUrl(_))
+            ),
+            "{:?}",
+            err
+        );
+    }
+}
+
+#[test]
+fn test_template_from_url_invalid_url_error() {
+    let result = Template::from_url("l1x-ft");
+
+    assert!(matches!(result, Err(CloneError::InvalidUrl(_))));
+}
+
+/// Local template with a single manifest named after the template
+fn local_template(template_dir: &std::path::Path) {
+    std::fs::write(
+        template_dir.join("Cargo.toml"),
+        "[package]\nname = \"l1x-ft\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_create_contract_project_failed_copy_cleans_up() {
+    // Arrange
+    let base_dir = tempfile::tempdir().unwrap();
+    let missing_template = base_dir.path().join("missing");
+
+    // Act
+    let result = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(missing_template),
+        Some(base_dir.path()),
+        false,
+    );
+
+    // Assert
+    assert!(result.is_err());
+    assert!(!base_dir.path().join("my_contract").exists());
+}
+
+#[test]
+fn test_create_contract_project_failed_copy_keeps_existing_dir() {
+    // Arrange
+    let base_dir = tempfile::tempdir().unwrap();
+    let project_dir = base_dir.path().join("my_contract");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    // Act
+    let result = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(base_dir.path().join("missing")),
+        Some(base_dir.path()),
+        false,
+    );
+
+    // Assert
+    assert!(result.is_err());
+    assert!(project_dir.is_dir());
+}
+
+#[test]
+fn test_create_contract_project_reuses_empty_dir() {
+    // Arrange
+    let template_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    local_template(template_dir.path());
+    std::fs::create_dir(base_dir.path().join("my_contract")).unwrap();
+
+    // Act
+    let result = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(template_dir.path().to_path_buf()),
+        Some(base_dir.path()),
+        false,
+    );
+
+    // Assert
+    assert!(result.is_ok());
+    assert!(base_dir.path().join("my_contract/Cargo.toml").exists());
+}
+
+#[test]
+fn test_create_contract_project_refuses_non_empty_dir() {
+    // Arrange
+    let template_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    local_template(template_dir.path());
+    let project_dir = base_dir.path().join("my_contract");
+    std::fs::create_dir(&project_dir).unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "keep me").unwrap();
+
+    // Act
+    let result = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(template_dir.path().to_path_buf()),
+        Some(base_dir.path()),
+        false,
+    );
+
+    // Assert
+    assert!(result.err().unwrap().to_string().contains("--force"));
+    assert_eq!(
+        std::fs::read_to_string(project_dir.join("notes.txt")).unwrap(),
+        "keep me"
+    );
+}
+
+#[test]
+fn test_create_contract_project_force_overwrites_dir() {
+    // Arrange
+    let template_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    local_template(template_dir.path());
+    let project_dir = base_dir.path().join("my_contract");
+    std::fs::create_dir(&project_dir).unwrap();
+    std::fs::write(project_dir.join("Cargo.toml"), "[package]").unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "stale").unwrap();
+
+    // Act
+    let result = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(template_dir.path().to_path_buf()),
+        Some(base_dir.path()),
+        true,
+    );
+
+    // Assert
+    assert!(result.is_ok());
+    assert!(!project_dir.join("notes.txt").exists());
+    assert!(std::fs::read_to_string(project_dir.join("Cargo.toml"))
+        .unwrap()
+        .contains("name = \"my_contract\""));
+}
+
+const TEMPLATE_METADATA_FIXTURE: &str = r#"
+    name = "l1x-ft"
+    description = "Fungible token contract"
+    min_forge_version = "0.1.0"
+    required_env = ["PATH"]
+    default_contract_id = "token"
+    post_commands = ["touch post-command-ran"]
+
+    [[rename]]
+    from = "src/l1x_ft.rs"
+    to = "src/{{project_name}}.rs"
+"#;
+
+#[test]
+fn test_template_manifest_parse_fixture() {
+    // Act
+    let manifest = TemplateManifest::parse(TEMPLATE_METADATA_FIXTURE).unwrap();
+
+    // Assert
+    assert_eq!(manifest.name.as_deref(), Some("l1x-ft"));
+    assert_eq!(manifest.default_contract_id.as_deref(), Some("token"));
+    assert_eq!(
+        manifest.rename,
+        vec![TemplateRenameRule {
+            from: PathBuf::from("src/l1x_ft.rs"),
+            to: "src/{{project_name}}.rs".to_string(),
+        }]
+    );
+    assert!(manifest.check_requirements().is_ok());
+}
+
+#[test]
+fn test_template_manifest_unsupported_min_forge_version() {
+    // Arrange
+    let manifest = TemplateManifest::parse(
+        "name = \"l1x-ft\"\nmin_forge_version = \"99.0.0\"",
+    )
+    .unwrap();
+
+    // Act
+    let result = manifest.check_requirements();
+
+    // Assert
+    assert_eq!(
+        result.err().unwrap().to_string(),
+        format!(
+            "Template 'l1x-ft' requires l1x-forge 99.0.0 or newer, this is l1x-forge {}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
+
+#[test]
+fn test_template_manifest_invalid_min_forge_version() {
+    let manifest =
+        TemplateManifest::parse("min_forge_version = \"latest\"").unwrap();
+
+    let result = manifest.check_requirements();
+
+    assert!(result
+        .err()
+        .unwrap()
+        .to_string()
+        .starts_with("Invalid min_forge_version 'latest'"));
+}
+
+#[test]
+fn test_template_manifest_rejects_rename_outside_project() {
+    let project_dir = tempfile::tempdir().unwrap();
+    let manifest = TemplateManifest::parse(
+        "[[rename]]\nfrom = \"src/lib.rs\"\nto = \"../lib.rs\"",
+    )
+    .unwrap();
+
+    let result = manifest.apply_rename_rules(project_dir.path(), "my_contract");
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_create_contract_project_applies_template_metadata() {
+    // Arrange
+    let template_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    local_template(template_dir.path());
+    std::fs::create_dir(template_dir.path().join("src")).unwrap();
+    std::fs::write(template_dir.path().join("src/l1x_ft.rs"), "").unwrap();
+    std::fs::write(
+        template_dir.path().join(TEMPLATE_METADATA),
+        TEMPLATE_METADATA_FIXTURE,
+    )
+    .unwrap();
+    let project_dir = base_dir.path().join("my_contract");
+
+    // Act
+    let manifest = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(template_dir.path().to_path_buf()),
+        Some(base_dir.path()),
+        false,
+    )
+    .unwrap();
+
+    // Assert
+    assert_eq!(manifest.unwrap().default_contract_id.as_deref(), Some("token"));
+    assert!(project_dir.join("src/my_contract.rs").exists());
+    assert!(!project_dir.join("src/l1x_ft.rs").exists());
+    assert!(project_dir.join("post-command-ran").exists());
+}
+
+#[test]
+fn test_create_contract_project_without_template_metadata() {
+    let template_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    local_template(template_dir.path());
+
+    let manifest = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(template_dir.path().to_path_buf()),
+        Some(base_dir.path()),
+        false,
+    )
+    .unwrap();
+
+    assert!(manifest.is_none());
+}
+
+#[test]
+fn test_create_contract_project_unsupported_template_cleans_up() {
+    // Arrange
+    let template_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    local_template(template_dir.path());
+    std::fs::write(
+        template_dir.path().join(TEMPLATE_METADATA),
+        "min_forge_version = \"99.0.0\"",
+    )
+    .unwrap();
+
+    // Act
+    let result = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(template_dir.path().to_path_buf()),
+        Some(base_dir.path()),
+        false,
+    );
+
+    // Assert
+    assert!(result.err().unwrap().to_string().contains("99.0.0"));
+    assert!(!base_dir.path().join("my_contract").exists());
+}
+
+static TEMPLATE_CONFIG_ENV_LOCK: std::sync::Mutex<()> =
+    std::sync::Mutex::new(());
+
+/// Run `test` with `L1X_CFG_WS_HOME` pointing at a workspace holding the
+/// `l1x_templates.yaml` `templates`, `None` for a workspace without one
+fn with_template_config<T>(
+    templates: Option<&str>,
+    test: impl FnOnce() -> T,
+) -> T {
+    let _guard =
+        TEMPLATE_CONFIG_ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let ws_home = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(ws_home.path().join("l1x-conf")).unwrap();
+    if let Some(templates) = templates {
+        std::fs::write(
+            ws_home.path().join("l1x-conf/l1x_templates.yaml"),
+            templates,
+        )
+        .unwrap();
+    }
+    std::env::set_var("L1X_CFG_WS_HOME", ws_home.path());
+
+    let result = test();
+    std::env::remove_var("L1X_CFG_WS_HOME");
+    result
+}
+
+const TEMPLATE_CONFIG_FIXTURE: &str = r#"
+internal-amm:
+  url: https://git.example.com/contracts/amm.git
+  description: Internal AMM
+l1x-ft:
+  url: https://git.example.com/contracts/ft.git
+"#;
+
+#[test]
+fn test_template_hub_adds_user_templates() {
+    // Act
+    let hub = with_template_config(
+        Some(TEMPLATE_CONFIG_FIXTURE),
+        L1XContractTemplateHub::default,
+    );
+
+    // Assert
+    let amm = hub.list().into_iter().find(|t| t.name == "internal-amm");
+    assert_eq!(amm.unwrap().description, "Internal AMM");
+    assert!(hub.get_template("l1x-nft").is_ok());
+    assert_eq!(
+        hub.get_template("internal-amm").unwrap().url(),
+        "https://git.example.com/contracts/amm.git"
+    );
+}
+
+#[test]
+fn test_template_hub_user_templates_override_builtin() {
+    let hub = with_template_config(
+        Some(TEMPLATE_CONFIG_FIXTURE),
+        L1XContractTemplateHub::default,
+    );
+
+    assert_eq!(
+        hub.get_template("l1x-ft").unwrap().url(),
+        "https://git.example.com/contracts/ft.git"
+    );
+}
+
+#[test]
+fn test_template_hub_ignores_missing_or_malformed_config() {
+    for templates in [None, Some("internal-amm:\n  description: No url")] {
+        let hub =
+            with_template_config(templates, L1XContractTemplateHub::default);
+
+        assert_eq!(hub.list().len(), 5);
+        assert!(hub.get_template("internal-amm").is_err());
+    }
+}
+
+fn commit_version(repo_dir: &std::path::Path, version: &str) {
+    std::fs::write(repo_dir.join("VERSION"), version).unwrap();
+    let status = std::process::Command::new("git")
+        .args(["-c", "user.name=forge", "-c", "user.email=forge@l1x"])
+        .args(["commit", "-q", "-am", version])
+        .current_dir(repo_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_template_cache_reuses_checkout_offline() {
+    // Arrange
+    let repo_dir = tempfile::tempdir().unwrap();
+    let cache_dir = tempfile::tempdir().unwrap();
+    template_repo_with_tag(repo_dir.path());
+    let template = Template::new(repo_dir.path().to_str().unwrap());
+    let cache = TemplateCache::new(cache_dir.path().to_path_buf());
+    let hub = L1XContractTemplateHub::default();
+    let cloned = cache.checkout(&hub, "l1x-ft", &template, false).unwrap();
+
+    // Act
+    repo_dir.close().unwrap();
+    let cached = cache.checkout(&hub, "l1x-ft", &template, false).unwrap();
+
+    // Assert
+    assert_eq!(cloned, cached);
+    assert_eq!(std::fs::read_to_string(cached.join("VERSION")).unwrap(), "v2");
+    assert!(!cached.join(".git").exists());
+}
+
+#[test]
+fn test_template_cache_refresh_clones_again() {
+    // Arrange
+    let repo_dir = tempfile::tempdir().unwrap();
+    let cache_dir = tempfile::tempdir().unwrap();
+    template_repo_with_tag(repo_dir.path());
+    let template = Template::new(repo_dir.path().to_str().unwrap());
+    let cache = TemplateCache::new(cache_dir.path().to_path_buf());
+    let hub = L1XContractTemplateHub::default();
+    cache.checkout(&hub, "l1x-ft", &template, false).unwrap();
+    commit_version(repo_dir.path(), "v3");
+
+    // Act
+    let kept = cache.checkout(&hub, "l1x-ft", &template, false).unwrap();
+    let kept_version = std::fs::read_to_string(kept.join("VERSION")).unwrap();
+    let refreshed = cache.checkout(&hub, "l1x-ft", &template, true).unwrap();
+
+    // Assert
+    assert_eq!(kept_version, "v2");
+    assert_eq!(
+        std::fs::read_to_string(refreshed.join("VERSION")).unwrap(),
+        "v3"
+    );
+}
+
+#[test]
+fn test_template_cache_replaces_corrupt_or_stale_entries() {
+    // Arrange
+    let repo_dir = tempfile::tempdir().unwrap();
+    let cache_dir = tempfile::tempdir().unwrap();
+    template_repo_with_tag(repo_dir.path());
+    let template = Template::new(repo_dir.path().to_str().unwrap());
+    let cache = TemplateCache::new(cache_dir.path().to_path_buf());
+    let hub = L1XContractTemplateHub::default();
+    let entry_dir = cache_dir.path().join("l1x-ft");
+
+    // Corrupt: the source of the entry is missing
+    std::fs::create_dir_all(entry_dir.join("template")).unwrap();
+    let checkout = cache.checkout(&hub, "l1x-ft", &template, false).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(checkout.join("VERSION")).unwrap(),
+        "v2"
+    );
+
+    // Stale: the entry was cloned from another url
+    std::fs::write(entry_dir.join("source"), "https://example.com/old.git\n\n")
+        .unwrap();
+    std::fs::remove_file(checkout.join("VERSION")).unwrap();
+    let checkout = cache.checkout(&hub, "l1x-ft", &template, false).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(checkout.join("VERSION")).unwrap(),
+        "v2"
+    );
+}
+
+#[test]
+fn test_template_cache_keys_entries_by_ref() {
+    let repo_dir = tempfile::tempdir().unwrap();
+    let cache_dir = tempfile::tempdir().unwrap();
+    template_repo_with_tag(repo_dir.path());
+    let url = repo_dir.path().to_str().unwrap();
+    let cache = TemplateCache::new(cache_dir.path().to_path_buf());
+    let hub = L1XContractTemplateHub::default();
+
+    let tagged = cache
+        .checkout(
+            &hub,
+            "l1x-ft",
+            &Template::new(url).with_reference(Some("v1".to_string())),
+            false,
+        )
+        .unwrap();
+    let latest =
+        cache.checkout(&hub, "l1x-ft", &Template::new(url), false).unwrap();
+
+    assert_eq!(std::fs::read_to_string(tagged.join("VERSION")).unwrap(), "v1");
+    assert_eq!(std::fs::read_to_string(latest.join("VERSION")).unwrap(), "v2");
+}
+
+#[test]
+fn test_template_cache_update_all_skips_uncached_templates() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let cache = TemplateCache::new(cache_dir.path().to_path_buf());
+
+    let updates = cache.update_all(&L1XContractTemplateHub::default());
+
+    assert!(updates.is_empty());
+}
+
+#[test]
+fn test_template_cache_from_env_override() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let repo_dir = tempfile::tempdir().unwrap();
+    template_repo_with_tag(repo_dir.path());
+    let template = Template::new(repo_dir.path().to_str().unwrap());
+
+    let checkout = with_template_config(None, || {
+        std::env::set_var(TEMPLATE_CACHE_ENV, cache_dir.path());
+        let checkout = TemplateCache::from_env().unwrap().checkout(
+            &L1XContractTemplateHub::default(),
+            "l1x-ft",
+            &template,
+            false,
+        );
+        std::env::remove_var(TEMPLATE_CACHE_ENV);
+        checkout
+    });
+
+    assert!(checkout.unwrap().starts_with(cache_dir.path()));
+}
+
+/// Local template of a library crate compiling to `lib_rs`
+fn crate_template(template_dir: &std::path::Path, lib_rs: &str) {
+    std::fs::write(
+        template_dir.join("Cargo.toml"),
+        "[package]\nname = \"l1x-ft\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+    )
+    .unwrap();
+    std::fs::create_dir(template_dir.join("src")).unwrap();
+    std::fs::write(template_dir.join("src/lib.rs"), lib_rs).unwrap();
+}
+
+#[test]
+fn test_check_project_valid_template() {
+    // Arrange
+    let template_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    crate_template(template_dir.path(), "pub fn supply() -> u64 { 1 }\n");
+    let manifest = create_contract_project(
+        "my_contract",
+        TemplateSource::Local(template_dir.path().to_path_buf()),
+        Some(base_dir.path()),
+        false,
+    )
+    .unwrap();
+
+    // Act
+    let result =
+        check_project(&base_dir.path().join("my_contract"), manifest.as_ref());
+
+    // Assert
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_check_project_broken_template_keeps_project() {
+    // Arrange
+    let template_dir = tempfile::tempdir().unwrap();
+    let base_dir = tempfile::tempdir().unwrap();
+    crate_template(template_dir.path(), "pub fn supply() -> u64 { \"1\" }\n");
+    let project_dir = base_dir.path().join("my_contract");
+    create_contract_project(
+        "my_contract",
+        TemplateSource::Local(template_dir.path().to_path_buf()),
+        Some(base_dir.path()),
+        false,
+    )
+    .unwrap();
+
+    // Act
+    let result = check_project(&project_dir, None);
+
+    // Assert
+    assert!(result
+        .err()
+        .unwrap()
+        .to_string()
+        .starts_with("`cargo check` failed"));
+    assert!(project_dir.join("src/lib.rs").exists());
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+
+use super::new::{L1XContractTemplateHub, TemplateCache, TemplateInfo};
+
+/// List the project templates `new` can create a project from
+#[derive(Debug, clap::Args)]
+#[clap(name = "templates")]
+pub struct TemplatesCommand {
+    /// Print the templates as a JSON array
+    #[clap(long = "json", default_value_t = false)]
+    json: bool,
+    /// Clone again every cached template
+    #[clap(long = "update", default_value_t = false)]
+    update: bool,
+}
+
+/// One `name :: url :: description` line per template
+pub fn render_templates(templates: &[TemplateInfo]) -> String {
+    templates
+        .iter()
+        .map(|template| {
+            format!(
+                "{} :: {} :: {}",
+                template.name, template.url, template.description
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl TemplatesCommand {
+    pub async fn exec(&self) -> Result<()> {
+        if self.update {
+            return update_cached_templates(&TemplateCache::from_env()?);
+        }
+
+        let templates = L1XContractTemplateHub::default().list();
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&templates)?);
+        } else {
+            println!("{}", render_templates(&templates));
+        }
+
+        Ok(())
+    }
+}
+
+/// Refresh the cached templates, failing if any of them couldn't be cloned
+fn update_cached_templates(cache: &TemplateCache) -> Result<()> {
+    let updates = cache.update_all(&L1XContractTemplateHub::default());
+    if updates.is_empty() {
+        println!("No cached templates");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for (name, updated) in updates {
+        match updated {
+            Ok(_) => println!("Updated {}", name),
+            Err(err) => {
+                println!("Failed to update {} :: {}", name, err);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Failed to update {} cached templates", failed);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::cmd::new::{L1XContractTemplateHub, TemplateInfo};
+use crate::cmd::templates::*;
+
+#[test]
+fn test_template_hub_list_sorted_by_name() {
+    // Act
+    let templates = L1XContractTemplateHub::default().list();
+
+    // Assert
+    let names: Vec<&str> =
+        templates.iter().map(|template| template.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "l1x-balancer-v2",
+            "l1x-cross-chain-swap",
+            "l1x-erc20-cc-call",
+            "l1x-ft",
+            "l1x-nft"
+        ]
+    );
+    assert!(templates.iter().all(|template| template.url.ends_with(".git")
+        && !template.description.is_empty()));
+}
+
+#[test]
+fn test_template_hub_list_json() {
+    // Arrange
+    let templates = L1XContractTemplateHub::default().list();
+
+    // Act
+    let json = serde_json::to_value(templates).unwrap();
+
+    // Assert
+    let ft = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|template| template["name"] == "l1x-ft")
+        .unwrap();
+    assert_eq!(
+        ft["url"],
+        "https://github.com/L1X-Foundation-Consensus/l1x-templ-ft.git"
+    );
+    assert_eq!(ft["description"], "Fungible token contract");
+}
+
+#[test]
+fn test_render_templates_one_line_per_template() {
+    let templates = vec![TemplateInfo {
+        name: "l1x-ft".to_string(),
+        url: "https://example.com/ft.git".to_string(),
+        description: "Fungible token contract".to_string(),
+    }];
+
+    let rendered = render_templates(&templates);
+
+    assert_eq!(
+        rendered,
+        "l1x-ft :: https://example.com/ft.git :: Fungible token contract"
+    );
+}
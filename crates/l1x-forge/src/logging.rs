@@ -0,0 +1,25 @@
+use std::{fs::File, path::Path, sync::Mutex};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
+
+/// Human readable traces on stderr, filtered by `RUST_LOG`. With `log_file`,
+/// every trace is also written to it as JSON lines.
+pub(crate) fn init(log_file: Option<&Path>) -> anyhow::Result<()> {
+    let file_layer = log_file
+        .map(|path| -> anyhow::Result<_> {
+            let file = File::create(path)?;
+            Ok(fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .with_writer(Mutex::new(file)))
+        })
+        .transpose()?;
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::from_default_env())
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(file_layer)
+        .try_init()?;
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 #![deny(unused_crate_dependencies)]
 
 mod cmd;
+mod logging;
 
 use clap::Parser;
-use std::{fmt::Debug, str::FromStr};
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub(crate) struct HexData(pub Vec<u8>);
@@ -18,6 +19,16 @@ impl FromStr for HexData {
 
 #[derive(Debug, clap::Parser)]
 #[clap(bin_name = "l1x-forge")]
+pub(crate) struct Cli {
+    /// Also write JSON formatted traces to this file
+    #[clap(long = "log-file", global = true)]
+    log_file: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    opts: Opts,
+}
+
+#[derive(Debug, clap::Subcommand)]
 pub(crate) enum Opts {
     /// Utilities to develop Wasm smart contracts.
     #[command(
@@ -25,6 +36,36 @@ pub(crate) enum Opts {
         about = "Create new project workspace from template."
     )]
     New(cmd::NewCommand),
+    /// List the project templates of `new`
+    #[command(
+        name = "templates",
+        about = "List the available project templates."
+    )]
+    Templates(cmd::TemplatesCommand),
+    /// Utility to set up a new workspace
+    #[command(
+        name = "init-workspace",
+        about = "Create the l1x-conf files and artifact directories."
+    )]
+    InitWorkspace(l1x_cli::L1XInitWorkspaceCmd),
+    /// Utility to validate the workspace environment
+    #[command(
+        name = "doctor",
+        about = "Check the workspace configuration and the active chain."
+    )]
+    Doctor(l1x_cli::L1XDoctorCmd),
+    /// Utility to edit the workspace configuration
+    #[command(
+        name = "config",
+        about = "Manage the networks and show the configuration files."
+    )]
+    Config(l1x_cli::L1XConfigCmd),
+    /// Utility to generate shell completions
+    #[command(
+        name = "completions",
+        about = "Print the completion script [ bash | zsh | fish | powershell ]"
+    )]
+    Completions(cmd::CompletionsCommand),
     /// Utility to install contract to L1X VM
     #[command(
         name = "vm-install-contract",
@@ -37,23 +78,208 @@ pub(crate) enum Opts {
         about = "submit the transactions to L1X VM [ ebpf | evm ]"
     )]
     L1XVmSubTxn(l1x_cli::L1XVmSubTxnCmd),
+    /// Utility to load test a contract function
+    #[command(
+        name = "vm-stress",
+        about = "submit many function calls at a fixed rate [ ebpf | evm ]"
+    )]
+    L1xVmStress(l1x_cli::L1XVmStressCmd),
+    /// Utility to uninstall contract from L1X VM
+    #[command(
+        name = "vm-uninstall-contract",
+        about = "Remove the contracts from the L1X VM registry [ ebpf | evm ]"
+    )]
+    L1xVmUninstallContract(l1x_cli::L1XVmUninstallContractCmd),
+    /// Utility to create a transaction envelope for co-signing
+    #[command(
+        name = "vm-sign-txn",
+        about = "Create a signed or --partial transaction envelope"
+    )]
+    L1xVmSignTxn(l1x_cli::L1XVmSignTxnCmd),
+    /// Utility to co-sign a transaction envelope
+    #[command(
+        name = "vm-cosign",
+        about = "Add the owner's signature to a transaction envelope"
+    )]
+    L1xVmCosign(l1x_cli::L1XVmCosignCmd),
+    /// Utility to submit a co-signed transaction envelope
+    #[command(
+        name = "vm-send-raw",
+        about = "Submit a transaction envelope once its threshold is met"
+    )]
+    L1xVmSendRaw(l1x_cli::L1XVmSendRawCmd),
+    /// Utility to sign transactions offline
+    #[command(
+        name = "tx",
+        about = "Build, sign offline, verify and broadcast transactions [ build | sign | verify | broadcast ]"
+    )]
+    Tx(l1x_cli::L1XTxCmd),
+    /// Utility to build transaction payload files
+    #[command(name = "payload", about = "Build transaction payload files")]
+    Payload(l1x_cli::L1XPayloadCmd),
+    /// Utility to derive account addresses
+    #[command(
+        name = "address-of",
+        about = "Print the account address of a wallet, public or private key"
+    )]
+    AddressOf(l1x_cli::L1XAddressOfCmd),
+    /// Utility to query account balances
+    #[command(
+        name = "balance",
+        about = "Print the balance and nonce of an address or wallet"
+    )]
+    Balance(l1x_cli::L1XBalanceCmd),
+    /// Utility to fund dev accounts
+    #[command(
+        name = "faucet",
+        about = "Fund dev accounts from the faucet wallet of a local devnet"
+    )]
+    Faucet(l1x_cli::L1XFaucetCmd),
+    /// Utility to query account nonces
+    #[command(
+        name = "nonce",
+        about = "Print the nonce the chain reports for a wallet or address"
+    )]
+    Nonce(l1x_cli::L1XNonceCmd),
+    /// Utility to transfer native tokens
+    #[command(
+        name = "transfer",
+        about = "Transfer native tokens from a dev wallet to an address"
+    )]
+    Transfer(l1x_cli::L1XTransferCmd),
+    /// Utility to stake in staking pools
+    #[command(name = "stake", about = "Stake native tokens in a staking pool")]
+    Stake(l1x_cli::L1XStakeCmd),
+    /// Utility to withdraw stakes
+    #[command(
+        name = "unstake",
+        about = "Withdraw staked native tokens from a staking pool"
+    )]
+    Unstake(l1x_cli::L1XUnstakeCmd),
+    /// Utility to create staking pools
+    #[command(
+        name = "create-pool",
+        about = "Create a staking pool and print its predicted address"
+    )]
+    CreatePool(l1x_cli::L1XCreatePoolCmd),
+    /// Utility to sign off-chain messages
+    #[command(
+        name = "sign-message",
+        about = "Sign a message with a dev wallet key"
+    )]
+    SignMessage(l1x_cli::L1XSignMessageCmd),
+    /// Utility to verify off-chain message signatures
+    #[command(
+        name = "verify-message",
+        about = "Check a message signature, exits 1 when it does not match"
+    )]
+    VerifyMessage(l1x_cli::L1XVerifyMessageCmd),
+    /// Utility to generate node identities
+    #[command(
+        name = "keygen",
+        about = "Generate node keys [ secp256k1 | ed25519 ]"
+    )]
+    Keygen(l1x_cli::L1XKeygenCmd),
+    /// Utility to derive libp2p peer ids
+    #[command(
+        name = "peer-id",
+        about = "Print the libp2p PeerId of a validator key"
+    )]
+    PeerId(l1x_cli::L1XPeerIdCmd),
+    /// Utility to derive and predict addresses
+    #[command(
+        name = "address",
+        about = "Derive account addresses and predict contract addresses"
+    )]
+    Address(l1x_cli::L1XAddressCmd),
+    /// Utility to manage the dev accounts
+    #[command(
+        name = "account",
+        about = "Create, import, list and export the workspace dev accounts"
+    )]
+    Account(l1x_cli::L1XAccountCmd),
+    /// Utility to inspect the build artifacts
+    #[command(
+        name = "artifacts",
+        about = "List the build artifacts [ ebpf | evm ]"
+    )]
+    Artifacts(l1x_cli::L1XArtifactsCmd),
+    /// Utility to inspect and edit the contract address registry
+    #[command(
+        name = "registry",
+        about = "Show, remove and register contracts of the contract address registry"
+    )]
+    Registry(l1x_cli::L1XRegistryCmd),
+    /// Utility to verify deployed contract code
+    #[command(
+        name = "verify",
+        about = "Compare the code of a registered contract with its artifact, exits 1 when it does not match"
+    )]
+    Verify(l1x_cli::L1XVerifyCmd),
 }
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt::init();
+    let cli = Cli::parse();
 
-    let exec_status = match Opts::parse() {
-        Opts::New(new_cmd) => new_cmd.exec(),
+    if let Err(err) = logging::init(cli.log_file.as_deref()) {
+        eprintln!("{err:?}");
+        std::process::exit(1);
+    }
+
+    let exec_status = match cli.opts {
+        Opts::New(new_cmd) => new_cmd.exec().await,
+        Opts::Templates(templates_cmd) => templates_cmd.exec().await,
+        Opts::InitWorkspace(init_cmd) => init_cmd.exec().await,
+        Opts::Doctor(doctor_cmd) => doctor_cmd.exec().await,
+        Opts::Config(config_cmd) => config_cmd.exec().await,
+        Opts::Completions(completions_cmd) => completions_cmd.exec().await,
         Opts::L1xVmInstallContract(install_cmd) => install_cmd.exec().await,
         Opts::L1XVmSubTxn(sub_txn_cmd) => sub_txn_cmd.exec().await,
+        Opts::L1xVmStress(stress_cmd) => stress_cmd.exec().await,
+        Opts::L1xVmUninstallContract(uninstall_cmd) => {
+            uninstall_cmd.exec().await
+        }
+        Opts::L1xVmSignTxn(sign_cmd) => sign_cmd.exec().await,
+        Opts::L1xVmCosign(cosign_cmd) => cosign_cmd.exec().await,
+        Opts::L1xVmSendRaw(send_raw_cmd) => send_raw_cmd.exec().await,
+        Opts::Tx(tx_cmd) => tx_cmd.exec().await,
+        Opts::Payload(payload_cmd) => payload_cmd.exec().await,
+        Opts::AddressOf(address_of_cmd) => address_of_cmd.exec().await,
+        Opts::Artifacts(artifacts_cmd) => artifacts_cmd.exec().await,
+        Opts::Balance(balance_cmd) => balance_cmd.exec().await,
+        Opts::Faucet(faucet_cmd) => faucet_cmd.exec().await,
+        Opts::Nonce(nonce_cmd) => nonce_cmd.exec().await,
+        Opts::Transfer(transfer_cmd) => transfer_cmd.exec().await,
+        Opts::Stake(stake_cmd) => stake_cmd.exec().await,
+        Opts::Unstake(unstake_cmd) => unstake_cmd.exec().await,
+        Opts::CreatePool(create_pool_cmd) => create_pool_cmd.exec().await,
+        Opts::SignMessage(sign_cmd) => sign_cmd.exec().await,
+        Opts::VerifyMessage(verify_cmd) => verify_cmd.exec().await,
+        Opts::Keygen(keygen_cmd) => keygen_cmd.exec().await,
+        Opts::PeerId(peer_id_cmd) => peer_id_cmd.exec().await,
+        Opts::Address(address_cmd) => address_cmd.exec().await,
+        Opts::Registry(registry_cmd) => registry_cmd.exec().await,
+        Opts::Verify(verify_cmd) => verify_cmd.exec().await,
+        Opts::Account(account_cmd) => account_cmd.exec().await,
     };
 
     match exec_status {
         Ok(()) => {}
         Err(err) => {
+            // Configuration mistakes are for the user to fix, not a crash
+            if let Some(config_err) = err.chain().find_map(|cause| {
+                cause.downcast_ref::<l1x_cli::L1XConfigError>()
+            }) {
+                eprintln!("error: {config_err}");
+                std::process::exit(2);
+            }
+
             eprintln!("{err:?}");
-            std::process::exit(1);
+            let install_err = err.chain().find_map(|cause| {
+                cause.downcast_ref::<l1x_cli::L1XVmContractInstallError>()
+            });
+            std::process::exit(install_err.map_or(1, |err| err.exit_code()));
         }
     }
 }